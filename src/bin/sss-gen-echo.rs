@@ -0,0 +1,58 @@
+//! A minimal `sssc` plugin, used to test the plugin protocol and as a starting point for writing
+//! plugins in Rust. It writes the names of the messages of the protocol to `<protocol>.txt`, the
+//! `fail` and `warn` parameters make it report an error or a warning with the given message.
+
+use sss::codegen::plugin::{
+    Diagnostic, PluginFile, PluginRequest, PluginResponse, Severity, PLUGIN_PROTOCOL_VERSION,
+};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let request: PluginRequest = match serde_json::from_reader(std::io::stdin().lock()) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("sss-gen-echo: invalid request: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut diagnostics = vec![];
+    if request.version != PLUGIN_PROTOCOL_VERSION {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            message: format!("unsupported plugin protocol version {}", request.version),
+        });
+    }
+    if let Some(message) = request.parameters.get("fail") {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            message: message.clone(),
+        });
+    }
+    if let Some(message) = request.parameters.get("warn") {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            message: message.clone(),
+        });
+    }
+
+    let protocol = &request.protocol;
+    let mut contents = format!("protocol {}\n", protocol.qualified_name());
+    for m in &protocol.messages {
+        contents += &format!("{} {} {}\n", m.id, m.name, m.layout.size);
+    }
+
+    let response = PluginResponse {
+        version: PLUGIN_PROTOCOL_VERSION,
+        files: vec![PluginFile {
+            path: format!("{}.txt", protocol.qualified_name()),
+            contents,
+        }],
+        diagnostics,
+    };
+    println!(
+        "{}",
+        serde_json::to_string(&response).expect("responses are always serializable")
+    );
+    ExitCode::SUCCESS
+}
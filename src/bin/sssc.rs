@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let status = sss::sssc::run(&args, &mut std::io::stdout(), &mut std::io::stderr());
+    ExitCode::from(status as u8)
+}
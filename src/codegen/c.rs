@@ -0,0 +1,914 @@
+//! Generates a C99 header of `static inline` accessor functions, every access goes through
+//! `memcpy` and an explicit byte order conversion so misaligned buffers are never undefined
+//! behaviour. Layouts which a C compiler would lay out identically also get a plain struct mirror.
+
+use crate::codegen::{
+    file_header, screaming_snake_case, snake_case, type_order, CodeWriter, Generator,
+    GeneratorError, GeneratorOptions, Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Mantissa};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, UnionType};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol};
+
+const KEYWORDS: Keywords = Keywords(&[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while",
+]);
+
+/// The name of the accessors generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Shared by every generated header, guarded so several headers can be included together
+const RUNTIME: &str = r#"#ifndef SSS_C_RUNTIME_V1
+#define SSS_C_RUNTIME_V1
+
+#if defined(__BYTE_ORDER__) && defined(__ORDER_BIG_ENDIAN__) && __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+#define SSS_HOST_BIG_ENDIAN 1
+#else
+#define SSS_HOST_BIG_ENDIAN 0
+#endif
+
+static inline uint16_t sss_bswap16(uint16_t v) {
+    return (uint16_t)((v >> 8) | (v << 8));
+}
+
+static inline uint32_t sss_bswap32(uint32_t v) {
+    return ((v >> 24) & 0xffu) | ((v >> 8) & 0xff00u) | ((v << 8) & 0xff0000u) | (v << 24);
+}
+
+static inline uint64_t sss_bswap64(uint64_t v) {
+    return ((uint64_t)sss_bswap32((uint32_t)v) << 32) | sss_bswap32((uint32_t)(v >> 32));
+}
+
+static inline uint8_t sss_load8(const uint8_t *p, int big_endian) {
+    (void)big_endian;
+    return *p;
+}
+
+static inline uint16_t sss_load16(const uint8_t *p, int big_endian) {
+    uint16_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+}
+
+static inline uint32_t sss_load32(const uint8_t *p, int big_endian) {
+    uint32_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+}
+
+static inline uint64_t sss_load64(const uint8_t *p, int big_endian) {
+    uint64_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+}
+
+static inline void sss_store8(uint8_t *p, uint8_t v, int big_endian) {
+    (void)big_endian;
+    *p = v;
+}
+
+static inline void sss_store16(uint8_t *p, uint16_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store32(uint8_t *p, uint32_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store64(uint8_t *p, uint64_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline float sss_load_f32(const uint8_t *p, int big_endian) {
+    uint32_t bits = sss_load32(p, big_endian);
+    float v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline double sss_load_f64(const uint8_t *p, int big_endian) {
+    uint64_t bits = sss_load64(p, big_endian);
+    double v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline void sss_store_f32(uint8_t *p, float v, int big_endian) {
+    uint32_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store32(p, bits, big_endian);
+}
+
+static inline void sss_store_f64(uint8_t *p, double v, int big_endian) {
+    uint64_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store64(p, bits, big_endian);
+}
+
+/* The length of the text in an ascii field, which ends at the first nul byte and leaves out the
+   pad bytes ending it */
+static inline size_t sss_ascii_length(const uint8_t *p, size_t capacity, uint8_t pad) {
+    size_t length = 0;
+    while (length < capacity && p[length] != 0) {
+        length++;
+    }
+    while (length > 0 && p[length - 1] == pad) {
+        length--;
+    }
+    return length;
+}
+
+/* Copies a nul terminated string into an ascii field and fills the rest with pad bytes,
+   returns -1 without writing anything if the string is too long or isn't ascii */
+static inline int sss_store_ascii(uint8_t *p, size_t capacity, uint8_t pad, const char *value) {
+    size_t length = strlen(value);
+    size_t i;
+    if (length > capacity) {
+        return -1;
+    }
+    for (i = 0; i < length; i++) {
+        if ((unsigned char)value[i] > 0x7f) {
+            return -1;
+        }
+    }
+    memcpy(p, value, length);
+    memset(p + length, pad, capacity - length);
+    return 0;
+}
+
+#endif"#;
+
+/// Generates a single header holding every type of a protocol
+pub struct CGenerator;
+
+impl Generator for CGenerator {
+    fn name(&self) -> &str {
+        "c"
+    }
+
+    fn description(&self) -> &str {
+        "C99 header of inline accessor functions"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&[])?;
+
+        let prefix = snake_case(&protocol.name.join("_"));
+        let header = CHeader {
+            protocol,
+            prefix: prefix.clone(),
+            big_endian: if protocol.byte_order == ByteOrder::Big {
+                1
+            } else {
+                0
+            },
+        };
+        Ok(vec![OutputFile::new(
+            format!("{}.h", prefix),
+            header.generate()?,
+        )])
+    }
+}
+
+struct CHeader<'p> {
+    protocol: &'p Protocol,
+    /// Every generated name starts with the protocol name, C has no namespaces
+    prefix: String,
+    big_endian: u8,
+}
+
+fn primitive(scalar: &ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "uint8_t",
+            2 => "uint16_t",
+            4 => "uint32_t",
+            _ => "uint64_t",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "int8_t",
+            2 => "int16_t",
+            4 => "int32_t",
+            _ => "int64_t",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "float",
+            _ => "double",
+        },
+        ScalarType::ByteSized(ByteSize::Byte) => "uint8_t",
+        ScalarType::ByteSized(_) => "char",
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => primitive(&ScalarType::Unsigned(u)),
+            Mantissa::Signed(s) => primitive(&ScalarType::Signed(s)),
+        },
+        ScalarType::Timestamp(t) => primitive(&t.storage()),
+        ScalarType::Duration(d) => primitive(&d.storage()),
+    }
+}
+
+/// Enums become a C `enum` when every value fits in an `int`, otherwise `#define`s
+fn fits_c_enum(e: &Enum) -> bool {
+    e.kind == EnumKind::Enum && e.values.iter().all(|v| v.value <= i32::MAX as u64)
+}
+
+impl CHeader<'_> {
+    fn type_name(&self, name: &str) -> String {
+        format!("{}_{}", self.prefix, snake_case(name))
+    }
+
+    fn macro_name(&self, name: &str) -> String {
+        screaming_snake_case(&self.type_name(name))
+    }
+
+    fn field_name(name: &str) -> String {
+        KEYWORDS.escape(&snake_case(name))
+    }
+
+    /// Unsigned values come out of the runtime functions in their own type, anything else is
+    /// converted explicitly
+    fn needs_cast(scalar: &ScalarType) -> bool {
+        !matches!(
+            scalar,
+            ScalarType::Unsigned(_) | ScalarType::ByteSized(ByteSize::Byte)
+        )
+    }
+
+    fn load(&self, scalar: &ScalarType, at: &str) -> String {
+        let bits = scalar.size_bytes() * 8;
+        match scalar {
+            ScalarType::Float(_) => format!("sss_load_f{}({}, {})", bits, at, self.big_endian),
+            s if Self::needs_cast(s) => format!(
+                "({})sss_load{}({}, {})",
+                primitive(s),
+                bits,
+                at,
+                self.big_endian
+            ),
+            _ => format!("sss_load{}({}, {})", bits, at, self.big_endian),
+        }
+    }
+
+    fn store(&self, scalar: &ScalarType, at: &str, value: &str) -> String {
+        let bits = scalar.size_bytes() * 8;
+        match scalar {
+            ScalarType::Float(_) => format!(
+                "sss_store_f{}({}, {}, {});",
+                bits, at, value, self.big_endian
+            ),
+            s if Self::needs_cast(s) => format!(
+                "sss_store{}({}, (uint{}_t){}, {});",
+                bits, at, bits, value, self.big_endian
+            ),
+            _ => format!("sss_store{}({}, {}, {});", bits, at, value, self.big_endian),
+        }
+    }
+
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let guard = format!("{}_H", screaming_snake_case(&self.prefix));
+        let mut w = CodeWriter::new("    ");
+        w.line(file_header("//", protocol).trim_end())
+            .line(format!("#ifndef {}", guard))
+            .line(format!("#define {}", guard))
+            .blank()
+            .line("#include <stddef.h>")
+            .line("#include <stdint.h>")
+            .line("#include <string.h>")
+            .blank()
+            .lines(RUNTIME)
+            .blank()
+            .line(format!(
+                "#define {}_BIG_ENDIAN {}",
+                screaming_snake_case(&self.prefix),
+                self.big_endian
+            ));
+
+        for e in &protocol.enums {
+            w.blank();
+            self.write_enum(&mut w, e)?;
+        }
+        for declaration in type_order(protocol) {
+            w.blank();
+            match declaration {
+                TypeDeclaration::Struct(s) => self.write_layout(
+                    &mut w,
+                    &s.name,
+                    &format!("struct {}", s.name),
+                    None,
+                    &s.layout,
+                )?,
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    let doc = format!(
+                        "union {}, the active variant is selected by a {}",
+                        u.name, u.discriminator_type
+                    );
+                    self.write_layout(&mut w, &u.name, &doc, None, &layout)?
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_layout(
+                &mut w,
+                FRAME_HEADER,
+                "the header in front of every message",
+                None,
+                &header.layout,
+            )?;
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a uint32_t",
+                    m.name
+                ))
+            })?;
+            w.blank();
+            self.write_layout(
+                &mut w,
+                &m.name,
+                &format!("message {} [{}]", m.name, m.id),
+                Some(id),
+                &m.layout,
+            )?;
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_dispatch(&mut w, header);
+        }
+
+        w.blank().line(format!("#endif /* {} */", guard));
+        Ok(w.finish())
+    }
+
+    /// A struct of callbacks, one per message, and the `dispatch` function calling them
+    fn write_dispatch(&self, w: &mut CodeWriter, header: &Header) {
+        let handler = format!("{}_handler", self.prefix);
+        let frame_header = self.type_name(FRAME_HEADER);
+        let header_size = format!("{}_SIZE", self.macro_name(FRAME_HEADER));
+
+        w.line(format!(
+            "/* callbacks for {}_dispatch, a NULL callback skips its message */",
+            self.prefix
+        ));
+        w.block(
+            format!("typedef struct {} {{", handler),
+            format!("}} {};", handler),
+            |w| {
+                for m in &self.protocol.messages {
+                    w.line(format!(
+                        "void (*on_{})(void *context, const uint8_t *message);",
+                        snake_case(&m.name)
+                    ));
+                }
+            },
+        );
+        w.blank()
+            .line("/* reads the header at the start of buf and calls the callback for its id with the message")
+            .line(" * after it, returns the size of the frame or 0 when the size bytes of buf don't hold the")
+            .line(" * whole frame, its length is too small for its message or no message has its id */");
+        w.block(
+            format!(
+                "static inline size_t {}_dispatch(const uint8_t *buf, size_t size, const {} *handler, void *context) {{",
+                self.prefix, handler
+            ),
+            "}",
+            |w| {
+                w.block(format!("if (size < {}) {{", header_size), "}", |w| {
+                    w.line("return 0;");
+                });
+                let length = header.length().map(|l| Self::field_name(&l.name));
+                if let Some(length) = &length {
+                    let value = format!("(size_t){}_get_{}(buf)", frame_header, length);
+                    w.line(match header.length_value(0) {
+                        0 => format!("size_t frame = {} + {};", header_size, value),
+                        _ => format!("size_t frame = {};", value),
+                    });
+                }
+                w.block(
+                    format!(
+                        "switch ({}_get_{}(buf)) {{",
+                        frame_header,
+                        Self::field_name(&header.id().name)
+                    ),
+                    "}",
+                    |w| {
+                        for m in &self.protocol.messages {
+                            let macro_name = self.macro_name(&m.name);
+                            let callback = format!("handler->on_{}", snake_case(&m.name));
+                            w.dedent()
+                                .line(format!("case {}_ID:", macro_name))
+                                .indent();
+                            let (check, returns) = match length {
+                                Some(_) => (
+                                    format!("frame < {}_FRAME_SIZE || size < frame", macro_name),
+                                    String::from("frame"),
+                                ),
+                                None => (
+                                    format!("size < {}_FRAME_SIZE", macro_name),
+                                    format!("{}_FRAME_SIZE", macro_name),
+                                ),
+                            };
+                            w.block(format!("if ({}) {{", check), "}", |w| {
+                                w.line("return 0;");
+                            })
+                            .block(format!("if ({}) {{", callback), "}", |w| {
+                                w.line(format!("{}(context, buf + {});", callback, header_size));
+                            })
+                            .line(format!("return {};", returns));
+                        }
+                        w.dedent().line("default:").indent().line("return 0;");
+                    },
+                );
+            },
+        );
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        let underlying = match e.underlying {
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_) => {
+                primitive(&e.underlying)
+            }
+            other => {
+                return Err(GeneratorError::Unsupported(format!(
+                    "enum '{}' is stored as a {}",
+                    e.name, other
+                )))
+            }
+        };
+        let ty = self.type_name(&e.name);
+        let value_name =
+            |v: &str| format!("{}_{}", self.macro_name(&e.name), screaming_snake_case(v));
+
+        w.line(format!(
+            "/* {} {}, stored as a {} */",
+            match e.kind {
+                EnumKind::Enum => "enum",
+                EnumKind::Bitflags => "bitflags",
+            },
+            e.name,
+            e.underlying
+        ));
+        if fits_c_enum(e) {
+            w.block(
+                format!("typedef enum {} {{", ty),
+                format!("}} {};", ty),
+                |w| {
+                    for v in &e.values {
+                        w.line(format!("{} = {},", value_name(&v.name), v.value));
+                    }
+                },
+            );
+        } else {
+            w.line(format!("typedef {} {};", underlying, ty));
+            for v in &e.values {
+                w.line(format!(
+                    "#define {} (({}){}u)",
+                    value_name(&v.name),
+                    ty,
+                    v.value
+                ));
+            }
+        }
+
+        if e.kind == EnumKind::Enum {
+            w.blank()
+                .line("/* The name of the value in the schema, NULL for an undeclared value */");
+            w.block(
+                format!(
+                    "static inline const char *{}_name({} value) {{",
+                    ty, underlying
+                ),
+                "}",
+                |w| {
+                    w.block("switch (value) {", "}", |w| {
+                        for v in &e.values {
+                            w.line(format!("case {}: return \"{}\";", v.value, v.name));
+                        }
+                        w.line("default: return NULL;");
+                    });
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// The C type of a field in a struct mirror and its alignment, `None` if it has no mirror
+    fn mirror_type(&self, field_type: &FieldType_) -> Option<(String, String, usize)> {
+        match field_type {
+            FieldType_::Scalar(s) => {
+                Some((primitive(s).to_string(), String::new(), s.size_bytes()))
+            }
+            FieldType_::Enum(et) => Some((
+                primitive(&et.underlying).to_string(),
+                String::new(),
+                et.size_bytes(),
+            )),
+            FieldType_::Vector(ArrayLike::AsciiString { length, .. }) => {
+                Some((String::from("char"), format!("[{}]", length), 1))
+            }
+            FieldType_::Vector(ArrayLike::Bytes { length }) => {
+                Some((String::from("uint8_t"), format!("[{}]", length), 1))
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => Some((
+                primitive(scalar).to_string(),
+                format!("[{}]", length),
+                scalar.size_bytes(),
+            )),
+            // a union is mirrored as its raw bytes, the accessors give typed access to variants
+            FieldType_::Union(ut) => Some((String::from("uint8_t"), format!("[{}]", ut.size), 1)),
+            FieldType_::Struct(st) => {
+                let layout = &self.protocol.structure(&st.name)?.layout;
+                let alignment = self.mirror_alignment(layout)?;
+                Some((
+                    format!("{}_t", self.type_name(&st.name)),
+                    String::new(),
+                    alignment,
+                ))
+            }
+            FieldType_::Padding(_) => None,
+        }
+    }
+
+    /// A C compiler lays out a struct exactly like `layout` when every field sits on its own
+    /// alignment and the size is a multiple of the largest, gaps are filled by explicit members
+    fn mirror_alignment(&self, layout: &Layout) -> Option<usize> {
+        let mut alignment = 1;
+        for field in layout.fields() {
+            let (_, _, field_alignment) = self.mirror_type(&field.field_type)?;
+            if field.offset % field_alignment != 0 {
+                return None;
+            }
+            alignment = alignment.max(field_alignment);
+        }
+        match layout.size % alignment {
+            0 => Some(alignment),
+            _ => None,
+        }
+    }
+
+    fn write_mirror(&self, w: &mut CodeWriter, name: &str, layout: &Layout) {
+        if layout.size == 0 || self.mirror_alignment(layout).is_none() {
+            return;
+        }
+        let ty = format!("{}_t", self.type_name(name));
+        w.blank()
+            .line("/* Laid out like the wire format, only valid to read or write directly when the")
+            .line(" * host byte order matches the protocol's */");
+        w.block("typedef struct {", format!("}} {};", ty), |w| {
+            let mut reserved = 0;
+            for member in &layout.members {
+                match member {
+                    Member::Field(f) => {
+                        let (t, array, _) = self
+                            .mirror_type(&f.field_type)
+                            .expect("every field was checked to have a mirror");
+                        w.line(format!("{} {}{};", t, Self::field_name(&f.name), array));
+                    }
+                    Member::Padding(p) => {
+                        w.line(format!("uint8_t reserved{}[{}];", reserved, p.size));
+                        reserved += 1;
+                    }
+                }
+            }
+        });
+        let check = |w: &mut CodeWriter, what: &str, condition: String| {
+            w.line(format!(
+                "typedef char {}_{}_check[({}) ? 1 : -1];",
+                self.type_name(name),
+                what,
+                condition
+            ));
+        };
+        check(w, "size", format!("sizeof({}) == {}", ty, layout.size));
+        for f in layout.fields() {
+            check(
+                w,
+                &Self::field_name(&f.name),
+                format!(
+                    "offsetof({}, {}) == {}",
+                    ty,
+                    Self::field_name(&f.name),
+                    f.offset
+                ),
+            );
+        }
+    }
+
+    /// Writes a function per variant of a union field returning the union if the discriminator
+    /// selects that variant and `NULL` if it doesn't
+    fn write_variant_accessors(
+        &self,
+        w: &mut CodeWriter,
+        ty: &str,
+        function: &str,
+        at: &str,
+        ut: &UnionType,
+    ) -> Result<(), GeneratorError> {
+        let union = self.protocol.union(&ut.name).ok_or_else(|| {
+            GeneratorError::Failed(format!("union '{}' is not declared", ut.name))
+        })?;
+        let discriminator = Self::field_name(&ut.discriminator);
+        let kind = self.macro_name(&union.discriminator_type);
+        w.line(format!(
+            "/* the union if {} selects the variant, NULL otherwise */",
+            ut.discriminator
+        ));
+        for v in &union.variants {
+            w.line(format!(
+                "static inline const uint8_t *{}_{}(const uint8_t *buf) {{ return {}_get_{}(buf) == {}_{} ? {} : NULL; }}",
+                function,
+                Self::field_name(&v.name),
+                ty,
+                discriminator,
+                kind,
+                screaming_snake_case(&v.selector),
+                at
+            ));
+        }
+        Ok(())
+    }
+
+    fn write_layout(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        doc: &str,
+        id: Option<u32>,
+        layout: &Layout,
+    ) -> Result<(), GeneratorError> {
+        let ty = self.type_name(name);
+        let macro_name = self.macro_name(name);
+
+        w.line(format!("/* {} */", doc))
+            .line(format!("#define {}_SIZE {}", macro_name, layout.size));
+        if let Some(id) = id {
+            w.line(format!("#define {}_ID {}u", macro_name, id));
+        }
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        if let Some(header) = frame {
+            w.line(format!(
+                "#define {}_FRAME_SIZE {}",
+                macro_name,
+                header.layout.size + layout.size
+            ));
+        }
+        for f in layout.fields() {
+            w.line(format!(
+                "#define {}_{}_OFFSET {}",
+                macro_name,
+                screaming_snake_case(&f.name),
+                f.offset
+            ));
+            match &f.field_type {
+                FieldType_::Scalar(ScalarType::Decimal(d))
+                | FieldType_::Vector(ArrayLike::FixedArray {
+                    scalar: ScalarType::Decimal(d),
+                    ..
+                }) => {
+                    w.line(format!(
+                        "#define {}_{}_EXPONENT ({})",
+                        macro_name,
+                        screaming_snake_case(&f.name),
+                        d.exponent
+                    ));
+                }
+                _ => {}
+            }
+            if let FieldType_::Vector(v) = &f.field_type {
+                let length = match v {
+                    ArrayLike::FixedArray { length, .. }
+                    | ArrayLike::AsciiString { length, .. }
+                    | ArrayLike::Bytes { length } => length,
+                };
+                w.line(format!(
+                    "#define {}_{}_LENGTH {}",
+                    macro_name,
+                    screaming_snake_case(&f.name),
+                    length
+                ));
+            }
+        }
+
+        for f in layout.fields() {
+            let field = Self::field_name(&f.name);
+            let function = format!("{}_{}", ty, field);
+            let o = f.offset;
+            let at = match o {
+                0 => String::from("buf"),
+                o => format!("buf + {}", o),
+            };
+            let doc = match &f.description {
+                Some(d) => format!("/* {}: {} */", f.name, d),
+                None => format!("/* {}: {} at offset {} */", f.name, f.field_type, o),
+            };
+            w.blank().line(doc);
+
+            match &f.field_type {
+                FieldType_::Scalar(s) => {
+                    let t = primitive(s);
+                    w.line(format!(
+                        "static inline {} {}_get_{}(const uint8_t *buf) {{ return {}; }}",
+                        t,
+                        ty,
+                        field,
+                        self.load(s, &at)
+                    ))
+                    .line(format!(
+                        "static inline void {}_set_{}(uint8_t *buf, {} value) {{ {} }}",
+                        ty,
+                        field,
+                        t,
+                        self.store(s, &at, "value")
+                    ));
+                }
+                FieldType_::Enum(et) => {
+                    let t = self.type_name(&et.name);
+                    let value = match Self::needs_cast(&et.underlying) {
+                        true => String::from("value"),
+                        false => format!("({})value", primitive(&et.underlying)),
+                    };
+                    w.line(format!(
+                        "static inline {} {}_get_{}(const uint8_t *buf) {{ return ({}){}; }}",
+                        t,
+                        ty,
+                        field,
+                        t,
+                        self.load(&et.underlying, &at)
+                    ))
+                    .line(format!(
+                        "static inline void {}_set_{}(uint8_t *buf, {} value) {{ {} }}",
+                        ty,
+                        field,
+                        t,
+                        self.store(&et.underlying, &at, &value)
+                    ));
+                }
+                FieldType_::Struct(_) | FieldType_::Union(_) => {
+                    w.line(format!(
+                        "static inline const uint8_t *{}(const uint8_t *buf) {{ return {}; }}",
+                        function, at
+                    ))
+                    .line(format!(
+                        "static inline uint8_t *{}_mut(uint8_t *buf) {{ return {}; }}",
+                        function, at
+                    ));
+                    if let FieldType_::Union(ut) = &f.field_type {
+                        self.write_variant_accessors(w, &ty, &function, &at, ut)?;
+                    }
+                }
+                FieldType_::Vector(ArrayLike::AsciiString { length, padding }) => {
+                    let pad = match padding {
+                        AsciiPadding::Nul => "0",
+                        AsciiPadding::Space => "' '",
+                    };
+                    w.line("/* not nul terminated when the text fills the field, see _length */")
+                        .line(format!(
+                            "static inline const char *{}(const uint8_t *buf) {{ return (const char *)({}); }}",
+                            function, at
+                        ))
+                        .line(format!(
+                            "static inline size_t {}_length(const uint8_t *buf) {{ return sss_ascii_length({}, {}, {}); }}",
+                            function, at, length, pad
+                        ))
+                        .line(format!(
+                            "static inline int {}_set_{}(uint8_t *buf, const char *value) {{ return sss_store_ascii({}, {}, {}, value); }}",
+                            ty, field, at, length, pad
+                        ));
+                }
+                FieldType_::Vector(ArrayLike::Bytes { length }) => {
+                    w.line(format!(
+                        "static inline void {}_get_{}(const uint8_t *buf, uint8_t out[{}]) {{ memcpy(out, {}, {}); }}",
+                        ty, field, length, at, length
+                    ))
+                    .line(format!(
+                        "static inline void {}_set_{}(uint8_t *buf, const uint8_t value[{}]) {{ memcpy({}, value, {}); }}",
+                        ty, field, length, at, length
+                    ));
+                }
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                    let t = primitive(scalar);
+                    let at = format!("{} + index * {}", at, scalar.size_bytes());
+                    w.line("/* index is not bounds checked */")
+                        .line(format!(
+                            "static inline {} {}_get_{}(const uint8_t *buf, size_t index) {{ return {}; }}",
+                            t,
+                            ty,
+                            field,
+                            self.load(scalar, &at)
+                        ))
+                        .line(format!(
+                            "static inline void {}_set_{}(uint8_t *buf, size_t index, {} value) {{ {} }}",
+                            ty,
+                            field,
+                            t,
+                            self.store(scalar, &at, "value")
+                        ));
+                }
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            }
+        }
+
+        if let Some(header) = frame {
+            let frame_header = self.type_name(FRAME_HEADER);
+            let mut body = format!(
+                "{}_set_{}(buf, {}_ID);",
+                frame_header,
+                Self::field_name(&header.id().name),
+                macro_name
+            );
+            if let Some(length) = header.length() {
+                body += &format!(
+                    " {}_set_{}(buf, {});",
+                    frame_header,
+                    Self::field_name(&length.name),
+                    match header.length_value(0) {
+                        0 => format!("{}_SIZE", macro_name),
+                        _ => format!("{}_FRAME_SIZE", macro_name),
+                    }
+                );
+            }
+            w.blank()
+                .line("/* writes the header to the start of buf and returns the message after it, buf must hold")
+                .line(format!(" * {}_FRAME_SIZE bytes */", macro_name))
+                .line(format!(
+                    "static inline uint8_t *{}_write_frame(uint8_t *buf) {{ {} return buf + {}_SIZE; }}",
+                    ty,
+                    body,
+                    self.macro_name(FRAME_HEADER)
+                ));
+        }
+
+        self.write_mirror(w, name, layout);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::c::CGenerator;
+    use crate::codegen::{Generator, GeneratorOptions};
+
+    fn generate(schema: &str) -> String {
+        let protocol = crate::sssc::compile(schema).unwrap();
+        CGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()[0]
+            .contents
+            .clone()
+    }
+
+    #[test]
+    fn test_accessors_use_byte_order() {
+        let code = generate(
+            "protocol foo.bar [byte_order = big];\
+            message Quote [7] { u8: side; u32: price; }",
+        );
+        assert!(code.contains("#define FOO_BAR_QUOTE_SIZE 5"));
+        assert!(code.contains("#define FOO_BAR_QUOTE_PRICE_OFFSET 1"));
+        assert!(code.contains(
+            "static inline uint32_t foo_bar_quote_get_price(const uint8_t *buf) { return sss_load32(buf + 1, 1); }"
+        ));
+    }
+
+    #[test]
+    fn test_mirror_only_when_layout_matches_c() {
+        let aligned = generate("protocol foo; message M [1] { u32: a; u8: b; 3; }");
+        assert!(aligned.contains("} foo_m_t;"));
+        assert!(aligned.contains("uint8_t reserved0[3];"));
+
+        let misaligned = generate("protocol foo; message M [1] { u8: a; u32: b; }");
+        assert!(!misaligned.contains("foo_m_t"));
+    }
+}
@@ -0,0 +1,1041 @@
+//! Generates a header-only C++17 flyweight class for every message, struct and union, each class
+//! wraps a `std::byte*` and reads or writes its fields in place at their fixed offsets.
+
+use crate::codegen::{
+    file_header, snake_case, type_order, CodeWriter, Generator, GeneratorError, GeneratorOptions,
+    Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Mantissa, TimeUnit};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, UnionType};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol};
+
+const KEYWORDS: Keywords = Keywords(&[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "const",
+    "constexpr",
+    "const_cast",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+]);
+
+/// Members every flyweight has, a field with one of these names gets a `_` suffix
+const RESERVED_MEMBERS: Keywords =
+    Keywords(&["size", "id", "offsets", "data", "frame_size", "write_frame"]);
+
+/// The class generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Shared by every generated header, guarded so several headers can be included together
+const RUNTIME: &str = r#"#ifndef SSS_CPP_RUNTIME_V1
+#define SSS_CPP_RUNTIME_V1
+namespace sss {
+
+/// A fixed length ascii field, the text ends at the first nul byte or after `N` characters and
+/// leaves out the `Pad` characters ending it
+template <std::size_t N, char Pad = '\0'>
+class ascii_view {
+public:
+    explicit ascii_view(const std::byte* data) noexcept : data_(data) {}
+
+    static constexpr std::size_t capacity() noexcept { return N; }
+
+    std::string_view view() const noexcept {
+        const char* chars = reinterpret_cast<const char*>(data_);
+        std::size_t length = 0;
+        while (length < N && chars[length] != '\0') {
+            ++length;
+        }
+        while (length > 0 && chars[length - 1] == Pad) {
+            --length;
+        }
+        return std::string_view(chars, length);
+    }
+
+    operator std::string_view() const noexcept { return view(); }
+
+    const std::byte* data() const noexcept { return data_; }
+
+private:
+    const std::byte* data_;
+};
+
+template <typename Duration>
+using sys_time = std::chrono::time_point<std::chrono::system_clock, Duration>;
+
+namespace detail {
+
+template <std::size_t N> struct uint_of;
+template <> struct uint_of<1> { using type = std::uint8_t; };
+template <> struct uint_of<2> { using type = std::uint16_t; };
+template <> struct uint_of<4> { using type = std::uint32_t; };
+template <> struct uint_of<8> { using type = std::uint64_t; };
+
+/// Reads a `T` byte by byte, which is safe at any alignment and independent of the host byte order
+template <typename T, bool BigEndian>
+inline T load(const std::byte* p) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits = 0;
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        bits |= static_cast<U>(static_cast<U>(std::to_integer<std::uint8_t>(p[i])) << shift);
+    }
+    T value;
+    std::memcpy(&value, &bits, sizeof(T));
+    return value;
+}
+
+template <typename T, bool BigEndian>
+inline void store(std::byte* p, T value) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits;
+    std::memcpy(&bits, &value, sizeof(T));
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        p[i] = static_cast<std::byte>(bits >> shift);
+    }
+}
+
+/// Copies `value` into an ascii field and fills the rest with `pad`, fails if it doesn't fit
+inline bool store_ascii(std::byte* p, std::size_t capacity, char pad, std::string_view value) noexcept {
+    if (value.size() > capacity) {
+        return false;
+    }
+    for (char c : value) {
+        if (static_cast<unsigned char>(c) > 0x7f) {
+            return false;
+        }
+    }
+    std::memcpy(p, value.data(), value.size());
+    std::memset(p + value.size(), pad, capacity - value.size());
+    return true;
+}
+
+} // namespace detail
+} // namespace sss
+#endif"#;
+
+/// Generates a single header holding every type of a protocol
+pub struct CppGenerator;
+
+impl Generator for CppGenerator {
+    fn name(&self) -> &str {
+        "cpp"
+    }
+
+    fn description(&self) -> &str {
+        "header-only C++17 flyweights"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&[])?;
+
+        let header = CppHeader {
+            protocol,
+            big_endian: protocol.byte_order == ByteOrder::Big,
+        };
+        Ok(vec![OutputFile::new(
+            format!("{}.hpp", snake_case(&protocol.name.join("_"))),
+            header.generate()?,
+        )])
+    }
+}
+
+struct CppHeader<'p> {
+    protocol: &'p Protocol,
+    big_endian: bool,
+}
+
+fn name(name: &str) -> String {
+    KEYWORDS.escape(name)
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&KEYWORDS.escape(name))
+}
+
+fn primitive(scalar: &ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "std::uint8_t",
+            2 => "std::uint16_t",
+            4 => "std::uint32_t",
+            _ => "std::uint64_t",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "std::int8_t",
+            2 => "std::int16_t",
+            4 => "std::int32_t",
+            _ => "std::int64_t",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "float",
+            _ => "double",
+        },
+        ScalarType::ByteSized(ByteSize::Byte) => "std::uint8_t",
+        ScalarType::ByteSized(_) => "char",
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => primitive(&ScalarType::Unsigned(u)),
+            Mantissa::Signed(s) => primitive(&ScalarType::Signed(s)),
+        },
+        ScalarType::Timestamp(t) => primitive(&t.storage()),
+        ScalarType::Duration(d) => primitive(&d.storage()),
+    }
+}
+
+/// The type of the id or length field of a header, which are always unsigned integers
+fn header_primitive(field: &Field) -> &'static str {
+    match &field.field_type {
+        FieldType_::Scalar(s) => primitive(s),
+        _ => unreachable!("header id and length fields are unsigned integers"),
+    }
+}
+
+/// The C++ type a field's size is checked against, its getter's return type and body, and the
+/// parameter and body of its setter if it has one
+type Accessor = (String, (String, String), Option<(String, String)>);
+
+fn chrono_duration(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Seconds => "std::chrono::seconds",
+        TimeUnit::Millis => "std::chrono::milliseconds",
+        TimeUnit::Micros => "std::chrono::microseconds",
+        TimeUnit::Nanos => "std::chrono::nanoseconds",
+    }
+}
+
+impl CppHeader<'_> {
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let mut w = CodeWriter::new("    ");
+        w.line(file_header("//", protocol).trim_end())
+            .line("#pragma once")
+            .blank();
+        for include in [
+            "array",
+            "chrono",
+            "cstddef",
+            "cstdint",
+            "cstring",
+            "string_view",
+        ] {
+            w.line(format!("#include <{}>", include));
+        }
+        if !protocol.unions.is_empty() {
+            w.line("#include <optional>");
+        }
+        if protocol.header.is_some() {
+            w.line("#include <type_traits>");
+        }
+        w.blank().lines(RUNTIME).blank();
+
+        let namespace: Vec<String> = protocol.name.iter().map(|n| name(n)).collect();
+        w.line(format!("namespace {} {{", namespace.join("::")))
+            .blank()
+            .line(format!(
+                "inline constexpr bool big_endian = {};",
+                self.big_endian
+            ));
+
+        for e in &protocol.enums {
+            w.blank();
+            self.write_enum(&mut w, e)?;
+        }
+        for declaration in type_order(protocol) {
+            w.blank();
+            match declaration {
+                TypeDeclaration::Struct(s) => self.write_class(
+                    &mut w,
+                    &s.name,
+                    &format!("struct {}", s.name),
+                    None,
+                    &s.layout,
+                )?,
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "Active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    let doc = format!(
+                        "union {}, the active variant is selected by a {}",
+                        u.name, u.discriminator_type
+                    );
+                    self.write_class(&mut w, &u.name, &doc, None, &layout)?
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_class(
+                &mut w,
+                FRAME_HEADER,
+                "the header in front of every message",
+                None,
+                &header.layout,
+            )?;
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a uint32",
+                    m.name
+                ))
+            })?;
+            w.blank();
+            self.write_class(
+                &mut w,
+                &m.name,
+                &format!("message {} [{}]", m.name, m.id),
+                Some(id),
+                &m.layout,
+            )?;
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_dispatch(&mut w, header);
+        }
+
+        w.blank()
+            .line(format!("}} // namespace {}", namespace.join("::")));
+        Ok(w.finish())
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        let underlying = match e.underlying {
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_) => {
+                primitive(&e.underlying)
+            }
+            other => {
+                return Err(GeneratorError::Unsupported(format!(
+                    "enum '{}' is stored as a {}",
+                    e.name, other
+                )))
+            }
+        };
+        let ty = name(&e.name);
+
+        w.block(
+            format!("enum class {} : {} {{", ty, underlying),
+            "};",
+            |w| {
+                for v in &e.values {
+                    w.line(format!("{} = {},", name(&v.name), v.value));
+                }
+            },
+        );
+
+        match e.kind {
+            EnumKind::Enum => {
+                w.blank()
+                    .line("/// The name of the value in the schema, empty for an undeclared value");
+                w.block(
+                    format!(
+                        "constexpr std::string_view to_string({} value) noexcept {{",
+                        ty
+                    ),
+                    "}",
+                    |w| {
+                        w.block("switch (value) {", "}", |w| {
+                            for v in &e.values {
+                                w.line(format!(
+                                    "case {}::{}: return \"{}\";",
+                                    ty,
+                                    name(&v.name),
+                                    v.name
+                                ));
+                            }
+                        });
+                        w.line("return {};");
+                    },
+                );
+                w.blank();
+                w.block(
+                    format!("constexpr bool is_valid({} value) noexcept {{", ty),
+                    "}",
+                    |w| {
+                        w.line("return !to_string(value).empty();");
+                    },
+                );
+            }
+            EnumKind::Bitflags => {
+                let cast = |v: &str| format!("static_cast<{}>({})", underlying, v);
+                for op in ["|", "&", "^"] {
+                    w.blank().block(
+                        format!(
+                            "constexpr {} operator{}({} a, {} b) noexcept {{",
+                            ty, op, ty, ty
+                        ),
+                        "}",
+                        |w| {
+                            w.line(format!(
+                                "return static_cast<{}>({} {} {});",
+                                ty,
+                                cast("a"),
+                                op,
+                                cast("b")
+                            ));
+                        },
+                    );
+                    w.blank().block(
+                        format!(
+                            "constexpr {}& operator{}=({}& a, {} b) noexcept {{",
+                            ty, op, ty, ty
+                        ),
+                        "}",
+                        |w| {
+                            w.line(format!("return a = a {} b;", op));
+                        },
+                    );
+                }
+                w.blank().block(
+                    format!("constexpr {} operator~({} a) noexcept {{", ty, ty),
+                    "}",
+                    |w| {
+                        w.line(format!(
+                            "return static_cast<{}>(static_cast<{}>(~{}));",
+                            ty,
+                            underlying,
+                            cast("a")
+                        ));
+                    },
+                );
+                w.blank()
+                    .line("/// True if every flag of `flags` is set in `value`")
+                    .block(
+                        format!("constexpr bool has({} value, {} flags) noexcept {{", ty, ty),
+                        "}",
+                        |w| {
+                            w.line("return (value & flags) == flags;");
+                        },
+                    );
+                w.blank().block(
+                    format!("constexpr bool any({} value) noexcept {{", ty),
+                    "}",
+                    |w| {
+                        w.line(format!("return {} != 0;", cast("value")));
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// `dispatch` calls the handler with the message of a frame if it can be called with its type
+    fn write_dispatch(&self, w: &mut CodeWriter, header: &Header) {
+        let messages: Vec<String> = self
+            .protocol
+            .messages
+            .iter()
+            .map(|m| format!("`{}`", name(&m.name)))
+            .collect();
+        w.line("/// Reads the header at the start of `data` and calls `handler` with the message after it, as")
+            .line(format!("/// one of {}. A message the handler can't be called with is skipped.", messages.join(", ")))
+            .line("/// Returns the size of the frame, or 0 when the `size` bytes of `data` don't hold the whole")
+            .line("/// frame, its length is too small for its message or no message has its id.")
+            .line("template <typename Handler>");
+        w.block(
+            "std::size_t dispatch(std::byte* data, std::size_t size, Handler&& handler) {",
+            "}",
+            |w| {
+                w.block(format!("if (size < {}::size) {{", FRAME_HEADER), "}", |w| {
+                    w.line("return 0;");
+                })
+                .line(format!("{} header(data);", FRAME_HEADER));
+                let length = header.length().map(|l| member_name(&l.name));
+                if let Some(length) = &length {
+                    w.line(match header.length_value(0) {
+                        0 => format!(
+                            "std::size_t frame = {}::size + header.{}();",
+                            FRAME_HEADER, length
+                        ),
+                        _ => format!("std::size_t frame = header.{}();", length),
+                    });
+                }
+                w.block(
+                    format!("switch (header.{}()) {{", member_name(&header.id().name)),
+                    "}",
+                    |w| {
+                        for m in &self.protocol.messages {
+                            let ty = name(&m.name);
+                            w.dedent().line(format!("case {}::id:", ty)).indent();
+                            let (check, returns) = match length {
+                                Some(_) => (
+                                    format!("frame < {}::frame_size || size < frame", ty),
+                                    String::from("frame"),
+                                ),
+                                None => (
+                                    format!("size < {}::frame_size", ty),
+                                    format!("{}::frame_size", ty),
+                                ),
+                            };
+                            w.block(format!("if ({}) {{", check), "}", |w| {
+                                w.line("return 0;");
+                            })
+                            .block(
+                                format!("if constexpr (std::is_invocable_v<Handler&, {}>) {{", ty),
+                                "}",
+                                |w| {
+                                    w.line(format!(
+                                        "handler({}(data + {}::size));",
+                                        ty, FRAME_HEADER
+                                    ));
+                                },
+                            )
+                            .line(format!("return {};", returns));
+                        }
+                        w.dedent().line("default:").indent().line("return 0;");
+                    },
+                );
+            },
+        );
+    }
+
+    fn load(&self, ty: &str, offset: usize) -> String {
+        format!("sss::detail::load<{}, big_endian>(data_ + {})", ty, offset)
+    }
+
+    fn store(&self, ty: &str, offset: usize, value: &str) -> String {
+        format!(
+            "sss::detail::store<{}, big_endian>(data_ + {}, {});",
+            ty, offset, value
+        )
+    }
+
+    /// The accessors of a field at `offset`
+    fn accessor(&self, field_type: &FieldType_, offset: usize) -> Accessor {
+        match field_type {
+            FieldType_::Scalar(ScalarType::Timestamp(t)) => {
+                let duration = chrono_duration(t.unit);
+                let storage = primitive(&t.storage());
+                (
+                    storage.to_string(),
+                    (
+                        format!("sss::sys_time<{}>", duration),
+                        format!(
+                            "return sss::sys_time<{}>({}(static_cast<{}::rep>({})));",
+                            duration,
+                            duration,
+                            duration,
+                            self.load(storage, offset)
+                        ),
+                    ),
+                    Some((
+                        format!("sss::sys_time<{}> value", duration),
+                        self.store(
+                            storage,
+                            offset,
+                            &format!("static_cast<{}>(value.time_since_epoch().count())", storage),
+                        ),
+                    )),
+                )
+            }
+            FieldType_::Scalar(ScalarType::Duration(d)) => {
+                let duration = chrono_duration(d.unit);
+                let storage = primitive(&d.storage());
+                (
+                    storage.to_string(),
+                    (
+                        String::from(duration),
+                        format!(
+                            "return {}(static_cast<{}::rep>({}));",
+                            duration,
+                            duration,
+                            self.load(storage, offset)
+                        ),
+                    ),
+                    Some((
+                        format!("{} value", duration),
+                        self.store(
+                            storage,
+                            offset,
+                            &format!("static_cast<{}>(value.count())", storage),
+                        ),
+                    )),
+                )
+            }
+            FieldType_::Scalar(s) => {
+                let t = primitive(s);
+                (
+                    t.to_string(),
+                    (String::from(t), format!("return {};", self.load(t, offset))),
+                    Some((format!("{} value", t), self.store(t, offset, "value"))),
+                )
+            }
+            FieldType_::Enum(et) => {
+                let t = name(&et.name);
+                let raw = primitive(&et.underlying);
+                (
+                    t.clone(),
+                    (
+                        t.clone(),
+                        format!("return static_cast<{}>({});", t, self.load(raw, offset)),
+                    ),
+                    Some((
+                        format!("{} value", t),
+                        self.store(raw, offset, &format!("static_cast<{}>(value)", raw)),
+                    )),
+                )
+            }
+            FieldType_::Struct(st) => {
+                let t = name(&st.name);
+                (
+                    t.clone(),
+                    (t.clone(), format!("return {}(data_ + {});", t, offset)),
+                    None,
+                )
+            }
+            FieldType_::Union(ut) => {
+                let t = name(&ut.name);
+                (
+                    t.clone(),
+                    (t.clone(), format!("return {}(data_ + {});", t, offset)),
+                    None,
+                )
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { length, padding }) => {
+                let (t, pad) = match padding {
+                    AsciiPadding::Nul => (format!("sss::ascii_view<{}>", length), "'\\0'"),
+                    AsciiPadding::Space => (format!("sss::ascii_view<{}, ' '>", length), "' '"),
+                };
+                (
+                    format!("char[{}]", length),
+                    (t.clone(), format!("return {}(data_ + {});", t, offset)),
+                    Some((
+                        String::from("std::string_view value"),
+                        format!(
+                            "return sss::detail::store_ascii(data_ + {}, {}, {}, value);",
+                            offset, length, pad
+                        ),
+                    )),
+                )
+            }
+            FieldType_::Vector(ArrayLike::Bytes { length }) => {
+                let t = format!("std::array<std::uint8_t, {}>", length);
+                (
+                    format!("std::uint8_t[{}]", length),
+                    (
+                        t.clone(),
+                        format!(
+                            "{} value;\nstd::memcpy(value.data(), data_ + {}, {});\nreturn value;",
+                            t, offset, length
+                        ),
+                    ),
+                    Some((
+                        format!("const {}& value", t),
+                        format!("std::memcpy(data_ + {}, value.data(), {});", offset, length),
+                    )),
+                )
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                let element = primitive(scalar);
+                let t = format!("std::array<{}, {}>", element, length);
+                let stride = scalar.size_bytes();
+                (
+                    format!("{}[{}]", element, length),
+                    (
+                        t.clone(),
+                        format!(
+                            "{} value;\nfor (std::size_t i = 0; i < {}; ++i) {{\n    value[i] = sss::detail::load<{}, big_endian>(data_ + {} + i * {});\n}}\nreturn value;",
+                            t, length, element, offset, stride
+                        ),
+                    ),
+                    Some((
+                        format!("const {}& value", t),
+                        format!(
+                            "for (std::size_t i = 0; i < {}; ++i) {{\n    sss::detail::store<{}, big_endian>(data_ + {} + i * {}, value[i]);\n}}",
+                            length, element, offset, stride
+                        ),
+                    )),
+                )
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+    /// Writes an accessor for every variant of the union field `member` which is empty unless
+    /// the discriminator selects that variant
+    fn write_variant_accessors(
+        &self,
+        w: &mut CodeWriter,
+        member: &str,
+        ut: &UnionType,
+    ) -> Result<(), GeneratorError> {
+        let union = self.protocol.union(&ut.name).ok_or_else(|| {
+            GeneratorError::Failed(format!("union '{}' is not declared", ut.name))
+        })?;
+        let discriminator = member_name(&ut.discriminator);
+        let kind = name(&union.discriminator_type);
+        for v in &union.variants {
+            let variant = member_name(&v.name);
+            let selector = format!("{}::{}", kind, name(&v.selector));
+            let (_, (returns, _), _) = self.accessor(&v.field_type, 0);
+            w.blank()
+                .line(format!(
+                    "/// `{}().{}()`, or nothing unless `{}()` is `{}`",
+                    member, variant, discriminator, selector
+                ))
+                .block(
+                    format!(
+                        "std::optional<{}> {}_{}() const noexcept {{",
+                        returns, member, variant
+                    ),
+                    "}",
+                    |w| {
+                        w.block(
+                            format!("if ({}() != {}) {{", discriminator, selector),
+                            "}",
+                            |w| {
+                                w.line("return std::nullopt;");
+                            },
+                        )
+                        .line(format!("return {}().{}();", member, variant));
+                    },
+                );
+        }
+        Ok(())
+    }
+
+    fn write_class(
+        &self,
+        w: &mut CodeWriter,
+        class: &str,
+        doc: &str,
+        id: Option<u32>,
+        layout: &Layout,
+    ) -> Result<(), GeneratorError> {
+        let ty = name(class);
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        let mut accessors = CodeWriter::new("    ");
+        let mut checks: Vec<String> = vec![];
+        // what each member adds to the size of the type, by offset
+        let mut sizes: Vec<(usize, String)> = vec![];
+
+        for field in layout.fields() {
+            let member = member_name(&field.name);
+            let offset = field.offset;
+            let size = field.field_type.size_bytes();
+            let at = format!("offsets::{}", member);
+            let doc = match &field.description {
+                Some(d) => format!("/// {}", d),
+                None => format!(
+                    "/// {}, offset {}, {} bytes",
+                    field.field_type, offset, size
+                ),
+            };
+            accessors.blank().line(doc);
+
+            let (cpp_type, getter, setter) = self.accessor(&field.field_type, offset);
+
+            let (returns, body) = getter;
+            accessors.block(
+                format!("{} {}() const noexcept {{", returns, member),
+                "}",
+                |w| {
+                    w.lines(body);
+                },
+            );
+            if let Some((params, body)) = setter {
+                let returns = match field.field_type {
+                    FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                        accessors
+                            .line("/// Fails if the value is longer than the field or isn't ascii");
+                        "bool"
+                    }
+                    _ => "void",
+                };
+                accessors.block(
+                    format!("{} set_{}({}) noexcept {{", returns, member, params),
+                    "}",
+                    |w| {
+                        w.lines(body);
+                    },
+                );
+            }
+            if let FieldType_::Union(ut) = &field.field_type {
+                self.write_variant_accessors(&mut accessors, &member, ut)?;
+            }
+
+            let size_check = match &field.field_type {
+                FieldType_::Struct(_) | FieldType_::Union(_) => format!("{}::size", cpp_type),
+                _ => format!("sizeof({})", cpp_type),
+            };
+            checks.push(format!(
+                "static_assert({}::{} + {} == {}, \"{}.{} must end at byte {}\");",
+                ty,
+                at,
+                size_check,
+                offset + size,
+                class,
+                field.name,
+                offset + size
+            ));
+            sizes.push((offset, size_check));
+        }
+
+        let fields: Vec<(String, usize)> = layout
+            .fields()
+            .map(|f| (member_name(&f.name), f.offset))
+            .collect();
+        let decimals: Vec<(String, i8)> = layout
+            .fields()
+            .filter_map(|f| match &f.field_type {
+                FieldType_::Scalar(ScalarType::Decimal(d))
+                | FieldType_::Vector(ArrayLike::FixedArray {
+                    scalar: ScalarType::Decimal(d),
+                    ..
+                }) => Some((member_name(&f.name), d.exponent)),
+                _ => None,
+            })
+            .collect();
+
+        w.line(format!("/// {}", doc));
+        w.block(format!("class {} {{", ty), "};", |w| {
+            w.dedent().line("public:").indent();
+            w.line(format!(
+                "static constexpr std::size_t size = {};",
+                layout.size
+            ));
+            if let Some(id) = id {
+                w.line(format!("static constexpr std::uint32_t id = {};", id));
+            }
+            if let Some(header) = frame {
+                w.line("/// The size of the header followed by the message")
+                    .line(format!(
+                        "static constexpr std::size_t frame_size = {};",
+                        header.layout.size + layout.size
+                    ));
+            }
+            for (member, exponent) in &decimals {
+                w.line(format!(
+                    "/// `{}` holds the mantissa of a decimal, its value is `{}() * 10^{}`",
+                    member, member, exponent
+                ))
+                .line(format!(
+                    "static constexpr int {}_exponent = {};",
+                    member, exponent
+                ));
+            }
+            w.blank();
+            w.block("struct offsets {", "};", |w| {
+                for (member, offset) in &fields {
+                    w.line(format!(
+                        "static constexpr std::size_t {} = {};",
+                        member, offset
+                    ));
+                }
+            });
+            w.blank()
+                .line(format!(
+                    "explicit {}(std::byte* data) noexcept : data_(data) {{}}",
+                    ty
+                ))
+                .blank()
+                .line("std::byte* data() const noexcept { return data_; }");
+            if let Some(header) = frame {
+                w.blank()
+                    .line("/// Writes the header to the start of `data` and returns the message after it, `data` must")
+                    .line("/// hold `frame_size` bytes");
+                w.block(
+                    format!("static {} write_frame(std::byte* data) noexcept {{", ty),
+                    "}",
+                    |w| {
+                        let id_field = header.id();
+                        w.line(format!("{} header(data);", FRAME_HEADER)).line(format!(
+                            "header.set_{}(static_cast<{}>(id));",
+                            member_name(&id_field.name),
+                            header_primitive(id_field)
+                        ));
+                        if let Some(length) = header.length() {
+                            w.line(format!(
+                                "header.set_{}(static_cast<{}>({}));",
+                                member_name(&length.name),
+                                header_primitive(length),
+                                match header.length_value(0) {
+                                    0 => "size",
+                                    _ => "frame_size",
+                                }
+                            ));
+                        }
+                        w.line(format!("return {}(data + {}::size);", ty, FRAME_HEADER));
+                    },
+                );
+            }
+            w.lines(accessors.finish());
+            w.blank().dedent().line("private:").indent();
+            w.line("std::byte* data_;");
+        });
+        for check in checks {
+            w.line(check);
+        }
+        // the variants of a union overlap, each of them was checked against its size above
+        let contiguous = layout
+            .members
+            .windows(2)
+            .all(|m| m[0].offset() + m[0].size_bytes() == m[1].offset());
+        let end = layout
+            .members
+            .last()
+            .map_or(0, |m| m.offset() + m.size_bytes());
+        if contiguous && end == layout.size {
+            for member in &layout.members {
+                if let Member::Padding(padding) = member {
+                    sizes.push((padding.offset, padding.size.to_string()));
+                }
+            }
+            sizes.sort_by_key(|(offset, _)| *offset);
+            let total: Vec<String> = sizes.into_iter().map(|(_, size)| size).collect();
+            w.line(format!(
+                "static_assert({} == {}::size, \"{} must be {} bytes\");",
+                if total.is_empty() {
+                    String::from("0")
+                } else {
+                    total.join(" + ")
+                },
+                ty,
+                class,
+                layout.size
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::cpp::CppGenerator;
+    use crate::codegen::{Generator, GeneratorOptions};
+
+    fn generate(schema: &str) -> String {
+        let protocol = crate::sssc::compile(schema).unwrap();
+        CppGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()[0]
+            .contents
+            .clone()
+    }
+
+    #[test]
+    fn test_offsets_and_byte_order() {
+        let code = generate(
+            "protocol foo.bar [byte_order = big];\
+            message Quote [7] { u8: side; u32: price; ascii[4]: symbol; }",
+        );
+
+        assert!(code.contains("namespace foo::bar {"));
+        assert!(code.contains("inline constexpr bool big_endian = true;"));
+        assert!(code.contains("static constexpr std::size_t price = 1;"));
+        assert!(code.contains("sss::detail::load<std::uint32_t, big_endian>(data_ + 1)"));
+        assert!(code.contains("sss::ascii_view<4> symbol() const noexcept"));
+        assert!(code.contains(
+            "static_assert(Quote::offsets::symbol + sizeof(char[4]) == 9, \"Quote.symbol must end at byte 9\");"
+        ));
+        assert!(code.contains(
+            "static_assert(sizeof(std::uint8_t) + sizeof(std::uint32_t) + sizeof(char[4]) \
+            == Quote::size, \"Quote must be 9 bytes\");"
+        ));
+    }
+
+    #[test]
+    fn test_bitflags_have_operators() {
+        let code = generate("protocol foo; bitflags Flags : u8 { A, B }");
+        assert!(code.contains("enum class Flags : std::uint8_t {"));
+        assert!(code.contains("constexpr Flags operator|(Flags a, Flags b) noexcept {"));
+    }
+}
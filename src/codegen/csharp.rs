@@ -0,0 +1,1110 @@
+//! Generates C# `ref struct` views, `XView` reads a message in place from a `ReadOnlySpan<byte>`
+//! and `XWriter` writes one in place to a `Span<byte>`. Every value goes through
+//! `BinaryPrimitives` so the protocol's byte order holds on any host.
+
+use crate::codegen::{
+    file_header, pascal_case, type_order, CodeWriter, Generator, GeneratorError, GeneratorOptions,
+    Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{Mantissa, TimeUnit};
+use crate::data_types::{
+    ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, StructType, UnionType,
+};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol, Union};
+
+/// Only checked against the `namespace` option, the names generated are all Pascal case
+const KEYWORDS: Keywords = Keywords(&[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+]);
+
+/// C# keywords are all lower case so they can't collide with the Pascal case names generated,
+/// only with the members every view has
+const RESERVED_MEMBERS: Keywords = Keywords(&[
+    "Size",
+    "Id",
+    "TryRead",
+    "AsView",
+    "Equals",
+    "GetHashCode",
+    "GetType",
+    "ToString",
+    "FrameSize",
+    "WriteFrame",
+]);
+
+/// The views generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Shared by the views of a protocol
+const ASCII_FIELD: &str = r#"/// <summary>Reads and writes fixed length ascii fields, the text ends at the first nul byte, without the pad bytes ending it</summary>
+internal static class AsciiField
+{
+    public static string Read(ReadOnlySpan<byte> field, byte pad)
+    {
+        int length = field.IndexOf((byte)0);
+        return Encoding.ASCII.GetString((length < 0 ? field : field.Slice(0, length)).TrimEnd(pad));
+    }
+
+    public static void Write(Span<byte> field, byte pad, string value, string name)
+    {
+        if (value.Length > field.Length)
+        {
+            throw new ArgumentException($"{name} holds at most {field.Length} characters but the value has {value.Length}", nameof(value));
+        }
+        foreach (char c in value)
+        {
+            if (c > 0x7f)
+            {
+                throw new ArgumentException($"{name} only holds ascii characters", nameof(value));
+            }
+        }
+        field.Fill(pad);
+        for (int i = 0; i < value.Length; i++)
+        {
+            field[i] = (byte)value[i];
+        }
+    }
+}"#;
+
+/// Generates a single source file holding every type of a protocol, in the namespace given by
+/// the `namespace` option or named after the protocol
+pub struct CSharpGenerator;
+
+impl Generator for CSharpGenerator {
+    fn name(&self) -> &str {
+        "csharp"
+    }
+
+    fn description(&self) -> &str {
+        "C# ref struct views over Span<byte>, options: namespace"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&["namespace"])?;
+
+        let namespace = match options.get("namespace") {
+            Some(namespace) => {
+                let valid = namespace.split('.').all(|part| {
+                    let mut chars = part.chars();
+                    chars
+                        .next()
+                        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                        && !KEYWORDS.is_keyword(part)
+                });
+                if !valid {
+                    return Err(GeneratorError::InvalidOption {
+                        name: String::from("namespace"),
+                        reason: format!("'{}' is not a C# namespace", namespace),
+                    });
+                }
+                namespace.to_string()
+            }
+            None => protocol
+                .name
+                .iter()
+                .map(|n| pascal_case(n))
+                .collect::<Vec<String>>()
+                .join("."),
+        };
+
+        let file = CSharpFile {
+            protocol,
+            namespace,
+            endian: match protocol.byte_order {
+                ByteOrder::Little => "LittleEndian",
+                ByteOrder::Big => "BigEndian",
+            },
+        };
+        Ok(vec![OutputFile::new(
+            format!("{}.cs", pascal_case(&protocol.name.join("_"))),
+            file.generate()?,
+        )])
+    }
+}
+
+struct CSharpFile<'p> {
+    protocol: &'p Protocol,
+    namespace: String,
+    /// The suffix of the `BinaryPrimitives` methods used
+    endian: &'static str,
+}
+
+fn type_name(name: &str) -> String {
+    pascal_case(name)
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&pascal_case(name))
+}
+
+/// Doc comments are xml, type names like `decimal<i64, -4>` need escaping
+/// The byte filling an ascii field after its text
+fn pad_byte(padding: AsciiPadding) -> &'static str {
+    match padding {
+        AsciiPadding::Nul => "0",
+        AsciiPadding::Space => "(byte)' '",
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn storage(scalar: &ScalarType) -> ScalarType {
+    match scalar {
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => ScalarType::Unsigned(u),
+            Mantissa::Signed(s) => ScalarType::Signed(s),
+        },
+        ScalarType::Timestamp(t) => t.storage(),
+        ScalarType::Duration(d) => d.storage(),
+        other => *other,
+    }
+}
+
+/// The type, property suffix and conversions to and from the raw count of a timestamp or
+/// duration, the conversions go through ticks of 100ns
+fn native_time(scalar: &ScalarType) -> Option<(&'static str, &'static str, String, String)> {
+    let ticks = |unit: TimeUnit| -> (&str, &str) {
+        match unit {
+            TimeUnit::Seconds => ("* TimeSpan.TicksPerSecond", "/ TimeSpan.TicksPerSecond"),
+            TimeUnit::Millis => (
+                "* TimeSpan.TicksPerMillisecond",
+                "/ TimeSpan.TicksPerMillisecond",
+            ),
+            TimeUnit::Micros => (
+                "* TimeSpan.TicksPerMicrosecond",
+                "/ TimeSpan.TicksPerMicrosecond",
+            ),
+            TimeUnit::Nanos => (
+                "/ TimeSpan.NanosecondsPerTick",
+                "* TimeSpan.NanosecondsPerTick",
+            ),
+        }
+    };
+    match scalar {
+        ScalarType::Timestamp(t) => {
+            let (to, from) = ticks(t.unit);
+            Some((
+                "DateTimeOffset",
+                "Time",
+                format!("DateTimeOffset.UnixEpoch.AddTicks((long){{}} {})", to),
+                format!("(ulong)((value - DateTimeOffset.UnixEpoch).Ticks {})", from),
+            ))
+        }
+        ScalarType::Duration(d) => {
+            let (to, from) = ticks(d.unit);
+            Some((
+                "TimeSpan",
+                "TimeSpan",
+                format!("TimeSpan.FromTicks({{}} {})", to),
+                format!("value.Ticks {}", from),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The `decimal` literal a mantissa is multiplied by for the value of a decimal
+fn decimal_factor(exponent: i8) -> String {
+    match exponent {
+        e if e < 0 => format!("0.{}1m", "0".repeat(e.unsigned_abs() as usize - 1)),
+        e => format!("1{}m", "0".repeat(e as usize)),
+    }
+}
+
+fn csharp_type(scalar: &ScalarType) -> &'static str {
+    match storage(scalar) {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "byte",
+            2 => "ushort",
+            4 => "uint",
+            _ => "ulong",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "sbyte",
+            2 => "short",
+            4 => "int",
+            _ => "long",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "float",
+            _ => "double",
+        },
+        _ => "byte",
+    }
+}
+
+/// The C# type of a header's id or length field, which are always unsigned integers
+fn header_type(field: &Field) -> &'static str {
+    match &field.field_type {
+        FieldType_::Scalar(s) => csharp_type(s),
+        _ => unreachable!("header id and length fields are unsigned integers"),
+    }
+}
+
+/// `value` of type `from` converted to the type of a header field, leaving out a needless cast
+fn header_value(field: &Field, from: &str, value: &str) -> String {
+    match header_type(field) {
+        "ulong" => value.to_string(),
+        ty if ty == from => value.to_string(),
+        ty => format!("({}){}", ty, value),
+    }
+}
+
+/// The type name `BinaryPrimitives` uses in its method names
+fn primitive_name(scalar: &ScalarType) -> &'static str {
+    match csharp_type(scalar) {
+        "ushort" => "UInt16",
+        "uint" => "UInt32",
+        "ulong" => "UInt64",
+        "short" => "Int16",
+        "int" => "Int32",
+        "long" => "Int64",
+        "float" => "Single",
+        _ => "Double",
+    }
+}
+
+impl CSharpFile<'_> {
+    fn read(&self, scalar: &ScalarType, at: &str) -> String {
+        match csharp_type(scalar) {
+            "byte" => format!("_buffer[{}]", at),
+            "sbyte" => format!("(sbyte)_buffer[{}]", at),
+            _ => format!(
+                "BinaryPrimitives.Read{}{}(_buffer.Slice({}))",
+                primitive_name(scalar),
+                self.endian,
+                at
+            ),
+        }
+    }
+
+    fn write(&self, scalar: &ScalarType, at: &str, value: &str) -> String {
+        match csharp_type(scalar) {
+            "byte" => format!("_buffer[{}] = {};", at, value),
+            "sbyte" => format!("_buffer[{}] = (byte){};", at, value),
+            _ => format!(
+                "BinaryPrimitives.Write{}{}(_buffer.Slice({}), {});",
+                primitive_name(scalar),
+                self.endian,
+                at,
+                value
+            ),
+        }
+    }
+
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let mut w = CodeWriter::new("    ");
+        w.line(file_header("//", protocol).trim_end())
+            .line("using System;")
+            .line("using System.Buffers.Binary;")
+            .line("using System.Diagnostics.CodeAnalysis;")
+            .line("using System.Text;")
+            .blank()
+            .line(format!("namespace {}", self.namespace))
+            .line("{")
+            .indent()
+            .lines(ASCII_FIELD);
+
+        for e in &protocol.enums {
+            w.blank();
+            self.write_enum(&mut w, e)?;
+        }
+        for declaration in type_order(protocol) {
+            match declaration {
+                TypeDeclaration::Struct(s) => {
+                    self.write_views(&mut w, &s.name, None, &s.layout, None)
+                }
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "Active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    self.write_views(&mut w, &u.name, None, &layout, Some(u))
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            self.write_views(&mut w, FRAME_HEADER, None, &header.layout, None);
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a uint",
+                    m.name
+                ))
+            })?;
+            self.write_views(&mut w, &m.name, Some(id), &m.layout, None);
+        }
+        if let Some(header) = &protocol.header {
+            self.write_dispatcher(&mut w, header);
+        }
+
+        w.dedent().line("}");
+        Ok(w.finish())
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let kind = match e.kind {
+            EnumKind::Enum => "enum",
+            EnumKind::Bitflags => {
+                w.line(format!(
+                    "/// <summary>bitflags {}, stored as a {}</summary>",
+                    e.name, e.underlying
+                ))
+                .line("[Flags]");
+                "bitflags"
+            }
+        };
+        if kind == "enum" {
+            w.line(format!(
+                "/// <summary>enum {}, stored as a {}</summary>",
+                e.name, e.underlying
+            ));
+        }
+        w.line(format!(
+            "public enum {} : {}",
+            type_name(&e.name),
+            csharp_type(&e.underlying)
+        ))
+        .block("{", "}", |w| {
+            for v in &e.values {
+                w.line(format!("{} = {},", pascal_case(&v.name), v.value));
+            }
+        });
+        Ok(())
+    }
+
+    fn write_views(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        id: Option<u32>,
+        layout: &Layout,
+        union: Option<&Union>,
+    ) {
+        let ty = type_name(name);
+        let view = format!("{}View", ty);
+        let writer = format!("{}Writer", ty);
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        let doc = match (id, union) {
+            (Some(id), _) => format!("message {} [{}] in place", name, id),
+            (None, Some(u)) => format!(
+                "union {} in place, the active variant is selected by a {}",
+                u.name, u.discriminator_type
+            ),
+            (None, None) => format!("struct {} in place", name),
+        };
+
+        w.blank()
+            .line(format!(
+                "/// <summary>Reads {}</summary>",
+                doc
+            ))
+            .line(format!("public readonly ref struct {}", view))
+            .block("{", "}", |w| {
+                w.line(format!("public const int Size = {};", layout.size));
+                if let Some(id) = id {
+                    w.line(format!("public const uint Id = {};", id));
+                }
+                if let Some(header) = frame {
+                    w.line(format!(
+                        "public const int FrameSize = {};",
+                        header.layout.size + layout.size
+                    ));
+                }
+                for f in layout.fields() {
+                    w.line(format!(
+                        "public const int {}Offset = {};",
+                        member_name(&f.name),
+                        f.offset
+                    ));
+                    match &f.field_type {
+                        FieldType_::Scalar(ScalarType::Decimal(d))
+                        | FieldType_::Vector(ArrayLike::FixedArray {
+                            scalar: ScalarType::Decimal(d),
+                            ..
+                        }) => {
+                            w.line(format!(
+                                "public const int {}Exponent = {};",
+                                member_name(&f.name),
+                                d.exponent
+                            ));
+                        }
+                        _ => {}
+                    }
+                    if let FieldType_::Vector(ArrayLike::FixedArray { length, .. }) =
+                        &f.field_type
+                    {
+                        w.line(format!(
+                            "public const int {}Length = {};",
+                            member_name(&f.name),
+                            length
+                        ));
+                    }
+                }
+                w.blank()
+                    .line("private readonly ReadOnlySpan<byte> _buffer;")
+                    .blank()
+                    .line("/// <exception cref=\"ArgumentException\">The buffer is shorter than Size</exception>")
+                    .line(format!("public {}(ReadOnlySpan<byte> buffer)", view))
+                    .block("{", "}", |w| {
+                        w.line("if (buffer.Length < Size)")
+                            .block("{", "}", |w| {
+                                w.line(format!(
+                                    "throw new ArgumentException($\"{} needs {{Size}} bytes but the buffer holds {{buffer.Length}}\", nameof(buffer));",
+                                    name
+                                ));
+                            })
+                            .line("_buffer = buffer.Slice(0, Size);");
+                    })
+                    .blank()
+                    .line("/// <summary>Views the start of buffer, false if it is shorter than Size</summary>")
+                    .line(format!(
+                        "public static bool TryRead(ReadOnlySpan<byte> buffer, out {} view)",
+                        view
+                    ))
+                    .block("{", "}", |w| {
+                        w.line("if (buffer.Length < Size)")
+                            .block("{", "}", |w| {
+                                w.line("view = default;").line("return false;");
+                            })
+                            .line(format!("view = new {}(buffer);", view))
+                            .line("return true;");
+                    })
+                    .blank()
+                    .line("public ReadOnlySpan<byte> Buffer => _buffer;");
+                for f in layout.fields() {
+                    w.blank();
+                    self.write_getter(w, f);
+                    if let FieldType_::Union(ut) = &f.field_type {
+                        self.write_variant_getters(w, f, ut);
+                    }
+                }
+            });
+
+        w.blank()
+            .line(format!(
+                "/// <summary>Writes {}</summary>",
+                doc
+            ))
+            .line(format!("public readonly ref struct {}", writer))
+            .block("{", "}", |w| {
+                w.line(format!("public const int Size = {}.Size;", view));
+                if id.is_some() {
+                    w.line(format!("public const uint Id = {}.Id;", view));
+                }
+                if frame.is_some() {
+                    w.line(format!("public const int FrameSize = {}.FrameSize;", view));
+                }
+                w.blank()
+                    .line("private readonly Span<byte> _buffer;")
+                    .blank()
+                    .line("/// <exception cref=\"ArgumentException\">The buffer is shorter than Size</exception>")
+                    .line(format!("public {}(Span<byte> buffer)", writer))
+                    .block("{", "}", |w| {
+                        w.line("if (buffer.Length < Size)")
+                            .block("{", "}", |w| {
+                                w.line(format!(
+                                    "throw new ArgumentException($\"{} needs {{Size}} bytes but the buffer holds {{buffer.Length}}\", nameof(buffer));",
+                                    name
+                                ));
+                            })
+                            .line("_buffer = buffer.Slice(0, Size);");
+                    })
+                    .blank()
+                    .line("public Span<byte> Buffer => _buffer;")
+                    .blank()
+                    .line(format!("public {} AsView() => new {}(_buffer);", view, view));
+                if let Some(header) = frame {
+                    w.blank()
+                        .line("/// <summary>Writes the header's id and length to the start of buffer and returns the message after it</summary>")
+                        .line("/// <exception cref=\"ArgumentException\">The buffer is shorter than FrameSize</exception>")
+                        .line(format!("public static {} WriteFrame(Span<byte> buffer)", writer))
+                        .block("{", "}", |w| {
+                            w.line("if (buffer.Length < FrameSize)")
+                                .block("{", "}", |w| {
+                                    w.line(format!(
+                                        "throw new ArgumentException($\"the frame of {} needs {{FrameSize}} bytes but the buffer holds {{buffer.Length}}\", nameof(buffer));",
+                                        name
+                                    ));
+                                })
+                                .line(format!("var header = new {}Writer(buffer);", FRAME_HEADER))
+                                .line(format!(
+                                    "header.{} = {};",
+                                    member_name(&header.id().name),
+                                    header_value(header.id(), "uint", "Id")
+                                ));
+                            if let Some(length) = header.length() {
+                                let value = match header.length_value(0) {
+                                    0 => "Size",
+                                    _ => "FrameSize",
+                                };
+                                w.line(format!(
+                                    "header.{} = {};",
+                                    member_name(&length.name),
+                                    header_value(length, "int", value)
+                                ));
+                            }
+                            w.line(format!(
+                                "return new {}(buffer.Slice({}View.Size));",
+                                writer, FRAME_HEADER
+                            ));
+                        });
+                }
+                for f in layout.fields() {
+                    w.blank();
+                    self.write_setter(w, &view, f);
+                }
+            });
+    }
+
+    /// An `IHandler` interface with a callback per message and `Dispatcher.Dispatch` calling them
+    fn write_dispatcher(&self, w: &mut CodeWriter, header: &Header) {
+        let messages: Vec<String> = self
+            .protocol
+            .messages
+            .iter()
+            .map(|m| type_name(&m.name))
+            .collect();
+        let header_view = format!("{}View", FRAME_HEADER);
+
+        w.blank()
+            .line("/// <summary>Callbacks for Dispatcher.Dispatch, a message without an override is ignored</summary>")
+            .line("public interface IHandler")
+            .block("{", "}", |w| {
+                for (i, ty) in messages.iter().enumerate() {
+                    if i > 0 {
+                        w.blank();
+                    }
+                    w.line(format!("void On{}({}View message) {{ }}", ty, ty));
+                }
+            });
+
+        w.blank()
+            .line("/// <summary>Calls a handler with the message of each frame</summary>")
+            .line("public static class Dispatcher")
+            .block("{", "}", |w| {
+                w.line("/// <summary>Reads the header at the start of buffer and calls handler with the message after it, returns the size of the frame</summary>")
+                    .line("/// <exception cref=\"ArgumentException\">The buffer doesn't hold the whole frame, its length is too small for its message or no message has its id</exception>")
+                    .line("public static int Dispatch(ReadOnlySpan<byte> buffer, IHandler handler)")
+                    .block("{", "}", |w| {
+                        w.line(format!("var header = new {}(buffer);", header_view));
+                        if let Some(length) = header.length() {
+                            let value = format!("header.{}", member_name(&length.name));
+                            let value = match header_type(length) {
+                                "ulong" => format!("(long){}", value),
+                                _ => value,
+                            };
+                            w.line(match header.length_value(0) {
+                                0 => format!("long frame = {}.Size + {};", header_view, value),
+                                _ => format!("long frame = {};", value),
+                            });
+                        }
+                        let id = format!("header.{}", member_name(&header.id().name));
+                        w.line(format!("switch ((ulong){})", id)).block("{", "}", |w| {
+                            for ty in &messages {
+                                w.line(format!("case {}View.Id:", ty)).indent();
+                                if header.length().is_some() {
+                                    w.line(format!(
+                                        "CheckFrame(buffer, frame, {}View.FrameSize);",
+                                        ty
+                                    ));
+                                }
+                                w.line(format!(
+                                    "handler.On{}(new {}View(buffer.Slice({}.Size)));",
+                                    ty, ty, header_view
+                                ))
+                                .line(match header.length() {
+                                    Some(_) => String::from("return (int)frame;"),
+                                    None => format!("return {}View.FrameSize;", ty),
+                                })
+                                .dedent();
+                            }
+                            w.line("default:").indent().line(format!(
+                                "throw new ArgumentException($\"no message has the id {{{}}}\", nameof(buffer));",
+                                id
+                            ))
+                            .dedent();
+                        });
+                    });
+                if header.length().is_some() {
+                    w.blank()
+                        .line("private static void CheckFrame(ReadOnlySpan<byte> buffer, long frame, int size)")
+                        .block("{", "}", |w| {
+                            w.line("if (frame < size)")
+                                .block("{", "}", |w| {
+                                    w.line("throw new ArgumentException($\"a frame length of {frame} is too small for a message of {size} bytes\", nameof(buffer));");
+                                })
+                                .line("if (frame > buffer.Length)")
+                                .block("{", "}", |w| {
+                                    w.line("throw new ArgumentException($\"a frame of {frame} bytes overruns a buffer of {buffer.Length}\", nameof(buffer));");
+                                });
+                        });
+                }
+            });
+    }
+
+    fn doc(f: &Field) -> String {
+        let summary = match &f.description {
+            Some(d) => format!("{}: {}", f.field_type, d),
+            None => format!("{} at offset {}", f.field_type, f.offset),
+        };
+        format!("/// <summary>{}</summary>", xml_escape(&summary))
+    }
+
+    fn write_getter(&self, w: &mut CodeWriter, f: &Field) {
+        let member = member_name(&f.name);
+        let o = f.offset;
+        let size = f.field_type.size_bytes();
+        w.line(Self::doc(f));
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                w.line(format!(
+                    "public {} {} => {};",
+                    csharp_type(s),
+                    member,
+                    self.read(s, &o.to_string())
+                ));
+                if let Some((ty, suffix, read, _)) = native_time(s) {
+                    w.blank()
+                        .line(format!("/// <summary>{} as a {}</summary>", f.name, ty))
+                        .line(format!(
+                            "public {} {}{} => {};",
+                            ty,
+                            member,
+                            suffix,
+                            read.replace("{}", &member)
+                        ));
+                }
+                if let ScalarType::Decimal(d) = s {
+                    w.blank()
+                        .line(format!("/// <summary>{} as a decimal</summary>", f.name))
+                        .line(format!(
+                            "public decimal {}Decimal => {} * {};",
+                            member,
+                            member,
+                            decimal_factor(d.exponent)
+                        ));
+                }
+            }
+            FieldType_::Enum(et) => {
+                w.line(format!(
+                    "public {} {} => ({}){};",
+                    type_name(&et.name),
+                    member,
+                    type_name(&et.name),
+                    self.read(&et.underlying, &o.to_string())
+                ));
+            }
+            FieldType_::Struct(st) => {
+                w.line(format!(
+                    "public {}View {} => new {}View(_buffer.Slice({}, {}));",
+                    type_name(&st.name),
+                    member,
+                    type_name(&st.name),
+                    o,
+                    size
+                ));
+            }
+            FieldType_::Union(ut) => {
+                w.line(format!(
+                    "public {}View {} => new {}View(_buffer.Slice({}, {}));",
+                    type_name(&ut.name),
+                    member,
+                    type_name(&ut.name),
+                    o,
+                    size
+                ));
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                w.line(format!(
+                    "public string {} => AsciiField.Read(_buffer.Slice({}, {}), {});",
+                    member,
+                    o,
+                    size,
+                    pad_byte(*padding)
+                ))
+                .line(format!(
+                    "public ReadOnlySpan<byte> {}Bytes => _buffer.Slice({}, {});",
+                    member, o, size
+                ));
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                w.line(format!(
+                    "public ReadOnlySpan<byte> {} => _buffer.Slice({}, {});",
+                    member, o, size
+                ));
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                let at = format!("{} + index * {}", o, scalar.size_bytes());
+                w.line(format!(
+                    "public {} Get{}(int index)",
+                    csharp_type(scalar),
+                    member
+                ))
+                .block("{", "}", |w| {
+                    w.line(format!("if ((uint)index >= {}Length)", member))
+                        .block("{", "}", |w| {
+                            w.line("throw new ArgumentOutOfRangeException(nameof(index));");
+                        })
+                        .line(format!("return {};", self.read(scalar, &at)));
+                });
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    /// Writes a `TryGet` method for every variant of the union field `f` which fails unless the
+    /// discriminator selects that variant
+    fn write_variant_getters(&self, w: &mut CodeWriter, f: &Field, ut: &UnionType) {
+        let union = self
+            .protocol
+            .union(&ut.name)
+            .expect("the union of a resolved field is declared");
+        let member = member_name(&f.name);
+        let discriminator = member_name(&ut.discriminator);
+        let kind = type_name(&union.discriminator_type);
+        for v in &union.variants {
+            let variant = member_name(&v.name);
+            let (value_type, params, value) = match &v.field_type {
+                FieldType_::Scalar(s) => (csharp_type(s).to_string(), "", variant.clone()),
+                FieldType_::Enum(et) => (type_name(&et.name), "", variant.clone()),
+                FieldType_::Struct(StructType { name, .. })
+                | FieldType_::Union(UnionType { name, .. }) => {
+                    (format!("{}View", type_name(name)), "", variant.clone())
+                }
+                // the only reference type, it's null when the method returns false
+                FieldType_::Vector(ArrayLike::AsciiString { .. }) => (
+                    String::from("string?"),
+                    "[NotNullWhen(true)] ",
+                    variant.clone(),
+                ),
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                    (String::from("ReadOnlySpan<byte>"), "", variant.clone())
+                }
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => (
+                    csharp_type(scalar).to_string(),
+                    "int index, ",
+                    format!("Get{}(index)", variant),
+                ),
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            };
+            w.blank()
+                .line(format!(
+                    "/// <summary>{}.{}, false unless {} is {}</summary>",
+                    member, value, discriminator, v.selector
+                ))
+                .line(format!(
+                    "public bool TryGet{}{}({}out {} value)",
+                    member, variant, params, value_type
+                ))
+                .block("{", "}", |w| {
+                    w.line(format!(
+                        "if ({} != {}.{})",
+                        discriminator,
+                        kind,
+                        pascal_case(&v.selector)
+                    ))
+                    .block("{", "}", |w| {
+                        w.line("value = default;").line("return false;");
+                    })
+                    .line(format!("value = {}.{};", member, value))
+                    .line("return true;");
+                });
+        }
+    }
+
+    fn write_setter(&self, w: &mut CodeWriter, view_type: &str, f: &Field) {
+        let member = member_name(&f.name);
+        let o = f.offset;
+        let size = f.field_type.size_bytes();
+        w.line(Self::doc(f));
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                w.line(format!("public {} {}", csharp_type(s), member))
+                    .block("{", "}", |w| {
+                        w.line(format!("get => AsView().{};", member))
+                            .line(format!("set => {}", self.write(s, &o.to_string(), "value")));
+                    });
+                if let Some((ty, suffix, _, write)) = native_time(s) {
+                    w.blank()
+                        .line(format!("/// <summary>{} as a {}</summary>", f.name, ty))
+                        .line(format!("public {} {}{}", ty, member, suffix))
+                        .block("{", "}", |w| {
+                            w.line(format!("get => AsView().{}{};", member, suffix))
+                                .line(format!("set => {} = {};", member, write));
+                        });
+                }
+                if let ScalarType::Decimal(d) = s {
+                    let factor = decimal_factor(d.exponent);
+                    w.blank()
+                        .line(format!("/// <summary>{} as a decimal</summary>", f.name))
+                        .line(
+                            "/// <exception cref=\"ArgumentException\">The value isn't a multiple of the field's scale</exception>",
+                        )
+                        .line(
+                            "/// <exception cref=\"OverflowException\">The mantissa doesn't fit the field</exception>",
+                        )
+                        .line(format!("public decimal {}Decimal", member))
+                        .block("{", "}", |w| {
+                            w.line(format!("get => AsView().{}Decimal;", member))
+                                .line("set")
+                                .block("{", "}", |w| {
+                                    w.line(format!("var mantissa = value / {};", factor))
+                                        .line("if (mantissa != decimal.Truncate(mantissa))")
+                                        .block("{", "}", |w| {
+                                            w.line(format!(
+                                                "throw new ArgumentException($\"{} only holds multiples of {}, not {{value}}\", nameof(value));",
+                                                f.name,
+                                                factor.trim_end_matches('m')
+                                            ));
+                                        })
+                                        .line(format!(
+                                            "{} = checked(({})mantissa);",
+                                            member,
+                                            csharp_type(s)
+                                        ));
+                                });
+                        });
+                }
+            }
+            FieldType_::Enum(et) => {
+                let value = format!("({})value", csharp_type(&et.underlying));
+                w.line(format!("public {} {}", type_name(&et.name), member))
+                    .block("{", "}", |w| {
+                        w.line(format!("get => AsView().{};", member)).line(format!(
+                            "set => {}",
+                            self.write(&et.underlying, &o.to_string(), &value)
+                        ));
+                    });
+            }
+            FieldType_::Struct(st) => {
+                w.line(format!(
+                    "public {}Writer {} => new {}Writer(_buffer.Slice({}, {}));",
+                    type_name(&st.name),
+                    member,
+                    type_name(&st.name),
+                    o,
+                    size
+                ));
+            }
+            FieldType_::Union(ut) => {
+                w.line(format!(
+                    "public {}Writer {} => new {}Writer(_buffer.Slice({}, {}));",
+                    type_name(&ut.name),
+                    member,
+                    type_name(&ut.name),
+                    o,
+                    size
+                ));
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                w.line("/// <exception cref=\"ArgumentException\">The value is too long or isn't ascii</exception>")
+                    .line(format!("public string {}", member))
+                    .block("{", "}", |w| {
+                        w.line(format!("get => AsView().{};", member)).line(format!(
+                            "set => AsciiField.Write(_buffer.Slice({}, {}), {}, value, \"{}\");",
+                            o,
+                            size,
+                            pad_byte(*padding),
+                            f.name
+                        ));
+                    });
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                w.line(format!(
+                    "public Span<byte> {} => _buffer.Slice({}, {});",
+                    member, o, size
+                ));
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                w.line(format!(
+                    "public {} Get{}(int index) => AsView().Get{}(index);",
+                    csharp_type(scalar),
+                    member,
+                    member
+                ))
+                .blank()
+                .line(format!(
+                    "public void Set{}(int index, {} value)",
+                    member,
+                    csharp_type(scalar)
+                ))
+                .block("{", "}", |w| {
+                    w.line(format!(
+                        "if ((uint)index >= {}.{}Length)",
+                        view_type, member
+                    ))
+                    .block("{", "}", |w| {
+                        w.line("throw new ArgumentOutOfRangeException(nameof(index));");
+                    })
+                    .line(self.write(
+                        scalar,
+                        &format!("{} + index * {}", o, scalar.size_bytes()),
+                        "value",
+                    ));
+                });
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::csharp::CSharpGenerator;
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+
+    fn options(namespace: &str) -> GeneratorOptions {
+        GeneratorOptions {
+            parameters: vec![(String::from("namespace"), String::from(namespace))],
+        }
+    }
+
+    #[test]
+    fn test_namespace() {
+        let protocol = crate::sssc::compile("protocol foo.bar; message M [1] { u8: a; }").unwrap();
+        let files = CSharpGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!("FooBar.cs", files[0].path.to_str().unwrap());
+        assert!(files[0].contents.contains("namespace Foo.Bar\n"));
+
+        let files = CSharpGenerator
+            .generate(&protocol, &options("Example.Risk"))
+            .unwrap();
+        assert!(files[0].contents.contains("namespace Example.Risk\n"));
+        assert!(matches!(
+            CSharpGenerator.generate(&protocol, &options("Example.class")),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+    }
+
+    #[test]
+    fn test_big_endian_and_reserved_members() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [1] { u16: size; decimal<i32, -2>: price; }",
+        )
+        .unwrap();
+        let contents = &CSharpGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()[0]
+            .contents;
+        assert!(contents.contains(
+            "public ushort Size_ => BinaryPrimitives.ReadUInt16BigEndian(_buffer.Slice(0));"
+        ));
+        assert!(contents.contains("/// <summary>decimal&lt;i32, -2&gt; at offset 2</summary>"));
+    }
+}
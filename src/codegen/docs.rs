@@ -0,0 +1,760 @@
+//! Generates a protocol specification to hand to counterparties, as GitHub flavoured Markdown and a
+//! self-contained HTML page. Both are written from the same outline: a table of fields per
+//! message with a byte-layout diagram, the header framing them if the protocol has one, the
+//! structs and unions they contain, enum value tables and a changelog.
+//!
+//! The description of a field is its `///` doc comment. Protocols carry no history of their own,
+//! the changelog is read from the file named by the `changelog` option and included as is.
+
+use crate::codegen::{
+    file_header, type_order, CodeWriter, Generator, GeneratorError, GeneratorOptions, OutputFile,
+    TypeDeclaration,
+};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_};
+use crate::protocol::{AlignmentMode, ByteOrder, Enum, Header, Layout, Member, Protocol, Union};
+
+/// Bytes drawn on each row of a layout diagram
+const ROW_BYTES: usize = 8;
+
+/// Characters inside the cell of a single byte in a layout diagram
+const CELL_WIDTH: usize = 7;
+
+const STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #1f2328; line-height: 1.5; }
+h1, h2 { border-bottom: 1px solid #d1d9e0; padding-bottom: .3em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #d1d9e0; padding: .3em .8em; text-align: left; }
+th { background: #f6f8fa; }
+td.number { text-align: right; }
+code, pre { font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 90%; }
+code { background: #f6f8fa; padding: .1em .3em; border-radius: 4px; }
+pre { background: #f6f8fa; padding: 1em; overflow-x: auto; }"#;
+
+/// Generates `<protocol>.md` and `<protocol>.html`, options: `format` to write only `markdown` or
+/// `html`, and `changelog` the path of a Markdown file to include as the changelog
+pub struct DocsGenerator;
+
+impl Generator for DocsGenerator {
+    fn name(&self) -> &str {
+        "docs"
+    }
+
+    fn description(&self) -> &str {
+        "Protocol documentation as Markdown and HTML, options: format, changelog"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&["format", "changelog"])?;
+
+        let (markdown, html) = match options.get("format") {
+            None => (true, true),
+            Some("markdown") => (true, false),
+            Some("html") => (false, true),
+            Some(other) => {
+                return Err(GeneratorError::InvalidOption {
+                    name: String::from("format"),
+                    reason: format!("expected markdown or html but got '{}'", other),
+                })
+            }
+        };
+        let changelog =
+            match options.get("changelog") {
+                None => None,
+                Some(path) => Some(std::fs::read_to_string(path).map_err(|e| {
+                    GeneratorError::InvalidOption {
+                        name: String::from("changelog"),
+                        reason: format!("could not read '{}': {}", path, e),
+                    }
+                })?),
+            };
+
+        let document = Document {
+            protocol,
+            changelog: changelog.as_deref(),
+        };
+        let name = protocol.name.join("_");
+        let mut files = vec![];
+        if markdown {
+            let mut out = Markdown::new(protocol);
+            document.write(&mut out);
+            files.push(OutputFile::new(format!("{}.md", name), out.w.finish()));
+        }
+        if html {
+            let mut out = Html::new(protocol);
+            document.write(&mut out);
+            files.push(OutputFile::new(format!("{}.html", name), out.finish()));
+        }
+        Ok(files)
+    }
+}
+
+/// A piece of text inside a paragraph or a table cell
+enum Span {
+    Text(String),
+    Code(String),
+    /// Text linking to the section with the given anchor
+    Link(String, String),
+}
+
+type Cell = Vec<Span>;
+
+fn text(text: impl Into<String>) -> Cell {
+    vec![Span::Text(text.into())]
+}
+
+fn code(text: impl Into<String>) -> Cell {
+    vec![Span::Code(text.into())]
+}
+
+/// Ids of sections are the lowercased name, as GitHub makes them from a heading
+fn anchor(name: &str) -> String {
+    name.to_lowercase()
+}
+
+/// The output format a [`Document`] is written to
+trait Format {
+    fn heading(&mut self, level: usize, text: &str);
+    fn paragraph(&mut self, spans: &[Span]);
+    /// `numeric` columns are right aligned
+    fn table(&mut self, header: &[(&str, bool)], rows: &[Vec<Cell>]);
+    fn preformatted(&mut self, text: &str);
+    /// The contents of the changelog file, which is Markdown
+    fn changelog(&mut self, text: &str);
+}
+
+struct Document<'p> {
+    protocol: &'p Protocol,
+    changelog: Option<&'p str>,
+}
+
+impl Document<'_> {
+    fn write(&self, out: &mut dyn Format) {
+        let protocol = self.protocol;
+        out.heading(1, &protocol.qualified_name());
+        out.paragraph(&[Span::Text(format!(
+            "Multi-byte values are {}. {}",
+            match protocol.byte_order {
+                ByteOrder::Little => "little-endian",
+                ByteOrder::Big => "big-endian",
+            },
+            match protocol.alignment {
+                AlignmentMode::Packed => "Fields are packed, each directly follows the previous one.",
+                AlignmentMode::Natural => {
+                    "Fields are padded to sit on their natural alignment, as a C compiler would lay them out."
+                }
+            }
+        ))]);
+
+        if let Some(header) = &protocol.header {
+            self.write_header(out, header);
+        }
+
+        out.heading(2, "Messages");
+        let mut columns = vec![("Message", false), ("Id", true), ("Size", true)];
+        if protocol.header.is_some() {
+            columns.push(("Frame size", true));
+        }
+        out.table(
+            &columns,
+            &protocol
+                .messages
+                .iter()
+                .map(|m| {
+                    let mut row = vec![
+                        vec![Span::Link(m.name.clone(), anchor(&m.name))],
+                        text(m.id.to_string()),
+                        text(m.layout.size.to_string()),
+                    ];
+                    if let Some(header) = &protocol.header {
+                        row.push(text((header.layout.size + m.layout.size).to_string()));
+                    }
+                    row
+                })
+                .collect::<Vec<_>>(),
+        );
+        for m in &protocol.messages {
+            out.heading(3, &m.name);
+            out.paragraph(&[Span::Text(format!("Id {}, {} bytes.", m.id, m.layout.size))]);
+            self.write_layout(out, &m.layout);
+        }
+
+        let types = type_order(protocol);
+        if !types.is_empty() {
+            out.heading(2, "Types");
+            for declaration in types {
+                match declaration {
+                    TypeDeclaration::Struct(s) => {
+                        out.heading(3, &s.name);
+                        out.paragraph(&[Span::Text(format!(
+                            "Struct, {} bytes aligned to {}.",
+                            s.layout.size, s.layout.alignment
+                        ))]);
+                        self.write_layout(out, &s.layout);
+                    }
+                    TypeDeclaration::Union(u) => self.write_union(out, u),
+                }
+            }
+        }
+
+        if !protocol.enums.is_empty() {
+            out.heading(2, "Enums");
+            for e in &protocol.enums {
+                self.write_enum(out, e);
+            }
+        }
+
+        out.heading(2, "Changelog");
+        match self.changelog {
+            Some(changelog) => out.changelog(changelog),
+            None => out.paragraph(&[Span::Text(String::from("No changes recorded."))]),
+        }
+    }
+
+    /// What the header in front of every message holds, then its layout
+    fn write_header(&self, out: &mut dyn Format, header: &Header) {
+        out.heading(2, "Framing");
+        let mut spans = vec![
+            Span::Text(format!(
+                "Every message is preceded by a header of {} bytes. Its ",
+                header.layout.size
+            )),
+            Span::Code(header.id().name.clone()),
+            Span::Text(String::from(" field holds the id of the message")),
+        ];
+        match header.length() {
+            Some(length) => spans.extend([
+                Span::Text(String::from(" and its ")),
+                Span::Code(length.name.clone()),
+                Span::Text(String::from(match header.length_value(0) {
+                    0 => " field the size of the message alone, without the header.",
+                    _ => " field the size of the header and the message together.",
+                })),
+            ]),
+            None => spans.push(Span::Text(String::from(
+                ", whose size gives the size of the frame.",
+            ))),
+        }
+        out.paragraph(&spans);
+        self.write_layout(out, &header.layout);
+    }
+
+    /// The table of members followed by the diagram of which bytes they occupy
+    fn write_layout(&self, out: &mut dyn Format, layout: &Layout) {
+        let rows: Vec<Vec<Cell>> = layout
+            .members
+            .iter()
+            .map(|member| match member {
+                Member::Field(f) => vec![
+                    code(f.name.clone()),
+                    self.type_cell(&f.field_type),
+                    text(f.offset.to_string()),
+                    text(f.size_bytes().to_string()),
+                    text(self.byte_order(&f.field_type)),
+                    text(f.description.clone().unwrap_or_default()),
+                ],
+                Member::Padding(p) => vec![
+                    text("padding"),
+                    vec![],
+                    text(p.offset.to_string()),
+                    text(p.size.to_string()),
+                    vec![],
+                    text(p.reason.to_string()),
+                ],
+            })
+            .collect();
+        out.table(
+            &[
+                ("Field", false),
+                ("Type", false),
+                ("Offset", true),
+                ("Size", true),
+                ("Byte order", false),
+                ("Description", false),
+            ],
+            &rows,
+        );
+        out.preformatted(&diagram(layout));
+    }
+
+    fn write_union(&self, out: &mut dyn Format, u: &Union) {
+        out.heading(3, &u.name);
+        out.paragraph(&[
+            Span::Text(format!(
+                "Union, {} bytes aligned to {}. Every variant starts at the first byte, which one is present is given by a ",
+                u.size, u.alignment
+            )),
+            Span::Link(u.discriminator_type.clone(), anchor(&u.discriminator_type)),
+            Span::Text(String::from(" field.")),
+        ]);
+        out.table(
+            &[
+                ("Variant", false),
+                ("When", false),
+                ("Type", false),
+                ("Size", true),
+                ("Byte order", false),
+            ],
+            &u.variants
+                .iter()
+                .map(|v| {
+                    vec![
+                        code(v.name.clone()),
+                        code(v.selector.clone()),
+                        self.type_cell(&v.field_type),
+                        text(v.field_type.size_bytes().to_string()),
+                        text(self.byte_order(&v.field_type)),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    fn write_enum(&self, out: &mut dyn Format, e: &Enum) {
+        out.heading(3, &e.name);
+        let width = e.underlying.size_bytes() * 2;
+        match e.kind {
+            EnumKind::Enum => {
+                out.paragraph(&[
+                    Span::Text(String::from("Enum stored as ")),
+                    Span::Code(e.underlying.to_string()),
+                    Span::Text(String::from(".")),
+                ]);
+                out.table(
+                    &[("Name", false), ("Value", true)],
+                    &e.values
+                        .iter()
+                        .map(|v| vec![code(v.name.clone()), text(v.value.to_string())])
+                        .collect::<Vec<_>>(),
+                );
+            }
+            EnumKind::Bitflags => {
+                out.paragraph(&[
+                    Span::Text(String::from("Bitflags stored as ")),
+                    Span::Code(e.underlying.to_string()),
+                    Span::Text(String::from(", any combination of the flags may be set.")),
+                ]);
+                out.table(
+                    &[("Flag", false), ("Value", true)],
+                    &e.values
+                        .iter()
+                        .map(|v| {
+                            vec![
+                                code(v.name.clone()),
+                                code(format!("0x{:0width$x}", v.value, width = width)),
+                            ]
+                        })
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+    }
+
+    /// Types declared in the protocol link to their own section
+    fn type_cell(&self, field_type: &FieldType_) -> Cell {
+        match field_type {
+            FieldType_::Struct(st) => vec![Span::Link(st.name.clone(), anchor(&st.name))],
+            FieldType_::Union(ut) => vec![Span::Link(ut.name.clone(), anchor(&ut.name))],
+            FieldType_::Enum(et) => vec![Span::Link(et.name.clone(), anchor(&et.name))],
+            other => code(other.to_string()),
+        }
+    }
+
+    /// Only values wider than a byte have a byte order, structs and unions give their own
+    fn byte_order(&self, field_type: &FieldType_) -> String {
+        let value_size = match field_type {
+            FieldType_::Scalar(s) => s.size_bytes(),
+            FieldType_::Enum(e) => e.underlying.size_bytes(),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => scalar.size_bytes(),
+            _ => 0,
+        };
+        if value_size < 2 {
+            return String::new();
+        }
+        String::from(match self.protocol.byte_order {
+            ByteOrder::Little => "little-endian",
+            ByteOrder::Big => "big-endian",
+        })
+    }
+}
+
+/// Draws the bytes of `layout` as rows of cells, a member spans the cells of the bytes it occupies
+/// and is labelled in the first of them, e.g.
+///
+/// ```text
+///           0       1       2       3
+///       +-------+-------+-------+-------+
+///     0 |  side |padding|   quantity    |
+///       +-------+-------+---------------+
+/// ```
+fn diagram(layout: &Layout) -> String {
+    let size = layout.size;
+    if size == 0 {
+        return String::from("(no bytes)\n");
+    }
+
+    // the index of the member each byte belongs to
+    let mut owner = vec![None; size];
+    for (i, member) in layout.members.iter().enumerate() {
+        let end = (member.offset() + member.size_bytes()).min(size);
+        for byte in &mut owner[member.offset()..end] {
+            *byte = Some(i);
+        }
+    }
+    let label = |i: Option<usize>| match i.map(|i| &layout.members[i]) {
+        Some(Member::Field(f)) => f.name.as_str(),
+        Some(Member::Padding(_)) => "padding",
+        None => "",
+    };
+    let rows = size.div_ceil(ROW_BYTES);
+    let columns = |row: usize| (size - row * ROW_BYTES).min(ROW_BYTES);
+    let byte = |row: usize, column: usize| owner[row * ROW_BYTES + column];
+    // whether a `|` separates the byte before `boundary` on `row` from the one after it
+    let vertical = |row: usize, boundary: usize| {
+        let columns = columns(row);
+        boundary == 0
+            || boundary == columns
+            || (boundary < columns && byte(row, boundary - 1) != byte(row, boundary))
+    };
+    // the line drawn between two rows, or above the first and below the last
+    let border = |above: Option<usize>, below: Option<usize>| {
+        let width = above
+            .into_iter()
+            .chain(below)
+            .map(columns)
+            .max()
+            .unwrap_or(0);
+        let dash = |column: usize| {
+            let above = above
+                .filter(|r| column < columns(*r))
+                .map(|r| byte(r, column));
+            let below = below
+                .filter(|r| column < columns(*r))
+                .map(|r| byte(r, column));
+            match (above, below) {
+                (Some(a), Some(b)) => a != b,
+                _ => true,
+            }
+        };
+        let mut line = String::from("      ");
+        for boundary in 0..=width {
+            let crossed = above.is_some_and(|r| boundary <= columns(r) && vertical(r, boundary))
+                || below.is_some_and(|r| boundary <= columns(r) && vertical(r, boundary));
+            let joined =
+                (boundary > 0 && dash(boundary - 1)) || (boundary < width && dash(boundary));
+            line.push(match (crossed, joined) {
+                (true, _) => '+',
+                (false, true) => '-',
+                (false, false) => ' ',
+            });
+            if boundary < width {
+                let fill = if dash(boundary) { "-" } else { " " };
+                line.push_str(&fill.repeat(CELL_WIDTH));
+            }
+        }
+        line
+    };
+
+    let mut lines = vec![];
+    let mut header = String::from("       ");
+    for column in 0..columns(0) {
+        header.push_str(&format!("{:^width$} ", column, width = CELL_WIDTH));
+    }
+    lines.push(String::from(header.trim_end()));
+    lines.push(border(None, Some(0)));
+    for row in 0..rows {
+        let mut line = format!("{:>4}  |", row * ROW_BYTES);
+        let mut column = 0;
+        while column < columns(row) {
+            let start = column;
+            let member = byte(row, column);
+            while column < columns(row) && byte(row, column) == member {
+                column += 1;
+            }
+            let width = (column - start) * (CELL_WIDTH + 1) - 1;
+            // a member carrying on directly below its bytes on the row above is left blank
+            let continued =
+                row > 0 && member.is_some() && (start..column).any(|c| byte(row - 1, c) == member);
+            let text: String = if continued {
+                String::new()
+            } else {
+                label(member).chars().take(width).collect()
+            };
+            line.push_str(&format!("{:^width$}|", text, width = width));
+        }
+        lines.push(line);
+        lines.push(border(Some(row), (row + 1 < rows).then_some(row + 1)));
+    }
+    let mut diagram = lines.join("\n");
+    diagram.push('\n');
+    diagram
+}
+
+/// Escapes the characters Markdown would otherwise treat as formatting
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+struct Markdown {
+    w: CodeWriter,
+}
+
+impl Markdown {
+    fn new(protocol: &Protocol) -> Self {
+        let mut w = CodeWriter::new("");
+        w.line(format!("<!-- {} -->", file_header("", protocol).trim()))
+            .blank();
+        Markdown { w }
+    }
+
+    fn spans(spans: &[Span]) -> String {
+        spans
+            .iter()
+            .map(|span| match span {
+                Span::Text(t) => markdown_escape(t),
+                Span::Code(c) => format!("`{}`", c),
+                Span::Link(t, anchor) => format!("[{}](#{})", markdown_escape(t), anchor),
+            })
+            .collect()
+    }
+}
+
+impl Format for Markdown {
+    fn heading(&mut self, level: usize, text: &str) {
+        self.w
+            .line(format!("{} {}", "#".repeat(level), markdown_escape(text)))
+            .blank();
+    }
+
+    fn paragraph(&mut self, spans: &[Span]) {
+        self.w.line(Self::spans(spans)).blank();
+    }
+
+    fn table(&mut self, header: &[(&str, bool)], rows: &[Vec<Cell>]) {
+        self.w.line(format!(
+            "| {} |",
+            header
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+        self.w.line(format!(
+            "|{}|",
+            header
+                .iter()
+                .map(|(_, numeric)| if *numeric { "---:" } else { "---" })
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+        for row in rows {
+            self.w.line(format!(
+                "| {} |",
+                row.iter()
+                    .map(|cell| Self::spans(cell))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ));
+        }
+        self.w.blank();
+    }
+
+    fn preformatted(&mut self, text: &str) {
+        self.w
+            .line("```text")
+            .lines(text.trim_end())
+            .line("```")
+            .blank();
+    }
+
+    fn changelog(&mut self, text: &str) {
+        self.w.lines(text.trim_end()).blank();
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct Html {
+    w: CodeWriter,
+}
+
+impl Html {
+    fn new(protocol: &Protocol) -> Self {
+        let mut w = CodeWriter::new("  ");
+        w.line("<!DOCTYPE html>")
+            .line(format!("<!-- {} -->", file_header("", protocol).trim()))
+            .line("<html lang=\"en\">")
+            .line("<head>")
+            .indent()
+            .line("<meta charset=\"utf-8\">")
+            .line(format!(
+                "<title>{}</title>",
+                html_escape(&protocol.qualified_name())
+            ))
+            .line("<style>")
+            .lines(STYLE)
+            .line("</style>")
+            .dedent()
+            .line("</head>")
+            .line("<body>")
+            .indent();
+        Html { w }
+    }
+
+    fn spans(spans: &[Span]) -> String {
+        spans
+            .iter()
+            .map(|span| match span {
+                Span::Text(t) => html_escape(t),
+                Span::Code(c) => format!("<code>{}</code>", html_escape(c)),
+                Span::Link(t, anchor) => format!("<a href=\"#{}\">{}</a>", anchor, html_escape(t)),
+            })
+            .collect()
+    }
+
+    fn finish(mut self) -> String {
+        self.w.dedent().line("</body>").line("</html>");
+        self.w.finish()
+    }
+}
+
+impl Format for Html {
+    fn heading(&mut self, level: usize, text: &str) {
+        self.w.line(format!(
+            "<h{l} id=\"{}\">{}</h{l}>",
+            anchor(text),
+            html_escape(text),
+            l = level
+        ));
+    }
+
+    fn paragraph(&mut self, spans: &[Span]) {
+        self.w.line(format!("<p>{}</p>", Self::spans(spans)));
+    }
+
+    fn table(&mut self, header: &[(&str, bool)], rows: &[Vec<Cell>]) {
+        self.w.block("<table>", "</table>", |w| {
+            w.line(format!(
+                "<tr>{}</tr>",
+                header
+                    .iter()
+                    .map(|(name, _)| format!("<th>{}</th>", html_escape(name)))
+                    .collect::<String>()
+            ));
+            for row in rows {
+                w.line(format!(
+                    "<tr>{}</tr>",
+                    row.iter()
+                        .zip(header)
+                        .map(|(cell, (_, numeric))| {
+                            let class = if *numeric { " class=\"number\"" } else { "" };
+                            format!("<td{}>{}</td>", class, Self::spans(cell))
+                        })
+                        .collect::<String>()
+                ));
+            }
+        });
+    }
+
+    fn preformatted(&mut self, text: &str) {
+        // only the first line is indented, the rest of a <pre> is kept as is
+        self.w
+            .line(format!("<pre>{}</pre>", html_escape(text.trim_end())));
+    }
+
+    fn changelog(&mut self, text: &str) {
+        self.w.line(format!(
+            "<pre class=\"changelog\">{}</pre>",
+            html_escape(text.trim_end())
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::docs::{diagram, DocsGenerator};
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+
+    fn options(parameters: &[(&str, &str)]) -> GeneratorOptions {
+        GeneratorOptions {
+            parameters: parameters
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diagram() {
+        let protocol =
+            crate::sssc::compile("protocol foo; message M [1] { u8: a; 1; u16: b; u64: c; }")
+                .unwrap();
+        let expected = "          0       1       2       3       4       5       6       7
+      +-------+-------+---------------+-------------------------------+
+   0  |   a   |padding|       b       |               c               |
+      +-------+-------+---------------+-------------------------------+
+   8  |               c               |
+      +-------------------------------+
+";
+        assert_eq!(expected, diagram(&protocol.messages[0].layout));
+    }
+
+    #[test]
+    fn test_options() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [1] { u16: a; u8: b; }",
+        )
+        .unwrap();
+        let files = DocsGenerator
+            .generate(&protocol, &options(&[("format", "markdown")]))
+            .unwrap();
+        assert_eq!(1, files.len());
+        assert_eq!("foo.md", files[0].path.to_str().unwrap());
+        assert!(files[0]
+            .contents
+            .contains("| `a` | `u16` | 0 | 2 | big-endian |  |"));
+        assert!(files[0].contents.contains("| `b` | `u8` | 2 | 1 |  |  |"));
+
+        let changelog = std::env::temp_dir().join("sss_docs_changelog.md");
+        std::fs::write(&changelog, "* Added <b>\n").unwrap();
+        let files = DocsGenerator
+            .generate(
+                &protocol,
+                &options(&[
+                    ("format", "html"),
+                    ("changelog", changelog.to_str().unwrap()),
+                ]),
+            )
+            .unwrap();
+        assert_eq!("foo.html", files[0].path.to_str().unwrap());
+        assert!(files[0]
+            .contents
+            .contains("<pre class=\"changelog\">* Added &lt;b&gt;</pre>"));
+
+        assert!(matches!(
+            DocsGenerator.generate(&protocol, &options(&[("format", "pdf")])),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+        assert!(matches!(
+            DocsGenerator.generate(&protocol, &options(&[("changelog", "/does/not/exist")])),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+    }
+}
@@ -0,0 +1,1213 @@
+//! Generates a Go package per protocol. Every type gets a plain Go type, which implements
+//! `encoding.BinaryMarshaler` and `encoding.BinaryUnmarshaler`, and an `XView` over a `[]byte`
+//! reading and writing it in place through `encoding/binary`.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use crate::codegen::{
+    snake_case, type_order, words, CodeWriter, Generator, GeneratorError, GeneratorOptions,
+    Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{Mantissa, TimeUnit};
+use crate::data_types::{
+    ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, StructType, UnionType,
+};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol, Union};
+
+/// Only checked against the package name, every other name generated is exported
+const KEYWORDS: Keywords = Keywords(&[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+]);
+
+/// Methods of the generated types which a field of the same name would collide with
+const RESERVED_MEMBERS: Keywords = Keywords(&[
+    "MarshalBinary",
+    "UnmarshalBinary",
+    "MarshalFrame",
+    "View",
+    "String",
+    "Has",
+]);
+
+/// Words Go spells in capitals wherever they appear in a name, the list `golint` checks
+const INITIALISMS: &[&str] = &[
+    "acl", "api", "ascii", "cpu", "css", "dns", "eof", "guid", "html", "http", "https", "id", "ip",
+    "json", "lhs", "qps", "ram", "rhs", "rpc", "sla", "smtp", "sql", "ssh", "tcp", "tls", "ttl",
+    "udp", "ui", "uid", "uuid", "uri", "url", "utf8", "vm", "xml", "xmpp", "xsrf", "xss",
+];
+
+/// The type generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Generates `<package>/<protocol>.go`, the package is named after the last part of the protocol
+/// name unless set with the `package` option
+pub struct GoGenerator;
+
+impl Generator for GoGenerator {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn description(&self) -> &str {
+        "Go package with BinaryMarshaler types and []byte views, options: package"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&["package"])?;
+
+        let package = match options.get("package") {
+            Some(package) => package.to_string(),
+            None => protocol
+                .name
+                .last()
+                .map(|n| n.to_lowercase().replace('_', ""))
+                .unwrap_or_default(),
+        };
+        let mut chars = package.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !KEYWORDS.is_keyword(&package);
+        if !valid {
+            return Err(GeneratorError::InvalidOption {
+                name: String::from("package"),
+                reason: format!("'{}' is not a Go package name", package),
+            });
+        }
+
+        let file = GoFile {
+            protocol,
+            package: &package,
+            imports: RefCell::new(BTreeSet::from(["encoding/binary", "fmt", "io"])),
+        };
+        let contents = file.generate()?;
+        Ok(vec![OutputFile::new(
+            format!("{}/{}.go", package, snake_case(&protocol.name.join("_"))),
+            contents,
+        )])
+    }
+}
+
+struct GoFile<'p> {
+    protocol: &'p Protocol,
+    package: &'p str,
+    /// Filled in as the body is written, Go refuses to compile unused imports
+    imports: RefCell<BTreeSet<&'static str>>,
+}
+
+/// Pascal case with the initialisms in capitals, `message_id` becomes `MessageID`
+fn go_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| {
+            if INITIALISMS.contains(&w.as_str()) {
+                w.to_uppercase()
+            } else {
+                let mut chars = w.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        })
+        .collect()
+}
+
+fn type_name(name: &str) -> String {
+    go_case(name)
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&go_case(name))
+}
+
+fn storage(scalar: &ScalarType) -> ScalarType {
+    match scalar {
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => ScalarType::Unsigned(u),
+            Mantissa::Signed(s) => ScalarType::Signed(s),
+        },
+        ScalarType::Timestamp(t) => t.storage(),
+        ScalarType::Duration(d) => d.storage(),
+        other => *other,
+    }
+}
+
+fn go_type(scalar: &ScalarType) -> &'static str {
+    match storage(scalar) {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "uint8",
+            2 => "uint16",
+            4 => "uint32",
+            _ => "uint64",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "int8",
+            2 => "int16",
+            4 => "int32",
+            _ => "int64",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "float32",
+            _ => "float64",
+        },
+        _ => "byte",
+    }
+}
+
+fn time_unit(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Seconds => "time.Second",
+        TimeUnit::Millis => "time.Millisecond",
+        TimeUnit::Micros => "time.Microsecond",
+        TimeUnit::Nanos => "time.Nanosecond",
+    }
+}
+
+/// Pads the names so the values line up, the way gofmt aligns consecutive lines of a block
+fn aligned(rows: &[(String, String)], separator: &str) -> Vec<String> {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(name, value)| format!("{:width$}{}{}", name, separator, value, width = width))
+        .collect()
+}
+
+impl GoFile<'_> {
+    fn import(&self, package: &'static str) {
+        self.imports.borrow_mut().insert(package);
+    }
+
+    /// Reads the scalar at `at` from the view `v`
+    fn read(&self, scalar: &ScalarType, at: &str) -> String {
+        let bits = scalar.size_bytes() * 8;
+        match (storage(scalar), go_type(scalar)) {
+            (_, "uint8") | (_, "byte") => format!("v[{}]", at),
+            (_, "int8") => format!("int8(v[{}])", at),
+            (ScalarType::Float(_), ty) => {
+                self.import("math");
+                format!(
+                    "math.Float{}frombits(byteOrder.Uint{}(v[{}:]))",
+                    &ty[5..],
+                    bits,
+                    at
+                )
+            }
+            (ScalarType::Signed(_), ty) => format!("{}(byteOrder.Uint{}(v[{}:]))", ty, bits, at),
+            _ => format!("byteOrder.Uint{}(v[{}:])", bits, at),
+        }
+    }
+
+    /// Writes `value` as the scalar at `at` of the view `v`
+    fn write(&self, scalar: &ScalarType, at: &str, value: &str) -> String {
+        let bits = scalar.size_bytes() * 8;
+        match (storage(scalar), go_type(scalar)) {
+            (_, "uint8") | (_, "byte") => format!("v[{}] = {}", at, value),
+            (_, "int8") => format!("v[{}] = byte({})", at, value),
+            (ScalarType::Float(_), _) => {
+                self.import("math");
+                format!(
+                    "byteOrder.PutUint{}(v[{}:], math.Float{}bits({}))",
+                    bits, at, bits, value
+                )
+            }
+            (ScalarType::Signed(_), _) => format!(
+                "byteOrder.PutUint{}(v[{}:], uint{}({}))",
+                bits, at, bits, value
+            ),
+            _ => format!("byteOrder.PutUint{}(v[{}:], {})", bits, at, value),
+        }
+    }
+
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let mut body = CodeWriter::new("\t");
+        body.line(format!(
+            "var byteOrder = binary.{}",
+            match protocol.byte_order {
+                ByteOrder::Little => "LittleEndian",
+                ByteOrder::Big => "BigEndian",
+            }
+        ))
+        .blank()
+        .line("// shortBuffer is returned when a buffer is too short for the type read from it.")
+        .block(
+            "func shortBuffer(name string, size, length int) error {",
+            "}",
+            |w| {
+                w.line(format!(
+                    "return fmt.Errorf(\"{}: %s needs %d bytes but the buffer holds %d: %w\", name, size, length, io.ErrUnexpectedEOF)",
+                    self.package
+                ));
+            },
+        );
+        if self.has_ascii() {
+            body.blank()
+                .line("// getASCII returns the text of an ascii field, which ends at the first nul byte, without")
+                .line("// the pad bytes ending it.")
+                .block("func getASCII(b []byte, pad byte) string {", "}", |w| {
+                    w.line("n := 0")
+                        .block("for n < len(b) && b[n] != 0 {", "}", |w| {
+                            w.line("n++");
+                        })
+                        .block("for n > 0 && b[n-1] == pad {", "}", |w| {
+                            w.line("n--");
+                        })
+                        .line("return string(b[:n])");
+                })
+                .blank()
+                .line("// setASCII writes s to an ascii field, followed by pad bytes.")
+                .block("func setASCII(b []byte, pad byte, s, name string) error {", "}", |w| {
+                    w.block("if len(s) > len(b) {", "}", |w| {
+                        w.line(format!(
+                            "return fmt.Errorf(\"{}: %s holds at most %d characters but the value has %d\", name, len(b), len(s))",
+                            self.package
+                        ));
+                    })
+                    .block("for i := 0; i < len(s); i++ {", "}", |w| {
+                        w.block("if s[i] > 0x7f {", "}", |w| {
+                            w.line(format!(
+                                "return fmt.Errorf(\"{}: %s only holds ascii characters\", name)",
+                                self.package
+                            ));
+                        });
+                    })
+                    .block("for i := copy(b, s); i < len(b); i++ {", "}", |w| {
+                        w.line("b[i] = pad");
+                    })
+                    .line("return nil");
+                });
+        }
+
+        for e in &protocol.enums {
+            body.blank();
+            self.write_enum(&mut body, e)?;
+        }
+        for declaration in type_order(protocol) {
+            match declaration {
+                TypeDeclaration::Struct(s) => {
+                    self.write_type(&mut body, &s.name, None, &s.layout, None)
+                }
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    self.write_type(&mut body, &u.name, None, &layout, Some(u))
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            self.write_type(&mut body, FRAME_HEADER, None, &header.layout, None);
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a uint32",
+                    m.name
+                ))
+            })?;
+            self.write_type(&mut body, &m.name, Some(id), &m.layout, None);
+        }
+        if let Some(header) = &protocol.header {
+            self.write_dispatch(&mut body, header);
+        }
+
+        let mut w = CodeWriter::new("\t");
+        w.line(format!(
+            "// Code generated by sssc {} from protocol {}. DO NOT EDIT.",
+            env!("CARGO_PKG_VERSION"),
+            protocol.qualified_name()
+        ))
+        .blank()
+        .line(format!(
+            "// Package {} reads and writes the types of protocol {}.",
+            self.package,
+            protocol.qualified_name()
+        ))
+        .line(format!("package {}", self.package))
+        .blank()
+        .block("import (", ")", |w| {
+            for import in self.imports.borrow().iter() {
+                w.line(format!("\"{}\"", import));
+            }
+        })
+        .blank();
+        Ok(w.finish() + &body.finish())
+    }
+
+    fn has_ascii(&self) -> bool {
+        let ascii = |t: &FieldType_| matches!(t, FieldType_::Vector(ArrayLike::AsciiString { .. }));
+        self.protocol
+            .structs
+            .iter()
+            .any(|s| s.layout.fields().any(|f| ascii(&f.field_type)))
+            || self
+                .protocol
+                .messages
+                .iter()
+                .any(|m| m.layout.fields().any(|f| ascii(&f.field_type)))
+            || self
+                .protocol
+                .unions
+                .iter()
+                .any(|u| u.variants.iter().any(|v| ascii(&v.field_type)))
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let ty = type_name(&e.name);
+        let underlying = go_type(&e.underlying);
+        let kind = match e.kind {
+            EnumKind::Enum => "enum",
+            EnumKind::Bitflags => "bitflags",
+        };
+        let constant = |name: &str| format!("{}{}", ty, go_case(name));
+
+        w.line(format!(
+            "// {} is {} {}, stored as a {}.",
+            ty, kind, e.name, e.underlying
+        ))
+        .line(format!("type {} {}", ty, underlying))
+        .blank();
+        let rows: Vec<(String, String)> = e
+            .values
+            .iter()
+            .map(|v| (constant(&v.name), format!("{} = {}", ty, v.value)))
+            .collect();
+        w.block("const (", ")", |w| {
+            for row in aligned(&rows, " ") {
+                w.line(row);
+            }
+        })
+        .blank();
+
+        match e.kind {
+            EnumKind::Enum => {
+                w.line("// String returns the name of v as declared in the schema.")
+                    .block(format!("func (v {}) String() string {{", ty), "}", |w| {
+                        w.line("switch v {");
+                        for v in &e.values {
+                            w.line(format!("case {}:", constant(&v.name)))
+                                .indent()
+                                .line(format!("return \"{}\"", v.name))
+                                .dedent();
+                        }
+                        w.line("}").line(format!(
+                            "return fmt.Sprintf(\"{}(%d)\", {}(v))",
+                            ty, underlying
+                        ));
+                    });
+            }
+            EnumKind::Bitflags => {
+                self.import("strings");
+                let all = e.values.iter().fold(0, |all, v| all | v.value);
+                w.line("// Has reports whether every flag of flags is set in v.")
+                    .line(format!(
+                        "func (v {}) Has(flags {}) bool {{ return v&flags == flags }}",
+                        ty, ty
+                    ))
+                    .blank()
+                    .line("// String joins the names of the flags set in v with |.")
+                    .block(format!("func (v {}) String() string {{", ty), "}", |w| {
+                        w.line("var names []string");
+                        for v in &e.values {
+                            w.block(format!("if v&{} != 0 {{", constant(&v.name)), "}", |w| {
+                                w.line(format!("names = append(names, \"{}\")", v.name));
+                            });
+                        }
+                        w.block(
+                            format!("if rest := v &^ {:#x}; rest != 0 {{", all),
+                            "}",
+                            |w| {
+                                w.line(format!(
+                                    "names = append(names, fmt.Sprintf(\"%#x\", {}(rest)))",
+                                    underlying
+                                ));
+                            },
+                        )
+                        .line("return strings.Join(names, \"|\")");
+                    });
+            }
+        }
+        Ok(())
+    }
+
+    fn value_type(f: &Field) -> String {
+        match &f.field_type {
+            FieldType_::Scalar(s) => go_type(s).to_string(),
+            FieldType_::Enum(et) => type_name(&et.name),
+            FieldType_::Struct(st) => type_name(&st.name),
+            FieldType_::Union(ut) => type_name(&ut.name),
+            FieldType_::Vector(ArrayLike::AsciiString { .. }) => String::from("string"),
+            FieldType_::Vector(ArrayLike::Bytes { length }) => format!("[{}]byte", length),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                format!("[{}]{}", length, go_type(scalar))
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    fn write_type(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        id: Option<u32>,
+        layout: &Layout,
+        union: Option<&Union>,
+    ) {
+        let ty = type_name(name);
+        let view = format!("{}View", ty);
+        let doc = match (id, union) {
+            (Some(id), _) => format!("message {} [{}]", name, id),
+            (None, Some(u)) => format!("union {}", u.name),
+            (None, None) => format!("struct {}", name),
+        };
+
+        w.blank();
+        match union {
+            Some(u) => {
+                w.line(format!(
+                    "// {} holds {}, the active variant is selected by a {}.",
+                    ty, doc, u.discriminator_type
+                ))
+                .line(format!("type {} [{}Size]byte", ty, ty));
+            }
+            None => {
+                let rows: Vec<(String, String)> = layout
+                    .fields()
+                    .map(|f| (member_name(&f.name), Self::value_type(f)))
+                    .collect();
+                w.line(format!("// {} holds {}.", ty, doc));
+                if rows.is_empty() {
+                    w.line(format!("type {} struct{{}}", ty));
+                } else {
+                    w.block(format!("type {} struct {{", ty), "}", |w| {
+                        for row in aligned(&rows, " ") {
+                            w.line(row);
+                        }
+                    });
+                }
+            }
+        }
+
+        let mut constants = Vec::new();
+        if let Some(id) = id {
+            constants.push((format!("{}ID", ty), id.to_string()));
+        }
+        constants.push((format!("{}Size", ty), layout.size.to_string()));
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        if let Some(header) = frame {
+            constants.push((
+                format!("{}FrameSize", ty),
+                (header.layout.size + layout.size).to_string(),
+            ));
+        }
+        for f in layout.fields() {
+            let prefix = format!("{}{}", ty, go_case(&f.name));
+            constants.push((format!("{}Offset", prefix), f.offset.to_string()));
+            let scalar = match &f.field_type {
+                FieldType_::Scalar(s) => Some(s),
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                    constants.push((format!("{}Length", prefix), length.to_string()));
+                    Some(scalar)
+                }
+                _ => None,
+            };
+            match scalar {
+                Some(ScalarType::Decimal(d)) => {
+                    constants.push((format!("{}Exponent", prefix), d.exponent.to_string()));
+                }
+                Some(ScalarType::Timestamp(t)) => {
+                    self.import("time");
+                    constants.push((format!("{}Unit", prefix), time_unit(t.unit).to_string()));
+                }
+                Some(ScalarType::Duration(d)) => {
+                    self.import("time");
+                    constants.push((format!("{}Unit", prefix), time_unit(d.unit).to_string()));
+                }
+                _ => {}
+            }
+        }
+        w.blank()
+            .line(format!("// Layout of {}.", ty))
+            .block("const (", ")", |w| {
+                for row in aligned(&constants, " = ") {
+                    w.line(row);
+                }
+            });
+
+        w.blank()
+            .line(format!("// {} reads and writes {} in place.", view, doc))
+            .line(format!("type {} []byte", view))
+            .blank()
+            .line(format!(
+                "// New{} views the start of b, which must hold at least {}Size bytes.",
+                view, ty
+            ))
+            .block(
+                format!("func New{}(b []byte) ({}, error) {{", view, view),
+                "}",
+                |w| {
+                    w.block(format!("if len(b) < {}Size {{", ty), "}", |w| {
+                        w.line(format!(
+                            "return nil, shortBuffer(\"{}\", {}Size, len(b))",
+                            name, ty
+                        ));
+                    })
+                    .line(format!("return {}(b[:{}Size]), nil", view, ty));
+                },
+            );
+        for f in layout.fields() {
+            w.blank();
+            self.write_accessors(w, &view, &ty, f);
+            if let FieldType_::Union(ut) = &f.field_type {
+                self.write_variant_accessors(w, &view, f, ut);
+            }
+        }
+
+        w.blank();
+        match union {
+            Some(_) => {
+                w.line("// View reads and writes the variants held by u.")
+                    .line(format!(
+                        "func (u *{}) View() {} {{ return u[:] }}",
+                        ty, view
+                    ))
+                    .blank()
+                    .line(format!(
+                        "// MarshalBinary returns a copy of the {}Size bytes of u.",
+                        ty
+                    ))
+                    .line(format!(
+                        "func (u {}) MarshalBinary() ([]byte, error) {{ return u[:], nil }}",
+                        ty
+                    ))
+                    .blank()
+                    .line(format!(
+                        "// UnmarshalBinary copies the start of data, which must hold at least {}Size bytes.",
+                        ty
+                    ))
+                    .block(
+                        format!("func (u *{}) UnmarshalBinary(data []byte) error {{", ty),
+                        "}",
+                        |w| {
+                            w.block(format!("if len(data) < {}Size {{", ty), "}", |w| {
+                                w.line(format!(
+                                    "return shortBuffer(\"{}\", {}Size, len(data))",
+                                    name, ty
+                                ));
+                            })
+                            .line("copy(u[:], data)")
+                            .line("return nil");
+                        },
+                    );
+            }
+            None => {
+                w.line(format!(
+                    "// MarshalBinary encodes m into a new buffer of {}Size bytes.",
+                    ty
+                ))
+                .block(
+                    format!("func (m {}) MarshalBinary() ([]byte, error) {{", ty),
+                    "}",
+                    |w| {
+                        w.line(format!("b := make([]byte, {}Size)", ty))
+                            .block("if err := m.encode(b); err != nil {", "}", |w| {
+                                w.line("return nil, err");
+                            })
+                            .line("return b, nil");
+                    },
+                )
+                .blank()
+                .line(format!(
+                    "// UnmarshalBinary decodes m from the start of data, which must hold at least {}Size bytes.",
+                    ty
+                ))
+                .block(
+                    format!("func (m *{}) UnmarshalBinary(data []byte) error {{", ty),
+                    "}",
+                    |w| {
+                        w.line(format!("v, err := New{}(data)", view))
+                            .block("if err != nil {", "}", |w| {
+                                w.line("return err");
+                            })
+                            .line("m.decode(v)")
+                            .line("return nil");
+                    },
+                )
+                .blank();
+                if let Some(header) = frame {
+                    w.line(format!(
+                        "// MarshalFrame encodes m after a {} holding its id and length.",
+                        FRAME_HEADER
+                    ))
+                    .block(
+                        format!("func (m {}) MarshalFrame() ([]byte, error) {{", ty),
+                        "}",
+                        |w| {
+                            w.line(format!("b := make([]byte, {}FrameSize)", ty))
+                                .line(format!("h := {}View(b)", FRAME_HEADER))
+                                .line(format!("h.Set{}({}ID)", member_name(&header.id().name), ty));
+                            if let Some(length) = header.length() {
+                                w.line(format!(
+                                    "h.Set{}({}{})",
+                                    member_name(&length.name),
+                                    ty,
+                                    match header.length_value(0) {
+                                        0 => "Size",
+                                        _ => "FrameSize",
+                                    }
+                                ));
+                            }
+                            w.block(
+                                format!(
+                                    "if err := m.encode({}(b[{}Size:])); err != nil {{",
+                                    view, FRAME_HEADER
+                                ),
+                                "}",
+                                |w| {
+                                    w.line("return nil, err");
+                                },
+                            )
+                            .line("return b, nil");
+                        },
+                    )
+                    .blank();
+                }
+                w.block(
+                    format!("func (m *{}) decode(v {}) {{", ty, view),
+                    "}",
+                    |w| {
+                        for f in layout.fields() {
+                            let member = member_name(&f.name);
+                            match &f.field_type {
+                                FieldType_::Struct(_) => {
+                                    w.line(format!("m.{}.decode(v.{}())", member, member));
+                                }
+                                FieldType_::Union(_)
+                                | FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                                    w.line(format!("copy(m.{}[:], v.{}())", member, member));
+                                }
+                                FieldType_::Vector(ArrayLike::FixedArray { .. }) => {
+                                    w.block(format!("for i := range m.{} {{", member), "}", |w| {
+                                        w.line(format!("m.{}[i] = v.{}(i)", member, member));
+                                    });
+                                }
+                                _ => {
+                                    w.line(format!("m.{} = v.{}()", member, member));
+                                }
+                            }
+                        }
+                    },
+                )
+                .blank()
+                .block(
+                    format!("func (m *{}) encode(v {}) error {{", ty, view),
+                    "}",
+                    |w| {
+                        for f in layout.fields() {
+                            let member = member_name(&f.name);
+                            match &f.field_type {
+                                FieldType_::Struct(_) => {
+                                    w.block(
+                                        format!(
+                                            "if err := m.{}.encode(v.{}()); err != nil {{",
+                                            member, member
+                                        ),
+                                        "}",
+                                        |w| {
+                                            w.line("return err");
+                                        },
+                                    );
+                                }
+                                FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                                    w.block(
+                                        format!(
+                                            "if err := v.Set{}(m.{}); err != nil {{",
+                                            member, member
+                                        ),
+                                        "}",
+                                        |w| {
+                                            w.line("return err");
+                                        },
+                                    );
+                                }
+                                FieldType_::Union(_)
+                                | FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                                    w.line(format!("copy(v.{}(), m.{}[:])", member, member));
+                                }
+                                FieldType_::Vector(ArrayLike::FixedArray { .. }) => {
+                                    w.block(
+                                        format!("for i, x := range m.{} {{", member),
+                                        "}",
+                                        |w| {
+                                            w.line(format!("v.Set{}(i, x)", member));
+                                        },
+                                    );
+                                }
+                                _ => {
+                                    w.line(format!("v.Set{}(m.{})", member, member));
+                                }
+                            }
+                        }
+                        w.line("return nil");
+                    },
+                );
+            }
+        }
+    }
+
+    /// A `Handler` interface with a method per message, a `NopHandler` to embed in one and
+    /// `Dispatch` calling it
+    fn write_dispatch(&self, w: &mut CodeWriter, header: &Header) {
+        let messages: Vec<(String, &str)> = self
+            .protocol
+            .messages
+            .iter()
+            .map(|m| (type_name(&m.name), m.name.as_str()))
+            .collect();
+        let header_view = format!("{}View", FRAME_HEADER);
+
+        w.blank()
+            .line("// Handler receives the messages of the frames passed to Dispatch.")
+            .block("type Handler interface {", "}", |w| {
+                for (ty, _) in &messages {
+                    w.line(format!("On{}({}View)", ty, ty));
+                }
+            })
+            .blank()
+            .line("// NopHandler ignores every message, embed it in a Handler to handle only some of them.")
+            .line("type NopHandler struct{}");
+        for (ty, _) in &messages {
+            w.blank()
+                .line(format!("// On{} ignores the message.", ty))
+                .line(format!("func (NopHandler) On{}({}View) {{}}", ty, ty));
+        }
+
+        w.blank()
+            .line("// checkFrame returns an error when the length of a frame is too small for its message or")
+            .line("// the buffer doesn't hold the whole frame.")
+            .block("func checkFrame(name string, frame, size, length int) error {", "}", |w| {
+                w.block("if frame < size {", "}", |w| {
+                    w.line(format!(
+                        "return fmt.Errorf(\"{}: a frame length of %d is too small for %s\", frame, name)",
+                        self.package
+                    ));
+                })
+                .block("if length < frame {", "}", |w| {
+                    w.line("return shortBuffer(\"the frame of \"+name, frame, length)");
+                })
+                .line("return nil");
+            })
+            .blank()
+            .line(format!(
+                "// Dispatch reads the {} at the start of b and passes the message after it to h, returning",
+                FRAME_HEADER
+            ))
+            .line("// the size of the frame.")
+            .block("func Dispatch(b []byte, h Handler) (int, error) {", "}", |w| {
+                let id = format!("header.{}()", member_name(&header.id().name));
+                w.line(format!("header, err := New{}(b)", header_view))
+                    .block("if err != nil {", "}", |w| {
+                        w.line("return 0, err");
+                    });
+                if let Some(length) = header.length() {
+                    let value = format!("int(header.{}())", member_name(&length.name));
+                    w.line(match header.length_value(0) {
+                        0 => format!("frame := {}Size + {}", FRAME_HEADER, value),
+                        _ => format!("frame := {}", value),
+                    });
+                }
+                w.line(format!("switch {} {{", id));
+                for (ty, name) in &messages {
+                    let frame = match header.length() {
+                        Some(_) => String::from("frame"),
+                        None => format!("{}FrameSize", ty),
+                    };
+                    w.line(format!("case {}ID:", ty))
+                        .indent()
+                        .block(
+                            format!(
+                                "if err := checkFrame(\"{}\", {}, {}FrameSize, len(b)); err != nil {{",
+                                name, frame, ty
+                            ),
+                            "}",
+                            |w| {
+                                w.line("return 0, err");
+                            },
+                        )
+                        .line(format!(
+                            "h.On{}({}View(b[{}Size : {}Size+{}Size]))",
+                            ty, ty, FRAME_HEADER, FRAME_HEADER, ty
+                        ))
+                        .line(format!("return {}, nil", frame))
+                        .dedent();
+                }
+                w.line("}").line(format!(
+                    "return 0, fmt.Errorf(\"{}: no message has the id %d\", {})",
+                    self.package, id
+                ));
+            });
+    }
+
+    /// Writes an accessor for every variant of the union field `f`, `ok` is false unless the
+    /// discriminator selects that variant
+    fn write_variant_accessors(&self, w: &mut CodeWriter, view: &str, f: &Field, ut: &UnionType) {
+        let union = self
+            .protocol
+            .union(&ut.name)
+            .expect("the union of a resolved field is declared");
+        let member = member_name(&f.name);
+        let discriminator = member_name(&ut.discriminator);
+        for v in &union.variants {
+            let variant = member_name(&v.name);
+            let (params, args, value_type) = match &v.field_type {
+                FieldType_::Scalar(s) => ("", "", go_type(s).to_string()),
+                FieldType_::Enum(et) => ("", "", type_name(&et.name)),
+                FieldType_::Struct(StructType { name, .. })
+                | FieldType_::Union(UnionType { name, .. }) => {
+                    ("", "", format!("{}View", type_name(name)))
+                }
+                FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                    ("", "", String::from("string"))
+                }
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => ("", "", String::from("[]byte")),
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                    ("i int", "i", go_type(scalar).to_string())
+                }
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            };
+            let selector = format!(
+                "{}{}",
+                type_name(&union.discriminator_type),
+                go_case(&v.selector)
+            );
+            w.blank()
+                .line(format!(
+                    "// {}{} reads {}().{}({}), ok is false unless {}() is {}.",
+                    member, variant, member, variant, args, discriminator, selector
+                ))
+                .block(
+                    format!(
+                        "func (v {}) {}{}({}) (x {}, ok bool) {{",
+                        view, member, variant, params, value_type
+                    ),
+                    "}",
+                    |w| {
+                        w.block(
+                            format!("if v.{}() != {} {{", discriminator, selector),
+                            "}",
+                            |w| {
+                                w.line("return x, false");
+                            },
+                        )
+                        .line(format!("return v.{}().{}({}), true", member, variant, args));
+                    },
+                );
+        }
+    }
+
+    /// Timestamps and durations also get accessors in terms of the `time` package, next to the
+    /// raw counts
+    fn write_time_accessors(
+        &self,
+        w: &mut CodeWriter,
+        receiver: &str,
+        ty: &str,
+        f: &Field,
+        scalar: &ScalarType,
+    ) {
+        let member = member_name(&f.name);
+        let unit = format!("{}{}Unit", ty, go_case(&f.name));
+        match scalar {
+            ScalarType::Timestamp(t) => {
+                let (read, write) = match t.unit {
+                    TimeUnit::Seconds => ("time.Unix(int64(v.{}()), 0)", "t.Unix()"),
+                    TimeUnit::Millis => ("time.UnixMilli(int64(v.{}()))", "t.UnixMilli()"),
+                    TimeUnit::Micros => ("time.UnixMicro(int64(v.{}()))", "t.UnixMicro()"),
+                    TimeUnit::Nanos => ("time.Unix(0, int64(v.{}()))", "t.UnixNano()"),
+                };
+                w.blank()
+                    .line(format!("// {}Time reads {} as a UTC time.", member, f.name))
+                    .line(format!(
+                        "{} {}Time() time.Time {{ return {}.UTC() }}",
+                        receiver,
+                        member,
+                        read.replace("{}", &member)
+                    ))
+                    .blank()
+                    .line(format!(
+                        "// Set{}Time writes t to {}, truncated to {}.",
+                        member, f.name, unit
+                    ))
+                    .line(format!(
+                        "{} Set{}Time(t time.Time) {{ v.Set{}(uint64({})) }}",
+                        receiver, member, member, write
+                    ));
+            }
+            ScalarType::Duration(_) => {
+                w.blank()
+                    .line(format!(
+                        "// {}Duration reads {} as a time.Duration.",
+                        member, f.name
+                    ))
+                    .line(format!(
+                        "{} {}Duration() time.Duration {{ return time.Duration(v.{}()) * {} }}",
+                        receiver, member, member, unit
+                    ))
+                    .blank()
+                    .line(format!(
+                        "// Set{}Duration writes d to {}, truncated to {}.",
+                        member, f.name, unit
+                    ))
+                    .line(format!(
+                        "{} Set{}Duration(d time.Duration) {{ v.Set{}(int64(d / {})) }}",
+                        receiver, member, member, unit
+                    ));
+            }
+            _ => {}
+        }
+    }
+
+    fn write_accessors(&self, w: &mut CodeWriter, view: &str, ty: &str, f: &Field) {
+        let member = member_name(&f.name);
+        let o = f.offset;
+        let end = o + f.field_type.size_bytes();
+        let about = match &f.description {
+            Some(d) => format!("the {} at offset {}, {}", f.field_type, o, d),
+            None => format!("the {} at offset {}", f.field_type, o),
+        };
+        let receiver = format!("func (v {})", view);
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                w.line(format!("// {} reads {}.", member, about))
+                    .line(format!(
+                        "{} {}() {} {{ return {} }}",
+                        receiver,
+                        member,
+                        go_type(s),
+                        self.read(s, &o.to_string())
+                    ))
+                    .blank()
+                    .line(format!("// Set{} writes {}.", member, about))
+                    .line(format!(
+                        "{} Set{}(x {}) {{ {} }}",
+                        receiver,
+                        member,
+                        go_type(s),
+                        self.write(s, &o.to_string(), "x")
+                    ));
+                self.write_time_accessors(w, &receiver, ty, f, s);
+            }
+            FieldType_::Enum(et) => {
+                let enum_type = type_name(&et.name);
+                w.line(format!("// {} reads {}.", member, about))
+                    .line(format!(
+                        "{} {}() {} {{ return {}({}) }}",
+                        receiver,
+                        member,
+                        enum_type,
+                        enum_type,
+                        self.read(&et.underlying, &o.to_string())
+                    ))
+                    .blank()
+                    .line(format!("// Set{} writes {}.", member, about))
+                    .line(format!(
+                        "{} Set{}(x {}) {{ {} }}",
+                        receiver,
+                        member,
+                        enum_type,
+                        self.write(
+                            &et.underlying,
+                            &o.to_string(),
+                            &format!("{}(x)", go_type(&et.underlying))
+                        )
+                    ));
+            }
+            FieldType_::Struct(StructType { name, .. })
+            | FieldType_::Union(UnionType { name, .. }) => {
+                let nested = format!("{}View", type_name(name));
+                w.line(format!("// {} views {}.", member, about))
+                    .line(format!(
+                        "{} {}() {} {{ return {}(v[{}:{}]) }}",
+                        receiver, member, nested, nested, o, end
+                    ));
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                let pad = match padding {
+                    AsciiPadding::Nul => "0",
+                    AsciiPadding::Space => "' '",
+                };
+                w.line(format!("// {} reads {}.", member, about))
+                    .line(format!(
+                        "{} {}() string {{ return getASCII(v[{}:{}], {}) }}",
+                        receiver, member, o, end, pad
+                    ))
+                    .blank()
+                    .line(format!(
+                        "// Set{} writes {}, it fails when s is too long or isn't ascii.",
+                        member, about
+                    ))
+                    .line(format!(
+                        "{} Set{}(s string) error {{ return setASCII(v[{}:{}], {}, s, \"{}\") }}",
+                        receiver, member, o, end, pad, f.name
+                    ));
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                w.line(format!("// {} views {}.", member, about))
+                    .line(format!(
+                        "{} {}() []byte {{ return v[{}:{}] }}",
+                        receiver, member, o, end
+                    ));
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                let at = format!("{}+{}*i", o, scalar.size_bytes());
+                let length = format!("{}{}Length", ty, go_case(&f.name));
+                let check = |w: &mut CodeWriter| {
+                    w.block(format!("if uint(i) >= {} {{", length), "}", |w| {
+                        w.line(format!(
+                            "panic(fmt.Sprintf(\"{}: index %d out of range for {}\", i))",
+                            self.package, f.name
+                        ));
+                    });
+                };
+                w.line(format!("// {} reads element i of {}.", member, about))
+                    .block(
+                        format!("{} {}(i int) {} {{", receiver, member, go_type(scalar)),
+                        "}",
+                        |w| {
+                            check(w);
+                            w.line(format!("return {}", self.read(scalar, &at)));
+                        },
+                    )
+                    .blank()
+                    .line(format!("// Set{} writes element i of {}.", member, about))
+                    .block(
+                        format!(
+                            "{} Set{}(i int, x {}) {{",
+                            receiver,
+                            member,
+                            go_type(scalar)
+                        ),
+                        "}",
+                        |w| {
+                            check(w);
+                            w.line(self.write(scalar, &at, "x"));
+                        },
+                    );
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::go::{go_case, GoGenerator};
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+    use std::path::Path;
+
+    fn options(package: &str) -> GeneratorOptions {
+        GeneratorOptions {
+            parameters: vec![(String::from("package"), String::from(package))],
+        }
+    }
+
+    #[test]
+    fn test_package() {
+        let protocol =
+            crate::sssc::compile("protocol foo.risk_limits; message M [1] { u8: a; }").unwrap();
+        let files = GoGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!(Path::new("risklimits/foo_risk_limits.go"), files[0].path);
+        assert!(files[0].contents.contains("\npackage risklimits\n"));
+
+        let files = GoGenerator.generate(&protocol, &options("risk")).unwrap();
+        assert_eq!(Path::new("risk/foo_risk_limits.go"), files[0].path);
+        for invalid in ["Risk", "func", "risk.limits"] {
+            assert!(matches!(
+                GoGenerator.generate(&protocol, &options(invalid)),
+                Err(GeneratorError::InvalidOption { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_initialisms() {
+        assert_eq!("MessageID", go_case("message_id"));
+        assert_eq!("TTL", go_case("ttl"));
+        assert_eq!("HTTPServerURL", go_case("http_server_url"));
+        assert_eq!("Identity", go_case("identity"));
+    }
+
+    #[test]
+    fn test_only_used_imports() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [1] { i16: a; u8: b; }",
+        )
+        .unwrap();
+        let contents = &GoGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()[0]
+            .contents;
+        assert!(contents.contains("import (\n\t\"encoding/binary\"\n\t\"fmt\"\n\t\"io\"\n)\n"));
+        assert!(contents.contains("var byteOrder = binary.BigEndian\n"));
+        assert!(!contents.contains("getASCII"));
+        assert!(contents
+            .contains("func (v MView) SetA(x int16) { byteOrder.PutUint16(v[0:], uint16(x)) }"));
+    }
+}
@@ -0,0 +1,1482 @@
+//! Generates SBE style Java flyweights, a class per message, struct and union which is pointed at
+//! a `ByteBuffer` and an offset and reads or writes fields in place without allocating. Files are
+//! written in the Maven layout, `src/main/java/<package>/<Class>.java`.
+
+use crate::codegen::{
+    camel_case, file_header, pascal_case, screaming_snake_case, type_order, CodeWriter, Generator,
+    GeneratorError, GeneratorOptions, Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{Mantissa, TimeUnit, Unsigned};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, UnionType};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol, Union};
+
+const KEYWORDS: Keywords = Keywords(&[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+]);
+
+/// Methods every flyweight has or inherits, a field with one of these names gets a `_` suffix
+const RESERVED_MEMBERS: Keywords = Keywords(&[
+    "wrap",
+    "buffer",
+    "offset",
+    "toString",
+    "hashCode",
+    "equals",
+    "getClass",
+    "notify",
+    "notifyAll",
+    "wait",
+    "clone",
+    "finalize",
+    "wrapFrame",
+    "frameHeader",
+]);
+
+/// The class generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Shared by the flyweights of a protocol, written into the same package
+const ASCII_SEQUENCE: &str = r#"/**
+ * A reusable view of a fixed length ascii field, the text ends at the first nul byte and leaves
+ * out the pad characters ending it. Flyweights hold one per ascii field so reading the field
+ * doesn't allocate.
+ */
+public final class AsciiSequence implements CharSequence {
+    private ByteBuffer buffer;
+    private int offset;
+    private int length;
+
+    AsciiSequence wrap(final ByteBuffer buffer, final int offset, final int capacity, final char pad) {
+        int length = 0;
+        while (length < capacity && buffer.get(offset + length) != 0) {
+            length++;
+        }
+        while (length > 0 && buffer.get(offset + length - 1) == pad) {
+            length--;
+        }
+        this.buffer = buffer;
+        this.offset = offset;
+        this.length = length;
+        return this;
+    }
+
+    @Override
+    public int length() {
+        return length;
+    }
+
+    @Override
+    public char charAt(final int index) {
+        Objects.checkIndex(index, length);
+        return (char) (buffer.get(offset + index) & 0xFF);
+    }
+
+    @Override
+    public CharSequence subSequence(final int start, final int end) {
+        return toString().substring(start, end);
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder(length);
+        for (int i = 0; i < length; i++) {
+            text.append(charAt(i));
+        }
+        return text.toString();
+    }
+
+    /** Writes value and fills the rest of the field with pad, nothing is written if it doesn't fit */
+    static void put(final ByteBuffer buffer, final int offset, final int capacity, final char pad, final CharSequence value, final String field) {
+        final int length = value.length();
+        if (length > capacity) {
+            throw new IllegalArgumentException(field + " holds at most " + capacity + " characters but the value has " + length);
+        }
+        for (int i = 0; i < length; i++) {
+            if (value.charAt(i) > 0x7F) {
+                throw new IllegalArgumentException(field + " only holds ascii characters");
+            }
+        }
+        for (int i = 0; i < capacity; i++) {
+            buffer.put(offset + i, (byte) (i < length ? value.charAt(i) : pad));
+        }
+    }
+}"#;
+
+/// Generates a source file per type, in the package given by the `package` option or named
+/// after the protocol
+pub struct JavaGenerator;
+
+impl Generator for JavaGenerator {
+    fn name(&self) -> &str {
+        "java"
+    }
+
+    fn description(&self) -> &str {
+        "Java flyweights over ByteBuffer in a Maven layout, options: package"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&["package"])?;
+
+        let package = match options.get("package") {
+            Some(package) => {
+                let valid = package.split('.').all(|part| {
+                    let mut chars = part.chars();
+                    chars
+                        .next()
+                        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                        && !KEYWORDS.is_keyword(part)
+                });
+                if !valid {
+                    return Err(GeneratorError::InvalidOption {
+                        name: String::from("package"),
+                        reason: format!("'{}' is not a Java package name", package),
+                    });
+                }
+                package.to_string()
+            }
+            None => protocol
+                .name
+                .iter()
+                .map(|n| KEYWORDS.escape(&n.to_lowercase()))
+                .collect::<Vec<String>>()
+                .join("."),
+        };
+
+        let sources = JavaSources {
+            protocol,
+            package: package.clone(),
+            byte_order: match protocol.byte_order {
+                ByteOrder::Little => "ByteOrder.LITTLE_ENDIAN",
+                ByteOrder::Big => "ByteOrder.BIG_ENDIAN",
+            },
+        };
+        let dir = format!("src/main/java/{}", package.replace('.', "/"));
+        Ok(sources
+            .generate()?
+            .into_iter()
+            .map(|(class, source)| OutputFile::new(format!("{}/{}.java", dir, class), source))
+            .collect())
+    }
+}
+
+struct JavaSources<'p> {
+    protocol: &'p Protocol,
+    package: String,
+    byte_order: &'static str,
+}
+
+fn class_name(name: &str) -> String {
+    KEYWORDS.escape(&pascal_case(name))
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&KEYWORDS.escape(&camel_case(name)))
+}
+
+fn constant_name(name: &str) -> String {
+    screaming_snake_case(name)
+}
+
+/// The storage of a scalar stripped of its meaning, e.g. a timestamp is a `u64`
+fn storage(scalar: &ScalarType) -> ScalarType {
+    match scalar {
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => ScalarType::Unsigned(u),
+            Mantissa::Signed(s) => ScalarType::Signed(s),
+        },
+        ScalarType::Timestamp(t) => t.storage(),
+        ScalarType::Duration(d) => d.storage(),
+        other => *other,
+    }
+}
+
+/// The primitive holding a scalar, unsigned types widen to the next larger primitive as in SBE
+/// except `u64` which keeps its bits in a `long`
+fn java_type(scalar: &ScalarType) -> &'static str {
+    match storage(scalar) {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "short",
+            2 => "int",
+            _ => "long",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "byte",
+            2 => "short",
+            4 => "int",
+            _ => "long",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "float",
+            _ => "double",
+        },
+        _ => "byte",
+    }
+}
+
+/// The `VarHandle` a scalar is accessed through, single bytes are read from the buffer directly
+fn var_handle(scalar: &ScalarType) -> Option<&'static str> {
+    match (storage(scalar), scalar.size_bytes()) {
+        (ScalarType::Float(_), 4) => Some("F32"),
+        (ScalarType::Float(_), _) => Some("F64"),
+        (_, 2) => Some("I16"),
+        (_, 4) => Some("I32"),
+        (_, 8) => Some("I64"),
+        _ => None,
+    }
+}
+
+fn var_handle_declaration(handle: &str) -> String {
+    let array = match handle {
+        "I16" => "short",
+        "I32" => "int",
+        "I64" => "long",
+        "F32" => "float",
+        _ => "double",
+    };
+    format!(
+        "private static final VarHandle {} = MethodHandles.byteBufferViewVarHandle({}[].class, BYTE_ORDER);",
+        handle, array
+    )
+}
+
+fn load(scalar: &ScalarType, index: &str) -> String {
+    let unsigned = matches!(storage(scalar), ScalarType::Unsigned(_));
+    match (var_handle(scalar), scalar.size_bytes(), unsigned) {
+        (None, _, true) => format!("(short) (buffer.get({}) & 0xFF)", index),
+        (None, _, false) => format!("buffer.get({})", index),
+        (Some(h), 2, true) => format!("(short) {}.get(buffer, {}) & 0xFFFF", h, index),
+        (Some(h), 2, false) => format!("(short) {}.get(buffer, {})", h, index),
+        (Some(h), 4, true) => format!("Integer.toUnsignedLong((int) {}.get(buffer, {}))", h, index),
+        (Some(h), _, _) => format!("({}) {}.get(buffer, {})", java_type(scalar), h, index),
+    }
+}
+
+fn store(scalar: &ScalarType, index: &str, value: &str) -> String {
+    // unsigned values are held in a wider primitive and narrowed back to their stored bits
+    let stored = match (var_handle(scalar), scalar.size_bytes()) {
+        (None, _) => "byte",
+        (Some(_), 2) => "short",
+        (Some("I32"), _) => "int",
+        (Some(_), _) => java_type(scalar),
+    };
+    let value = match stored == java_type(scalar) {
+        true => value.to_string(),
+        false => format!("({}) {}", stored, value),
+    };
+    match var_handle(scalar) {
+        None => format!("buffer.put({}, {});", index, value),
+        Some(h) => format!("{}.set(buffer, {}, {});", h, index, value),
+    }
+}
+
+/// A literal of the type `java_type` gives the scalar, `value` holds the stored bits
+fn literal(scalar: &ScalarType, value: u64) -> String {
+    let size = scalar.size_bytes();
+    let signed = matches!(storage(scalar), ScalarType::Signed(_));
+    let bits = size * 8;
+    // sign extend values of signed types so they print as the number they stand for
+    let number: i128 = if signed && bits < 64 && value & (1 << (bits - 1)) != 0 {
+        value as i128 - (1i128 << bits)
+    } else if signed || bits == 64 {
+        value as i64 as i128
+    } else {
+        value as i128
+    };
+    match java_type(scalar) {
+        "byte" => format!("(byte) {}", number),
+        "short" => format!("(short) {}", number),
+        "long" => format!("{}L", number),
+        _ => number.to_string(),
+    }
+}
+
+/// An int expression passed to a setter of a header field, narrowed when the setter takes less
+fn header_argument(field: &Field, value: &str) -> String {
+    match &field.field_type {
+        FieldType_::Scalar(scalar) if java_type(scalar) == "short" => format!("(short) {}", value),
+        _ => value.to_string(),
+    }
+}
+
+/// SBE's null value of an enum's underlying type, the largest unsigned or smallest signed value
+fn null_value(scalar: &ScalarType) -> u64 {
+    let bits = scalar.size_bytes() * 8;
+    match storage(scalar) {
+        ScalarType::Signed(_) => 1 << (bits - 1),
+        _ if bits == 64 => u64::MAX,
+        _ => (1 << bits) - 1,
+    }
+}
+
+fn chrono_unit(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Seconds => "ChronoUnit.SECONDS",
+        TimeUnit::Millis => "ChronoUnit.MILLIS",
+        TimeUnit::Micros => "ChronoUnit.MICROS",
+        TimeUnit::Nanos => "ChronoUnit.NANOS",
+    }
+}
+
+fn time_unit(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Seconds => "TimeUnit.SECONDS",
+        TimeUnit::Millis => "TimeUnit.MILLISECONDS",
+        TimeUnit::Micros => "TimeUnit.MICROSECONDS",
+        TimeUnit::Nanos => "TimeUnit.NANOSECONDS",
+    }
+}
+
+impl JavaSources<'_> {
+    fn generate(&self) -> Result<Vec<(String, String)>, GeneratorError> {
+        let protocol = self.protocol;
+        let mut sources = vec![(
+            String::from("AsciiSequence"),
+            self.source(
+                &["java.nio.ByteBuffer", "java.util.Objects"],
+                ASCII_SEQUENCE,
+            ),
+        )];
+
+        for e in &protocol.enums {
+            sources.push((class_name(&e.name), self.write_enum(e)?));
+        }
+        for declaration in type_order(protocol) {
+            match declaration {
+                TypeDeclaration::Struct(s) => sources.push((
+                    class_name(&s.name),
+                    self.write_flyweight(&s.name, None, &s.layout, None),
+                )),
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    sources.push((
+                        class_name(&u.name),
+                        self.write_flyweight(&u.name, None, &layout, Some(u)),
+                    ))
+                }
+            }
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in an int",
+                    m.name
+                ))
+            })?;
+            sources.push((
+                class_name(&m.name),
+                self.write_flyweight(&m.name, Some(id), &m.layout, None),
+            ));
+        }
+        if let Some(header) = &protocol.header {
+            sources.push((
+                String::from(FRAME_HEADER),
+                self.write_flyweight(FRAME_HEADER, None, &header.layout, None),
+            ));
+            sources.push((String::from("Handler"), self.write_handler()));
+            sources.push((String::from("Dispatcher"), self.write_dispatcher(header)));
+        }
+        Ok(sources)
+    }
+
+    fn source(&self, imports: &[&str], body: &str) -> String {
+        let mut w = CodeWriter::new("    ");
+        w.line(file_header("//", self.protocol).trim_end())
+            .line(format!("package {};", self.package))
+            .blank();
+        if !imports.is_empty() {
+            for import in imports {
+                w.line(format!("import {};", import));
+            }
+            w.blank();
+        }
+        w.lines(body);
+        w.finish()
+    }
+
+    /// The callbacks `Dispatcher` calls, each does nothing unless it is overridden
+    fn write_handler(&self) -> String {
+        let mut w = CodeWriter::new("    ");
+        w.line("/** Callbacks for Dispatcher, a message without an override is ignored */");
+        w.block("public interface Handler {", "}", |w| {
+            for (i, m) in self.protocol.messages.iter().enumerate() {
+                if i > 0 {
+                    w.blank();
+                }
+                let ty = class_name(&m.name);
+                w.line(format!("/** Called with a {} */", ty)).block(
+                    format!("default void on{}(final {} message) {{", ty, ty),
+                    "}",
+                    |_| {},
+                );
+            }
+        });
+        self.source(&[], &w.finish())
+    }
+
+    /// Reads a frame and calls a `Handler` with its message, reusing a flyweight per message
+    fn write_dispatcher(&self, header: &Header) -> String {
+        let messages: Vec<(String, String)> = self
+            .protocol
+            .messages
+            .iter()
+            .map(|m| (class_name(&m.name), camel_case(&m.name)))
+            .collect();
+        let mut w = CodeWriter::new("    ");
+        w.line("/** Reads frames and calls a Handler with the message of each, reusing one flyweight per message */");
+        w.block("public final class Dispatcher {", "}", |w| {
+            w.line("private final Handler handler;")
+                .line(format!(
+                    "private final {} header = new {}();",
+                    FRAME_HEADER, FRAME_HEADER
+                ));
+            for (ty, member) in &messages {
+                w.line(format!("private final {} {} = new {}();", ty, KEYWORDS.escape(member), ty));
+            }
+            w.blank()
+                .block("public Dispatcher(final Handler handler) {", "}", |w| {
+                    w.line("this.handler = Objects.requireNonNull(handler);");
+                })
+                .blank()
+                .line("/**")
+                .line(" * Reads the header at offset and calls the handler with the message after it, returns the")
+                .line(" * size of the frame. Throws IndexOutOfBoundsException when the buffer doesn't hold the whole")
+                .line(" * frame and IllegalArgumentException when its length is too small or no message has its id.")
+                .line(" */")
+                .block(
+                    "public int dispatch(final ByteBuffer buffer, final int offset) {",
+                    "}",
+                    |w| {
+                        w.line("header.wrap(buffer, offset);")
+                            .line(format!(
+                                "final long id = header.{}();",
+                                member_name(&header.id().name)
+                            ));
+                        if let Some(length) = header.length() {
+                            let length = format!("header.{}()", member_name(&length.name));
+                            w.line(match header.length_value(0) {
+                                0 => format!("final long length = {}.SIZE + {};", FRAME_HEADER, length),
+                                _ => format!("final long length = {};", length),
+                            });
+                        }
+                        for (ty, member) in &messages {
+                            w.block(format!("if (id == {}.ID) {{", ty), "}", |w| {
+                                let frame = match header.length() {
+                                    Some(_) => {
+                                        w.line(format!(
+                                            "final int frame = frame(buffer, offset, length, {}.FRAME_SIZE);",
+                                            ty
+                                        ));
+                                        "frame"
+                                    }
+                                    None => "",
+                                };
+                                w.line(format!(
+                                    "handler.on{}({}.wrap(buffer, offset + {}.SIZE));",
+                                    ty,
+                                    KEYWORDS.escape(member),
+                                    FRAME_HEADER
+                                ))
+                                .line(match frame {
+                                    "" => format!("return {}.FRAME_SIZE;", ty),
+                                    frame => format!("return {};", frame),
+                                });
+                            });
+                        }
+                        w.line("throw new IllegalArgumentException(\"no message has the id \" + id);");
+                    },
+                );
+            if header.length().is_some() {
+                w.blank()
+                    .block(
+                        "private static int frame(final ByteBuffer buffer, final int offset, final long length, final int size) {",
+                        "}",
+                        |w| {
+                            w.block("if (length < size) {", "}", |w| {
+                                w.line("throw new IllegalArgumentException(\"a frame length of \" + length + \" is too small for a message of \" + size + \" bytes\");");
+                            })
+                            .block("if (length > buffer.capacity() - offset) {", "}", |w| {
+                                w.line("throw new IndexOutOfBoundsException(\"a frame of \" + length + \" bytes at \" + offset + \" overruns a buffer of \" + buffer.capacity());");
+                            })
+                            .line("return (int) length;");
+                        },
+                    );
+            }
+        });
+        self.source(&["java.nio.ByteBuffer", "java.util.Objects"], &w.finish())
+    }
+
+    fn write_enum(&self, e: &Enum) -> Result<String, GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let ty = class_name(&e.name);
+        let raw = java_type(&e.underlying);
+        let mut w = CodeWriter::new("    ");
+
+        match e.kind {
+            EnumKind::Enum => {
+                let null = null_value(&e.underlying);
+                if let Some(v) = e.values.iter().find(|v| v.value == null) {
+                    return Err(GeneratorError::Unsupported(format!(
+                        "{}.{} has the value {} which Java flyweights reserve for NULL_VAL",
+                        e.name, v.name, v.value
+                    )));
+                }
+                w.line(format!(
+                    "/** enum {}, stored as a {} */",
+                    e.name, e.underlying
+                ));
+                w.block(format!("public enum {} {{", ty), "}", |w| {
+                    for v in &e.values {
+                        w.line(format!(
+                            "{}({}),",
+                            KEYWORDS.escape(&v.name),
+                            literal(&e.underlying, v.value)
+                        ));
+                    }
+                    w.line("/** Not declared in the schema, returned for any undeclared value */")
+                        .line(format!("NULL_VAL({});", literal(&e.underlying, null)))
+                        .blank()
+                        .line(format!("private final {} value;", raw))
+                        .blank()
+                        .block(format!("{}(final {} value) {{", ty, raw), "}", |w| {
+                            w.line("this.value = value;");
+                        })
+                        .blank()
+                        .line("/** The value stored in a message */")
+                        .block(format!("public {} value() {{", raw), "}", |w| {
+                            w.line("return value;");
+                        })
+                        .blank()
+                        .line(
+                            "/** The constant for a stored value, NULL_VAL if it isn't declared */",
+                        )
+                        .block(
+                            format!("public static {} get(final {} value) {{", ty, raw),
+                            "}",
+                            |w| {
+                                for v in &e.values {
+                                    w.block(
+                                        format!(
+                                            "if (value == {}) {{",
+                                            literal(&e.underlying, v.value)
+                                                .trim_start_matches("(short) ")
+                                                .trim_start_matches("(byte) ")
+                                        ),
+                                        "}",
+                                        |w| {
+                                            w.line(format!("return {};", KEYWORDS.escape(&v.name)));
+                                        },
+                                    );
+                                }
+                                w.line("return NULL_VAL;");
+                            },
+                        );
+                });
+            }
+            EnumKind::Bitflags => {
+                w.line(format!(
+                    "/** bitflags {}, stored as a {} */",
+                    e.name, e.underlying
+                ));
+                w.block(format!("public final class {} {{", ty), "}", |w| {
+                    let mut all = 0u64;
+                    for v in &e.values {
+                        all |= v.value;
+                        w.line(format!(
+                            "public static final {} {} = {};",
+                            raw,
+                            KEYWORDS.escape(&v.name),
+                            literal(&e.underlying, v.value)
+                        ));
+                    }
+                    w.line("/** Every declared flag */")
+                        .line(format!(
+                            "public static final {} ALL = {};",
+                            raw,
+                            literal(&e.underlying, all)
+                        ))
+                        .blank()
+                        .block(format!("private {}() {{", ty), "}", |_| {})
+                        .blank()
+                        .line("/** True if every flag of flags is set in value */")
+                        .block(
+                            format!(
+                                "public static boolean has(final {} value, final {} flags) {{",
+                                raw, raw
+                            ),
+                            "}",
+                            |w| {
+                                w.line("return (value & flags) == flags;");
+                            },
+                        )
+                        .blank()
+                        .line("/** The names of the flags set in value, undeclared bits as a hex number */")
+                        .block(
+                            format!("public static String toString(final {} value) {{", raw),
+                            "}",
+                            |w| {
+                                w.line("final StringBuilder text = new StringBuilder();");
+                                for v in &e.values {
+                                    w.block(
+                                        format!(
+                                            "if (has(value, {})) {{",
+                                            KEYWORDS.escape(&v.name)
+                                        ),
+                                        "}",
+                                        |w| {
+                                            w.line(format!(
+                                                "text.append(text.length() == 0 ? \"\" : \" | \").append(\"{}\");",
+                                                v.name
+                                            ));
+                                        },
+                                    );
+                                }
+                                w.line("final long rest = value & ~ALL;")
+                                    .block("if (rest != 0 || text.length() == 0) {", "}", |w| {
+                                        w.line("text.append(text.length() == 0 ? \"\" : \" | \").append(\"0x\").append(Long.toHexString(rest));");
+                                    })
+                                    .line("return text.toString();");
+                            },
+                        );
+                });
+            }
+        }
+        Ok(self.source(&[], &w.finish()))
+    }
+
+    fn write_flyweight(
+        &self,
+        name: &str,
+        id: Option<u32>,
+        layout: &Layout,
+        union: Option<&Union>,
+    ) -> String {
+        let ty = class_name(name);
+        let doc = match (id, union) {
+            (Some(id), _) => format!("message {} [{}]", name, id),
+            (None, Some(u)) => format!(
+                "union {}, the active variant is selected by a {}",
+                u.name, u.discriminator_type
+            ),
+            (None, None) => format!("struct {}", name),
+        };
+        let fields: Vec<&Field> = layout.fields().collect();
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+
+        let mut imports = vec![
+            "java.lang.invoke.MethodHandles",
+            "java.lang.invoke.VarHandle",
+            "java.nio.ByteBuffer",
+            "java.nio.ByteOrder",
+            "java.util.Objects",
+        ];
+        let mut handles: Vec<&str> = vec![];
+        for f in &fields {
+            let scalar = match &f.field_type {
+                FieldType_::Scalar(s)
+                | FieldType_::Vector(ArrayLike::FixedArray { scalar: s, .. }) => s,
+                FieldType_::Enum(et) => &et.underlying,
+                _ => continue,
+            };
+            if let Some(h) = var_handle(scalar) {
+                if !handles.contains(&h) {
+                    handles.push(h);
+                }
+            }
+            if matches!(
+                f.field_type,
+                FieldType_::Scalar(ScalarType::Timestamp(_) | ScalarType::Duration(_))
+            ) && !imports.contains(&"java.util.concurrent.TimeUnit")
+            {
+                imports.push("java.util.concurrent.TimeUnit");
+                imports.push("java.time.temporal.ChronoUnit");
+            }
+            let native: &[&str] = match f.field_type {
+                FieldType_::Scalar(ScalarType::Timestamp(_)) => &["java.time.Instant"],
+                FieldType_::Scalar(ScalarType::Duration(_)) => &["java.time.Duration"],
+                FieldType_::Scalar(ScalarType::Decimal(d)) => {
+                    match d.mantissa == Mantissa::Unsigned(Unsigned::U64) {
+                        true => &[
+                            "java.math.BigDecimal",
+                            "java.math.BigInteger",
+                            "java.math.RoundingMode",
+                        ],
+                        false => &["java.math.BigDecimal", "java.math.RoundingMode"],
+                    }
+                }
+                _ => continue,
+            };
+            for import in native {
+                if !imports.contains(import) {
+                    imports.push(import);
+                }
+            }
+        }
+        handles.sort();
+        if handles.is_empty() {
+            imports.retain(|i| !i.starts_with("java.lang.invoke"));
+        }
+        imports.sort();
+
+        let mut w = CodeWriter::new("    ");
+        w.line(format!("/** {} */", doc));
+        w.block(format!("public final class {} {{", ty), "}", |w| {
+            if let Some(id) = id {
+                w.line(format!("public static final int ID = {};", id));
+            }
+            w.line(format!("public static final int SIZE = {};", layout.size));
+            if let Some(header) = frame {
+                w.line(format!(
+                    "public static final int FRAME_SIZE = {};",
+                    header.layout.size + layout.size
+                ));
+            }
+            w.line(format!(
+                    "public static final ByteOrder BYTE_ORDER = {};",
+                    self.byte_order
+                ))
+                .blank();
+            for f in &fields {
+                let constant = constant_name(&f.name);
+                w.line(format!(
+                    "public static final int {}_OFFSET = {};",
+                    constant, f.offset
+                ));
+                if let FieldType_::Vector(
+                    ArrayLike::AsciiString { length, .. }
+                    | ArrayLike::Bytes { length }
+                    | ArrayLike::FixedArray { length, .. },
+                ) = &f.field_type
+                {
+                    w.line(format!(
+                        "public static final int {}_LENGTH = {};",
+                        constant, length
+                    ));
+                }
+                match &f.field_type {
+                    FieldType_::Scalar(ScalarType::Decimal(d))
+                    | FieldType_::Vector(ArrayLike::FixedArray {
+                        scalar: ScalarType::Decimal(d),
+                        ..
+                    }) => {
+                        w.line(format!(
+                            "public static final int {}_EXPONENT = {};",
+                            constant, d.exponent
+                        ));
+                    }
+                    FieldType_::Scalar(ScalarType::Timestamp(t)) => {
+                        w.line(format!(
+                            "public static final TimeUnit {}_UNIT = {};",
+                            constant,
+                            time_unit(t.unit)
+                        ));
+                    }
+                    FieldType_::Scalar(ScalarType::Duration(d)) => {
+                        w.line(format!(
+                            "public static final TimeUnit {}_UNIT = {};",
+                            constant,
+                            time_unit(d.unit)
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+
+            if !handles.is_empty() {
+                w.blank();
+                for h in &handles {
+                    w.line(var_handle_declaration(h));
+                }
+            }
+
+            w.blank();
+            for f in &fields {
+                let member = member_name(&f.name);
+                match &f.field_type {
+                    FieldType_::Struct(st) => {
+                        let t = class_name(&st.name);
+                        w.line(format!("private final {} {} = new {}();", t, member, t));
+                    }
+                    FieldType_::Union(ut) => {
+                        let t = class_name(&ut.name);
+                        w.line(format!("private final {} {} = new {}();", t, member, t));
+                    }
+                    FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                        w.line(format!(
+                            "private final AsciiSequence {} = new AsciiSequence();",
+                            member
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            if frame.is_some() {
+                w.line(format!(
+                    "private final {} frameHeader = new {}();",
+                    FRAME_HEADER, FRAME_HEADER
+                ));
+            }
+            w.line("private ByteBuffer buffer;")
+                .line("private int offset;")
+                .blank()
+                .line("/** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */")
+                .block(
+                    format!(
+                        "public {} wrap(final ByteBuffer buffer, final int offset) {{",
+                        ty
+                    ),
+                    "}",
+                    |w| {
+                        w.line("Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());")
+                            .line("this.buffer = buffer;")
+                            .line("this.offset = offset;")
+                            .line("return this;");
+                    },
+                )
+                .blank();
+            if let Some(header) = frame {
+                w.line("/** Writes the header's id and length at offset and points this flyweight at the message after it */")
+                    .block(
+                        format!(
+                            "public {} wrapFrame(final ByteBuffer buffer, final int offset) {{",
+                            ty
+                        ),
+                        "}",
+                        |w| {
+                            w.line("Objects.checkFromIndexSize(offset, FRAME_SIZE, buffer.capacity());");
+                            let id_field = header.id();
+                            let mut write = format!(
+                                "frameHeader.wrap(buffer, offset).{}({})",
+                                member_name(&id_field.name),
+                                header_argument(id_field, "ID")
+                            );
+                            if let Some(length) = header.length() {
+                                let value = match header.length_value(0) {
+                                    0 => "SIZE",
+                                    _ => "FRAME_SIZE",
+                                };
+                                write.push_str(&format!(
+                                    ".{}({})",
+                                    member_name(&length.name),
+                                    header_argument(length, value)
+                                ));
+                            }
+                            w.line(format!("{};", write)).line(format!(
+                                "return wrap(buffer, offset + {}.SIZE);",
+                                FRAME_HEADER
+                            ));
+                        },
+                    )
+                    .blank();
+            }
+            w
+                .block("public ByteBuffer buffer() {", "}", |w| {
+                    w.line("return buffer;");
+                })
+                .blank()
+                .block("public int offset() {", "}", |w| {
+                    w.line("return offset;");
+                });
+
+            for f in &fields {
+                w.blank();
+                self.write_accessors(w, &ty, f);
+                if let FieldType_::Union(ut) = &f.field_type {
+                    self.write_variant_accessors(w, f, ut);
+                }
+            }
+
+            w.blank();
+            match union {
+                Some(u) => self.write_union_to_string(w, u),
+                None => self.write_to_string(w, &ty, layout),
+            }
+            let has_bytes = fields
+                .iter()
+                .any(|f| matches!(f.field_type, FieldType_::Vector(ArrayLike::Bytes { .. })));
+            if union.is_some() || has_bytes {
+                w.blank().block(
+                    "private String hex(final int from, final int length) {",
+                    "}",
+                    |w| {
+                        w.line("final StringBuilder text = new StringBuilder(\"0x\");")
+                            .block("for (int i = 0; i < length; i++) {", "}", |w| {
+                                w.line("text.append(String.format(\"%02x\", buffer.get(offset + from + i)));");
+                            })
+                            .line("return text.toString();");
+                    },
+                );
+            }
+        });
+
+        self.source(&imports, &w.finish())
+    }
+
+    fn write_accessors(&self, w: &mut CodeWriter, ty: &str, f: &Field) {
+        let member = member_name(&f.name);
+        let index = match f.offset {
+            0 => String::from("offset"),
+            o => format!("offset + {}", o),
+        };
+        let doc = match &f.description {
+            Some(d) => format!("/** {}: {} */", f.field_type, d),
+            None => format!("/** {} at offset {} */", f.field_type, f.offset),
+        };
+        let setter = |w: &mut CodeWriter, params: &str, body: &str| {
+            w.block(
+                format!("public {} {}({}) {{", ty, member, params),
+                "}",
+                |w| {
+                    w.lines(body).line("return this;");
+                },
+            );
+        };
+
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                let t = java_type(s);
+                w.line(doc)
+                    .block(format!("public {} {}() {{", t, member), "}", |w| {
+                        w.line(format!("return {};", load(s, &index)));
+                    })
+                    .blank();
+                setter(w, &format!("final {} value", t), &store(s, &index, "value"));
+                let native = match s {
+                    ScalarType::Timestamp(t) => Some((
+                        "Instant",
+                        "an Instant",
+                        format!("Instant.EPOCH.plus({{}}(), {})", chrono_unit(t.unit)),
+                        format!("{}.between(Instant.EPOCH, value)", chrono_unit(t.unit)),
+                    )),
+                    ScalarType::Duration(d) => Some((
+                        "Duration",
+                        "a Duration",
+                        format!("Duration.of({{}}(), {})", chrono_unit(d.unit)),
+                        format!("value.dividedBy({}.getDuration())", chrono_unit(d.unit)),
+                    )),
+                    ScalarType::Decimal(d) => {
+                        let scale = format!("-{}_EXPONENT", constant_name(&f.name));
+                        let u64 = d.mantissa == Mantissa::Unsigned(Unsigned::U64);
+                        Some((
+                            "BigDecimal",
+                            "a BigDecimal, setting it throws ArithmeticException unless it fits",
+                            match u64 {
+                                true => format!(
+                                    "new BigDecimal(new BigInteger(Long.toUnsignedString({{}}())), {})",
+                                    scale
+                                ),
+                                false => format!("BigDecimal.valueOf({{}}(), {})", scale),
+                            },
+                            format!(
+                                "value.setScale({}, RoundingMode.UNNECESSARY).unscaledValue().{}()",
+                                scale,
+                                match u64 {
+                                    true => "longValue",
+                                    false => match t {
+                                        "byte" => "byteValueExact",
+                                        "short" => "shortValueExact",
+                                        "int" => "intValueExact",
+                                        _ => "longValueExact",
+                                    },
+                                }
+                            ),
+                        ))
+                    }
+                    _ => None,
+                };
+                if let Some((class, what, load, store)) = native {
+                    let name = format!("{}{}", member, class);
+                    w.blank()
+                        .line(format!("/** {} as {} */", f.name, what))
+                        .block(format!("public {} {}() {{", class, name), "}", |w| {
+                            w.line(format!("return {};", load.replace("{}", &member)));
+                        })
+                        .blank()
+                        .block(
+                            format!("public {} {}(final {} value) {{", ty, name, class),
+                            "}",
+                            |w| {
+                                w.line(format!("{}({});", member, store))
+                                    .line("return this;");
+                            },
+                        );
+                }
+            }
+            FieldType_::Enum(et) => {
+                let t = class_name(&et.name);
+                let raw = java_type(&et.underlying);
+                let raw_member = format!("{}Raw", member);
+                match et.kind {
+                    EnumKind::Enum => {
+                        w.line(doc)
+                            .block(format!("public {} {}() {{", t, member), "}", |w| {
+                                w.line(format!("return {}.get({}());", t, raw_member));
+                            })
+                            .blank();
+                        setter(
+                            w,
+                            &format!("final {} value", t),
+                            &store(&et.underlying, &index, "value.value()"),
+                        );
+                        w.blank()
+                            .block(format!("public {} {}() {{", raw, raw_member), "}", |w| {
+                                w.line(format!("return {};", load(&et.underlying, &index)));
+                            })
+                            .blank()
+                            .block(
+                                format!("public {} {}(final {} value) {{", ty, raw_member, raw),
+                                "}",
+                                |w| {
+                                    w.line(store(&et.underlying, &index, "value"))
+                                        .line("return this;");
+                                },
+                            );
+                    }
+                    EnumKind::Bitflags => {
+                        w.line(format!(
+                            "/** {} at offset {}, the flags are the constants of {} */",
+                            f.field_type, f.offset, t
+                        ))
+                        .block(format!("public {} {}() {{", raw, member), "}", |w| {
+                            w.line(format!("return {};", load(&et.underlying, &index)));
+                        })
+                        .blank();
+                        setter(
+                            w,
+                            &format!("final {} value", raw),
+                            &store(&et.underlying, &index, "value"),
+                        );
+                    }
+                }
+            }
+            FieldType_::Struct(st) => {
+                w.line(doc).block(
+                    format!("public {} {}() {{", class_name(&st.name), member),
+                    "}",
+                    |w| {
+                        w.line(format!("return {}.wrap(buffer, {});", member, index));
+                    },
+                );
+            }
+            FieldType_::Union(ut) => {
+                w.line(format!(
+                    "/** {} at offset {}, the active variant is selected by {} */",
+                    f.field_type, f.offset, ut.discriminator
+                ))
+                .block(
+                    format!("public {} {}() {{", class_name(&ut.name), member),
+                    "}",
+                    |w| {
+                        w.line(format!("return {}.wrap(buffer, {});", member, index));
+                    },
+                );
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                let length = format!("{}_LENGTH", constant_name(&f.name));
+                let pad = match padding {
+                    AsciiPadding::Nul => "'\\0'",
+                    AsciiPadding::Space => "' '",
+                };
+                w.line(doc)
+                    .block(format!("public CharSequence {}() {{", member), "}", |w| {
+                        w.line(format!(
+                            "return {}.wrap(buffer, {}, {}, {});",
+                            member, index, length, pad
+                        ));
+                    })
+                    .blank()
+                    .line("/** Fails if the value is longer than the field or isn't ascii */");
+                setter(
+                    w,
+                    "final CharSequence value",
+                    &format!(
+                        "AsciiSequence.put(buffer, {}, {}, {}, value, \"{}.{}\");",
+                        index, length, pad, ty, f.name
+                    ),
+                );
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                let length = format!("{}_LENGTH", constant_name(&f.name));
+                let capitalized = pascal_case(&f.name);
+                w.line(format!(
+                    "/** {} at offset {}, copied into dst from dstOffset */",
+                    f.field_type, f.offset
+                ))
+                .block(
+                    format!(
+                        "public void get{}(final byte[] dst, final int dstOffset) {{",
+                        capitalized
+                    ),
+                    "}",
+                    |w| {
+                        w.line(format!(
+                            "Objects.checkFromIndexSize(dstOffset, {}, dst.length);",
+                            length
+                        ))
+                        .block(
+                            format!("for (int i = 0; i < {}; i++) {{", length),
+                            "}",
+                            |w| {
+                                w.line(format!("dst[dstOffset + i] = buffer.get({} + i);", index));
+                            },
+                        );
+                    },
+                )
+                .blank()
+                .line("/** Copies the field from src starting at srcOffset */")
+                .block(
+                    format!(
+                        "public {} put{}(final byte[] src, final int srcOffset) {{",
+                        ty, capitalized
+                    ),
+                    "}",
+                    |w| {
+                        w.line(format!(
+                            "Objects.checkFromIndexSize(srcOffset, {}, src.length);",
+                            length
+                        ))
+                        .block(
+                            format!("for (int i = 0; i < {}; i++) {{", length),
+                            "}",
+                            |w| {
+                                w.line(format!("buffer.put({} + i, src[srcOffset + i]);", index));
+                            },
+                        )
+                        .line("return this;");
+                    },
+                );
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                let t = java_type(scalar);
+                let length = format!("{}_LENGTH", constant_name(&f.name));
+                let element = format!("{} + index * {}", index, scalar.size_bytes());
+                w.line(doc)
+                    .block(
+                        format!("public {} {}(final int index) {{", t, member),
+                        "}",
+                        |w| {
+                            w.line(format!("Objects.checkIndex(index, {});", length))
+                                .line(format!("return {};", load(scalar, &element)));
+                        },
+                    )
+                    .blank();
+                setter(
+                    w,
+                    &format!("final int index, final {} value", t),
+                    &format!(
+                        "Objects.checkIndex(index, {});\n{}",
+                        length,
+                        store(scalar, &element, "value")
+                    ),
+                );
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    /// Writes an accessor for every variant of the union field `f` which throws unless the
+    /// discriminator selects that variant
+    fn write_variant_accessors(&self, w: &mut CodeWriter, f: &Field, ut: &UnionType) {
+        let union = self
+            .protocol
+            .union(&ut.name)
+            .expect("the union of a resolved field is declared");
+        let member = member_name(&f.name);
+        let discriminator = member_name(&ut.discriminator);
+        for v in &union.variants {
+            let variant = member_name(&v.name);
+            let selector = format!(
+                "{}.{}",
+                class_name(&union.discriminator_type),
+                KEYWORDS.escape(&v.selector)
+            );
+            let checked = format!("{}{}", member, pascal_case(&v.name));
+            // the same signature as the variant's getter on the union
+            let (returns, name, getter, params, args) = match &v.field_type {
+                FieldType_::Scalar(s) => (java_type(s).to_string(), checked, variant, "", ""),
+                FieldType_::Enum(et) => match et.kind {
+                    EnumKind::Enum => (class_name(&et.name), checked, variant, "", ""),
+                    EnumKind::Bitflags => (
+                        java_type(&et.underlying).to_string(),
+                        checked,
+                        variant,
+                        "",
+                        "",
+                    ),
+                },
+                FieldType_::Struct(st) => (class_name(&st.name), checked, variant, "", ""),
+                FieldType_::Union(u) => (class_name(&u.name), checked, variant, "", ""),
+                FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                    (String::from("CharSequence"), checked, variant, "", "")
+                }
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => (
+                    String::from("void"),
+                    format!("get{}", pascal_case(&checked)),
+                    format!("get{}", pascal_case(&v.name)),
+                    "final byte[] dst, final int dstOffset",
+                    "dst, dstOffset",
+                ),
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => (
+                    java_type(scalar).to_string(),
+                    checked,
+                    variant,
+                    "final int index",
+                    "index",
+                ),
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            };
+            let call = format!("{}().{}({})", member, getter, args);
+            w.blank()
+                .line(format!(
+                    "/** {}, throws IllegalStateException unless {} is {} */",
+                    call, discriminator, v.selector
+                ))
+                .block(
+                    format!("public {} {}({}) {{", returns, name, params),
+                    "}",
+                    |w| {
+                        w.block(
+                            format!("if ({}Raw() != {}.value()) {{", discriminator, selector),
+                            "}",
+                            |w| {
+                                w.line(format!(
+                                "throw new IllegalStateException(\"{} is not {}, it is \" + {}());",
+                                discriminator, v.selector, discriminator
+                            ));
+                            },
+                        );
+                        match returns.as_str() {
+                            "void" => w.line(format!("{};", call)),
+                            _ => w.line(format!("return {};", call)),
+                        };
+                    },
+                );
+        }
+    }
+
+    fn write_to_string(&self, w: &mut CodeWriter, ty: &str, layout: &Layout) {
+        w.line("@Override")
+            .block("public String toString() {", "}", |w| {
+                w.line(format!(
+                    "final StringBuilder text = new StringBuilder(\"{}{{\");",
+                    ty
+                ));
+                for (i, f) in layout.fields().enumerate() {
+                    let member = member_name(&f.name);
+                    let separator = if i == 0 { "" } else { ", " };
+                    let value = match &f.field_type {
+                        FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                            format!("hex({}, {}_LENGTH)", f.offset, constant_name(&f.name))
+                        }
+                        FieldType_::Vector(ArrayLike::FixedArray { length, .. }) => {
+                            let elements: Vec<String> =
+                                (0..*length).map(|i| format!("{}({})", member, i)).collect();
+                            format!("\"[\" + {} + \"]\"", elements.join(" + \", \" + "))
+                        }
+                        FieldType_::Enum(et) if et.kind == EnumKind::Bitflags => {
+                            format!("{}.toString({}())", class_name(&et.name), member)
+                        }
+                        FieldType_::Union(ut) => {
+                            let discriminator = layout.field(&ut.discriminator);
+                            match discriminator.map(|d| &d.field_type) {
+                                Some(FieldType_::Enum(et)) if et.kind == EnumKind::Enum => format!(
+                                    "{}().toString({}Raw())",
+                                    member,
+                                    member_name(&ut.discriminator)
+                                ),
+                                _ => format!(
+                                    "{}().toString({}())",
+                                    member,
+                                    member_name(&ut.discriminator)
+                                ),
+                            }
+                        }
+                        _ => format!("{}()", member),
+                    };
+                    w.line(format!(
+                        "text.append(\"{}{}=\").append({});",
+                        separator, member, value
+                    ));
+                }
+                w.line("return text.append('}').toString();");
+            });
+    }
+
+    fn write_union_to_string(&self, w: &mut CodeWriter, u: &Union) {
+        w.line("/** The variant selected by a discriminator value, the raw bytes if none is */")
+            .block(
+                "public String toString(final long discriminator) {",
+                "}",
+                |w| {
+                    for v in &u.variants {
+                        let member = member_name(&v.name);
+                        let value = match &v.field_type {
+                            FieldType_::Vector(ArrayLike::FixedArray { length, .. }) => {
+                                let elements: Vec<String> =
+                                    (0..*length).map(|i| format!("{}({})", member, i)).collect();
+                                format!("\"[\" + {} + \"]\"", elements.join(" + \", \" + "))
+                            }
+                            FieldType_::Vector(ArrayLike::Bytes { .. }) | FieldType_::Union(_) => {
+                                String::from("toString()")
+                            }
+                            _ => format!("{}()", member),
+                        };
+                        w.block(
+                            format!("if (discriminator == {}L) {{", v.value as i64),
+                            "}",
+                            |w| {
+                                w.line(format!("return \"{}=\" + {};", member, value));
+                            },
+                        );
+                    }
+                    w.line("return toString();");
+                },
+            )
+            .blank()
+            .line("@Override")
+            .block("public String toString() {", "}", |w| {
+                w.line("return hex(0, SIZE);");
+            });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::java::JavaGenerator;
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+    use std::path::Path;
+
+    fn options(package: &str) -> GeneratorOptions {
+        GeneratorOptions {
+            parameters: vec![(String::from("package"), String::from(package))],
+        }
+    }
+
+    #[test]
+    fn test_maven_layout() {
+        let protocol = crate::sssc::compile("protocol foo.bar; message M [1] { u8: a; }").unwrap();
+        let files = JavaGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!(
+            Path::new("src/main/java/foo/bar/M.java"),
+            files.last().unwrap().path
+        );
+        assert!(files.last().unwrap().contents.contains("package foo.bar;"));
+
+        let files = JavaGenerator
+            .generate(&protocol, &options("com.example.risk"))
+            .unwrap();
+        assert_eq!(
+            Path::new("src/main/java/com/example/risk/M.java"),
+            files.last().unwrap().path
+        );
+    }
+
+    #[test]
+    fn test_invalid_package() {
+        let protocol = crate::sssc::compile("protocol foo; message M [1] { u8: a; }").unwrap();
+        assert!(matches!(
+            JavaGenerator.generate(&protocol, &options("com.class")),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+    }
+
+    #[test]
+    fn test_null_val_must_be_free() {
+        let protocol = crate::sssc::compile(
+            "protocol foo; enum E : u8 { A = 1, B = 255 } message M [1] { E: e; }",
+        )
+        .unwrap();
+        assert!(matches!(
+            JavaGenerator.generate(&protocol, &GeneratorOptions::default()),
+            Err(GeneratorError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::data_types::FieldType_;
+use crate::protocol::{Protocol, Struct, Union};
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::path::PathBuf;
+
+pub use naming::{camel_case, pascal_case, screaming_snake_case, snake_case, words, Keywords};
+pub use writer::CodeWriter;
+
+pub mod c;
+pub mod cpp;
+pub mod csharp;
+pub mod docs;
+pub mod go;
+pub mod java;
+pub mod naming;
+#[cfg(feature = "serde")]
+pub mod plugin;
+pub mod python;
+pub mod rust;
+pub mod typescript;
+pub mod wireshark;
+pub mod writer;
+
+/// Turns a resolved protocol into source files for some target language
+pub trait Generator {
+    /// The name used to select the generator, e.g. `sssc build --lang rust`
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError>;
+
+    /// Like `generate` but writes warnings to `warnings` rather than to the process' stderr, this
+    /// is what `sssc` calls so a tool embedding it sees them on the writer it passed in
+    fn generate_reporting(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+        _warnings: &mut dyn Write,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        self.generate(protocol, options)
+    }
+}
+
+/// `key=value` parameters passed through to a generator, e.g. `sssc build --opt package=foo`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    pub parameters: Vec<(String, String)>,
+}
+
+impl GeneratorOptions {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Fails if any parameter isn't one of `known`, so typos don't go unnoticed
+    pub fn check_known(&self, known: &[&str]) -> Result<(), GeneratorError> {
+        match self
+            .parameters
+            .iter()
+            .find(|(n, _)| !known.contains(&n.as_str()))
+        {
+            Some((name, _)) => Err(GeneratorError::UnknownOption(name.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A generated file, the path is relative to the output directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+impl OutputFile {
+    pub fn new(path: impl Into<PathBuf>, contents: String) -> Self {
+        OutputFile {
+            path: path.into(),
+            contents,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GeneratorError {
+    UnknownOption(String),
+    InvalidOption {
+        name: String,
+        reason: String,
+    },
+    /// The protocol uses something the target language can't represent
+    Unsupported(String),
+    Failed(String),
+}
+
+impl Display for GeneratorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorError::UnknownOption(name) => write!(f, "unknown option '{}'", name),
+            GeneratorError::InvalidOption { name, reason } => {
+                write!(f, "invalid option '{}': {}", name, reason)
+            }
+            GeneratorError::Unsupported(what) => write!(f, "unsupported: {}", what),
+            GeneratorError::Failed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// The generators `sssc build` can choose from by name
+pub struct Registry {
+    generators: Vec<Box<dyn Generator>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry { generators: vec![] }
+    }
+
+    /// A registry holding every generator built into the crate
+    pub fn with_builtins() -> Self {
+        let mut registry = Registry::new();
+        registry.register(Box::new(rust::RustGenerator));
+        registry.register(Box::new(cpp::CppGenerator));
+        registry.register(Box::new(c::CGenerator));
+        registry.register(Box::new(python::PythonGenerator));
+        registry.register(Box::new(java::JavaGenerator));
+        registry.register(Box::new(csharp::CSharpGenerator));
+        registry.register(Box::new(go::GoGenerator));
+        registry.register(Box::new(typescript::TypeScriptGenerator));
+        registry.register(Box::new(wireshark::WiresharkGenerator));
+        registry.register(Box::new(docs::DocsGenerator));
+        registry
+    }
+
+    /// Adds a generator, replacing any already registered with the same name
+    pub fn register(&mut self, generator: Box<dyn Generator>) {
+        self.generators.retain(|g| g.name() != generator.name());
+        self.generators.push(generator);
+    }
+
+    pub fn find(&self, name: &str) -> Option<&dyn Generator> {
+        self.generators
+            .iter()
+            .find(|g| g.name() == name)
+            .map(|g| g.as_ref())
+    }
+
+    pub fn generators(&self) -> impl Iterator<Item = &dyn Generator> {
+        self.generators.iter().map(|g| g.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Registry::with_builtins()
+    }
+}
+
+/// The comment placed at the top of every generated file, each line is prefixed with `comment`
+pub fn file_header(comment: &str, protocol: &Protocol) -> String {
+    format!(
+        "{c} Generated by sssc {} from protocol {}, do not edit.\n",
+        env!("CARGO_PKG_VERSION"),
+        protocol.qualified_name(),
+        c = comment
+    )
+}
+
+/// A struct or union declared in a protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDeclaration<'p> {
+    Struct(&'p Struct),
+    Union(&'p Union),
+}
+
+/// Every struct and union, each one after the types it contains, for languages which need a type
+/// declared before it is used
+pub fn type_order(protocol: &Protocol) -> Vec<TypeDeclaration<'_>> {
+    fn visit<'p>(protocol: &'p Protocol, name: &str, order: &mut Vec<TypeDeclaration<'p>>) {
+        let declaration = match (protocol.structure(name), protocol.union(name)) {
+            (Some(s), _) => TypeDeclaration::Struct(s),
+            (_, Some(u)) => TypeDeclaration::Union(u),
+            _ => return,
+        };
+        if order.contains(&declaration) {
+            return;
+        }
+        let contained: Vec<&FieldType_> = match declaration {
+            TypeDeclaration::Struct(s) => s.layout.fields().map(|f| &f.field_type).collect(),
+            TypeDeclaration::Union(u) => u.variants.iter().map(|v| &v.field_type).collect(),
+        };
+        for field_type in contained {
+            match field_type {
+                FieldType_::Struct(st) => visit(protocol, &st.name, order),
+                FieldType_::Union(ut) => visit(protocol, &ut.name, order),
+                _ => {}
+            }
+        }
+        order.push(declaration);
+    }
+
+    let mut order = vec![];
+    for s in &protocol.structs {
+        visit(protocol, &s.name, &mut order);
+    }
+    for u in &protocol.unions {
+        visit(protocol, &u.name, &mut order);
+    }
+    order
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::{
+        type_order, Generator, GeneratorError, GeneratorOptions, OutputFile, Registry,
+        TypeDeclaration,
+    };
+    use crate::protocol::Protocol;
+
+    struct Fixed(&'static str, &'static str);
+
+    impl Generator for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "test generator"
+        }
+
+        fn generate(
+            &self,
+            protocol: &Protocol,
+            _options: &GeneratorOptions,
+        ) -> Result<Vec<OutputFile>, GeneratorError> {
+            Ok(vec![OutputFile::new(
+                format!("{}.txt", protocol.qualified_name()),
+                String::from(self.1),
+            )])
+        }
+    }
+
+    #[test]
+    fn test_registry_replaces_by_name() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Fixed("a", "first")));
+        registry.register(Box::new(Fixed("b", "other")));
+        registry.register(Box::new(Fixed("a", "second")));
+
+        assert_eq!(2, registry.generators().count());
+        assert!(registry.find("c").is_none());
+
+        let protocol = crate::sssc::compile("protocol foo;").unwrap();
+        let files = registry
+            .find("a")
+            .unwrap()
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!("second", files[0].contents);
+    }
+
+    #[test]
+    fn test_type_order() {
+        let protocol = crate::sssc::compile(
+            "protocol foo;\
+            enum K : u8 { A = 1, B = 2 }\
+            struct Outer { K: k; @discriminator(k) U: u; }\
+            union U : K { @when(A) Inner: a; @when(B) u8: b; }\
+            struct Inner { u8: x; }",
+        )
+        .unwrap();
+
+        let names: Vec<&str> = type_order(&protocol)
+            .iter()
+            .map(|d| match d {
+                TypeDeclaration::Struct(s) => s.name.as_str(),
+                TypeDeclaration::Union(u) => u.name.as_str(),
+            })
+            .collect();
+        assert_eq!(vec!["Inner", "U", "Outer"], names);
+    }
+
+    #[test]
+    fn test_options() {
+        let options = GeneratorOptions {
+            parameters: vec![
+                (String::from("package"), String::from("a")),
+                (String::from("package"), String::from("b")),
+            ],
+        };
+        assert_eq!(Some("b"), options.get("package"));
+        assert_eq!(Ok(()), options.check_known(&["package"]));
+        assert_eq!(
+            Err(GeneratorError::UnknownOption(String::from("package"))),
+            options.check_known(&["namespace"])
+        );
+    }
+}
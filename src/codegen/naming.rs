@@ -0,0 +1,113 @@
+/// Splits an identifier into lower case words, whatever convention it was written in, e.g.
+/// `HTTPServer_id2` becomes `["http", "server", "id2"]`
+pub fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            // a new word starts at `aB` and at the last capital of an acronym in `ABc`
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn snake_case(name: &str) -> String {
+    words(name).join("_")
+}
+
+pub fn screaming_snake_case(name: &str) -> String {
+    snake_case(name).to_uppercase()
+}
+
+pub fn pascal_case(name: &str) -> String {
+    words(name).iter().map(|w| capitalise(w)).collect()
+}
+
+pub fn camel_case(name: &str) -> String {
+    let words = words(name);
+    let mut result = String::new();
+    for (i, w) in words.iter().enumerate() {
+        if i == 0 {
+            result.push_str(w);
+        } else {
+            result.push_str(&capitalise(w));
+        }
+    }
+    result
+}
+
+/// The reserved words of a target language, names which collide with one get a `_` suffix
+pub struct Keywords(pub &'static [&'static str]);
+
+impl Keywords {
+    pub fn is_keyword(&self, name: &str) -> bool {
+        self.0.contains(&name)
+    }
+
+    pub fn escape(&self, name: &str) -> String {
+        if self.is_keyword(name) {
+            format!("{}_", name)
+        } else {
+            String::from(name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::naming::{
+        camel_case, pascal_case, screaming_snake_case, snake_case, words, Keywords,
+    };
+
+    #[test]
+    fn test_words() {
+        assert_eq!(vec!["http", "server", "id2"], words("HTTPServer_id2"));
+        assert_eq!(vec!["event", "request"], words("EventRequest"));
+        assert_eq!(vec!["quantity", "requested"], words("quantity_requested"));
+        assert_eq!(vec!["flag", "1"], words("FLAG_1"));
+    }
+
+    #[test]
+    fn test_conventions() {
+        assert_eq!("event_request", snake_case("EventRequest"));
+        assert_eq!("EVENT_REQUEST", screaming_snake_case("eventRequest"));
+        assert_eq!("EventRequest", pascal_case("event_request"));
+        assert_eq!("eventRequest", camel_case("EVENT_REQUEST"));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let keywords = Keywords(&["type", "struct"]);
+        assert_eq!("type_", keywords.escape("type"));
+        assert_eq!("kind", keywords.escape("kind"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Generators running as separate executables, see `docs/plugin-protocol.md` for the format of
+//! the messages exchanged with them.
+
+use crate::codegen::{Generator, GeneratorError, GeneratorOptions, OutputFile};
+use crate::protocol::Protocol;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Bumped whenever the request or response format changes incompatibly
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Plugins are executables named with this prefix followed by the language, e.g. `sss-gen-kotlin`
+pub const PLUGIN_PREFIX: &str = "sss-gen-";
+
+/// Written to the plugin's stdin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginRequest {
+    pub version: u32,
+    pub compiler_version: String,
+    pub parameters: BTreeMap<String, String>,
+    pub protocol: Protocol,
+}
+
+/// Read from the plugin's stdout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginResponse {
+    pub version: u32,
+    #[serde(default)]
+    pub files: Vec<PluginFile>,
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginFile {
+    pub path: String,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl PluginRequest {
+    pub fn new(protocol: &Protocol, options: &GeneratorOptions) -> Self {
+        PluginRequest {
+            version: PLUGIN_PROTOCOL_VERSION,
+            compiler_version: String::from(env!("CARGO_PKG_VERSION")),
+            parameters: options.parameters.iter().cloned().collect(),
+            protocol: protocol.clone(),
+        }
+    }
+}
+
+/// A generator implemented by an external executable
+pub struct PluginGenerator {
+    name: String,
+    executable: PathBuf,
+}
+
+impl PluginGenerator {
+    pub fn new(name: impl Into<String>, executable: impl Into<PathBuf>) -> Self {
+        PluginGenerator {
+            name: name.into(),
+            executable: executable.into(),
+        }
+    }
+
+    pub fn executable(&self) -> &Path {
+        &self.executable
+    }
+
+    /// Runs the plugin, whatever it writes to its stderr is copied to `stderr`
+    fn run(
+        &self,
+        request: &PluginRequest,
+        stderr: &mut dyn Write,
+    ) -> Result<PluginResponse, GeneratorError> {
+        let failed = |what: &str, e: &dyn std::fmt::Display| {
+            GeneratorError::Failed(format!(
+                "plugin '{}' {}: {}",
+                self.executable.display(),
+                what,
+                e
+            ))
+        };
+
+        let input = serde_json::to_vec(request).expect("plugin requests are always serializable");
+        let mut child = Command::new(&self.executable)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed("could not be started", &e))?;
+
+        // the request is written from another thread so a plugin which starts writing its
+        // response before reading all of its input can't deadlock against us
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = std::thread::spawn(move || stdin.write_all(&input));
+        let output = child
+            .wait_with_output()
+            .map_err(|e| failed("could not be run", &e))?;
+        // a plugin may exit without reading its input, that is only an error if it also failed
+        let written = writer.join().expect("the writer thread doesn't panic");
+        _ = stderr.write_all(&output.stderr);
+
+        if !output.status.success() {
+            return Err(failed("failed", &output.status));
+        }
+        written.map_err(|e| failed("did not read its request", &e))?;
+
+        let response: PluginResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|e| failed("wrote an invalid response", &e))?;
+        if response.version != PLUGIN_PROTOCOL_VERSION {
+            return Err(GeneratorError::Failed(format!(
+                "plugin '{}' responded with protocol version {} but version {} is required",
+                self.executable.display(),
+                response.version,
+                PLUGIN_PROTOCOL_VERSION
+            )));
+        }
+        Ok(response)
+    }
+}
+
+impl Generator for PluginGenerator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "external plugin"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        self.generate_reporting(protocol, options, &mut std::io::stderr())
+    }
+
+    fn generate_reporting(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+        warnings: &mut dyn Write,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        let response = self.run(&PluginRequest::new(protocol, options), warnings)?;
+
+        let mut errors: Vec<&str> = vec![];
+        for diagnostic in &response.diagnostics {
+            match diagnostic.severity {
+                Severity::Error => errors.push(&diagnostic.message),
+                Severity::Warning => {
+                    _ = writeln!(warnings, "warning: {}: {}", self.name, diagnostic.message);
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(GeneratorError::Failed(errors.join("\n")));
+        }
+
+        Ok(response
+            .files
+            .into_iter()
+            .map(|f| OutputFile::new(f.path, f.contents))
+            .collect())
+    }
+}
+
+/// Looks for an executable named `sss-gen-<name>` on the `PATH`
+pub fn find_plugin(name: &str) -> Option<PluginGenerator> {
+    let file_name = format!("{}{}{}", PLUGIN_PREFIX, name, std::env::consts::EXE_SUFFIX);
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+        .map(|executable| PluginGenerator::new(name, executable))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::plugin::{
+        Diagnostic, PluginRequest, PluginResponse, Severity, PLUGIN_PROTOCOL_VERSION,
+    };
+    use crate::codegen::GeneratorOptions;
+
+    #[test]
+    fn test_request_round_trips() {
+        let protocol = crate::sssc::compile("protocol foo; message bar [1] { u8: a; }").unwrap();
+        let options = GeneratorOptions {
+            parameters: vec![(String::from("package"), String::from("com.foo"))],
+        };
+        let request = PluginRequest::new(&protocol, &options);
+
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: PluginRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request, parsed);
+        assert_eq!(PLUGIN_PROTOCOL_VERSION, parsed.version);
+        assert_eq!("com.foo", parsed.parameters["package"]);
+    }
+
+    #[test]
+    fn test_response_fields_default() {
+        let response: PluginResponse = serde_json::from_str(
+            r#"{"version": 1, "diagnostics": [{"severity": "warning", "message": "hm"}]}"#,
+        )
+        .unwrap();
+        assert!(response.files.is_empty());
+        assert_eq!(
+            vec![Diagnostic {
+                severity: Severity::Warning,
+                message: String::from("hm")
+            }],
+            response.diagnostics
+        );
+    }
+}
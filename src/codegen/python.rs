@@ -0,0 +1,1021 @@
+//! Generates a Python module with a class per message, struct and union, each class wraps a
+//! `memoryview` and decodes its fields with precompiled `struct.Struct`s only when they're read.
+//! A `.pyi` stub next to the module gives type checkers the types of every field.
+
+use crate::codegen::{
+    file_header, pascal_case, snake_case, type_order, CodeWriter, Generator, GeneratorError,
+    GeneratorOptions, Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Mantissa, TimeUnit};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, UnionType};
+use crate::protocol::{
+    ByteOrder, Enum, Field, Header, Layout, Member, Protocol, Union, UnionVariant,
+};
+
+const KEYWORDS: Keywords = Keywords(&[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+]);
+
+/// Members every generated class has, a field with one of these names gets a `_` suffix
+const RESERVED_MEMBERS: Keywords = Keywords(&[
+    "ID",
+    "SIZE",
+    "FORMAT",
+    "OFFSETS",
+    "FRAME_SIZE",
+    "decode",
+    "encode",
+    "encode_frame",
+    "unpack",
+    "to_dict",
+    "select",
+]);
+
+/// The class generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Private helpers at the top of every generated module
+const RUNTIME: &str = r#"def _view(buf, size, name):
+    view = memoryview(buf).cast("B")
+    if len(view) < size:
+        raise ValueError(f"{name} needs {size} bytes but the buffer holds {len(view)}")
+    return view[:size]
+
+
+def _get_ascii(view, offset, length, pad):
+    raw = bytes(view[offset:offset + length])
+    end = raw.find(b"\0")
+    return (raw if end < 0 else raw[:end]).rstrip(pad).decode("ascii")
+
+
+def _set_ascii(view, offset, length, pad, value, field):
+    raw = value.encode("ascii")
+    if len(raw) > length:
+        raise ValueError(f"{field} holds at most {length} characters but {value!r} has {len(raw)}")
+    view[offset:offset + length] = raw.ljust(length, pad)
+
+
+def _set_bytes(view, offset, length, value, field):
+    if len(value) != length:
+        raise ValueError(f"{field} holds {length} bytes but {len(value)} were given")
+    view[offset:offset + length] = bytes(value)
+
+
+def _enum(cls, raw):
+    """The member of cls for raw, or raw itself when the value isn't declared"""
+    try:
+        return cls(raw)
+    except ValueError:
+        return raw
+
+
+def _plain(value):
+    return value.to_dict() if hasattr(value, "to_dict") else value"#;
+
+/// Generates a module and its type stubs
+pub struct PythonGenerator;
+
+impl Generator for PythonGenerator {
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn description(&self) -> &str {
+        "Python module over memoryview with type stubs"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&[])?;
+
+        let module = PythonModule {
+            protocol,
+            byte_order: match protocol.byte_order {
+                ByteOrder::Little => '<',
+                ByteOrder::Big => '>',
+            },
+        };
+        let name = snake_case(&protocol.name.join("_"));
+        let (source, stub) = module.generate()?;
+        Ok(vec![
+            OutputFile::new(format!("{}.py", name), source),
+            OutputFile::new(format!("{}.pyi", name), stub),
+        ])
+    }
+}
+
+struct PythonModule<'p> {
+    protocol: &'p Protocol,
+    /// The `struct` module's byte order prefix
+    byte_order: char,
+}
+
+fn class_name(name: &str) -> String {
+    KEYWORDS.escape(&pascal_case(name))
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&KEYWORDS.escape(&snake_case(name)))
+}
+
+/// The `struct` format character of a scalar
+fn format_char(scalar: &ScalarType) -> char {
+    match scalar {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => 'B',
+            2 => 'H',
+            4 => 'I',
+            _ => 'Q',
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => 'b',
+            2 => 'h',
+            4 => 'i',
+            _ => 'q',
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => 'f',
+            _ => 'd',
+        },
+        ScalarType::ByteSized(ByteSize::Byte | ByteSize::Char | ByteSize::Ascii) => 'B',
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => format_char(&ScalarType::Unsigned(u)),
+            Mantissa::Signed(s) => format_char(&ScalarType::Signed(s)),
+        },
+        ScalarType::Timestamp(t) => format_char(&t.storage()),
+        ScalarType::Duration(d) => format_char(&d.storage()),
+    }
+}
+
+fn python_type(scalar: &ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Float(_) => "float",
+        _ => "int",
+    }
+}
+
+/// The property suffix, type and conversions from and to the raw count of a timestamp or
+/// duration, `datetime` only has microseconds so nanoseconds are truncated
+fn native_time(scalar: &ScalarType) -> Option<(&'static str, &'static str, String, String)> {
+    let delta = |unit: TimeUnit| match unit {
+        TimeUnit::Seconds => (
+            "datetime.timedelta(seconds={})",
+            "datetime.timedelta(seconds=1)",
+        ),
+        TimeUnit::Millis => (
+            "datetime.timedelta(milliseconds={})",
+            "datetime.timedelta(milliseconds=1)",
+        ),
+        TimeUnit::Micros | TimeUnit::Nanos => (
+            "datetime.timedelta(microseconds={})",
+            "datetime.timedelta(microseconds=1)",
+        ),
+    };
+    let (unit, ty, suffix, epoch) = match scalar {
+        ScalarType::Timestamp(t) => (t.unit, "datetime.datetime", "datetime", "_EPOCH + "),
+        ScalarType::Duration(d) => (d.unit, "datetime.timedelta", "timedelta", ""),
+        _ => return None,
+    };
+    let (to, one) = delta(unit);
+    let (raw, scale) = match unit {
+        TimeUnit::Nanos => ("self.{} // 1000", " * 1000"),
+        _ => ("self.{}", ""),
+    };
+    let since = match epoch {
+        "" => "value",
+        _ => "(value - _EPOCH)",
+    };
+    Some((
+        suffix,
+        ty,
+        format!("return {}{}", epoch, to.replace("{}", raw)),
+        format!("self.{{}} = {} // {}{}", since, one, scale),
+    ))
+}
+
+/// A field's Python and stub types, without the enclosing class
+struct Accessor {
+    doc: String,
+    stub_type: String,
+    getter: String,
+    setter: Option<String>,
+    /// The expression `to_dict` uses, which never raises for an undeclared enum value
+    plain: String,
+}
+
+impl PythonModule<'_> {
+    /// A format covering a whole layout, nested structs are flattened and unions kept as bytes
+    fn flat_format(&self, layout: &Layout) -> String {
+        let mut format = String::new();
+        let mut cursor = 0;
+        for member in &layout.members {
+            if member.offset() > cursor {
+                format += &format!("{}x", member.offset() - cursor);
+            }
+            match member {
+                Member::Padding(p) => format += &format!("{}x", p.size),
+                Member::Field(f) => format += &self.field_format(&f.field_type),
+            }
+            cursor = cursor.max(member.offset() + member.size_bytes());
+        }
+        if layout.size > cursor {
+            format += &format!("{}x", layout.size - cursor);
+        }
+        format
+    }
+
+    fn field_format(&self, field_type: &FieldType_) -> String {
+        match field_type {
+            FieldType_::Scalar(s) => format_char(s).to_string(),
+            FieldType_::Enum(et) => format_char(&et.underlying).to_string(),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                format!("{}{}", length, format_char(scalar))
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { length, .. })
+            | FieldType_::Vector(ArrayLike::Bytes { length }) => format!("{}s", length),
+            FieldType_::Struct(st) => match self.protocol.structure(&st.name) {
+                Some(s) => self.flat_format(&s.layout),
+                None => format!("{}s", st.size),
+            },
+            FieldType_::Union(ut) => format!("{}s", ut.size),
+            FieldType_::Padding(size) => format!("{}x", size),
+        }
+    }
+
+    fn generate(&self) -> Result<(String, String), GeneratorError> {
+        let protocol = self.protocol;
+        let has_time =
+            self.uses(|s| matches!(s, ScalarType::Timestamp(_) | ScalarType::Duration(_)));
+        let mut imports = vec!["enum", "struct"];
+        let mut stub_imports = vec!["enum", "typing"];
+        if has_time {
+            imports.push("datetime");
+            stub_imports.push("datetime");
+        }
+        if self.uses(|s| matches!(s, ScalarType::Decimal(_))) {
+            imports.push("decimal");
+            stub_imports.push("decimal");
+        }
+        imports.sort();
+        stub_imports.sort();
+        let mut py = CodeWriter::new("    ");
+        let mut pyi = CodeWriter::new("    ");
+
+        py.line(file_header("#", protocol).trim_end())
+            .line(format!(
+                "\"\"\"Protocol {}\"\"\"",
+                protocol.qualified_name()
+            ))
+            .blank();
+        for import in &imports {
+            py.line(format!("import {}", import));
+        }
+        py.blank().line(format!(
+            "BYTE_ORDER = \"{}\"",
+            match self.byte_order {
+                '<' => "little",
+                _ => "big",
+            }
+        ));
+        if has_time {
+            py.line("_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)");
+        }
+        py.blank().blank().lines(RUNTIME);
+        pyi.line(file_header("#", protocol).trim_end());
+        for import in &stub_imports {
+            pyi.line(format!("import {}", import));
+        }
+        pyi.blank()
+            .line("Buffer = typing.Union[bytes, bytearray, memoryview]")
+            .blank()
+            .line("BYTE_ORDER: str");
+
+        for e in &protocol.enums {
+            py.blank().blank();
+            pyi.blank();
+            self.write_enum(&mut py, &mut pyi, e)?;
+        }
+        for declaration in type_order(protocol) {
+            py.blank().blank();
+            pyi.blank();
+            match declaration {
+                TypeDeclaration::Struct(s) => {
+                    self.write_class(&mut py, &mut pyi, &s.name, None, &s.layout, None)?
+                }
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    self.write_class(&mut py, &mut pyi, &u.name, None, &layout, Some(u))?
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            py.blank().blank();
+            pyi.blank();
+            self.write_class(&mut py, &mut pyi, FRAME_HEADER, None, &header.layout, None)?;
+        }
+        for m in &protocol.messages {
+            py.blank().blank();
+            pyi.blank();
+            self.write_class(&mut py, &mut pyi, &m.name, Some(m.id), &m.layout, None)?;
+        }
+        if let Some(header) = &protocol.header {
+            py.blank().blank();
+            pyi.blank();
+            self.write_dispatch(&mut py, &mut pyi, header);
+        }
+
+        Ok((py.finish(), pyi.finish()))
+    }
+
+    /// A `Handler` base class with a callback per message and the `dispatch` function calling it
+    fn write_dispatch(&self, py: &mut CodeWriter, pyi: &mut CodeWriter, header: &Header) {
+        let messages: Vec<(String, String)> = self
+            .protocol
+            .messages
+            .iter()
+            .map(|m| (class_name(&m.name), format!("on_{}", snake_case(&m.name))))
+            .collect();
+
+        py.line("class Handler:").indent();
+        py.line("\"\"\"Callbacks for dispatch, a message without an override is ignored\"\"\"");
+        for (ty, callback) in &messages {
+            py.blank()
+                .suite(format!("def {}(self, message):", callback), |w| {
+                    w.line(format!("\"\"\"Called with a {}\"\"\"", ty));
+                });
+        }
+        py.dedent().blank().blank();
+        py.block("_HANDLERS = {", "}", |w| {
+            for ((ty, callback), m) in messages.iter().zip(&self.protocol.messages) {
+                w.line(format!("{}: ({}, \"{}\"),", m.id, ty, callback));
+            }
+        });
+        let header_class = class_name(FRAME_HEADER);
+        let id = member_name(&header.id().name);
+        py.blank().blank().suite("def dispatch(buf, handler):", |w| {
+            w.line("\"\"\"Reads the header at the start of buf and calls the handler's callback for its id with the")
+                .line("message after it, returns the size of the frame. Raises ValueError when buf doesn't hold the")
+                .line("whole frame, its length is too small for its message or no message has its id.\"\"\"")
+                .line(format!("header = {}(buf)", header_class))
+                .line(format!("entry = _HANDLERS.get(header.{})", id))
+                .suite("if entry is None:", |w| {
+                    w.line(format!(
+                        "raise ValueError(f\"no message has the id {{header.{}}}\")",
+                        id
+                    ));
+                })
+                .line("cls, callback = entry");
+            match header.length() {
+                Some(length) => {
+                    let length = format!("header.{}", member_name(&length.name));
+                    w.line(match header.length_value(0) {
+                        0 => format!("frame = {}.SIZE + {}", header_class, length),
+                        _ => format!("frame = {}", length),
+                    })
+                    .suite("if frame < cls.FRAME_SIZE:", |w| {
+                        w.line("raise ValueError(f\"a frame length of {frame} is too small for {cls.__name__}\")");
+                    });
+                }
+                None => {
+                    w.line("frame = cls.FRAME_SIZE");
+                }
+            }
+            w.line("view = _view(buf, frame, \"the frame\")")
+                .line(format!(
+                    "getattr(handler, callback)(cls(view[{}.SIZE:]))",
+                    header_class
+                ))
+                .line("return frame");
+        });
+
+        pyi.line("class Handler:").indent();
+        for (ty, callback) in &messages {
+            pyi.line(format!(
+                "def {}(self, message: {}) -> None: ...",
+                callback, ty
+            ));
+        }
+        pyi.dedent()
+            .blank()
+            .line("def dispatch(buf: Buffer, handler: Handler) -> int: ...");
+    }
+
+    fn write_enum(
+        &self,
+        py: &mut CodeWriter,
+        pyi: &mut CodeWriter,
+        e: &Enum,
+    ) -> Result<(), GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let (base, kind) = match e.kind {
+            EnumKind::Enum => ("enum.IntEnum", "enum"),
+            EnumKind::Bitflags => ("enum.IntFlag", "bitflags"),
+        };
+        let ty = class_name(&e.name);
+        py.suite(format!("class {}({}):", ty, base), |w| {
+            w.line(format!(
+                "\"\"\"{} {}, stored as a {}\"\"\"",
+                kind, e.name, e.underlying
+            ))
+            .blank();
+            for v in &e.values {
+                w.line(format!("{} = {}", KEYWORDS.escape(&v.name), v.value));
+            }
+        });
+        pyi.suite(format!("class {}({}):", ty, base), |w| {
+            for v in &e.values {
+                w.line(format!("{} = {}", KEYWORDS.escape(&v.name), v.value));
+            }
+        });
+        Ok(())
+    }
+
+    /// Whether any scalar field matches `wanted`, to only import the modules a protocol needs
+    fn uses(&self, wanted: fn(&ScalarType) -> bool) -> bool {
+        let used = |t: &FieldType_| matches!(t, FieldType_::Scalar(s) if wanted(s));
+        let protocol = self.protocol;
+        protocol
+            .structs
+            .iter()
+            .any(|s| s.layout.fields().any(|f| used(&f.field_type)))
+            || protocol
+                .messages
+                .iter()
+                .any(|m| m.layout.fields().any(|f| used(&f.field_type)))
+            || protocol
+                .unions
+                .iter()
+                .any(|u| u.variants.iter().any(|v| used(&v.field_type)))
+            || protocol
+                .header
+                .as_ref()
+                .is_some_and(|h| h.layout.fields().any(|f| used(&f.field_type)))
+    }
+
+    fn accessor(&self, field: &Field, qualified: &str) -> Accessor {
+        let o = field.offset;
+        let member = member_name(&field.name);
+        let packer = format!("self._{}", member.to_uppercase());
+        let doc = match &field.description {
+            Some(d) => format!("{}: {}", field.field_type, d),
+            None => format!("{} at offset {}", field.field_type, o),
+        };
+        let end = o + field.field_type.size_bytes();
+
+        match &field.field_type {
+            FieldType_::Scalar(s) => Accessor {
+                doc,
+                stub_type: python_type(s).to_string(),
+                getter: format!("return {}.unpack_from(self._buf, {})[0]", packer, o),
+                setter: Some(format!("{}.pack_into(self._buf, {}, value)", packer, o)),
+                plain: format!("self.{}", member),
+            },
+            FieldType_::Enum(et) => {
+                let ty = class_name(&et.name);
+                let doc = match et.kind {
+                    EnumKind::Enum => format!("{}, raises ValueError for an undeclared value", doc),
+                    EnumKind::Bitflags => doc,
+                };
+                Accessor {
+                    doc,
+                    stub_type: ty.clone(),
+                    getter: format!("return {}({}.unpack_from(self._buf, {})[0])", ty, packer, o),
+                    setter: Some(format!("{}.pack_into(self._buf, {}, value)", packer, o)),
+                    plain: format!("_enum({}, self.{}_raw)", ty, member),
+                }
+            }
+            FieldType_::Struct(st) => {
+                let ty = class_name(&st.name);
+                Accessor {
+                    doc: format!("{}, a view sharing the same buffer", doc),
+                    stub_type: ty.clone(),
+                    getter: format!("return {}(self._buf[{}:{}])", ty, o, end),
+                    setter: Some(format!("self._buf[{}:{}] = value.encode()", o, end)),
+                    plain: format!("self.{}.to_dict()", member),
+                }
+            }
+            FieldType_::Union(ut) => {
+                let ty = class_name(&ut.name);
+                Accessor {
+                    doc: format!(
+                        "{}, the active variant is selected by {}",
+                        doc, ut.discriminator
+                    ),
+                    stub_type: ty.clone(),
+                    getter: format!("return {}(self._buf[{}:{}])", ty, o, end),
+                    setter: Some(format!("self._buf[{}:{}] = value.encode()", o, end)),
+                    plain: format!(
+                        "self.{}.to_dict(self.{}_raw)",
+                        member,
+                        member_name(&ut.discriminator)
+                    ),
+                }
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { length, padding }) => {
+                let pad = match padding {
+                    AsciiPadding::Nul => "b\"\\0\"",
+                    AsciiPadding::Space => "b\" \"",
+                };
+                Accessor {
+                    doc,
+                    stub_type: String::from("str"),
+                    getter: format!("return _get_ascii(self._buf, {}, {}, {})", o, length, pad),
+                    setter: Some(format!(
+                        "_set_ascii(self._buf, {}, {}, {}, value, \"{}\")",
+                        o, length, pad, qualified
+                    )),
+                    plain: format!("self.{}", member),
+                }
+            }
+            FieldType_::Vector(ArrayLike::Bytes { length }) => Accessor {
+                doc,
+                stub_type: String::from("bytes"),
+                getter: format!("return bytes(self._buf[{}:{}])", o, end),
+                setter: Some(format!(
+                    "_set_bytes(self._buf, {}, {}, value, \"{}\")",
+                    o, length, qualified
+                )),
+                plain: format!("self.{}", member),
+            },
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => Accessor {
+                doc,
+                stub_type: format!("typing.Tuple[{}, ...]", python_type(scalar)),
+                getter: format!("return {}.unpack_from(self._buf, {})", packer, o),
+                setter: Some(format!("{}.pack_into(self._buf, {}, *value)", packer, o)),
+                plain: format!("self.{}", member),
+            },
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    /// The variants of the union of a field with their member names and accessors
+    fn variants(
+        &self,
+        ut: &UnionType,
+    ) -> Result<Vec<(&UnionVariant, String, Accessor)>, GeneratorError> {
+        let union = self.protocol.union(&ut.name).ok_or_else(|| {
+            GeneratorError::Failed(format!("union '{}' is not declared", ut.name))
+        })?;
+        Ok(union
+            .variants
+            .iter()
+            .map(|v| {
+                let field = Field {
+                    name: v.name.clone(),
+                    offset: 0,
+                    field_type: v.field_type.clone(),
+                    description: None,
+                };
+                let accessor = self.accessor(&field, &format!("{}.{}", ut.name, v.name));
+                (v, member_name(&v.name), accessor)
+            })
+            .collect())
+    }
+
+    fn write_class(
+        &self,
+        py: &mut CodeWriter,
+        pyi: &mut CodeWriter,
+        name: &str,
+        id: Option<usize>,
+        layout: &Layout,
+        union: Option<&Union>,
+    ) -> Result<(), GeneratorError> {
+        let ty = class_name(name);
+        let doc = match (id, union) {
+            (Some(id), _) => format!("message {} [{}]", name, id),
+            (None, Some(u)) => format!(
+                "union {}, the active variant is selected by a {}",
+                u.name, u.discriminator_type
+            ),
+            (None, None) => format!("struct {}", name),
+        };
+        let fields: Vec<(&Field, String, Accessor)> = layout
+            .fields()
+            .map(|f| {
+                let accessor = self.accessor(f, &format!("{}.{}", name, f.name));
+                (f, member_name(&f.name), accessor)
+            })
+            .collect();
+        let decimals: Vec<(String, i8)> = layout
+            .fields()
+            .filter_map(|f| match &f.field_type {
+                FieldType_::Scalar(ScalarType::Decimal(d))
+                | FieldType_::Vector(ArrayLike::FixedArray {
+                    scalar: ScalarType::Decimal(d),
+                    ..
+                }) => Some((member_name(&f.name).to_uppercase(), d.exponent)),
+                _ => None,
+            })
+            .collect();
+
+        py.line(format!("class {}:", ty)).indent();
+        py.line(format!("\"\"\"{}\"\"\"", doc))
+            .blank()
+            .line("__slots__ = (\"_buf\",)")
+            .blank();
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        if let Some(id) = id {
+            py.line(format!("ID = {}", id));
+        }
+        py.line(format!("SIZE = {}", layout.size));
+        if let Some(header) = frame {
+            py.line(format!("FRAME_SIZE = {}", header.layout.size + layout.size));
+        }
+        py.line(format!(
+            "FORMAT = \"{}{}\"",
+            self.byte_order,
+            match union {
+                // the variants overlap, so a union is unpacked as its raw bytes
+                Some(u) => format!("{}s", u.size),
+                None => self.flat_format(layout),
+            }
+        ))
+        .line("_STRUCT = struct.Struct(FORMAT)");
+        py.block("OFFSETS = {", "}", |w| {
+            for (f, _, _) in &fields {
+                w.line(format!("\"{}\": {},", f.name, f.offset));
+            }
+        });
+        for (constant, exponent) in &decimals {
+            py.line(format!("{}_EXPONENT = {}", constant, exponent));
+        }
+        for (f, member, _) in &fields {
+            let format = match &f.field_type {
+                FieldType_::Scalar(s) => format_char(s).to_string(),
+                FieldType_::Enum(et) => format_char(&et.underlying).to_string(),
+                FieldType_::Vector(ArrayLike::FixedArray { .. }) => {
+                    self.field_format(&f.field_type)
+                }
+                _ => continue,
+            };
+            py.line(format!(
+                "_{} = struct.Struct(\"{}{}\")",
+                member.to_uppercase(),
+                self.byte_order,
+                format
+            ));
+        }
+        if let Some(u) = union {
+            py.block("_VARIANTS = {", "}", |w| {
+                for v in &u.variants {
+                    w.line(format!("{}: \"{}\",", v.value, member_name(&v.name)));
+                }
+            });
+        }
+
+        let what = match (id, union) {
+            (Some(_), _) => "message",
+            (None, Some(_)) => "union",
+            (None, None) => "struct",
+        };
+        py.blank()
+            .suite("def __init__(self, buf=None):", |w| {
+                w.line(format!(
+                    "\"\"\"Wraps the first SIZE bytes of buf without copying them, a zeroed {} when buf is None\"\"\"",
+                    what
+                ))
+                .line(format!(
+                    "self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, \"{}\")",
+                    name
+                ));
+            })
+            .blank()
+            .line("@classmethod")
+            .suite("def decode(cls, buf):", |w| {
+                w.line("\"\"\"Reads from buf without copying it, each field is only decoded when it's accessed\"\"\"")
+                    .line("return cls(buf)");
+            })
+            .blank()
+            .suite("def encode(self):", |w| {
+                w.line("return bytes(self._buf)");
+            })
+            .blank();
+        if let Some(header) = frame {
+            let header_class = class_name(FRAME_HEADER);
+            py.suite("def encode_frame(self):", |w| {
+                w.line("\"\"\"The header followed by the message\"\"\"")
+                    .line(format!("header = {}()", header_class))
+                    .line(format!(
+                        "header.{} = self.ID",
+                        member_name(&header.id().name)
+                    ));
+                if let Some(length) = header.length() {
+                    w.line(format!(
+                        "header.{} = {}",
+                        member_name(&length.name),
+                        match header.length_value(0) {
+                            0 => "self.SIZE",
+                            _ => "self.FRAME_SIZE",
+                        }
+                    ));
+                }
+                w.line("return header.encode() + self.encode()");
+            })
+            .blank();
+        }
+        py.suite("def unpack(self):", |w| {
+            w.line("\"\"\"Every raw value at once, in the order of FORMAT\"\"\"")
+                .line("return self._STRUCT.unpack_from(self._buf)");
+        });
+
+        match union {
+            Some(_) => {
+                py.blank()
+                    .suite("def select(self, discriminator):", |w| {
+                        w.line("\"\"\"The variant selected by a discriminator value\"\"\"")
+                            .line("name = self._VARIANTS.get(int(discriminator))")
+                            .suite("if name is None:", |w| {
+                                w.line(format!(
+                                    "raise ValueError(f\"{} has no variant for {{discriminator!r}}\")",
+                                    name
+                                ));
+                            })
+                            .line("return getattr(self, name)");
+                    })
+                    .blank()
+                    .suite("def to_dict(self, discriminator):", |w| {
+                        w.line("\"\"\"The active variant decoded, empty when the discriminator selects none\"\"\"")
+                            .line("name = self._VARIANTS.get(int(discriminator))")
+                            .line("return {} if name is None else {name: _plain(getattr(self, name))}");
+                    });
+            }
+            None => {
+                py.blank()
+                    .suite("def to_dict(self):", |w| {
+                        w.line("\"\"\"Every field decoded, nested structs become dicts too\"\"\"");
+                        w.block("return {", "}", |w| {
+                            for (f, _, accessor) in &fields {
+                                w.line(format!("\"{}\": {},", f.name, accessor.plain));
+                            }
+                        });
+                    })
+                    .blank()
+                    .suite("def __repr__(self):", |w| {
+                        w.line(format!("return f\"{}({{self.to_dict()!r}})\"", ty));
+                    });
+            }
+        }
+
+        for (f, member, accessor) in &fields {
+            py.blank()
+                .line("@property")
+                .suite(format!("def {}(self):", member), |w| {
+                    w.line(format!("\"\"\"{}\"\"\"", accessor.doc))
+                        .line(&accessor.getter);
+                });
+            if let Some(setter) = &accessor.setter {
+                py.blank().line(format!("@{}.setter", member)).suite(
+                    format!("def {}(self, value):", member),
+                    |w| {
+                        w.line(setter);
+                    },
+                );
+            }
+            if let FieldType_::Enum(_) = &f.field_type {
+                let packer = format!("self._{}", member.to_uppercase());
+                py.blank()
+                    .line("@property")
+                    .suite(format!("def {}_raw(self):", member), |w| {
+                        w.line(format!(
+                            "return {}.unpack_from(self._buf, {})[0]",
+                            packer, f.offset
+                        ));
+                    })
+                    .blank()
+                    .line(format!("@{}_raw.setter", member))
+                    .suite(format!("def {}_raw(self, value):", member), |w| {
+                        w.line(format!(
+                            "{}.pack_into(self._buf, {}, value)",
+                            packer, f.offset
+                        ));
+                    });
+            }
+            if let FieldType_::Scalar(s) = &f.field_type {
+                if let Some((suffix, ty, getter, setter)) = native_time(s) {
+                    let native = format!("{}_{}", member, suffix);
+                    py.blank()
+                        .line("@property")
+                        .suite(format!("def {}(self):", native), |w| {
+                            w.line(format!("\"\"\"{} as a {}\"\"\"", f.name, ty))
+                                .line(getter.replace("{}", member));
+                        })
+                        .blank()
+                        .line(format!("@{}.setter", native))
+                        .suite(format!("def {}(self, value):", native), |w| {
+                            w.line(setter.replace("{}", member));
+                        });
+                }
+                if let ScalarType::Decimal(_) = s {
+                    let exponent = format!("self.{}_EXPONENT", member.to_uppercase());
+                    py.blank()
+                        .line("@property")
+                        .suite(format!("def {}_decimal(self):", member), |w| {
+                            w.line(format!("\"\"\"{} as a decimal.Decimal\"\"\"", f.name))
+                                .line(format!(
+                                    "return decimal.Decimal(self.{}).scaleb({})",
+                                    member, exponent
+                                ));
+                        })
+                        .blank()
+                        .line(format!("@{}_decimal.setter", member))
+                        .suite(format!("def {}_decimal(self, value):", member), |w| {
+                            w.line(format!(
+                                "mantissa = decimal.Decimal(value).scaleb(-{})",
+                                exponent
+                            ))
+                            .suite("if mantissa != mantissa.to_integral_value():", |w| {
+                                w.line(format!(
+                                    "raise ValueError(f\"{}.{} only holds multiples of 1e{{{}}}, not {{value}}\")",
+                                    name, f.name, exponent
+                                ));
+                            })
+                            .line(format!("self.{} = int(mantissa)", member));
+                        });
+                }
+            }
+            if let FieldType_::Union(ut) = &f.field_type {
+                let discriminator = member_name(&ut.discriminator);
+                for (v, variant, _) in self.variants(ut)? {
+                    py.blank().line("@property").suite(
+                        format!("def {}_{}(self):", member, variant),
+                        |w| {
+                            w.line(format!(
+                                "\"\"\"{}.{}, None unless {} is {}\"\"\"",
+                                member, variant, discriminator, v.selector
+                            ))
+                            .line(format!(
+                                "return self.{}.{} if self.{}_raw == {} else None",
+                                member, variant, discriminator, v.value
+                            ));
+                        },
+                    );
+                }
+            }
+        }
+        py.dedent();
+
+        // the stub
+        pyi.line(format!("class {}:", ty)).indent();
+        if id.is_some() {
+            pyi.line("ID: typing.ClassVar[int]");
+        }
+        pyi.line("SIZE: typing.ClassVar[int]");
+        if frame.is_some() {
+            pyi.line("FRAME_SIZE: typing.ClassVar[int]");
+        }
+        pyi.line("FORMAT: typing.ClassVar[str]")
+            .line("OFFSETS: typing.ClassVar[typing.Dict[str, int]]");
+        for (constant, _) in &decimals {
+            pyi.line(format!("{}_EXPONENT: typing.ClassVar[int]", constant));
+        }
+        pyi.line("def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...")
+            .line("@classmethod")
+            .line(format!("def decode(cls, buf: Buffer) -> {}: ...", ty))
+            .line("def encode(self) -> bytes: ...");
+        if frame.is_some() {
+            pyi.line("def encode_frame(self) -> bytes: ...");
+        }
+        pyi.line("def unpack(self) -> typing.Tuple[typing.Any, ...]: ...");
+        match union {
+            Some(_) => {
+                let mut variants: Vec<&str> = vec![];
+                for (_, _, accessor) in &fields {
+                    if !variants.contains(&accessor.stub_type.as_str()) {
+                        variants.push(&accessor.stub_type);
+                    }
+                }
+                pyi.line(format!(
+                    "def select(self, discriminator: int) -> typing.Union[{}]: ...",
+                    variants.join(", ")
+                ))
+                .line("def to_dict(self, discriminator: int) -> typing.Dict[str, typing.Any]: ...");
+            }
+            None => {
+                pyi.line("def to_dict(self) -> typing.Dict[str, typing.Any]: ...");
+            }
+        }
+        for (f, member, accessor) in &fields {
+            let value_type = match &f.field_type {
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                    format!("typing.Sequence[{}]", python_type(scalar))
+                }
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => String::from("Buffer"),
+                _ => accessor.stub_type.clone(),
+            };
+            pyi.line("@property")
+                .line(format!(
+                    "def {}(self) -> {}: ...",
+                    member, accessor.stub_type
+                ))
+                .line(format!("@{}.setter", member))
+                .line(format!(
+                    "def {}(self, value: {}) -> None: ...",
+                    member, value_type
+                ));
+            if let FieldType_::Enum(_) = &f.field_type {
+                pyi.line("@property")
+                    .line(format!("def {}_raw(self) -> int: ...", member))
+                    .line(format!("@{}_raw.setter", member))
+                    .line(format!("def {}_raw(self, value: int) -> None: ...", member));
+            }
+            if let FieldType_::Scalar(s) = &f.field_type {
+                if let Some((suffix, ty, _, _)) = native_time(s) {
+                    let native = format!("{}_{}", member, suffix);
+                    pyi.line("@property")
+                        .line(format!("def {}(self) -> {}: ...", native, ty))
+                        .line(format!("@{}.setter", native))
+                        .line(format!("def {}(self, value: {}) -> None: ...", native, ty));
+                }
+                if let ScalarType::Decimal(_) = s {
+                    pyi.line("@property")
+                        .line(format!("def {}_decimal(self) -> decimal.Decimal: ...", member))
+                        .line(format!("@{}_decimal.setter", member))
+                        .line(format!(
+                            "def {}_decimal(self, value: typing.Union[decimal.Decimal, int, str]) -> None: ...",
+                            member
+                        ));
+                }
+            }
+            if let FieldType_::Union(ut) = &f.field_type {
+                for (_, variant, accessor) in self.variants(ut)? {
+                    pyi.line("@property").line(format!(
+                        "def {}_{}(self) -> typing.Optional[{}]: ...",
+                        member, variant, accessor.stub_type
+                    ));
+                }
+            }
+        }
+        pyi.dedent();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::python::PythonGenerator;
+    use crate::codegen::{Generator, GeneratorOptions};
+
+    fn generate(schema: &str) -> Vec<String> {
+        let protocol = crate::sssc::compile(schema).unwrap();
+        PythonGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()
+            .into_iter()
+            .map(|f| f.contents)
+            .collect()
+    }
+
+    #[test]
+    fn test_format_follows_layout() {
+        let files = generate(
+            "protocol foo [byte_order = big];\
+            struct Inner { u16: a; }\
+            message M [1] { u8: kind; 3; Inner: inner; ascii[4]: name; i32[2]: values; }",
+        );
+        assert!(files[0].contains("FORMAT = \">B3xH4s2i\""));
+        assert!(files[0].contains("_VALUES = struct.Struct(\">2i\")"));
+        assert!(files[1].contains("def values(self) -> typing.Tuple[int, ...]: ..."));
+    }
+
+    #[test]
+    fn test_reserved_names_are_escaped() {
+        let files = generate("protocol foo; message M [1] { u8: encode; u8: from; }");
+        assert!(files[0].contains("def encode_(self):"));
+        assert!(files[0].contains("def from_(self):"));
+    }
+}
@@ -0,0 +1,1444 @@
+//! Generates zero-copy view types for Rust, a `FooView<'a>` reading a message in place from a
+//! `&[u8]` and a `FooMut<'a>` writing it in place to a `&mut [u8]`. The output only uses `core` so
+//! it can be used from `#![no_std]` crates.
+
+use crate::codegen::{
+    file_header, pascal_case, screaming_snake_case, snake_case, CodeWriter, Generator,
+    GeneratorError, GeneratorOptions, Keywords, OutputFile,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{Mantissa, TimeUnit};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType};
+use crate::protocol::{ByteOrder, Enum, Header, Layout, Protocol, Union};
+
+const KEYWORDS: Keywords = Keywords(&[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+]);
+
+/// Methods every view has, a field with one of these names gets a `_` suffix
+const RESERVED_METHODS: Keywords = Keywords(&["new", "view", "as_bytes"]);
+
+/// The name of the type generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Written once at the top of every generated module
+const PRELUDE: &str = r#"use core::fmt;
+
+/// Errors from reading or writing a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is shorter than the message or struct
+    BufferTooShort { required: usize, actual: usize },
+    /// A field holds a value which isn't declared by its enum or union
+    InvalidEnumValue { name: &'static str, value: i128 },
+    /// An ascii field holds a byte outside of the ascii range
+    InvalidAscii { field: &'static str },
+    /// A value is longer than the field it is written to
+    ValueTooLong { field: &'static str, max: usize, actual: usize },
+    /// The header of a frame holds an id no message has
+    UnknownMessageId { id: u64 },
+    /// The header of a frame gives a length too small for the frame
+    InvalidLength { length: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BufferTooShort { required, actual } => {
+                write!(f, "buffer of {} bytes is shorter than {} bytes", actual, required)
+            }
+            Error::InvalidEnumValue { name, value } => {
+                write!(f, "{} is not a value of '{}'", value, name)
+            }
+            Error::InvalidAscii { field } => write!(f, "'{}' is not ascii", field),
+            Error::ValueTooLong { field, max, actual } => write!(
+                f,
+                "{} bytes is too long for '{}' which holds {} bytes",
+                actual, field, max
+            ),
+            Error::UnknownMessageId { id } => write!(f, "no message has the id {}", id),
+            Error::InvalidLength { length } => {
+                write!(f, "a frame length of {} is too small", length)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[inline]
+fn check_size(buf: &[u8], required: usize) -> Result<(), Error> {
+    if buf.len() < required {
+        return Err(Error::BufferTooShort {
+            required,
+            actual: buf.len(),
+        });
+    }
+    Ok(())
+}
+
+#[inline]
+#[allow(dead_code)]
+fn get<const N: usize>(buf: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&buf[offset..offset + N]);
+    bytes
+}
+
+#[inline]
+#[allow(dead_code)]
+fn put<const N: usize>(buf: &mut [u8], offset: usize, bytes: [u8; N]) {
+    buf[offset..offset + N].copy_from_slice(&bytes);
+}
+
+#[inline]
+#[allow(dead_code)]
+fn bytes<const N: usize>(buf: &[u8], offset: usize) -> &[u8; N] {
+    buf[offset..offset + N].try_into().unwrap()
+}
+
+/// The text of an ascii field up to the first nul byte, without the `pad` bytes ending it
+#[allow(dead_code)]
+fn ascii_str<'a>(bytes: &'a [u8], pad: u8, field: &'static str) -> Result<&'a str, Error> {
+    let mut end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    while end > 0 && bytes[end - 1] == pad {
+        end -= 1;
+    }
+    match core::str::from_utf8(&bytes[..end]) {
+        Ok(text) if text.is_ascii() => Ok(text),
+        _ => Err(Error::InvalidAscii { field }),
+    }
+}
+
+/// Writes `value` to an ascii field, the rest of the field is filled with `pad`
+#[allow(dead_code)]
+fn put_ascii(buf: &mut [u8], value: &str, pad: u8, field: &'static str) -> Result<(), Error> {
+    if !value.is_ascii() {
+        return Err(Error::InvalidAscii { field });
+    }
+    if value.len() > buf.len() {
+        return Err(Error::ValueTooLong {
+            field,
+            max: buf.len(),
+            actual: value.len(),
+        });
+    }
+    buf[..value.len()].copy_from_slice(value.as_bytes());
+    buf[value.len()..].fill(pad);
+    Ok(())
+}
+
+/// Shows a value which couldn't be decoded as its raw form
+#[allow(dead_code)]
+struct Decoded<T, R>(Result<T, R>);
+
+impl<T: fmt::Debug, R: fmt::Debug> fmt::Debug for Decoded<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(value) => value.fmt(f),
+            Err(raw) => write!(f, "{:?} (invalid)", raw),
+        }
+    }
+}
+
+/// Shows a decimal's mantissa scaled by its exponent
+#[allow(dead_code)]
+struct Decimal(i128, i8);
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Decimal(mantissa, exponent) = *self;
+        if exponent >= 0 || mantissa == 0 {
+            write!(f, "{}", mantissa)?;
+            for _ in 0..exponent.max(0) {
+                f.write_str("0")?;
+            }
+            return Ok(());
+        }
+        let scale = 10u128.pow(exponent.unsigned_abs() as u32);
+        let sign = if mantissa < 0 { "-" } else { "" };
+        let abs = mantissa.unsigned_abs();
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale,
+            abs % scale,
+            width = exponent.unsigned_abs() as usize
+        )
+    }
+}
+"#;
+
+/// Generates a single module holding view types for every message and struct
+pub struct RustGenerator;
+
+impl Generator for RustGenerator {
+    fn name(&self) -> &str {
+        "rust"
+    }
+
+    fn description(&self) -> &str {
+        "zero-copy view types for Rust, usable from no_std"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&[])?;
+
+        let module = RustModule {
+            protocol,
+            endian: match protocol.byte_order {
+                ByteOrder::Little => "le",
+                ByteOrder::Big => "be",
+            },
+        };
+        Ok(vec![OutputFile::new(
+            format!("{}.rs", snake_case(&protocol.name.join("_"))),
+            module.generate()?,
+        )])
+    }
+}
+
+struct RustModule<'p> {
+    protocol: &'p Protocol,
+    /// `le` or `be`, as in `u32::from_le_bytes`
+    endian: &'static str,
+}
+
+/// A read accessor, `{lt}` in `returns` stands for the lifetime of the view
+struct Getter {
+    name: String,
+    doc: String,
+    returns: String,
+    body: Vec<String>,
+}
+
+struct Setter {
+    name: String,
+    doc: String,
+    /// Everything after `&mut self` in the parameter list
+    params: String,
+    returns: Option<String>,
+    body: Vec<String>,
+}
+
+struct Constant {
+    name: String,
+    doc: String,
+    declaration: String,
+}
+
+/// Everything generated for the fields of a layout, shared by its view and mut types
+#[derive(Default)]
+struct Accessors {
+    constants: Vec<Constant>,
+    getters: Vec<Getter>,
+    setters: Vec<Setter>,
+    /// The schema name of each field and the expression showing it in `Debug`
+    debug: Vec<(String, String)>,
+}
+
+fn primitive(scalar: &ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "u8",
+            2 => "u16",
+            4 => "u32",
+            _ => "u64",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "i8",
+            2 => "i16",
+            4 => "i32",
+            _ => "i64",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "f32",
+            _ => "f64",
+        },
+        ScalarType::ByteSized(_) => "u8",
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => primitive(&ScalarType::Unsigned(u)),
+            Mantissa::Signed(s) => primitive(&ScalarType::Signed(s)),
+        },
+        ScalarType::Timestamp(t) => primitive(&t.storage()),
+        ScalarType::Duration(d) => primitive(&d.storage()),
+    }
+}
+
+fn type_name(name: &str) -> String {
+    KEYWORDS.escape(&pascal_case(name))
+}
+
+fn field_name(name: &str) -> String {
+    RESERVED_METHODS.escape(&KEYWORDS.escape(&snake_case(name)))
+}
+
+/// The byte literal filling an ascii field after its text
+fn pad_byte(padding: AsciiPadding) -> &'static str {
+    match padding {
+        AsciiPadding::Nul => "0",
+        AsciiPadding::Space => "b' '",
+    }
+}
+
+impl RustModule<'_> {
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let mut w = CodeWriter::new("    ");
+        w.line(file_header("//", protocol).trim_end())
+            .line("//")
+            .line("// Only `core` is used so this module can be part of a `#![no_std]` crate.")
+            .blank()
+            .lines(PRELUDE)
+            .blank()
+            .line("/// The qualified name of the protocol")
+            .line(format!(
+                "pub const PROTOCOL: &str = \"{}\";",
+                protocol.qualified_name()
+            ));
+
+        for e in &protocol.enums {
+            w.blank();
+            match e.kind {
+                EnumKind::Enum => self.write_enum(&mut w, e)?,
+                EnumKind::Bitflags => self.write_bitflags(&mut w, e)?,
+            }
+        }
+        for u in &protocol.unions {
+            w.blank();
+            self.write_union(&mut w, u)?;
+        }
+        for s in &protocol.structs {
+            w.blank();
+            self.write_layout(
+                &mut w,
+                &s.name,
+                &format!("The struct `{}`", s.name),
+                None,
+                &s.layout,
+            )?;
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_layout(
+                &mut w,
+                FRAME_HEADER,
+                "The header in front of every message",
+                None,
+                &header.layout,
+            )?;
+        }
+        for m in &protocol.messages {
+            let id = u32::try_from(m.id).map_err(|_| {
+                GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a u32",
+                    m.name
+                ))
+            })?;
+            w.blank();
+            self.write_layout(
+                &mut w,
+                &m.name,
+                &format!("The message `{}` [{}]", m.name, m.id),
+                Some(id),
+                &m.layout,
+            )?;
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_dispatch(&mut w, header);
+        }
+        Ok(w.finish())
+    }
+
+    /// A `Handler` trait with a callback for every message and the `dispatch` function calling it
+    fn write_dispatch(&self, w: &mut CodeWriter, header: &Header) {
+        let header_view = format!("{}View", FRAME_HEADER);
+        w.line("/// Called by [`dispatch`] with the message of a frame, a message without an")
+            .line("/// implementation is ignored");
+        w.block("pub trait Handler {", "}", |w| {
+            for (i, m) in self.protocol.messages.iter().enumerate() {
+                if i > 0 {
+                    w.blank();
+                }
+                w.line(format!("/// Called with a `{}` [{}]", m.name, m.id))
+                    .block(
+                        format!(
+                            "fn on_{}(&mut self, message: {}View<'_>) {{",
+                            snake_case(&m.name),
+                            type_name(&m.name)
+                        ),
+                        "}",
+                        |w| {
+                            w.line("let _ = message;");
+                        },
+                    );
+            }
+        });
+        w.blank()
+            .line("/// Reads the header at the start of `buf` and passes the message after it to the")
+            .line("/// callback for its id. Returns the size of the frame, anything after it is left")
+            .line("/// alone.")
+            .block(
+                "pub fn dispatch<H: Handler + ?Sized>(buf: &[u8], handler: &mut H) -> Result<usize, Error> {",
+                "}",
+                |w| {
+                    w.line(format!("let header = {}::new(buf)?;", header_view));
+                    let length = header
+                        .length()
+                        .map(|l| (field_name(&l.name), header.length_value(0)));
+                    if let Some((length, adjust)) = &length {
+                        let frame = match adjust {
+                            0 => format!("{}::SIZE + header.{}() as usize", header_view, length),
+                            _ => format!("header.{}() as usize", length),
+                        };
+                        w.line(format!("let frame = {};", frame))
+                            .block(format!("if frame < {}::SIZE {{", header_view), "}", |w| {
+                                w.line("return Err(Error::InvalidLength { length: frame });");
+                            })
+                            .line("check_size(buf, frame)?;")
+                            .line(format!("let body = &buf[{}::SIZE..frame];", header_view));
+                    } else {
+                        w.line(format!("let body = &buf[{}::SIZE..];", header_view));
+                    }
+                    let id = field_name(&header.id().name);
+                    let assign = if length.is_some() { "" } else { "let size = " };
+                    w.block(
+                        format!("{}match u64::from(header.{}()) {{", assign, id),
+                        if length.is_some() { "}" } else { "};" },
+                        |w| {
+                            for m in &self.protocol.messages {
+                                let view = format!("{}View", type_name(&m.name));
+                                let call = format!(
+                                    "handler.on_{}({}::new(body)?)",
+                                    snake_case(&m.name),
+                                    view
+                                );
+                                match length {
+                                    Some(_) => w.line(format!("{} => {},", m.id, call)),
+                                    None => w.block(format!("{} => {{", m.id), "}", |w| {
+                                        w.line(format!("{};", call))
+                                            .line(format!("{}::SIZE", view));
+                                    }),
+                                };
+                            }
+                            w.line("id => return Err(Error::UnknownMessageId { id }),");
+                        },
+                    );
+                    match length {
+                        Some(_) => w.line("Ok(frame)"),
+                        None => w.line(format!("Ok({}::SIZE + size)", header_view)),
+                    };
+                },
+            );
+    }
+
+    /// The Rust type of the id or length field of the header, which are always unsigned
+    fn header_primitive(&self, field: &crate::protocol::Field) -> &'static str {
+        match &field.field_type {
+            FieldType_::Scalar(s) => primitive(s),
+            _ => unreachable!("header id and length fields are unsigned integers"),
+        }
+    }
+
+    fn underlying(&self, e: &Enum) -> Result<&'static str, GeneratorError> {
+        match e.underlying {
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_) => {
+                Ok(primitive(&e.underlying))
+            }
+            other => Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, other
+            ))),
+        }
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        let name = type_name(&e.name);
+        let repr = self.underlying(e)?;
+        if e.values.is_empty() {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' has no values",
+                e.name
+            )));
+        }
+        let variants: Vec<(String, &str, u64)> = e
+            .values
+            .iter()
+            .map(|v| (type_name(&v.name), v.name.as_str(), v.value))
+            .collect();
+
+        w.line("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]")
+            .line(format!("#[repr({})]", repr));
+        w.block(format!("pub enum {} {{", name), "}", |w| {
+            for (variant, _, value) in &variants {
+                w.line(format!("{} = {},", variant, value));
+            }
+        });
+        w.blank();
+        w.block(format!("impl {} {{", name), "}", |w| {
+            let all: Vec<String> = variants
+                .iter()
+                .map(|(v, _, _)| format!("{}::{}", name, v))
+                .collect();
+            w.line("/// Every declared value")
+                .line(format!(
+                    "pub const ALL: [{}; {}] = [{}];",
+                    name,
+                    all.len(),
+                    all.join(", ")
+                ))
+                .blank()
+                .line("/// The name of the value in the schema");
+            w.block("pub const fn name(self) -> &'static str {", "}", |w| {
+                w.block("match self {", "}", |w| {
+                    for (variant, schema_name, _) in &variants {
+                        w.line(format!("{}::{} => \"{}\",", name, variant, schema_name));
+                    }
+                });
+            });
+        });
+        w.blank();
+        w.block(format!("impl TryFrom<{}> for {} {{", repr, name), "}", |w| {
+            w.line("type Error = Error;").blank();
+            w.block(
+                format!("fn try_from(value: {}) -> Result<Self, Error> {{", repr),
+                "}",
+                |w| {
+                    w.block("match value {", "}", |w| {
+                        for (variant, _, value) in &variants {
+                            w.line(format!("{} => Ok({}::{}),", value, name, variant));
+                        }
+                        w.line(format!(
+                            "_ => Err(Error::InvalidEnumValue {{ name: \"{}\", value: value as i128 }}),",
+                            e.name
+                        ));
+                    });
+                },
+            );
+        });
+        w.blank();
+        w.block(format!("impl From<{}> for {} {{", name, repr), "}", |w| {
+            w.block(
+                format!("fn from(value: {}) -> {} {{", name, repr),
+                "}",
+                |w| {
+                    w.line(format!("value as {}", repr));
+                },
+            );
+        });
+        w.blank();
+        w.block(format!("impl fmt::Display for {} {{", name), "}", |w| {
+            w.block(
+                "fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {",
+                "}",
+                |w| {
+                    w.line("f.write_str(self.name())");
+                },
+            );
+        });
+        Ok(())
+    }
+
+    fn write_bitflags(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        let name = type_name(&e.name);
+        let repr = self.underlying(e)?;
+        let all = e.values.iter().fold(0, |all, v| all | v.value);
+
+        w.line("#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]")
+            .line(format!("pub struct {}({});", name, repr))
+            .blank();
+        w.block(format!("impl {} {{", name), "}", |w| {
+            for v in &e.values {
+                w.line(format!(
+                    "pub const {}: {} = {}({});",
+                    KEYWORDS.escape(&screaming_snake_case(&v.name)),
+                    name,
+                    name,
+                    v.value
+                ));
+            }
+            w.line("/// Every declared flag")
+                .line(format!("pub const ALL: {} = {}({});", name, name, all))
+                .blank();
+            w.block(
+                format!(
+                    "const NAMES: [({}, &'static str); {}] = [",
+                    name,
+                    e.values.len()
+                ),
+                "];",
+                |w| {
+                    for v in &e.values {
+                        w.line(format!(
+                            "(Self::{}, \"{}\"),",
+                            KEYWORDS.escape(&screaming_snake_case(&v.name)),
+                            v.name
+                        ));
+                    }
+                },
+            );
+            w.blank()
+                .line("pub const fn empty() -> Self {")
+                .line(format!("    {}(0)", name))
+                .line("}")
+                .blank()
+                .line(format!("pub const fn bits(self) -> {} {{", repr))
+                .line("    self.0")
+                .line("}")
+                .blank()
+                .line("/// Keeps any bits which aren't declared flags")
+                .line(format!(
+                    "pub const fn from_bits_retain(bits: {}) -> Self {{",
+                    repr
+                ))
+                .line(format!("    {}(bits)", name))
+                .line("}")
+                .blank()
+                .line("pub const fn is_empty(self) -> bool {")
+                .line("    self.0 == 0")
+                .line("}")
+                .blank()
+                .line("pub const fn contains(self, other: Self) -> bool {")
+                .line("    self.0 & other.0 == other.0")
+                .line("}")
+                .blank()
+                .line("pub fn insert(&mut self, other: Self) {")
+                .line("    self.0 |= other.0;")
+                .line("}")
+                .blank()
+                .line("pub fn remove(&mut self, other: Self) {")
+                .line("    self.0 &= !other.0;")
+                .line("}");
+        });
+        w.blank();
+        w.block(format!("impl TryFrom<{}> for {} {{", repr, name), "}", |w| {
+            w.line("type Error = Error;")
+                .blank()
+                .line("/// Fails if any bit isn't a declared flag")
+                .block(
+                    format!("fn try_from(bits: {}) -> Result<Self, Error> {{", repr),
+                    "}",
+                    |w| {
+                        w.block("if bits & !Self::ALL.0 != 0 {", "}", |w| {
+                            w.line(format!(
+                                "return Err(Error::InvalidEnumValue {{ name: \"{}\", value: bits as i128 }});",
+                                e.name
+                            ));
+                        });
+                        w.line(format!("Ok({}(bits))", name));
+                    },
+                );
+        });
+        w.blank();
+        w.block(format!("impl From<{}> for {} {{", name, repr), "}", |w| {
+            w.block(
+                format!("fn from(value: {}) -> {} {{", name, repr),
+                "}",
+                |w| {
+                    w.line("value.0");
+                },
+            );
+        });
+        for (op, method, symbol) in [
+            ("BitOr", "bitor", "|"),
+            ("BitAnd", "bitand", "&"),
+            ("BitXor", "bitxor", "^"),
+        ] {
+            w.blank();
+            w.block(
+                format!("impl core::ops::{} for {} {{", op, name),
+                "}",
+                |w| {
+                    w.line("type Output = Self;").blank();
+                    w.block(
+                        format!("fn {}(self, other: Self) -> Self {{", method),
+                        "}",
+                        |w| {
+                            w.line(format!("{}(self.0 {} other.0)", name, symbol));
+                        },
+                    );
+                },
+            );
+            w.blank();
+            w.block(
+                format!("impl core::ops::{}Assign for {} {{", op, name),
+                "}",
+                |w| {
+                    w.block(
+                        format!("fn {}_assign(&mut self, other: Self) {{", method),
+                        "}",
+                        |w| {
+                            w.line(format!("self.0 {}= other.0;", symbol));
+                        },
+                    );
+                },
+            );
+        }
+        w.blank();
+        w.block(format!("impl core::ops::Not for {} {{", name), "}", |w| {
+            w.line("type Output = Self;")
+                .blank()
+                .line("/// Only the declared flags are inverted")
+                .block("fn not(self) -> Self {", "}", |w| {
+                    w.line(format!("{}(!self.0 & Self::ALL.0)", name));
+                });
+        });
+        w.blank();
+        w.block(format!("impl fmt::Display for {} {{", name), "}", |w| {
+            w.line("/// The names of the set flags separated by ` | `, undeclared bits are shown in hex")
+                .block(
+                    "fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {",
+                    "}",
+                    |w| {
+                        w.line("let mut remaining = self.0;")
+                            .line("let mut first = true;")
+                            .block("for (flag, name) in Self::NAMES {", "}", |w| {
+                                w.block(
+                                    "if flag.0 != 0 && remaining & flag.0 == flag.0 {",
+                                    "}",
+                                    |w| {
+                                        w.line("f.write_str(if first { \"\" } else { \" | \" })?;")
+                                            .line("f.write_str(name)?;")
+                                            .line("remaining &= !flag.0;")
+                                            .line("first = false;");
+                                    },
+                                );
+                            })
+                            .block("if remaining != 0 || first {", "}", |w| {
+                                w.line("f.write_str(if first { \"\" } else { \" | \" })?;")
+                                    .line("write!(f, \"{:#x}\", remaining)?;");
+                            })
+                            .line("Ok(())");
+                    },
+                );
+        });
+        w.blank();
+        w.block(format!("impl fmt::Debug for {} {{", name), "}", |w| {
+            w.block(
+                "fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {",
+                "}",
+                |w| {
+                    w.line(format!("write!(f, \"{}({{}})\", self)", name));
+                },
+            );
+        });
+        Ok(())
+    }
+
+    /// Union variants borrowing from the buffer make the union's view type take a lifetime
+    fn union_has_lifetime(u: &Union) -> bool {
+        u.variants.iter().any(|v| {
+            matches!(
+                v.field_type,
+                FieldType_::Struct(_)
+                    | FieldType_::Vector(ArrayLike::AsciiString { .. })
+                    | FieldType_::Vector(ArrayLike::Bytes { .. })
+            )
+        })
+    }
+
+    fn union_view(&self, name: &str, lt: &str) -> String {
+        let union = self.protocol.union(name);
+        match union {
+            Some(u) if Self::union_has_lifetime(u) => {
+                format!("{}View<{}>", type_name(name), lt)
+            }
+            _ => format!("{}View", type_name(name)),
+        }
+    }
+
+    fn write_union(&self, w: &mut CodeWriter, u: &Union) -> Result<(), GeneratorError> {
+        let mut variants = vec![];
+        for v in &u.variants {
+            let (value_type, _) = self.read_value(&v.field_type, 0)?;
+            variants.push(format!(
+                "{}({}),",
+                type_name(&v.name),
+                value_type.replace("{lt}", "'a")
+            ));
+        }
+
+        w.line(format!(
+            "/// The active variant of the union `{}`, selected by a `{}`",
+            u.name, u.discriminator_type
+        ))
+        .line("#[derive(Debug, Clone, Copy)]");
+        w.block(
+            format!("pub enum {} {{", self.union_view(&u.name, "'a")),
+            "}",
+            |w| {
+                for v in &variants {
+                    w.line(v);
+                }
+            },
+        );
+        Ok(())
+    }
+
+    /// The type and expression reading a value of `field_type` at `offset`, used where the value
+    /// has to be a single expression
+    fn read_value(
+        &self,
+        field_type: &FieldType_,
+        offset: usize,
+    ) -> Result<(String, String), GeneratorError> {
+        let end = offset + field_type.size_bytes();
+        Ok(match field_type {
+            FieldType_::Scalar(s) => (String::from(primitive(s)), self.read_scalar(s, offset)),
+            FieldType_::Enum(et) => {
+                let name = type_name(&et.name);
+                let raw = self.read_scalar(&et.underlying, offset);
+                match et.kind {
+                    EnumKind::Enum => (name.clone(), format!("{}::try_from({})?", name, raw)),
+                    EnumKind::Bitflags => {
+                        (name.clone(), format!("{}::from_bits_retain({})", name, raw))
+                    }
+                }
+            }
+            FieldType_::Struct(st) => {
+                let name = type_name(&st.name);
+                (
+                    format!("{}View<{{lt}}>", name),
+                    format!("{}View {{ buf: &self.buf[{}..{}] }}", name, offset, end),
+                )
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { length, .. })
+            | FieldType_::Vector(ArrayLike::Bytes { length }) => (
+                format!("&{{lt}} [u8; {}]", length),
+                format!("bytes(self.buf, {})", offset),
+            ),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => (
+                format!("[{}; {}]", primitive(scalar), length),
+                format!(
+                    "core::array::from_fn(|i| {}::from_{}_bytes(get(self.buf, {} + i * {})))",
+                    primitive(scalar),
+                    self.endian,
+                    offset,
+                    scalar.size_bytes()
+                ),
+            ),
+            FieldType_::Union(ut) => {
+                return Err(GeneratorError::Unsupported(format!(
+                    "union '{}' used inside another union",
+                    ut.name
+                )))
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        })
+    }
+
+    /// The parameter type, return type and statements writing `value` as a `field_type` at
+    /// `offset`, `None` for types which are written through their own mut view
+    fn write_value(
+        &self,
+        field_type: &FieldType_,
+        offset: usize,
+        schema_name: &str,
+    ) -> Option<(String, Option<String>, Vec<String>)> {
+        let end = offset + field_type.size_bytes();
+        Some(match field_type {
+            FieldType_::Scalar(s) => (
+                String::from(primitive(s)),
+                None,
+                vec![self.write_scalar(offset, "value")],
+            ),
+            FieldType_::Enum(et) => {
+                let raw = match et.kind {
+                    EnumKind::Enum => format!("{}::from(value)", primitive(&et.underlying)),
+                    EnumKind::Bitflags => String::from("value.bits()"),
+                };
+                (
+                    type_name(&et.name),
+                    None,
+                    vec![self.write_scalar(offset, &raw)],
+                )
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => (
+                String::from("&str"),
+                Some(String::from("Result<(), Error>")),
+                vec![format!(
+                    "put_ascii(&mut self.buf[{}..{}], value, {}, \"{}\")",
+                    offset,
+                    end,
+                    pad_byte(*padding),
+                    schema_name
+                )],
+            ),
+            FieldType_::Vector(ArrayLike::Bytes { length }) => (
+                format!("&[u8; {}]", length),
+                None,
+                vec![format!(
+                    "self.buf[{}..{}].copy_from_slice(value);",
+                    offset, end
+                )],
+            ),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => (
+                format!("&[{}; {}]", primitive(scalar), length),
+                None,
+                vec![
+                    String::from("for (i, v) in value.iter().enumerate() {"),
+                    format!(
+                        "    put(self.buf, {} + i * {}, v.to_{}_bytes());",
+                        offset,
+                        scalar.size_bytes(),
+                        self.endian
+                    ),
+                    String::from("}"),
+                ],
+            ),
+            FieldType_::Struct(_) | FieldType_::Union(_) | FieldType_::Padding(_) => return None,
+        })
+    }
+
+    fn read_scalar(&self, scalar: &ScalarType, offset: usize) -> String {
+        format!(
+            "{}::from_{}_bytes(get(self.buf, {}))",
+            primitive(scalar),
+            self.endian,
+            offset
+        )
+    }
+
+    fn write_scalar(&self, offset: usize, value: &str) -> String {
+        format!(
+            "put(self.buf, {}, {}.to_{}_bytes());",
+            offset, value, self.endian
+        )
+    }
+
+    fn accessors(&self, layout: &Layout) -> Result<Accessors, GeneratorError> {
+        let mut a = Accessors::default();
+        for field in layout.fields() {
+            let name = field_name(&field.name);
+            let offset = field.offset;
+            let size = field.field_type.size_bytes();
+            let end = offset + size;
+            let position = format!("offset {}, {} bytes", offset, size);
+            let doc = match &field.description {
+                Some(d) => format!("{} ({})", d, position),
+                None => format!("`{}`, {}", field.field_type, position),
+            };
+
+            match &field.field_type {
+                FieldType_::Enum(et) if et.kind == EnumKind::Enum => {
+                    let enum_name = type_name(&et.name);
+                    let raw = primitive(&et.underlying);
+                    a.getters.push(Getter {
+                        name: name.clone(),
+                        doc: doc.clone(),
+                        returns: format!("Result<{}, Error>", enum_name),
+                        body: vec![format!("{}::try_from(self.{}_raw())", enum_name, name)],
+                    });
+                    a.getters.push(Getter {
+                        name: format!("{}_raw", name),
+                        doc: format!("The stored value of `{}`, even if it isn't declared", name),
+                        returns: String::from(raw),
+                        body: vec![self.read_scalar(&et.underlying, offset)],
+                    });
+                    a.setters.push(Setter {
+                        name: format!("set_{}_raw", name),
+                        doc: format!(
+                            "Stores any value in `{}`, even one which isn't declared",
+                            name
+                        ),
+                        params: format!("value: {}", raw),
+                        returns: None,
+                        body: vec![self.write_scalar(offset, "value")],
+                    });
+                    a.debug.push((
+                        field.name.clone(),
+                        format!("&Decoded(self.{}().map_err(|_| self.{}_raw()))", name, name),
+                    ));
+                }
+                FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                    let (returns, body) = self.read_value(&field.field_type, offset)?;
+                    a.getters.push(Getter {
+                        name: name.clone(),
+                        doc: doc.clone(),
+                        returns,
+                        body: vec![body],
+                    });
+                    a.getters.push(Getter {
+                        name: format!("{}_str", name),
+                        doc: match padding {
+                            AsciiPadding::Nul => {
+                                format!("The text of `{}` up to the first nul byte", name)
+                            }
+                            AsciiPadding::Space => format!(
+                                "The text of `{}` up to the first nul byte, without trailing spaces",
+                                name
+                            ),
+                        },
+                        returns: String::from("Result<&{lt} str, Error>"),
+                        body: vec![format!(
+                            "ascii_str(self.{}(), {}, \"{}\")",
+                            name,
+                            pad_byte(*padding),
+                            field.name
+                        )],
+                    });
+                    a.debug.push((
+                        field.name.clone(),
+                        format!("&Decoded(self.{}_str().map_err(|_| self.{}()))", name, name),
+                    ));
+                }
+                FieldType_::Struct(st) => {
+                    let (returns, body) = self.read_value(&field.field_type, offset)?;
+                    a.getters.push(Getter {
+                        name: name.clone(),
+                        doc: doc.clone(),
+                        returns,
+                        body: vec![body],
+                    });
+                    a.setters.push(Setter {
+                        name: format!("{}_mut", name),
+                        doc: doc.clone(),
+                        params: String::new(),
+                        returns: Some(format!("{}Mut<'_>", type_name(&st.name))),
+                        body: vec![format!(
+                            "{}Mut {{ buf: &mut self.buf[{}..{}] }}",
+                            type_name(&st.name),
+                            offset,
+                            end
+                        )],
+                    });
+                    a.debug
+                        .push((field.name.clone(), format!("&self.{}()", name)));
+                }
+                FieldType_::Union(ut) => {
+                    self.union_accessors(&mut a, layout, &name, &doc, ut, offset)?;
+                    a.debug.push((
+                        field.name.clone(),
+                        format!(
+                            "&Decoded(self.{}().map_err(|_| self.{}_bytes()))",
+                            name, name
+                        ),
+                    ));
+                }
+                other => {
+                    let (returns, body) = self.read_value(other, offset)?;
+                    a.getters.push(Getter {
+                        name: name.clone(),
+                        doc: doc.clone(),
+                        returns,
+                        body: vec![body],
+                    });
+                    a.debug
+                        .push((field.name.clone(), format!("&self.{}()", name)));
+                }
+            }
+
+            if let Some((param, returns, body)) =
+                self.write_value(&field.field_type, offset, &field.name)
+            {
+                a.setters.push(Setter {
+                    name: format!("set_{}", name),
+                    doc: doc.clone(),
+                    params: format!("value: {}", param),
+                    returns,
+                    body,
+                });
+            }
+
+            match &field.field_type {
+                FieldType_::Scalar(ScalarType::Decimal(d))
+                | FieldType_::Vector(ArrayLike::FixedArray {
+                    scalar: ScalarType::Decimal(d),
+                    ..
+                }) => {
+                    a.constants.push(Constant {
+                        name: format!("{}_EXPONENT", screaming_snake_case(&field.name)),
+                        doc: format!(
+                            "`{}` holds the mantissa of a decimal, its value is `{}() * 10^{}`",
+                            name, name, d.exponent
+                        ),
+                        declaration: format!("i8 = {}", d.exponent),
+                    });
+                    let last = a.debug.last_mut().expect("the field was just added");
+                    if let FieldType_::Scalar(_) = field.field_type {
+                        last.1 = format!(
+                            "&Decimal(self.{}() as i128, Self::{}_EXPONENT)",
+                            name,
+                            screaming_snake_case(&field.name)
+                        );
+                    }
+                }
+                FieldType_::Scalar(ScalarType::Timestamp(t)) => {
+                    let from = match t.unit {
+                        TimeUnit::Seconds => "from_secs",
+                        TimeUnit::Millis => "from_millis",
+                        TimeUnit::Micros => "from_micros",
+                        TimeUnit::Nanos => "from_nanos",
+                    };
+                    a.getters.push(Getter {
+                        name: format!("{}_since_epoch", name),
+                        doc: format!("`{}` as the time since the unix epoch", name),
+                        returns: String::from("core::time::Duration"),
+                        body: vec![format!("core::time::Duration::{}(self.{}())", from, name)],
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(a)
+    }
+
+    fn union_accessors(
+        &self,
+        a: &mut Accessors,
+        layout: &Layout,
+        name: &str,
+        doc: &str,
+        ut: &crate::data_types::UnionType,
+        offset: usize,
+    ) -> Result<(), GeneratorError> {
+        let union = self.protocol.union(&ut.name).ok_or_else(|| {
+            GeneratorError::Failed(format!("union '{}' is not declared", ut.name))
+        })?;
+        let discriminator = layout.field(&ut.discriminator).ok_or_else(|| {
+            GeneratorError::Failed(format!("there is no field named '{}'", ut.discriminator))
+        })?;
+        let raw = format!("{}_raw", field_name(&discriminator.name));
+        let end = offset + ut.size;
+        let view = type_name(&ut.name) + "View";
+
+        let mut body = vec![format!("Ok(match self.{}() {{", raw)];
+        for v in &union.variants {
+            let (_, value) = self.read_value(&v.field_type, offset)?;
+            body.push(format!(
+                "    {} => {}::{}({}),",
+                v.value,
+                view,
+                type_name(&v.name),
+                value
+            ));
+        }
+        body.push(format!(
+            "    value => return Err(Error::InvalidEnumValue {{ name: \"{}\", value: value as i128 }}),",
+            union.name
+        ));
+        body.push(String::from("})"));
+        a.getters.push(Getter {
+            name: String::from(name),
+            doc: format!("{}, the variant is selected by `{}`", doc, ut.discriminator),
+            returns: format!("Result<{}, Error>", self.union_view(&ut.name, "{lt}")),
+            body,
+        });
+        a.getters.push(Getter {
+            name: format!("{}_bytes", name),
+            doc: format!("The bytes of `{}` whichever variant is active", name),
+            returns: format!("&{{lt}} [u8; {}]", ut.size),
+            body: vec![format!("bytes(self.buf, {})", offset)],
+        });
+
+        for v in &union.variants {
+            let select = vec![
+                format!("self.buf[{}..{}].fill(0);", offset, end),
+                format!("self.set_{}({});", raw, v.value),
+            ];
+            let variant = field_name(&v.name);
+            let doc = format!(
+                "Makes `{}` the active variant by setting `{}` to `{}`",
+                v.name, ut.discriminator, v.selector
+            );
+            match &v.field_type {
+                FieldType_::Struct(st) => {
+                    let mut body = select;
+                    body.push(format!(
+                        "{}Mut {{ buf: &mut self.buf[{}..{}] }}",
+                        type_name(&st.name),
+                        offset,
+                        offset + st.size
+                    ));
+                    a.setters.push(Setter {
+                        name: format!("{}_{}_mut", name, variant),
+                        doc,
+                        params: String::new(),
+                        returns: Some(format!("{}Mut<'_>", type_name(&st.name))),
+                        body,
+                    });
+                }
+                other => {
+                    let (param, returns, write) = self
+                        .write_value(other, offset, &v.name)
+                        .expect("every other variant type has a setter");
+                    let mut body = select;
+                    body.extend(write);
+                    a.setters.push(Setter {
+                        name: format!("set_{}_{}", name, variant),
+                        doc,
+                        params: format!("value: {}", param),
+                        returns,
+                        body,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_layout(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        doc: &str,
+        id: Option<u32>,
+        layout: &Layout,
+    ) -> Result<(), GeneratorError> {
+        let a = self.accessors(layout)?;
+        let ty = type_name(name);
+        let view = format!("{}View", ty);
+        let mutable = format!("{}Mut", ty);
+
+        let header = self.protocol.header.as_ref().filter(|_| id.is_some());
+        let write_constants = |w: &mut CodeWriter| {
+            w.line(format!("pub const SIZE: usize = {};", layout.size));
+            if let Some(id) = id {
+                w.line(format!("pub const ID: u32 = {};", id));
+            }
+            if let Some(header) = header {
+                w.line("/// The size of the header followed by the message")
+                    .line(format!(
+                        "pub const FRAME_SIZE: usize = {};",
+                        header.layout.size + layout.size
+                    ));
+            }
+            for c in &a.constants {
+                w.line(format!("/// {}", c.doc))
+                    .line(format!("pub const {}: {};", c.name, c.declaration));
+            }
+        };
+        let write_debug = |w: &mut CodeWriter, target: &str, body: &dyn Fn(&mut CodeWriter)| {
+            w.blank();
+            w.block(format!("impl fmt::Debug for {}<'_> {{", target), "}", |w| {
+                w.block(
+                    "fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {",
+                    "}",
+                    |w| body(w),
+                );
+            });
+            w.blank();
+            w.block(
+                format!("impl fmt::Display for {}<'_> {{", target),
+                "}",
+                |w| {
+                    w.block(
+                        "fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {",
+                        "}",
+                        |w| {
+                            w.line("fmt::Debug::fmt(self, f)");
+                        },
+                    );
+                },
+            );
+        };
+
+        w.line(format!("/// {}, read in place from a byte slice", doc))
+            .line("#[derive(Clone, Copy)]");
+        w.block(format!("pub struct {}<'a> {{", view), "}", |w| {
+            w.line("buf: &'a [u8],");
+        });
+        w.blank();
+        w.block(format!("impl<'a> {}<'a> {{", view), "}", |w| {
+            write_constants(w);
+            w.blank()
+                .line(format!(
+                    "/// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the {} are ignored",
+                    if id.is_some() { "message" } else { "struct" }
+                ))
+                .block("pub fn new(buf: &'a [u8]) -> Result<Self, Error> {", "}", |w| {
+                    w.line("check_size(buf, Self::SIZE)?;")
+                        .line(format!("Ok({} {{ buf: &buf[..Self::SIZE] }})", view));
+                })
+                .blank()
+                .block("pub fn as_bytes(&self) -> &'a [u8] {", "}", |w| {
+                    w.line("self.buf");
+                });
+            for g in &a.getters {
+                w.blank()
+                    .line(format!("/// {}", g.doc))
+                    .block(
+                        format!(
+                            "pub fn {}(&self) -> {} {{",
+                            g.name,
+                            g.returns.replace("{lt}", "'a")
+                        ),
+                        "}",
+                        |w| {
+                            for line in &g.body {
+                                w.line(line);
+                            }
+                        },
+                    );
+            }
+        });
+        write_debug(w, &view, &|w| {
+            w.line(format!("f.debug_struct(\"{}\")", name));
+            w.indent();
+            for (field, expr) in &a.debug {
+                w.line(format!(".field(\"{}\", {})", field, expr));
+            }
+            w.line(".finish()");
+            w.dedent();
+        });
+
+        w.blank()
+            .line(format!("/// {}, written in place to a byte slice", doc));
+        w.block(format!("pub struct {}<'a> {{", mutable), "}", |w| {
+            w.line("buf: &'a mut [u8],");
+        });
+        w.blank();
+        w.block(format!("impl<'a> {}<'a> {{", mutable), "}", |w| {
+            write_constants(w);
+            w.blank()
+                .line(format!(
+                    "/// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the {} are left untouched",
+                    if id.is_some() { "message" } else { "struct" }
+                ))
+                .block(
+                    "pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {",
+                    "}",
+                    |w| {
+                        w.line("check_size(buf, Self::SIZE)?;")
+                            .line(format!("Ok({} {{ buf: &mut buf[..Self::SIZE] }})", mutable));
+                    },
+                )
+                .blank()
+                .block(format!("pub fn view(&self) -> {}<'_> {{", view), "}", |w| {
+                    w.line(format!("{} {{ buf: self.buf }}", view));
+                });
+            if let Some(header) = header {
+                let header_mut = format!("{}Mut", FRAME_HEADER);
+                w.blank().line("/// Writes the header to the start of `buf` and returns the message after it, fails if")
+                    .line("/// `buf` is shorter than [`Self::FRAME_SIZE`]")
+                    .block(
+                        "pub fn new_frame(buf: &'a mut [u8]) -> Result<Self, Error> {",
+                        "}",
+                        |w| {
+                            let id_field = header.id();
+                            w.line("check_size(buf, Self::FRAME_SIZE)?;")
+                                .line(format!(
+                                    "let (header, body) = buf.split_at_mut({}::SIZE);",
+                                    header_mut
+                                ))
+                                .line(format!("let mut header = {}::new(header)?;", header_mut))
+                                .line(format!(
+                                    "header.set_{}(Self::ID as {});",
+                                    field_name(&id_field.name),
+                                    self.header_primitive(id_field)
+                                ));
+                            if let Some(length) = header.length() {
+                                w.line(format!(
+                                    "header.set_{}({} as {});",
+                                    field_name(&length.name),
+                                    match header.length_value(0) {
+                                        0 => "Self::SIZE",
+                                        _ => "Self::FRAME_SIZE",
+                                    },
+                                    self.header_primitive(length)
+                                ));
+                            }
+                            w.line("Self::new(body)");
+                        },
+                    );
+            }
+            w.blank()
+                .block("pub fn as_bytes(&self) -> &[u8] {", "}", |w| {
+                    w.line("self.buf");
+                });
+            for g in &a.getters {
+                w.blank()
+                    .line(format!("/// {}", g.doc))
+                    .block(
+                        format!(
+                            "pub fn {}(&self) -> {} {{",
+                            g.name,
+                            g.returns.replace("{lt}", "'_")
+                        ),
+                        "}",
+                        |w| {
+                            w.line(format!("self.view().{}()", g.name));
+                        },
+                    );
+            }
+            for s in &a.setters {
+                let params = match s.params.as_str() {
+                    "" => String::from("&mut self"),
+                    p => format!("&mut self, {}", p),
+                };
+                let returns = match &s.returns {
+                    Some(r) => format!(" -> {}", r),
+                    None => String::new(),
+                };
+                w.blank().line(format!("/// {}", s.doc)).block(
+                    format!("pub fn {}({}){} {{", s.name, params, returns),
+                    "}",
+                    |w| {
+                        for line in &s.body {
+                            w.line(line);
+                        }
+                    },
+                );
+            }
+        });
+        write_debug(w, &mutable, &|w| {
+            w.line("fmt::Debug::fmt(&self.view(), f)");
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::rust::RustGenerator;
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+
+    fn generate(schema: &str) -> Result<String, GeneratorError> {
+        let protocol = crate::sssc::compile(schema).unwrap();
+        RustGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .map(|files| files[0].contents.clone())
+    }
+
+    #[test]
+    fn test_accessors_use_offsets_and_byte_order() {
+        let code = generate(
+            "protocol foo.bar [byte_order = big];\
+            message Quote [7] { u8: side; u32: price; ascii[4]: symbol; }",
+        )
+        .unwrap();
+
+        assert!(code.contains("pub struct QuoteView<'a>"));
+        assert!(code.contains("pub struct QuoteMut<'a>"));
+        assert!(code.contains("pub const SIZE: usize = 9;"));
+        assert!(code.contains("pub const ID: u32 = 7;"));
+        assert!(code.contains("u32::from_be_bytes(get(self.buf, 1))"));
+        assert!(code.contains("put_ascii(&mut self.buf[5..9], value, 0, \"symbol\")"));
+    }
+
+    #[test]
+    fn test_file_is_named_after_protocol() {
+        let protocol = crate::sssc::compile("protocol my.protocol;").unwrap();
+        let files = RustGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!("my_protocol.rs", files[0].path.to_str().unwrap());
+    }
+
+    #[test]
+    fn test_names_are_escaped() {
+        let code = generate("protocol foo; message M [1] { u8: type; u8: new; }").unwrap();
+        assert!(code.contains("pub fn type_(&self) -> u8"));
+        assert!(code.contains("pub fn new_(&self) -> u8"));
+    }
+}
@@ -0,0 +1,1026 @@
+//! Generates an ES module of classes wrapping a `DataView` and a byte offset, typed by a `.d.ts`
+//! declaration file so it can be used from TypeScript and plain JavaScript alike.
+
+use crate::codegen::{
+    camel_case, pascal_case, screaming_snake_case, snake_case, type_order, CodeWriter, Generator,
+    GeneratorError, GeneratorOptions, Keywords, OutputFile, TypeDeclaration,
+};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{Mantissa, TimeUnit};
+use crate::data_types::{
+    ArrayLike, EnumKind, FieldType, FieldType_, ScalarType, StructType, UnionType,
+};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Member, Protocol, Union};
+
+/// JavaScript allows keywords as property names, so only the members every class has are reserved
+const RESERVED_MEMBERS: Keywords =
+    Keywords(&["view", "offset", "constructor", "allocate", "wrapFrame"]);
+
+/// The class generated for the header of a protocol
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// Shared by the classes of the module
+const RUNTIME: &str = r#"
+const ASCII = new TextDecoder("ascii");
+
+function checkSize(view, offset, size, name) {
+  if (!Number.isInteger(offset) || offset < 0 || offset + size > view.byteLength) {
+    throw new RangeError(`${name} needs ${size} bytes at offset ${offset} but the view holds ${view.byteLength}`);
+  }
+}
+
+function checkIndex(index, length, name) {
+  if (!Number.isInteger(index) || index < 0 || index >= length) {
+    throw new RangeError(`${index} is out of range for ${name}, which holds ${length} elements`);
+  }
+}
+
+function bytes(view, offset, length) {
+  return new Uint8Array(view.buffer, view.byteOffset + offset, length);
+}
+
+function getAscii(view, offset, length, pad) {
+  const field = bytes(view, offset, length);
+  let end = field.indexOf(0);
+  if (end < 0) {
+    end = length;
+  }
+  while (end > 0 && field[end - 1] === pad) {
+    end--;
+  }
+  return ASCII.decode(field.subarray(0, end));
+}
+
+function setAscii(view, offset, length, pad, value, name) {
+  if (value.length > length) {
+    throw new RangeError(`${name} holds at most ${length} characters but the value has ${value.length}`);
+  }
+  for (let i = 0; i < value.length; i++) {
+    if (value.charCodeAt(i) > 0x7f) {
+      throw new RangeError(`${name} only holds ascii characters`);
+    }
+  }
+  const field = bytes(view, offset, length);
+  field.fill(pad);
+  for (let i = 0; i < value.length; i++) {
+    field[i] = value.charCodeAt(i);
+  }
+}
+
+function setBytes(view, offset, length, value, name) {
+  if (value.length !== length) {
+    throw new RangeError(`${name} holds exactly ${length} bytes but the value has ${value.length}`);
+  }
+  bytes(view, offset, length).set(value);
+}
+
+function enumName(names, value, type) {
+  const name = names.get(value);
+  if (name === undefined) {
+    throw new RangeError(`${value} is not a declared ${type}`);
+  }
+  return name;
+}
+
+function enumValue(values, name, type) {
+  if (!Object.hasOwn(values, name)) {
+    throw new RangeError(`${name} is not a declared ${type}`);
+  }
+  return values[name];
+}
+
+function flagNames(values, raw) {
+  return Object.keys(values).filter((name) => (raw & values[name]) === values[name]);
+}
+
+function flagValue(values, names, type, zero) {
+  return names.reduce((raw, name) => raw | enumValue(values, name, type), zero);
+}"#;
+
+/// Generates `<protocol>.js` and `<protocol>.d.ts`
+pub struct TypeScriptGenerator;
+
+impl Generator for TypeScriptGenerator {
+    fn name(&self) -> &str {
+        "typescript"
+    }
+
+    fn description(&self) -> &str {
+        "ES module of DataView classes with .d.ts typings"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&[])?;
+
+        let stem = snake_case(&protocol.name.join("_"));
+        let (js, dts) = Module { protocol }.generate()?;
+        Ok(vec![
+            OutputFile::new(format!("{}.js", stem), js),
+            OutputFile::new(format!("{}.d.ts", stem), dts),
+        ])
+    }
+}
+
+struct Module<'p> {
+    protocol: &'p Protocol,
+}
+
+fn type_name(name: &str) -> String {
+    pascal_case(name)
+}
+
+fn member_name(name: &str) -> String {
+    RESERVED_MEMBERS.escape(&camel_case(name))
+}
+
+fn storage(scalar: &ScalarType) -> ScalarType {
+    match scalar {
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => ScalarType::Unsigned(u),
+            Mantissa::Signed(s) => ScalarType::Signed(s),
+        },
+        ScalarType::Timestamp(t) => t.storage(),
+        ScalarType::Duration(d) => d.storage(),
+        other => *other,
+    }
+}
+
+/// The suffix of the `DataView` get and set methods
+fn accessor(scalar: &ScalarType) -> &'static str {
+    match storage(scalar) {
+        ScalarType::Unsigned(u) => match u.size_bytes() {
+            1 => "Uint8",
+            2 => "Uint16",
+            4 => "Uint32",
+            _ => "BigUint64",
+        },
+        ScalarType::Signed(s) => match s.size_bytes() {
+            1 => "Int8",
+            2 => "Int16",
+            4 => "Int32",
+            _ => "BigInt64",
+        },
+        ScalarType::Float(f) => match f.size_bytes() {
+            4 => "Float32",
+            _ => "Float64",
+        },
+        _ => "Uint8",
+    }
+}
+
+/// The member suffix, type, description and conversions from and to the raw count of a
+/// timestamp or duration, a timestamp becomes a `Date` and a duration a count of milliseconds
+type NativeTime = (&'static str, &'static str, &'static str, String, String);
+
+fn native_time(scalar: &ScalarType) -> Option<NativeTime> {
+    let millis = |unit: TimeUnit| match unit {
+        TimeUnit::Seconds => (" * 1000n", " / 1000n"),
+        TimeUnit::Millis => ("", ""),
+        TimeUnit::Micros => (" / 1000n", " * 1000n"),
+        TimeUnit::Nanos => (" / 1000000n", " * 1000000n"),
+    };
+    match scalar {
+        ScalarType::Timestamp(t) => {
+            let (to, from) = millis(t.unit);
+            Some((
+                "Date",
+                "Date",
+                "as a Date",
+                format!("new Date(Number(this.{{}}{}))", to),
+                format!("BigInt(value.getTime()){}", from),
+            ))
+        }
+        ScalarType::Duration(d) => {
+            let (to, from) = millis(d.unit);
+            Some((
+                "Ms",
+                "bigint",
+                "in milliseconds",
+                format!("this.{{}}{}", to),
+                format!("value{}", from),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn ts_type(scalar: &ScalarType) -> &'static str {
+    if accessor(scalar).starts_with("Big") {
+        "bigint"
+    } else {
+        "number"
+    }
+}
+
+fn literal(scalar: &ScalarType, value: u64) -> String {
+    if accessor(scalar).starts_with("Big") {
+        format!("{}n", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// A header field read as a number, 64 bit fields are bigints
+fn header_number(field: &Field, value: &str) -> String {
+    match &field.field_type {
+        FieldType_::Scalar(s) if ts_type(s) == "bigint" => format!("Number({})", value),
+        _ => value.to_string(),
+    }
+}
+
+/// A number written to a header field, 64 bit fields are bigints
+fn header_literal(field: &Field, value: usize) -> String {
+    match &field.field_type {
+        FieldType_::Scalar(s) => literal(s, value as u64),
+        _ => value.to_string(),
+    }
+}
+
+/// `this.offset + offset`, leaving out adding zero
+fn at(offset: usize) -> String {
+    if offset == 0 {
+        String::from("this.offset")
+    } else {
+        format!("this.offset + {}", offset)
+    }
+}
+
+impl Module<'_> {
+    fn generate(&self) -> Result<(String, String), GeneratorError> {
+        let protocol = self.protocol;
+        let header = format!(
+            "// Generated by sssc {} from protocol {}, do not edit.",
+            env!("CARGO_PKG_VERSION"),
+            protocol.qualified_name()
+        );
+        let mut js = CodeWriter::new("  ");
+        let mut dts = CodeWriter::new("  ");
+        js.line(&header)
+            .blank()
+            .line(format!(
+                "const LITTLE_ENDIAN = {};",
+                protocol.byte_order == ByteOrder::Little
+            ))
+            .lines(RUNTIME);
+        dts.line(&header);
+
+        for e in &protocol.enums {
+            js.blank();
+            dts.blank();
+            self.write_enum(&mut js, &mut dts, e)?;
+        }
+        for declaration in type_order(protocol) {
+            match declaration {
+                TypeDeclaration::Struct(s) => self.write_class(
+                    &mut js,
+                    &mut dts,
+                    &s.name,
+                    None,
+                    &format!("struct {}", s.name),
+                    &s.layout,
+                ),
+                TypeDeclaration::Union(u) => {
+                    // every variant of a union starts at its first byte
+                    let layout = Layout {
+                        size: u.size,
+                        alignment: u.alignment,
+                        members: u
+                            .variants
+                            .iter()
+                            .map(|v| {
+                                Member::Field(Field {
+                                    name: v.name.clone(),
+                                    offset: 0,
+                                    field_type: v.field_type.clone(),
+                                    description: Some(format!(
+                                        "active when the discriminator is {}",
+                                        v.selector
+                                    )),
+                                })
+                            })
+                            .collect(),
+                    };
+                    self.write_class(
+                        &mut js,
+                        &mut dts,
+                        &u.name,
+                        None,
+                        &format!(
+                            "union {}, the active variant is selected by a {}",
+                            u.name, u.discriminator_type
+                        ),
+                        &layout,
+                    )
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            self.write_class(
+                &mut js,
+                &mut dts,
+                FRAME_HEADER,
+                None,
+                "the header in front of every message",
+                &header.layout,
+            );
+        }
+        for m in &protocol.messages {
+            // ids are compared as numbers, which are only exact up to 2^53
+            if m.id > u32::MAX as usize {
+                return Err(GeneratorError::Unsupported(format!(
+                    "the id of message '{}' does not fit in a u32",
+                    m.name
+                )));
+            }
+            self.write_class(
+                &mut js,
+                &mut dts,
+                &m.name,
+                Some(m.id),
+                &format!("message {} [{}]", m.name, m.id),
+                &m.layout,
+            );
+        }
+        self.write_decode(&mut js, &mut dts);
+        if let Some(header) = &protocol.header {
+            self.write_dispatch(&mut js, &mut dts, header);
+        }
+        Ok((js.finish(), dts.finish()))
+    }
+
+    fn write_enum(
+        &self,
+        js: &mut CodeWriter,
+        dts: &mut CodeWriter,
+        e: &Enum,
+    ) -> Result<(), GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let ty = type_name(&e.name);
+        let kind = match e.kind {
+            EnumKind::Enum => "enum",
+            EnumKind::Bitflags => "bitflags",
+        };
+        let doc = format!("/** {} {}, stored as a {} */", kind, e.name, e.underlying);
+
+        js.line(&doc).block(
+            format!("export const {} = Object.freeze({{", ty),
+            "});",
+            |w| {
+                for v in &e.values {
+                    w.line(format!("{}: {},", v.name, literal(&e.underlying, v.value)));
+                }
+            },
+        );
+        if e.kind == EnumKind::Enum {
+            js.line(format!(
+                "const {}_NAMES = new Map(Object.entries({}).map(([name, value]) => [value, name]));",
+                screaming_snake_case(&e.name),
+                ty
+            ));
+        }
+
+        let names = e
+            .values
+            .iter()
+            .map(|v| format!("\"{}\"", v.name))
+            .collect::<Vec<String>>();
+        dts.line(&doc)
+            .line(format!(
+                "export type {} = {};",
+                ty,
+                if names.is_empty() {
+                    String::from("never")
+                } else {
+                    names.join(" | ")
+                }
+            ))
+            .block(format!("export declare const {}: {{", ty), "};", |w| {
+                for v in &e.values {
+                    w.line(format!(
+                        "readonly {}: {};",
+                        v.name,
+                        literal(&e.underlying, v.value)
+                    ));
+                }
+            });
+        Ok(())
+    }
+
+    fn write_class(
+        &self,
+        js: &mut CodeWriter,
+        dts: &mut CodeWriter,
+        name: &str,
+        id: Option<usize>,
+        doc: &str,
+        layout: &Layout,
+    ) {
+        let ty = type_name(name);
+        let mut constants = Vec::new();
+        if let Some(id) = id {
+            constants.push((String::from("ID"), id.to_string()));
+        }
+        constants.push((String::from("SIZE"), layout.size.to_string()));
+        let frame = self.protocol.header.as_ref().filter(|_| id.is_some());
+        if let Some(header) = frame {
+            constants.push((
+                String::from("FRAME_SIZE"),
+                (header.layout.size + layout.size).to_string(),
+            ));
+        }
+        for f in layout.fields() {
+            let prefix = screaming_snake_case(&f.name);
+            constants.push((format!("{}_OFFSET", prefix), f.offset.to_string()));
+            let scalar = match &f.field_type {
+                FieldType_::Scalar(s) => Some(s),
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                    constants.push((format!("{}_LENGTH", prefix), length.to_string()));
+                    Some(scalar)
+                }
+                _ => None,
+            };
+            if let Some(ScalarType::Decimal(d)) = scalar {
+                constants.push((format!("{}_EXPONENT", prefix), d.exponent.to_string()));
+            }
+        }
+
+        js.blank().line(format!("/** {} */", doc)).block(
+            format!("export class {} {{", ty),
+            "}",
+            |w| {
+                for (name, value) in &constants {
+                    w.line(format!("static {} = {};", name, value));
+                }
+                w.blank()
+                    .line("/** Throws a RangeError when the view is too short */")
+                    .block("constructor(view, offset = 0) {", "}", |w| {
+                        w.line(format!(
+                            "checkSize(view, offset, {}.SIZE, \"{}\");",
+                            ty, name
+                        ))
+                        .line("this.view = view;")
+                        .line("this.offset = offset;");
+                    })
+                    .blank()
+                    .line("/** Wraps a new zeroed buffer */")
+                    .block("static allocate() {", "}", |w| {
+                        w.line(format!(
+                            "return new {}(new DataView(new ArrayBuffer({}.SIZE)));",
+                            ty, ty
+                        ));
+                    });
+                if let (Some(header), Some(id)) = (frame, id) {
+                    w.blank()
+                        .line("/** Writes the header's id and length at offset and views the message after it */")
+                        .block("static wrapFrame(view, offset = 0) {", "}", |w| {
+                            let id_field = header.id();
+                            w.line(format!(
+                                "checkSize(view, offset, {}.FRAME_SIZE, \"the frame of {}\");",
+                                ty, name
+                            ))
+                            .line(format!(
+                                "const header = new {}(view, offset);",
+                                FRAME_HEADER
+                            ))
+                            .line(format!(
+                                "header.{} = {};",
+                                member_name(&id_field.name),
+                                header_literal(id_field, id)
+                            ));
+                            if let Some(length) = header.length() {
+                                w.line(format!(
+                                    "header.{} = {};",
+                                    member_name(&length.name),
+                                    header_literal(length, header.length_value(layout.size))
+                                ));
+                            }
+                            w.line(format!(
+                                "return new {}(view, offset + {}.SIZE);",
+                                ty, FRAME_HEADER
+                            ));
+                        });
+                }
+                for f in layout.fields() {
+                    w.blank();
+                    self.write_accessors(w, &ty, f);
+                    if let FieldType_::Union(ut) = &f.field_type {
+                        self.write_variant_accessors(w, f, ut);
+                    }
+                }
+            },
+        );
+
+        dts.blank().line(format!("/** {} */", doc)).block(
+            format!("export declare class {} {{", ty),
+            "}",
+            |w| {
+                for (name, value) in &constants {
+                    w.line(format!("static readonly {}: {};", name, value));
+                }
+                w.blank()
+                    .line("readonly view: DataView;")
+                    .line("readonly offset: number;")
+                    .line("/** Throws a RangeError when the view is too short */")
+                    .line("constructor(view: DataView, offset?: number);")
+                    .line("/** Wraps a new zeroed buffer */")
+                    .line(format!("static allocate(): {};", ty));
+                if frame.is_some() {
+                    w.line("/** Writes the header's id and length at offset and views the message after it */")
+                        .line(format!(
+                            "static wrapFrame(view: DataView, offset?: number): {};",
+                            ty
+                        ));
+                }
+                for f in layout.fields() {
+                    Self::write_declarations(w, f);
+                    if let FieldType_::Union(ut) = &f.field_type {
+                        self.write_variant_declarations(w, f, ut);
+                    }
+                }
+            },
+        );
+    }
+
+    fn doc(f: &Field) -> String {
+        match &f.description {
+            Some(d) => format!("/** {} at offset {}, {} */", f.field_type, f.offset, d),
+            None => format!("/** {} at offset {} */", f.field_type, f.offset),
+        }
+    }
+
+    fn write_accessors(&self, w: &mut CodeWriter, ty: &str, f: &Field) {
+        let member = member_name(&f.name);
+        let o = at(f.offset);
+        let size = f.field_type.size_bytes();
+        w.line(Self::doc(f));
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                let endian = if s.size_bytes() > 1 {
+                    ", LITTLE_ENDIAN"
+                } else {
+                    ""
+                };
+                w.block(format!("get {}() {{", member), "}", |w| {
+                    w.line(format!(
+                        "return this.view.get{}({}{});",
+                        accessor(s),
+                        o,
+                        endian
+                    ));
+                })
+                .block(format!("set {}(value) {{", member), "}", |w| {
+                    w.line(format!(
+                        "this.view.set{}({}, value{});",
+                        accessor(s),
+                        o,
+                        endian
+                    ));
+                });
+                if let Some((suffix, _, about, get, set)) = native_time(s) {
+                    w.blank()
+                        .line(format!("/** {} {} */", f.name, about))
+                        .block(format!("get {}{}() {{", member, suffix), "}", |w| {
+                            w.line(format!("return {};", get.replace("{}", &member)));
+                        })
+                        .block(format!("set {}{}(value) {{", member, suffix), "}", |w| {
+                            w.line(format!("this.{} = {};", member, set));
+                        });
+                }
+            }
+            FieldType_::Enum(et) => {
+                let endian = if et.underlying.size_bytes() > 1 {
+                    ", LITTLE_ENDIAN"
+                } else {
+                    ""
+                };
+                let enum_type = type_name(&et.name);
+                w.block(format!("get {}() {{", member), "}", |w| {
+                    w.line(match et.kind {
+                        EnumKind::Enum => format!(
+                            "return enumName({}_NAMES, this.{}Raw, \"{}\");",
+                            screaming_snake_case(&et.name),
+                            member,
+                            et.name
+                        ),
+                        EnumKind::Bitflags => {
+                            format!("return flagNames({}, this.{}Raw);", enum_type, member)
+                        }
+                    });
+                })
+                .block(format!("set {}(value) {{", member), "}", |w| {
+                    w.line(match et.kind {
+                        EnumKind::Enum => format!(
+                            "this.{}Raw = enumValue({}, value, \"{}\");",
+                            member, enum_type, et.name
+                        ),
+                        EnumKind::Bitflags => format!(
+                            "this.{}Raw = flagValue({}, value, \"{}\", {});",
+                            member,
+                            enum_type,
+                            et.name,
+                            literal(&et.underlying, 0)
+                        ),
+                    });
+                })
+                .block(format!("get {}Raw() {{", member), "}", |w| {
+                    w.line(format!(
+                        "return this.view.get{}({}{});",
+                        accessor(&et.underlying),
+                        o,
+                        endian
+                    ));
+                })
+                .block(format!("set {}Raw(value) {{", member), "}", |w| {
+                    w.line(format!(
+                        "this.view.set{}({}, value{});",
+                        accessor(&et.underlying),
+                        o,
+                        endian
+                    ));
+                });
+            }
+            FieldType_::Struct(StructType { name, .. })
+            | FieldType_::Union(UnionType { name, .. }) => {
+                w.block(format!("get {}() {{", member), "}", |w| {
+                    w.line(format!("return new {}(this.view, {});", type_name(name), o));
+                });
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                let pad = match padding {
+                    AsciiPadding::Nul => "0",
+                    AsciiPadding::Space => "0x20",
+                };
+                w.block(format!("get {}() {{", member), "}", |w| {
+                    w.line(format!(
+                        "return getAscii(this.view, {}, {}, {});",
+                        o, size, pad
+                    ));
+                })
+                .block(format!("set {}(value) {{", member), "}", |w| {
+                    w.line(format!(
+                        "setAscii(this.view, {}, {}, {}, value, \"{}\");",
+                        o, size, pad, f.name
+                    ));
+                });
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                w.block(format!("get {}() {{", member), "}", |w| {
+                    w.line(format!("return bytes(this.view, {}, {});", o, size));
+                })
+                .block(format!("set {}(value) {{", member), "}", |w| {
+                    w.line(format!(
+                        "setBytes(this.view, {}, {}, value, \"{}\");",
+                        o, size, f.name
+                    ));
+                });
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                let endian = if scalar.size_bytes() > 1 {
+                    ", LITTLE_ENDIAN"
+                } else {
+                    ""
+                };
+                let element = format!("{} + index * {}", o, scalar.size_bytes());
+                let check = format!(
+                    "checkIndex(index, {}.{}_LENGTH, \"{}\");",
+                    ty,
+                    screaming_snake_case(&f.name),
+                    f.name
+                );
+                let pascal = pascal_case(&f.name);
+                w.block(format!("get{}(index) {{", pascal), "}", |w| {
+                    w.line(&check).line(format!(
+                        "return this.view.get{}({}{});",
+                        accessor(scalar),
+                        element,
+                        endian
+                    ));
+                })
+                .block(format!("set{}(index, value) {{", pascal), "}", |w| {
+                    w.line(&check).line(format!(
+                        "this.view.set{}({}, value{});",
+                        accessor(scalar),
+                        element,
+                        endian
+                    ));
+                });
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    fn write_declarations(w: &mut CodeWriter, f: &Field) {
+        let member = member_name(&f.name);
+        w.line(Self::doc(f));
+        match &f.field_type {
+            FieldType_::Scalar(s) => {
+                w.line(format!("{}: {};", member, ts_type(s)));
+                if let Some((suffix, ty, about, _, _)) = native_time(s) {
+                    w.line(format!("/** {} {} */", f.name, about))
+                        .line(format!("{}{}: {};", member, suffix, ty));
+                }
+            }
+            FieldType_::Enum(et) => {
+                let enum_type = type_name(&et.name);
+                match et.kind {
+                    EnumKind::Enum => w.line(format!("{}: {};", member, enum_type)),
+                    EnumKind::Bitflags => w.line(format!("{}: {}[];", member, enum_type)),
+                };
+                w.line(format!("{}Raw: {};", member, ts_type(&et.underlying)));
+            }
+            FieldType_::Struct(StructType { name, .. })
+            | FieldType_::Union(UnionType { name, .. }) => {
+                w.line(format!("readonly {}: {};", member, type_name(name)));
+            }
+            FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                w.line(format!("{}: string;", member));
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                w.line(format!("get {}(): Uint8Array;", member))
+                    .line(format!("set {}(value: ArrayLike<number>);", member));
+            }
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                let pascal = pascal_case(&f.name);
+                w.line(format!(
+                    "get{}(index: number): {};",
+                    pascal,
+                    ts_type(scalar)
+                ))
+                .line(format!(
+                    "set{}(index: number, value: {}): void;",
+                    pascal,
+                    ts_type(scalar)
+                ));
+            }
+            FieldType_::Padding(_) => unreachable!("padding is never a field"),
+        }
+    }
+
+    /// The union of a field, the discriminator's member and the type of its raw value
+    fn union_of(&self, ut: &UnionType) -> (&Union, String, &ScalarType) {
+        let union = self
+            .protocol
+            .union(&ut.name)
+            .expect("the union of a resolved field is declared");
+        let discriminator = self
+            .protocol
+            .enumeration(&union.discriminator_type)
+            .expect("the discriminator of a resolved union is declared");
+        (
+            union,
+            member_name(&ut.discriminator),
+            &discriminator.underlying,
+        )
+    }
+
+    /// Writes a getter for every variant of the union field `f` which is `undefined` unless the
+    /// discriminator selects that variant
+    fn write_variant_accessors(&self, w: &mut CodeWriter, f: &Field, ut: &UnionType) {
+        let (union, discriminator, raw) = self.union_of(ut);
+        let member = member_name(&f.name);
+        for v in &union.variants {
+            let checked = format!("{}{}", member, pascal_case(&v.name));
+            let (getter, params, value) = match &v.field_type {
+                FieldType_::Vector(ArrayLike::FixedArray { .. }) => (
+                    format!("get{}", pascal_case(&checked)),
+                    "index",
+                    format!("this.{}.get{}(index)", member, pascal_case(&v.name)),
+                ),
+                _ => (
+                    format!("get {}", checked),
+                    "",
+                    format!("this.{}.{}", member, member_name(&v.name)),
+                ),
+            };
+            w.blank()
+                .line(format!(
+                    "/** {}.{}, undefined unless {} is {} */",
+                    member,
+                    member_name(&v.name),
+                    discriminator,
+                    v.selector
+                ))
+                .block(format!("{}({}) {{", getter, params), "}", |w| {
+                    w.line(format!(
+                        "return this.{}Raw === {} ? {} : undefined;",
+                        discriminator,
+                        literal(raw, v.value),
+                        value
+                    ));
+                });
+        }
+    }
+
+    fn write_variant_declarations(&self, w: &mut CodeWriter, f: &Field, ut: &UnionType) {
+        let (union, discriminator, _) = self.union_of(ut);
+        let member = member_name(&f.name);
+        for v in &union.variants {
+            let checked = format!("{}{}", member, pascal_case(&v.name));
+            w.line(format!(
+                "/** {}.{}, undefined unless {} is {} */",
+                member,
+                member_name(&v.name),
+                discriminator,
+                v.selector
+            ));
+            match &v.field_type {
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => w.line(format!(
+                    "get{}(index: number): {} | undefined;",
+                    pascal_case(&checked),
+                    ts_type(scalar)
+                )),
+                FieldType_::Scalar(s) => {
+                    w.line(format!("readonly {}: {} | undefined;", checked, ts_type(s)))
+                }
+                FieldType_::Enum(et) => w.line(format!(
+                    "readonly {}: {}{} | undefined;",
+                    checked,
+                    type_name(&et.name),
+                    match et.kind {
+                        EnumKind::Enum => "",
+                        EnumKind::Bitflags => "[]",
+                    }
+                )),
+                FieldType_::Struct(StructType { name, .. })
+                | FieldType_::Union(UnionType { name, .. }) => w.line(format!(
+                    "readonly {}: {} | undefined;",
+                    checked,
+                    type_name(name)
+                )),
+                FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                    w.line(format!("readonly {}: string | undefined;", checked))
+                }
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                    w.line(format!("readonly {}: Uint8Array | undefined;", checked))
+                }
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            };
+        }
+    }
+
+    fn write_decode(&self, js: &mut CodeWriter, dts: &mut CodeWriter) {
+        let protocol = self.protocol;
+        let doc =
+            "/** Views the message with the given id, throws a RangeError for an unknown id */";
+        js.blank()
+            .line(doc)
+            .block("export function decode(id, view, offset = 0) {", "}", |w| {
+                w.block("switch (id) {", "}", |w| {
+                    for m in &protocol.messages {
+                        let ty = type_name(&m.name);
+                        w.line(format!("case {}.ID:", ty))
+                            .indent()
+                            .line(format!("return new {}(view, offset);", ty))
+                            .dedent();
+                    }
+                    w.line("default:")
+                        .indent()
+                        .line(format!(
+                            "throw new RangeError(`${{id}} is not the id of a message of {}`);",
+                            protocol.qualified_name()
+                        ))
+                        .dedent();
+                });
+            });
+
+        let messages = protocol
+            .messages
+            .iter()
+            .map(|m| type_name(&m.name))
+            .collect::<Vec<String>>();
+        dts.blank()
+            .line("/** Every message of the protocol */")
+            .line(format!(
+                "export type AnyMessage = {};",
+                if messages.is_empty() {
+                    String::from("never")
+                } else {
+                    messages.join(" | ")
+                }
+            ))
+            .blank()
+            .line(doc)
+            .line("export declare function decode(id: number, view: DataView, offset?: number): AnyMessage;");
+    }
+
+    /// `dispatch` finds the message of a frame with `decode` and calls the handler's callback for
+    /// it
+    fn write_dispatch(&self, js: &mut CodeWriter, dts: &mut CodeWriter, header: &Header) {
+        let protocol = self.protocol;
+        let doc = [
+            "/**",
+            " * Reads the header at offset and calls the handler's callback for the message after it, returns",
+            " * the size of the frame. Throws a RangeError when the view doesn't hold the whole frame, its",
+            " * length is too small for its message or no message has its id.",
+            " */",
+        ];
+        js.blank()
+            .block("const CALLBACKS = new Map([", "]);", |w| {
+                for m in &protocol.messages {
+                    let ty = type_name(&m.name);
+                    w.line(format!("[{}, \"on{}\"],", ty, ty));
+                }
+            })
+            .blank();
+        for line in doc {
+            js.line(line);
+        }
+        js.block("export function dispatch(view, handler, offset = 0) {", "}", |w| {
+            let id = format!("header.{}", member_name(&header.id().name));
+            w.line(format!("const header = new {}(view, offset);", FRAME_HEADER))
+                .line(format!(
+                    "const message = decode({}, view, offset + {}.SIZE);",
+                    header_number(header.id(), &id),
+                    FRAME_HEADER
+                ))
+                .line("const type = message.constructor;");
+            match header.length() {
+                Some(length) => {
+                    let value = header_number(
+                        length,
+                        &format!("header.{}", member_name(&length.name)),
+                    );
+                    w.line(match header.length_value(0) {
+                        0 => format!("const frame = {}.SIZE + {};", FRAME_HEADER, value),
+                        _ => format!("const frame = {};", value),
+                    })
+                    .block("if (frame < type.FRAME_SIZE) {", "}", |w| {
+                        w.line("throw new RangeError(`a frame length of ${frame} is too small for ${type.name}`);");
+                    })
+                    .line("checkSize(view, offset, frame, \"the frame\");");
+                }
+                None => {
+                    w.line("const frame = type.FRAME_SIZE;");
+                }
+            }
+            w.line("handler[CALLBACKS.get(type)]?.(message);")
+                .line("return frame;");
+        });
+
+        dts.blank()
+            .line("/** Callbacks for dispatch, a message without a callback is ignored */")
+            .block("export interface Handler {", "}", |w| {
+                for m in &protocol.messages {
+                    let ty = type_name(&m.name);
+                    w.line(format!("on{}?(message: {}): void;", ty, ty));
+                }
+            })
+            .blank();
+        for line in doc {
+            dts.line(line);
+        }
+        dts.line("export declare function dispatch(view: DataView, handler: Handler, offset?: number): number;");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::typescript::TypeScriptGenerator;
+    use crate::codegen::{Generator, GeneratorOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_module_and_typings() {
+        let protocol = crate::sssc::compile(
+            "protocol foo.bar [byte_order = big]; message M [1] { u64: offset; i16: b; }",
+        )
+        .unwrap();
+        let files = TypeScriptGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert_eq!(Path::new("foo_bar.js"), files[0].path);
+        assert_eq!(Path::new("foo_bar.d.ts"), files[1].path);
+        assert!(files[0].contents.contains("const LITTLE_ENDIAN = false;"));
+        assert!(files[0]
+            .contents
+            .contains("return this.view.getBigUint64(this.offset, LITTLE_ENDIAN);"));
+        assert!(files[1].contents.contains("offset_: bigint;"));
+        assert!(files[1].contents.contains("b: number;"));
+    }
+
+    #[test]
+    fn test_64_bit_enums_are_bigints() {
+        let protocol = crate::sssc::compile(
+            "protocol foo; enum E : u64 { A = 1, B = 2 } message M [1] { E: e; }",
+        )
+        .unwrap();
+        let files = TypeScriptGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap();
+        assert!(files[0].contents.contains("  A: 1n,"));
+        assert!(files[1].contents.contains("readonly A: 1n;"));
+        assert!(files[1].contents.contains("eRaw: bigint;"));
+    }
+}
@@ -0,0 +1,898 @@
+//! Generates a Wireshark dissector in Lua. Every field gets a `ProtoField` added at its offset in
+//! the protocol's byte order, enums get value strings and bitflags a boolean field per flag.
+//!
+//! A protocol with a header has every message preceded by it, the dissector finds the message by
+//! the header's id field and skips to the next one by its length field. Without a header the wire
+//! carries no framing of its own, so the dissector expects every message to be preceded by its id,
+//! an unsigned integer of `id_size` bytes.
+
+use crate::codegen::{
+    snake_case, type_order, CodeWriter, Generator, GeneratorError, GeneratorOptions, OutputFile,
+    TypeDeclaration,
+};
+use crate::data_types::scalar::{ByteSize, Mantissa};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType};
+use crate::protocol::{ByteOrder, Enum, Field, Header, Layout, Protocol, Union};
+
+/// Turns the mantissa of a decimal into text, without going through a float
+const DECIMAL_TEXT: &str = r#"local function decimal_text(mantissa, exponent)
+    local text = tostring(mantissa)
+    if exponent >= 0 then
+        return text .. string.rep("0", exponent)
+    end
+    local sign = ""
+    if text:sub(1, 1) == "-" then
+        sign, text = "-", text:sub(2)
+    end
+    local digits = -exponent
+    if #text <= digits then
+        text = string.rep("0", digits - #text + 1) .. text
+    end
+    return sign .. text:sub(1, #text - digits) .. "." .. text:sub(#text - digits + 1)
+end
+
+local function ascii(range)
+    return (range:string():match("^[^\0]*"))
+end"#;
+
+/// Generates `<protocol>.lua`, options: `id_size` the size of the id before each message, 1, 2 or 4
+/// bytes defaulting to 2 and unused when the protocol has a header, and `port` a UDP port to
+/// register the dissector on
+pub struct WiresharkGenerator;
+
+impl Generator for WiresharkGenerator {
+    fn name(&self) -> &str {
+        "wireshark"
+    }
+
+    fn description(&self) -> &str {
+        "Wireshark Lua dissector, options: id_size, port"
+    }
+
+    fn generate(
+        &self,
+        protocol: &Protocol,
+        options: &GeneratorOptions,
+    ) -> Result<Vec<OutputFile>, GeneratorError> {
+        options.check_known(&["id_size", "port"])?;
+
+        let id_size = match options.get("id_size") {
+            None => 2,
+            Some(size) => match size.parse::<usize>() {
+                Ok(size @ (1 | 2 | 4)) => size,
+                _ => {
+                    return Err(GeneratorError::InvalidOption {
+                        name: String::from("id_size"),
+                        reason: format!("expected 1, 2 or 4 but got '{}'", size),
+                    })
+                }
+            },
+        };
+        if let Some(m) = protocol
+            .messages
+            .iter()
+            .filter(|_| protocol.header.is_none())
+            .find(|m| m.id >= 1 << (id_size * 8))
+        {
+            return Err(GeneratorError::Unsupported(format!(
+                "the id of message '{}' does not fit in {} bytes",
+                m.name, id_size
+            )));
+        }
+        let port = match options.get("port") {
+            None => None,
+            Some(port) => Some(
+                port.parse::<u16>()
+                    .map_err(|_| GeneratorError::InvalidOption {
+                        name: String::from("port"),
+                        reason: format!("'{}' is not a UDP port", port),
+                    })?,
+            ),
+        };
+
+        let filter = snake_case(&protocol.name.join("_"));
+        let dissector = Dissector {
+            protocol,
+            filter: &filter,
+            id_size,
+            port,
+            little: protocol.byte_order == ByteOrder::Little,
+        };
+        Ok(vec![OutputFile::new(
+            format!("{}.lua", filter),
+            dissector.generate()?,
+        )])
+    }
+}
+
+struct Dissector<'p> {
+    protocol: &'p Protocol,
+    /// The name of the protocol in display filters, which prefixes every field
+    filter: &'p str,
+    id_size: usize,
+    port: Option<u16>,
+    little: bool,
+}
+
+fn storage(scalar: &ScalarType) -> ScalarType {
+    match scalar {
+        ScalarType::Decimal(d) => match d.mantissa {
+            Mantissa::Unsigned(u) => ScalarType::Unsigned(u),
+            Mantissa::Signed(s) => ScalarType::Signed(s),
+        },
+        ScalarType::Timestamp(t) => t.storage(),
+        ScalarType::Duration(d) => d.storage(),
+        other => *other,
+    }
+}
+
+/// The `ProtoField` constructor and display base of an integer or a byte
+fn integer_field(scalar: &ScalarType) -> (String, &'static str) {
+    match storage(scalar) {
+        ScalarType::Unsigned(u) => (format!("uint{}", u.size_bytes() * 8), "base.DEC"),
+        ScalarType::Signed(s) => (format!("int{}", s.size_bytes() * 8), "base.DEC"),
+        ScalarType::ByteSized(ByteSize::Byte) => (String::from("uint8"), "base.HEX"),
+        _ => (String::from("char"), "base.NONE"),
+    }
+}
+
+/// A `ProtoField` showing a scalar, `abbrev`, `name` and `description` are already quoted
+fn scalar_field(scalar: &ScalarType, abbrev: &str, name: &str, description: &str) -> String {
+    match storage(scalar) {
+        ScalarType::Float(f) => format!(
+            "ProtoField.{}({}, {}, nil, {})",
+            if f.size_bytes() == 4 {
+                "float"
+            } else {
+                "double"
+            },
+            abbrev,
+            name,
+            description
+        ),
+        _ => {
+            let (constructor, base) = integer_field(scalar);
+            format!(
+                "ProtoField.{}({}, {}, {}, nil, nil, {})",
+                constructor, abbrev, name, base, description
+            )
+        }
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The name of the header in the tree and the prefix of its fields
+const FRAME_HEADER: &str = "FrameHeader";
+
+/// `offset + n`, leaving out adding zero
+fn at(offset: usize) -> String {
+    if offset == 0 {
+        String::from("offset")
+    } else {
+        format!("offset + {}", offset)
+    }
+}
+
+impl Dissector<'_> {
+    fn add(&self) -> &'static str {
+        if self.little {
+            "add_le"
+        } else {
+            "add"
+        }
+    }
+
+    /// The `TvbRange` method reading an integer of `size` bytes in the protocol's byte order
+    fn reader(&self, signed: bool, size: usize) -> String {
+        format!(
+            "{}{}{}",
+            if self.little { "le_" } else { "" },
+            if signed { "int" } else { "uint" },
+            if size == 8 { "64" } else { "" }
+        )
+    }
+
+    fn abbrev(&self, path: &str) -> String {
+        format!("{}.{}", self.filter, path)
+    }
+
+    /// Reads an unsigned field of the header at the start of a frame, as a Lua number
+    fn read_header(&self, field: &Field) -> String {
+        let size = field.field_type.size_bytes();
+        format!(
+            "buffer({}, {}):{}(){}",
+            at(field.offset),
+            size,
+            self.reader(false, size),
+            if size == 8 { ":tonumber()" } else { "" }
+        )
+    }
+
+    fn generate(&self) -> Result<String, GeneratorError> {
+        let protocol = self.protocol;
+        let mut w = CodeWriter::new("    ");
+        w.line(format!(
+            "-- Generated by sssc {} from protocol {}, do not edit.",
+            env!("CARGO_PKG_VERSION"),
+            protocol.qualified_name()
+        ))
+        .line(match &protocol.header {
+            Some(header) => format!(
+                "-- Load with `wireshark -X lua_script:{}.lua`, every message is preceded by a header of {} bytes.",
+                self.filter, header.layout.size
+            ),
+            None => format!(
+                "-- Load with `wireshark -X lua_script:{}.lua`, every message is preceded by its id, a {} byte unsigned integer.",
+                self.filter, self.id_size
+            ),
+        })
+        .blank()
+        .line(format!(
+            "local proto = Proto({}, {})",
+            quote(self.filter),
+            quote(&protocol.qualified_name())
+        ))
+        .blank()
+        .lines(DECIMAL_TEXT)
+        .blank()
+        .line("-- a boolean field per flag of each bitflags")
+        .line("local flags = {}");
+
+        for e in &protocol.enums {
+            w.blank();
+            self.write_enum(&mut w, e)?;
+        }
+
+        w.blank().block("local message_names = {", "}", |w| {
+            for m in &protocol.messages {
+                w.line(format!("[{}] = {},", m.id, quote(&m.name)));
+            }
+        });
+
+        w.blank().line("local fields = {}");
+        match &protocol.header {
+            Some(header) => {
+                let prefix = snake_case(FRAME_HEADER);
+                w.line(format!(
+                    "fields.{} = ProtoField.none({}, {})",
+                    prefix,
+                    quote(&self.abbrev(&prefix)),
+                    quote(FRAME_HEADER)
+                ));
+                self.write_fields(&mut w, FRAME_HEADER, &header.layout);
+            }
+            None => {
+                w.line(format!(
+                    "fields.id = ProtoField.uint{}({}, \"id\", base.DEC, message_names)",
+                    self.id_size * 8,
+                    quote(&self.abbrev("id"))
+                ));
+            }
+        }
+        for declaration in type_order(protocol) {
+            match declaration {
+                TypeDeclaration::Struct(s) => self.write_fields(&mut w, &s.name, &s.layout),
+                TypeDeclaration::Union(u) => self.write_fields(&mut w, &u.name, &Self::layout(u)),
+            }
+        }
+        for m in &protocol.messages {
+            self.write_fields(&mut w, &m.name, &m.layout);
+        }
+        w.blank()
+            .line("local unknown_id = ProtoExpert.new(")
+            .indent()
+            .line(format!(
+                "{}, \"Unknown message id\", expert.group.MALFORMED, expert.severity.ERROR)",
+                quote(&self.abbrev("unknown_id"))
+            ))
+            .dedent()
+            .line("local truncated = ProtoExpert.new(")
+            .indent()
+            .line(format!(
+                "{}, \"Truncated message\", expert.group.MALFORMED, expert.severity.ERROR)",
+                quote(&self.abbrev("truncated"))
+            ))
+            .dedent();
+        let bad_length = protocol
+            .header
+            .as_ref()
+            .is_some_and(|h| h.length().is_some());
+        if bad_length {
+            w.line("local bad_length = ProtoExpert.new(")
+                .indent()
+                .line(format!(
+                    "{}, \"Frame length too small for its message\", expert.group.MALFORMED, expert.severity.ERROR)",
+                    quote(&self.abbrev("bad_length"))
+                ))
+                .dedent();
+        }
+        w.blank()
+            .line("local field_list = {}")
+            .block("for _, field in pairs(fields) do", "end", |w| {
+                w.line("field_list[#field_list + 1] = field");
+            })
+            .block("for _, field in pairs(flags) do", "end", |w| {
+                w.line("field_list[#field_list + 1] = field");
+            })
+            .line("proto.fields = field_list")
+            .line(if bad_length {
+                "proto.experts = { unknown_id, truncated, bad_length }"
+            } else {
+                "proto.experts = { unknown_id, truncated }"
+            });
+
+        for declaration in type_order(protocol) {
+            w.blank();
+            match declaration {
+                TypeDeclaration::Struct(s) => self.write_dissect(&mut w, &s.name, &s.layout, None),
+                TypeDeclaration::Union(u) => {
+                    self.write_dissect(&mut w, &u.name, &Self::layout(u), Some(u))
+                }
+            }
+        }
+        if let Some(header) = &protocol.header {
+            w.blank();
+            self.write_dissect(&mut w, FRAME_HEADER, &header.layout, None);
+        }
+        for m in &protocol.messages {
+            w.blank();
+            self.write_dissect(&mut w, &m.name, &m.layout, None);
+        }
+
+        w.blank().block("local messages = {", "}", |w| {
+            for m in &protocol.messages {
+                w.line(format!(
+                    "[{}] = {{ name = {}, size = {}, dissect = dissect_{} }},",
+                    m.id,
+                    quote(&m.name),
+                    m.layout.size,
+                    snake_case(&m.name)
+                ));
+            }
+        });
+        match &protocol.header {
+            Some(header) => self.write_frame_dissector(&mut w, header),
+            None => self.write_dissector(&mut w),
+        }
+        self.register(&mut w);
+        Ok(w.finish())
+    }
+
+    /// Every variant of a union starts at its first byte
+    fn layout(u: &Union) -> Layout {
+        Layout {
+            size: u.size,
+            alignment: u.alignment,
+            members: u
+                .variants
+                .iter()
+                .map(|v| {
+                    crate::protocol::Member::Field(Field {
+                        name: v.name.clone(),
+                        offset: 0,
+                        field_type: v.field_type.clone(),
+                        description: Some(format!(
+                            "active when the discriminator is {}",
+                            v.selector
+                        )),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn write_enum(&self, w: &mut CodeWriter, e: &Enum) -> Result<(), GeneratorError> {
+        if !matches!(
+            e.underlying,
+            ScalarType::Unsigned(_) | ScalarType::Signed(_) | ScalarType::ByteSized(_)
+        ) {
+            return Err(GeneratorError::Unsupported(format!(
+                "enum '{}' is stored as a {}",
+                e.name, e.underlying
+            )));
+        }
+        let name = snake_case(&e.name);
+        match e.kind {
+            EnumKind::Enum => {
+                w.line(format!("-- enum {}, stored as a {}", e.name, e.underlying))
+                    .block(format!("local {}_names = {{", name), "}", |w| {
+                        for v in &e.values {
+                            w.line(format!("[{}] = {},", v.value, quote(&v.name)));
+                        }
+                    });
+            }
+            EnumKind::Bitflags => {
+                let bits = e.underlying.size_bytes() * 8;
+                w.line(format!(
+                    "-- bitflags {}, stored as a {}",
+                    e.name, e.underlying
+                ));
+                for v in &e.values {
+                    w.line(format!(
+                        "flags[{}] = ProtoField.bool({}, {}, {}, nil, {:#x})",
+                        quote(&format!("{}.{}", name, snake_case(&v.name))),
+                        quote(&self.abbrev(&format!("{}.{}", name, snake_case(&v.name)))),
+                        quote(&v.name),
+                        bits,
+                        v.value
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+    fn write_fields(&self, w: &mut CodeWriter, name: &str, layout: &Layout) {
+        let prefix = snake_case(name);
+        w.blank().line(format!("-- {}", name));
+        for f in layout.fields() {
+            let key = format!("{}.{}", prefix, f.name);
+            let description = quote(&f.field_type.to_string());
+            let header_id = name == FRAME_HEADER
+                && self
+                    .protocol
+                    .header
+                    .as_ref()
+                    .is_some_and(|h| h.id_field == f.name);
+            let field = match &f.field_type {
+                // the header's id shows the name of its message
+                FieldType_::Scalar(s) if header_id => format!(
+                    "ProtoField.{}({}, {}, base.DEC, message_names, nil, {})",
+                    integer_field(s).0,
+                    quote(&self.abbrev(&key)),
+                    quote(&f.name),
+                    description
+                ),
+                FieldType_::Scalar(s) => {
+                    scalar_field(s, &quote(&self.abbrev(&key)), &quote(&f.name), &description)
+                }
+                FieldType_::Enum(et) => {
+                    let (constructor, _) = integer_field(&et.underlying);
+                    let (base, names) = match et.kind {
+                        EnumKind::Enum => ("base.DEC", format!("{}_names", snake_case(&et.name))),
+                        EnumKind::Bitflags => ("base.HEX", String::from("nil")),
+                    };
+                    format!(
+                        "ProtoField.{}({}, {}, {}, {}, nil, {})",
+                        constructor,
+                        quote(&self.abbrev(&key)),
+                        quote(&f.name),
+                        base,
+                        names,
+                        description
+                    )
+                }
+                FieldType_::Struct(_) | FieldType_::Union(_) => format!(
+                    "ProtoField.none({}, {}, {})",
+                    quote(&self.abbrev(&key)),
+                    quote(&f.name),
+                    description
+                ),
+                FieldType_::Vector(ArrayLike::AsciiString { .. }) => format!(
+                    "ProtoField.string({}, {}, base.ASCII, {})",
+                    quote(&self.abbrev(&key)),
+                    quote(&f.name),
+                    description
+                ),
+                FieldType_::Vector(ArrayLike::Bytes { .. }) => format!(
+                    "ProtoField.bytes({}, {}, base.NONE, {})",
+                    quote(&self.abbrev(&key)),
+                    quote(&f.name),
+                    description
+                ),
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+                    w.line(format!(
+                        "fields[{}] = {}",
+                        quote(&format!("{}.element", key)),
+                        scalar_field(
+                            scalar,
+                            &quote(&self.abbrev(&format!("{}.element", key))),
+                            &quote(&f.name),
+                            &quote(&scalar.to_string())
+                        )
+                    ));
+                    format!(
+                        "ProtoField.none({}, {}, {})",
+                        quote(&self.abbrev(&key)),
+                        quote(&f.name),
+                        description
+                    )
+                }
+                FieldType_::Padding(_) => unreachable!("padding is never a field"),
+            };
+            w.line(format!("fields[{}] = {}", quote(&key), field));
+        }
+    }
+
+    /// Adds a scalar to `tree`, decimals get their value appended as text
+    fn add_scalar(&self, tree: &str, key: &str, at: &str, scalar: &ScalarType) -> String {
+        let size = scalar.size_bytes();
+        match scalar {
+            ScalarType::Decimal(d) => format!(
+                "{}:{}(fields[{}], buffer({}, {})):append_text(\" (\" .. decimal_text(buffer({}, {}):{}(), {}) .. \")\")",
+                tree,
+                self.add(),
+                quote(key),
+                at,
+                size,
+                at,
+                size,
+                self.reader(matches!(d.mantissa, Mantissa::Signed(_)), size),
+                d.exponent
+            ),
+            ScalarType::ByteSized(_) => {
+                format!("{}:add(fields[{}], buffer({}, 1))", tree, quote(key), at)
+            }
+            _ => format!(
+                "{}:{}(fields[{}], buffer({}, {}))",
+                tree,
+                self.add(),
+                quote(key),
+                at,
+                size
+            ),
+        }
+    }
+
+    fn write_dissect(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        layout: &Layout,
+        union: Option<&Union>,
+    ) {
+        let prefix = snake_case(name);
+        let signature = match union {
+            Some(_) => format!(
+                "local function dissect_{}(buffer, offset, tree, selector)",
+                prefix
+            ),
+            None => format!("local function dissect_{}(buffer, offset, tree)", prefix),
+        };
+        w.block(signature, "end", |w| {
+            for f in layout.fields() {
+                let key = format!("{}.{}", prefix, f.name);
+                let o = at(f.offset);
+                let size = f.field_type.size_bytes();
+                let variant = union.and_then(|u| u.variants.iter().find(|v| v.name == f.name));
+                if let Some(v) = variant {
+                    w.line(format!("if selector == {} then", v.value)).indent();
+                }
+                match &f.field_type {
+                    FieldType_::Scalar(s) => {
+                        w.line(self.add_scalar("tree", &key, &o, s));
+                    }
+                    FieldType_::Enum(et) => match et.kind {
+                        EnumKind::Enum => {
+                            w.line(format!(
+                                "tree:{}(fields[{}], buffer({}, {}))",
+                                self.add(),
+                                quote(&key),
+                                o,
+                                size
+                            ));
+                        }
+                        EnumKind::Bitflags => {
+                            let item = format!("{}_item", snake_case(&f.name));
+                            w.line(format!(
+                                "local {} = tree:{}(fields[{}], buffer({}, {}))",
+                                item,
+                                self.add(),
+                                quote(&key),
+                                o,
+                                size
+                            ));
+                            if let Some(e) = self.protocol.enums.iter().find(|e| e.name == et.name)
+                            {
+                                for v in &e.values {
+                                    w.line(format!(
+                                        "{}:{}(flags[{}], buffer({}, {}))",
+                                        item,
+                                        self.add(),
+                                        quote(&format!(
+                                            "{}.{}",
+                                            snake_case(&e.name),
+                                            snake_case(&v.name)
+                                        )),
+                                        o,
+                                        size
+                                    ));
+                                }
+                            }
+                        }
+                    },
+                    FieldType_::Struct(st) => {
+                        w.line(format!(
+                            "dissect_{}(buffer, {}, tree:add(fields[{}], buffer({}, {})))",
+                            snake_case(&st.name),
+                            o,
+                            quote(&key),
+                            o,
+                            size
+                        ));
+                    }
+                    FieldType_::Union(ut) => {
+                        let selector = match layout.field(&ut.discriminator) {
+                            Some(d) => {
+                                format!(
+                                    "buffer({}, {}):{}()",
+                                    at(d.offset),
+                                    d.field_type.size_bytes(),
+                                    self.reader(false, d.field_type.size_bytes())
+                                ) + if d.field_type.size_bytes() == 8 {
+                                    ":tonumber()"
+                                } else {
+                                    ""
+                                }
+                            }
+                            None => String::from("nil"),
+                        };
+                        w.line(format!(
+                            "dissect_{}(buffer, {}, tree:add(fields[{}], buffer({}, {})), {})",
+                            snake_case(&ut.name),
+                            o,
+                            quote(&key),
+                            o,
+                            size,
+                            selector
+                        ));
+                    }
+                    FieldType_::Vector(ArrayLike::AsciiString { .. }) => {
+                        w.line(format!(
+                            "tree:add(fields[{}], buffer({}, {}), ascii(buffer({}, {})))",
+                            quote(&key),
+                            o,
+                            size,
+                            o,
+                            size
+                        ));
+                    }
+                    FieldType_::Vector(ArrayLike::Bytes { .. }) => {
+                        w.line(format!(
+                            "tree:add(fields[{}], buffer({}, {}))",
+                            quote(&key),
+                            o,
+                            size
+                        ));
+                    }
+                    FieldType_::Vector(ArrayLike::FixedArray { scalar, length }) => {
+                        let local = format!("{}_item", snake_case(&f.name));
+                        w.line(format!(
+                            "local {} = tree:add(fields[{}], buffer({}, {}))",
+                            local,
+                            quote(&key),
+                            o,
+                            size
+                        ))
+                        .block(
+                            format!("for i = 0, {} do", length - 1),
+                            "end",
+                            |w| {
+                                let element = self.add_scalar(
+                                    &local,
+                                    &format!("{}.element", key),
+                                    &format!("{} + i * {}", o, scalar.size_bytes()),
+                                    scalar,
+                                );
+                                w.line(format!("{}:prepend_text(\"[\" .. i .. \"] \")", element));
+                            },
+                        );
+                    }
+                    FieldType_::Padding(_) => unreachable!("padding is never a field"),
+                }
+                if variant.is_some() {
+                    w.dedent().line("end");
+                }
+            }
+        });
+    }
+
+    fn write_dissector(&self, w: &mut CodeWriter) {
+        let id_size = self.id_size;
+        w.blank()
+            .block("function proto.dissector(buffer, pinfo, tree)", "end", |w| {
+                w.line("pinfo.cols.protocol = proto.name")
+                    .line("local names = {}")
+                    .line("local offset = 0")
+                    .block(
+                        format!("while offset + {} <= buffer:len() do", id_size),
+                        "end",
+                        |w| {
+                            w.line(format!(
+                                "local id = buffer(offset, {}):{}()",
+                                id_size,
+                                self.reader(false, id_size)
+                            ))
+                            .line("local message = messages[id]")
+                            .block("if message == nil then", "end", |w| {
+                                w.line(format!(
+                                    "tree:{}(fields.id, buffer(offset, {})):add_proto_expert_info(unknown_id)",
+                                    self.add(),
+                                    id_size
+                                ))
+                                .line("break");
+                            })
+                            .line(format!(
+                                "local length = math.min({} + message.size, buffer:len() - offset)",
+                                id_size
+                            ))
+                            .line("local subtree = tree:add(proto, buffer(offset, length), message.name)")
+                            .line(format!(
+                                "subtree:{}(fields.id, buffer(offset, {}))",
+                                self.add(),
+                                id_size
+                            ))
+                            .line("names[#names + 1] = message.name")
+                            .block(
+                                format!("if length < {} + message.size then", id_size),
+                                "end",
+                                |w| {
+                                    w.line("subtree:add_proto_expert_info(truncated)")
+                                        .line("break");
+                                },
+                            )
+                            .line(format!(
+                                "message.dissect(buffer, offset + {}, subtree)",
+                                id_size
+                            ))
+                            .line("offset = offset + length");
+                        },
+                    )
+                    .line("pinfo.cols.info = table.concat(names, \", \")")
+                    .line("return offset");
+            });
+    }
+
+    /// Walks the frames of a packet, each a header followed by the message its id field names
+    fn write_frame_dissector(&self, w: &mut CodeWriter, header: &Header) {
+        let size = header.layout.size;
+        let prefix = snake_case(FRAME_HEADER);
+        let add_header = format!(
+            "dissect_{}(buffer, offset, {{}}:add(fields.{}, buffer(offset, {})))",
+            prefix, prefix, size
+        );
+        w.blank()
+            .block("function proto.dissector(buffer, pinfo, tree)", "end", |w| {
+                w.line("pinfo.cols.protocol = proto.name")
+                    .line("local names = {}")
+                    .line("local offset = 0")
+                    .block(
+                        format!("while offset + {} <= buffer:len() do", size),
+                        "end",
+                        |w| {
+                            w.line(format!("local id = {}", self.read_header(header.id())))
+                                .line("local message = messages[id]")
+                                .block("if message == nil then", "end", |w| {
+                                    w.line(format!(
+                                        "local item = tree:add(fields.{}, buffer(offset, {}))",
+                                        prefix, size
+                                    ))
+                                    .line(format!(
+                                        "dissect_{}(buffer, offset, item)",
+                                        prefix
+                                    ))
+                                    .line("item:add_proto_expert_info(unknown_id)")
+                                    .line("break");
+                                });
+                            let frame = match header.length() {
+                                Some(length) => match header.length_value(0) {
+                                    0 => format!("{} + {}", size, self.read_header(length)),
+                                    _ => self.read_header(length),
+                                },
+                                None => format!("{} + message.size", size),
+                            };
+                            w.line(format!("local frame = {}", frame))
+                                .line("local length = math.min(frame, buffer:len() - offset)")
+                                .line("local subtree = tree:add(proto, buffer(offset, length), message.name)")
+                                .line(add_header.replace("{}", "subtree"))
+                                .line("names[#names + 1] = message.name");
+                            if header.length().is_some() {
+                                w.block(
+                                    format!("if frame < {} + message.size then", size),
+                                    "end",
+                                    |w| {
+                                        w.line("subtree:add_proto_expert_info(bad_length)")
+                                            .line("break");
+                                    },
+                                );
+                            }
+                            w.block("if length < frame then", "end", |w| {
+                                w.line("subtree:add_proto_expert_info(truncated)")
+                                    .line("break");
+                            })
+                            .line(format!(
+                                "message.dissect(buffer, offset + {}, subtree)",
+                                size
+                            ))
+                            .line("offset = offset + frame");
+                        },
+                    )
+                    .line("pinfo.cols.info = table.concat(names, \", \")")
+                    .line("return offset");
+            });
+    }
+
+    fn register(&self, w: &mut CodeWriter) {
+        w.blank();
+        match self.port {
+            Some(port) => w.line(format!(
+                "DissectorTable.get(\"udp.port\"):add({}, proto)",
+                port
+            )),
+            None => w.line("DissectorTable.get(\"udp.port\"):add_for_decode_as(proto)"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::wireshark::WiresharkGenerator;
+    use crate::codegen::{Generator, GeneratorError, GeneratorOptions};
+
+    fn options(parameters: &[(&str, &str)]) -> GeneratorOptions {
+        GeneratorOptions {
+            parameters: parameters
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_options() {
+        let protocol = crate::sssc::compile("protocol foo; message M [300] { u8: a; }").unwrap();
+        let contents = &WiresharkGenerator
+            .generate(&protocol, &options(&[("id_size", "4"), ("port", "9000")]))
+            .unwrap()[0]
+            .contents;
+        assert!(contents.contains("local id = buffer(offset, 4):le_uint()"));
+        assert!(contents.contains("DissectorTable.get(\"udp.port\"):add(9000, proto)"));
+
+        assert!(matches!(
+            WiresharkGenerator.generate(&protocol, &options(&[("id_size", "1")])),
+            Err(GeneratorError::Unsupported(_))
+        ));
+        assert!(matches!(
+            WiresharkGenerator.generate(&protocol, &options(&[("id_size", "3")])),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+        assert!(matches!(
+            WiresharkGenerator.generate(&protocol, &options(&[("port", "70000")])),
+            Err(GeneratorError::InvalidOption { .. })
+        ));
+    }
+
+    #[test]
+    fn test_big_endian() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [1] { u16: a; decimal<u32, -2>: b; }",
+        )
+        .unwrap();
+        let contents = &WiresharkGenerator
+            .generate(&protocol, &GeneratorOptions::default())
+            .unwrap()[0]
+            .contents;
+        assert!(contents.contains("tree:add(fields[\"m.a\"], buffer(offset, 2))"));
+        assert!(contents.contains("decimal_text(buffer(offset + 2, 4):uint(), -2)"));
+        assert!(!contents.contains("add_le"));
+    }
+
+    #[test]
+    fn test_header() {
+        let protocol = crate::sssc::compile(
+            "protocol foo; header { @length(message) u32: length; @id u32: id; } message M [70000] { u8: a; }",
+        )
+        .unwrap();
+        // the id comes from the header, so id_size doesn't limit it
+        let contents = &WiresharkGenerator
+            .generate(&protocol, &options(&[("id_size", "1")]))
+            .unwrap()[0]
+            .contents;
+        assert!(contents.contains("local id = buffer(offset + 4, 4):le_uint()"));
+        assert!(contents.contains("local frame = 8 + buffer(offset, 4):le_uint()"));
+        assert!(contents.contains("message.dissect(buffer, offset + 8, subtree)"));
+        assert!(!contents.contains("fields.id ="));
+    }
+}
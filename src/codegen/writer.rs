@@ -0,0 +1,112 @@
+/// Accumulates generated source, keeping track of the current indentation
+pub struct CodeWriter {
+    buffer: String,
+    depth: usize,
+    indent: &'static str,
+}
+
+impl CodeWriter {
+    /// `indent` is written once per level of indentation, e.g. four spaces or a tab
+    pub fn new(indent: &'static str) -> Self {
+        CodeWriter {
+            buffer: String::new(),
+            depth: 0,
+            indent,
+        }
+    }
+
+    /// Writes a single line at the current indentation, empty lines are left unindented
+    pub fn line(&mut self, text: impl AsRef<str>) -> &mut Self {
+        let text = text.as_ref();
+        if !text.is_empty() {
+            for _ in 0..self.depth {
+                self.buffer.push_str(self.indent);
+            }
+            self.buffer.push_str(text);
+        }
+        self.buffer.push('\n');
+        self
+    }
+
+    /// Writes each line of `text` at the current indentation
+    pub fn lines(&mut self, text: impl AsRef<str>) -> &mut Self {
+        for line in text.as_ref().lines() {
+            self.line(line);
+        }
+        self
+    }
+
+    pub fn blank(&mut self) -> &mut Self {
+        self.line("")
+    }
+
+    pub fn indent(&mut self) -> &mut Self {
+        self.depth += 1;
+        self
+    }
+
+    pub fn dedent(&mut self) -> &mut Self {
+        self.depth = self.depth.saturating_sub(1);
+        self
+    }
+
+    /// Writes `open`, the body one level deeper, then `close`
+    pub fn block(
+        &mut self,
+        open: impl AsRef<str>,
+        close: impl AsRef<str>,
+        body: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        self.line(open);
+        self.indent();
+        body(self);
+        self.dedent();
+        self.line(close)
+    }
+
+    /// Writes `open` then the body one level deeper, for languages where indentation alone
+    /// ends a block
+    pub fn suite(&mut self, open: impl AsRef<str>, body: impl FnOnce(&mut Self)) -> &mut Self {
+        self.line(open);
+        self.indent();
+        body(self);
+        self.dedent()
+    }
+
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::writer::CodeWriter;
+
+    #[test]
+    fn test_blocks_are_indented() {
+        let mut w = CodeWriter::new("  ");
+        w.line("// header").blank();
+        w.block("struct Foo {", "}", |w| {
+            w.line("a: u8,");
+            w.block("inner {", "}", |w| {
+                w.lines("b\nc");
+            });
+        });
+
+        assert_eq!(
+            "// header\n\nstruct Foo {\n  a: u8,\n  inner {\n    b\n    c\n  }\n}\n",
+            w.finish()
+        );
+    }
+
+    #[test]
+    fn test_suites_have_no_close() {
+        let mut w = CodeWriter::new("    ");
+        w.suite("def foo():", |w| {
+            w.line("return 1");
+        })
+        .line("x = foo()");
+
+        assert_eq!("def foo():\n    return 1\nx = foo()\n", w.finish());
+    }
+}
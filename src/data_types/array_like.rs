@@ -1,17 +1,66 @@
 use crate::data_types::{FieldType, ScalarType};
-#[derive(Debug)]
+use std::fmt::{Display, Formatter};
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ArrayLike {
-    FixedArray { scalar: ScalarType, length: usize },
-    AsciiString { length: usize },
-    Bytes { length: usize },
+    FixedArray {
+        scalar: ScalarType,
+        length: usize,
+    },
+    AsciiString {
+        length: usize,
+        #[cfg_attr(feature = "serde", serde(default))]
+        padding: AsciiPadding,
+    },
+    Bytes {
+        length: usize,
+    },
+}
+
+/// What fills an `ascii` string shorter than its field, chosen with `@pad(nul)` or `@pad(space)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AsciiPadding {
+    #[default]
+    Nul,
+    Space,
+}
+
+impl AsciiPadding {
+    pub fn byte(&self) -> u8 {
+        match self {
+            AsciiPadding::Nul => 0,
+            AsciiPadding::Space => b' ',
+        }
+    }
 }
 
 impl FieldType for ArrayLike {
     fn size_bytes(&self) -> usize {
         match self {
             ArrayLike::FixedArray { scalar, length } => length * scalar.size_bytes(),
-            ArrayLike::AsciiString { length } => *length,
+            ArrayLike::AsciiString { length, .. } => *length,
             ArrayLike::Bytes { length } => *length,
         }
     }
+
+    fn natural_alignment(&self) -> usize {
+        match self {
+            ArrayLike::FixedArray { scalar, .. } => scalar.natural_alignment(),
+            ArrayLike::AsciiString { .. } => 1,
+            ArrayLike::Bytes { .. } => 1,
+        }
+    }
+}
+
+impl Display for ArrayLike {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayLike::FixedArray { scalar, length } => write!(f, "{}[{}]", scalar, length),
+            ArrayLike::AsciiString { length, .. } => write!(f, "ascii[{}]", length),
+            ArrayLike::Bytes { length } => write!(f, "byte[{}]", length),
+        }
+    }
 }
@@ -1 +1,32 @@
-//todo
+use crate::data_types::{FieldType, ScalarType};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EnumKind {
+    Enum,
+    /// Each value is a bit (or set of bits) which can be combined with the others
+    Bitflags,
+}
+
+/// A reference to an enum or bitflags declared in the protocol, stored as its underlying type
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumType {
+    pub name: String,
+    pub kind: EnumKind,
+    pub underlying: ScalarType,
+}
+
+impl FieldType for EnumType {
+    fn size_bytes(&self) -> usize {
+        self.underlying.size_bytes()
+    }
+}
+
+impl Display for EnumType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
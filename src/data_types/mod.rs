@@ -1,5 +1,9 @@
 pub use array_like::ArrayLike;
+pub use enumeration::{EnumKind, EnumType};
 pub use scalar::ScalarType;
+use std::fmt::{Display, Formatter};
+pub use structure::StructType;
+pub use union::UnionType;
 pub mod array_like;
 pub mod scalar;
 
@@ -7,15 +11,27 @@ mod enumeration;
 
 mod structure;
 
+mod union;
+
 pub trait FieldType {
     fn size_bytes(&self) -> usize;
+
+    /// The alignment this type would have in a C struct
+    fn natural_alignment(&self) -> usize {
+        self.size_bytes()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum FieldType_ {
     Scalar(ScalarType),
     Vector(ArrayLike),
     Padding(usize),
+    Struct(StructType),
+    Enum(EnumType),
+    Union(UnionType),
 }
 
 impl FieldType for FieldType_ {
@@ -24,6 +40,33 @@ impl FieldType for FieldType_ {
             FieldType_::Scalar(st) => st.size_bytes(),
             FieldType_::Vector(vt) => vt.size_bytes(),
             FieldType_::Padding(len) => *len,
+            FieldType_::Struct(st) => st.size_bytes(),
+            FieldType_::Enum(et) => et.size_bytes(),
+            FieldType_::Union(ut) => ut.size_bytes(),
+        }
+    }
+
+    fn natural_alignment(&self) -> usize {
+        match self {
+            FieldType_::Scalar(st) => st.natural_alignment(),
+            FieldType_::Vector(vt) => vt.natural_alignment(),
+            FieldType_::Padding(_) => 1,
+            FieldType_::Struct(st) => st.natural_alignment(),
+            FieldType_::Enum(et) => et.natural_alignment(),
+            FieldType_::Union(ut) => ut.natural_alignment(),
+        }
+    }
+}
+
+impl Display for FieldType_ {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldType_::Scalar(st) => write!(f, "{}", st),
+            FieldType_::Vector(vt) => write!(f, "{}", vt),
+            FieldType_::Padding(len) => write!(f, "{};", len),
+            FieldType_::Struct(st) => write!(f, "{}", st),
+            FieldType_::Enum(et) => write!(f, "{}", et),
+            FieldType_::Union(ut) => write!(f, "{}", ut),
         }
     }
 }
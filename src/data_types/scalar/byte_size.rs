@@ -1,6 +1,9 @@
 use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
 use ByteSize::*;
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ByteSize {
     Byte,
     Char,
@@ -23,3 +26,14 @@ impl ByteSize {
         };
     }
 }
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Byte => "byte",
+            Char => "char",
+            Ascii => "ascii",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::data_types::scalar::{Signed, Unsigned};
+use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
+
+/// The integer type holding a decimal's mantissa
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Mantissa {
+    Unsigned(Unsigned),
+    Signed(Signed),
+}
+
+/// A fixed-point number stored as an integer mantissa, the value is `mantissa * 10^exponent`
+/// where the exponent is fixed by the schema, e.g. `decimal<i64, -8>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decimal {
+    pub mantissa: Mantissa,
+    pub exponent: i8,
+}
+
+impl Decimal {
+    /// Exponents are limited to what a 64 bit mantissa can meaningfully scale
+    pub const MIN_EXPONENT: i8 = -18;
+    pub const MAX_EXPONENT: i8 = 18;
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self.mantissa, Mantissa::Signed(_))
+    }
+}
+
+impl FieldType for Decimal {
+    fn size_bytes(&self) -> usize {
+        match self.mantissa {
+            Mantissa::Unsigned(u) => u.size_bytes(),
+            Mantissa::Signed(s) => s.size_bytes(),
+        }
+    }
+}
+
+impl Display for Mantissa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mantissa::Unsigned(u) => write!(f, "{}", u),
+            Mantissa::Signed(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "decimal<{}, {}>", self.mantissa, self.exponent)
+    }
+}
@@ -1,7 +1,10 @@
 use crate::data_types::scalar::Float::*;
 use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Float {
     F32,
     F64,
@@ -25,3 +28,13 @@ impl Float {
         };
     }
 }
+
+impl Display for Float {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            F32 => "f32",
+            F64 => "f64",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -1,20 +1,30 @@
-use crate::data_types::{FieldType, FieldType_};
+use crate::data_types::FieldType;
 pub use byte_size::ByteSize;
+pub use decimal::{Decimal, Mantissa};
 pub use float::Float;
 pub use signed::Signed;
+use std::fmt::{Display, Formatter};
+pub use time::{Duration, TimeUnit, Timestamp};
 pub use unsigned::Unsigned;
 
 mod byte_size;
+mod decimal;
 mod float;
 mod signed;
+mod time;
 mod unsigned;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ScalarType {
     Unsigned(Unsigned),
     Signed(Signed),
     Float(Float),
     ByteSized(ByteSize),
+    Decimal(Decimal),
+    Timestamp(Timestamp),
+    Duration(Duration),
 }
 
 impl FieldType for ScalarType {
@@ -24,6 +34,23 @@ impl FieldType for ScalarType {
             ScalarType::Signed(u) => u.size_bytes(),
             ScalarType::Float(u) => u.size_bytes(),
             ScalarType::ByteSized(u) => u.size_bytes(),
+            ScalarType::Decimal(u) => u.size_bytes(),
+            ScalarType::Timestamp(u) => u.size_bytes(),
+            ScalarType::Duration(u) => u.size_bytes(),
+        }
+    }
+}
+
+impl Display for ScalarType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScalarType::Unsigned(u) => write!(f, "{}", u),
+            ScalarType::Signed(u) => write!(f, "{}", u),
+            ScalarType::Float(u) => write!(f, "{}", u),
+            ScalarType::ByteSized(u) => write!(f, "{}", u),
+            ScalarType::Decimal(u) => write!(f, "{}", u),
+            ScalarType::Timestamp(u) => write!(f, "{}", u),
+            ScalarType::Duration(u) => write!(f, "{}", u),
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
 use Signed::*;
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Signed {
     I8,
     I16,
@@ -30,3 +33,15 @@ impl Signed {
         };
     }
 }
+
+impl Display for Signed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            I8 => "i8",
+            I16 => "i16",
+            I32 => "i32",
+            I64 => "i64",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::data_types::scalar::{Signed, Unsigned};
+use crate::data_types::{FieldType, ScalarType};
+use std::fmt::{Display, Formatter};
+use TimeUnit::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimeUnit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimeUnit {
+    pub fn try_parse(txt: &str) -> Option<TimeUnit> {
+        match txt {
+            "s" => Some(Seconds),
+            "ms" => Some(Millis),
+            "us" => Some(Micros),
+            "ns" => Some(Nanos),
+            _ => None,
+        }
+    }
+
+    /// How many of this unit make up a second
+    pub fn per_second(&self) -> u64 {
+        match self {
+            Seconds => 1,
+            Millis => 1_000,
+            Micros => 1_000_000,
+            Nanos => 1_000_000_000,
+        }
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Seconds => "s",
+            Millis => "ms",
+            Micros => "us",
+            Nanos => "ns",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A point in time, counted in `unit` since the unix epoch and stored as a `u64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp {
+    pub unit: TimeUnit,
+}
+
+/// A signed span of time counted in `unit` and stored as an `i64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duration {
+    pub unit: TimeUnit,
+}
+
+impl Timestamp {
+    pub fn storage(&self) -> ScalarType {
+        ScalarType::Unsigned(Unsigned::U64)
+    }
+}
+
+impl Duration {
+    pub fn storage(&self) -> ScalarType {
+        ScalarType::Signed(Signed::I64)
+    }
+}
+
+impl FieldType for Timestamp {
+    fn size_bytes(&self) -> usize {
+        self.storage().size_bytes()
+    }
+}
+
+impl FieldType for Duration {
+    fn size_bytes(&self) -> usize {
+        self.storage().size_bytes()
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timestamp<{}>", self.unit)
+    }
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "duration<{}>", self.unit)
+    }
+}
@@ -1,6 +1,9 @@
 use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
 use Unsigned::*;
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Unsigned {
     U8,
     U16,
@@ -30,3 +33,15 @@ impl Unsigned {
         };
     }
 }
+
+impl Display for Unsigned {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            U8 => "u8",
+            U16 => "u16",
+            U32 => "u32",
+            U64 => "u64",
+        };
+        write!(f, "{}", name)
+    }
+}
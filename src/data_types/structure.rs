@@ -1 +1,28 @@
-// TODO
+use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
+
+/// A reference to a struct declared in the protocol, the struct's own layout lives in the
+/// resolved protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructType {
+    pub name: String,
+    pub size: usize,
+    pub alignment: usize,
+}
+
+impl FieldType for StructType {
+    fn size_bytes(&self) -> usize {
+        self.size
+    }
+
+    fn natural_alignment(&self) -> usize {
+        self.alignment
+    }
+}
+
+impl Display for StructType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
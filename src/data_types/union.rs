@@ -0,0 +1,29 @@
+use crate::data_types::FieldType;
+use std::fmt::{Display, Formatter};
+
+/// A reference to a union declared in the protocol, along with the field of the enclosing
+/// message or struct which selects the active variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnionType {
+    pub name: String,
+    pub size: usize,
+    pub alignment: usize,
+    pub discriminator: String,
+}
+
+impl FieldType for UnionType {
+    fn size_bytes(&self) -> usize {
+        self.size
+    }
+
+    fn natural_alignment(&self) -> usize {
+        self.alignment
+    }
+}
+
+impl Display for UnionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
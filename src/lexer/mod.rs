@@ -87,7 +87,9 @@ impl<'a> Lexer<'a> {
         let first_char = self.current_char()?;
         let start_loc = self.current_location.clone();
 
-        const KEY_CHARS: [char; 8] = ['[', ']', '{', '}', ',', ':', ';', '.'];
+        const KEY_CHARS: [char; 15] = [
+            '[', ']', '{', '}', '(', ')', '<', '>', ',', ':', ';', '.', '=', '@', '-',
+        ];
 
         return match first_char {
             char if char.is_ascii_alphabetic() || char == '_' => {
@@ -126,6 +128,28 @@ impl<'a> Lexer<'a> {
                     )),
                 };
             }
+            '/' if self.peek_next_char() == Some('/') => {
+                let line: String = self
+                    .input
+                    .chars()
+                    .skip(self.curr_offset)
+                    .take_while(|c| *c != '\n')
+                    .collect();
+
+                // advance_cursor stops short at the end of the input, which a comment can reach
+                for _ in 0..line.chars().count() {
+                    self.next_char();
+                }
+                match line.strip_prefix("///") {
+                    // `////` rulers are plain comments like `//`
+                    Some(doc) if !doc.starts_with('/') => Some(token!(
+                        DocComment,
+                        Range::new(start_loc, self.current_location),
+                        String::from(doc.trim())
+                    )),
+                    _ => self.next_token(),
+                }
+            }
             char if KEY_CHARS.contains(&char) => {
                 _ = self.next_char();
                 return match char {
@@ -133,10 +157,17 @@ impl<'a> Lexer<'a> {
                     ']' => Some(token!(CloseBracket, start_loc)),
                     '{' => Some(token!(OpenBrace, start_loc)),
                     '}' => Some(token!(CloseBrace, start_loc)),
+                    '(' => Some(token!(OpenParen, start_loc)),
+                    ')' => Some(token!(CloseParen, start_loc)),
+                    '<' => Some(token!(LessThan, start_loc)),
+                    '>' => Some(token!(GreaterThan, start_loc)),
+                    '-' => Some(token!(Minus, start_loc)),
                     ',' => Some(token!(Comma, start_loc)),
                     ':' => Some(token!(Colon, start_loc)),
                     '.' => Some(token!(Dot, start_loc)),
                     ';' => Some(token!(SemiColon, start_loc)),
+                    '=' => Some(token!(Equals, start_loc)),
+                    '@' => Some(token!(At, start_loc)),
                     _ => panic!("unreachable"),
                 };
             }
@@ -4,10 +4,19 @@ use crate::source::{SourceLocation, ToLocation};
 pub enum TokenType {
     Identifier(String),
     IntegerLiteral(usize),
+    /// The text of a `///` comment, documenting the field after it
+    DocComment(String),
     SemiColon,
     Colon,
     Comma,
     Dot,
+    Equals,
+    At,
+    OpenParen,
+    CloseParen,
+    LessThan,
+    GreaterThan,
+    Minus,
     OpenBrace,
     CloseBrace,
     OpenBracket,
@@ -21,7 +30,7 @@ pub struct Token {
     location: SourceLocation,
 }
 
-#[macro_export(crate)]
+#[macro_export]
 macro_rules! token {
     ($tt:ident, $loc:expr) => {
         Token::new(TokenType::$tt, $loc)
@@ -1,7 +1,9 @@
+pub mod codegen;
 pub mod data_types;
 pub mod lexer;
 pub mod parser;
 pub mod protocol;
+pub mod runtime;
 pub mod source;
 pub mod sssc;
 pub mod syntax;
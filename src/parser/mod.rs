@@ -1,12 +1,17 @@
-use crate::data_types::scalar::ByteSize;
-use crate::data_types::{ArrayLike, FieldType_, ScalarType};
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Decimal, Duration, Mantissa, TimeUnit, Timestamp};
+use crate::data_types::{ArrayLike, EnumKind, FieldType_, ScalarType};
 use crate::lexer::{Token, TokenType};
 use crate::parser::ParseError::UnexpectedToken;
+use crate::source::SourceLocation;
 use crate::syntax::{
-    DeclarationSyntax, FieldDeclaration, MemberDeclaration, MessageDeclarationSyntax,
-    ProtocolDeclarationSyntax, SyntaxUnit,
+    AttributeSyntax, AttributeValue, DeclarationSyntax, EnumDeclarationSyntax, EnumValue,
+    FieldDeclaration, FieldTypeSyntax, HeaderDeclarationSyntax, MemberDeclaration,
+    MessageDeclarationSyntax, ProtocolDeclarationSyntax, StructDeclarationSyntax, SyntaxUnit,
+    UnionDeclarationSyntax,
 };
 use std::cmp::PartialEq;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -19,6 +24,40 @@ pub enum ParseError {
     UnknownType,
 }
 
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnexpectedToken(token, message) => {
+                let at = match token.get_location() {
+                    SourceLocation::Point(p) => *p,
+                    SourceLocation::Range(r) => r.from,
+                };
+                write!(
+                    f,
+                    "{}:{}: unexpected {:?}",
+                    at.get_line() + 1,
+                    at.get_column() + 1,
+                    token.get_type()
+                )?;
+                match message {
+                    Some(m) => write!(f, ", {}", m),
+                    None => Ok(()),
+                }
+            }
+            ParseError::Err(message) => write!(f, "{}", message),
+            ParseError::ExpectedAToken => write!(f, "unexpected end of input"),
+            ParseError::MissingIdentifier => write!(f, "expected an identifier"),
+            ParseError::ExpectedProtocolDeclaration => {
+                write!(f, "expected a protocol declaration")
+            }
+            ParseError::InvalidNumberFormat => write!(f, "invalid number"),
+            ParseError::UnknownType => write!(f, "unknown type"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 pub struct Parser<'a, T: Iterator<Item = Token> + Clone> {
     tokens: &'a mut T,
 }
@@ -54,6 +93,23 @@ where
     }
 
     pub fn parse_declaration(&mut self) -> Result<Option<DeclarationSyntax>, ParseError> {
+        // only fields keep their documentation
+        self.parse_doc_comment();
+        let annotations = self.parse_annotations()?;
+        if !annotations.is_empty() {
+            // only structs can be annotated at the declaration level
+            return match self.get_identifier()? {
+                Some(id) if id == "struct" => Ok(Some(DeclarationSyntax::Struct(
+                    self.parse_struct_declaration(annotations)?,
+                ))),
+                Some(id) => Err(ParseError::Err(format!(
+                    "Unexpected identifier '{}', expected 'struct' after annotations",
+                    id
+                ))),
+                None => Err(ParseError::ExpectedAToken),
+            };
+        }
+
         return self.get_identifier().and_then(|maybe_id| match maybe_id {
             None => Ok(None),
             Some(id) if id == "message" => {
@@ -61,14 +117,26 @@ where
                     .parse_message_declaration()
                     .and_then(move |m| Ok(Some(DeclarationSyntax::Message(m))))
             }
-            Some(id) if id == "enum" => {
-                todo!()
-            }
-            Some(id) if id == "bitflags" => {
-                todo!()
-            }
+            Some(id) if id == "struct" => self
+                .parse_struct_declaration(vec![])
+                .map(|s| Some(DeclarationSyntax::Struct(s))),
+            Some(id) if id == "union" => self
+                .parse_union_declaration()
+                .map(|u| Some(DeclarationSyntax::Union(u))),
+            Some(id) if id == "enum" => self
+                .parse_enum_declaration(EnumKind::Enum)
+                .map(|e| Some(DeclarationSyntax::Enum(e))),
+            Some(id) if id == "bitflags" => self
+                .parse_enum_declaration(EnumKind::Bitflags)
+                .map(|e| Some(DeclarationSyntax::Enum(e))),
+            Some(id) if id == "header" => self.parse_members().map(|members| {
+                Some(DeclarationSyntax::Header(HeaderDeclarationSyntax {
+                    members,
+                }))
+            }),
             Some(id) => Err(ParseError::Err(String::from(format!(
-                "Unexpected identifier '{}', expected one of {{message, enum, bitflags}}",
+                "Unexpected identifier '{}', expected one of \
+                 {{message, struct, union, enum, bitflags, header}}",
                 id
             )))),
         });
@@ -88,7 +156,122 @@ where
         self.assert_next_token_matches(TokenType::OpenBracket)?;
         let id = self.parse_number()?;
 
+        let mut attributes: Vec<AttributeSyntax> = vec![];
+        while self.next_token_is(TokenType::Comma) {
+            self.tokens.next();
+            attributes.push(self.parse_attribute()?);
+        }
+
         self.assert_next_token_matches(TokenType::CloseBracket)?;
+        let members = self.parse_members()?;
+
+        return Ok(MessageDeclarationSyntax {
+            name,
+            id,
+            attributes,
+            members,
+        });
+    }
+
+    fn parse_struct_declaration(
+        &mut self,
+        attributes: Vec<AttributeSyntax>,
+    ) -> Result<StructDeclarationSyntax, ParseError> {
+        let name = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+        let members = self.parse_members()?;
+
+        Ok(StructDeclarationSyntax {
+            name,
+            attributes,
+            members,
+        })
+    }
+
+    fn parse_union_declaration(&mut self) -> Result<UnionDeclarationSyntax, ParseError> {
+        let name = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+        self.assert_next_token_matches(TokenType::Colon)?;
+        let discriminator_type = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+
+        let variants = self
+            .parse_members()?
+            .into_iter()
+            .map(|m| match m {
+                MemberDeclaration::Field(f) => Ok(f),
+                MemberDeclaration::Padding(_) => Err(ParseError::Err(format!(
+                    "Padding is not allowed in union '{}'",
+                    name
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(UnionDeclarationSyntax {
+            name,
+            discriminator_type,
+            variants,
+        })
+    }
+
+    /// Parses `Name : type { A = 1, B, ... }` for both enums and bitflags
+    fn parse_enum_declaration(
+        &mut self,
+        kind: EnumKind,
+    ) -> Result<EnumDeclarationSyntax, ParseError> {
+        let name = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+        self.assert_next_token_matches(TokenType::Colon)?;
+
+        let underlying_type = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+        let underlying_type = match ScalarType::try_parse(underlying_type.as_str()) {
+            Some(t @ ScalarType::Unsigned(_)) | Some(t @ ScalarType::Signed(_)) => t,
+            _ => {
+                return Err(ParseError::Err(format!(
+                    "'{}' must be based on an integer type, found '{}'",
+                    name, underlying_type
+                )))
+            }
+        };
+
+        self.assert_next_token_matches(TokenType::OpenBrace)?;
+        let mut values: Vec<EnumValue> = vec![];
+        while !self.next_token_is(TokenType::CloseBrace) {
+            self.parse_doc_comment();
+            let value_name = self.get_identifier()?.ok_or(ParseError::ExpectedAToken)?;
+            let mut value = None;
+            if self.next_token_is(TokenType::Equals) {
+                self.tokens.next();
+                value = Some(self.parse_number()?);
+            }
+            values.push(EnumValue {
+                name: value_name,
+                value,
+            });
+
+            if !self.next_token_is(TokenType::Comma) {
+                break;
+            }
+            self.tokens.next();
+        }
+        self.assert_next_token_matches(TokenType::CloseBrace)?;
+
+        Ok(EnumDeclarationSyntax {
+            name,
+            kind,
+            underlying_type,
+            values,
+        })
+    }
+
+    /// Parses a brace delimited list of members, including the braces
+    fn parse_members(&mut self) -> Result<Vec<MemberDeclaration>, ParseError> {
         self.assert_next_token_matches(TokenType::OpenBrace)?;
 
         let mut members: Vec<MemberDeclaration> = vec![];
@@ -102,7 +285,86 @@ where
         }
         self.tokens.next();
 
-        return Ok(MessageDeclarationSyntax { name, id, members });
+        Ok(members)
+    }
+
+    /// Parses any number of `@name(value)` annotations
+    fn parse_annotations(&mut self) -> Result<Vec<AttributeSyntax>, ParseError> {
+        let mut annotations: Vec<AttributeSyntax> = vec![];
+        while self.next_token_is(TokenType::At) {
+            self.tokens.next();
+            annotations.push(self.parse_annotation()?);
+        }
+        Ok(annotations)
+    }
+
+    /// Parses an optional `[name = value, ...]` list
+    fn parse_attribute_list(&mut self) -> Result<Vec<AttributeSyntax>, ParseError> {
+        let mut attributes: Vec<AttributeSyntax> = vec![];
+        if !self.next_token_is(TokenType::OpenBracket) {
+            return Ok(attributes);
+        }
+        self.tokens.next();
+
+        attributes.push(self.parse_attribute()?);
+        while self.next_token_is(TokenType::Comma) {
+            self.tokens.next();
+            attributes.push(self.parse_attribute()?);
+        }
+        self.assert_next_token_matches(TokenType::CloseBracket)?;
+        Ok(attributes)
+    }
+
+    /// Parses `name` or `name = value` inside an attribute list
+    fn parse_attribute(&mut self) -> Result<AttributeSyntax, ParseError> {
+        let name = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+
+        if !self.next_token_is(TokenType::Equals) {
+            return Ok(AttributeSyntax::new(name, None));
+        }
+        self.tokens.next();
+
+        let value = self.parse_attribute_value()?;
+        Ok(AttributeSyntax::new(name, Some(value)))
+    }
+
+    /// Parses an annotation following an `@`, e.g. `offset(12)`
+    fn parse_annotation(&mut self) -> Result<AttributeSyntax, ParseError> {
+        let name = self
+            .get_identifier()?
+            .ok_or(ParseError::MissingIdentifier)?;
+
+        if !self.next_token_is(TokenType::OpenParen) {
+            return Ok(AttributeSyntax::new(name, None));
+        }
+        self.tokens.next();
+
+        let value = self.parse_attribute_value()?;
+        self.assert_next_token_matches(TokenType::CloseParen)?;
+        Ok(AttributeSyntax::new(name, Some(value)))
+    }
+
+    fn parse_attribute_value(&mut self) -> Result<AttributeValue, ParseError> {
+        match self.tokens.next() {
+            None => Err(ParseError::ExpectedAToken),
+            Some(t) => match t.get_type() {
+                TokenType::IntegerLiteral(int) => Ok(AttributeValue::Integer(*int)),
+                TokenType::Identifier(id) => Ok(AttributeValue::Identifier(id.clone())),
+                _ => Err(UnexpectedToken(
+                    t.clone(),
+                    Some(String::from("Expected an integer or identifier value")),
+                )),
+            },
+        }
+    }
+
+    fn next_token_is(&self, tt: TokenType) -> bool {
+        match self.tokens.clone().next() {
+            Some(t) => *t.get_type() == tt,
+            None => false,
+        }
     }
 
     fn parse_number(&mut self) -> Result<usize, ParseError> {
@@ -115,7 +377,35 @@ where
         }
     }
 
+    /// Parses consecutive `///` comments, their lines are joined with spaces
+    fn parse_doc_comment(&mut self) -> Option<String> {
+        let mut lines: Vec<String> = vec![];
+        while let Some(TokenType::DocComment(line)) =
+            self.tokens.clone().next().map(|t| t.get_type().clone())
+        {
+            self.tokens.next();
+            lines.push(line);
+        }
+        lines.retain(|l| !l.is_empty());
+        match lines.is_empty() {
+            true => None,
+            false => Some(lines.join(" ")),
+        }
+    }
+
     fn parse_member(&mut self) -> Result<Option<MemberDeclaration>, ParseError> {
+        let description = self.parse_doc_comment();
+        let member = self.parse_undocumented_member()?;
+        Ok(match member {
+            Some(MemberDeclaration::Field(f)) => Some(MemberDeclaration::Field(FieldDeclaration {
+                description,
+                ..f
+            })),
+            member => member,
+        })
+    }
+
+    fn parse_undocumented_member(&mut self) -> Result<Option<MemberDeclaration>, ParseError> {
         match self.tokens.next() {
             None => Ok(None),
             Some(t) => match t.get_type() {
@@ -125,6 +415,19 @@ where
                         Some(f) => Ok(Some(MemberDeclaration::Field(f))),
                     }
                 }
+                TokenType::At => {
+                    let mut attributes = vec![self.parse_annotation()?];
+                    attributes.append(&mut self.parse_annotations()?);
+
+                    let type_name = self.get_identifier()?.ok_or(ParseError::ExpectedAToken)?;
+                    match self.try_parse_field(type_name.as_str())? {
+                        None => Ok(None),
+                        Some(mut f) => {
+                            f.attributes = attributes;
+                            Ok(Some(MemberDeclaration::Field(f)))
+                        }
+                    }
+                }
                 TokenType::IntegerLiteral(size) => {
                     // only padding can start with an integer literal it will always be followed by
                     // a semi colon
@@ -141,24 +444,27 @@ where
         &mut self,
         identifier: &str,
     ) -> Result<Option<FieldDeclaration>, ParseError> {
-        let scalar_type: ScalarType = match ScalarType::try_parse(identifier) {
-            None => None,
-            Some(scalarType) => Some(scalarType),
-        }
-        .ok_or(ParseError::UnknownType)?;
-
-        let field_type: FieldType_ = match self.tokens.next() {
-            None => Err(ParseError::ExpectedAToken),
-            Some(tok) => match tok.get_type() {
-                TokenType::OpenBracket => {
-                    let r = Ok(FieldType_::Vector(self.try_parse_vector_type(scalar_type)?));
-                    self.assert_next_token_matches(TokenType::Colon)?;
-                    r
-                }
-                TokenType::Colon => Ok(FieldType_::Scalar(scalar_type)),
-                _ => Err(UnexpectedToken(tok.clone(), None)),
-            },
-        }?;
+        let field_type: FieldTypeSyntax = match self.try_parse_scalar_type(identifier)? {
+            // anything that isn't built in must be declared elsewhere in the protocol, whether
+            // it was is only known once the whole protocol has been parsed
+            None => {
+                self.assert_next_token_matches(TokenType::Colon)?;
+                FieldTypeSyntax::Named(String::from(identifier))
+            }
+            Some(scalar_type) => match self.tokens.next() {
+                None => Err(ParseError::ExpectedAToken),
+                Some(tok) => match tok.get_type() {
+                    TokenType::OpenBracket => {
+                        let r = Ok(FieldType_::Vector(self.try_parse_vector_type(scalar_type)?));
+                        self.assert_next_token_matches(TokenType::Colon)?;
+                        r
+                    }
+                    TokenType::Colon => Ok(FieldType_::Scalar(scalar_type)),
+                    _ => Err(UnexpectedToken(tok.clone(), None)),
+                },
+            }
+            .map(FieldTypeSyntax::BuiltIn)?,
+        };
 
         let name = self
             .get_identifier()?
@@ -170,9 +476,91 @@ where
             name,
             field_type,
             description: None,
+            attributes: vec![],
         }))
     }
 
+    /// Parses a built in scalar type, including parameterised ones such as `decimal<i64, -8>`
+    fn try_parse_scalar_type(
+        &mut self,
+        identifier: &str,
+    ) -> Result<Option<ScalarType>, ParseError> {
+        match identifier {
+            "decimal" => self.parse_decimal_type().map(Some),
+            "timestamp" => self
+                .parse_time_unit()
+                .map(|unit| Some(ScalarType::Timestamp(Timestamp { unit }))),
+            "duration" => self
+                .parse_time_unit()
+                .map(|unit| Some(ScalarType::Duration(Duration { unit }))),
+            _ => Ok(ScalarType::try_parse(identifier)),
+        }
+    }
+
+    fn parse_decimal_type(&mut self) -> Result<ScalarType, ParseError> {
+        self.assert_next_token_matches(TokenType::LessThan)?;
+        let mantissa = self.get_identifier()?.ok_or(ParseError::ExpectedAToken)?;
+        let mantissa = match ScalarType::try_parse(mantissa.as_str()) {
+            Some(ScalarType::Signed(s)) => Mantissa::Signed(s),
+            Some(ScalarType::Unsigned(u)) => Mantissa::Unsigned(u),
+            _ => {
+                return Err(ParseError::Err(format!(
+                    "A decimal mantissa must be an integer type, found '{}'",
+                    mantissa
+                )))
+            }
+        };
+
+        self.assert_next_token_matches(TokenType::Comma)?;
+        let exponent = self.parse_signed_number()?;
+        self.assert_next_token_matches(TokenType::GreaterThan)?;
+
+        let range = (Decimal::MIN_EXPONENT as i64)..=(Decimal::MAX_EXPONENT as i64);
+        if !range.contains(&exponent) {
+            return Err(ParseError::Err(format!(
+                "A decimal exponent must be between {} and {}, found {}",
+                Decimal::MIN_EXPONENT,
+                Decimal::MAX_EXPONENT,
+                exponent
+            )));
+        }
+
+        Ok(ScalarType::Decimal(Decimal {
+            mantissa,
+            exponent: exponent as i8,
+        }))
+    }
+
+    /// Parses the `<ns>` following `timestamp` or `duration`
+    fn parse_time_unit(&mut self) -> Result<TimeUnit, ParseError> {
+        self.assert_next_token_matches(TokenType::LessThan)?;
+        let unit = self.get_identifier()?.ok_or(ParseError::ExpectedAToken)?;
+        let unit = TimeUnit::try_parse(unit.as_str()).ok_or_else(|| {
+            ParseError::Err(format!(
+                "Unknown time unit '{}', expected one of {{s, ms, us, ns}}",
+                unit
+            ))
+        })?;
+        self.assert_next_token_matches(TokenType::GreaterThan)?;
+        Ok(unit)
+    }
+
+    fn parse_signed_number(&mut self) -> Result<i64, ParseError> {
+        let negative = self.next_token_is(TokenType::Minus);
+        if negative {
+            self.tokens.next();
+        }
+        let number = self.parse_number()?;
+        // negated in an i128 so `-9223372036854775808` fits even though its magnitude doesn't
+        let value = if negative {
+            -(number as i128)
+        } else {
+            number as i128
+        };
+        i64::try_from(value)
+            .map_err(|_| ParseError::Err(format!("{} does not fit in an i64", value)))
+    }
+
     pub fn try_parse_vector_type(
         &mut self,
         scalar_type: ScalarType,
@@ -197,8 +585,14 @@ where
         if let ScalarType::ByteSized(b) = scalar_type {
             return match b {
                 ByteSize::Byte => Ok(ArrayLike::Bytes { length: size }),
-                ByteSize::Char => Ok(ArrayLike::AsciiString { length: size }),
-                ByteSize::Ascii => Ok(ArrayLike::AsciiString { length: size }),
+                ByteSize::Char => Ok(ArrayLike::AsciiString {
+                    length: size,
+                    padding: AsciiPadding::Nul,
+                }),
+                ByteSize::Ascii => Ok(ArrayLike::AsciiString {
+                    length: size,
+                    padding: AsciiPadding::Nul,
+                }),
             };
         }
 
@@ -262,8 +656,9 @@ where
             Some(token) => match token.get_type() {
                 TokenType::Identifier(id) if id == "protocol" => {
                     self.parse_qualified_name().and_then(|pds| {
+                        let attributes = self.parse_attribute_list()?;
                         self.assert_next_token_matches(TokenType::SemiColon)?;
-                        Ok(ProtocolDeclarationSyntax::new(pds))
+                        Ok(ProtocolDeclarationSyntax::with_attributes(pds, attributes))
                     })
                 }
                 _ => Err(UnexpectedToken(token, None)),
@@ -313,8 +708,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::lexer::tokenize;
-    use crate::parser::Parser;
-    use crate::syntax::DeclarationSyntax;
+    use crate::parser::{ParseError, Parser};
+    use crate::syntax::{DeclarationSyntax, FieldDeclaration, FieldTypeSyntax, MemberDeclaration};
 
     #[test]
     fn test_parse_message() {
@@ -402,4 +797,155 @@ mod test {
         assert_is_message!(d2, name: bar, id: 2, &bar.members.len() == &1);
         assert_is_message!(d3, name: baz, id: 3, &baz.members.len() == &1);
     }
+
+    #[test]
+    fn test_parse_decimal_field() {
+        let mut tokens =
+            tokenize("message quote [1] { decimal<i64, -8>: price; decimal<u32, 2>[4]: levels; }");
+        let mut parser = Parser::new(&mut tokens);
+
+        let message = match parser.parse_declaration() {
+            Ok(Some(DeclarationSyntax::Message(m))) => m,
+            other => panic!("expected a message, found {:?}", other),
+        };
+
+        let field_types: Vec<String> = message
+            .members
+            .iter()
+            .map(|m| match m {
+                MemberDeclaration::Field(FieldDeclaration {
+                    field_type: FieldTypeSyntax::BuiltIn(t),
+                    ..
+                }) => t.to_string(),
+                other => panic!("expected a built in field, found {:?}", other),
+            })
+            .collect();
+        assert_eq!(vec!["decimal<i64, -8>", "decimal<u32, 2>[4]"], field_types);
+    }
+
+    #[test]
+    fn test_parse_decimal_rejects_bad_parameters() {
+        for text in [
+            "message quote [1] { decimal<f64, -8>: price; }",
+            "message quote [1] { decimal<i64, -19>: price; }",
+            "message quote [1] { decimal<i64>: price; }",
+        ] {
+            let mut tokens = tokenize(text);
+            let mut parser = Parser::new(&mut tokens);
+            assert!(parser.parse_declaration().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_decimal_exponent_out_of_i64() {
+        for (text, message) in [
+            // i64::MIN fits, it's only out of the exponent range
+            (
+                "message quote [1] { decimal<i64, -9223372036854775808>: price; }",
+                "A decimal exponent must be between -18 and 18, found -9223372036854775808",
+            ),
+            (
+                "message quote [1] { decimal<i64, -9223372036854775809>: price; }",
+                "-9223372036854775809 does not fit in an i64",
+            ),
+            (
+                "message quote [1] { decimal<i64, 18446744073709551615>: price; }",
+                "18446744073709551615 does not fit in an i64",
+            ),
+        ] {
+            let mut tokens = tokenize(text);
+            let mut parser = Parser::new(&mut tokens);
+            match parser.parse_declaration() {
+                Err(ParseError::Err(m)) => assert_eq!(message, m),
+                other => panic!("expected an error for {}, found {:?}", text, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_time_fields() {
+        let mut tokens = tokenize(
+            "message tick [1] { timestamp<ns>: sent_at; duration<ms>: timeout; timestamp<s>: day; }",
+        );
+        let mut parser = Parser::new(&mut tokens);
+
+        let message = match parser.parse_declaration() {
+            Ok(Some(DeclarationSyntax::Message(m))) => m,
+            other => panic!("expected a message, found {:?}", other),
+        };
+
+        let field_types: Vec<String> = message
+            .members
+            .iter()
+            .map(|m| match m {
+                MemberDeclaration::Field(FieldDeclaration {
+                    field_type: FieldTypeSyntax::BuiltIn(t),
+                    ..
+                }) => t.to_string(),
+                other => panic!("expected a built in field, found {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            vec!["timestamp<ns>", "duration<ms>", "timestamp<s>"],
+            field_types
+        );
+
+        let mut tokens = tokenize("message tick [1] { timestamp<days>: day; }");
+        assert!(Parser::new(&mut tokens).parse_declaration().is_err());
+    }
+
+    #[test]
+    fn test_parse_doc_comments() {
+        let mut tokens = tokenize(
+            "/// ignored
+            message tick [1] {
+                /// first line,
+                ///
+                /// second line
+                u8: a;
+                // not documentation
+                u8: b;
+                /// annotated
+                @pad(space) ascii[2]: c;
+            }",
+        );
+        let mut parser = Parser::new(&mut tokens);
+
+        let message = match parser.parse_declaration() {
+            Ok(Some(DeclarationSyntax::Message(m))) => m,
+            other => panic!("expected a message, found {:?}", other),
+        };
+
+        let descriptions: Vec<Option<&str>> = message
+            .members
+            .iter()
+            .map(|m| match m {
+                MemberDeclaration::Field(f) => f.description.as_deref(),
+                other => panic!("expected a field, found {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            vec![Some("first line, second line"), None, Some("annotated")],
+            descriptions
+        );
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let mut tokens = tokenize("header { @length u16: length; @id u16: message_id; 2; }");
+        let mut parser = Parser::new(&mut tokens);
+
+        let header = match parser.parse_declaration() {
+            Ok(Some(DeclarationSyntax::Header(h))) => h,
+            other => panic!("expected a header, found {:?}", other),
+        };
+        assert_eq!(3, header.members.len());
+        match &header.members[1] {
+            MemberDeclaration::Field(f) => {
+                assert_eq!("message_id", f.name);
+                assert_eq!("id", f.attributes[0].name);
+            }
+            other => panic!("expected a field, found {:?}", other),
+        }
+    }
 }
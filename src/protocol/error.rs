@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ResolveError {
+    DuplicateName(String),
+    UnknownType(String),
+    RecursiveStruct(String),
+    InvalidEnum {
+        name: String,
+        reason: String,
+    },
+    InvalidUnion {
+        name: String,
+        reason: String,
+    },
+    InvalidDiscriminator {
+        field: String,
+        reason: String,
+    },
+    /// The `header` declaration is wrong, or a message doesn't fit in its id or length field
+    InvalidHeader(String),
+    DuplicateMessageId {
+        id: usize,
+        first: String,
+        second: String,
+    },
+    UnknownAttribute {
+        declaration: String,
+        attribute: String,
+    },
+    InvalidAttribute {
+        declaration: String,
+        attribute: String,
+        reason: String,
+    },
+    OffsetMismatch {
+        message: String,
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+    SizeMismatch {
+        message: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolveError::DuplicateName(name) => write!(f, "'{}' is declared more than once", name),
+            ResolveError::UnknownType(name) => write!(f, "unknown type '{}'", name),
+            ResolveError::RecursiveStruct(name) => write!(f, "'{}' contains itself", name),
+            ResolveError::InvalidEnum { name, reason } => {
+                write!(f, "invalid enum '{}': {}", name, reason)
+            }
+            ResolveError::InvalidUnion { name, reason } => {
+                write!(f, "invalid union '{}': {}", name, reason)
+            }
+            ResolveError::InvalidDiscriminator { field, reason } => {
+                write!(f, "invalid discriminator for '{}': {}", field, reason)
+            }
+            ResolveError::InvalidHeader(reason) => write!(f, "invalid header: {}", reason),
+            ResolveError::DuplicateMessageId { id, first, second } => write!(
+                f,
+                "messages '{}' and '{}' both use the id {}",
+                first, second, id
+            ),
+            ResolveError::UnknownAttribute {
+                declaration,
+                attribute,
+            } => write!(f, "unknown attribute '{}' on '{}'", attribute, declaration),
+            ResolveError::InvalidAttribute {
+                declaration,
+                attribute,
+                reason,
+            } => write!(
+                f,
+                "invalid attribute '{}' on '{}': {}",
+                attribute, declaration, reason
+            ),
+            ResolveError::OffsetMismatch {
+                message,
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field '{}.{}' was expected at offset {} but is at offset {}",
+                message, field, expected, actual
+            ),
+            ResolveError::SizeMismatch {
+                message,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "message '{}' was expected to be {} bytes but is {} bytes",
+                message, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
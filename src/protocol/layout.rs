@@ -0,0 +1,202 @@
+use crate::data_types::{FieldType, FieldType_};
+use std::fmt::{Display, Formatter};
+
+/// The computed byte layout of a message or struct, every member has a fixed offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    pub size: usize,
+    pub alignment: usize,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Member {
+    Field(Field),
+    Padding(Padding),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Field {
+    pub name: String,
+    pub offset: usize,
+    pub field_type: FieldType_,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Padding {
+    pub offset: usize,
+    pub size: usize,
+    pub reason: PaddingReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PaddingReason {
+    /// Declared in the schema with `N;`
+    Reserved,
+    /// Added to fill a message up to its declared `size`
+    Tail,
+    /// Added so the following member, or the end of the layout, sits on a multiple of the
+    /// given alignment
+    Alignment(usize),
+}
+
+impl Display for PaddingReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaddingReason::Reserved => write!(f, "reserved"),
+            PaddingReason::Tail => write!(f, "fill to declared size"),
+            PaddingReason::Alignment(n) => write!(f, "align to {} bytes", n),
+        }
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:>8} {:>6}  member", "offset", "size")?;
+        for member in &self.members {
+            match member {
+                Member::Field(field) => writeln!(
+                    f,
+                    "{:>8} {:>6}  {}: {}",
+                    field.offset,
+                    field.size_bytes(),
+                    field.field_type,
+                    field.name
+                )?,
+                Member::Padding(padding) => writeln!(
+                    f,
+                    "{:>8} {:>6}  <padding, {}>",
+                    padding.offset, padding.size, padding.reason
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Layout {
+    pub fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Field(f) => Some(f),
+            Member::Padding(_) => None,
+        })
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields().find(|f| f.name == name)
+    }
+}
+
+impl Member {
+    pub fn offset(&self) -> usize {
+        match self {
+            Member::Field(f) => f.offset,
+            Member::Padding(p) => p.offset,
+        }
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        match self {
+            Member::Field(f) => f.size_bytes(),
+            Member::Padding(p) => p.size,
+        }
+    }
+}
+
+impl Field {
+    pub fn size_bytes(&self) -> usize {
+        self.field_type.size_bytes()
+    }
+}
+
+/// Places members one after another, tracking the running offset and the strictest alignment
+/// any member has asked for
+pub(crate) struct LayoutBuilder {
+    offset: usize,
+    alignment: usize,
+    members: Vec<Member>,
+}
+
+impl LayoutBuilder {
+    pub(crate) fn new() -> Self {
+        LayoutBuilder {
+            offset: 0,
+            alignment: 1,
+            members: vec![],
+        }
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub(crate) fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Field(f) => Some(f),
+            Member::Padding(_) => None,
+        })
+    }
+
+    pub(crate) fn require_alignment(&mut self, alignment: usize) {
+        self.alignment = self.alignment.max(alignment);
+    }
+
+    /// Pads up to the next multiple of `alignment`, the layout as a whole will be aligned at
+    /// least as strictly
+    pub(crate) fn align_to(&mut self, alignment: usize) {
+        self.require_alignment(alignment);
+        let misalignment = self.offset % alignment;
+        if misalignment != 0 {
+            self.push_padding(
+                alignment - misalignment,
+                PaddingReason::Alignment(alignment),
+            );
+        }
+    }
+
+    pub(crate) fn push_field(
+        &mut self,
+        name: String,
+        field_type: FieldType_,
+        description: Option<String>,
+    ) {
+        let size = field_type.size_bytes();
+        self.members.push(Member::Field(Field {
+            name,
+            offset: self.offset,
+            field_type,
+            description,
+        }));
+        self.offset += size;
+    }
+
+    pub(crate) fn push_padding(&mut self, size: usize, reason: PaddingReason) {
+        if size == 0 {
+            return;
+        }
+        self.members.push(Member::Padding(Padding {
+            offset: self.offset,
+            size,
+            reason,
+        }));
+        self.offset += size;
+    }
+
+    /// Rounds the size up to the layout's alignment so it can be placed back to back in an array
+    pub(crate) fn finish(mut self) -> Layout {
+        self.align_to(self.alignment);
+        Layout {
+            size: self.offset,
+            alignment: self.alignment,
+            members: self.members,
+        }
+    }
+}
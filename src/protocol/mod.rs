@@ -1 +1,233 @@
+use crate::data_types::{EnumKind, FieldType_, ScalarType};
+pub use error::ResolveError;
+pub use layout::{Field, Layout, Member, Padding, PaddingReason};
+pub use resolve::resolve;
+use std::fmt::{Display, Formatter};
 
+mod error;
+mod layout;
+mod resolve;
+
+/// A protocol after every declaration has been checked and laid out
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Protocol {
+    pub name: Vec<String>,
+    pub alignment: AlignmentMode,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub byte_order: ByteOrder,
+    /// Structs in dependency order, a struct always comes after the structs it contains
+    pub structs: Vec<Struct>,
+    pub unions: Vec<Union>,
+    pub enums: Vec<Enum>,
+    pub messages: Vec<Message>,
+    /// Written in front of every message when the protocol declares one
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub header: Option<Header>,
+}
+
+/// The fields in front of every message, declared with `header { ... }`. A frame is the header
+/// followed directly by the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Header {
+    pub layout: Layout,
+    /// The field marked `@id`, holding the id of the message which follows
+    pub id_field: String,
+    /// The field marked `@length`, if any
+    pub length: Option<HeaderLength>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeaderLength {
+    pub field: String,
+    /// Whether the length counts the whole frame, `@length(frame)`, or only the message after
+    /// the header, `@length(message)`
+    pub includes_header: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+    pub name: String,
+    pub id: usize,
+    pub layout: Layout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Struct {
+    pub name: String,
+    pub layout: Layout,
+}
+
+/// Every variant of a union starts at the same offset, the union is as big as its largest variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Union {
+    pub name: String,
+    /// The enum whose value selects the active variant
+    pub discriminator_type: String,
+    pub size: usize,
+    pub alignment: usize,
+    pub variants: Vec<UnionVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnionVariant {
+    pub name: String,
+    /// The name of the discriminator value which selects this variant
+    pub selector: String,
+    pub value: u64,
+    pub field_type: FieldType_,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Enum {
+    pub name: String,
+    pub kind: EnumKind,
+    pub underlying: ScalarType,
+    pub values: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumVariant {
+    pub name: String,
+    pub value: u64,
+}
+
+/// How fields without an explicit `@align` are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AlignmentMode {
+    /// Every field directly follows the previous one
+    Packed,
+    /// Every field is padded to sit on its natural alignment, as a C compiler would
+    Natural,
+}
+
+/// The order in which the bytes of multi-byte values are stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ByteOrder {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Protocol {
+    pub fn qualified_name(&self) -> String {
+        self.name.join(".")
+    }
+
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages.iter().find(|m| m.name == name)
+    }
+
+    pub fn message_by_id(&self, id: usize) -> Option<&Message> {
+        self.messages.iter().find(|m| m.id == id)
+    }
+
+    pub fn structure(&self, name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    pub fn union(&self, name: &str) -> Option<&Union> {
+        self.unions.iter().find(|u| u.name == name)
+    }
+
+    pub fn enumeration(&self, name: &str) -> Option<&Enum> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+}
+
+impl Enum {
+    pub fn variant(&self, name: &str) -> Option<&EnumVariant> {
+        self.values.iter().find(|v| v.name == name)
+    }
+
+    pub fn variant_by_value(&self, value: u64) -> Option<&EnumVariant> {
+        self.values.iter().find(|v| v.value == value)
+    }
+}
+
+impl Header {
+    pub fn id(&self) -> &Field {
+        self.layout
+            .field(&self.id_field)
+            .expect("resolved headers have their id field")
+    }
+
+    pub fn length(&self) -> Option<&Field> {
+        self.length.as_ref().map(|l| {
+            self.layout
+                .field(&l.field)
+                .expect("resolved headers have their length field")
+        })
+    }
+
+    /// The value of the length field in front of a message of `message_size` bytes
+    pub fn length_value(&self, message_size: usize) -> usize {
+        match &self.length {
+            Some(l) if l.includes_header => self.layout.size + message_size,
+            _ => message_size,
+        }
+    }
+
+    /// The number of bytes in the frame whose length field holds `length`, `None` when the
+    /// length is too small to cover the header
+    pub fn frame_size(&self, length: usize) -> Option<usize> {
+        match &self.length {
+            Some(l) if l.includes_header => (length >= self.layout.size).then_some(length),
+            _ => Some(self.layout.size + length),
+        }
+    }
+}
+
+impl Union {
+    pub fn variant_for(&self, value: u64) -> Option<&UnionVariant> {
+        self.variants.iter().find(|v| v.value == value)
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "message {} [{}] size {} align {}",
+            self.name, self.id, self.layout.size, self.layout.alignment
+        )?;
+        write!(f, "{}", self.layout)
+    }
+}
+
+impl Display for Header {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "header size {} align {}",
+            self.layout.size, self.layout.alignment
+        )?;
+        write!(f, "{}", self.layout)
+    }
+}
+
+impl Display for Struct {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "struct {} size {} align {}",
+            self.name, self.layout.size, self.layout.alignment
+        )?;
+        write!(f, "{}", self.layout)
+    }
+}
@@ -0,0 +1,1232 @@
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::{
+    ArrayLike, EnumKind, EnumType, FieldType, FieldType_, ScalarType, StructType, UnionType,
+};
+use crate::protocol::layout::LayoutBuilder;
+use crate::protocol::{
+    AlignmentMode, ByteOrder, Enum, EnumVariant, Header, HeaderLength, Message, PaddingReason,
+    Protocol, ResolveError, Struct, Union, UnionVariant,
+};
+use crate::syntax::{
+    AttributeSyntax, AttributeValue, DeclarationSyntax, EnumDeclarationSyntax, FieldDeclaration,
+    FieldTypeSyntax, HeaderDeclarationSyntax, MemberDeclaration, MessageDeclarationSyntax,
+    StructDeclarationSyntax, SyntaxUnit, UnionDeclarationSyntax,
+};
+
+pub fn resolve(unit: &SyntaxUnit) -> Result<Protocol, ResolveError> {
+    let mut alignment = AlignmentMode::Packed;
+    let mut byte_order = ByteOrder::Little;
+    for attribute in &unit.protocol.attributes {
+        let declaration = unit.protocol.components.join(".");
+        match attribute.name.as_str() {
+            "align" => match align_attribute(&declaration, attribute)? {
+                AlignAttribute::Mode(mode) => alignment = mode,
+                AlignAttribute::Bytes(_) => {
+                    return Err(ResolveError::InvalidAttribute {
+                        declaration,
+                        attribute: attribute.name.clone(),
+                        reason: String::from("expected 'natural' or 'packed'"),
+                    })
+                }
+            },
+            "byte_order" => match &attribute.value {
+                Some(AttributeValue::Identifier(v)) if v == "little" => {
+                    byte_order = ByteOrder::Little
+                }
+                Some(AttributeValue::Identifier(v)) if v == "big" => byte_order = ByteOrder::Big,
+                _ => {
+                    return Err(ResolveError::InvalidAttribute {
+                        declaration,
+                        attribute: attribute.name.clone(),
+                        reason: String::from("expected 'little' or 'big'"),
+                    })
+                }
+            },
+            _ => return Err(unknown_attribute(&declaration, attribute)),
+        }
+    }
+
+    check_unique_names(unit)?;
+
+    // enums don't depend on anything else so they are resolved up front
+    let enums = unit
+        .declarations
+        .iter()
+        .filter_map(|d| match d {
+            DeclarationSyntax::Enum(e) => Some(resolve_enum(e)),
+            _ => None,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut resolver = Resolver {
+        unit,
+        alignment,
+        enums,
+        structs: vec![],
+        unions: vec![],
+        in_progress: vec![],
+    };
+    let mut messages: Vec<Message> = vec![];
+    let mut header: Option<Header> = None;
+
+    for decl in &unit.declarations {
+        match decl {
+            DeclarationSyntax::Message(m) => {
+                let message = resolver.resolve_message(m)?;
+                if let Some(existing) = messages.iter().find(|x| x.id == message.id) {
+                    return Err(ResolveError::DuplicateMessageId {
+                        id: message.id,
+                        first: existing.name.clone(),
+                        second: message.name,
+                    });
+                }
+                messages.push(message);
+            }
+            DeclarationSyntax::Struct(s) => {
+                resolver.resolve_named_type(&s.name)?;
+            }
+            DeclarationSyntax::Union(u) => {
+                resolver.resolve_named_type(&u.name)?;
+            }
+            DeclarationSyntax::Enum(_) => {}
+            DeclarationSyntax::Header(h) => {
+                if header.is_some() {
+                    return Err(ResolveError::InvalidHeader(String::from(
+                        "a protocol declares at most one header",
+                    )));
+                }
+                header = Some(resolver.resolve_header(h)?);
+            }
+        }
+    }
+    if let Some(header) = &header {
+        check_header_fits(header, &messages)?;
+    }
+
+    Ok(Protocol {
+        name: unit.protocol.components.clone(),
+        alignment,
+        byte_order,
+        structs: resolver.structs,
+        unions: resolver.unions,
+        enums: resolver.enums,
+        messages,
+        header,
+    })
+}
+
+/// Every message id has to fit in the id field of the header and every length in its length field
+fn check_header_fits(header: &Header, messages: &[Message]) -> Result<(), ResolveError> {
+    let max = |size: usize| u64::MAX >> (64 - size * 8);
+    let id = header.id();
+    for m in messages {
+        if m.id as u64 > max(id.size_bytes()) {
+            return Err(ResolveError::InvalidHeader(format!(
+                "the id {} of message '{}' does not fit in '{}', a {}",
+                m.id, m.name, id.name, id.field_type
+            )));
+        }
+        if let Some(length) = header.length() {
+            let value = header.length_value(m.layout.size);
+            if value as u64 > max(length.size_bytes()) {
+                return Err(ResolveError::InvalidHeader(format!(
+                    "the length {} of message '{}' does not fit in '{}', a {}",
+                    value, m.name, length.name, length.field_type
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_unique_names(unit: &SyntaxUnit) -> Result<(), ResolveError> {
+    let mut names: Vec<&str> = vec![];
+    for decl in &unit.declarations {
+        let name = match decl {
+            DeclarationSyntax::Message(m) => m.name.as_str(),
+            DeclarationSyntax::Struct(s) => s.name.as_str(),
+            DeclarationSyntax::Union(u) => u.name.as_str(),
+            DeclarationSyntax::Enum(e) => e.name.as_str(),
+            DeclarationSyntax::Header(_) => continue,
+        };
+        if names.contains(&name) {
+            return Err(ResolveError::DuplicateName(String::from(name)));
+        }
+        names.push(name);
+    }
+    Ok(())
+}
+
+fn resolve_enum(decl: &EnumDeclarationSyntax) -> Result<Enum, ResolveError> {
+    let invalid = |reason: String| ResolveError::InvalidEnum {
+        name: decl.name.clone(),
+        reason,
+    };
+
+    let bits = decl.underlying_type.size_bytes() * 8;
+    let max_value: u64 = match decl.underlying_type {
+        ScalarType::Signed(_) => (1u64 << (bits - 1)) - 1,
+        _ => u64::MAX >> (64 - bits),
+    };
+
+    let mut values: Vec<EnumVariant> = vec![];
+    for v in &decl.values {
+        let value = match (v.value, decl.kind) {
+            (Some(value), _) => value as u64,
+            // like C, an enum value follows on from the one before it
+            (None, EnumKind::Enum) => match values.last() {
+                None => 0,
+                Some(p) => p.value.checked_add(1).ok_or_else(|| {
+                    invalid(format!("'{}' follows {} and overflows", v.name, p.value))
+                })?,
+            },
+            // a flag takes the lowest bit no flag before it uses
+            (None, EnumKind::Bitflags) => {
+                let used = values.iter().fold(0, |used, p| used | p.value);
+                match (0..bits).find(|bit| used & (1u64 << bit) == 0) {
+                    Some(bit) => 1u64 << bit,
+                    None => {
+                        return Err(invalid(format!(
+                            "'{}' does not fit in a {}",
+                            v.name, decl.underlying_type
+                        )))
+                    }
+                }
+            }
+        };
+
+        if value > max_value {
+            return Err(invalid(format!(
+                "{} = {} does not fit in a {}",
+                v.name, value, decl.underlying_type
+            )));
+        }
+        if values.iter().any(|p| p.name == v.name) {
+            return Err(invalid(format!("'{}' is declared more than once", v.name)));
+        }
+        if let Some(p) = values.iter().find(|p| p.value == value) {
+            return Err(invalid(format!(
+                "'{}' and '{}' both have the value {}",
+                p.name, v.name, value
+            )));
+        }
+        values.push(EnumVariant {
+            name: v.name.clone(),
+            value,
+        });
+    }
+
+    Ok(Enum {
+        name: decl.name.clone(),
+        kind: decl.kind,
+        underlying: decl.underlying_type,
+        values,
+    })
+}
+
+enum AlignAttribute {
+    Mode(AlignmentMode),
+    Bytes(usize),
+}
+
+struct Resolver<'a> {
+    unit: &'a SyntaxUnit,
+    alignment: AlignmentMode,
+    enums: Vec<Enum>,
+    structs: Vec<Struct>,
+    unions: Vec<Union>,
+    /// Structs and unions currently being laid out, used to detect types which contain themselves
+    in_progress: Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+    fn resolve_message(
+        &mut self,
+        decl: &MessageDeclarationSyntax,
+    ) -> Result<Message, ResolveError> {
+        let mut expected_size: Option<usize> = None;
+        let mut fill_tail = false;
+        let mut mode = self.alignment;
+        let mut min_alignment = 1;
+
+        for attribute in &decl.attributes {
+            match attribute.name.as_str() {
+                "size" => expected_size = Some(integer_attribute(&decl.name, attribute)?),
+                "fill_tail" => fill_tail = flag_attribute(&decl.name, attribute)?,
+                "align" => match align_attribute(&decl.name, attribute)? {
+                    AlignAttribute::Mode(m) => mode = m,
+                    AlignAttribute::Bytes(n) => min_alignment = n,
+                },
+                _ => return Err(unknown_attribute(&decl.name, attribute)),
+            }
+        }
+
+        if fill_tail && expected_size.is_none() {
+            return Err(ResolveError::InvalidAttribute {
+                declaration: decl.name.clone(),
+                attribute: String::from("fill_tail"),
+                reason: String::from("requires a 'size' to fill up to"),
+            });
+        }
+
+        let mut builder = self.layout_members(&decl.name, mode, &decl.members)?;
+        builder.require_alignment(min_alignment);
+
+        if let Some(expected) = expected_size {
+            if fill_tail && builder.offset() < expected {
+                builder.push_padding(expected - builder.offset(), PaddingReason::Tail);
+            }
+        }
+
+        let layout = builder.finish();
+        if let Some(expected) = expected_size {
+            if layout.size != expected {
+                return Err(ResolveError::SizeMismatch {
+                    message: decl.name.clone(),
+                    expected,
+                    actual: layout.size,
+                });
+            }
+        }
+
+        Ok(Message {
+            name: decl.name.clone(),
+            id: decl.id,
+            layout,
+        })
+    }
+
+    /// The `@id` and `@length` annotations mark fields of the header, they are taken off before
+    /// the fields are laid out like those of a struct
+    fn resolve_header(&mut self, decl: &HeaderDeclarationSyntax) -> Result<Header, ResolveError> {
+        let mut id_field: Option<String> = None;
+        let mut length: Option<HeaderLength> = None;
+        let mut members: Vec<MemberDeclaration> = vec![];
+
+        for member in &decl.members {
+            let MemberDeclaration::Field(f) = member else {
+                members.push(member.clone());
+                continue;
+            };
+            let declaration = format!("header.{}", f.name);
+            let mut field = f.clone();
+            field.attributes.clear();
+            for attribute in &f.attributes {
+                let invalid = |reason: &str| ResolveError::InvalidAttribute {
+                    declaration: declaration.clone(),
+                    attribute: attribute.name.clone(),
+                    reason: String::from(reason),
+                };
+                let marked = match (attribute.name.as_str(), &attribute.value) {
+                    ("id", None) => id_field.replace(f.name.clone()).is_some(),
+                    ("id", _) => return Err(invalid("expected no value")),
+                    ("length", value) => {
+                        let includes_header = match value {
+                            None => true,
+                            Some(AttributeValue::Identifier(v)) if v == "frame" => true,
+                            Some(AttributeValue::Identifier(v)) if v == "message" => false,
+                            _ => return Err(invalid("expected no value, 'frame' or 'message'")),
+                        };
+                        let field = f.name.clone();
+                        length
+                            .replace(HeaderLength {
+                                field,
+                                includes_header,
+                            })
+                            .is_some()
+                    }
+                    _ => {
+                        field.attributes.push(attribute.clone());
+                        continue;
+                    }
+                };
+                if marked {
+                    return Err(ResolveError::InvalidHeader(format!(
+                        "more than one field is marked @{}",
+                        attribute.name
+                    )));
+                }
+            }
+            members.push(MemberDeclaration::Field(field));
+        }
+
+        let builder = self.layout_members("header", self.alignment, &members)?;
+        let layout = builder.finish();
+        let id_field = id_field.ok_or_else(|| {
+            ResolveError::InvalidHeader(String::from("a header needs an @id field"))
+        })?;
+        let mut marked = vec![("id", &id_field)];
+        if let Some(l) = &length {
+            if l.field == id_field {
+                return Err(ResolveError::InvalidHeader(String::from(
+                    "the @id and @length fields must be different fields",
+                )));
+            }
+            marked.push(("length", &l.field));
+        }
+        for (attribute, name) in marked {
+            let field = layout.field(name).expect("marked fields are laid out");
+            if !matches!(
+                field.field_type,
+                FieldType_::Scalar(ScalarType::Unsigned(_))
+            ) {
+                return Err(ResolveError::InvalidHeader(format!(
+                    "the @{} field '{}' is a {}, expected an unsigned integer",
+                    attribute, name, field.field_type
+                )));
+            }
+        }
+
+        Ok(Header {
+            layout,
+            id_field,
+            length,
+        })
+    }
+
+    /// Resolves a reference to a declared type, structs and unions are laid out the first time
+    /// they are used
+    fn resolve_named_type(&mut self, name: &str) -> Result<FieldType_, ResolveError> {
+        if let Some(e) = self.enums.iter().find(|e| e.name == name) {
+            return Ok(FieldType_::Enum(EnumType {
+                name: e.name.clone(),
+                kind: e.kind,
+                underlying: e.underlying,
+            }));
+        }
+        if let Some(s) = self.structs.iter().find(|s| s.name == name) {
+            return Ok(FieldType_::Struct(struct_type(s)));
+        }
+        if let Some(u) = self.unions.iter().find(|u| u.name == name) {
+            return Ok(FieldType_::Union(union_type(u)));
+        }
+        if self.in_progress.iter().any(|n| n == name) {
+            return Err(ResolveError::RecursiveStruct(String::from(name)));
+        }
+
+        let decl = self
+            .unit
+            .declarations
+            .iter()
+            .find(|d| match d {
+                DeclarationSyntax::Struct(s) => s.name == name,
+                DeclarationSyntax::Union(u) => u.name == name,
+                _ => false,
+            })
+            .ok_or_else(|| ResolveError::UnknownType(String::from(name)))?;
+
+        self.in_progress.push(String::from(name));
+        let resolved = match decl {
+            DeclarationSyntax::Struct(s) => {
+                let resolved = self.resolve_struct(s)?;
+                let st = struct_type(&resolved);
+                self.structs.push(resolved);
+                FieldType_::Struct(st)
+            }
+            DeclarationSyntax::Union(u) => {
+                let resolved = self.resolve_union(u)?;
+                let ut = union_type(&resolved);
+                self.unions.push(resolved);
+                FieldType_::Union(ut)
+            }
+            _ => unreachable!(),
+        };
+        self.in_progress.pop();
+
+        Ok(resolved)
+    }
+
+    fn resolve_struct(&mut self, decl: &StructDeclarationSyntax) -> Result<Struct, ResolveError> {
+        let mut mode = self.alignment;
+        let mut min_alignment = 1;
+
+        for attribute in &decl.attributes {
+            match attribute.name.as_str() {
+                "align" => match align_attribute(&decl.name, attribute)? {
+                    AlignAttribute::Mode(m) => mode = m,
+                    AlignAttribute::Bytes(n) => min_alignment = n,
+                },
+                _ => return Err(unknown_attribute(&decl.name, attribute)),
+            }
+        }
+
+        let mut builder = self.layout_members(&decl.name, mode, &decl.members)?;
+        builder.require_alignment(min_alignment);
+
+        Ok(Struct {
+            name: decl.name.clone(),
+            layout: builder.finish(),
+        })
+    }
+
+    fn resolve_union(&mut self, decl: &UnionDeclarationSyntax) -> Result<Union, ResolveError> {
+        let invalid = |reason: String| ResolveError::InvalidUnion {
+            name: decl.name.clone(),
+            reason,
+        };
+
+        let discriminator = self
+            .enums
+            .iter()
+            .find(|e| e.name == decl.discriminator_type && e.kind == EnumKind::Enum)
+            .cloned()
+            .ok_or_else(|| {
+                invalid(format!(
+                    "the discriminator '{}' is not a declared enum",
+                    decl.discriminator_type
+                ))
+            })?;
+
+        if decl.variants.is_empty() {
+            return Err(invalid(String::from("a union needs at least one variant")));
+        }
+
+        let mut size = 0;
+        let mut alignment = 1;
+        let mut variants: Vec<UnionVariant> = vec![];
+        for field in &decl.variants {
+            let declaration = format!("{}.{}", decl.name, field.name);
+            let field_type = self.resolve_field_type(&field.field_type)?;
+
+            let mut selector: Option<String> = None;
+            for attribute in &field.attributes {
+                match (attribute.name.as_str(), &attribute.value) {
+                    ("when", Some(AttributeValue::Identifier(v))) => selector = Some(v.clone()),
+                    ("when", _) => {
+                        return Err(ResolveError::InvalidAttribute {
+                            declaration,
+                            attribute: attribute.name.clone(),
+                            reason: format!("expected a value of '{}'", discriminator.name),
+                        })
+                    }
+                    _ => return Err(unknown_attribute(&declaration, attribute)),
+                }
+            }
+
+            let selector = selector.ok_or_else(|| {
+                invalid(format!(
+                    "variant '{}' needs a @when(..) naming the '{}' value which selects it",
+                    field.name, discriminator.name
+                ))
+            })?;
+            let value = discriminator
+                .variant(&selector)
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "'{}' is not a value of '{}'",
+                        selector, discriminator.name
+                    ))
+                })?
+                .value;
+            if let Some(other) = variants.iter().find(|v| v.value == value) {
+                return Err(invalid(format!(
+                    "'{}' and '{}' are both selected by '{}'",
+                    other.name, field.name, selector
+                )));
+            }
+
+            size = size.max(field_type.size_bytes());
+            alignment = alignment.max(match (self.alignment, &field_type) {
+                (_, FieldType_::Struct(st)) => st.alignment,
+                (AlignmentMode::Packed, _) => 1,
+                (AlignmentMode::Natural, t) => t.natural_alignment(),
+            });
+            variants.push(UnionVariant {
+                name: field.name.clone(),
+                selector,
+                value,
+                field_type,
+            });
+        }
+
+        Ok(Union {
+            name: decl.name.clone(),
+            discriminator_type: discriminator.name,
+            size: size.div_ceil(alignment) * alignment,
+            alignment,
+            variants,
+        })
+    }
+
+    fn layout_members(
+        &mut self,
+        declaration: &str,
+        mode: AlignmentMode,
+        members: &[MemberDeclaration],
+    ) -> Result<LayoutBuilder, ResolveError> {
+        let mut builder = LayoutBuilder::new();
+        for member in members {
+            match member {
+                MemberDeclaration::Field(f) => {
+                    self.add_field(declaration, mode, &mut builder, f)?
+                }
+                MemberDeclaration::Padding(size) => {
+                    builder.push_padding(*size, PaddingReason::Reserved)
+                }
+            }
+        }
+        self.check_discriminators(declaration, &builder)?;
+        Ok(builder)
+    }
+
+    /// Every union must be selected by a field of the union's enum type in the same layout
+    fn check_discriminators(
+        &self,
+        declaration: &str,
+        builder: &LayoutBuilder,
+    ) -> Result<(), ResolveError> {
+        for field in builder.fields() {
+            if let FieldType_::Union(ut) = &field.field_type {
+                let invalid = |reason: String| ResolveError::InvalidDiscriminator {
+                    field: format!("{}.{}", declaration, field.name),
+                    reason,
+                };
+                let union = self
+                    .unions
+                    .iter()
+                    .find(|u| u.name == ut.name)
+                    .expect("union fields are only created from resolved unions");
+
+                let discriminator = builder
+                    .fields()
+                    .find(|f| f.name == ut.discriminator)
+                    .ok_or_else(|| {
+                        invalid(format!("there is no field named '{}'", ut.discriminator))
+                    })?;
+                match &discriminator.field_type {
+                    FieldType_::Enum(et) if et.name == union.discriminator_type => {}
+                    other => {
+                        return Err(invalid(format!(
+                            "'{}' is a {} but '{}' is selected by a {}",
+                            discriminator.name, other, union.name, union.discriminator_type
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_field(
+        &mut self,
+        parent: &str,
+        mode: AlignmentMode,
+        builder: &mut LayoutBuilder,
+        field: &FieldDeclaration,
+    ) -> Result<(), ResolveError> {
+        let declaration = format!("{}.{}", parent, field.name);
+        let mut field_type = self.resolve_field_type(&field.field_type)?;
+
+        // structs and unions carry the alignment they were laid out with, even into a packed
+        // layout
+        let mut alignment = match (mode, &field_type) {
+            (_, FieldType_::Struct(st)) => st.alignment,
+            (_, FieldType_::Union(ut)) => ut.alignment,
+            (AlignmentMode::Packed, _) => 1,
+            (AlignmentMode::Natural, t) => t.natural_alignment(),
+        };
+        let mut expected_offset: Option<usize> = None;
+
+        for attribute in &field.attributes {
+            match attribute.name.as_str() {
+                "offset" => expected_offset = Some(integer_attribute(&declaration, attribute)?),
+                "align" => match align_attribute(&declaration, attribute)? {
+                    AlignAttribute::Mode(AlignmentMode::Packed) => alignment = 1,
+                    AlignAttribute::Mode(AlignmentMode::Natural) => {
+                        alignment = alignment.max(field_type.natural_alignment())
+                    }
+                    AlignAttribute::Bytes(n) => alignment = alignment.max(n),
+                },
+                "discriminator" => match (&mut field_type, &attribute.value) {
+                    (FieldType_::Union(ut), Some(AttributeValue::Identifier(name))) => {
+                        ut.discriminator = name.clone()
+                    }
+                    _ => {
+                        return Err(ResolveError::InvalidAttribute {
+                            declaration,
+                            attribute: attribute.name.clone(),
+                            reason: String::from(
+                                "expected the name of a field on a field of a union type",
+                            ),
+                        })
+                    }
+                },
+                "pad" => match (&mut field_type, &attribute.value) {
+                    (
+                        FieldType_::Vector(ArrayLike::AsciiString { padding, .. }),
+                        Some(AttributeValue::Identifier(fill)),
+                    ) if fill == "nul" || fill == "space" => {
+                        *padding = match fill.as_str() {
+                            "space" => AsciiPadding::Space,
+                            _ => AsciiPadding::Nul,
+                        }
+                    }
+                    _ => {
+                        return Err(ResolveError::InvalidAttribute {
+                            declaration,
+                            attribute: attribute.name.clone(),
+                            reason: String::from(
+                                "expected 'nul' or 'space' on a field of an ascii string type",
+                            ),
+                        })
+                    }
+                },
+                _ => return Err(unknown_attribute(&declaration, attribute)),
+            }
+        }
+
+        if let FieldType_::Union(ut) = &field_type {
+            if ut.discriminator.is_empty() {
+                return Err(ResolveError::InvalidDiscriminator {
+                    field: declaration,
+                    reason: String::from("a union field needs a @discriminator(field)"),
+                });
+            }
+        }
+
+        builder.align_to(alignment);
+
+        if let Some(expected) = expected_offset {
+            if builder.offset() != expected {
+                return Err(ResolveError::OffsetMismatch {
+                    message: String::from(parent),
+                    field: field.name.clone(),
+                    expected,
+                    actual: builder.offset(),
+                });
+            }
+        }
+
+        builder.push_field(field.name.clone(), field_type, field.description.clone());
+        Ok(())
+    }
+
+    fn resolve_field_type(
+        &mut self,
+        field_type: &FieldTypeSyntax,
+    ) -> Result<FieldType_, ResolveError> {
+        match field_type {
+            FieldTypeSyntax::BuiltIn(t) => Ok(t.clone()),
+            FieldTypeSyntax::Named(name) => self.resolve_named_type(name),
+        }
+    }
+}
+
+fn struct_type(s: &Struct) -> StructType {
+    StructType {
+        name: s.name.clone(),
+        size: s.layout.size,
+        alignment: s.layout.alignment,
+    }
+}
+
+/// The discriminator is filled in by the field which uses the union
+fn union_type(u: &Union) -> UnionType {
+    UnionType {
+        name: u.name.clone(),
+        size: u.size,
+        alignment: u.alignment,
+        discriminator: String::new(),
+    }
+}
+
+fn align_attribute(
+    declaration: &str,
+    attribute: &AttributeSyntax,
+) -> Result<AlignAttribute, ResolveError> {
+    let invalid = |reason: &str| ResolveError::InvalidAttribute {
+        declaration: String::from(declaration),
+        attribute: attribute.name.clone(),
+        reason: String::from(reason),
+    };
+    match &attribute.value {
+        Some(AttributeValue::Integer(n)) if n.is_power_of_two() => Ok(AlignAttribute::Bytes(*n)),
+        Some(AttributeValue::Integer(_)) => Err(invalid("alignment must be a power of two")),
+        Some(AttributeValue::Identifier(id)) if id == "natural" => {
+            Ok(AlignAttribute::Mode(AlignmentMode::Natural))
+        }
+        Some(AttributeValue::Identifier(id)) if id == "packed" => {
+            Ok(AlignAttribute::Mode(AlignmentMode::Packed))
+        }
+        _ => Err(invalid("expected a power of two, 'natural' or 'packed'")),
+    }
+}
+
+fn integer_attribute(
+    declaration: &str,
+    attribute: &AttributeSyntax,
+) -> Result<usize, ResolveError> {
+    match attribute.value {
+        Some(AttributeValue::Integer(v)) => Ok(v),
+        _ => Err(ResolveError::InvalidAttribute {
+            declaration: String::from(declaration),
+            attribute: attribute.name.clone(),
+            reason: String::from("expected an integer value"),
+        }),
+    }
+}
+
+fn flag_attribute(declaration: &str, attribute: &AttributeSyntax) -> Result<bool, ResolveError> {
+    match &attribute.value {
+        None => Ok(true),
+        Some(AttributeValue::Identifier(v)) if v == "true" => Ok(true),
+        Some(AttributeValue::Identifier(v)) if v == "false" => Ok(false),
+        Some(_) => Err(ResolveError::InvalidAttribute {
+            declaration: String::from(declaration),
+            attribute: attribute.name.clone(),
+            reason: String::from("expected no value, 'true' or 'false'"),
+        }),
+    }
+}
+
+fn unknown_attribute(declaration: &str, attribute: &AttributeSyntax) -> ResolveError {
+    ResolveError::UnknownAttribute {
+        declaration: String::from(declaration),
+        attribute: attribute.name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data_types::array_like::AsciiPadding;
+    use crate::data_types::{ArrayLike, FieldType_};
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+    use crate::protocol::{
+        resolve, ByteOrder, Member, Padding, PaddingReason, Protocol, ResolveError,
+    };
+
+    fn resolve_text(text: &str) -> Result<Protocol, ResolveError> {
+        let unit = parse(&mut tokenize(text)).unwrap().unwrap();
+        resolve(&unit)
+    }
+
+    #[test]
+    fn test_offsets_follow_declaration_order() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            message bar [1] {\
+                u8: a;\
+                4;\
+                u32: b;\
+                ascii[10]: c;\
+            }",
+        )
+        .unwrap();
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(19, layout.size);
+        assert_eq!(0, layout.field("a").unwrap().offset);
+        assert_eq!(5, layout.field("b").unwrap().offset);
+        assert_eq!(9, layout.field("c").unwrap().offset);
+    }
+
+    #[test]
+    fn test_offset_assertion() {
+        let ok = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; @offset(1) u32: b; }",
+        );
+        assert!(ok.is_ok());
+
+        let err = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; @offset(4) u32: b; }",
+        );
+        assert_eq!(
+            err,
+            Err(ResolveError::OffsetMismatch {
+                message: String::from("bar"),
+                field: String::from("b"),
+                expected: 4,
+                actual: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_size_assertion() {
+        let err = resolve_text(
+            "protocol foo;\
+            message bar [1, size = 8] { u8: a; u32: b; }",
+        );
+        assert_eq!(
+            err,
+            Err(ResolveError::SizeMismatch {
+                message: String::from("bar"),
+                expected: 8,
+                actual: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_fill_tail() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            message bar [1, size = 8, fill_tail] { u8: a; u32: b; }",
+        )
+        .unwrap();
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(8, layout.size);
+        match layout.members.last() {
+            Some(Member::Padding(p)) => {
+                assert_eq!((5, 3, PaddingReason::Tail), (p.offset, p.size, p.reason))
+            }
+            other => panic!("expected tail padding, found {:?}", other),
+        }
+
+        let too_big = resolve_text(
+            "protocol foo;\
+            message bar [1, size = 4, fill_tail] { u8: a; u32: b; }",
+        );
+        assert!(matches!(too_big, Err(ResolveError::SizeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_duplicate_message_id() {
+        let err = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; }\
+            message baz [1] { u8: a; }",
+        );
+        assert!(matches!(
+            err,
+            Err(ResolveError::DuplicateMessageId { id: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_natural_alignment() {
+        let protocol = resolve_text(
+            "protocol foo [align = natural];\
+            message bar [1] { u8: a; u32: b; u8: c; u64: d; u16: e; }",
+        )
+        .unwrap();
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(4, layout.field("b").unwrap().offset);
+        assert_eq!(8, layout.field("c").unwrap().offset);
+        assert_eq!(16, layout.field("d").unwrap().offset);
+        assert_eq!(24, layout.field("e").unwrap().offset);
+        assert_eq!(8, layout.alignment);
+        assert_eq!(32, layout.size);
+        assert!(layout.members.iter().any(|m| matches!(
+            m,
+            Member::Padding(Padding {
+                offset: 1,
+                size: 3,
+                reason: PaddingReason::Alignment(4)
+            })
+        )));
+    }
+
+    #[test]
+    fn test_byte_order() {
+        let default = resolve_text("protocol foo;").unwrap();
+        assert_eq!(ByteOrder::Little, default.byte_order);
+
+        let big = resolve_text("protocol foo [byte_order = big];").unwrap();
+        assert_eq!(ByteOrder::Big, big.byte_order);
+
+        let invalid = resolve_text("protocol foo [byte_order = middle];");
+        assert!(matches!(
+            invalid,
+            Err(ResolveError::InvalidAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn test_message_overrides_protocol_alignment() {
+        let protocol = resolve_text(
+            "protocol foo [align = natural];\
+            message bar [1, align = packed] { u8: a; u32: b; }",
+        )
+        .unwrap();
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(1, layout.field("b").unwrap().offset);
+        assert_eq!(5, layout.size);
+    }
+
+    #[test]
+    fn test_field_alignment() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; @align(8) @offset(8) u8: b; }",
+        )
+        .unwrap();
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(8, layout.field("b").unwrap().offset);
+        assert_eq!(16, layout.size);
+
+        let err = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; @align(3) u8: b; }",
+        );
+        assert!(matches!(err, Err(ResolveError::InvalidAttribute { .. })));
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            message bar [1] { u8: a; Inner: inner; u8: b; }\
+            @align(4) struct Inner { u16: x; u8: y; }",
+        )
+        .unwrap();
+
+        let inner = protocol.structure("Inner").unwrap();
+        assert_eq!((4, 4), (inner.layout.size, inner.layout.alignment));
+
+        let layout = &protocol.message("bar").unwrap().layout;
+        assert_eq!(4, layout.field("inner").unwrap().offset);
+        assert_eq!(8, layout.field("b").unwrap().offset);
+        assert_eq!(12, layout.size);
+    }
+
+    #[test]
+    fn test_unknown_and_recursive_structs() {
+        let unknown = resolve_text("protocol foo; message bar [1] { Missing: m; }");
+        assert_eq!(
+            unknown,
+            Err(ResolveError::UnknownType(String::from("Missing")))
+        );
+
+        let recursive = resolve_text("protocol foo; struct A { B: b; } struct B { A: a; }");
+        assert_eq!(
+            recursive,
+            Err(ResolveError::RecursiveStruct(String::from("A")))
+        );
+    }
+
+    #[test]
+    fn test_layout_report_shows_padding_reason() {
+        let protocol = resolve_text(
+            "protocol foo [align = natural];\
+            message bar [1] { u8: a; u32: b; }",
+        )
+        .unwrap();
+
+        let report = protocol.message("bar").unwrap().to_string();
+        assert!(report.contains("message bar [1] size 8 align 4"));
+        assert!(report.contains("<padding, align to 4 bytes>"));
+        assert!(report.contains("u32: b"));
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            enum Outcome : u8 { SUCCESS = 1, FAIL, UNKNOWN = 7 }\
+            bitflags Flags : u8 { A, B, C }",
+        )
+        .unwrap();
+
+        let outcome = protocol.enumeration("Outcome").unwrap();
+        let values: Vec<u64> = outcome.values.iter().map(|v| v.value).collect();
+        assert_eq!(vec![1, 2, 7], values);
+
+        let flags = protocol.enumeration("Flags").unwrap();
+        let values: Vec<u64> = flags.values.iter().map(|v| v.value).collect();
+        assert_eq!(vec![1, 2, 4], values);
+
+        let too_big = resolve_text("protocol foo; enum E : u8 { A = 256 }");
+        assert!(matches!(too_big, Err(ResolveError::InvalidEnum { .. })));
+
+        let duplicate = resolve_text("protocol foo; enum E : u8 { A = 1, B = 1 }");
+        assert!(matches!(duplicate, Err(ResolveError::InvalidEnum { .. })));
+
+        let overflow = resolve_text("protocol foo; enum E : u64 { A = 18446744073709551615, B }");
+        assert!(matches!(overflow, Err(ResolveError::InvalidEnum { .. })));
+    }
+
+    #[test]
+    fn test_bitflags_take_the_lowest_free_bit() {
+        let protocol = resolve_text(
+            "protocol foo; bitflags F : u8 { A = 2, B, C = 8, D } bitflags G : u8 { A = 3, B }",
+        )
+        .unwrap();
+        let values = |name| -> Vec<u64> {
+            let flags = protocol.enumeration(name).unwrap();
+            flags.values.iter().map(|v| v.value).collect()
+        };
+        assert_eq!(vec![2, 1, 8, 4], values("F"));
+        assert_eq!(vec![3, 4], values("G"));
+
+        let full = resolve_text("protocol foo; bitflags F : u8 { A = 255, B }");
+        assert!(matches!(full, Err(ResolveError::InvalidEnum { .. })));
+    }
+
+    #[test]
+    fn test_union_is_sized_to_largest_variant() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            enum Kind : u8 { ORDER = 1, CANCEL = 2 }\
+            struct OrderBody { u64: price; u32: quantity; }\
+            struct CancelBody { u64: order_id; }\
+            union Payload : Kind { @when(ORDER) OrderBody: order; @when(CANCEL) CancelBody: cancel; }\
+            message Request [1] { Kind: kind; @discriminator(kind) Payload: payload; u8: trailer; }",
+        )
+        .unwrap();
+
+        let payload = protocol.union("Payload").unwrap();
+        assert_eq!(12, payload.size);
+        assert_eq!("cancel", payload.variant_for(2).unwrap().name);
+
+        let layout = &protocol.message("Request").unwrap().layout;
+        assert_eq!(1, layout.field("payload").unwrap().offset);
+        assert_eq!(13, layout.field("trailer").unwrap().offset);
+    }
+
+    #[test]
+    fn test_union_discriminator_checks() {
+        let declarations = "protocol foo;\
+            enum Kind : u8 { A = 1, B = 2 }\
+            enum Other : u8 { A = 1 }\
+            union Payload : Kind { @when(A) u32: a; @when(B) u64: b; }";
+
+        let missing = resolve_text(&format!(
+            "{} message M [1] {{ Payload: payload; }}",
+            declarations
+        ));
+        assert!(matches!(
+            missing,
+            Err(ResolveError::InvalidDiscriminator { .. })
+        ));
+
+        let unknown_field = resolve_text(&format!(
+            "{} message M [1] {{ @discriminator(kind) Payload: payload; }}",
+            declarations
+        ));
+        assert!(matches!(
+            unknown_field,
+            Err(ResolveError::InvalidDiscriminator { .. })
+        ));
+
+        let wrong_type = resolve_text(&format!(
+            "{} message M [1] {{ Other: kind; @discriminator(kind) Payload: payload; }}",
+            declarations
+        ));
+        assert!(matches!(
+            wrong_type,
+            Err(ResolveError::InvalidDiscriminator { .. })
+        ));
+
+        let unknown_selector = resolve_text(
+            "protocol foo;\
+            enum Kind : u8 { A = 1 }\
+            union Payload : Kind { @when(C) u32: c; }",
+        );
+        assert!(matches!(
+            unknown_selector,
+            Err(ResolveError::InvalidUnion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ascii_padding() {
+        let protocol = resolve_text(
+            "protocol foo; message M [1] { ascii[4]: a; @pad(space) ascii[4]: b; @pad(nul) ascii[4]: c; }",
+        )
+        .unwrap();
+        let layout = &protocol.message("M").unwrap().layout;
+        let padding = |name: &str| match &layout.field(name).unwrap().field_type {
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => *padding,
+            _ => unreachable!(),
+        };
+        assert_eq!(AsciiPadding::Nul, padding("a"));
+        assert_eq!(AsciiPadding::Space, padding("b"));
+        assert_eq!(AsciiPadding::Nul, padding("c"));
+
+        for invalid in [
+            "@pad(space) u32: a;",
+            "@pad(tab) ascii[4]: a;",
+            "@pad ascii[4]: a;",
+        ] {
+            let err = resolve_text(&format!("protocol foo; message M [1] {{ {} }}", invalid));
+            assert!(
+                matches!(err, Err(ResolveError::InvalidAttribute { .. })),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_header() {
+        let protocol = resolve_text(
+            "protocol foo;\
+            header { @length u16: length; @id u8: kind; 1; }\
+            message M [7] { u32: a; }",
+        )
+        .unwrap();
+        let header = protocol.header.as_ref().unwrap();
+        assert_eq!(4, header.layout.size);
+        assert_eq!("kind", header.id().name);
+        assert_eq!(2, header.id().offset);
+        assert_eq!("length", header.length().unwrap().name);
+        assert_eq!(8, header.length_value(4));
+        assert_eq!(Some(8), header.frame_size(8));
+        assert_eq!(None, header.frame_size(3));
+
+        let protocol = resolve_text(
+            "protocol foo; header { @id u16: id; @length(message) u16: length; } message M [1] { u32: a; }",
+        )
+        .unwrap();
+        let header = protocol.header.as_ref().unwrap();
+        assert_eq!(4, header.length_value(4));
+        assert_eq!(Some(8), header.frame_size(4));
+        assert!(resolve_text("protocol foo; message M [1] { u8: a; }")
+            .unwrap()
+            .header
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        for (schema, reason) in [
+            ("header { u16: id; }", "a header needs an @id field"),
+            (
+                "header { @id u8: a; @id u8: b; }",
+                "more than one field is marked @id",
+            ),
+            (
+                "header { @id @length u16: a; }",
+                "the @id and @length fields must be different fields",
+            ),
+            (
+                "header { @id i16: id; }",
+                "the @id field 'id' is a i16, expected an unsigned integer",
+            ),
+            (
+                "header { @id u8: id; } header { @id u8: id; }",
+                "a protocol declares at most one header",
+            ),
+            (
+                "header { @id u8: id; } message N [256] { u8: a; }",
+                "the id 256 of message 'N' does not fit in 'id', a u8",
+            ),
+            (
+                "header { @id u8: id; @length u8: length; } message N [2] { u8[254]: a; }",
+                "the length 256 of message 'N' does not fit in 'length', a u8",
+            ),
+        ] {
+            assert_eq!(
+                Err(ResolveError::InvalidHeader(String::from(reason))),
+                resolve_text(&format!(
+                    "protocol foo; message M [1] {{ u8: a; }} {}",
+                    schema
+                )),
+                "{}",
+                schema
+            );
+        }
+        assert!(matches!(
+            resolve_text("protocol foo; header { @id u8: id; @length(header) u8: length; }"),
+            Err(ResolveError::InvalidAttribute { .. })
+        ));
+    }
+}
@@ -0,0 +1,507 @@
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Float, Mantissa};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType};
+use crate::protocol::{ByteOrder, Layout, Message, Protocol};
+use crate::runtime::{DynamicMessage, Value};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ends before the end of `what`
+    TooShort {
+        what: String,
+        needed: usize,
+        available: usize,
+    },
+    UnknownId(u64),
+    /// The length in the header of a frame is too small for the message the frame holds
+    InvalidLength {
+        message: String,
+        length: u64,
+    },
+    /// The discriminator of a union holds a value none of its variants is selected by
+    UnknownVariant {
+        union: String,
+        value: u64,
+    },
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::TooShort {
+                what,
+                needed,
+                available,
+            } => write!(
+                f,
+                "{} needs {} bytes but the buffer only has {}",
+                what, needed, available
+            ),
+            DecodeError::UnknownId(id) => write!(f, "no message has the id {}", id),
+            DecodeError::InvalidLength { message, length } => write!(
+                f,
+                "the header gives a length of {} which is too short for message '{}'",
+                length, message
+            ),
+            DecodeError::UnknownVariant { union, value } => write!(
+                f,
+                "union '{}' has no variant for the discriminator value {}",
+                union, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reads messages from buffers using the layouts of a resolved protocol, for tools which have to
+/// handle any protocol rather than one they were generated for.
+///
+/// When the protocol declares a `header` [`Decoder::decode`] reads it in front of every message,
+/// otherwise it expects every message to be preceded by its id, an unsigned integer of `id_size`
+/// bytes in the protocol's byte order.
+pub struct Decoder<'p> {
+    protocol: &'p Protocol,
+    id_size: usize,
+}
+
+impl<'p> Decoder<'p> {
+    /// A decoder expecting ids of 2 bytes
+    pub fn new(protocol: &'p Protocol) -> Self {
+        Decoder {
+            protocol,
+            id_size: 2,
+        }
+    }
+
+    /// Panics unless `id_size` is 1, 2, 4 or 8
+    pub fn with_id_size(mut self, id_size: usize) -> Self {
+        assert!(
+            matches!(id_size, 1 | 2 | 4 | 8),
+            "ids are 1, 2, 4 or 8 bytes, not {}",
+            id_size
+        );
+        self.id_size = id_size;
+        self
+    }
+
+    pub fn protocol(&self) -> &'p Protocol {
+        self.protocol
+    }
+
+    pub fn id_size(&self) -> usize {
+        self.id_size
+    }
+
+    /// Reads the header or id at the start of `buf` then the message it identifies, returns the
+    /// message and the number of bytes the frame took up, anything after it is left alone.
+    ///
+    /// The length field of a header is trusted for the size of the frame, so a frame longer than
+    /// its message is skipped over whole.
+    pub fn decode(&self, buf: &[u8]) -> Result<(DynamicMessage, usize), DecodeError> {
+        let id = self.read_id(buf)?;
+        let message = usize::try_from(id)
+            .ok()
+            .and_then(|id| self.protocol.message_by_id(id))
+            .ok_or(DecodeError::UnknownId(id))?;
+        let Some(header) = &self.protocol.header else {
+            let decoded = self.decode_message(message, &buf[self.id_size..])?;
+            return Ok((decoded, self.id_size + message.layout.size));
+        };
+
+        let start = header.layout.size;
+        let mut end = start + message.layout.size;
+        if let Some(field) = header.length() {
+            let length = self.unsigned(&buf[field.offset..field.offset + field.size_bytes()]);
+            end = usize::try_from(length)
+                .ok()
+                .and_then(|length| header.frame_size(length))
+                .filter(|frame| *frame >= end)
+                .ok_or_else(|| DecodeError::InvalidLength {
+                    message: message.name.clone(),
+                    length,
+                })?;
+            if buf.len() < end {
+                return Err(DecodeError::TooShort {
+                    what: format!("the frame of message '{}'", message.name),
+                    needed: end,
+                    available: buf.len(),
+                });
+            }
+        }
+        let decoded = self.decode_message(message, &buf[start..])?;
+        Ok((decoded, end))
+    }
+
+    /// Reads `message` from the start of `buf`, which doesn't hold an id
+    pub fn decode_message(
+        &self,
+        message: &Message,
+        buf: &[u8],
+    ) -> Result<DynamicMessage, DecodeError> {
+        if buf.len() < message.layout.size {
+            return Err(DecodeError::TooShort {
+                what: format!("message '{}'", message.name),
+                needed: message.layout.size,
+                available: buf.len(),
+            });
+        }
+        Ok(DynamicMessage {
+            name: message.name.clone(),
+            id: message.id,
+            fields: self.fields(&message.layout, &buf[..message.layout.size])?,
+        })
+    }
+
+    /// Reads a value of `field_type` from the start of `buf`, as a field of a message would be.
+    ///
+    /// Panics on a union as the variant depends on the value of another field, read unions as
+    /// part of their message.
+    pub fn decode_value(&self, field_type: &FieldType_, buf: &[u8]) -> Result<Value, DecodeError> {
+        assert!(
+            !matches!(field_type, FieldType_::Union(_)),
+            "unions are only read as fields of a layout"
+        );
+        let size = field_type.size_bytes();
+        if buf.len() < size {
+            return Err(DecodeError::TooShort {
+                what: format!("a {}", field_type),
+                needed: size,
+                available: buf.len(),
+            });
+        }
+        self.value(field_type, &buf[..size], None)
+    }
+
+    /// The message id at the start of `buf`, read from the header when the protocol has one
+    pub fn read_id(&self, buf: &[u8]) -> Result<u64, DecodeError> {
+        if let Some(header) = &self.protocol.header {
+            if buf.len() < header.layout.size {
+                return Err(DecodeError::TooShort {
+                    what: String::from("the header"),
+                    needed: header.layout.size,
+                    available: buf.len(),
+                });
+            }
+            let id = header.id();
+            return Ok(self.unsigned(&buf[id.offset..id.offset + id.size_bytes()]));
+        }
+        if buf.len() < self.id_size {
+            return Err(DecodeError::TooShort {
+                what: String::from("the message id"),
+                needed: self.id_size,
+                available: buf.len(),
+            });
+        }
+        Ok(self.unsigned(&buf[..self.id_size]))
+    }
+
+    /// `bytes` holds exactly the bytes of `layout`
+    fn fields(&self, layout: &Layout, bytes: &[u8]) -> Result<Vec<(String, Value)>, DecodeError> {
+        layout
+            .fields()
+            .map(|f| {
+                let field_bytes = &bytes[f.offset..f.offset + f.size_bytes()];
+                // a union is selected by the value of a sibling field
+                let selector = match &f.field_type {
+                    FieldType_::Union(ut) => {
+                        let discriminator = layout
+                            .field(&ut.discriminator)
+                            .expect("resolved unions have a discriminator next to them");
+                        let offset = discriminator.offset;
+                        Some(self.unsigned(&bytes[offset..offset + discriminator.size_bytes()]))
+                    }
+                    _ => None,
+                };
+                Ok((
+                    f.name.clone(),
+                    self.value(&f.field_type, field_bytes, selector)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// `selector` is the value of the discriminator when `field_type` is a union
+    fn value(
+        &self,
+        field_type: &FieldType_,
+        bytes: &[u8],
+        selector: Option<u64>,
+    ) -> Result<Value, DecodeError> {
+        Ok(match field_type {
+            FieldType_::Scalar(s) => self.scalar(s, bytes),
+            FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => Value::Array(
+                bytes
+                    .chunks(scalar.size_bytes())
+                    .map(|element| self.scalar(scalar, element))
+                    .collect(),
+            ),
+            FieldType_::Vector(ArrayLike::AsciiString { padding, .. }) => {
+                Value::Ascii(ascii(bytes, *padding))
+            }
+            FieldType_::Vector(ArrayLike::Bytes { .. }) | FieldType_::Padding(_) => {
+                Value::Bytes(bytes.to_vec())
+            }
+            FieldType_::Struct(st) => {
+                let s = self
+                    .protocol
+                    .structure(&st.name)
+                    .expect("resolved protocols declare every struct they use");
+                Value::Struct(self.fields(&s.layout, bytes)?)
+            }
+            FieldType_::Enum(et) => {
+                let value = self.unsigned(bytes);
+                let e = self
+                    .protocol
+                    .enumeration(&et.name)
+                    .expect("resolved protocols declare every enum they use");
+                match et.kind {
+                    EnumKind::Enum => Value::Enum {
+                        name: e.variant_by_value(value).map(|v| v.name.clone()),
+                        value,
+                    },
+                    EnumKind::Bitflags => Value::Flags {
+                        names: e
+                            .values
+                            .iter()
+                            .filter(|flag| flag.value != 0 && value & flag.value == flag.value)
+                            .map(|flag| flag.name.clone())
+                            .collect(),
+                        value,
+                    },
+                }
+            }
+            FieldType_::Union(ut) => {
+                let u = self
+                    .protocol
+                    .union(&ut.name)
+                    .expect("resolved protocols declare every union they use");
+                let selector = selector.expect("unions are only read as fields of a layout");
+                let variant =
+                    u.variant_for(selector)
+                        .ok_or_else(|| DecodeError::UnknownVariant {
+                            union: u.name.clone(),
+                            value: selector,
+                        })?;
+                let size = variant.field_type.size_bytes();
+                Value::Union {
+                    variant: variant.name.clone(),
+                    value: Box::new(self.value(&variant.field_type, &bytes[..size], None)?),
+                }
+            }
+        })
+    }
+
+    fn scalar(&self, scalar: &ScalarType, bytes: &[u8]) -> Value {
+        match scalar {
+            ScalarType::Unsigned(_) => Value::Unsigned(self.unsigned(bytes)),
+            ScalarType::Signed(_) => Value::Signed(self.signed(bytes)),
+            ScalarType::Float(Float::F32) => {
+                Value::Float(f32::from_bits(self.unsigned(bytes) as u32) as f64)
+            }
+            ScalarType::Float(Float::F64) => Value::Float(f64::from_bits(self.unsigned(bytes))),
+            ScalarType::ByteSized(ByteSize::Byte) => Value::Byte(bytes[0]),
+            ScalarType::ByteSized(_) => Value::Ascii(ascii(bytes, AsciiPadding::Nul)),
+            ScalarType::Decimal(d) => Value::Decimal {
+                mantissa: match d.mantissa {
+                    Mantissa::Unsigned(_) => self.unsigned(bytes) as i128,
+                    Mantissa::Signed(_) => self.signed(bytes) as i128,
+                },
+                exponent: d.exponent,
+            },
+            ScalarType::Timestamp(t) => Value::Timestamp {
+                value: self.unsigned(bytes),
+                unit: t.unit,
+            },
+            ScalarType::Duration(d) => Value::Duration {
+                value: self.signed(bytes),
+                unit: d.unit,
+            },
+        }
+    }
+
+    /// An integer of up to 8 bytes in the protocol's byte order
+    fn unsigned(&self, bytes: &[u8]) -> u64 {
+        let fold = |value: u64, byte: &u8| (value << 8) | *byte as u64;
+        match self.protocol.byte_order {
+            ByteOrder::Little => bytes.iter().rev().fold(0, fold),
+            ByteOrder::Big => bytes.iter().fold(0, fold),
+        }
+    }
+
+    fn signed(&self, bytes: &[u8]) -> i64 {
+        let unused = 64 - bytes.len() * 8;
+        ((self.unsigned(bytes) << unused) as i64) >> unused
+    }
+}
+
+/// The text of an ascii field up to the first NUL, without the trailing spaces of a space padded
+/// field
+fn ascii(bytes: &[u8], padding: AsciiPadding) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    let text = String::from_utf8_lossy(&bytes[..end]);
+    match padding {
+        AsciiPadding::Nul => text.into_owned(),
+        AsciiPadding::Space => String::from(text.trim_end_matches(' ')),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::{DecodeError, Decoder, Value};
+
+    const SCHEMA: &str = "protocol foo;
+        enum Side : u8 { BUY = 1, SELL = 2 }
+        bitflags Permissions : u16 { READ, WRITE, ADMIN = 128 }
+        enum Kind : u8 { PRICE = 1, TEXT = 2 }
+        struct Price { decimal<i64, -2>: value; ascii[3]: currency; }
+        union Body : Kind { @when(PRICE) Price: price; @when(TEXT) ascii[4]: text; }
+        message Quote [7] {
+            Side: side;
+            Permissions: permissions;
+            i16[2]: levels;
+            Kind: kind;
+            @discriminator(kind) Body: body;
+            2;
+        }";
+
+    fn quote() -> Vec<u8> {
+        let mut buf = vec![7, 0, 2, 0x83, 0];
+        buf.extend_from_slice(&(-2i16).to_le_bytes());
+        buf.extend_from_slice(&300i16.to_le_bytes());
+        buf.push(1);
+        buf.extend_from_slice(&12345i64.to_le_bytes());
+        buf.extend_from_slice(b"USD");
+        buf.extend_from_slice(&[0, 0]);
+        buf
+    }
+
+    #[test]
+    fn test_decode() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let mut buf = quote();
+        buf.push(0xff);
+        let (message, size) = Decoder::new(&protocol).decode(&buf).unwrap();
+
+        assert_eq!(buf.len() - 1, size);
+        assert_eq!("Quote", message.name);
+        assert_eq!(
+            Some(&Value::Enum {
+                name: Some(String::from("SELL")),
+                value: 2
+            }),
+            message.get("side")
+        );
+        assert_eq!(
+            Some(&Value::Flags {
+                names: vec![
+                    String::from("READ"),
+                    String::from("WRITE"),
+                    String::from("ADMIN")
+                ],
+                value: 0x83
+            }),
+            message.get("permissions")
+        );
+        assert_eq!(
+            Some(&Value::Array(vec![Value::Signed(-2), Value::Signed(300)])),
+            message.get("levels")
+        );
+        assert_eq!(
+            "Quote { side: SELL, permissions: READ|WRITE|ADMIN, levels: [-2, 300], kind: PRICE, \
+            body: price: { value: 123.45, currency: \"USD\" } }",
+            message.to_string()
+        );
+    }
+
+    #[test]
+    fn test_big_endian_and_id_size() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [258] { i32: a; u16: b; }",
+        )
+        .unwrap();
+        let buf = [0, 0, 1, 2, 0xff, 0xff, 0xff, 0xfe, 1, 0];
+        let (message, _) = Decoder::new(&protocol)
+            .with_id_size(4)
+            .decode(&buf)
+            .unwrap();
+        assert_eq!(Some(&Value::Signed(-2)), message.get("a"));
+        assert_eq!(Some(&Value::Unsigned(256)), message.get("b"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let decoder = Decoder::new(&protocol);
+        let buf = quote();
+
+        assert_eq!(
+            Err(DecodeError::TooShort {
+                what: String::from("the message id"),
+                needed: 2,
+                available: 1
+            }),
+            decoder.decode(&buf[..1])
+        );
+        assert_eq!(Err(DecodeError::UnknownId(8)), decoder.decode(&[8, 0, 0]));
+        assert_eq!(
+            "message 'Quote' needs 21 bytes but the buffer only has 20",
+            decoder
+                .decode(&buf[..buf.len() - 1])
+                .unwrap_err()
+                .to_string()
+        );
+
+        let mut unknown_variant = buf.clone();
+        unknown_variant[9] = 3;
+        assert_eq!(
+            Err(DecodeError::UnknownVariant {
+                union: String::from("Body"),
+                value: 3
+            }),
+            decoder.decode(&unknown_variant)
+        );
+    }
+
+    #[test]
+    fn test_header_frames() {
+        let protocol = crate::sssc::compile(
+            "protocol foo;
+            header { @length(message) u16: length; u8: version; @id u8: id; }
+            message Cancel [2] { u32: order; }",
+        )
+        .unwrap();
+        let decoder = Decoder::new(&protocol);
+        let frame = [4, 0, 1, 2, 7, 0, 0, 0];
+        let (message, size) = decoder.decode(&frame).unwrap();
+        assert_eq!((String::from("Cancel"), 8), (message.name.clone(), size));
+        assert_eq!(Some(&Value::Unsigned(7)), message.get("order"));
+        assert_eq!(
+            vec![4, 0, 0, 2, 7, 0, 0, 0],
+            crate::runtime::Encoder::new(&protocol)
+                .encode(&message)
+                .unwrap()
+        );
+
+        // a longer frame is skipped over whole
+        let (_, size) = decoder.decode(&[6, 0, 1, 2, 7, 0, 0, 0, 9, 9, 3]).unwrap();
+        assert_eq!(10, size);
+        assert_eq!(
+            Err(DecodeError::InvalidLength {
+                message: String::from("Cancel"),
+                length: 3
+            }),
+            decoder.decode(&[3, 0, 1, 2, 7, 0, 0, 0])
+        );
+        assert_eq!(
+            "the frame of message 'Cancel' needs 10 bytes but the buffer only has 8",
+            decoder
+                .decode(&[6, 0, 1, 2, 7, 0, 0, 0])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(Ok(2), decoder.read_id(&[0, 0, 0, 2]));
+        assert!(decoder.read_id(&[0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,707 @@
+use crate::data_types::array_like::AsciiPadding;
+use crate::data_types::scalar::{ByteSize, Float, Mantissa};
+use crate::data_types::{ArrayLike, EnumKind, FieldType, FieldType_, ScalarType};
+use crate::protocol::{ByteOrder, Layout, Message, Protocol};
+use crate::runtime::{DynamicMessage, Value};
+use std::fmt::{Display, Formatter};
+
+/// `field` is the path of the field the error is about, e.g. `Quote.body.price.value` or
+/// `Quote.levels[1]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    UnknownMessage(String),
+    /// A value was given for a field the layout doesn't have
+    UnknownField {
+        field: String,
+    },
+    MissingField {
+        field: String,
+    },
+    /// A number or string too large for its field
+    Overflow {
+        field: String,
+        value: String,
+    },
+    /// An enum value, flag or union variant the schema doesn't declare
+    UnknownName {
+        field: String,
+        name: String,
+    },
+    /// A value of the wrong kind, e.g. text given for an integer field
+    InvalidValue {
+        field: String,
+        reason: String,
+    },
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeError::UnknownMessage(name) => write!(f, "no message is called '{}'", name),
+            EncodeError::UnknownField { field } => write!(f, "'{}' is not a field", field),
+            EncodeError::MissingField { field } => write!(f, "no value was given for '{}'", field),
+            EncodeError::Overflow { field, value } => {
+                write!(f, "{} does not fit in '{}'", value, field)
+            }
+            EncodeError::UnknownName { field, name } => {
+                write!(f, "'{}' is not declared by the type of '{}'", name, field)
+            }
+            EncodeError::InvalidValue { field, reason } => {
+                write!(f, "invalid value for '{}': {}", field, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+fn invalid(field: &str, reason: impl Into<String>) -> EncodeError {
+    EncodeError::InvalidValue {
+        field: String::from(field),
+        reason: reason.into(),
+    }
+}
+
+fn overflow(field: &str, value: impl Display) -> EncodeError {
+    EncodeError::Overflow {
+        field: String::from(field),
+        value: value.to_string(),
+    }
+}
+
+/// Writes [`DynamicMessage`]s into buffers using the layouts of a resolved protocol, the reverse
+/// of a [`Decoder`](crate::runtime::Decoder).
+///
+/// Values are accepted in the forms the decoder produces as well as the simpler ones a person
+/// would write: enums by name, bitflags as the names of the set flags, decimals, timestamps and
+/// durations as plain integers. The discriminator of a union can be left out as it follows from
+/// the variant. Strings, byte arrays and unions shorter than their field are padded, with the
+/// `@pad` byte of the field for ascii strings and zeros otherwise, as are reserved bytes.
+pub struct Encoder<'p> {
+    protocol: &'p Protocol,
+    id_size: usize,
+}
+
+impl<'p> Encoder<'p> {
+    /// An encoder writing ids of 2 bytes
+    pub fn new(protocol: &'p Protocol) -> Self {
+        Encoder {
+            protocol,
+            id_size: 2,
+        }
+    }
+
+    /// Panics unless `id_size` is 1, 2, 4 or 8
+    pub fn with_id_size(mut self, id_size: usize) -> Self {
+        assert!(
+            matches!(id_size, 1 | 2 | 4 | 8),
+            "ids are 1, 2, 4 or 8 bytes, not {}",
+            id_size
+        );
+        self.id_size = id_size;
+        self
+    }
+
+    pub fn protocol(&self) -> &'p Protocol {
+        self.protocol
+    }
+
+    pub fn id_size(&self) -> usize {
+        self.id_size
+    }
+
+    /// Writes the id of `message` followed by the message, the declared message is found by
+    /// name and its id is the one written. When the protocol declares a `header` the id is
+    /// written to it along with the length, any other fields of the header are zeroed.
+    pub fn encode(&self, message: &DynamicMessage) -> Result<Vec<u8>, EncodeError> {
+        let declared = self.message(message)?;
+        if let Some(header) = &self.protocol.header {
+            let mut buf = vec![0; header.layout.size];
+            let id = header.id();
+            self.write_unsigned(
+                &mut buf[id.offset..id.offset + id.size_bytes()],
+                declared.id as u64,
+            );
+            if let Some(length) = header.length() {
+                self.write_unsigned(
+                    &mut buf[length.offset..length.offset + length.size_bytes()],
+                    header.length_value(declared.layout.size) as u64,
+                );
+            }
+            buf.extend(self.encode_message(message)?);
+            return Ok(buf);
+        }
+        let mut buf = vec![0; self.id_size];
+        let id = declared.id as u64;
+        if self.id_size < 8 && id >> (self.id_size * 8) != 0 {
+            return Err(overflow("the message id", id));
+        }
+        self.write_unsigned(&mut buf, id);
+        buf.extend(self.encode_message(message)?);
+        Ok(buf)
+    }
+
+    /// Writes `message` without an id
+    pub fn encode_message(&self, message: &DynamicMessage) -> Result<Vec<u8>, EncodeError> {
+        let declared = self.message(message)?;
+        let mut buf = vec![0; declared.layout.size];
+        self.write_fields(&declared.name, &declared.layout, &message.fields, &mut buf)?;
+        Ok(buf)
+    }
+
+    fn message(&self, message: &DynamicMessage) -> Result<&'p Message, EncodeError> {
+        self.protocol
+            .message(&message.name)
+            .ok_or_else(|| EncodeError::UnknownMessage(message.name.clone()))
+    }
+
+    /// `bytes` holds exactly the bytes of `layout` and starts out zeroed
+    fn write_fields(
+        &self,
+        path: &str,
+        layout: &Layout,
+        values: &[(String, Value)],
+        bytes: &mut [u8],
+    ) -> Result<(), EncodeError> {
+        if let Some((name, _)) = values.iter().find(|(n, _)| layout.field(n).is_none()) {
+            return Err(EncodeError::UnknownField {
+                field: format!("{}.{}", path, name),
+            });
+        }
+
+        // the variant given for a union decides the value of its discriminator
+        let mut selectors: Vec<(&str, u64, String)> = vec![];
+        for f in layout.fields() {
+            let FieldType_::Union(ut) = &f.field_type else {
+                continue;
+            };
+            if let Some(Value::Union { variant, .. }) = find(values, &f.name) {
+                let u = self
+                    .protocol
+                    .union(&ut.name)
+                    .expect("resolved protocols declare every union they use");
+                if let Some(v) = u.variants.iter().find(|v| &v.name == variant) {
+                    selectors.push((&ut.discriminator, v.value, format!("{}.{}", path, f.name)));
+                }
+            }
+        }
+
+        for f in layout.fields() {
+            let field = format!("{}.{}", path, f.name);
+            let field_bytes = &mut bytes[f.offset..f.offset + f.size_bytes()];
+            let selector = selectors.iter().find(|(d, _, _)| *d == f.name);
+            match (find(values, &f.name), selector) {
+                (Some(value), None) => {
+                    self.write_value(&field, &f.field_type, value, field_bytes)?
+                }
+                (Some(value), Some((_, expected, union))) => {
+                    self.write_value(&field, &f.field_type, value, field_bytes)?;
+                    if self.read_unsigned(field_bytes) != *expected {
+                        return Err(invalid(
+                            union,
+                            format!("the variant given doesn't match the value of '{}'", field),
+                        ));
+                    }
+                }
+                (None, Some((_, expected, _))) => self.write_unsigned(field_bytes, *expected),
+                (None, None) => return Err(EncodeError::MissingField { field }),
+            }
+        }
+        Ok(())
+    }
+
+    fn write_value(
+        &self,
+        field: &str,
+        field_type: &FieldType_,
+        value: &Value,
+        bytes: &mut [u8],
+    ) -> Result<(), EncodeError> {
+        match (field_type, value) {
+            (FieldType_::Scalar(s), _) => self.write_scalar(field, s, value, bytes),
+            (
+                FieldType_::Vector(ArrayLike::FixedArray { scalar, length }),
+                Value::Array(values),
+            ) => {
+                if values.len() > *length {
+                    return Err(overflow(field, format!("{} elements", values.len())));
+                }
+                if values.len() < *length {
+                    return Err(invalid(
+                        field,
+                        format!("expected {} elements, not {}", length, values.len()),
+                    ));
+                }
+                for (i, (value, element)) in values
+                    .iter()
+                    .zip(bytes.chunks_mut(scalar.size_bytes()))
+                    .enumerate()
+                {
+                    self.write_scalar(&format!("{}[{}]", field, i), scalar, value, element)?;
+                }
+                Ok(())
+            }
+            (FieldType_::Vector(ArrayLike::AsciiString { padding, .. }), Value::Ascii(text)) => {
+                write_ascii(field, text, *padding, bytes)
+            }
+            (
+                FieldType_::Vector(ArrayLike::Bytes { .. }) | FieldType_::Padding(_),
+                Value::Bytes(given),
+            ) => {
+                if given.len() > bytes.len() {
+                    return Err(overflow(field, format!("{} bytes", given.len())));
+                }
+                bytes[..given.len()].copy_from_slice(given);
+                Ok(())
+            }
+            (FieldType_::Struct(st), Value::Struct(fields)) => {
+                let s = self
+                    .protocol
+                    .structure(&st.name)
+                    .expect("resolved protocols declare every struct they use");
+                self.write_fields(field, &s.layout, fields, bytes)
+            }
+            (FieldType_::Enum(et), _) => {
+                let e = self
+                    .protocol
+                    .enumeration(&et.name)
+                    .expect("resolved protocols declare every enum they use");
+                let named = |name: &String| {
+                    e.variant(name)
+                        .map(|v| v.value)
+                        .ok_or_else(|| EncodeError::UnknownName {
+                            field: String::from(field),
+                            name: name.clone(),
+                        })
+                };
+                let raw = match (et.kind, value) {
+                    (EnumKind::Enum, Value::Enum { name: Some(n), .. }) => named(n)?,
+                    (EnumKind::Enum, Value::Enum { name: None, value }) => *value,
+                    (EnumKind::Bitflags, Value::Flags { names, value }) => names
+                        .iter()
+                        .try_fold(*value, |bits, name| Ok(bits | named(name)?))?,
+                    (_, Value::Unsigned(v)) => *v,
+                    (EnumKind::Enum, _) => {
+                        return Err(invalid(field, "expected a value of the enum"))
+                    }
+                    (EnumKind::Bitflags, _) => {
+                        return Err(invalid(field, "expected a set of flags"))
+                    }
+                };
+                let raw =
+                    fit_unsigned(raw as i128, bytes.len()).ok_or_else(|| overflow(field, raw))?;
+                self.write_unsigned(bytes, raw);
+                Ok(())
+            }
+            (FieldType_::Union(ut), Value::Union { variant, value }) => {
+                let u = self
+                    .protocol
+                    .union(&ut.name)
+                    .expect("resolved protocols declare every union they use");
+                let v = u
+                    .variants
+                    .iter()
+                    .find(|v| &v.name == variant)
+                    .ok_or_else(|| EncodeError::UnknownName {
+                        field: String::from(field),
+                        name: variant.clone(),
+                    })?;
+                let size = v.field_type.size_bytes();
+                self.write_value(
+                    &format!("{}.{}", field, v.name),
+                    &v.field_type,
+                    value,
+                    &mut bytes[..size],
+                )
+            }
+            (t, _) => Err(invalid(field, format!("expected a value of type {}", t))),
+        }
+    }
+
+    fn write_scalar(
+        &self,
+        field: &str,
+        scalar: &ScalarType,
+        value: &Value,
+        bytes: &mut [u8],
+    ) -> Result<(), EncodeError> {
+        let size = bytes.len();
+        let raw = match (scalar, value) {
+            (ScalarType::Unsigned(_), _) => {
+                let v = integer(field, value)?;
+                fit_unsigned(v, size).ok_or_else(|| overflow(field, v))?
+            }
+            (ScalarType::Signed(_), _) => {
+                let v = integer(field, value)?;
+                fit_signed(v, size).ok_or_else(|| overflow(field, v))?
+            }
+            (ScalarType::Float(f), _) => {
+                let v = match value {
+                    Value::Float(v) => *v,
+                    Value::Unsigned(v) => *v as f64,
+                    Value::Signed(v) => *v as f64,
+                    _ => return Err(invalid(field, "expected a number")),
+                };
+                match f {
+                    Float::F32 if v.is_finite() && (v as f32).is_infinite() => {
+                        return Err(overflow(field, v))
+                    }
+                    Float::F32 => (v as f32).to_bits() as u64,
+                    Float::F64 => v.to_bits(),
+                }
+            }
+            (ScalarType::ByteSized(ByteSize::Byte), Value::Byte(b)) => *b as u64,
+            (ScalarType::ByteSized(ByteSize::Byte), _) => {
+                let v = integer(field, value)?;
+                fit_unsigned(v, size).ok_or_else(|| overflow(field, v))?
+            }
+            (ScalarType::ByteSized(_), Value::Ascii(text)) => {
+                return write_ascii(field, text, AsciiPadding::Nul, bytes)
+            }
+            (ScalarType::ByteSized(_), _) => return Err(invalid(field, "expected a character")),
+            (ScalarType::Decimal(d), _) => {
+                let mantissa = match *value {
+                    Value::Decimal { mantissa, exponent } => {
+                        rescale(field, mantissa, exponent, d.exponent)?
+                    }
+                    Value::Unsigned(_) | Value::Signed(_) => {
+                        rescale(field, integer(field, value)?, 0, d.exponent)?
+                    }
+                    _ => return Err(invalid(field, "expected a decimal")),
+                };
+                let fits = match d.mantissa {
+                    Mantissa::Unsigned(_) => fit_unsigned(mantissa, size),
+                    Mantissa::Signed(_) => fit_signed(mantissa, size),
+                };
+                fits.ok_or_else(|| overflow(field, value))?
+            }
+            (ScalarType::Timestamp(t), _) => {
+                let v = match value {
+                    Value::Timestamp { value, unit } if *unit == t.unit => *value as i128,
+                    Value::Unsigned(_) | Value::Signed(_) => integer(field, value)?,
+                    _ => return Err(invalid(field, format!("expected a count of {}", t.unit))),
+                };
+                fit_unsigned(v, size).ok_or_else(|| overflow(field, v))?
+            }
+            (ScalarType::Duration(d), _) => {
+                let v = match value {
+                    Value::Duration { value, unit } if *unit == d.unit => *value as i128,
+                    Value::Unsigned(_) | Value::Signed(_) => integer(field, value)?,
+                    _ => return Err(invalid(field, format!("expected a count of {}", d.unit))),
+                };
+                fit_signed(v, size).ok_or_else(|| overflow(field, v))?
+            }
+        };
+        self.write_unsigned(bytes, raw);
+        Ok(())
+    }
+
+    /// The lowest `bytes.len()` bytes of `value` in the protocol's byte order
+    fn write_unsigned(&self, bytes: &mut [u8], value: u64) {
+        let size = bytes.len();
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let shift = match self.protocol.byte_order {
+                ByteOrder::Little => i,
+                ByteOrder::Big => size - 1 - i,
+            };
+            *byte = (value >> (shift * 8)) as u8;
+        }
+    }
+
+    fn read_unsigned(&self, bytes: &[u8]) -> u64 {
+        let fold = |value: u64, byte: &u8| (value << 8) | *byte as u64;
+        match self.protocol.byte_order {
+            ByteOrder::Little => bytes.iter().rev().fold(0, fold),
+            ByteOrder::Big => bytes.iter().fold(0, fold),
+        }
+    }
+}
+
+fn find<'v>(values: &'v [(String, Value)], name: &str) -> Option<&'v Value> {
+    values.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+fn integer(field: &str, value: &Value) -> Result<i128, EncodeError> {
+    match value {
+        Value::Unsigned(v) => Ok(*v as i128),
+        Value::Signed(v) => Ok(*v as i128),
+        Value::Byte(v) => Ok(*v as i128),
+        _ => Err(invalid(field, "expected an integer")),
+    }
+}
+
+/// `value` if it is an unsigned integer of `size` bytes
+fn fit_unsigned(value: i128, size: usize) -> Option<u64> {
+    let max = u64::MAX >> (64 - size * 8);
+    (0..=max as i128).contains(&value).then_some(value as u64)
+}
+
+/// `value` as the bits of a signed integer of `size` bytes
+fn fit_signed(value: i128, size: usize) -> Option<u64> {
+    let max = i64::MAX >> (64 - size * 8);
+    (-(max as i128) - 1..=max as i128)
+        .contains(&value)
+        .then_some(value as i64 as u64)
+}
+
+/// The mantissa of `mantissa * 10^exponent` for the exponent `to`, which must not lose digits
+fn rescale(field: &str, mantissa: i128, exponent: i8, to: i8) -> Result<i128, EncodeError> {
+    let too_large = || overflow(field, Value::Decimal { mantissa, exponent });
+    let shift = exponent as i32 - to as i32;
+    let scale = 10i128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or_else(too_large)?;
+    if shift >= 0 {
+        return mantissa.checked_mul(scale).ok_or_else(too_large);
+    }
+    if mantissa % scale != 0 {
+        return Err(invalid(
+            field,
+            format!(
+                "{} has more than {} decimal places",
+                Value::Decimal { mantissa, exponent },
+                -to
+            ),
+        ));
+    }
+    Ok(mantissa / scale)
+}
+
+fn write_ascii(
+    field: &str,
+    text: &str,
+    padding: AsciiPadding,
+    bytes: &mut [u8],
+) -> Result<(), EncodeError> {
+    if !text.is_ascii() {
+        return Err(invalid(field, format!("{:?} is not ascii", text)));
+    }
+    if text.len() > bytes.len() {
+        return Err(overflow(field, format!("{:?}", text)));
+    }
+    bytes.fill(padding.byte());
+    bytes[..text.len()].copy_from_slice(text.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::{Decoder, DynamicMessage, EncodeError, Encoder, Value};
+
+    const SCHEMA: &str = "protocol foo;
+        enum Side : u8 { BUY = 1, SELL = 2 }
+        bitflags Permissions : u16 { READ, WRITE, ADMIN = 128 }
+        enum Kind : u8 { PRICE = 1, TEXT = 2 }
+        struct Price { decimal<i64, -2>: value; @pad(space) ascii[3]: currency; }
+        union Body : Kind { @when(PRICE) Price: price; @when(TEXT) ascii[4]: text; }
+        message Quote [7] {
+            Side: side;
+            Permissions: permissions;
+            i16[2]: levels;
+            Kind: kind;
+            @discriminator(kind) Body: body;
+            2;
+        }";
+
+    fn text(value: &str) -> Value {
+        Value::Ascii(String::from(value))
+    }
+
+    fn quote(fields: Vec<(&str, Value)>) -> DynamicMessage {
+        DynamicMessage {
+            name: String::from("Quote"),
+            id: 7,
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (String::from(name), value))
+                .collect(),
+        }
+    }
+
+    fn price(currency: &str) -> Value {
+        Value::Union {
+            variant: String::from("price"),
+            value: Box::new(Value::Struct(vec![
+                (
+                    String::from("value"),
+                    Value::Decimal {
+                        mantissa: 1234,
+                        exponent: -1,
+                    },
+                ),
+                (String::from("currency"), text(currency)),
+            ])),
+        }
+    }
+
+    fn names(names: &[&str]) -> Value {
+        Value::Flags {
+            names: names.iter().map(|n| String::from(*n)).collect(),
+            value: 0,
+        }
+    }
+
+    fn enum_name(name: &str) -> Value {
+        Value::Enum {
+            name: Some(String::from(name)),
+            value: 0,
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let message = quote(vec![
+            ("side", enum_name("SELL")),
+            ("permissions", names(&["READ", "ADMIN"])),
+            (
+                "levels",
+                Value::Array(vec![Value::Signed(-2), Value::Unsigned(300)]),
+            ),
+            ("body", price("EU")),
+        ]);
+        let buf = Encoder::new(&protocol).encode(&message).unwrap();
+
+        let mut expected = vec![7, 0, 2, 0x81, 0];
+        expected.extend_from_slice(&(-2i16).to_le_bytes());
+        expected.extend_from_slice(&300i16.to_le_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&12340i64.to_le_bytes());
+        expected.extend_from_slice(b"EU ");
+        expected.extend_from_slice(&[0, 0]);
+        assert_eq!(expected, buf);
+
+        let (decoded, _) = Decoder::new(&protocol).decode(&buf).unwrap();
+        assert_eq!(
+            Some(&text("EU")),
+            decoded.get("body").and_then(|b| match b {
+                Value::Union { value, .. } => value.get("currency"),
+                _ => None,
+            })
+        );
+        assert_eq!(buf, Encoder::new(&protocol).encode(&decoded).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let encoder = Encoder::new(&protocol);
+        let fields = || {
+            vec![
+                ("side", enum_name("BUY")),
+                ("permissions", Value::Unsigned(3)),
+                (
+                    "levels",
+                    Value::Array(vec![Value::Signed(1), Value::Signed(2)]),
+                ),
+                ("body", price("USD")),
+            ]
+        };
+        let with = |name: &str, value: Value| {
+            let mut fields = fields();
+            fields.retain(|(n, _)| *n != name);
+            fields.push((name, value));
+            encoder.encode(&quote(fields)).map(|_| ())
+        };
+
+        assert_eq!(Ok(()), encoder.encode(&quote(fields())).map(|_| ()));
+        assert_eq!(
+            Err(EncodeError::UnknownField {
+                field: String::from("Quote.size")
+            }),
+            with("size", Value::Unsigned(1))
+        );
+        assert_eq!(
+            Err(EncodeError::Overflow {
+                field: String::from("Quote.levels[1]"),
+                value: String::from("40000")
+            }),
+            with(
+                "levels",
+                Value::Array(vec![Value::Signed(1), Value::Unsigned(40000)])
+            )
+        );
+        assert_eq!(
+            "\"EURO\" does not fit in 'Quote.body.price.currency'",
+            with("body", price("EURO")).unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(EncodeError::UnknownName {
+                field: String::from("Quote.permissions"),
+                name: String::from("DELETE")
+            }),
+            with("permissions", names(&["READ", "DELETE"]))
+        );
+        assert_eq!(
+            Err(EncodeError::UnknownName {
+                field: String::from("Quote.side"),
+                name: String::from("HOLD")
+            }),
+            with("side", enum_name("HOLD"))
+        );
+        assert!(matches!(
+            with("kind", enum_name("TEXT")),
+            Err(EncodeError::InvalidValue { field, .. }) if field == "Quote.body"
+        ));
+        assert_eq!(Ok(()), with("kind", enum_name("PRICE")));
+        assert!(matches!(
+            with("side", text("BUY")),
+            Err(EncodeError::InvalidValue { .. })
+        ));
+
+        let mut missing = fields();
+        missing.remove(0);
+        assert_eq!(
+            Err(EncodeError::MissingField {
+                field: String::from("Quote.side")
+            }),
+            encoder.encode(&quote(missing)).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_decimals_and_ids() {
+        let protocol = crate::sssc::compile(
+            "protocol foo [byte_order = big]; message M [300] { decimal<u16, -2>: price; }",
+        )
+        .unwrap();
+        let message = |value: Value| DynamicMessage {
+            name: String::from("M"),
+            id: 300,
+            fields: vec![(String::from("price"), value)],
+        };
+        let decimal = |mantissa, exponent| Value::Decimal { mantissa, exponent };
+        let encoder = Encoder::new(&protocol);
+
+        assert_eq!(
+            Ok(vec![1, 44, 0x04, 0xd2]),
+            encoder.encode(&message(decimal(1234, -2)))
+        );
+        assert_eq!(
+            Ok(vec![1, 44, 0x01, 0xf4]),
+            encoder.encode(&message(Value::Unsigned(5)))
+        );
+        assert!(matches!(
+            encoder.encode(&message(decimal(1, -3))),
+            Err(EncodeError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            "-1 does not fit in 'M.price'",
+            encoder
+                .encode(&message(decimal(-1, 0)))
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(matches!(
+            encoder.encode(&message(decimal(1000, 0))),
+            Err(EncodeError::Overflow { .. })
+        ));
+        assert_eq!(
+            Err(EncodeError::Overflow {
+                field: String::from("the message id"),
+                value: String::from("300")
+            }),
+            encoder.with_id_size(1).encode(&message(decimal(0, 0)))
+        );
+    }
+}
@@ -0,0 +1,440 @@
+//! Messages as JSON, an object whose only key is the name of the message, e.g.
+//!
+//! ```json
+//! {"Quote": {"side": "SELL", "permissions": ["READ", "WRITE"], "price": "123.45",
+//!            "symbol": "AAPL"}}
+//! ```
+//!
+//! Enums are written by name, bitflags as the names of the set flags followed by the integer
+//! value of any set bits no flag is declared for, decimals as strings so no digits are lost to a
+//! float, timestamps and durations as integer counts of their unit, byte arrays as hex and unions
+//! as an object whose only key is the name of the active variant. Reading also accepts decimals
+//! as numbers, enums and bitflags as their integer value and byte arrays as arrays of integers.
+
+use crate::data_types::scalar::ByteSize;
+use crate::data_types::{ArrayLike, EnumKind, FieldType_, ScalarType};
+use crate::protocol::{Layout, Protocol};
+use crate::runtime::{DynamicMessage, EncodeError, Value};
+use serde_json::{Map, Value as Json};
+
+/// `message` as JSON, a message the protocol doesn't declare is written from its values alone
+/// which can't tell the bits of a bitflags value no flag is declared for
+pub fn to_json(protocol: &Protocol, message: &DynamicMessage) -> Json {
+    let layout = protocol.message(&message.name).map(|m| &m.layout);
+    let mut object = Map::new();
+    object.insert(
+        message.name.clone(),
+        fields_to_json(protocol, layout, &message.fields),
+    );
+    Json::Object(object)
+}
+
+/// Reads a message from JSON written as described by the [module](self), each value is checked
+/// against the type of its field as far as JSON can tell them apart and the rest is left to the
+/// [`Encoder`](crate::runtime::Encoder)
+pub fn from_json(protocol: &Protocol, json: &Json) -> Result<DynamicMessage, EncodeError> {
+    let (name, fields) = match json.as_object() {
+        Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+        _ => {
+            return Err(EncodeError::InvalidValue {
+                field: String::from("the message"),
+                reason: String::from(
+                    "expected an object with the name of the message as its only key",
+                ),
+            })
+        }
+    };
+    let message = protocol
+        .message(name)
+        .ok_or_else(|| EncodeError::UnknownMessage(name.clone()))?;
+    Ok(DynamicMessage {
+        name: name.clone(),
+        id: message.id,
+        fields: fields_from_json(protocol, name, &message.layout, fields)?,
+    })
+}
+
+fn fields_to_json(
+    protocol: &Protocol,
+    layout: Option<&Layout>,
+    fields: &[(String, Value)],
+) -> Json {
+    let mut object = Map::new();
+    for (name, value) in fields {
+        let field_type = layout.and_then(|l| l.field(name)).map(|f| &f.field_type);
+        object.insert(name.clone(), value_to_json(protocol, field_type, value));
+    }
+    Json::Object(object)
+}
+
+fn value_to_json(protocol: &Protocol, field_type: Option<&FieldType_>, value: &Value) -> Json {
+    match value {
+        Value::Unsigned(v) => Json::from(*v),
+        Value::Signed(v) => Json::from(*v),
+        Value::Float(v) => Json::from(*v),
+        Value::Decimal { .. } => Json::from(value.to_string()),
+        Value::Timestamp { value, .. } => Json::from(*value),
+        Value::Duration { value, .. } => Json::from(*value),
+        Value::Byte(v) => Json::from(*v),
+        Value::Ascii(text) => Json::from(text.as_str()),
+        Value::Bytes(bytes) => Json::from(
+            bytes
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>(),
+        ),
+        Value::Enum {
+            name: Some(name), ..
+        } => Json::from(name.as_str()),
+        Value::Enum { name: None, value } => Json::from(*value),
+        Value::Flags { names, value } => {
+            let declared = match field_type {
+                Some(FieldType_::Enum(et)) => protocol
+                    .enumeration(&et.name)
+                    .map(|e| e.values.iter().fold(0, |bits, flag| bits | flag.value)),
+                _ => None,
+            };
+            let mut flags: Vec<Json> = names.iter().map(|n| Json::from(n.as_str())).collect();
+            match declared {
+                Some(declared) if value & !declared != 0 => {
+                    flags.push(Json::from(value & !declared))
+                }
+                None if names.is_empty() && *value != 0 => flags.push(Json::from(*value)),
+                _ => {}
+            }
+            Json::Array(flags)
+        }
+        Value::Array(values) => Json::Array(
+            values
+                .iter()
+                .map(|v| value_to_json(protocol, None, v))
+                .collect(),
+        ),
+        Value::Struct(fields) => {
+            let layout = match field_type {
+                Some(FieldType_::Struct(st)) => protocol.structure(&st.name).map(|s| &s.layout),
+                _ => None,
+            };
+            fields_to_json(protocol, layout, fields)
+        }
+        Value::Union { variant, value } => {
+            let variant_type = match field_type {
+                Some(FieldType_::Union(ut)) => protocol
+                    .union(&ut.name)
+                    .and_then(|u| u.variants.iter().find(|v| &v.name == variant))
+                    .map(|v| &v.field_type),
+                _ => None,
+            };
+            let mut object = Map::new();
+            object.insert(
+                variant.clone(),
+                value_to_json(protocol, variant_type, value),
+            );
+            Json::Object(object)
+        }
+    }
+}
+
+fn invalid(field: &str, reason: &str) -> EncodeError {
+    EncodeError::InvalidValue {
+        field: String::from(field),
+        reason: String::from(reason),
+    }
+}
+
+fn fields_from_json(
+    protocol: &Protocol,
+    path: &str,
+    layout: &Layout,
+    json: &Json,
+) -> Result<Vec<(String, Value)>, EncodeError> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| invalid(path, "expected an object"))?;
+    if let Some(name) = object.keys().find(|name| layout.field(name).is_none()) {
+        return Err(EncodeError::UnknownField {
+            field: format!("{}.{}", path, name),
+        });
+    }
+    layout
+        .fields()
+        .filter_map(|f| object.get(&f.name).map(|json| (f, json)))
+        .map(|(f, json)| {
+            let field = format!("{}.{}", path, f.name);
+            Ok((
+                f.name.clone(),
+                value_from_json(protocol, &field, &f.field_type, json)?,
+            ))
+        })
+        .collect()
+}
+
+fn value_from_json(
+    protocol: &Protocol,
+    field: &str,
+    field_type: &FieldType_,
+    json: &Json,
+) -> Result<Value, EncodeError> {
+    match field_type {
+        FieldType_::Scalar(s) => scalar_from_json(field, s, json),
+        FieldType_::Vector(ArrayLike::FixedArray { scalar, .. }) => {
+            let values = json
+                .as_array()
+                .ok_or_else(|| invalid(field, "expected an array"))?;
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(i, json)| scalar_from_json(&format!("{}[{}]", field, i), scalar, json))
+                .collect::<Result<_, _>>()?;
+            Ok(Value::Array(values))
+        }
+        FieldType_::Vector(ArrayLike::AsciiString { .. }) => json
+            .as_str()
+            .map(|text| Value::Ascii(String::from(text)))
+            .ok_or_else(|| invalid(field, "expected a string")),
+        FieldType_::Vector(ArrayLike::Bytes { .. }) | FieldType_::Padding(_) => {
+            let bytes = match json {
+                Json::String(text) => from_hex(text),
+                Json::Array(values) => values
+                    .iter()
+                    .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+                    .collect(),
+                _ => None,
+            };
+            bytes
+                .map(Value::Bytes)
+                .ok_or_else(|| invalid(field, "expected a hex string or an array of bytes"))
+        }
+        FieldType_::Struct(st) => {
+            let s = protocol
+                .structure(&st.name)
+                .expect("resolved protocols declare every struct they use");
+            Ok(Value::Struct(fields_from_json(
+                protocol, field, &s.layout, json,
+            )?))
+        }
+        FieldType_::Enum(et) => match (et.kind, json) {
+            (_, Json::Number(_)) => integer(field, json),
+            (EnumKind::Enum, Json::String(name)) => Ok(Value::Enum {
+                name: Some(name.clone()),
+                value: 0,
+            }),
+            (EnumKind::Enum, _) => Err(invalid(field, "expected the name of a value")),
+            (EnumKind::Bitflags, Json::Array(flags)) => {
+                let mut names = vec![];
+                let mut value = 0;
+                for flag in flags {
+                    match flag {
+                        Json::String(name) => names.push(name.clone()),
+                        _ => match flag.as_u64() {
+                            Some(bits) => value |= bits,
+                            None => return Err(invalid(field, "expected flag names")),
+                        },
+                    }
+                }
+                Ok(Value::Flags { names, value })
+            }
+            (EnumKind::Bitflags, _) => Err(invalid(field, "expected an array of flag names")),
+        },
+        FieldType_::Union(ut) => {
+            let (variant, json) = match json.as_object() {
+                Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+                _ => {
+                    return Err(invalid(
+                        field,
+                        "expected an object with the name of the variant as its only key",
+                    ))
+                }
+            };
+            let u = protocol
+                .union(&ut.name)
+                .expect("resolved protocols declare every union they use");
+            let v = u
+                .variants
+                .iter()
+                .find(|v| &v.name == variant)
+                .ok_or_else(|| EncodeError::UnknownName {
+                    field: String::from(field),
+                    name: variant.clone(),
+                })?;
+            Ok(Value::Union {
+                variant: variant.clone(),
+                value: Box::new(value_from_json(
+                    protocol,
+                    &format!("{}.{}", field, variant),
+                    &v.field_type,
+                    json,
+                )?),
+            })
+        }
+    }
+}
+
+fn scalar_from_json(field: &str, scalar: &ScalarType, json: &Json) -> Result<Value, EncodeError> {
+    match scalar {
+        ScalarType::Float(_) => json
+            .as_f64()
+            .map(Value::Float)
+            .ok_or_else(|| invalid(field, "expected a number")),
+        ScalarType::ByteSized(ByteSize::Char | ByteSize::Ascii) => json
+            .as_str()
+            .map(|text| Value::Ascii(String::from(text)))
+            .ok_or_else(|| invalid(field, "expected a string")),
+        ScalarType::Decimal(_) => {
+            let text = match json {
+                Json::Number(n) => n.to_string(),
+                Json::String(text) => text.clone(),
+                _ => return Err(invalid(field, "expected a decimal")),
+            };
+            parse_decimal(&text)
+                .map(|(mantissa, exponent)| Value::Decimal { mantissa, exponent })
+                .ok_or_else(|| invalid(field, "expected a decimal"))
+        }
+        _ => integer(field, json),
+    }
+}
+
+fn integer(field: &str, json: &Json) -> Result<Value, EncodeError> {
+    json.as_u64()
+        .map(Value::Unsigned)
+        .or_else(|| json.as_i64().map(Value::Signed))
+        .ok_or_else(|| invalid(field, "expected an integer"))
+}
+
+/// Pairs of hex digits, optionally starting with `0x`
+pub(crate) fn from_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    let pairs = digits.as_bytes().chunks_exact(2);
+    // `from_str_radix` alone would also take a sign, as in `+f`
+    if !digits.bytes().all(|c| c.is_ascii_hexdigit()) || !pairs.remainder().is_empty() {
+        return None;
+    }
+    pairs
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// A decimal number such as `-123.45` or `1.5e3` as its mantissa and exponent, without going
+/// through a float
+fn parse_decimal(text: &str) -> Option<(i128, i8)> {
+    let (number, exponent) = match text.find(['e', 'E']) {
+        Some(i) => (&text[..i], text[i + 1..].parse::<i32>().ok()?),
+        None => (text, 0),
+    };
+    let (negative, digits) = match number.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, number),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit());
+    if !all_digits || whole.len() + fraction.len() == 0 {
+        return None;
+    }
+    let mantissa: i128 = format!("{}{}", whole, fraction).parse().ok()?;
+    let exponent = i8::try_from(exponent - fraction.len() as i32).ok()?;
+    Some((if negative { -mantissa } else { mantissa }, exponent))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::json::{from_hex, from_json, parse_decimal, to_json};
+    use crate::runtime::{Decoder, EncodeError, Encoder};
+    use serde_json::json;
+
+    const SCHEMA: &str = "protocol foo;
+        enum Side : u8 { BUY = 1, SELL = 2 }
+        bitflags Permissions : u8 { READ, WRITE }
+        struct Price { decimal<i64, -2>: value; @pad(space) ascii[3]: currency; }
+        message Quote [7] {
+            Side: side;
+            Permissions: permissions;
+            Price: price;
+            byte[2]: checksum;
+            f32: ratio;
+        }";
+
+    #[test]
+    fn test_round_trip() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let json = json!({"Quote": {
+            "side": "SELL",
+            "permissions": ["WRITE", 64],
+            "price": {"value": 12.5, "currency": "EU"},
+            "checksum": "cafe",
+            "ratio": 0.5,
+        }});
+        let message = from_json(&protocol, &json).unwrap();
+        let buf = Encoder::new(&protocol).encode(&message).unwrap();
+        assert_eq!(
+            vec![
+                7, 0, 2, 0x42, 0xe2, 4, 0, 0, 0, 0, 0, 0, b'E', b'U', b' ', 0xca, 0xfe, 0, 0, 0,
+                0x3f
+            ],
+            buf
+        );
+
+        let (decoded, _) = Decoder::new(&protocol).decode(&buf).unwrap();
+        assert_eq!(
+            json!({"Quote": {
+                "side": "SELL",
+                "permissions": ["WRITE", 64],
+                "price": {"value": "12.50", "currency": "EU"},
+                "checksum": "cafe",
+                "ratio": 0.5,
+            }}),
+            to_json(&protocol, &decoded)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        assert_eq!(
+            Err(EncodeError::UnknownMessage(String::from("Trade"))),
+            from_json(&protocol, &json!({"Trade": {}}))
+        );
+        assert_eq!(
+            Err(EncodeError::UnknownField {
+                field: String::from("Quote.price.amount")
+            }),
+            from_json(&protocol, &json!({"Quote": {"price": {"amount": 1}}}))
+        );
+        for invalid in [
+            json!({"Quote": {"side": true}}),
+            json!({"Quote": {"permissions": "READ"}}),
+            json!({"Quote": {"checksum": "xyz"}}),
+            json!({"Quote": {"price": {"value": "1.2.3"}}}),
+            json!({"Quote": {}, "Other": {}}),
+        ] {
+            assert!(
+                matches!(
+                    from_json(&protocol, &invalid),
+                    Err(EncodeError::InvalidValue { .. })
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(Some((12345, -2)), parse_decimal("123.45"));
+        assert_eq!(Some((-5, -3)), parse_decimal("-0.005"));
+        assert_eq!(Some((15, 2)), parse_decimal("1.5e3"));
+        assert_eq!(Some((7, 0)), parse_decimal("7"));
+        assert_eq!(None, parse_decimal("."));
+        assert_eq!(None, parse_decimal("1,5"));
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(Some(vec![0x0f, 0xa0]), from_hex("0x0fA0"));
+        assert_eq!(None, from_hex("+f"));
+        assert_eq!(None, from_hex("0f0"));
+        assert_eq!(None, from_hex("zz"));
+    }
+}
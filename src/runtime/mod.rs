@@ -0,0 +1,12 @@
+//! Reading and writing messages at runtime from nothing but the resolved protocol, for tools such
+//! as log viewers which have to handle any protocol rather than the one they were generated for.
+
+pub use decode::{DecodeError, Decoder};
+pub use encode::{EncodeError, Encoder};
+pub use value::{DynamicMessage, Value};
+
+mod decode;
+mod encode;
+#[cfg(feature = "serde")]
+pub mod json;
+mod value;
@@ -0,0 +1,184 @@
+use crate::data_types::scalar::TimeUnit;
+use std::fmt::{Display, Formatter};
+
+/// A message read from a buffer without generated code, see [`Decoder`](crate::runtime::Decoder)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicMessage {
+    pub name: String,
+    pub id: usize,
+    /// Every field of the message in order of offset, padding is left out
+    pub fields: Vec<(String, Value)>,
+}
+
+impl DynamicMessage {
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        field(&self.fields, name)
+    }
+}
+
+/// The value of a single field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    /// The value is `mantissa * 10^exponent`
+    Decimal {
+        mantissa: i128,
+        exponent: i8,
+    },
+    /// Counted in `unit` since the unix epoch
+    Timestamp {
+        value: u64,
+        unit: TimeUnit,
+    },
+    Duration {
+        value: i64,
+        unit: TimeUnit,
+    },
+    Byte(u8),
+    /// An `ascii` string or character, without the NUL bytes padding it
+    Ascii(String),
+    Bytes(Vec<u8>),
+    /// `name` is `None` when the value isn't one declared by the enum
+    Enum {
+        name: Option<String>,
+        value: u64,
+    },
+    /// The names of the flags which are set, bits belonging to no flag only show in `value`
+    Flags {
+        names: Vec<String>,
+        value: u64,
+    },
+    Array(Vec<Value>),
+    Struct(Vec<(String, Value)>),
+    /// The variant selected by the union's discriminator
+    Union {
+        variant: String,
+        value: Box<Value>,
+    },
+}
+
+impl Value {
+    /// The field called `name` of a struct
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) => field(fields, name),
+            _ => None,
+        }
+    }
+}
+
+fn field<'v>(fields: &'v [(String, Value)], name: &str) -> Option<&'v Value> {
+    fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+/// Writes a decimal without going through a float, e.g. `12345` with an exponent of `-2` is
+/// `123.45`
+fn write_decimal(f: &mut Formatter<'_>, mantissa: i128, exponent: i8) -> std::fmt::Result {
+    let digits = mantissa.unsigned_abs().to_string();
+    let sign = if mantissa < 0 { "-" } else { "" };
+    if exponent >= 0 {
+        return write!(f, "{}{}{}", sign, digits, "0".repeat(exponent as usize));
+    }
+    let scale = exponent.unsigned_abs() as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    write!(f, "{}{}.{}", sign, whole, fraction)
+}
+
+fn write_fields(f: &mut Formatter<'_>, fields: &[(String, Value)]) -> std::fmt::Result {
+    write!(f, "{{ ")?;
+    for (i, (name, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}: {}", name, value)?;
+    }
+    write!(f, " }}")
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Unsigned(v) => write!(f, "{}", v),
+            Value::Signed(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Decimal { mantissa, exponent } => write_decimal(f, *mantissa, *exponent),
+            Value::Timestamp { value, unit } => write!(f, "{}{}", value, unit),
+            Value::Duration { value, unit } => write!(f, "{}{}", value, unit),
+            Value::Byte(b) => write!(f, "0x{:02x}", b),
+            Value::Ascii(text) => write!(f, "{:?}", text),
+            Value::Bytes(bytes) => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+            }
+            Value::Enum {
+                name: Some(name), ..
+            } => write!(f, "{}", name),
+            Value::Enum { name: None, value } => write!(f, "{} (unknown)", value),
+            Value::Flags { names, value } if names.is_empty() => write!(f, "{}", value),
+            Value::Flags { names, .. } => write!(f, "{}", names.join("|")),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Struct(fields) => write_fields(f, fields),
+            Value::Union { variant, value } => write!(f, "{}: {}", variant, value),
+        }
+    }
+}
+
+impl Display for DynamicMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ", self.name)?;
+        write_fields(f, &self.fields)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data_types::scalar::TimeUnit;
+    use crate::runtime::Value;
+
+    #[test]
+    fn test_display() {
+        let decimal = |mantissa, exponent| Value::Decimal { mantissa, exponent }.to_string();
+        assert_eq!("123.45", decimal(12345, -2));
+        assert_eq!("-0.005", decimal(-5, -3));
+        assert_eq!("1200", decimal(12, 2));
+
+        let value = Value::Struct(vec![
+            (String::from("symbol"), Value::Ascii(String::from("AAPL"))),
+            (
+                String::from("sent_at"),
+                Value::Timestamp {
+                    value: 12,
+                    unit: TimeUnit::Nanos,
+                },
+            ),
+            (
+                String::from("flags"),
+                Value::Flags {
+                    names: vec![String::from("READ"), String::from("WRITE")],
+                    value: 3,
+                },
+            ),
+            (
+                String::from("levels"),
+                Value::Array(vec![Value::Signed(-1)]),
+            ),
+            (String::from("checksum"), Value::Bytes(vec![0xca, 0xfe])),
+        ]);
+        assert_eq!(
+            "{ symbol: \"AAPL\", sent_at: 12ns, flags: READ|WRITE, levels: [-1], checksum: 0xcafe }",
+            value.to_string()
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Version,
+    Check {
+        files: Vec<PathBuf>,
+    },
+    Build {
+        lang: String,
+        out: PathBuf,
+        options: Vec<(String, String)>,
+        files: Vec<PathBuf>,
+    },
+    Layout {
+        format: LayoutFormat,
+        files: Vec<PathBuf>,
+    },
+    Dump {
+        format: DumpFormat,
+        model: DumpModel,
+        output: Option<PathBuf>,
+        file: PathBuf,
+    },
+    /// Messages written as JSON, one per line, into binary frames
+    Encode(Transcode),
+    /// Binary frames into messages written as JSON, one per line
+    Decode(Transcode),
+    Hexdump {
+        schema: PathBuf,
+        /// The name or id of the message in the input
+        message: String,
+        color: ColorChoice,
+        /// Standard input when `None`
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Only when writing to a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+/// The options shared by `sssc encode` and `sssc decode`
+#[derive(Debug, PartialEq, Eq)]
+pub struct Transcode {
+    pub schema: PathBuf,
+    pub binary: BinaryFormat,
+    /// The size of the message id starting every frame, unused when the schema declares a header
+    pub id_size: usize,
+    /// Standard input when `None`
+    pub input: Option<PathBuf>,
+    /// Standard output when `None`
+    pub output: Option<PathBuf>,
+}
+
+/// How the binary side of `sssc encode` and `sssc decode` is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// One frame per line
+    Hex,
+    /// Frames directly following each other
+    Raw,
+    /// One frame per line
+    Base64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutFormat {
+    /// The offset and size of every member, one per line
+    Table,
+    /// Which member each byte belongs to, one cache line per row
+    Ruler,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Json,
+    Yaml,
+}
+
+/// Which stage of compilation `sssc dump` writes out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpModel {
+    /// The syntax tree, as written in the schema
+    Parsed,
+    /// The protocol with every type looked up and every layout computed
+    Resolved,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Arguments split into `--name value` options and positional arguments
+struct ParsedArgs {
+    options: Vec<(String, String)>,
+    positional: Vec<String>,
+}
+
+impl ParsedArgs {
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.options
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, UsageError> {
+        self.value(name)
+            .ok_or_else(|| UsageError(format!("missing required option '--{}'", name)))
+    }
+
+    /// The single input file, `None` for standard input which can also be given as `-`
+    fn input(&self) -> Result<Option<PathBuf>, UsageError> {
+        match self.positional.as_slice() {
+            [] => Ok(None),
+            [input] if input == "-" => Ok(None),
+            [input] => Ok(Some(PathBuf::from(input))),
+            _ => Err(UsageError(String::from("only one input file can be given"))),
+        }
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>, UsageError> {
+        if self.positional.is_empty() {
+            return Err(UsageError(String::from("no schema files given")));
+        }
+        Ok(self.positional.iter().map(PathBuf::from).collect())
+    }
+}
+
+/// Splits `args`, anything in `value_options` takes a value either as `--name value` or
+/// `--name=value`, any other option is rejected
+fn split_args(args: &[String], value_options: &[&str]) -> Result<ParsedArgs, UsageError> {
+    let mut parsed = ParsedArgs {
+        options: vec![],
+        positional: vec![],
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            parsed.positional.extend(iter.by_ref().cloned());
+            break;
+        }
+        let Some(option) = arg.strip_prefix("--") else {
+            parsed.positional.push(arg.clone());
+            continue;
+        };
+
+        let (name, inline_value) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(String::from(value))),
+            None => (option, None),
+        };
+        if !value_options.contains(&name) {
+            return Err(UsageError(format!("unknown option '--{}'", name)));
+        }
+        let value = match inline_value {
+            Some(v) => v,
+            None => iter
+                .next()
+                .cloned()
+                .ok_or_else(|| UsageError(format!("option '--{}' needs a value", name)))?,
+        };
+        parsed.options.push((String::from(name), value));
+    }
+
+    Ok(parsed)
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, UsageError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Help);
+    };
+
+    match command.as_str() {
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        "version" | "--version" | "-V" => Ok(Command::Version),
+        "check" => {
+            let parsed = split_args(rest, &[])?;
+            Ok(Command::Check {
+                files: parsed.files()?,
+            })
+        }
+        "build" => {
+            let parsed = split_args(rest, &["lang", "out", "opt"])?;
+            let options = parsed
+                .values("opt")
+                .map(|opt| match opt.split_once('=') {
+                    Some((k, v)) => Ok((String::from(k), String::from(v))),
+                    None => Err(UsageError(format!(
+                        "generator options are written 'key=value', found '{}'",
+                        opt
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Command::Build {
+                lang: String::from(parsed.required("lang")?),
+                out: PathBuf::from(parsed.required("out")?),
+                options,
+                files: parsed.files()?,
+            })
+        }
+        "layout" => {
+            let parsed = split_args(rest, &["format"])?;
+            let format = match parsed.value("format").unwrap_or("table") {
+                "table" => LayoutFormat::Table,
+                "ruler" => LayoutFormat::Ruler,
+                other => return Err(UsageError(format!("unknown layout format '{}'", other))),
+            };
+            Ok(Command::Layout {
+                format,
+                files: parsed.files()?,
+            })
+        }
+        "dump" => {
+            let parsed = split_args(rest, &["format", "model", "output"])?;
+            let format = match parsed.value("format").unwrap_or("json") {
+                "json" => DumpFormat::Json,
+                "yaml" => DumpFormat::Yaml,
+                other => return Err(UsageError(format!("unknown dump format '{}'", other))),
+            };
+            let model = match parsed.value("model").unwrap_or("resolved") {
+                "parsed" => DumpModel::Parsed,
+                "resolved" => DumpModel::Resolved,
+                other => return Err(UsageError(format!("unknown dump model '{}'", other))),
+            };
+            let mut files = parsed.files()?;
+            if files.len() > 1 {
+                return Err(UsageError(String::from("dump takes a single schema file")));
+            }
+            Ok(Command::Dump {
+                format,
+                model,
+                output: parsed.value("output").map(PathBuf::from),
+                file: files.remove(0),
+            })
+        }
+        "encode" => Ok(Command::Encode(parse_transcode(rest)?)),
+        "decode" => Ok(Command::Decode(parse_transcode(rest)?)),
+        "hexdump" => {
+            let parsed = split_args(rest, &["schema", "message", "color"])?;
+            let color = match parsed.value("color").unwrap_or("auto") {
+                "auto" => ColorChoice::Auto,
+                "always" => ColorChoice::Always,
+                "never" => ColorChoice::Never,
+                other => return Err(UsageError(format!("unknown color choice '{}'", other))),
+            };
+            Ok(Command::Hexdump {
+                schema: PathBuf::from(parsed.required("schema")?),
+                message: String::from(parsed.required("message")?),
+                color,
+                input: parsed.input()?,
+            })
+        }
+        other => Err(UsageError(format!("unknown command '{}'", other))),
+    }
+}
+
+fn parse_transcode(rest: &[String]) -> Result<Transcode, UsageError> {
+    let parsed = split_args(rest, &["schema", "binary", "id-size", "output"])?;
+    let binary = match parsed.value("binary").unwrap_or("hex") {
+        "hex" => BinaryFormat::Hex,
+        "raw" => BinaryFormat::Raw,
+        "base64" => BinaryFormat::Base64,
+        other => return Err(UsageError(format!("unknown binary format '{}'", other))),
+    };
+    let id_size = match parsed.value("id-size").unwrap_or("2") {
+        "1" => 1,
+        "2" => 2,
+        "4" => 4,
+        "8" => 8,
+        other => {
+            return Err(UsageError(format!(
+                "message ids are 1, 2, 4 or 8 bytes, not '{}'",
+                other
+            )))
+        }
+    };
+    Ok(Transcode {
+        schema: PathBuf::from(parsed.required("schema")?),
+        binary,
+        id_size,
+        input: parsed.input()?,
+        output: parsed.value("output").map(PathBuf::from),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sssc::args::{
+        parse_args, BinaryFormat, ColorChoice, Command, DumpFormat, DumpModel, LayoutFormat,
+        Transcode, UsageError,
+    };
+    use std::path::PathBuf;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_build() {
+        assert_eq!(
+            parse_args(&args(
+                "build --lang rust --out=gen --opt a=1 --opt b=2 a.sss b.sss"
+            )),
+            Ok(Command::Build {
+                lang: String::from("rust"),
+                out: PathBuf::from("gen"),
+                options: vec![
+                    (String::from("a"), String::from("1")),
+                    (String::from("b"), String::from("2"))
+                ],
+                files: vec![PathBuf::from("a.sss"), PathBuf::from("b.sss")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_dump_defaults_to_json() {
+        assert_eq!(
+            parse_args(&args("dump a.sss")),
+            Ok(Command::Dump {
+                format: DumpFormat::Json,
+                model: DumpModel::Resolved,
+                output: None,
+                file: PathBuf::from("a.sss"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_dump_options() {
+        assert_eq!(
+            parse_args(&args(
+                "dump --format yaml --model parsed --output a.yaml a.sss"
+            )),
+            Ok(Command::Dump {
+                format: DumpFormat::Yaml,
+                model: DumpModel::Parsed,
+                output: Some(PathBuf::from("a.yaml")),
+                file: PathBuf::from("a.sss"),
+            })
+        );
+        assert!(parse_args(&args("dump --format toml a.sss")).is_err());
+        assert!(parse_args(&args("dump --model typed a.sss")).is_err());
+    }
+
+    #[test]
+    fn test_parse_layout_format() {
+        assert_eq!(
+            parse_args(&args("layout a.sss")),
+            Ok(Command::Layout {
+                format: LayoutFormat::Table,
+                files: vec![PathBuf::from("a.sss")],
+            })
+        );
+        assert_eq!(
+            parse_args(&args("layout --format ruler a.sss")),
+            Ok(Command::Layout {
+                format: LayoutFormat::Ruler,
+                files: vec![PathBuf::from("a.sss")],
+            })
+        );
+        assert!(parse_args(&args("layout --format svg a.sss")).is_err());
+    }
+
+    #[test]
+    fn test_parse_transcode() {
+        assert_eq!(
+            parse_args(&args("encode --schema a.sss")),
+            Ok(Command::Encode(Transcode {
+                schema: PathBuf::from("a.sss"),
+                binary: BinaryFormat::Hex,
+                id_size: 2,
+                input: None,
+                output: None,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args(
+                "decode --schema a.sss --binary raw --id-size 4 --output out.json in.bin"
+            )),
+            Ok(Command::Decode(Transcode {
+                schema: PathBuf::from("a.sss"),
+                binary: BinaryFormat::Raw,
+                id_size: 4,
+                input: Some(PathBuf::from("in.bin")),
+                output: Some(PathBuf::from("out.json")),
+            }))
+        );
+        assert!(matches!(
+            parse_args(&args("decode --schema a.sss -")),
+            Ok(Command::Decode(Transcode { input: None, .. }))
+        ));
+        assert!(parse_args(&args("encode in.json")).is_err());
+        assert!(parse_args(&args("encode --schema a.sss --binary octal")).is_err());
+        assert!(parse_args(&args("encode --schema a.sss --id-size 3")).is_err());
+        assert!(parse_args(&args("encode --schema a.sss a.json b.json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_hexdump() {
+        assert_eq!(
+            parse_args(&args(
+                "hexdump --schema a.sss --message Quote --color never a.bin"
+            )),
+            Ok(Command::Hexdump {
+                schema: PathBuf::from("a.sss"),
+                message: String::from("Quote"),
+                color: ColorChoice::Never,
+                input: Some(PathBuf::from("a.bin")),
+            })
+        );
+        assert!(matches!(
+            parse_args(&args("hexdump --schema a.sss --message 7")),
+            Ok(Command::Hexdump {
+                color: ColorChoice::Auto,
+                input: None,
+                ..
+            })
+        ));
+        assert!(parse_args(&args("hexdump --schema a.sss a.bin")).is_err());
+        assert!(parse_args(&args("hexdump --schema a.sss --message M --color red")).is_err());
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert_eq!(
+            parse_args(&args("build --out gen a.sss")),
+            Err(UsageError(String::from("missing required option '--lang'")))
+        );
+        assert!(parse_args(&args("check")).is_err());
+        assert!(parse_args(&args("check --strict a.sss")).is_err());
+        assert!(parse_args(&args("frobnicate a.sss")).is_err());
+        assert!(parse_args(&args("build --lang")).is_err());
+        assert!(parse_args(&args("build --lang rust --out gen --opt a a.sss")).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! The annotated hexdump printed by `sssc hexdump`, every member of a message gets a row of its
+//! bytes labelled with the field and its decoded value, e.g.
+//!
+//! ```text
+//! message EventOutcome [2] size 21, 21 bytes given
+//!   offset  bytes                                            member
+//!        0  0c 00 00 00 00 00 00 00                          timestamp = 12
+//!        8  01                                               outcome = SUCCESS
+//!        9  00 00 2a!00                                      <padding, reserved>
+//!                                                            <- 1 non-zero byte
+//!       13  64 00 00 00 00 00 00 00                          quantity_granted = 100
+//! ```
+//!
+//! Structs are flattened into their fields and a union shows the variant its discriminator
+//! selects. When the protocol declares a header the buffer starts with it, its fields are shown
+//! as `header.length` and so on. A non-zero byte of padding is followed by a `!` and the row is
+//! marked with how many there are, which is wrapped onto its own line above. Bytes past the end
+//! of the buffer are drawn as `--` and anything after the message is shown as trailing bytes.
+
+use crate::data_types::{FieldType, FieldType_};
+use crate::protocol::{Layout, Member, Message};
+use crate::runtime::{Decoder, Value};
+use std::io::Write;
+
+const BYTES_PER_LINE: usize = 16;
+
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+struct Row {
+    offset: usize,
+    size: usize,
+    label: String,
+    padding: bool,
+}
+
+/// Writes `buf` as a hexdump of `message`, which starts at the first byte or right after the
+/// header. With `color` padding is dimmed and its non-zero bytes are shown in red.
+pub fn write_hexdump(
+    decoder: &Decoder,
+    message: &Message,
+    buf: &[u8],
+    color: bool,
+    out: &mut dyn Write,
+) -> std::io::Result<()> {
+    let mut rows = vec![];
+    let start = match &decoder.protocol().header {
+        Some(header) => {
+            layout_rows(decoder, &header.layout, 0, "header.", buf, &mut rows);
+            header.layout.size
+        }
+        None => 0,
+    };
+    layout_rows(decoder, &message.layout, start, "", buf, &mut rows);
+    let size = start + message.layout.size;
+    if buf.len() > size {
+        rows.push(Row {
+            offset: size,
+            size: buf.len() - size,
+            label: String::from("<trailing bytes after the message>"),
+            padding: false,
+        });
+    }
+
+    write!(
+        out,
+        "message {} [{}] size {}, {} bytes given",
+        message.name,
+        message.id,
+        size,
+        buf.len()
+    )?;
+    match buf.len() < size {
+        true => writeln!(out, "  <- {} bytes missing", size - buf.len())?,
+        false => writeln!(out)?,
+    }
+    writeln!(
+        out,
+        "{:>8}  {:<width$} member",
+        "offset",
+        "bytes",
+        width = BYTES_PER_LINE * 3
+    )?;
+    for row in rows.iter().filter(|r| r.size > 0) {
+        write_row(row, buf, color, out)?;
+    }
+    Ok(())
+}
+
+/// The rows of the members of `layout`, which starts at `base`
+fn layout_rows(
+    decoder: &Decoder,
+    layout: &Layout,
+    base: usize,
+    prefix: &str,
+    buf: &[u8],
+    rows: &mut Vec<Row>,
+) {
+    for member in &layout.members {
+        let f = match member {
+            Member::Padding(p) => {
+                rows.push(Row {
+                    offset: base + p.offset,
+                    size: p.size,
+                    label: format!("<padding, {}>", p.reason),
+                    padding: true,
+                });
+                continue;
+            }
+            Member::Field(f) => f,
+        };
+        let name = format!("{}{}", prefix, f.name);
+        let offset = base + f.offset;
+        let FieldType_::Union(ut) = &f.field_type else {
+            field_rows(decoder, &name, &f.field_type, offset, buf, rows);
+            continue;
+        };
+
+        let discriminator = layout
+            .field(&ut.discriminator)
+            .expect("resolved unions have a discriminator next to them");
+        let at = base + discriminator.offset;
+        let selector =
+            decoder.decode_value(&discriminator.field_type, buf.get(at..).unwrap_or(&[]));
+        let u = decoder
+            .protocol()
+            .union(&ut.name)
+            .expect("resolved protocols declare every union they use");
+        match selector {
+            Ok(Value::Enum { value, .. }) => match u.variant_for(value) {
+                Some(v) => {
+                    let variant = format!("{}.{}", name, v.name);
+                    field_rows(decoder, &variant, &v.field_type, offset, buf, rows);
+                    rows.push(Row {
+                        offset: offset + v.field_type.size_bytes(),
+                        size: ut.size - v.field_type.size_bytes(),
+                        label: format!("<padding, unused by {}>", variant),
+                        padding: true,
+                    });
+                }
+                None => rows.push(Row {
+                    offset,
+                    size: ut.size,
+                    label: format!(
+                        "{} = <no variant for {} = {}>",
+                        name, ut.discriminator, value
+                    ),
+                    padding: false,
+                }),
+            },
+            _ => rows.push(Row {
+                offset,
+                size: ut.size,
+                label: format!("{} = <missing>", name),
+                padding: false,
+            }),
+        }
+    }
+}
+
+fn field_rows(
+    decoder: &Decoder,
+    name: &str,
+    field_type: &FieldType_,
+    offset: usize,
+    buf: &[u8],
+    rows: &mut Vec<Row>,
+) {
+    if let FieldType_::Struct(st) = field_type {
+        let s = decoder
+            .protocol()
+            .structure(&st.name)
+            .expect("resolved protocols declare every struct they use");
+        let prefix = format!("{}.", name);
+        return layout_rows(decoder, &s.layout, offset, &prefix, buf, rows);
+    }
+    let value = match decoder.decode_value(field_type, buf.get(offset..).unwrap_or(&[])) {
+        Ok(value) => value.to_string(),
+        Err(_) => String::from("<missing>"),
+    };
+    rows.push(Row {
+        offset,
+        size: field_type.size_bytes(),
+        label: format!("{} = {}", name, value),
+        padding: false,
+    });
+}
+
+fn write_row(row: &Row, buf: &[u8], color: bool, out: &mut dyn Write) -> std::io::Result<()> {
+    let bytes: Vec<Option<u8>> = (row.offset..row.offset + row.size)
+        .map(|i| buf.get(i).copied())
+        .collect();
+    let non_zero = bytes.iter().filter(|b| matches!(b, Some(1..))).count();
+
+    let mut label = row.label.clone();
+    if row.padding && non_zero > 0 {
+        let plural = if non_zero == 1 { "" } else { "s" };
+        label += &format!("  <- {} non-zero byte{}", non_zero, plural);
+    }
+
+    for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let mut cells = String::new();
+        for byte in chunk {
+            let flagged = row.padding && matches!(byte, Some(1..));
+            let style = match (color, flagged, row.padding) {
+                (true, true, _) => RED,
+                (true, false, true) => DIM,
+                _ => "",
+            };
+            cells += style;
+            match byte {
+                Some(b) => cells += &format!("{:02x}", b),
+                None => cells += "--",
+            }
+            if !style.is_empty() {
+                cells += RESET;
+            }
+            cells.push(if flagged { '!' } else { ' ' });
+        }
+        cells += &" ".repeat((BYTES_PER_LINE - chunk.len()) * 3);
+
+        let text = format!(
+            "{:>8}  {} {}",
+            row.offset + line * BYTES_PER_LINE,
+            cells,
+            if line == 0 { label.as_str() } else { "" }
+        );
+        writeln!(out, "{}", text.trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::Decoder;
+    use crate::sssc::hexdump::write_hexdump;
+
+    const SCHEMA: &str = "protocol foo;
+        enum Kind : u8 { PRICE = 1, TEXT = 2 }
+        struct Price { u16: value; 1; }
+        union Body : Kind { @when(PRICE) Price: price; @when(TEXT) ascii[4]: text; }
+        message M [1] { Kind: kind; @discriminator(kind) Body: body; 2; }";
+
+    fn hexdump(buf: &[u8], color: bool) -> String {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let mut out = vec![];
+        let message = protocol.message("M").unwrap();
+        write_hexdump(&Decoder::new(&protocol), message, buf, color, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let text = hexdump(&[1, 0x2c, 1, 0, 0, 0, 0, 3], false);
+        let expected = [
+            "message M [1] size 7, 8 bytes given",
+            "  offset  bytes                                            member",
+            "       0  01                                               kind = PRICE",
+            "       1  2c 01                                            body.price.value = 300",
+            "       3  00                                               <padding, reserved>",
+            "       4  00                                               <padding, unused by body.price>",
+            "       5  00 00                                            <padding, reserved>",
+            "       7  03                                               <trailing bytes after the message>",
+        ];
+        assert_eq!(expected.join("\n") + "\n", text);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let text = hexdump(&[2, b'a', b'b', b'c', b'd', 0, 7], false);
+        assert!(
+            text.contains(
+                "       1  61 62 63 64                                      body.text = \"abcd\"\n"
+            ),
+            "{}",
+            text
+        );
+        assert!(text.contains("       5  00 07!                                           <padding, reserved>  <- 1 non-zero byte\n"), "{}", text);
+
+        let text = hexdump(&[9, 0, 0], false);
+        assert!(text.contains("message M [1] size 7, 3 bytes given  <- 4 bytes missing\n"));
+        assert!(text.contains("       1  00 00 -- --                                      body = <no variant for kind = 9>\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_color() {
+        let text = hexdump(&[1, 0, 0, 5, 0, 0, 0], true);
+        assert!(text.contains("\x1b[1;31m05\x1b[0m!"), "{}", text);
+        assert!(text.contains("\x1b[2m00\x1b[0m "), "{}", text);
+    }
+
+    #[test]
+    fn test_header() {
+        let protocol = crate::sssc::compile(
+            "protocol foo; header { @length u8: length; @id u8: id; } message M [1] { u16: a; }",
+        )
+        .unwrap();
+        let mut out = vec![];
+        let message = protocol.message("M").unwrap();
+        write_hexdump(
+            &Decoder::new(&protocol),
+            message,
+            &[4, 1, 5, 0],
+            false,
+            &mut out,
+        )
+        .unwrap();
+        let expected = [
+            "message M [1] size 4, 4 bytes given",
+            "  offset  bytes                                            member",
+            "       0  04                                               header.length = 4",
+            "       1  01                                               header.id = 1",
+            "       2  05 00                                            a = 5",
+        ];
+        assert_eq!(expected.join("\n") + "\n", String::from_utf8(out).unwrap());
+    }
+}
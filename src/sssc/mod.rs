@@ -0,0 +1,522 @@
+use crate::codegen::{GeneratorOptions, OutputFile, Registry};
+use crate::data_types::FieldType;
+use crate::lexer::tokenize;
+use crate::parser::{parse, ParseError};
+use crate::protocol::{resolve, Protocol, ResolveError};
+use crate::runtime::Decoder;
+use crate::syntax::SyntaxUnit;
+use args::{parse_args, ColorChoice, Command, DumpFormat, DumpModel, LayoutFormat, Transcode};
+use std::fmt::{Display, Formatter};
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+pub mod args;
+pub mod hexdump;
+pub mod ruler;
+#[cfg(feature = "serde")]
+pub mod transcode;
+
+const USAGE: &str = "\
+usage: sssc <command> [options] <schema.sss>...
+
+commands:
+  check                              validate one or more schema files
+  build --lang <lang> --out <dir> [--opt key=value]...
+                                     generate code for the schema files into <dir>
+  layout [--format table|ruler]      print the computed offset of every field, or a ruler
+                                     showing the member each byte belongs to
+  dump [--format json|yaml] [--model parsed|resolved] [--output <file>]
+                                     write out the syntax tree or the resolved protocol of
+                                     a schema file
+  encode --schema <file> [--binary hex|raw|base64] [--id-size 1|2|4|8]
+         [--output <file>] [<input>]
+                                     turn messages written as JSON, one per line, into
+                                     binary frames of the message id and the message, or
+                                     of the header when the schema declares one
+  decode --schema <file> [--binary hex|raw|base64] [--id-size 1|2|4|8]
+         [--output <file>] [<input>]
+                                     turn binary frames back into JSON, one message per
+                                     line, both read standard input without an <input>
+  hexdump --schema <file> --message <name|id> [--color auto|always|never] [<input>]
+                                     print the bytes of a message labelled with the field
+                                     they belong to and its value
+  help                               print this message
+  version                            print the compiler version
+
+exit codes:
+  0  success
+  1  a schema file is invalid
+  2  the command line is invalid
+  3  a file could not be read or written
+  4  code generation failed
+  5  the input of encode or decode is invalid
+";
+
+/// The process exit code of a `sssc` invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Status {
+    Success = 0,
+    InvalidSchema = 1,
+    Usage = 2,
+    Io = 3,
+    GeneratorFailed = 4,
+    InvalidInput = 5,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CompileError {
+    Parse(ParseError),
+    Resolve(ResolveError),
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::Parse(e) => write!(f, "{}", e),
+            CompileError::Resolve(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// The version of the documents written by `sssc dump`, described by
+/// `docs/dump-format.schema.json`. It is incremented whenever the format changes in a way existing
+/// readers could misinterpret, adding optional fields doesn't change it.
+pub const DUMP_FORMAT_VERSION: u32 = 1;
+
+/// A document written by `sssc dump`, the fields of the model follow the version
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Dump<T> {
+    pub format_version: u32,
+    #[serde(flatten)]
+    pub model: T,
+}
+
+/// Parses the text of a schema without resolving it
+pub fn parse_schema(source: &str) -> Result<SyntaxUnit, CompileError> {
+    parse(&mut tokenize(source))
+        .map_err(CompileError::Parse)?
+        .ok_or(CompileError::Parse(ParseError::ExpectedProtocolDeclaration))
+}
+
+/// Parses and resolves the text of a schema
+pub fn compile(source: &str) -> Result<Protocol, CompileError> {
+    resolve(&parse_schema(source)?).map_err(CompileError::Resolve)
+}
+
+/// Runs the compiler with the arguments following the program name
+pub fn run(args: &[String], stdout: &mut dyn Write, stderr: &mut dyn Write) -> Status {
+    run_with(&Registry::with_builtins(), args, stdout, stderr)
+}
+
+/// Runs the compiler choosing code generators from `registry`, this is how a tool embedding
+/// `sssc` makes its own generators available
+pub fn run_with(
+    registry: &Registry,
+    args: &[String],
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Status {
+    let command = match parse_args(args) {
+        Ok(c) => c,
+        Err(e) => {
+            _ = writeln!(stderr, "error: {}\n\n{}", e, USAGE);
+            return Status::Usage;
+        }
+    };
+
+    let result = match command {
+        Command::Help => write!(stdout, "{}", USAGE).map_err(|_| Status::Io),
+        Command::Version => {
+            writeln!(stdout, "sssc {}", env!("CARGO_PKG_VERSION")).map_err(|_| Status::Io)
+        }
+        Command::Check { files } => compile_files(&files, stderr).map(|_| ()),
+        Command::Build {
+            lang,
+            out,
+            options,
+            files,
+        } => build(
+            registry,
+            &lang,
+            &out,
+            &GeneratorOptions {
+                parameters: options,
+            },
+            &files,
+            stderr,
+        ),
+        Command::Layout { format, files } => layout(format, &files, stdout, stderr),
+        Command::Dump {
+            format,
+            model,
+            output,
+            file,
+        } => dump(format, model, output.as_deref(), &file, stdout, stderr),
+        Command::Encode(options) => transcode(Direction::Encode, &options, stdout, stderr),
+        Command::Decode(options) => transcode(Direction::Decode, &options, stdout, stderr),
+        Command::Hexdump {
+            schema,
+            message,
+            color,
+            input,
+        } => hexdump(&schema, &message, color, input.as_deref(), stdout, stderr),
+    };
+
+    match result {
+        Ok(()) => Status::Success,
+        Err(status) => status,
+    }
+}
+
+/// Compiles every file, reporting every failure rather than stopping at the first
+fn compile_files(
+    files: &[PathBuf],
+    stderr: &mut dyn Write,
+) -> Result<Vec<(PathBuf, Protocol)>, Status> {
+    let mut protocols = vec![];
+    let mut status = Status::Success;
+
+    for file in files {
+        match compile_file(file, stderr) {
+            Ok(protocol) => protocols.push((file.clone(), protocol)),
+            // an unreadable file is reported in preference to an invalid one
+            Err(s) if status != Status::Io => status = s,
+            Err(_) => {}
+        }
+    }
+
+    match status {
+        Status::Success => Ok(protocols),
+        s => Err(s),
+    }
+}
+
+fn read_file(file: &Path, stderr: &mut dyn Write) -> Result<String, Status> {
+    std::fs::read_to_string(file).map_err(|e| {
+        _ = writeln!(stderr, "{}: error: {}", file.display(), e);
+        Status::Io
+    })
+}
+
+fn compile_file(file: &Path, stderr: &mut dyn Write) -> Result<Protocol, Status> {
+    compile(&read_file(file, stderr)?).map_err(|e| {
+        _ = writeln!(stderr, "{}: error: {}", file.display(), e);
+        Status::InvalidSchema
+    })
+}
+
+fn parse_file(file: &Path, stderr: &mut dyn Write) -> Result<SyntaxUnit, Status> {
+    parse_schema(&read_file(file, stderr)?).map_err(|e| {
+        _ = writeln!(stderr, "{}: error: {}", file.display(), e);
+        Status::InvalidSchema
+    })
+}
+
+fn build(
+    registry: &Registry,
+    lang: &str,
+    out: &Path,
+    options: &GeneratorOptions,
+    files: &[PathBuf],
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    // built in generators take precedence over plugins of the same name
+    #[cfg(feature = "serde")]
+    let plugin = crate::codegen::plugin::find_plugin(lang);
+    #[cfg(feature = "serde")]
+    let found = registry
+        .find(lang)
+        .or(plugin.as_ref().map(|p| p as &dyn crate::codegen::Generator));
+    #[cfg(not(feature = "serde"))]
+    let found = registry.find(lang);
+
+    let Some(generator) = found else {
+        let available: Vec<&str> = registry.generators().map(|g| g.name()).collect();
+        _ = writeln!(
+            stderr,
+            "error: no code generator named '{}' and no 'sss-gen-{}' plugin on the PATH, \
+            available generators are: {}",
+            lang,
+            lang,
+            available.join(", ")
+        );
+        return Err(Status::Usage);
+    };
+
+    for (file, protocol) in compile_files(files, stderr)? {
+        let outputs = generator
+            .generate_reporting(&protocol, options, stderr)
+            .map_err(|e| {
+                _ = writeln!(stderr, "{}: error: {}: {}", file.display(), lang, e);
+                Status::GeneratorFailed
+            })?;
+        write_outputs(out, &outputs, stderr)?;
+    }
+    Ok(())
+}
+
+/// Writes generated files below `out`, files whose contents haven't changed are left untouched
+/// so build systems don't rebuild everything depending on them
+fn write_outputs(out: &Path, outputs: &[OutputFile], stderr: &mut dyn Write) -> Result<(), Status> {
+    for output in outputs {
+        let escapes = output
+            .path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)));
+        if escapes || output.path.as_os_str().is_empty() {
+            _ = writeln!(
+                stderr,
+                "error: refusing to write '{}' outside of the output directory",
+                output.path.display()
+            );
+            return Err(Status::GeneratorFailed);
+        }
+
+        let path = out.join(&output.path);
+        if std::fs::read_to_string(&path).is_ok_and(|existing| existing == output.contents) {
+            continue;
+        }
+        let written = match path.parent() {
+            Some(parent) => std::fs::create_dir_all(parent),
+            None => Ok(()),
+        }
+        .and_then(|_| std::fs::write(&path, &output.contents));
+        written.map_err(|e| {
+            _ = writeln!(stderr, "{}: error: {}", path.display(), e);
+            Status::Io
+        })?;
+    }
+    Ok(())
+}
+
+fn layout(
+    format: LayoutFormat,
+    files: &[PathBuf],
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    for (_, protocol) in compile_files(files, stderr)? {
+        match format {
+            LayoutFormat::Table => write_layout(&protocol, stdout),
+            LayoutFormat::Ruler => ruler::write_ruler(&protocol, stdout),
+        }
+        .map_err(|_| Status::Io)?;
+    }
+    Ok(())
+}
+
+fn write_layout(protocol: &Protocol, out: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(out, "protocol {}", protocol.qualified_name())?;
+    for s in &protocol.structs {
+        writeln!(out)?;
+        write!(out, "{}", s)?;
+    }
+    for u in &protocol.unions {
+        writeln!(
+            out,
+            "\nunion {} : {} size {} align {}",
+            u.name, u.discriminator_type, u.size, u.alignment
+        )?;
+        for v in &u.variants {
+            writeln!(
+                out,
+                "{:>8} {:>6}  {}: {} when {}",
+                0,
+                v.field_type.size_bytes(),
+                v.field_type,
+                v.name,
+                v.selector
+            )?;
+        }
+    }
+    for m in &protocol.messages {
+        writeln!(out)?;
+        write!(out, "{}", m)?;
+    }
+    Ok(())
+}
+
+fn dump(
+    format: DumpFormat,
+    model: DumpModel,
+    output: Option<&Path>,
+    file: &Path,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    let text = match model {
+        DumpModel::Parsed => serialize(format, &parse_file(file, stderr)?, stderr)?,
+        DumpModel::Resolved => serialize(format, &compile_file(file, stderr)?, stderr)?,
+    };
+
+    match output {
+        None => writeln!(stdout, "{}", text).map_err(|_| Status::Io),
+        Some(path) => std::fs::write(path, text + "\n").map_err(|e| {
+            _ = writeln!(stderr, "{}: error: {}", path.display(), e);
+            Status::Io
+        }),
+    }
+}
+
+/// Writes `model` in a [`Dump`], without a trailing newline
+#[cfg(feature = "serde")]
+fn serialize<T: serde::Serialize>(
+    format: DumpFormat,
+    model: &T,
+    stderr: &mut dyn Write,
+) -> Result<String, Status> {
+    let dump = Dump {
+        format_version: DUMP_FORMAT_VERSION,
+        model,
+    };
+    match format {
+        DumpFormat::Json => {
+            Ok(serde_json::to_string_pretty(&dump)
+                .expect("the dumped models are always serializable"))
+        }
+        DumpFormat::Yaml => to_yaml(&dump, stderr),
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn serialize<T>(_format: DumpFormat, _model: &T, stderr: &mut dyn Write) -> Result<String, Status> {
+    _ = writeln!(
+        stderr,
+        "error: sssc was built without the 'serde' feature, dump output is unavailable"
+    );
+    Err(Status::Usage)
+}
+
+/// Goes through a JSON value so enums come out as plain mappings, the same shape as the JSON,
+/// rather than as YAML tags which many readers don't understand
+#[cfg(feature = "yaml")]
+fn to_yaml<T: serde::Serialize>(dump: &T, _stderr: &mut dyn Write) -> Result<String, Status> {
+    let value = serde_json::to_value(dump).expect("the dumped models are always serializable");
+    let text = serde_yaml::to_string(&value).expect("json values are always serializable");
+    Ok(String::from(text.trim_end()))
+}
+
+#[cfg(all(feature = "serde", not(feature = "yaml")))]
+fn to_yaml<T: serde::Serialize>(_dump: &T, stderr: &mut dyn Write) -> Result<String, Status> {
+    _ = writeln!(
+        stderr,
+        "error: sssc was built without the 'yaml' feature, yaml output is unavailable"
+    );
+    Err(Status::Usage)
+}
+
+fn input_name(input: Option<&Path>) -> String {
+    match input {
+        Some(path) => path.display().to_string(),
+        None => String::from("<stdin>"),
+    }
+}
+
+/// The contents of `input`, or of standard input
+fn read_input(input: Option<&Path>, stderr: &mut dyn Write) -> Result<Vec<u8>, Status> {
+    let read = match input {
+        Some(path) => std::fs::read(path),
+        None => {
+            let mut buf = vec![];
+            std::io::stdin().read_to_end(&mut buf).map(|_| buf)
+        }
+    };
+    read.map_err(|e| {
+        _ = writeln!(stderr, "{}: error: {}", input_name(input), e);
+        Status::Io
+    })
+}
+
+fn hexdump(
+    schema: &Path,
+    message: &str,
+    color: ColorChoice,
+    input: Option<&Path>,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    let protocol = compile_file(schema, stderr)?;
+    // a message can also be picked by id, which is all some tools show
+    let found = protocol.message(message).or_else(|| {
+        message
+            .parse()
+            .ok()
+            .and_then(|id| protocol.message_by_id(id))
+    });
+    let Some(found) = found else {
+        _ = writeln!(
+            stderr,
+            "{}: error: no message is called '{}' or has it as its id",
+            schema.display(),
+            message
+        );
+        return Err(Status::Usage);
+    };
+
+    let buf = read_input(input, stderr)?;
+    let color = match color {
+        ColorChoice::Auto => std::io::stdout().is_terminal(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+    hexdump::write_hexdump(&Decoder::new(&protocol), found, &buf, color, stdout)
+        .map_err(|_| Status::Io)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Encode,
+    Decode,
+}
+
+#[cfg(feature = "serde")]
+fn transcode(
+    direction: Direction,
+    options: &Transcode,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    let protocol = compile_file(&options.schema, stderr)?;
+    let input = read_input(options.input.as_deref(), stderr)?;
+
+    let (binary, id_size) = (options.binary, options.id_size);
+    let output = match direction {
+        Direction::Encode => transcode::encode(&protocol, binary, id_size, &input),
+        Direction::Decode => transcode::decode(&protocol, binary, id_size, &input),
+    }
+    .map_err(|e| {
+        _ = writeln!(
+            stderr,
+            "{}: error: {}",
+            input_name(options.input.as_deref()),
+            e
+        );
+        Status::InvalidInput
+    })?;
+
+    match &options.output {
+        None => stdout.write_all(&output).map_err(|_| Status::Io),
+        Some(path) => std::fs::write(path, output).map_err(|e| {
+            _ = writeln!(stderr, "{}: error: {}", path.display(), e);
+            Status::Io
+        }),
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn transcode(
+    _direction: Direction,
+    _options: &Transcode,
+    _stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), Status> {
+    _ = writeln!(
+        stderr,
+        "error: sssc was built without the 'serde' feature, encode and decode are unavailable"
+    );
+    Err(Status::Usage)
+}
@@ -0,0 +1,226 @@
+//! The byte-ruler printed by `sssc layout --format ruler`, each row of the ruler is one cache line
+//! and every byte is drawn as the mark of the member it belongs to, e.g.
+//!
+//! ```text
+//! message EventOutcome [2] size 21 align 1
+//!           0         1         2
+//!           012345678901234567890
+//!        0  AAAAAAAAB----CCCCCCCC
+//!
+//!     mark   offset   size  member
+//!        A        0      8  u64: timestamp
+//!        B        8      1  Outcome: outcome
+//!        -        9      4  <padding, reserved>
+//!        C       13      8  u64: quantity_granted
+//! ```
+//!
+//! Offsets are counted from the start of the message, which is assumed to sit at the start of a
+//! cache line.
+
+use crate::data_types::{EnumKind, FieldType_};
+use crate::protocol::{Layout, Member, PaddingReason, Protocol};
+use std::io::Write;
+
+const CACHE_LINE: usize = 64;
+
+/// Marks given to fields in order, a layout with more fields than this reuses them
+const FIELD_MARKS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+fn padding_mark(reason: PaddingReason) -> char {
+    match reason {
+        PaddingReason::Reserved => '-',
+        PaddingReason::Alignment(_) => '~',
+        PaddingReason::Tail => '.',
+    }
+}
+
+/// Writes the ruler of every struct and message in `protocol`
+pub fn write_ruler(protocol: &Protocol, out: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(out, "protocol {}", protocol.qualified_name())?;
+    writeln!(
+        out,
+        "padding is marked '-' when reserved, '~' when added for alignment and '.' when filling to \
+        the declared size"
+    )?;
+    for s in &protocol.structs {
+        writeln!(
+            out,
+            "\nstruct {} size {} align {}",
+            s.name, s.layout.size, s.layout.alignment
+        )?;
+        write_layout(protocol, &s.layout, out)?;
+    }
+    for m in &protocol.messages {
+        writeln!(
+            out,
+            "\nmessage {} [{}] size {} align {}",
+            m.name, m.id, m.layout.size, m.layout.alignment
+        )?;
+        write_layout(protocol, &m.layout, out)?;
+    }
+    Ok(())
+}
+
+fn write_layout(protocol: &Protocol, layout: &Layout, out: &mut dyn Write) -> std::io::Result<()> {
+    let mut fields = 0;
+    let marks: Vec<char> = layout
+        .members
+        .iter()
+        .map(|member| match member {
+            Member::Field(_) => {
+                fields += 1;
+                FIELD_MARKS[(fields - 1) % FIELD_MARKS.len()] as char
+            }
+            Member::Padding(p) => padding_mark(p.reason),
+        })
+        .collect();
+
+    let mut bytes = vec![' '; layout.size];
+    for (member, mark) in layout.members.iter().zip(&marks) {
+        let end = (member.offset() + member.size_bytes()).min(layout.size);
+        for byte in &mut bytes[member.offset()..end] {
+            *byte = *mark;
+        }
+    }
+
+    let width = layout.size.min(CACHE_LINE);
+    let tens: String = (0..width)
+        .map(|i| match i % 10 {
+            0 => char::from_digit((i / 10 % 10) as u32, 10).unwrap(),
+            _ => ' ',
+        })
+        .collect();
+    let units: String = (0..width)
+        .map(|i| char::from_digit((i % 10) as u32, 10).unwrap())
+        .collect();
+    writeln!(out, "{:>8}  {}", "", tens.trim_end())?;
+    writeln!(out, "{:>8}  {}", "", units)?;
+    for (line, row) in bytes.chunks(CACHE_LINE).enumerate() {
+        let row: String = row.iter().collect();
+        writeln!(out, "{:>8}  {}", line * CACHE_LINE, row)?;
+    }
+
+    writeln!(out, "\n{:>8} {:>8} {:>6}  member", "mark", "offset", "size")?;
+    for (member, mark) in layout.members.iter().zip(&marks) {
+        match member {
+            Member::Field(f) => {
+                let end = f.offset + f.size_bytes();
+                let crosses = end > f.offset && f.offset / CACHE_LINE != (end - 1) / CACHE_LINE;
+                writeln!(
+                    out,
+                    "{:>8} {:>8} {:>6}  {}: {}{}",
+                    mark,
+                    f.offset,
+                    f.size_bytes(),
+                    f.field_type,
+                    f.name,
+                    if crosses {
+                        format!(
+                            "  <- crosses the cache line at {}",
+                            (f.offset / CACHE_LINE + 1) * CACHE_LINE
+                        )
+                    } else {
+                        String::new()
+                    }
+                )?;
+                if let Some(bits) = bit_map(protocol, &f.field_type) {
+                    writeln!(out, "{:>8} {:>8} {:>6}  bits {}", "", "", "", bits)?;
+                }
+            }
+            Member::Padding(p) => writeln!(
+                out,
+                "{:>8} {:>8} {:>6}  <padding, {}>{}",
+                mark,
+                p.offset,
+                p.size,
+                p.reason,
+                if matches!(p.reason, PaddingReason::Alignment(_)) {
+                    "  <- alignment gap"
+                } else {
+                    ""
+                }
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Bitflags are the only values in a protocol which are split into bits, e.g.
+/// `0 READ, 1 WRITE, 6-7 ADMIN` numbering the bits from the least significant
+fn bit_map(protocol: &Protocol, field_type: &FieldType_) -> Option<String> {
+    let FieldType_::Enum(et) = field_type else {
+        return None;
+    };
+    if et.kind != EnumKind::Bitflags {
+        return None;
+    }
+    let flags = protocol.enumeration(&et.name)?;
+    let described: Vec<String> = flags
+        .values
+        .iter()
+        .map(|flag| format!("{} {}", bit_ranges(flag.value), flag.name))
+        .collect();
+    Some(described.join(", "))
+}
+
+/// The set bits of `value` as ranges, e.g. `0,4-5`
+fn bit_ranges(value: u64) -> String {
+    let mut ranges: Vec<String> = vec![];
+    let mut bit = 0;
+    while bit < 64 {
+        if value & (1 << bit) == 0 {
+            bit += 1;
+            continue;
+        }
+        let start = bit;
+        while bit < 64 && value & (1 << bit) != 0 {
+            bit += 1;
+        }
+        ranges.push(match bit - 1 - start {
+            0 => start.to_string(),
+            _ => format!("{}-{}", start, bit - 1),
+        });
+    }
+    match ranges.is_empty() {
+        true => String::from("none"),
+        false => ranges.join(","),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sssc::ruler::{bit_ranges, write_ruler};
+
+    fn ruler(schema: &str) -> String {
+        let protocol = crate::sssc::compile(schema).unwrap();
+        let mut out = vec![];
+        write_ruler(&protocol, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_ruler() {
+        let text = ruler(
+            "protocol foo [align = natural]; message M [1, size = 16, fill_tail] { u8: a; 1; u32: b; u8: c; }",
+        );
+        assert!(text.contains("       0  A-~~BBBBC......."), "{}", text);
+        assert!(text
+            .contains("       ~        2      2  <padding, align to 4 bytes>  <- alignment gap"));
+        assert!(text.contains("       .        9      7  <padding, fill to declared size>"));
+    }
+
+    #[test]
+    fn test_cache_lines() {
+        let text = ruler("protocol foo; message M [1] { byte[60]: a; u64: b; }");
+        assert!(text.contains("      64  BBBB\n"), "{}", text);
+        assert!(text.contains("       B       60      8  u64: b  <- crosses the cache line at 64"));
+    }
+
+    #[test]
+    fn test_bit_ranges() {
+        assert_eq!("0", bit_ranges(1));
+        assert_eq!("0,4-5", bit_ranges(0b110001));
+        assert_eq!("63", bit_ranges(1 << 63));
+        assert_eq!("none", bit_ranges(0));
+    }
+}
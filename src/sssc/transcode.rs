@@ -0,0 +1,253 @@
+//! The conversions behind `sssc encode` and `sssc decode`, between messages written as JSON one per
+//! line and binary frames. A frame is the header declared by the schema, or else the id of the
+//! message, followed by the message, as read by a [`Decoder`], e.g. with the hex binary format
+//!
+//! ```text
+//! {"EventOutcome": {"timestamp": 12, "outcome": "SUCCESS", "quantity_granted": 100}}
+//! 02000c0000000000000001000000006400000000000000
+//! ```
+
+use crate::protocol::Protocol;
+use crate::runtime::json::{from_hex, from_json, to_json};
+use crate::runtime::{Decoder, Encoder};
+use crate::sssc::args::BinaryFormat;
+use std::fmt::{Display, Formatter};
+
+/// The input is wrong at `location`, e.g. `line 3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscodeError {
+    pub location: String,
+    pub reason: String,
+}
+
+impl Display for TranscodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.reason)
+    }
+}
+
+impl std::error::Error for TranscodeError {}
+
+fn at_line(index: usize, reason: impl Display) -> TranscodeError {
+    TranscodeError {
+        location: format!("line {}", index + 1),
+        reason: reason.to_string(),
+    }
+}
+
+/// Encodes every non-blank line of `input`
+pub fn encode(
+    protocol: &Protocol,
+    binary: BinaryFormat,
+    id_size: usize,
+    input: &[u8],
+) -> Result<Vec<u8>, TranscodeError> {
+    let encoder = Encoder::new(protocol).with_id_size(id_size);
+    let mut out = vec![];
+    for (index, line) in text(input)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let json: serde_json::Value = serde_json::from_str(line).map_err(|e| at_line(index, e))?;
+        let message = from_json(protocol, &json).map_err(|e| at_line(index, e))?;
+        let frame = encoder.encode(&message).map_err(|e| at_line(index, e))?;
+        match binary {
+            BinaryFormat::Raw => out.extend(frame),
+            BinaryFormat::Hex => out.extend(format!("{}\n", hex(&frame)).bytes()),
+            BinaryFormat::Base64 => out.extend(format!("{}\n", base64(&frame)).bytes()),
+        }
+    }
+    Ok(out)
+}
+
+/// Decodes every frame of `input`, a line of hex or base64 may hold more than one
+pub fn decode(
+    protocol: &Protocol,
+    binary: BinaryFormat,
+    id_size: usize,
+    input: &[u8],
+) -> Result<Vec<u8>, TranscodeError> {
+    let decoder = Decoder::new(protocol).with_id_size(id_size);
+    let mut out = vec![];
+    let mut decode_frames = |buf: &[u8], location: &dyn Fn(usize) -> String| {
+        let mut offset = 0;
+        while offset < buf.len() {
+            let (message, size) = decoder.decode(&buf[offset..]).map_err(|e| TranscodeError {
+                location: location(offset),
+                reason: e.to_string(),
+            })?;
+            out.extend(format!("{}\n", to_json(protocol, &message)).bytes());
+            offset += size;
+        }
+        Ok(())
+    };
+
+    if binary == BinaryFormat::Raw {
+        decode_frames(input, &|offset| format!("byte {}", offset))?;
+        return Ok(out);
+    }
+    for (index, line) in text(input)?.lines().enumerate() {
+        let line: String = line.split_whitespace().collect();
+        if line.is_empty() {
+            continue;
+        }
+        let frames = match binary {
+            BinaryFormat::Hex => from_hex(&line).ok_or("expected hex digits"),
+            _ => unbase64(&line).ok_or("expected base64"),
+        }
+        .map_err(|e| at_line(index, e))?;
+        decode_frames(&frames, &|offset| match offset {
+            0 => format!("line {}", index + 1),
+            _ => format!("line {}, byte {}", index + 1, offset),
+        })?;
+    }
+    Ok(out)
+}
+
+fn text(input: &[u8]) -> Result<&str, TranscodeError> {
+    std::str::from_utf8(input).map_err(|e| TranscodeError {
+        location: format!("byte {}", e.valid_up_to()),
+        reason: String::from("the input is not text"),
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (i, b)| group | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => text.push(BASE64[((group >> (18 - 6 * i)) & 0x3f) as usize] as char),
+                false => text.push('='),
+            }
+        }
+    }
+    text
+}
+
+fn unbase64(text: &str) -> Option<Vec<u8>> {
+    let chunks = text.as_bytes().chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+    let last = text.len() / 4;
+    let mut bytes = vec![];
+    for (n, chunk) in chunks.enumerate() {
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        // only the final chunk may be padded
+        if padding > 2 || (padding > 0 && n + 1 != last) {
+            return None;
+        }
+        let mut group = 0u32;
+        for (i, c) in chunk[..4 - padding].iter().enumerate() {
+            let sextet = BASE64.iter().position(|b| b == c)? as u32;
+            group |= sextet << (18 - 6 * i);
+        }
+        bytes.extend(&group.to_be_bytes()[1..4 - padding]);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sssc::args::BinaryFormat;
+    use crate::sssc::transcode::{base64, decode, encode, unbase64, TranscodeError};
+
+    const SCHEMA: &str = "protocol foo;
+        enum Side : u8 { BUY = 1, SELL = 2 }
+        message Order [1] { Side: side; u32: quantity; }
+        message Cancel [2] { u32: order; }";
+
+    const MESSAGES: &str = "{\"Order\":{\"side\":\"SELL\",\"quantity\":100}}\n\
+        {\"Cancel\":{\"order\":7}}\n";
+
+    #[test]
+    fn test_round_trip() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        let hex = encode(&protocol, BinaryFormat::Hex, 2, MESSAGES.as_bytes()).unwrap();
+        assert_eq!(
+            "01000264000000\n020007000000\n",
+            String::from_utf8(hex).unwrap()
+        );
+
+        for binary in [BinaryFormat::Hex, BinaryFormat::Raw, BinaryFormat::Base64] {
+            let frames = encode(&protocol, binary, 1, MESSAGES.as_bytes()).unwrap();
+            let json = decode(&protocol, binary, 1, &frames).unwrap();
+            assert_eq!(MESSAGES, String::from_utf8(json).unwrap(), "{:?}", binary);
+        }
+
+        // frames are found wherever they start, even several to a line
+        let json = decode(
+            &protocol,
+            BinaryFormat::Hex,
+            2,
+            b"0x0100 02 64000000 020007000000\n\n",
+        )
+        .unwrap();
+        assert_eq!(MESSAGES, String::from_utf8(json).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let protocol = crate::sssc::compile(SCHEMA).unwrap();
+        assert_eq!(
+            Err(TranscodeError {
+                location: String::from("line 2"),
+                reason: String::from("no message is called 'Trade'")
+            }),
+            encode(
+                &protocol,
+                BinaryFormat::Hex,
+                2,
+                b"{\"Cancel\":{\"order\":7}}\n{\"Trade\":{}}"
+            )
+        );
+        assert_eq!(
+            "line 1, byte 7: no message has the id 3",
+            decode(&protocol, BinaryFormat::Hex, 2, b"010002640000000300")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "byte 6: message 'Cancel' needs 4 bytes but the buffer only has 3",
+            decode(
+                &protocol,
+                BinaryFormat::Raw,
+                2,
+                &[2, 0, 7, 0, 0, 0, 2, 0, 7, 0, 0]
+            )
+            .unwrap_err()
+            .to_string()
+        );
+        assert!(decode(&protocol, BinaryFormat::Hex, 2, b"0100zz").is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        for (bytes, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (&[0xff, 0xfe, 0x00], "//4A"),
+        ] {
+            assert_eq!(text, base64(bytes));
+            assert_eq!(Some(bytes.to_vec()), unbase64(text));
+        }
+        assert_eq!(None, unbase64("Zm9"));
+        assert_eq!(None, unbase64("Zm!v"));
+        assert_eq!(None, unbase64("Z==="));
+        assert_eq!(None, unbase64("Zg==Zm9v"));
+    }
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AttributeValue {
+    Integer(usize),
+    Identifier(String),
+}
+
+/// A `name` or `name = value` pair, as found after a message id (`[1, size = 64]`) or as a
+/// field annotation (`@offset(12)`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeSyntax {
+    pub name: String,
+    pub value: Option<AttributeValue>,
+}
+
+impl AttributeSyntax {
+    pub fn new(name: String, value: Option<AttributeValue>) -> Self {
+        AttributeSyntax { name, value }
+    }
+}
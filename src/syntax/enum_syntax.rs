@@ -1,8 +1,11 @@
-use crate::syntax::{BuiltInType, EnumValue};
+use crate::data_types::{EnumKind, ScalarType};
+use crate::syntax::EnumValue;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumDeclarationSyntax {
     pub name: String,
-    pub underlying_type: BuiltInType,
-    pub value: Vec<EnumValue>,
+    pub kind: EnumKind,
+    pub underlying_type: ScalarType,
+    pub values: Vec<EnumValue>,
 }
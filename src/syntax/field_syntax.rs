@@ -1,8 +1,20 @@
 use crate::data_types::FieldType_;
+use crate::syntax::AttributeSyntax;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldDeclaration {
     pub name: String,
-    pub field_type: FieldType_,
+    pub field_type: FieldTypeSyntax,
     pub description: Option<String>,
+    pub attributes: Vec<AttributeSyntax>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FieldTypeSyntax {
+    BuiltIn(FieldType_),
+    /// A reference to a type declared in the protocol, such as a struct
+    Named(String),
 }
@@ -0,0 +1,8 @@
+use crate::syntax::MemberDeclaration;
+
+/// The `header { ... }` declaration, the fields written in front of every message of the protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeaderDeclarationSyntax {
+    pub members: Vec<MemberDeclaration>,
+}
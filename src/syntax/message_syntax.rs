@@ -1,13 +1,18 @@
 use crate::syntax::field_syntax::FieldDeclaration;
+use crate::syntax::AttributeSyntax;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageDeclarationSyntax {
     pub name: String,
     pub id: usize,
+    pub attributes: Vec<AttributeSyntax>,
     pub members: Vec<MemberDeclaration>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MemberDeclaration {
     Field(FieldDeclaration),
     Padding(usize),
@@ -1,29 +1,52 @@
+pub use attribute_syntax::{AttributeSyntax, AttributeValue};
 pub use enum_syntax::EnumDeclarationSyntax;
-pub use field_syntax::FieldDeclaration;
+pub use field_syntax::{FieldDeclaration, FieldTypeSyntax};
+pub use header_syntax::HeaderDeclarationSyntax;
 pub use message_syntax::{MemberDeclaration, MessageDeclarationSyntax};
+pub use struct_syntax::StructDeclarationSyntax;
+pub use union_syntax::UnionDeclarationSyntax;
 
+mod attribute_syntax;
 mod enum_syntax;
 mod field_syntax;
+mod header_syntax;
 mod message_syntax;
+mod struct_syntax;
+mod union_syntax;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DeclarationSyntax {
     Message(MessageDeclarationSyntax),
+    Struct(StructDeclarationSyntax),
+    Union(UnionDeclarationSyntax),
     Enum(EnumDeclarationSyntax),
+    Header(HeaderDeclarationSyntax),
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProtocolDeclarationSyntax {
     pub components: Vec<String>,
+    pub attributes: Vec<AttributeSyntax>,
 }
 
 impl ProtocolDeclarationSyntax {
     pub fn new(components: Vec<String>) -> Self {
-        ProtocolDeclarationSyntax { components }
+        Self::with_attributes(components, vec![])
+    }
+
+    pub fn with_attributes(components: Vec<String>, attributes: Vec<AttributeSyntax>) -> Self {
+        ProtocolDeclarationSyntax {
+            components,
+            attributes,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyntaxUnit {
     pub protocol: ProtocolDeclarationSyntax,
     pub declarations: Vec<DeclarationSyntax>,
@@ -42,28 +65,38 @@ impl SyntaxUnit {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Signedness {
     Signed,
     Unsigned,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum CustomType {
     Enum(EnumDeclarationSyntax),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumValue {
-    name: String,
-    value: String,
+    pub name: String,
+    /// Values without an explicit value are assigned one when the protocol is resolved
+    pub value: Option<usize>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Endianness {
     BigEndian,
     LittleEndian,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum BuiltInType {
     Integer {
         size_bits: usize,
@@ -75,7 +108,9 @@ pub enum BuiltInType {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum FixedStringType {
     Ascii { length_bytes: usize },
 }
@@ -0,0 +1,9 @@
+use crate::syntax::{AttributeSyntax, MemberDeclaration};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructDeclarationSyntax {
+    pub name: String,
+    pub attributes: Vec<AttributeSyntax>,
+    pub members: Vec<MemberDeclaration>,
+}
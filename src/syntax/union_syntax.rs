@@ -0,0 +1,10 @@
+use crate::syntax::FieldDeclaration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnionDeclarationSyntax {
+    pub name: String,
+    /// The enum whose value selects the active variant
+    pub discriminator_type: String,
+    pub variants: Vec<FieldDeclaration>,
+}
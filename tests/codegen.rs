@@ -0,0 +1,635 @@
+//! Generated code for `tests/codegen/schema.sss` is checked in under `tests/codegen/<lang>/` so
+//! changes to a generator show up in review, run with `SSS_UPDATE_GOLDEN=1` to rewrite it.
+//! `tests/codegen/big_endian/schema.sss` is big endian and its code is checked in under
+//! `tests/codegen/big_endian/<lang>/`.
+
+use sss::codegen::{Generator, GeneratorOptions};
+use std::path::Path;
+
+fn check_golden(generator: &dyn Generator, dir: &str) {
+    check_schema_golden(
+        "tests/codegen/schema.sss",
+        generator,
+        &GeneratorOptions::default(),
+        dir,
+    );
+}
+
+fn check_schema_golden(
+    schema: &str,
+    generator: &dyn Generator,
+    options: &GeneratorOptions,
+    dir: &str,
+) {
+    let schema = std::fs::read_to_string(schema).unwrap();
+    let protocol = sss::sssc::compile(&schema).unwrap();
+    let files = generator.generate(&protocol, options).unwrap();
+
+    let update = std::env::var_os("SSS_UPDATE_GOLDEN").is_some();
+    for file in files {
+        let path = Path::new("tests/codegen").join(dir).join(&file.path);
+        if update {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &file.contents).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            expected == file.contents,
+            "{} is out of date, run the tests with SSS_UPDATE_GOLDEN=1 to update it",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn test_rust_is_up_to_date() {
+    check_golden(&sss::codegen::rust::RustGenerator, "rust");
+}
+
+#[allow(dead_code)]
+mod rust {
+    include!("codegen/rust/test_codegen.rs");
+
+    #[test]
+    fn test_round_trip() {
+        let mut buf = [0u8; QuoteMut::SIZE];
+        let mut quote = QuoteMut::new(&mut buf).unwrap();
+        quote.set_sent_at(1_700_000_000_123_456_789);
+        quote.set_symbol("AAPL").unwrap();
+        quote.set_side(Side::Sell);
+        quote.bid_mut().set_value(1_234_500);
+        quote.bid_mut().set_currency("USD").unwrap();
+        quote.set_levels(&[-1, 0, 1]);
+        quote.set_weight(0.5);
+        quote.set_ttl(-250);
+
+        let view = QuoteView::new(&buf).unwrap();
+        assert_eq!(1_700_000_000_123_456_789, view.sent_at());
+        assert_eq!(
+            core::time::Duration::new(1_700_000_000, 123_456_789),
+            view.sent_at_since_epoch()
+        );
+        assert_eq!(Ok("AAPL"), view.symbol_str());
+        assert_eq!(b"AAPL\0\0\0\0", view.symbol());
+        assert_eq!(Ok(Side::Sell), view.side());
+        assert_eq!(1_234_500, view.bid().value());
+        assert_eq!(Ok("USD"), view.bid().currency_str());
+        assert_eq!(Ok(""), view.ask().currency_str());
+        assert_eq!([-1, 0, 1], view.levels());
+        assert_eq!(0.5, view.weight());
+        assert_eq!(-250, view.ttl());
+
+        assert_eq!(1, QuoteView::ID);
+        assert_eq!(&buf[16..17], &[2]);
+    }
+
+    #[test]
+    fn test_byte_order_is_little_endian() {
+        let mut buf = [0u8; RequestMut::SIZE];
+        RequestMut::new(&mut buf).unwrap().set_sequence(0x01020304);
+        assert_eq!([4, 3, 2, 1], buf[..4]);
+    }
+
+    #[test]
+    fn test_union_variants() {
+        let mut buf = [0u8; 48];
+        let mut request = RequestMut::new(&mut buf).unwrap();
+        request.set_body_cancel_id(99);
+        assert_eq!(Ok(BodyKind::Cancel), request.kind());
+        assert!(matches!(request.body(), Ok(BodyView::CancelId(99))));
+
+        let mut order = request.body_order_mut();
+        order.set_side(Side::Buy);
+        order.set_quantity(10);
+        assert_eq!(Ok(BodyKind::Order), request.kind());
+        match request.body() {
+            Ok(BodyView::Order(order)) => {
+                assert_eq!(Ok(Side::Buy), order.side());
+                assert_eq!(10, order.quantity());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        request.set_kind_raw(9);
+        assert_eq!(
+            Err(Error::InvalidEnumValue {
+                name: "Body",
+                value: 9
+            }),
+            request.body().map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_space_padding() {
+        let mut buf = [0u8; RequestMut::SIZE];
+        let mut request = RequestMut::new(&mut buf).unwrap();
+        request.set_venue("XLON").unwrap();
+        assert_eq!(b"XLON  ", request.venue());
+        assert_eq!(Ok("XLON"), request.venue_str());
+        request.set_body_ping("ab").unwrap();
+        assert_eq!(b"ab\0\0", &buf[7..11]);
+    }
+
+    #[test]
+    fn test_enums_and_bitflags() {
+        assert_eq!(Ok(Side::Buy), Side::try_from(1));
+        assert_eq!(
+            Err(Error::InvalidEnumValue {
+                name: "Side",
+                value: 7
+            }),
+            Side::try_from(7)
+        );
+        assert_eq!(2, u8::from(Side::Sell));
+        assert_eq!("SELL", Side::Sell.to_string());
+
+        let flags = Permissions::READ | Permissions::ADMIN;
+        assert_eq!(129, flags.bits());
+        assert!(flags.contains(Permissions::ADMIN));
+        assert!(!flags.contains(Permissions::WRITE));
+        assert_eq!(Permissions::WRITE, !flags);
+        assert_eq!("READ | ADMIN", flags.to_string());
+        assert_eq!(
+            "READ | 0x40",
+            Permissions::from_bits_retain(0x41).to_string()
+        );
+        assert!(Permissions::try_from(0x41).is_err());
+
+        let mut buf = [0u8; 48];
+        let mut request = RequestMut::new(&mut buf).unwrap();
+        request.set_permissions(flags);
+        request.set_type_(-3);
+        assert_eq!(flags, request.permissions());
+        assert_eq!(-3, request.type_());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(Error::BufferTooShort {
+                required: 67,
+                actual: 10
+            }),
+            QuoteView::new(&[0; 10]).map(|_| ())
+        );
+
+        let mut buf = [0u8; QuoteMut::SIZE];
+        let mut quote = QuoteMut::new(&mut buf).unwrap();
+        assert_eq!(
+            Err(Error::ValueTooLong {
+                field: "symbol",
+                max: 8,
+                actual: 9
+            }),
+            quote.set_symbol("TOOLONGXX")
+        );
+        assert_eq!(
+            Err(Error::InvalidAscii { field: "symbol" }),
+            quote.set_symbol("é")
+        );
+        buf[8] = 0xff;
+        assert_eq!(
+            Err(Error::InvalidAscii { field: "symbol" }),
+            QuoteView::new(&buf).unwrap().symbol_str()
+        );
+    }
+
+    #[test]
+    fn test_debug_decodes_fields() {
+        let mut buf = [0u8; QuoteMut::SIZE];
+        let mut quote = QuoteMut::new(&mut buf).unwrap();
+        quote.set_symbol("MSFT").unwrap();
+        quote.bid_mut().set_value(-12_345);
+        quote.set_side_raw(5);
+
+        let text = format!("{:?}", quote);
+        assert!(
+            text.starts_with("Quote { sent_at: 0, symbol: \"MSFT\""),
+            "{}",
+            text
+        );
+        assert!(text.contains("side: 5 (invalid)"), "{}", text);
+        assert!(text.contains("bid: Price { value: -1.2345"), "{}", text);
+        assert_eq!(text, quote.view().to_string());
+    }
+
+    #[derive(Default)]
+    struct Seen {
+        heartbeats: Vec<u32>,
+    }
+
+    impl Handler for Seen {
+        fn on_heartbeat(&mut self, message: HeartbeatView<'_>) {
+            self.heartbeats.push(message.sequence());
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut buf = [0u8; HeartbeatMut::FRAME_SIZE + QuoteMut::FRAME_SIZE + 1];
+        HeartbeatMut::new_frame(&mut buf).unwrap().set_sequence(42);
+        QuoteMut::new_frame(&mut buf[HeartbeatMut::FRAME_SIZE..]).unwrap();
+        assert_eq!(&buf[..4], &[24, 0, 3, 0]);
+
+        let mut seen = Seen::default();
+        let size = dispatch(&buf, &mut seen).unwrap();
+        assert_eq!(HeartbeatMut::FRAME_SIZE, size);
+        assert_eq!(Ok(QuoteMut::FRAME_SIZE), dispatch(&buf[size..], &mut seen));
+        assert_eq!(vec![42], seen.heartbeats);
+
+        buf[2] = 9;
+        assert_eq!(
+            Err(Error::UnknownMessageId { id: 9 }),
+            dispatch(&buf, &mut seen)
+        );
+        buf[0] = 4;
+        assert_eq!(
+            Err(Error::InvalidLength { length: 4 }),
+            dispatch(&buf, &mut seen)
+        );
+        assert_eq!(
+            Err(Error::BufferTooShort {
+                required: 24,
+                actual: 10
+            }),
+            HeartbeatMut::new_frame(&mut [0; 10]).map(|_| ())
+        );
+    }
+}
+
+/// The generated module must build without `std`
+#[test]
+fn test_rust_is_no_std() {
+    let dir = std::env::temp_dir().join(format!("sss-no-std-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let golden = std::fs::canonicalize("tests/codegen/rust/test_codegen.rs").unwrap();
+    let lib = dir.join("lib.rs");
+    std::fs::write(
+        &lib,
+        format!("#![no_std]\ninclude!({:?});\n", golden.to_str().unwrap()),
+    )
+    .unwrap();
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let output = std::process::Command::new(rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+        ])
+        .arg("--out-dir")
+        .arg(&dir)
+        .arg(&lib)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_cpp_is_up_to_date() {
+    check_golden(&sss::codegen::cpp::CppGenerator, "cpp");
+}
+
+/// Builds and runs `tests/codegen/cpp/round_trip.cpp`, skipped when there is no C++ compiler
+#[test]
+fn test_cpp_round_trip() {
+    let dir = std::env::temp_dir().join(format!("sss-cpp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join("round_trip");
+
+    let compiler = std::env::var("CXX").unwrap_or_else(|_| String::from("c++"));
+    let build = std::process::Command::new(&compiler)
+        .args(["-std=c++17", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&binary)
+        .arg("tests/codegen/cpp/round_trip.cpp")
+        .output();
+    let build = match build {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no C++ compiler '{}'", compiler);
+            return;
+        }
+    };
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stderr)
+    );
+
+    let run = std::process::Command::new(&binary).output().unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn test_c_is_up_to_date() {
+    check_golden(&sss::codegen::c::CGenerator, "c");
+}
+
+/// Builds and runs `tests/codegen/c/round_trip.c`, skipped when there is no C compiler
+#[test]
+fn test_c_round_trip() {
+    let dir = std::env::temp_dir().join(format!("sss-c-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join("round_trip");
+
+    let compiler = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let build = std::process::Command::new(&compiler)
+        .args(["-std=c99", "-pedantic", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&binary)
+        .arg("tests/codegen/c/round_trip.c")
+        .output();
+    let build = match build {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no C compiler '{}'", compiler);
+            return;
+        }
+    };
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stderr)
+    );
+
+    let run = std::process::Command::new(&binary).output().unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn test_python_is_up_to_date() {
+    check_golden(&sss::codegen::python::PythonGenerator, "python");
+}
+
+/// Runs `tests/codegen/python/round_trip.py`, skipped when there is no Python interpreter
+#[test]
+fn test_python_round_trip() {
+    let python = std::env::var("PYTHON").unwrap_or_else(|_| String::from("python3"));
+    let run = std::process::Command::new(&python)
+        .arg("tests/codegen/python/round_trip.py")
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .output();
+    let run = match run {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no Python interpreter '{}'", python);
+            return;
+        }
+    };
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn test_csharp_is_up_to_date() {
+    check_golden(&sss::codegen::csharp::CSharpGenerator, "csharp");
+}
+
+/// Builds and runs `tests/codegen/csharp/RoundTrip.cs`, skipped when there is no dotnet
+#[test]
+fn test_csharp_round_trip() {
+    let dir = std::env::temp_dir().join(format!("sss-csharp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for file in ["TestCodegen.cs", "RoundTrip.cs", "RoundTrip.csproj"] {
+        std::fs::copy(Path::new("tests/codegen/csharp").join(file), dir.join(file)).unwrap();
+    }
+    let out = dir.join("out");
+
+    let dotnet = std::env::var("DOTNET").unwrap_or_else(|_| String::from("dotnet"));
+    let build = std::process::Command::new(&dotnet)
+        .args(["build", "--nologo", "-o"])
+        .arg(&out)
+        .arg(dir.join("RoundTrip.csproj"))
+        .output();
+    let build = match build {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no dotnet '{}'", dotnet);
+            return;
+        }
+    };
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stdout)
+    );
+
+    let run = std::process::Command::new(&dotnet)
+        .arg(out.join("RoundTrip.dll"))
+        .output()
+        .unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn test_go_is_up_to_date() {
+    check_golden(&sss::codegen::go::GoGenerator, "go");
+}
+
+/// Vets the generated package and runs `tests/codegen/go/codegen/round_trip_test.go`, skipped
+/// when there is no go
+#[test]
+fn test_go_round_trip() {
+    let go = std::env::var("GO").unwrap_or_else(|_| String::from("go"));
+    for command in ["vet", "test"] {
+        let run = std::process::Command::new(&go)
+            .arg(command)
+            .arg(".")
+            .current_dir("tests/codegen/go/codegen")
+            .output();
+        let run = match run {
+            Ok(output) => output,
+            Err(_) => {
+                eprintln!("skipping, no go '{}'", go);
+                return;
+            }
+        };
+        assert!(
+            run.status.success(),
+            "go {}: {}{}",
+            command,
+            String::from_utf8_lossy(&run.stdout),
+            String::from_utf8_lossy(&run.stderr)
+        );
+    }
+}
+
+#[test]
+fn test_java_is_up_to_date() {
+    check_golden(&sss::codegen::java::JavaGenerator, "java");
+}
+
+/// Builds and runs `tests/codegen/java/src/test/java/test/codegen/RoundTrip.java`, skipped when
+/// there is no JDK
+#[test]
+fn test_java_round_trip() {
+    let dir = std::env::temp_dir().join(format!("sss-java-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut sources: Vec<_> = std::fs::read_dir("tests/codegen/java/src/main/java/test/codegen")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    sources.push("tests/codegen/java/src/test/java/test/codegen/RoundTrip.java".into());
+
+    let javac = std::env::var("JAVAC").unwrap_or_else(|_| String::from("javac"));
+    let build = std::process::Command::new(&javac)
+        .args(["-Xlint:all", "-Werror", "-d"])
+        .arg(&dir)
+        .args(&sources)
+        .output();
+    let build = match build {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no Java compiler '{}'", javac);
+            return;
+        }
+    };
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stderr)
+    );
+
+    let java = std::env::var("JAVA").unwrap_or_else(|_| String::from("java"));
+    let run = std::process::Command::new(&java)
+        .arg("-cp")
+        .arg(&dir)
+        .arg("test.codegen.RoundTrip")
+        .output()
+        .unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn test_wireshark_is_up_to_date() {
+    check_golden(&sss::codegen::wireshark::WiresharkGenerator, "wireshark");
+}
+
+#[test]
+fn test_docs_are_up_to_date() {
+    let options = GeneratorOptions {
+        parameters: vec![(
+            String::from("changelog"),
+            String::from("tests/codegen/changelog.md"),
+        )],
+    };
+    check_schema_golden(
+        "tests/codegen/schema.sss",
+        &sss::codegen::docs::DocsGenerator,
+        &options,
+        "docs",
+    );
+}
+
+#[test]
+fn test_big_endian_is_up_to_date() {
+    let generators: [(&dyn Generator, &str); 10] = [
+        (&sss::codegen::rust::RustGenerator, "rust"),
+        (&sss::codegen::cpp::CppGenerator, "cpp"),
+        (&sss::codegen::c::CGenerator, "c"),
+        (&sss::codegen::python::PythonGenerator, "python"),
+        (&sss::codegen::csharp::CSharpGenerator, "csharp"),
+        (&sss::codegen::go::GoGenerator, "go"),
+        (&sss::codegen::java::JavaGenerator, "java"),
+        (&sss::codegen::wireshark::WiresharkGenerator, "wireshark"),
+        (&sss::codegen::docs::DocsGenerator, "docs"),
+        (&sss::codegen::typescript::TypeScriptGenerator, "typescript"),
+    ];
+    for (generator, dir) in generators {
+        check_schema_golden(
+            "tests/codegen/big_endian/schema.sss",
+            generator,
+            &GeneratorOptions::default(),
+            &format!("big_endian/{}", dir),
+        );
+    }
+}
+
+#[allow(dead_code)]
+mod big_endian {
+    include!("codegen/big_endian/rust/test_big_endian.rs");
+
+    #[test]
+    fn test_round_trip() {
+        let mut buf = [0u8; QuoteMut::SIZE];
+        let mut quote = QuoteMut::new(&mut buf).unwrap();
+        quote.set_sent_at(0x0102030405060708);
+        quote.set_side(Side::Sell);
+        quote.bid_mut().set_value(-2);
+        quote.set_sequence(0x0a0b0c0d);
+        quote.set_levels(&[0x1122, -1]);
+        quote.set_ratio(1.0);
+        quote.set_ttl(-250);
+
+        assert_eq!([1, 2, 3, 4, 5, 6, 7, 8], buf[..8]);
+        assert_eq!([0, 2], buf[8..10]);
+        assert_eq!((-2i64).to_be_bytes(), buf[12..20]);
+        assert_eq!([0x0a, 0x0b, 0x0c, 0x0d], buf[23..27]);
+        assert_eq!([0x11, 0x22, 0xff, 0xff], buf[27..31]);
+        assert_eq!(1.0f32.to_be_bytes(), buf[31..35]);
+        assert_eq!((-250i64).to_be_bytes(), buf[43..51]);
+
+        let view = QuoteView::new(&buf).unwrap();
+        assert_eq!(0x0102030405060708, view.sent_at());
+        assert_eq!(Ok(Side::Sell), view.side());
+        assert_eq!(-2, view.bid().value());
+        assert_eq!(0x0a0b0c0d, view.sequence());
+        assert_eq!([0x1122, -1], view.levels());
+        assert_eq!(1.0, view.ratio());
+        assert_eq!(-250, view.ttl());
+    }
+}
+
+#[test]
+fn test_typescript_is_up_to_date() {
+    check_golden(&sss::codegen::typescript::TypeScriptGenerator, "typescript");
+}
+
+/// Runs `tests/codegen/typescript/round_trip.js`, skipped when there is no node
+#[test]
+fn test_typescript_round_trip() {
+    let node = std::env::var("NODE").unwrap_or_else(|_| String::from("node"));
+    let run = std::process::Command::new(&node)
+        .arg("tests/codegen/typescript/round_trip.js")
+        .output();
+    let run = match run {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping, no node '{}'", node);
+            return;
+        }
+    };
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
@@ -0,0 +1,278 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+#ifndef TEST_BIG_ENDIAN_H
+#define TEST_BIG_ENDIAN_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#ifndef SSS_C_RUNTIME_V1
+#define SSS_C_RUNTIME_V1
+
+#if defined(__BYTE_ORDER__) && defined(__ORDER_BIG_ENDIAN__) && __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+#define SSS_HOST_BIG_ENDIAN 1
+#else
+#define SSS_HOST_BIG_ENDIAN 0
+#endif
+
+static inline uint16_t sss_bswap16(uint16_t v) {
+    return (uint16_t)((v >> 8) | (v << 8));
+}
+
+static inline uint32_t sss_bswap32(uint32_t v) {
+    return ((v >> 24) & 0xffu) | ((v >> 8) & 0xff00u) | ((v << 8) & 0xff0000u) | (v << 24);
+}
+
+static inline uint64_t sss_bswap64(uint64_t v) {
+    return ((uint64_t)sss_bswap32((uint32_t)v) << 32) | sss_bswap32((uint32_t)(v >> 32));
+}
+
+static inline uint8_t sss_load8(const uint8_t *p, int big_endian) {
+    (void)big_endian;
+    return *p;
+}
+
+static inline uint16_t sss_load16(const uint8_t *p, int big_endian) {
+    uint16_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+}
+
+static inline uint32_t sss_load32(const uint8_t *p, int big_endian) {
+    uint32_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+}
+
+static inline uint64_t sss_load64(const uint8_t *p, int big_endian) {
+    uint64_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+}
+
+static inline void sss_store8(uint8_t *p, uint8_t v, int big_endian) {
+    (void)big_endian;
+    *p = v;
+}
+
+static inline void sss_store16(uint8_t *p, uint16_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store32(uint8_t *p, uint32_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store64(uint8_t *p, uint64_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline float sss_load_f32(const uint8_t *p, int big_endian) {
+    uint32_t bits = sss_load32(p, big_endian);
+    float v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline double sss_load_f64(const uint8_t *p, int big_endian) {
+    uint64_t bits = sss_load64(p, big_endian);
+    double v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline void sss_store_f32(uint8_t *p, float v, int big_endian) {
+    uint32_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store32(p, bits, big_endian);
+}
+
+static inline void sss_store_f64(uint8_t *p, double v, int big_endian) {
+    uint64_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store64(p, bits, big_endian);
+}
+
+/* The length of the text in an ascii field, which ends at the first nul byte and leaves out the
+   pad bytes ending it */
+static inline size_t sss_ascii_length(const uint8_t *p, size_t capacity, uint8_t pad) {
+    size_t length = 0;
+    while (length < capacity && p[length] != 0) {
+        length++;
+    }
+    while (length > 0 && p[length - 1] == pad) {
+        length--;
+    }
+    return length;
+}
+
+/* Copies a nul terminated string into an ascii field and fills the rest with pad bytes,
+   returns -1 without writing anything if the string is too long or isn't ascii */
+static inline int sss_store_ascii(uint8_t *p, size_t capacity, uint8_t pad, const char *value) {
+    size_t length = strlen(value);
+    size_t i;
+    if (length > capacity) {
+        return -1;
+    }
+    for (i = 0; i < length; i++) {
+        if ((unsigned char)value[i] > 0x7f) {
+            return -1;
+        }
+    }
+    memcpy(p, value, length);
+    memset(p + length, pad, capacity - length);
+    return 0;
+}
+
+#endif
+
+#define TEST_BIG_ENDIAN_BIG_ENDIAN 1
+
+/* enum Side, stored as a u16 */
+typedef enum test_big_endian_side {
+    TEST_BIG_ENDIAN_SIDE_BUY = 1,
+    TEST_BIG_ENDIAN_SIDE_SELL = 2,
+} test_big_endian_side;
+
+/* The name of the value in the schema, NULL for an undeclared value */
+static inline const char *test_big_endian_side_name(uint16_t value) {
+    switch (value) {
+        case 1: return "BUY";
+        case 2: return "SELL";
+        default: return NULL;
+    }
+}
+
+/* bitflags Permissions, stored as a u16 */
+typedef uint16_t test_big_endian_permissions;
+#define TEST_BIG_ENDIAN_PERMISSIONS_READ ((test_big_endian_permissions)1u)
+#define TEST_BIG_ENDIAN_PERMISSIONS_WRITE ((test_big_endian_permissions)2u)
+#define TEST_BIG_ENDIAN_PERMISSIONS_ADMIN ((test_big_endian_permissions)128u)
+
+/* struct Price */
+#define TEST_BIG_ENDIAN_PRICE_SIZE 11
+#define TEST_BIG_ENDIAN_PRICE_VALUE_OFFSET 0
+#define TEST_BIG_ENDIAN_PRICE_VALUE_EXPONENT (-4)
+#define TEST_BIG_ENDIAN_PRICE_CURRENCY_OFFSET 8
+#define TEST_BIG_ENDIAN_PRICE_CURRENCY_LENGTH 3
+
+/* value: decimal<i64, -4> at offset 0 */
+static inline int64_t test_big_endian_price_get_value(const uint8_t *buf) { return (int64_t)sss_load64(buf, 1); }
+static inline void test_big_endian_price_set_value(uint8_t *buf, int64_t value) { sss_store64(buf, (uint64_t)value, 1); }
+
+/* currency: ascii[3] at offset 8 */
+/* not nul terminated when the text fills the field, see _length */
+static inline const char *test_big_endian_price_currency(const uint8_t *buf) { return (const char *)(buf + 8); }
+static inline size_t test_big_endian_price_currency_length(const uint8_t *buf) { return sss_ascii_length(buf + 8, 3, 0); }
+static inline int test_big_endian_price_set_currency(uint8_t *buf, const char *value) { return sss_store_ascii(buf + 8, 3, 0, value); }
+
+/* the header in front of every message */
+#define TEST_BIG_ENDIAN_FRAME_HEADER_SIZE 4
+#define TEST_BIG_ENDIAN_FRAME_HEADER_LENGTH_OFFSET 0
+#define TEST_BIG_ENDIAN_FRAME_HEADER_MESSAGE_ID_OFFSET 2
+
+/* length: u16 at offset 0 */
+static inline uint16_t test_big_endian_frame_header_get_length(const uint8_t *buf) { return sss_load16(buf, 1); }
+static inline void test_big_endian_frame_header_set_length(uint8_t *buf, uint16_t value) { sss_store16(buf, value, 1); }
+
+/* message_id: u16 at offset 2 */
+static inline uint16_t test_big_endian_frame_header_get_message_id(const uint8_t *buf) { return sss_load16(buf + 2, 1); }
+static inline void test_big_endian_frame_header_set_message_id(uint8_t *buf, uint16_t value) { sss_store16(buf + 2, value, 1); }
+
+/* Laid out like the wire format, only valid to read or write directly when the
+ * host byte order matches the protocol's */
+typedef struct {
+    uint16_t length;
+    uint16_t message_id;
+} test_big_endian_frame_header_t;
+typedef char test_big_endian_frame_header_size_check[(sizeof(test_big_endian_frame_header_t) == 4) ? 1 : -1];
+typedef char test_big_endian_frame_header_length_check[(offsetof(test_big_endian_frame_header_t, length) == 0) ? 1 : -1];
+typedef char test_big_endian_frame_header_message_id_check[(offsetof(test_big_endian_frame_header_t, message_id) == 2) ? 1 : -1];
+
+/* message Quote [1] */
+#define TEST_BIG_ENDIAN_QUOTE_SIZE 51
+#define TEST_BIG_ENDIAN_QUOTE_ID 1u
+#define TEST_BIG_ENDIAN_QUOTE_FRAME_SIZE 55
+#define TEST_BIG_ENDIAN_QUOTE_SENT_AT_OFFSET 0
+#define TEST_BIG_ENDIAN_QUOTE_SIDE_OFFSET 8
+#define TEST_BIG_ENDIAN_QUOTE_PERMISSIONS_OFFSET 10
+#define TEST_BIG_ENDIAN_QUOTE_BID_OFFSET 12
+#define TEST_BIG_ENDIAN_QUOTE_SEQUENCE_OFFSET 23
+#define TEST_BIG_ENDIAN_QUOTE_LEVELS_OFFSET 27
+#define TEST_BIG_ENDIAN_QUOTE_LEVELS_LENGTH 2
+#define TEST_BIG_ENDIAN_QUOTE_RATIO_OFFSET 31
+#define TEST_BIG_ENDIAN_QUOTE_WEIGHT_OFFSET 35
+#define TEST_BIG_ENDIAN_QUOTE_TTL_OFFSET 43
+
+/* sent_at: timestamp<ns> at offset 0 */
+static inline uint64_t test_big_endian_quote_get_sent_at(const uint8_t *buf) { return (uint64_t)sss_load64(buf, 1); }
+static inline void test_big_endian_quote_set_sent_at(uint8_t *buf, uint64_t value) { sss_store64(buf, (uint64_t)value, 1); }
+
+/* side: Side at offset 8 */
+static inline test_big_endian_side test_big_endian_quote_get_side(const uint8_t *buf) { return (test_big_endian_side)sss_load16(buf + 8, 1); }
+static inline void test_big_endian_quote_set_side(uint8_t *buf, test_big_endian_side value) { sss_store16(buf + 8, (uint16_t)value, 1); }
+
+/* permissions: Permissions at offset 10 */
+static inline test_big_endian_permissions test_big_endian_quote_get_permissions(const uint8_t *buf) { return (test_big_endian_permissions)sss_load16(buf + 10, 1); }
+static inline void test_big_endian_quote_set_permissions(uint8_t *buf, test_big_endian_permissions value) { sss_store16(buf + 10, (uint16_t)value, 1); }
+
+/* bid: Price at offset 12 */
+static inline const uint8_t *test_big_endian_quote_bid(const uint8_t *buf) { return buf + 12; }
+static inline uint8_t *test_big_endian_quote_bid_mut(uint8_t *buf) { return buf + 12; }
+
+/* sequence: u32 at offset 23 */
+static inline uint32_t test_big_endian_quote_get_sequence(const uint8_t *buf) { return sss_load32(buf + 23, 1); }
+static inline void test_big_endian_quote_set_sequence(uint8_t *buf, uint32_t value) { sss_store32(buf + 23, value, 1); }
+
+/* levels: i16[2] at offset 27 */
+/* index is not bounds checked */
+static inline int16_t test_big_endian_quote_get_levels(const uint8_t *buf, size_t index) { return (int16_t)sss_load16(buf + 27 + index * 2, 1); }
+static inline void test_big_endian_quote_set_levels(uint8_t *buf, size_t index, int16_t value) { sss_store16(buf + 27 + index * 2, (uint16_t)value, 1); }
+
+/* ratio: f32 at offset 31 */
+static inline float test_big_endian_quote_get_ratio(const uint8_t *buf) { return sss_load_f32(buf + 31, 1); }
+static inline void test_big_endian_quote_set_ratio(uint8_t *buf, float value) { sss_store_f32(buf + 31, value, 1); }
+
+/* weight: f64 at offset 35 */
+static inline double test_big_endian_quote_get_weight(const uint8_t *buf) { return sss_load_f64(buf + 35, 1); }
+static inline void test_big_endian_quote_set_weight(uint8_t *buf, double value) { sss_store_f64(buf + 35, value, 1); }
+
+/* ttl: duration<us> at offset 43 */
+static inline int64_t test_big_endian_quote_get_ttl(const uint8_t *buf) { return (int64_t)sss_load64(buf + 43, 1); }
+static inline void test_big_endian_quote_set_ttl(uint8_t *buf, int64_t value) { sss_store64(buf + 43, (uint64_t)value, 1); }
+
+/* writes the header to the start of buf and returns the message after it, buf must hold
+ * TEST_BIG_ENDIAN_QUOTE_FRAME_SIZE bytes */
+static inline uint8_t *test_big_endian_quote_write_frame(uint8_t *buf) { test_big_endian_frame_header_set_message_id(buf, TEST_BIG_ENDIAN_QUOTE_ID); test_big_endian_frame_header_set_length(buf, TEST_BIG_ENDIAN_QUOTE_FRAME_SIZE); return buf + TEST_BIG_ENDIAN_FRAME_HEADER_SIZE; }
+
+/* callbacks for test_big_endian_dispatch, a NULL callback skips its message */
+typedef struct test_big_endian_handler {
+    void (*on_quote)(void *context, const uint8_t *message);
+} test_big_endian_handler;
+
+/* reads the header at the start of buf and calls the callback for its id with the message
+ * after it, returns the size of the frame or 0 when the size bytes of buf don't hold the
+ * whole frame, its length is too small for its message or no message has its id */
+static inline size_t test_big_endian_dispatch(const uint8_t *buf, size_t size, const test_big_endian_handler *handler, void *context) {
+    if (size < TEST_BIG_ENDIAN_FRAME_HEADER_SIZE) {
+        return 0;
+    }
+    size_t frame = (size_t)test_big_endian_frame_header_get_length(buf);
+    switch (test_big_endian_frame_header_get_message_id(buf)) {
+    case TEST_BIG_ENDIAN_QUOTE_ID:
+        if (frame < TEST_BIG_ENDIAN_QUOTE_FRAME_SIZE || size < frame) {
+            return 0;
+        }
+        if (handler->on_quote) {
+            handler->on_quote(context, buf + TEST_BIG_ENDIAN_FRAME_HEADER_SIZE);
+        }
+        return frame;
+    default:
+        return 0;
+    }
+}
+
+#endif /* TEST_BIG_ENDIAN_H */
@@ -0,0 +1,389 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+#pragma once
+
+#include <array>
+#include <chrono>
+#include <cstddef>
+#include <cstdint>
+#include <cstring>
+#include <string_view>
+#include <type_traits>
+
+#ifndef SSS_CPP_RUNTIME_V1
+#define SSS_CPP_RUNTIME_V1
+namespace sss {
+
+/// A fixed length ascii field, the text ends at the first nul byte or after `N` characters and
+/// leaves out the `Pad` characters ending it
+template <std::size_t N, char Pad = '\0'>
+class ascii_view {
+public:
+    explicit ascii_view(const std::byte* data) noexcept : data_(data) {}
+
+    static constexpr std::size_t capacity() noexcept { return N; }
+
+    std::string_view view() const noexcept {
+        const char* chars = reinterpret_cast<const char*>(data_);
+        std::size_t length = 0;
+        while (length < N && chars[length] != '\0') {
+            ++length;
+        }
+        while (length > 0 && chars[length - 1] == Pad) {
+            --length;
+        }
+        return std::string_view(chars, length);
+    }
+
+    operator std::string_view() const noexcept { return view(); }
+
+    const std::byte* data() const noexcept { return data_; }
+
+private:
+    const std::byte* data_;
+};
+
+template <typename Duration>
+using sys_time = std::chrono::time_point<std::chrono::system_clock, Duration>;
+
+namespace detail {
+
+template <std::size_t N> struct uint_of;
+template <> struct uint_of<1> { using type = std::uint8_t; };
+template <> struct uint_of<2> { using type = std::uint16_t; };
+template <> struct uint_of<4> { using type = std::uint32_t; };
+template <> struct uint_of<8> { using type = std::uint64_t; };
+
+/// Reads a `T` byte by byte, which is safe at any alignment and independent of the host byte order
+template <typename T, bool BigEndian>
+inline T load(const std::byte* p) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits = 0;
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        bits |= static_cast<U>(static_cast<U>(std::to_integer<std::uint8_t>(p[i])) << shift);
+    }
+    T value;
+    std::memcpy(&value, &bits, sizeof(T));
+    return value;
+}
+
+template <typename T, bool BigEndian>
+inline void store(std::byte* p, T value) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits;
+    std::memcpy(&bits, &value, sizeof(T));
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        p[i] = static_cast<std::byte>(bits >> shift);
+    }
+}
+
+/// Copies `value` into an ascii field and fills the rest with `pad`, fails if it doesn't fit
+inline bool store_ascii(std::byte* p, std::size_t capacity, char pad, std::string_view value) noexcept {
+    if (value.size() > capacity) {
+        return false;
+    }
+    for (char c : value) {
+        if (static_cast<unsigned char>(c) > 0x7f) {
+            return false;
+        }
+    }
+    std::memcpy(p, value.data(), value.size());
+    std::memset(p + value.size(), pad, capacity - value.size());
+    return true;
+}
+
+} // namespace detail
+} // namespace sss
+#endif
+
+namespace test::big_endian {
+
+inline constexpr bool big_endian = true;
+
+enum class Side : std::uint16_t {
+    BUY = 1,
+    SELL = 2,
+};
+
+/// The name of the value in the schema, empty for an undeclared value
+constexpr std::string_view to_string(Side value) noexcept {
+    switch (value) {
+        case Side::BUY: return "BUY";
+        case Side::SELL: return "SELL";
+    }
+    return {};
+}
+
+constexpr bool is_valid(Side value) noexcept {
+    return !to_string(value).empty();
+}
+
+enum class Permissions : std::uint16_t {
+    READ = 1,
+    WRITE = 2,
+    ADMIN = 128,
+};
+
+constexpr Permissions operator|(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) | static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator|=(Permissions& a, Permissions b) noexcept {
+    return a = a | b;
+}
+
+constexpr Permissions operator&(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) & static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator&=(Permissions& a, Permissions b) noexcept {
+    return a = a & b;
+}
+
+constexpr Permissions operator^(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) ^ static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator^=(Permissions& a, Permissions b) noexcept {
+    return a = a ^ b;
+}
+
+constexpr Permissions operator~(Permissions a) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(~static_cast<std::uint16_t>(a)));
+}
+
+/// True if every flag of `flags` is set in `value`
+constexpr bool has(Permissions value, Permissions flags) noexcept {
+    return (value & flags) == flags;
+}
+
+constexpr bool any(Permissions value) noexcept {
+    return static_cast<std::uint16_t>(value) != 0;
+}
+
+/// struct Price
+class Price {
+public:
+    static constexpr std::size_t size = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    static constexpr int value_exponent = -4;
+
+    struct offsets {
+        static constexpr std::size_t value = 0;
+        static constexpr std::size_t currency = 8;
+    };
+
+    explicit Price(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// decimal<i64, -4>, offset 0, 8 bytes
+    std::int64_t value() const noexcept {
+        return sss::detail::load<std::int64_t, big_endian>(data_ + 0);
+    }
+    void set_value(std::int64_t value) noexcept {
+        sss::detail::store<std::int64_t, big_endian>(data_ + 0, value);
+    }
+
+    /// ascii[3], offset 8, 3 bytes
+    sss::ascii_view<3> currency() const noexcept {
+        return sss::ascii_view<3>(data_ + 8);
+    }
+    /// Fails if the value is longer than the field or isn't ascii
+    bool set_currency(std::string_view value) noexcept {
+        return sss::detail::store_ascii(data_ + 8, 3, '\0', value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Price::offsets::value + sizeof(std::int64_t) == 8, "Price.value must end at byte 8");
+static_assert(Price::offsets::currency + sizeof(char[3]) == 11, "Price.currency must end at byte 11");
+static_assert(sizeof(std::int64_t) + sizeof(char[3]) == Price::size, "Price must be 11 bytes");
+
+/// the header in front of every message
+class FrameHeader {
+public:
+    static constexpr std::size_t size = 4;
+
+    struct offsets {
+        static constexpr std::size_t length = 0;
+        static constexpr std::size_t message_id = 2;
+    };
+
+    explicit FrameHeader(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// u16, offset 0, 2 bytes
+    std::uint16_t length() const noexcept {
+        return sss::detail::load<std::uint16_t, big_endian>(data_ + 0);
+    }
+    void set_length(std::uint16_t value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 0, value);
+    }
+
+    /// u16, offset 2, 2 bytes
+    std::uint16_t message_id() const noexcept {
+        return sss::detail::load<std::uint16_t, big_endian>(data_ + 2);
+    }
+    void set_message_id(std::uint16_t value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 2, value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(FrameHeader::offsets::length + sizeof(std::uint16_t) == 2, "FrameHeader.length must end at byte 2");
+static_assert(FrameHeader::offsets::message_id + sizeof(std::uint16_t) == 4, "FrameHeader.message_id must end at byte 4");
+static_assert(sizeof(std::uint16_t) + sizeof(std::uint16_t) == FrameHeader::size, "FrameHeader must be 4 bytes");
+
+/// message Quote [1]
+class Quote {
+public:
+    static constexpr std::size_t size = 51;
+    static constexpr std::uint32_t id = 1;
+    /// The size of the header followed by the message
+    static constexpr std::size_t frame_size = 55;
+
+    struct offsets {
+        static constexpr std::size_t sent_at = 0;
+        static constexpr std::size_t side = 8;
+        static constexpr std::size_t permissions = 10;
+        static constexpr std::size_t bid = 12;
+        static constexpr std::size_t sequence = 23;
+        static constexpr std::size_t levels = 27;
+        static constexpr std::size_t ratio = 31;
+        static constexpr std::size_t weight = 35;
+        static constexpr std::size_t ttl = 43;
+    };
+
+    explicit Quote(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Writes the header to the start of `data` and returns the message after it, `data` must
+    /// hold `frame_size` bytes
+    static Quote write_frame(std::byte* data) noexcept {
+        FrameHeader header(data);
+        header.set_message_id(static_cast<std::uint16_t>(id));
+        header.set_length(static_cast<std::uint16_t>(frame_size));
+        return Quote(data + FrameHeader::size);
+    }
+
+    /// timestamp<ns>, offset 0, 8 bytes
+    sss::sys_time<std::chrono::nanoseconds> sent_at() const noexcept {
+        return sss::sys_time<std::chrono::nanoseconds>(std::chrono::nanoseconds(static_cast<std::chrono::nanoseconds::rep>(sss::detail::load<std::uint64_t, big_endian>(data_ + 0))));
+    }
+    void set_sent_at(sss::sys_time<std::chrono::nanoseconds> value) noexcept {
+        sss::detail::store<std::uint64_t, big_endian>(data_ + 0, static_cast<std::uint64_t>(value.time_since_epoch().count()));
+    }
+
+    /// Side, offset 8, 2 bytes
+    Side side() const noexcept {
+        return static_cast<Side>(sss::detail::load<std::uint16_t, big_endian>(data_ + 8));
+    }
+    void set_side(Side value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 8, static_cast<std::uint16_t>(value));
+    }
+
+    /// Permissions, offset 10, 2 bytes
+    Permissions permissions() const noexcept {
+        return static_cast<Permissions>(sss::detail::load<std::uint16_t, big_endian>(data_ + 10));
+    }
+    void set_permissions(Permissions value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 10, static_cast<std::uint16_t>(value));
+    }
+
+    /// Price, offset 12, 11 bytes
+    Price bid() const noexcept {
+        return Price(data_ + 12);
+    }
+
+    /// u32, offset 23, 4 bytes
+    std::uint32_t sequence() const noexcept {
+        return sss::detail::load<std::uint32_t, big_endian>(data_ + 23);
+    }
+    void set_sequence(std::uint32_t value) noexcept {
+        sss::detail::store<std::uint32_t, big_endian>(data_ + 23, value);
+    }
+
+    /// i16[2], offset 27, 4 bytes
+    std::array<std::int16_t, 2> levels() const noexcept {
+        std::array<std::int16_t, 2> value;
+        for (std::size_t i = 0; i < 2; ++i) {
+            value[i] = sss::detail::load<std::int16_t, big_endian>(data_ + 27 + i * 2);
+        }
+        return value;
+    }
+    void set_levels(const std::array<std::int16_t, 2>& value) noexcept {
+        for (std::size_t i = 0; i < 2; ++i) {
+            sss::detail::store<std::int16_t, big_endian>(data_ + 27 + i * 2, value[i]);
+        }
+    }
+
+    /// f32, offset 31, 4 bytes
+    float ratio() const noexcept {
+        return sss::detail::load<float, big_endian>(data_ + 31);
+    }
+    void set_ratio(float value) noexcept {
+        sss::detail::store<float, big_endian>(data_ + 31, value);
+    }
+
+    /// f64, offset 35, 8 bytes
+    double weight() const noexcept {
+        return sss::detail::load<double, big_endian>(data_ + 35);
+    }
+    void set_weight(double value) noexcept {
+        sss::detail::store<double, big_endian>(data_ + 35, value);
+    }
+
+    /// duration<us>, offset 43, 8 bytes
+    std::chrono::microseconds ttl() const noexcept {
+        return std::chrono::microseconds(static_cast<std::chrono::microseconds::rep>(sss::detail::load<std::int64_t, big_endian>(data_ + 43)));
+    }
+    void set_ttl(std::chrono::microseconds value) noexcept {
+        sss::detail::store<std::int64_t, big_endian>(data_ + 43, static_cast<std::int64_t>(value.count()));
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Quote::offsets::sent_at + sizeof(std::uint64_t) == 8, "Quote.sent_at must end at byte 8");
+static_assert(Quote::offsets::side + sizeof(Side) == 10, "Quote.side must end at byte 10");
+static_assert(Quote::offsets::permissions + sizeof(Permissions) == 12, "Quote.permissions must end at byte 12");
+static_assert(Quote::offsets::bid + Price::size == 23, "Quote.bid must end at byte 23");
+static_assert(Quote::offsets::sequence + sizeof(std::uint32_t) == 27, "Quote.sequence must end at byte 27");
+static_assert(Quote::offsets::levels + sizeof(std::int16_t[2]) == 31, "Quote.levels must end at byte 31");
+static_assert(Quote::offsets::ratio + sizeof(float) == 35, "Quote.ratio must end at byte 35");
+static_assert(Quote::offsets::weight + sizeof(double) == 43, "Quote.weight must end at byte 43");
+static_assert(Quote::offsets::ttl + sizeof(std::int64_t) == 51, "Quote.ttl must end at byte 51");
+static_assert(sizeof(std::uint64_t) + sizeof(Side) + sizeof(Permissions) + Price::size + sizeof(std::uint32_t) + sizeof(std::int16_t[2]) + sizeof(float) + sizeof(double) + sizeof(std::int64_t) == Quote::size, "Quote must be 51 bytes");
+
+/// Reads the header at the start of `data` and calls `handler` with the message after it, as
+/// one of `Quote`. A message the handler can't be called with is skipped.
+/// Returns the size of the frame, or 0 when the `size` bytes of `data` don't hold the whole
+/// frame, its length is too small for its message or no message has its id.
+template <typename Handler>
+std::size_t dispatch(std::byte* data, std::size_t size, Handler&& handler) {
+    if (size < FrameHeader::size) {
+        return 0;
+    }
+    FrameHeader header(data);
+    std::size_t frame = header.length();
+    switch (header.message_id()) {
+    case Quote::id:
+        if (frame < Quote::frame_size || size < frame) {
+            return 0;
+        }
+        if constexpr (std::is_invocable_v<Handler&, Quote>) {
+            handler(Quote(data + FrameHeader::size));
+        }
+        return frame;
+    default:
+        return 0;
+    }
+}
+
+} // namespace test::big_endian
@@ -0,0 +1,468 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+using System;
+using System.Buffers.Binary;
+using System.Diagnostics.CodeAnalysis;
+using System.Text;
+
+namespace Test.BigEndian
+{
+    /// <summary>Reads and writes fixed length ascii fields, the text ends at the first nul byte, without the pad bytes ending it</summary>
+    internal static class AsciiField
+    {
+        public static string Read(ReadOnlySpan<byte> field, byte pad)
+        {
+            int length = field.IndexOf((byte)0);
+            return Encoding.ASCII.GetString((length < 0 ? field : field.Slice(0, length)).TrimEnd(pad));
+        }
+
+        public static void Write(Span<byte> field, byte pad, string value, string name)
+        {
+            if (value.Length > field.Length)
+            {
+                throw new ArgumentException($"{name} holds at most {field.Length} characters but the value has {value.Length}", nameof(value));
+            }
+            foreach (char c in value)
+            {
+                if (c > 0x7f)
+                {
+                    throw new ArgumentException($"{name} only holds ascii characters", nameof(value));
+                }
+            }
+            field.Fill(pad);
+            for (int i = 0; i < value.Length; i++)
+            {
+                field[i] = (byte)value[i];
+            }
+        }
+    }
+
+    /// <summary>enum Side, stored as a u16</summary>
+    public enum Side : ushort
+    {
+        Buy = 1,
+        Sell = 2,
+    }
+
+    /// <summary>bitflags Permissions, stored as a u16</summary>
+    [Flags]
+    public enum Permissions : ushort
+    {
+        Read = 1,
+        Write = 2,
+        Admin = 128,
+    }
+
+    /// <summary>Reads struct Price in place</summary>
+    public readonly ref struct PriceView
+    {
+        public const int Size = 11;
+        public const int ValueOffset = 0;
+        public const int ValueExponent = -4;
+        public const int CurrencyOffset = 8;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public PriceView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Price needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out PriceView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new PriceView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>decimal&lt;i64, -4&gt; at offset 0</summary>
+        public long Value => BinaryPrimitives.ReadInt64BigEndian(_buffer.Slice(0));
+
+        /// <summary>value as a decimal</summary>
+        public decimal ValueDecimal => Value * 0.0001m;
+
+        /// <summary>ascii[3] at offset 8</summary>
+        public string Currency => AsciiField.Read(_buffer.Slice(8, 3), 0);
+        public ReadOnlySpan<byte> CurrencyBytes => _buffer.Slice(8, 3);
+    }
+
+    /// <summary>Writes struct Price in place</summary>
+    public readonly ref struct PriceWriter
+    {
+        public const int Size = PriceView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public PriceWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Price needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public PriceView AsView() => new PriceView(_buffer);
+
+        /// <summary>decimal&lt;i64, -4&gt; at offset 0</summary>
+        public long Value
+        {
+            get => AsView().Value;
+            set => BinaryPrimitives.WriteInt64BigEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>value as a decimal</summary>
+        /// <exception cref="ArgumentException">The value isn't a multiple of the field's scale</exception>
+        /// <exception cref="OverflowException">The mantissa doesn't fit the field</exception>
+        public decimal ValueDecimal
+        {
+            get => AsView().ValueDecimal;
+            set
+            {
+                var mantissa = value / 0.0001m;
+                if (mantissa != decimal.Truncate(mantissa))
+                {
+                    throw new ArgumentException($"value only holds multiples of 0.0001, not {value}", nameof(value));
+                }
+                Value = checked((long)mantissa);
+            }
+        }
+
+        /// <summary>ascii[3] at offset 8</summary>
+        /// <exception cref="ArgumentException">The value is too long or isn't ascii</exception>
+        public string Currency
+        {
+            get => AsView().Currency;
+            set => AsciiField.Write(_buffer.Slice(8, 3), 0, value, "currency");
+        }
+    }
+
+    /// <summary>Reads struct FrameHeader in place</summary>
+    public readonly ref struct FrameHeaderView
+    {
+        public const int Size = 4;
+        public const int LengthOffset = 0;
+        public const int MessageIdOffset = 2;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public FrameHeaderView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"FrameHeader needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out FrameHeaderView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new FrameHeaderView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>u16 at offset 0</summary>
+        public ushort Length => BinaryPrimitives.ReadUInt16BigEndian(_buffer.Slice(0));
+
+        /// <summary>u16 at offset 2</summary>
+        public ushort MessageId => BinaryPrimitives.ReadUInt16BigEndian(_buffer.Slice(2));
+    }
+
+    /// <summary>Writes struct FrameHeader in place</summary>
+    public readonly ref struct FrameHeaderWriter
+    {
+        public const int Size = FrameHeaderView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public FrameHeaderWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"FrameHeader needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public FrameHeaderView AsView() => new FrameHeaderView(_buffer);
+
+        /// <summary>u16 at offset 0</summary>
+        public ushort Length
+        {
+            get => AsView().Length;
+            set => BinaryPrimitives.WriteUInt16BigEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>u16 at offset 2</summary>
+        public ushort MessageId
+        {
+            get => AsView().MessageId;
+            set => BinaryPrimitives.WriteUInt16BigEndian(_buffer.Slice(2), value);
+        }
+    }
+
+    /// <summary>Reads message Quote [1] in place</summary>
+    public readonly ref struct QuoteView
+    {
+        public const int Size = 51;
+        public const uint Id = 1;
+        public const int FrameSize = 55;
+        public const int SentAtOffset = 0;
+        public const int SideOffset = 8;
+        public const int PermissionsOffset = 10;
+        public const int BidOffset = 12;
+        public const int SequenceOffset = 23;
+        public const int LevelsOffset = 27;
+        public const int LevelsLength = 2;
+        public const int RatioOffset = 31;
+        public const int WeightOffset = 35;
+        public const int TtlOffset = 43;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public QuoteView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Quote needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out QuoteView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new QuoteView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>timestamp&lt;ns&gt; at offset 0</summary>
+        public ulong SentAt => BinaryPrimitives.ReadUInt64BigEndian(_buffer.Slice(0));
+
+        /// <summary>sent_at as a DateTimeOffset</summary>
+        public DateTimeOffset SentAtTime => DateTimeOffset.UnixEpoch.AddTicks((long)SentAt / TimeSpan.NanosecondsPerTick);
+
+        /// <summary>Side at offset 8</summary>
+        public Side Side => (Side)BinaryPrimitives.ReadUInt16BigEndian(_buffer.Slice(8));
+
+        /// <summary>Permissions at offset 10</summary>
+        public Permissions Permissions => (Permissions)BinaryPrimitives.ReadUInt16BigEndian(_buffer.Slice(10));
+
+        /// <summary>Price at offset 12</summary>
+        public PriceView Bid => new PriceView(_buffer.Slice(12, 11));
+
+        /// <summary>u32 at offset 23</summary>
+        public uint Sequence => BinaryPrimitives.ReadUInt32BigEndian(_buffer.Slice(23));
+
+        /// <summary>i16[2] at offset 27</summary>
+        public short GetLevels(int index)
+        {
+            if ((uint)index >= LevelsLength)
+            {
+                throw new ArgumentOutOfRangeException(nameof(index));
+            }
+            return BinaryPrimitives.ReadInt16BigEndian(_buffer.Slice(27 + index * 2));
+        }
+
+        /// <summary>f32 at offset 31</summary>
+        public float Ratio => BinaryPrimitives.ReadSingleBigEndian(_buffer.Slice(31));
+
+        /// <summary>f64 at offset 35</summary>
+        public double Weight => BinaryPrimitives.ReadDoubleBigEndian(_buffer.Slice(35));
+
+        /// <summary>duration&lt;us&gt; at offset 43</summary>
+        public long Ttl => BinaryPrimitives.ReadInt64BigEndian(_buffer.Slice(43));
+
+        /// <summary>ttl as a TimeSpan</summary>
+        public TimeSpan TtlTimeSpan => TimeSpan.FromTicks(Ttl * TimeSpan.TicksPerMicrosecond);
+    }
+
+    /// <summary>Writes message Quote [1] in place</summary>
+    public readonly ref struct QuoteWriter
+    {
+        public const int Size = QuoteView.Size;
+        public const uint Id = QuoteView.Id;
+        public const int FrameSize = QuoteView.FrameSize;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public QuoteWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Quote needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public QuoteView AsView() => new QuoteView(_buffer);
+
+        /// <summary>Writes the header's id and length to the start of buffer and returns the message after it</summary>
+        /// <exception cref="ArgumentException">The buffer is shorter than FrameSize</exception>
+        public static QuoteWriter WriteFrame(Span<byte> buffer)
+        {
+            if (buffer.Length < FrameSize)
+            {
+                throw new ArgumentException($"the frame of Quote needs {FrameSize} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            var header = new FrameHeaderWriter(buffer);
+            header.MessageId = (ushort)Id;
+            header.Length = (ushort)FrameSize;
+            return new QuoteWriter(buffer.Slice(FrameHeaderView.Size));
+        }
+
+        /// <summary>timestamp&lt;ns&gt; at offset 0</summary>
+        public ulong SentAt
+        {
+            get => AsView().SentAt;
+            set => BinaryPrimitives.WriteUInt64BigEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>sent_at as a DateTimeOffset</summary>
+        public DateTimeOffset SentAtTime
+        {
+            get => AsView().SentAtTime;
+            set => SentAt = (ulong)((value - DateTimeOffset.UnixEpoch).Ticks * TimeSpan.NanosecondsPerTick);
+        }
+
+        /// <summary>Side at offset 8</summary>
+        public Side Side
+        {
+            get => AsView().Side;
+            set => BinaryPrimitives.WriteUInt16BigEndian(_buffer.Slice(8), (ushort)value);
+        }
+
+        /// <summary>Permissions at offset 10</summary>
+        public Permissions Permissions
+        {
+            get => AsView().Permissions;
+            set => BinaryPrimitives.WriteUInt16BigEndian(_buffer.Slice(10), (ushort)value);
+        }
+
+        /// <summary>Price at offset 12</summary>
+        public PriceWriter Bid => new PriceWriter(_buffer.Slice(12, 11));
+
+        /// <summary>u32 at offset 23</summary>
+        public uint Sequence
+        {
+            get => AsView().Sequence;
+            set => BinaryPrimitives.WriteUInt32BigEndian(_buffer.Slice(23), value);
+        }
+
+        /// <summary>i16[2] at offset 27</summary>
+        public short GetLevels(int index) => AsView().GetLevels(index);
+
+        public void SetLevels(int index, short value)
+        {
+            if ((uint)index >= QuoteView.LevelsLength)
+            {
+                throw new ArgumentOutOfRangeException(nameof(index));
+            }
+            BinaryPrimitives.WriteInt16BigEndian(_buffer.Slice(27 + index * 2), value);
+        }
+
+        /// <summary>f32 at offset 31</summary>
+        public float Ratio
+        {
+            get => AsView().Ratio;
+            set => BinaryPrimitives.WriteSingleBigEndian(_buffer.Slice(31), value);
+        }
+
+        /// <summary>f64 at offset 35</summary>
+        public double Weight
+        {
+            get => AsView().Weight;
+            set => BinaryPrimitives.WriteDoubleBigEndian(_buffer.Slice(35), value);
+        }
+
+        /// <summary>duration&lt;us&gt; at offset 43</summary>
+        public long Ttl
+        {
+            get => AsView().Ttl;
+            set => BinaryPrimitives.WriteInt64BigEndian(_buffer.Slice(43), value);
+        }
+
+        /// <summary>ttl as a TimeSpan</summary>
+        public TimeSpan TtlTimeSpan
+        {
+            get => AsView().TtlTimeSpan;
+            set => Ttl = value.Ticks / TimeSpan.TicksPerMicrosecond;
+        }
+    }
+
+    /// <summary>Callbacks for Dispatcher.Dispatch, a message without an override is ignored</summary>
+    public interface IHandler
+    {
+        void OnQuote(QuoteView message) { }
+    }
+
+    /// <summary>Calls a handler with the message of each frame</summary>
+    public static class Dispatcher
+    {
+        /// <summary>Reads the header at the start of buffer and calls handler with the message after it, returns the size of the frame</summary>
+        /// <exception cref="ArgumentException">The buffer doesn't hold the whole frame, its length is too small for its message or no message has its id</exception>
+        public static int Dispatch(ReadOnlySpan<byte> buffer, IHandler handler)
+        {
+            var header = new FrameHeaderView(buffer);
+            long frame = header.Length;
+            switch ((ulong)header.MessageId)
+            {
+                case QuoteView.Id:
+                    CheckFrame(buffer, frame, QuoteView.FrameSize);
+                    handler.OnQuote(new QuoteView(buffer.Slice(FrameHeaderView.Size)));
+                    return (int)frame;
+                default:
+                    throw new ArgumentException($"no message has the id {header.MessageId}", nameof(buffer));
+            }
+        }
+
+        private static void CheckFrame(ReadOnlySpan<byte> buffer, long frame, int size)
+        {
+            if (frame < size)
+            {
+                throw new ArgumentException($"a frame length of {frame} is too small for a message of {size} bytes", nameof(buffer));
+            }
+            if (frame > buffer.Length)
+            {
+                throw new ArgumentException($"a frame of {frame} bytes overruns a buffer of {buffer.Length}", nameof(buffer));
+            }
+        }
+    }
+}
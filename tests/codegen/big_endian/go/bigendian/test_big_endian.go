@@ -0,0 +1,453 @@
+// Code generated by sssc 0.1.0 from protocol test.big_endian. DO NOT EDIT.
+
+// Package bigendian reads and writes the types of protocol test.big_endian.
+package bigendian
+
+import (
+	"encoding/binary"
+	"fmt"
+	"io"
+	"math"
+	"strings"
+	"time"
+)
+
+var byteOrder = binary.BigEndian
+
+// shortBuffer is returned when a buffer is too short for the type read from it.
+func shortBuffer(name string, size, length int) error {
+	return fmt.Errorf("bigendian: %s needs %d bytes but the buffer holds %d: %w", name, size, length, io.ErrUnexpectedEOF)
+}
+
+// getASCII returns the text of an ascii field, which ends at the first nul byte, without
+// the pad bytes ending it.
+func getASCII(b []byte, pad byte) string {
+	n := 0
+	for n < len(b) && b[n] != 0 {
+		n++
+	}
+	for n > 0 && b[n-1] == pad {
+		n--
+	}
+	return string(b[:n])
+}
+
+// setASCII writes s to an ascii field, followed by pad bytes.
+func setASCII(b []byte, pad byte, s, name string) error {
+	if len(s) > len(b) {
+		return fmt.Errorf("bigendian: %s holds at most %d characters but the value has %d", name, len(b), len(s))
+	}
+	for i := 0; i < len(s); i++ {
+		if s[i] > 0x7f {
+			return fmt.Errorf("bigendian: %s only holds ascii characters", name)
+		}
+	}
+	for i := copy(b, s); i < len(b); i++ {
+		b[i] = pad
+	}
+	return nil
+}
+
+// Side is enum Side, stored as a u16.
+type Side uint16
+
+const (
+	SideBuy  Side = 1
+	SideSell Side = 2
+)
+
+// String returns the name of v as declared in the schema.
+func (v Side) String() string {
+	switch v {
+	case SideBuy:
+		return "BUY"
+	case SideSell:
+		return "SELL"
+	}
+	return fmt.Sprintf("Side(%d)", uint16(v))
+}
+
+// Permissions is bitflags Permissions, stored as a u16.
+type Permissions uint16
+
+const (
+	PermissionsRead  Permissions = 1
+	PermissionsWrite Permissions = 2
+	PermissionsAdmin Permissions = 128
+)
+
+// Has reports whether every flag of flags is set in v.
+func (v Permissions) Has(flags Permissions) bool { return v&flags == flags }
+
+// String joins the names of the flags set in v with |.
+func (v Permissions) String() string {
+	var names []string
+	if v&PermissionsRead != 0 {
+		names = append(names, "READ")
+	}
+	if v&PermissionsWrite != 0 {
+		names = append(names, "WRITE")
+	}
+	if v&PermissionsAdmin != 0 {
+		names = append(names, "ADMIN")
+	}
+	if rest := v &^ 0x83; rest != 0 {
+		names = append(names, fmt.Sprintf("%#x", uint16(rest)))
+	}
+	return strings.Join(names, "|")
+}
+
+// Price holds struct Price.
+type Price struct {
+	Value    int64
+	Currency string
+}
+
+// Layout of Price.
+const (
+	PriceSize           = 11
+	PriceValueOffset    = 0
+	PriceValueExponent  = -4
+	PriceCurrencyOffset = 8
+)
+
+// PriceView reads and writes struct Price in place.
+type PriceView []byte
+
+// NewPriceView views the start of b, which must hold at least PriceSize bytes.
+func NewPriceView(b []byte) (PriceView, error) {
+	if len(b) < PriceSize {
+		return nil, shortBuffer("Price", PriceSize, len(b))
+	}
+	return PriceView(b[:PriceSize]), nil
+}
+
+// Value reads the decimal<i64, -4> at offset 0.
+func (v PriceView) Value() int64 { return int64(byteOrder.Uint64(v[0:])) }
+
+// SetValue writes the decimal<i64, -4> at offset 0.
+func (v PriceView) SetValue(x int64) { byteOrder.PutUint64(v[0:], uint64(x)) }
+
+// Currency reads the ascii[3] at offset 8.
+func (v PriceView) Currency() string { return getASCII(v[8:11], 0) }
+
+// SetCurrency writes the ascii[3] at offset 8, it fails when s is too long or isn't ascii.
+func (v PriceView) SetCurrency(s string) error { return setASCII(v[8:11], 0, s, "currency") }
+
+// MarshalBinary encodes m into a new buffer of PriceSize bytes.
+func (m Price) MarshalBinary() ([]byte, error) {
+	b := make([]byte, PriceSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least PriceSize bytes.
+func (m *Price) UnmarshalBinary(data []byte) error {
+	v, err := NewPriceView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+func (m *Price) decode(v PriceView) {
+	m.Value = v.Value()
+	m.Currency = v.Currency()
+}
+
+func (m *Price) encode(v PriceView) error {
+	v.SetValue(m.Value)
+	if err := v.SetCurrency(m.Currency); err != nil {
+		return err
+	}
+	return nil
+}
+
+// FrameHeader holds struct FrameHeader.
+type FrameHeader struct {
+	Length    uint16
+	MessageID uint16
+}
+
+// Layout of FrameHeader.
+const (
+	FrameHeaderSize            = 4
+	FrameHeaderLengthOffset    = 0
+	FrameHeaderMessageIDOffset = 2
+)
+
+// FrameHeaderView reads and writes struct FrameHeader in place.
+type FrameHeaderView []byte
+
+// NewFrameHeaderView views the start of b, which must hold at least FrameHeaderSize bytes.
+func NewFrameHeaderView(b []byte) (FrameHeaderView, error) {
+	if len(b) < FrameHeaderSize {
+		return nil, shortBuffer("FrameHeader", FrameHeaderSize, len(b))
+	}
+	return FrameHeaderView(b[:FrameHeaderSize]), nil
+}
+
+// Length reads the u16 at offset 0.
+func (v FrameHeaderView) Length() uint16 { return byteOrder.Uint16(v[0:]) }
+
+// SetLength writes the u16 at offset 0.
+func (v FrameHeaderView) SetLength(x uint16) { byteOrder.PutUint16(v[0:], x) }
+
+// MessageID reads the u16 at offset 2.
+func (v FrameHeaderView) MessageID() uint16 { return byteOrder.Uint16(v[2:]) }
+
+// SetMessageID writes the u16 at offset 2.
+func (v FrameHeaderView) SetMessageID(x uint16) { byteOrder.PutUint16(v[2:], x) }
+
+// MarshalBinary encodes m into a new buffer of FrameHeaderSize bytes.
+func (m FrameHeader) MarshalBinary() ([]byte, error) {
+	b := make([]byte, FrameHeaderSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least FrameHeaderSize bytes.
+func (m *FrameHeader) UnmarshalBinary(data []byte) error {
+	v, err := NewFrameHeaderView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+func (m *FrameHeader) decode(v FrameHeaderView) {
+	m.Length = v.Length()
+	m.MessageID = v.MessageID()
+}
+
+func (m *FrameHeader) encode(v FrameHeaderView) error {
+	v.SetLength(m.Length)
+	v.SetMessageID(m.MessageID)
+	return nil
+}
+
+// Quote holds message Quote [1].
+type Quote struct {
+	SentAt      uint64
+	Side        Side
+	Permissions Permissions
+	Bid         Price
+	Sequence    uint32
+	Levels      [2]int16
+	Ratio       float32
+	Weight      float64
+	TTL         int64
+}
+
+// Layout of Quote.
+const (
+	QuoteID                = 1
+	QuoteSize              = 51
+	QuoteFrameSize         = 55
+	QuoteSentAtOffset      = 0
+	QuoteSentAtUnit        = time.Nanosecond
+	QuoteSideOffset        = 8
+	QuotePermissionsOffset = 10
+	QuoteBidOffset         = 12
+	QuoteSequenceOffset    = 23
+	QuoteLevelsOffset      = 27
+	QuoteLevelsLength      = 2
+	QuoteRatioOffset       = 31
+	QuoteWeightOffset      = 35
+	QuoteTTLOffset         = 43
+	QuoteTTLUnit           = time.Microsecond
+)
+
+// QuoteView reads and writes message Quote [1] in place.
+type QuoteView []byte
+
+// NewQuoteView views the start of b, which must hold at least QuoteSize bytes.
+func NewQuoteView(b []byte) (QuoteView, error) {
+	if len(b) < QuoteSize {
+		return nil, shortBuffer("Quote", QuoteSize, len(b))
+	}
+	return QuoteView(b[:QuoteSize]), nil
+}
+
+// SentAt reads the timestamp<ns> at offset 0.
+func (v QuoteView) SentAt() uint64 { return byteOrder.Uint64(v[0:]) }
+
+// SetSentAt writes the timestamp<ns> at offset 0.
+func (v QuoteView) SetSentAt(x uint64) { byteOrder.PutUint64(v[0:], x) }
+
+// SentAtTime reads sent_at as a UTC time.
+func (v QuoteView) SentAtTime() time.Time { return time.Unix(0, int64(v.SentAt())).UTC() }
+
+// SetSentAtTime writes t to sent_at, truncated to QuoteSentAtUnit.
+func (v QuoteView) SetSentAtTime(t time.Time) { v.SetSentAt(uint64(t.UnixNano())) }
+
+// Side reads the Side at offset 8.
+func (v QuoteView) Side() Side { return Side(byteOrder.Uint16(v[8:])) }
+
+// SetSide writes the Side at offset 8.
+func (v QuoteView) SetSide(x Side) { byteOrder.PutUint16(v[8:], uint16(x)) }
+
+// Permissions reads the Permissions at offset 10.
+func (v QuoteView) Permissions() Permissions { return Permissions(byteOrder.Uint16(v[10:])) }
+
+// SetPermissions writes the Permissions at offset 10.
+func (v QuoteView) SetPermissions(x Permissions) { byteOrder.PutUint16(v[10:], uint16(x)) }
+
+// Bid views the Price at offset 12.
+func (v QuoteView) Bid() PriceView { return PriceView(v[12:23]) }
+
+// Sequence reads the u32 at offset 23.
+func (v QuoteView) Sequence() uint32 { return byteOrder.Uint32(v[23:]) }
+
+// SetSequence writes the u32 at offset 23.
+func (v QuoteView) SetSequence(x uint32) { byteOrder.PutUint32(v[23:], x) }
+
+// Levels reads element i of the i16[2] at offset 27.
+func (v QuoteView) Levels(i int) int16 {
+	if uint(i) >= QuoteLevelsLength {
+		panic(fmt.Sprintf("bigendian: index %d out of range for levels", i))
+	}
+	return int16(byteOrder.Uint16(v[27+2*i:]))
+}
+
+// SetLevels writes element i of the i16[2] at offset 27.
+func (v QuoteView) SetLevels(i int, x int16) {
+	if uint(i) >= QuoteLevelsLength {
+		panic(fmt.Sprintf("bigendian: index %d out of range for levels", i))
+	}
+	byteOrder.PutUint16(v[27+2*i:], uint16(x))
+}
+
+// Ratio reads the f32 at offset 31.
+func (v QuoteView) Ratio() float32 { return math.Float32frombits(byteOrder.Uint32(v[31:])) }
+
+// SetRatio writes the f32 at offset 31.
+func (v QuoteView) SetRatio(x float32) { byteOrder.PutUint32(v[31:], math.Float32bits(x)) }
+
+// Weight reads the f64 at offset 35.
+func (v QuoteView) Weight() float64 { return math.Float64frombits(byteOrder.Uint64(v[35:])) }
+
+// SetWeight writes the f64 at offset 35.
+func (v QuoteView) SetWeight(x float64) { byteOrder.PutUint64(v[35:], math.Float64bits(x)) }
+
+// TTL reads the duration<us> at offset 43.
+func (v QuoteView) TTL() int64 { return int64(byteOrder.Uint64(v[43:])) }
+
+// SetTTL writes the duration<us> at offset 43.
+func (v QuoteView) SetTTL(x int64) { byteOrder.PutUint64(v[43:], uint64(x)) }
+
+// TTLDuration reads ttl as a time.Duration.
+func (v QuoteView) TTLDuration() time.Duration { return time.Duration(v.TTL()) * QuoteTTLUnit }
+
+// SetTTLDuration writes d to ttl, truncated to QuoteTTLUnit.
+func (v QuoteView) SetTTLDuration(d time.Duration) { v.SetTTL(int64(d / QuoteTTLUnit)) }
+
+// MarshalBinary encodes m into a new buffer of QuoteSize bytes.
+func (m Quote) MarshalBinary() ([]byte, error) {
+	b := make([]byte, QuoteSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least QuoteSize bytes.
+func (m *Quote) UnmarshalBinary(data []byte) error {
+	v, err := NewQuoteView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+// MarshalFrame encodes m after a FrameHeader holding its id and length.
+func (m Quote) MarshalFrame() ([]byte, error) {
+	b := make([]byte, QuoteFrameSize)
+	h := FrameHeaderView(b)
+	h.SetMessageID(QuoteID)
+	h.SetLength(QuoteFrameSize)
+	if err := m.encode(QuoteView(b[FrameHeaderSize:])); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+func (m *Quote) decode(v QuoteView) {
+	m.SentAt = v.SentAt()
+	m.Side = v.Side()
+	m.Permissions = v.Permissions()
+	m.Bid.decode(v.Bid())
+	m.Sequence = v.Sequence()
+	for i := range m.Levels {
+		m.Levels[i] = v.Levels(i)
+	}
+	m.Ratio = v.Ratio()
+	m.Weight = v.Weight()
+	m.TTL = v.TTL()
+}
+
+func (m *Quote) encode(v QuoteView) error {
+	v.SetSentAt(m.SentAt)
+	v.SetSide(m.Side)
+	v.SetPermissions(m.Permissions)
+	if err := m.Bid.encode(v.Bid()); err != nil {
+		return err
+	}
+	v.SetSequence(m.Sequence)
+	for i, x := range m.Levels {
+		v.SetLevels(i, x)
+	}
+	v.SetRatio(m.Ratio)
+	v.SetWeight(m.Weight)
+	v.SetTTL(m.TTL)
+	return nil
+}
+
+// Handler receives the messages of the frames passed to Dispatch.
+type Handler interface {
+	OnQuote(QuoteView)
+}
+
+// NopHandler ignores every message, embed it in a Handler to handle only some of them.
+type NopHandler struct{}
+
+// OnQuote ignores the message.
+func (NopHandler) OnQuote(QuoteView) {}
+
+// checkFrame returns an error when the length of a frame is too small for its message or
+// the buffer doesn't hold the whole frame.
+func checkFrame(name string, frame, size, length int) error {
+	if frame < size {
+		return fmt.Errorf("bigendian: a frame length of %d is too small for %s", frame, name)
+	}
+	if length < frame {
+		return shortBuffer("the frame of "+name, frame, length)
+	}
+	return nil
+}
+
+// Dispatch reads the FrameHeader at the start of b and passes the message after it to h, returning
+// the size of the frame.
+func Dispatch(b []byte, h Handler) (int, error) {
+	header, err := NewFrameHeaderView(b)
+	if err != nil {
+		return 0, err
+	}
+	frame := int(header.Length())
+	switch header.MessageID() {
+	case QuoteID:
+		if err := checkFrame("Quote", frame, QuoteFrameSize, len(b)); err != nil {
+			return 0, err
+		}
+		h.OnQuote(QuoteView(b[FrameHeaderSize : FrameHeaderSize+QuoteSize]))
+		return frame, nil
+	}
+	return 0, fmt.Errorf("bigendian: no message has the id %d", header.MessageID())
+}
@@ -0,0 +1,71 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+import java.nio.ByteBuffer;
+import java.util.Objects;
+
+/**
+ * A reusable view of a fixed length ascii field, the text ends at the first nul byte and leaves
+ * out the pad characters ending it. Flyweights hold one per ascii field so reading the field
+ * doesn't allocate.
+ */
+public final class AsciiSequence implements CharSequence {
+    private ByteBuffer buffer;
+    private int offset;
+    private int length;
+
+    AsciiSequence wrap(final ByteBuffer buffer, final int offset, final int capacity, final char pad) {
+        int length = 0;
+        while (length < capacity && buffer.get(offset + length) != 0) {
+            length++;
+        }
+        while (length > 0 && buffer.get(offset + length - 1) == pad) {
+            length--;
+        }
+        this.buffer = buffer;
+        this.offset = offset;
+        this.length = length;
+        return this;
+    }
+
+    @Override
+    public int length() {
+        return length;
+    }
+
+    @Override
+    public char charAt(final int index) {
+        Objects.checkIndex(index, length);
+        return (char) (buffer.get(offset + index) & 0xFF);
+    }
+
+    @Override
+    public CharSequence subSequence(final int start, final int end) {
+        return toString().substring(start, end);
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder(length);
+        for (int i = 0; i < length; i++) {
+            text.append(charAt(i));
+        }
+        return text.toString();
+    }
+
+    /** Writes value and fills the rest of the field with pad, nothing is written if it doesn't fit */
+    static void put(final ByteBuffer buffer, final int offset, final int capacity, final char pad, final CharSequence value, final String field) {
+        final int length = value.length();
+        if (length > capacity) {
+            throw new IllegalArgumentException(field + " holds at most " + capacity + " characters but the value has " + length);
+        }
+        for (int i = 0; i < length; i++) {
+            if (value.charAt(i) > 0x7F) {
+                throw new IllegalArgumentException(field + " only holds ascii characters");
+            }
+        }
+        for (int i = 0; i < capacity; i++) {
+            buffer.put(offset + i, (byte) (i < length ? value.charAt(i) : pad));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+import java.nio.ByteBuffer;
+import java.util.Objects;
+
+/** Reads frames and calls a Handler with the message of each, reusing one flyweight per message */
+public final class Dispatcher {
+    private final Handler handler;
+    private final FrameHeader header = new FrameHeader();
+    private final Quote quote = new Quote();
+
+    public Dispatcher(final Handler handler) {
+        this.handler = Objects.requireNonNull(handler);
+    }
+
+    /**
+     * Reads the header at offset and calls the handler with the message after it, returns the
+     * size of the frame. Throws IndexOutOfBoundsException when the buffer doesn't hold the whole
+     * frame and IllegalArgumentException when its length is too small or no message has its id.
+     */
+    public int dispatch(final ByteBuffer buffer, final int offset) {
+        header.wrap(buffer, offset);
+        final long id = header.messageId();
+        final long length = header.length();
+        if (id == Quote.ID) {
+            final int frame = frame(buffer, offset, length, Quote.FRAME_SIZE);
+            handler.onQuote(quote.wrap(buffer, offset + FrameHeader.SIZE));
+            return frame;
+        }
+        throw new IllegalArgumentException("no message has the id " + id);
+    }
+
+    private static int frame(final ByteBuffer buffer, final int offset, final long length, final int size) {
+        if (length < size) {
+            throw new IllegalArgumentException("a frame length of " + length + " is too small for a message of " + size + " bytes");
+        }
+        if (length > buffer.capacity() - offset) {
+            throw new IndexOutOfBoundsException("a frame of " + length + " bytes at " + offset + " overruns a buffer of " + buffer.capacity());
+        }
+        return (int) length;
+    }
+}
@@ -0,0 +1,9 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+/** Callbacks for Dispatcher, a message without an override is ignored */
+public interface Handler {
+    /** Called with a Quote */
+    default void onQuote(final Quote message) {
+    }
+}
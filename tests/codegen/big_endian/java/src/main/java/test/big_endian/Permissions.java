@@ -0,0 +1,38 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+/** bitflags Permissions, stored as a u16 */
+public final class Permissions {
+    public static final int READ = 1;
+    public static final int WRITE = 2;
+    public static final int ADMIN = 128;
+    /** Every declared flag */
+    public static final int ALL = 131;
+
+    private Permissions() {
+    }
+
+    /** True if every flag of flags is set in value */
+    public static boolean has(final int value, final int flags) {
+        return (value & flags) == flags;
+    }
+
+    /** The names of the flags set in value, undeclared bits as a hex number */
+    public static String toString(final int value) {
+        final StringBuilder text = new StringBuilder();
+        if (has(value, READ)) {
+            text.append(text.length() == 0 ? "" : " | ").append("READ");
+        }
+        if (has(value, WRITE)) {
+            text.append(text.length() == 0 ? "" : " | ").append("WRITE");
+        }
+        if (has(value, ADMIN)) {
+            text.append(text.length() == 0 ? "" : " | ").append("ADMIN");
+        }
+        final long rest = value & ~ALL;
+        if (rest != 0 || text.length() == 0) {
+            text.append(text.length() == 0 ? "" : " | ").append("0x").append(Long.toHexString(rest));
+        }
+        return text.toString();
+    }
+}
@@ -0,0 +1,198 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.time.Duration;
+import java.time.Instant;
+import java.time.temporal.ChronoUnit;
+import java.util.Objects;
+import java.util.concurrent.TimeUnit;
+
+/** message Quote [1] */
+public final class Quote {
+    public static final int ID = 1;
+    public static final int SIZE = 51;
+    public static final int FRAME_SIZE = 55;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.BIG_ENDIAN;
+
+    public static final int SENT_AT_OFFSET = 0;
+    public static final TimeUnit SENT_AT_UNIT = TimeUnit.NANOSECONDS;
+    public static final int SIDE_OFFSET = 8;
+    public static final int PERMISSIONS_OFFSET = 10;
+    public static final int BID_OFFSET = 12;
+    public static final int SEQUENCE_OFFSET = 23;
+    public static final int LEVELS_OFFSET = 27;
+    public static final int LEVELS_LENGTH = 2;
+    public static final int RATIO_OFFSET = 31;
+    public static final int WEIGHT_OFFSET = 35;
+    public static final int TTL_OFFSET = 43;
+    public static final TimeUnit TTL_UNIT = TimeUnit.MICROSECONDS;
+
+    private static final VarHandle F32 = MethodHandles.byteBufferViewVarHandle(float[].class, BYTE_ORDER);
+    private static final VarHandle F64 = MethodHandles.byteBufferViewVarHandle(double[].class, BYTE_ORDER);
+    private static final VarHandle I16 = MethodHandles.byteBufferViewVarHandle(short[].class, BYTE_ORDER);
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+    private static final VarHandle I64 = MethodHandles.byteBufferViewVarHandle(long[].class, BYTE_ORDER);
+
+    private final Price bid = new Price();
+    private final FrameHeader frameHeader = new FrameHeader();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Quote wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    /** Writes the header's id and length at offset and points this flyweight at the message after it */
+    public Quote wrapFrame(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, FRAME_SIZE, buffer.capacity());
+        frameHeader.wrap(buffer, offset).messageId(ID).length(FRAME_SIZE);
+        return wrap(buffer, offset + FrameHeader.SIZE);
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** timestamp<ns> at offset 0 */
+    public long sentAt() {
+        return (long) I64.get(buffer, offset);
+    }
+
+    public Quote sentAt(final long value) {
+        I64.set(buffer, offset, value);
+        return this;
+    }
+
+    /** sent_at as an Instant */
+    public Instant sentAtInstant() {
+        return Instant.EPOCH.plus(sentAt(), ChronoUnit.NANOS);
+    }
+
+    public Quote sentAtInstant(final Instant value) {
+        sentAt(ChronoUnit.NANOS.between(Instant.EPOCH, value));
+        return this;
+    }
+
+    /** Side at offset 8 */
+    public Side side() {
+        return Side.get(sideRaw());
+    }
+
+    public Quote side(final Side value) {
+        I16.set(buffer, offset + 8, (short) value.value());
+        return this;
+    }
+
+    public int sideRaw() {
+        return (short) I16.get(buffer, offset + 8) & 0xFFFF;
+    }
+
+    public Quote sideRaw(final int value) {
+        I16.set(buffer, offset + 8, (short) value);
+        return this;
+    }
+
+    /** Permissions at offset 10, the flags are the constants of Permissions */
+    public int permissions() {
+        return (short) I16.get(buffer, offset + 10) & 0xFFFF;
+    }
+
+    public Quote permissions(final int value) {
+        I16.set(buffer, offset + 10, (short) value);
+        return this;
+    }
+
+    /** Price at offset 12 */
+    public Price bid() {
+        return bid.wrap(buffer, offset + 12);
+    }
+
+    /** u32 at offset 23 */
+    public long sequence() {
+        return Integer.toUnsignedLong((int) I32.get(buffer, offset + 23));
+    }
+
+    public Quote sequence(final long value) {
+        I32.set(buffer, offset + 23, (int) value);
+        return this;
+    }
+
+    /** i16[2] at offset 27 */
+    public short levels(final int index) {
+        Objects.checkIndex(index, LEVELS_LENGTH);
+        return (short) I16.get(buffer, offset + 27 + index * 2);
+    }
+
+    public Quote levels(final int index, final short value) {
+        Objects.checkIndex(index, LEVELS_LENGTH);
+        I16.set(buffer, offset + 27 + index * 2, value);
+        return this;
+    }
+
+    /** f32 at offset 31 */
+    public float ratio() {
+        return (float) F32.get(buffer, offset + 31);
+    }
+
+    public Quote ratio(final float value) {
+        F32.set(buffer, offset + 31, value);
+        return this;
+    }
+
+    /** f64 at offset 35 */
+    public double weight() {
+        return (double) F64.get(buffer, offset + 35);
+    }
+
+    public Quote weight(final double value) {
+        F64.set(buffer, offset + 35, value);
+        return this;
+    }
+
+    /** duration<us> at offset 43 */
+    public long ttl() {
+        return (long) I64.get(buffer, offset + 43);
+    }
+
+    public Quote ttl(final long value) {
+        I64.set(buffer, offset + 43, value);
+        return this;
+    }
+
+    /** ttl as a Duration */
+    public Duration ttlDuration() {
+        return Duration.of(ttl(), ChronoUnit.MICROS);
+    }
+
+    public Quote ttlDuration(final Duration value) {
+        ttl(value.dividedBy(ChronoUnit.MICROS.getDuration()));
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Quote{");
+        text.append("sentAt=").append(sentAt());
+        text.append(", side=").append(side());
+        text.append(", permissions=").append(Permissions.toString(permissions()));
+        text.append(", bid=").append(bid());
+        text.append(", sequence=").append(sequence());
+        text.append(", levels=").append("[" + levels(0) + ", " + levels(1) + "]");
+        text.append(", ratio=").append(ratio());
+        text.append(", weight=").append(weight());
+        text.append(", ttl=").append(ttl());
+        return text.append('}').toString();
+    }
+}
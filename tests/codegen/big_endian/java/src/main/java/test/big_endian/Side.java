@@ -0,0 +1,32 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+package test.big_endian;
+
+/** enum Side, stored as a u16 */
+public enum Side {
+    BUY(1),
+    SELL(2),
+    /** Not declared in the schema, returned for any undeclared value */
+    NULL_VAL(65535);
+
+    private final int value;
+
+    Side(final int value) {
+        this.value = value;
+    }
+
+    /** The value stored in a message */
+    public int value() {
+        return value;
+    }
+
+    /** The constant for a stored value, NULL_VAL if it isn't declared */
+    public static Side get(final int value) {
+        if (value == 1) {
+            return BUY;
+        }
+        if (value == 2) {
+            return SELL;
+        }
+        return NULL_VAL;
+    }
+}
@@ -0,0 +1,410 @@
+# Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+"""Protocol test.big_endian"""
+
+import datetime
+import decimal
+import enum
+import struct
+
+BYTE_ORDER = "big"
+_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def _view(buf, size, name):
+    view = memoryview(buf).cast("B")
+    if len(view) < size:
+        raise ValueError(f"{name} needs {size} bytes but the buffer holds {len(view)}")
+    return view[:size]
+
+
+def _get_ascii(view, offset, length, pad):
+    raw = bytes(view[offset:offset + length])
+    end = raw.find(b"\0")
+    return (raw if end < 0 else raw[:end]).rstrip(pad).decode("ascii")
+
+
+def _set_ascii(view, offset, length, pad, value, field):
+    raw = value.encode("ascii")
+    if len(raw) > length:
+        raise ValueError(f"{field} holds at most {length} characters but {value!r} has {len(raw)}")
+    view[offset:offset + length] = raw.ljust(length, pad)
+
+
+def _set_bytes(view, offset, length, value, field):
+    if len(value) != length:
+        raise ValueError(f"{field} holds {length} bytes but {len(value)} were given")
+    view[offset:offset + length] = bytes(value)
+
+
+def _enum(cls, raw):
+    """The member of cls for raw, or raw itself when the value isn't declared"""
+    try:
+        return cls(raw)
+    except ValueError:
+        return raw
+
+
+def _plain(value):
+    return value.to_dict() if hasattr(value, "to_dict") else value
+
+
+class Side(enum.IntEnum):
+    """enum Side, stored as a u16"""
+
+    BUY = 1
+    SELL = 2
+
+
+class Permissions(enum.IntFlag):
+    """bitflags Permissions, stored as a u16"""
+
+    READ = 1
+    WRITE = 2
+    ADMIN = 128
+
+
+class Price:
+    """struct Price"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 11
+    FORMAT = ">q3s"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "value": 0,
+        "currency": 8,
+    }
+    VALUE_EXPONENT = -4
+    _VALUE = struct.Struct(">q")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed struct when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Price")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "value": self.value,
+            "currency": self.currency,
+        }
+
+    def __repr__(self):
+        return f"Price({self.to_dict()!r})"
+
+    @property
+    def value(self):
+        """decimal<i64, -4> at offset 0"""
+        return self._VALUE.unpack_from(self._buf, 0)[0]
+
+    @value.setter
+    def value(self, value):
+        self._VALUE.pack_into(self._buf, 0, value)
+
+    @property
+    def value_decimal(self):
+        """value as a decimal.Decimal"""
+        return decimal.Decimal(self.value).scaleb(self.VALUE_EXPONENT)
+
+    @value_decimal.setter
+    def value_decimal(self, value):
+        mantissa = decimal.Decimal(value).scaleb(-self.VALUE_EXPONENT)
+        if mantissa != mantissa.to_integral_value():
+            raise ValueError(f"Price.value only holds multiples of 1e{self.VALUE_EXPONENT}, not {value}")
+        self.value = int(mantissa)
+
+    @property
+    def currency(self):
+        """ascii[3] at offset 8"""
+        return _get_ascii(self._buf, 8, 3, b"\0")
+
+    @currency.setter
+    def currency(self, value):
+        _set_ascii(self._buf, 8, 3, b"\0", value, "Price.currency")
+
+
+class FrameHeader:
+    """struct FrameHeader"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 4
+    FORMAT = ">HH"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "length": 0,
+        "message_id": 2,
+    }
+    _LENGTH = struct.Struct(">H")
+    _MESSAGE_ID = struct.Struct(">H")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed struct when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "FrameHeader")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "length": self.length,
+            "message_id": self.message_id,
+        }
+
+    def __repr__(self):
+        return f"FrameHeader({self.to_dict()!r})"
+
+    @property
+    def length(self):
+        """u16 at offset 0"""
+        return self._LENGTH.unpack_from(self._buf, 0)[0]
+
+    @length.setter
+    def length(self, value):
+        self._LENGTH.pack_into(self._buf, 0, value)
+
+    @property
+    def message_id(self):
+        """u16 at offset 2"""
+        return self._MESSAGE_ID.unpack_from(self._buf, 2)[0]
+
+    @message_id.setter
+    def message_id(self, value):
+        self._MESSAGE_ID.pack_into(self._buf, 2, value)
+
+
+class Quote:
+    """message Quote [1]"""
+
+    __slots__ = ("_buf",)
+
+    ID = 1
+    SIZE = 51
+    FRAME_SIZE = 55
+    FORMAT = ">QHHq3sI2hfdq"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "sent_at": 0,
+        "side": 8,
+        "permissions": 10,
+        "bid": 12,
+        "sequence": 23,
+        "levels": 27,
+        "ratio": 31,
+        "weight": 35,
+        "ttl": 43,
+    }
+    _SENT_AT = struct.Struct(">Q")
+    _SIDE = struct.Struct(">H")
+    _PERMISSIONS = struct.Struct(">H")
+    _SEQUENCE = struct.Struct(">I")
+    _LEVELS = struct.Struct(">2h")
+    _RATIO = struct.Struct(">f")
+    _WEIGHT = struct.Struct(">d")
+    _TTL = struct.Struct(">q")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed message when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Quote")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def encode_frame(self):
+        """The header followed by the message"""
+        header = FrameHeader()
+        header.message_id = self.ID
+        header.length = self.FRAME_SIZE
+        return header.encode() + self.encode()
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "sent_at": self.sent_at,
+            "side": _enum(Side, self.side_raw),
+            "permissions": _enum(Permissions, self.permissions_raw),
+            "bid": self.bid.to_dict(),
+            "sequence": self.sequence,
+            "levels": self.levels,
+            "ratio": self.ratio,
+            "weight": self.weight,
+            "ttl": self.ttl,
+        }
+
+    def __repr__(self):
+        return f"Quote({self.to_dict()!r})"
+
+    @property
+    def sent_at(self):
+        """timestamp<ns> at offset 0"""
+        return self._SENT_AT.unpack_from(self._buf, 0)[0]
+
+    @sent_at.setter
+    def sent_at(self, value):
+        self._SENT_AT.pack_into(self._buf, 0, value)
+
+    @property
+    def sent_at_datetime(self):
+        """sent_at as a datetime.datetime"""
+        return _EPOCH + datetime.timedelta(microseconds=self.sent_at // 1000)
+
+    @sent_at_datetime.setter
+    def sent_at_datetime(self, value):
+        self.sent_at = (value - _EPOCH) // datetime.timedelta(microseconds=1) * 1000
+
+    @property
+    def side(self):
+        """Side at offset 8, raises ValueError for an undeclared value"""
+        return Side(self._SIDE.unpack_from(self._buf, 8)[0])
+
+    @side.setter
+    def side(self, value):
+        self._SIDE.pack_into(self._buf, 8, value)
+
+    @property
+    def side_raw(self):
+        return self._SIDE.unpack_from(self._buf, 8)[0]
+
+    @side_raw.setter
+    def side_raw(self, value):
+        self._SIDE.pack_into(self._buf, 8, value)
+
+    @property
+    def permissions(self):
+        """Permissions at offset 10"""
+        return Permissions(self._PERMISSIONS.unpack_from(self._buf, 10)[0])
+
+    @permissions.setter
+    def permissions(self, value):
+        self._PERMISSIONS.pack_into(self._buf, 10, value)
+
+    @property
+    def permissions_raw(self):
+        return self._PERMISSIONS.unpack_from(self._buf, 10)[0]
+
+    @permissions_raw.setter
+    def permissions_raw(self, value):
+        self._PERMISSIONS.pack_into(self._buf, 10, value)
+
+    @property
+    def bid(self):
+        """Price at offset 12, a view sharing the same buffer"""
+        return Price(self._buf[12:23])
+
+    @bid.setter
+    def bid(self, value):
+        self._buf[12:23] = value.encode()
+
+    @property
+    def sequence(self):
+        """u32 at offset 23"""
+        return self._SEQUENCE.unpack_from(self._buf, 23)[0]
+
+    @sequence.setter
+    def sequence(self, value):
+        self._SEQUENCE.pack_into(self._buf, 23, value)
+
+    @property
+    def levels(self):
+        """i16[2] at offset 27"""
+        return self._LEVELS.unpack_from(self._buf, 27)
+
+    @levels.setter
+    def levels(self, value):
+        self._LEVELS.pack_into(self._buf, 27, *value)
+
+    @property
+    def ratio(self):
+        """f32 at offset 31"""
+        return self._RATIO.unpack_from(self._buf, 31)[0]
+
+    @ratio.setter
+    def ratio(self, value):
+        self._RATIO.pack_into(self._buf, 31, value)
+
+    @property
+    def weight(self):
+        """f64 at offset 35"""
+        return self._WEIGHT.unpack_from(self._buf, 35)[0]
+
+    @weight.setter
+    def weight(self, value):
+        self._WEIGHT.pack_into(self._buf, 35, value)
+
+    @property
+    def ttl(self):
+        """duration<us> at offset 43"""
+        return self._TTL.unpack_from(self._buf, 43)[0]
+
+    @ttl.setter
+    def ttl(self, value):
+        self._TTL.pack_into(self._buf, 43, value)
+
+    @property
+    def ttl_timedelta(self):
+        """ttl as a datetime.timedelta"""
+        return datetime.timedelta(microseconds=self.ttl)
+
+    @ttl_timedelta.setter
+    def ttl_timedelta(self, value):
+        self.ttl = value // datetime.timedelta(microseconds=1)
+
+
+class Handler:
+    """Callbacks for dispatch, a message without an override is ignored"""
+
+    def on_quote(self, message):
+        """Called with a Quote"""
+
+
+_HANDLERS = {
+    1: (Quote, "on_quote"),
+}
+
+
+def dispatch(buf, handler):
+    """Reads the header at the start of buf and calls the handler's callback for its id with the
+    message after it, returns the size of the frame. Raises ValueError when buf doesn't hold the
+    whole frame, its length is too small for its message or no message has its id."""
+    header = FrameHeader(buf)
+    entry = _HANDLERS.get(header.message_id)
+    if entry is None:
+        raise ValueError(f"no message has the id {header.message_id}")
+    cls, callback = entry
+    frame = header.length
+    if frame < cls.FRAME_SIZE:
+        raise ValueError(f"a frame length of {frame} is too small for {cls.__name__}")
+    view = _view(buf, frame, "the frame")
+    getattr(handler, callback)(cls(view[FrameHeader.SIZE:]))
+    return frame
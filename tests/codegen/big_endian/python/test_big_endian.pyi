@@ -0,0 +1,132 @@
+# Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+import datetime
+import decimal
+import enum
+import typing
+
+Buffer = typing.Union[bytes, bytearray, memoryview]
+
+BYTE_ORDER: str
+
+class Side(enum.IntEnum):
+    BUY = 1
+    SELL = 2
+
+class Permissions(enum.IntFlag):
+    READ = 1
+    WRITE = 2
+    ADMIN = 128
+
+class Price:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    VALUE_EXPONENT: typing.ClassVar[int]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Price: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def value(self) -> int: ...
+    @value.setter
+    def value(self, value: int) -> None: ...
+    @property
+    def value_decimal(self) -> decimal.Decimal: ...
+    @value_decimal.setter
+    def value_decimal(self, value: typing.Union[decimal.Decimal, int, str]) -> None: ...
+    @property
+    def currency(self) -> str: ...
+    @currency.setter
+    def currency(self, value: str) -> None: ...
+
+class FrameHeader:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> FrameHeader: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def length(self) -> int: ...
+    @length.setter
+    def length(self, value: int) -> None: ...
+    @property
+    def message_id(self) -> int: ...
+    @message_id.setter
+    def message_id(self, value: int) -> None: ...
+
+class Quote:
+    ID: typing.ClassVar[int]
+    SIZE: typing.ClassVar[int]
+    FRAME_SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Quote: ...
+    def encode(self) -> bytes: ...
+    def encode_frame(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def sent_at(self) -> int: ...
+    @sent_at.setter
+    def sent_at(self, value: int) -> None: ...
+    @property
+    def sent_at_datetime(self) -> datetime.datetime: ...
+    @sent_at_datetime.setter
+    def sent_at_datetime(self, value: datetime.datetime) -> None: ...
+    @property
+    def side(self) -> Side: ...
+    @side.setter
+    def side(self, value: Side) -> None: ...
+    @property
+    def side_raw(self) -> int: ...
+    @side_raw.setter
+    def side_raw(self, value: int) -> None: ...
+    @property
+    def permissions(self) -> Permissions: ...
+    @permissions.setter
+    def permissions(self, value: Permissions) -> None: ...
+    @property
+    def permissions_raw(self) -> int: ...
+    @permissions_raw.setter
+    def permissions_raw(self, value: int) -> None: ...
+    @property
+    def bid(self) -> Price: ...
+    @bid.setter
+    def bid(self, value: Price) -> None: ...
+    @property
+    def sequence(self) -> int: ...
+    @sequence.setter
+    def sequence(self, value: int) -> None: ...
+    @property
+    def levels(self) -> typing.Tuple[int, ...]: ...
+    @levels.setter
+    def levels(self, value: typing.Sequence[int]) -> None: ...
+    @property
+    def ratio(self) -> float: ...
+    @ratio.setter
+    def ratio(self, value: float) -> None: ...
+    @property
+    def weight(self) -> float: ...
+    @weight.setter
+    def weight(self, value: float) -> None: ...
+    @property
+    def ttl(self) -> int: ...
+    @ttl.setter
+    def ttl(self, value: int) -> None: ...
+    @property
+    def ttl_timedelta(self) -> datetime.timedelta: ...
+    @ttl_timedelta.setter
+    def ttl_timedelta(self, value: datetime.timedelta) -> None: ...
+
+class Handler:
+    def on_quote(self, message: Quote) -> None: ...
+
+def dispatch(buf: Buffer, handler: Handler) -> int: ...
@@ -0,0 +1,836 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+//
+// Only `core` is used so this module can be part of a `#![no_std]` crate.
+
+use core::fmt;
+
+/// Errors from reading or writing a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is shorter than the message or struct
+    BufferTooShort { required: usize, actual: usize },
+    /// A field holds a value which isn't declared by its enum or union
+    InvalidEnumValue { name: &'static str, value: i128 },
+    /// An ascii field holds a byte outside of the ascii range
+    InvalidAscii { field: &'static str },
+    /// A value is longer than the field it is written to
+    ValueTooLong { field: &'static str, max: usize, actual: usize },
+    /// The header of a frame holds an id no message has
+    UnknownMessageId { id: u64 },
+    /// The header of a frame gives a length too small for the frame
+    InvalidLength { length: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BufferTooShort { required, actual } => {
+                write!(f, "buffer of {} bytes is shorter than {} bytes", actual, required)
+            }
+            Error::InvalidEnumValue { name, value } => {
+                write!(f, "{} is not a value of '{}'", value, name)
+            }
+            Error::InvalidAscii { field } => write!(f, "'{}' is not ascii", field),
+            Error::ValueTooLong { field, max, actual } => write!(
+                f,
+                "{} bytes is too long for '{}' which holds {} bytes",
+                actual, field, max
+            ),
+            Error::UnknownMessageId { id } => write!(f, "no message has the id {}", id),
+            Error::InvalidLength { length } => {
+                write!(f, "a frame length of {} is too small", length)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[inline]
+fn check_size(buf: &[u8], required: usize) -> Result<(), Error> {
+    if buf.len() < required {
+        return Err(Error::BufferTooShort {
+            required,
+            actual: buf.len(),
+        });
+    }
+    Ok(())
+}
+
+#[inline]
+#[allow(dead_code)]
+fn get<const N: usize>(buf: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&buf[offset..offset + N]);
+    bytes
+}
+
+#[inline]
+#[allow(dead_code)]
+fn put<const N: usize>(buf: &mut [u8], offset: usize, bytes: [u8; N]) {
+    buf[offset..offset + N].copy_from_slice(&bytes);
+}
+
+#[inline]
+#[allow(dead_code)]
+fn bytes<const N: usize>(buf: &[u8], offset: usize) -> &[u8; N] {
+    buf[offset..offset + N].try_into().unwrap()
+}
+
+/// The text of an ascii field up to the first nul byte, without the `pad` bytes ending it
+#[allow(dead_code)]
+fn ascii_str<'a>(bytes: &'a [u8], pad: u8, field: &'static str) -> Result<&'a str, Error> {
+    let mut end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    while end > 0 && bytes[end - 1] == pad {
+        end -= 1;
+    }
+    match core::str::from_utf8(&bytes[..end]) {
+        Ok(text) if text.is_ascii() => Ok(text),
+        _ => Err(Error::InvalidAscii { field }),
+    }
+}
+
+/// Writes `value` to an ascii field, the rest of the field is filled with `pad`
+#[allow(dead_code)]
+fn put_ascii(buf: &mut [u8], value: &str, pad: u8, field: &'static str) -> Result<(), Error> {
+    if !value.is_ascii() {
+        return Err(Error::InvalidAscii { field });
+    }
+    if value.len() > buf.len() {
+        return Err(Error::ValueTooLong {
+            field,
+            max: buf.len(),
+            actual: value.len(),
+        });
+    }
+    buf[..value.len()].copy_from_slice(value.as_bytes());
+    buf[value.len()..].fill(pad);
+    Ok(())
+}
+
+/// Shows a value which couldn't be decoded as its raw form
+#[allow(dead_code)]
+struct Decoded<T, R>(Result<T, R>);
+
+impl<T: fmt::Debug, R: fmt::Debug> fmt::Debug for Decoded<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(value) => value.fmt(f),
+            Err(raw) => write!(f, "{:?} (invalid)", raw),
+        }
+    }
+}
+
+/// Shows a decimal's mantissa scaled by its exponent
+#[allow(dead_code)]
+struct Decimal(i128, i8);
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Decimal(mantissa, exponent) = *self;
+        if exponent >= 0 || mantissa == 0 {
+            write!(f, "{}", mantissa)?;
+            for _ in 0..exponent.max(0) {
+                f.write_str("0")?;
+            }
+            return Ok(());
+        }
+        let scale = 10u128.pow(exponent.unsigned_abs() as u32);
+        let sign = if mantissa < 0 { "-" } else { "" };
+        let abs = mantissa.unsigned_abs();
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale,
+            abs % scale,
+            width = exponent.unsigned_abs() as usize
+        )
+    }
+}
+
+/// The qualified name of the protocol
+pub const PROTOCOL: &str = "test.big_endian";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum Side {
+    Buy = 1,
+    Sell = 2,
+}
+
+impl Side {
+    /// Every declared value
+    pub const ALL: [Side; 2] = [Side::Buy, Side::Sell];
+
+    /// The name of the value in the schema
+    pub const fn name(self) -> &'static str {
+        match self {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        }
+    }
+}
+
+impl TryFrom<u16> for Side {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Side::Buy),
+            2 => Ok(Side::Sell),
+            _ => Err(Error::InvalidEnumValue { name: "Side", value: value as i128 }),
+        }
+    }
+}
+
+impl From<Side> for u16 {
+    fn from(value: Side) -> u16 {
+        value as u16
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Permissions(u16);
+
+impl Permissions {
+    pub const READ: Permissions = Permissions(1);
+    pub const WRITE: Permissions = Permissions(2);
+    pub const ADMIN: Permissions = Permissions(128);
+    /// Every declared flag
+    pub const ALL: Permissions = Permissions(131);
+
+    const NAMES: [(Permissions, &'static str); 3] = [
+        (Self::READ, "READ"),
+        (Self::WRITE, "WRITE"),
+        (Self::ADMIN, "ADMIN"),
+    ];
+
+    pub const fn empty() -> Self {
+        Permissions(0)
+    }
+
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Keeps any bits which aren't declared flags
+    pub const fn from_bits_retain(bits: u16) -> Self {
+        Permissions(bits)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl TryFrom<u16> for Permissions {
+    type Error = Error;
+
+    /// Fails if any bit isn't a declared flag
+    fn try_from(bits: u16) -> Result<Self, Error> {
+        if bits & !Self::ALL.0 != 0 {
+            return Err(Error::InvalidEnumValue { name: "Permissions", value: bits as i128 });
+        }
+        Ok(Permissions(bits))
+    }
+}
+
+impl From<Permissions> for u16 {
+    fn from(value: Permissions) -> u16 {
+        value.0
+    }
+}
+
+impl core::ops::BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Permissions(self.0 | other.0)
+    }
+}
+
+impl core::ops::BitOrAssign for Permissions {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl core::ops::BitAnd for Permissions {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Permissions(self.0 & other.0)
+    }
+}
+
+impl core::ops::BitAndAssign for Permissions {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0 &= other.0;
+    }
+}
+
+impl core::ops::BitXor for Permissions {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        Permissions(self.0 ^ other.0)
+    }
+}
+
+impl core::ops::BitXorAssign for Permissions {
+    fn bitxor_assign(&mut self, other: Self) {
+        self.0 ^= other.0;
+    }
+}
+
+impl core::ops::Not for Permissions {
+    type Output = Self;
+
+    /// Only the declared flags are inverted
+    fn not(self) -> Self {
+        Permissions(!self.0 & Self::ALL.0)
+    }
+}
+
+impl fmt::Display for Permissions {
+    /// The names of the set flags separated by ` | `, undeclared bits are shown in hex
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.0;
+        let mut first = true;
+        for (flag, name) in Self::NAMES {
+            if flag.0 != 0 && remaining & flag.0 == flag.0 {
+                f.write_str(if first { "" } else { " | " })?;
+                f.write_str(name)?;
+                remaining &= !flag.0;
+                first = false;
+            }
+        }
+        if remaining != 0 || first {
+            f.write_str(if first { "" } else { " | " })?;
+            write!(f, "{:#x}", remaining)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Permissions({})", self)
+    }
+}
+
+/// The struct `Price`, read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct PriceView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> PriceView<'a> {
+    pub const SIZE: usize = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    pub const VALUE_EXPONENT: i8 = -4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(PriceView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `decimal<i64, -4>`, offset 0, 8 bytes
+    pub fn value(&self) -> i64 {
+        i64::from_be_bytes(get(self.buf, 0))
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn currency(&self) -> &'a [u8; 3] {
+        bytes(self.buf, 8)
+    }
+
+    /// The text of `currency` up to the first nul byte
+    pub fn currency_str(&self) -> Result<&'a str, Error> {
+        ascii_str(self.currency(), 0, "currency")
+    }
+}
+
+impl fmt::Debug for PriceView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Price")
+            .field("value", &Decimal(self.value() as i128, Self::VALUE_EXPONENT))
+            .field("currency", &Decoded(self.currency_str().map_err(|_| self.currency())))
+            .finish()
+    }
+}
+
+impl fmt::Display for PriceView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The struct `Price`, written in place to a byte slice
+pub struct PriceMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> PriceMut<'a> {
+    pub const SIZE: usize = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    pub const VALUE_EXPONENT: i8 = -4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(PriceMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> PriceView<'_> {
+        PriceView { buf: self.buf }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `decimal<i64, -4>`, offset 0, 8 bytes
+    pub fn value(&self) -> i64 {
+        self.view().value()
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn currency(&self) -> &'_ [u8; 3] {
+        self.view().currency()
+    }
+
+    /// The text of `currency` up to the first nul byte
+    pub fn currency_str(&self) -> Result<&'_ str, Error> {
+        self.view().currency_str()
+    }
+
+    /// `decimal<i64, -4>`, offset 0, 8 bytes
+    pub fn set_value(&mut self, value: i64) {
+        put(self.buf, 0, value.to_be_bytes());
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn set_currency(&mut self, value: &str) -> Result<(), Error> {
+        put_ascii(&mut self.buf[8..11], value, 0, "currency")
+    }
+}
+
+impl fmt::Debug for PriceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for PriceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The header in front of every message, read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct FrameHeaderView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> FrameHeaderView<'a> {
+    pub const SIZE: usize = 4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(FrameHeaderView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn length(&self) -> u16 {
+        u16::from_be_bytes(get(self.buf, 0))
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn message_id(&self) -> u16 {
+        u16::from_be_bytes(get(self.buf, 2))
+    }
+}
+
+impl fmt::Debug for FrameHeaderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameHeader")
+            .field("length", &self.length())
+            .field("message_id", &self.message_id())
+            .finish()
+    }
+}
+
+impl fmt::Display for FrameHeaderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The header in front of every message, written in place to a byte slice
+pub struct FrameHeaderMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> FrameHeaderMut<'a> {
+    pub const SIZE: usize = 4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(FrameHeaderMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> FrameHeaderView<'_> {
+        FrameHeaderView { buf: self.buf }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn length(&self) -> u16 {
+        self.view().length()
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn message_id(&self) -> u16 {
+        self.view().message_id()
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn set_length(&mut self, value: u16) {
+        put(self.buf, 0, value.to_be_bytes());
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn set_message_id(&mut self, value: u16) {
+        put(self.buf, 2, value.to_be_bytes());
+    }
+}
+
+impl fmt::Debug for FrameHeaderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for FrameHeaderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Quote` [1], read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct QuoteView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> QuoteView<'a> {
+    pub const SIZE: usize = 51;
+    pub const ID: u32 = 1;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 55;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(QuoteView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `timestamp<ns>`, offset 0, 8 bytes
+    pub fn sent_at(&self) -> u64 {
+        u64::from_be_bytes(get(self.buf, 0))
+    }
+
+    /// `sent_at` as the time since the unix epoch
+    pub fn sent_at_since_epoch(&self) -> core::time::Duration {
+        core::time::Duration::from_nanos(self.sent_at())
+    }
+
+    /// `Side`, offset 8, 2 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        Side::try_from(self.side_raw())
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u16 {
+        u16::from_be_bytes(get(self.buf, 8))
+    }
+
+    /// `Permissions`, offset 10, 2 bytes
+    pub fn permissions(&self) -> Permissions {
+        Permissions::from_bits_retain(u16::from_be_bytes(get(self.buf, 10)))
+    }
+
+    /// `Price`, offset 12, 11 bytes
+    pub fn bid(&self) -> PriceView<'a> {
+        PriceView { buf: &self.buf[12..23] }
+    }
+
+    /// `u32`, offset 23, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        u32::from_be_bytes(get(self.buf, 23))
+    }
+
+    /// `i16[2]`, offset 27, 4 bytes
+    pub fn levels(&self) -> [i16; 2] {
+        core::array::from_fn(|i| i16::from_be_bytes(get(self.buf, 27 + i * 2)))
+    }
+
+    /// `f32`, offset 31, 4 bytes
+    pub fn ratio(&self) -> f32 {
+        f32::from_be_bytes(get(self.buf, 31))
+    }
+
+    /// `f64`, offset 35, 8 bytes
+    pub fn weight(&self) -> f64 {
+        f64::from_be_bytes(get(self.buf, 35))
+    }
+
+    /// `duration<us>`, offset 43, 8 bytes
+    pub fn ttl(&self) -> i64 {
+        i64::from_be_bytes(get(self.buf, 43))
+    }
+}
+
+impl fmt::Debug for QuoteView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quote")
+            .field("sent_at", &self.sent_at())
+            .field("side", &Decoded(self.side().map_err(|_| self.side_raw())))
+            .field("permissions", &self.permissions())
+            .field("bid", &self.bid())
+            .field("sequence", &self.sequence())
+            .field("levels", &self.levels())
+            .field("ratio", &self.ratio())
+            .field("weight", &self.weight())
+            .field("ttl", &self.ttl())
+            .finish()
+    }
+}
+
+impl fmt::Display for QuoteView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Quote` [1], written in place to a byte slice
+pub struct QuoteMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> QuoteMut<'a> {
+    pub const SIZE: usize = 51;
+    pub const ID: u32 = 1;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 55;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(QuoteMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> QuoteView<'_> {
+        QuoteView { buf: self.buf }
+    }
+
+    /// Writes the header to the start of `buf` and returns the message after it, fails if
+    /// `buf` is shorter than [`Self::FRAME_SIZE`]
+    pub fn new_frame(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::FRAME_SIZE)?;
+        let (header, body) = buf.split_at_mut(FrameHeaderMut::SIZE);
+        let mut header = FrameHeaderMut::new(header)?;
+        header.set_message_id(Self::ID as u16);
+        header.set_length(Self::FRAME_SIZE as u16);
+        Self::new(body)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `timestamp<ns>`, offset 0, 8 bytes
+    pub fn sent_at(&self) -> u64 {
+        self.view().sent_at()
+    }
+
+    /// `sent_at` as the time since the unix epoch
+    pub fn sent_at_since_epoch(&self) -> core::time::Duration {
+        self.view().sent_at_since_epoch()
+    }
+
+    /// `Side`, offset 8, 2 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        self.view().side()
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u16 {
+        self.view().side_raw()
+    }
+
+    /// `Permissions`, offset 10, 2 bytes
+    pub fn permissions(&self) -> Permissions {
+        self.view().permissions()
+    }
+
+    /// `Price`, offset 12, 11 bytes
+    pub fn bid(&self) -> PriceView<'_> {
+        self.view().bid()
+    }
+
+    /// `u32`, offset 23, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        self.view().sequence()
+    }
+
+    /// `i16[2]`, offset 27, 4 bytes
+    pub fn levels(&self) -> [i16; 2] {
+        self.view().levels()
+    }
+
+    /// `f32`, offset 31, 4 bytes
+    pub fn ratio(&self) -> f32 {
+        self.view().ratio()
+    }
+
+    /// `f64`, offset 35, 8 bytes
+    pub fn weight(&self) -> f64 {
+        self.view().weight()
+    }
+
+    /// `duration<us>`, offset 43, 8 bytes
+    pub fn ttl(&self) -> i64 {
+        self.view().ttl()
+    }
+
+    /// `timestamp<ns>`, offset 0, 8 bytes
+    pub fn set_sent_at(&mut self, value: u64) {
+        put(self.buf, 0, value.to_be_bytes());
+    }
+
+    /// Stores any value in `side`, even one which isn't declared
+    pub fn set_side_raw(&mut self, value: u16) {
+        put(self.buf, 8, value.to_be_bytes());
+    }
+
+    /// `Side`, offset 8, 2 bytes
+    pub fn set_side(&mut self, value: Side) {
+        put(self.buf, 8, u16::from(value).to_be_bytes());
+    }
+
+    /// `Permissions`, offset 10, 2 bytes
+    pub fn set_permissions(&mut self, value: Permissions) {
+        put(self.buf, 10, value.bits().to_be_bytes());
+    }
+
+    /// `Price`, offset 12, 11 bytes
+    pub fn bid_mut(&mut self) -> PriceMut<'_> {
+        PriceMut { buf: &mut self.buf[12..23] }
+    }
+
+    /// `u32`, offset 23, 4 bytes
+    pub fn set_sequence(&mut self, value: u32) {
+        put(self.buf, 23, value.to_be_bytes());
+    }
+
+    /// `i16[2]`, offset 27, 4 bytes
+    pub fn set_levels(&mut self, value: &[i16; 2]) {
+        for (i, v) in value.iter().enumerate() {
+            put(self.buf, 27 + i * 2, v.to_be_bytes());
+        }
+    }
+
+    /// `f32`, offset 31, 4 bytes
+    pub fn set_ratio(&mut self, value: f32) {
+        put(self.buf, 31, value.to_be_bytes());
+    }
+
+    /// `f64`, offset 35, 8 bytes
+    pub fn set_weight(&mut self, value: f64) {
+        put(self.buf, 35, value.to_be_bytes());
+    }
+
+    /// `duration<us>`, offset 43, 8 bytes
+    pub fn set_ttl(&mut self, value: i64) {
+        put(self.buf, 43, value.to_be_bytes());
+    }
+}
+
+impl fmt::Debug for QuoteMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for QuoteMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Called by [`dispatch`] with the message of a frame, a message without an
+/// implementation is ignored
+pub trait Handler {
+    /// Called with a `Quote` [1]
+    fn on_quote(&mut self, message: QuoteView<'_>) {
+        let _ = message;
+    }
+}
+
+/// Reads the header at the start of `buf` and passes the message after it to the
+/// callback for its id. Returns the size of the frame, anything after it is left
+/// alone.
+pub fn dispatch<H: Handler + ?Sized>(buf: &[u8], handler: &mut H) -> Result<usize, Error> {
+    let header = FrameHeaderView::new(buf)?;
+    let frame = header.length() as usize;
+    if frame < FrameHeaderView::SIZE {
+        return Err(Error::InvalidLength { length: frame });
+    }
+    check_size(buf, frame)?;
+    let body = &buf[FrameHeaderView::SIZE..frame];
+    match u64::from(header.message_id()) {
+        1 => handler.on_quote(QuoteView::new(body)?),
+        id => return Err(Error::UnknownMessageId { id }),
+    }
+    Ok(frame)
+}
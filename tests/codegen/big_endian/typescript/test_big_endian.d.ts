@@ -0,0 +1,122 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+
+/** enum Side, stored as a u16 */
+export type Side = "BUY" | "SELL";
+export declare const Side: {
+  readonly BUY: 1;
+  readonly SELL: 2;
+};
+
+/** bitflags Permissions, stored as a u16 */
+export type Permissions = "READ" | "WRITE" | "ADMIN";
+export declare const Permissions: {
+  readonly READ: 1;
+  readonly WRITE: 2;
+  readonly ADMIN: 128;
+};
+
+/** struct Price */
+export declare class Price {
+  static readonly SIZE: 11;
+  static readonly VALUE_OFFSET: 0;
+  static readonly VALUE_EXPONENT: -4;
+  static readonly CURRENCY_OFFSET: 8;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Price;
+  /** decimal<i64, -4> at offset 0 */
+  value: bigint;
+  /** ascii[3] at offset 8 */
+  currency: string;
+}
+
+/** the header in front of every message */
+export declare class FrameHeader {
+  static readonly SIZE: 4;
+  static readonly LENGTH_OFFSET: 0;
+  static readonly MESSAGE_ID_OFFSET: 2;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): FrameHeader;
+  /** u16 at offset 0 */
+  length: number;
+  /** u16 at offset 2 */
+  messageId: number;
+}
+
+/** message Quote [1] */
+export declare class Quote {
+  static readonly ID: 1;
+  static readonly SIZE: 51;
+  static readonly FRAME_SIZE: 55;
+  static readonly SENT_AT_OFFSET: 0;
+  static readonly SIDE_OFFSET: 8;
+  static readonly PERMISSIONS_OFFSET: 10;
+  static readonly BID_OFFSET: 12;
+  static readonly SEQUENCE_OFFSET: 23;
+  static readonly LEVELS_OFFSET: 27;
+  static readonly LEVELS_LENGTH: 2;
+  static readonly RATIO_OFFSET: 31;
+  static readonly WEIGHT_OFFSET: 35;
+  static readonly TTL_OFFSET: 43;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Quote;
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view: DataView, offset?: number): Quote;
+  /** timestamp<ns> at offset 0 */
+  sentAt: bigint;
+  /** sent_at as a Date */
+  sentAtDate: Date;
+  /** Side at offset 8 */
+  side: Side;
+  sideRaw: number;
+  /** Permissions at offset 10 */
+  permissions: Permissions[];
+  permissionsRaw: number;
+  /** Price at offset 12 */
+  readonly bid: Price;
+  /** u32 at offset 23 */
+  sequence: number;
+  /** i16[2] at offset 27 */
+  getLevels(index: number): number;
+  setLevels(index: number, value: number): void;
+  /** f32 at offset 31 */
+  ratio: number;
+  /** f64 at offset 35 */
+  weight: number;
+  /** duration<us> at offset 43 */
+  ttl: bigint;
+  /** ttl in milliseconds */
+  ttlMs: bigint;
+}
+
+/** Every message of the protocol */
+export type AnyMessage = Quote;
+
+/** Views the message with the given id, throws a RangeError for an unknown id */
+export declare function decode(id: number, view: DataView, offset?: number): AnyMessage;
+
+/** Callbacks for dispatch, a message without a callback is ignored */
+export interface Handler {
+  onQuote?(message: Quote): void;
+}
+
+/**
+ * Reads the header at offset and calls the handler's callback for the message after it, returns
+ * the size of the frame. Throws a RangeError when the view doesn't hold the whole frame, its
+ * length is too small for its message or no message has its id.
+ */
+export declare function dispatch(view: DataView, handler: Handler, offset?: number): number;
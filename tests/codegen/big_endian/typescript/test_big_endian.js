@@ -0,0 +1,333 @@
+// Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+
+const LITTLE_ENDIAN = false;
+
+const ASCII = new TextDecoder("ascii");
+
+function checkSize(view, offset, size, name) {
+  if (!Number.isInteger(offset) || offset < 0 || offset + size > view.byteLength) {
+    throw new RangeError(`${name} needs ${size} bytes at offset ${offset} but the view holds ${view.byteLength}`);
+  }
+}
+
+function checkIndex(index, length, name) {
+  if (!Number.isInteger(index) || index < 0 || index >= length) {
+    throw new RangeError(`${index} is out of range for ${name}, which holds ${length} elements`);
+  }
+}
+
+function bytes(view, offset, length) {
+  return new Uint8Array(view.buffer, view.byteOffset + offset, length);
+}
+
+function getAscii(view, offset, length, pad) {
+  const field = bytes(view, offset, length);
+  let end = field.indexOf(0);
+  if (end < 0) {
+    end = length;
+  }
+  while (end > 0 && field[end - 1] === pad) {
+    end--;
+  }
+  return ASCII.decode(field.subarray(0, end));
+}
+
+function setAscii(view, offset, length, pad, value, name) {
+  if (value.length > length) {
+    throw new RangeError(`${name} holds at most ${length} characters but the value has ${value.length}`);
+  }
+  for (let i = 0; i < value.length; i++) {
+    if (value.charCodeAt(i) > 0x7f) {
+      throw new RangeError(`${name} only holds ascii characters`);
+    }
+  }
+  const field = bytes(view, offset, length);
+  field.fill(pad);
+  for (let i = 0; i < value.length; i++) {
+    field[i] = value.charCodeAt(i);
+  }
+}
+
+function setBytes(view, offset, length, value, name) {
+  if (value.length !== length) {
+    throw new RangeError(`${name} holds exactly ${length} bytes but the value has ${value.length}`);
+  }
+  bytes(view, offset, length).set(value);
+}
+
+function enumName(names, value, type) {
+  const name = names.get(value);
+  if (name === undefined) {
+    throw new RangeError(`${value} is not a declared ${type}`);
+  }
+  return name;
+}
+
+function enumValue(values, name, type) {
+  if (!Object.hasOwn(values, name)) {
+    throw new RangeError(`${name} is not a declared ${type}`);
+  }
+  return values[name];
+}
+
+function flagNames(values, raw) {
+  return Object.keys(values).filter((name) => (raw & values[name]) === values[name]);
+}
+
+function flagValue(values, names, type, zero) {
+  return names.reduce((raw, name) => raw | enumValue(values, name, type), zero);
+}
+
+/** enum Side, stored as a u16 */
+export const Side = Object.freeze({
+  BUY: 1,
+  SELL: 2,
+});
+const SIDE_NAMES = new Map(Object.entries(Side).map(([name, value]) => [value, name]));
+
+/** bitflags Permissions, stored as a u16 */
+export const Permissions = Object.freeze({
+  READ: 1,
+  WRITE: 2,
+  ADMIN: 128,
+});
+
+/** struct Price */
+export class Price {
+  static SIZE = 11;
+  static VALUE_OFFSET = 0;
+  static VALUE_EXPONENT = -4;
+  static CURRENCY_OFFSET = 8;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Price.SIZE, "Price");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Price(new DataView(new ArrayBuffer(Price.SIZE)));
+  }
+
+  /** decimal<i64, -4> at offset 0 */
+  get value() {
+    return this.view.getBigInt64(this.offset, LITTLE_ENDIAN);
+  }
+  set value(value) {
+    this.view.setBigInt64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** ascii[3] at offset 8 */
+  get currency() {
+    return getAscii(this.view, this.offset + 8, 3, 0);
+  }
+  set currency(value) {
+    setAscii(this.view, this.offset + 8, 3, 0, value, "currency");
+  }
+}
+
+/** the header in front of every message */
+export class FrameHeader {
+  static SIZE = 4;
+  static LENGTH_OFFSET = 0;
+  static MESSAGE_ID_OFFSET = 2;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, FrameHeader.SIZE, "FrameHeader");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new FrameHeader(new DataView(new ArrayBuffer(FrameHeader.SIZE)));
+  }
+
+  /** u16 at offset 0 */
+  get length() {
+    return this.view.getUint16(this.offset, LITTLE_ENDIAN);
+  }
+  set length(value) {
+    this.view.setUint16(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** u16 at offset 2 */
+  get messageId() {
+    return this.view.getUint16(this.offset + 2, LITTLE_ENDIAN);
+  }
+  set messageId(value) {
+    this.view.setUint16(this.offset + 2, value, LITTLE_ENDIAN);
+  }
+}
+
+/** message Quote [1] */
+export class Quote {
+  static ID = 1;
+  static SIZE = 51;
+  static FRAME_SIZE = 55;
+  static SENT_AT_OFFSET = 0;
+  static SIDE_OFFSET = 8;
+  static PERMISSIONS_OFFSET = 10;
+  static BID_OFFSET = 12;
+  static SEQUENCE_OFFSET = 23;
+  static LEVELS_OFFSET = 27;
+  static LEVELS_LENGTH = 2;
+  static RATIO_OFFSET = 31;
+  static WEIGHT_OFFSET = 35;
+  static TTL_OFFSET = 43;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Quote.SIZE, "Quote");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Quote(new DataView(new ArrayBuffer(Quote.SIZE)));
+  }
+
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view, offset = 0) {
+    checkSize(view, offset, Quote.FRAME_SIZE, "the frame of Quote");
+    const header = new FrameHeader(view, offset);
+    header.messageId = 1;
+    header.length = 55;
+    return new Quote(view, offset + FrameHeader.SIZE);
+  }
+
+  /** timestamp<ns> at offset 0 */
+  get sentAt() {
+    return this.view.getBigUint64(this.offset, LITTLE_ENDIAN);
+  }
+  set sentAt(value) {
+    this.view.setBigUint64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** sent_at as a Date */
+  get sentAtDate() {
+    return new Date(Number(this.sentAt / 1000000n));
+  }
+  set sentAtDate(value) {
+    this.sentAt = BigInt(value.getTime()) * 1000000n;
+  }
+
+  /** Side at offset 8 */
+  get side() {
+    return enumName(SIDE_NAMES, this.sideRaw, "Side");
+  }
+  set side(value) {
+    this.sideRaw = enumValue(Side, value, "Side");
+  }
+  get sideRaw() {
+    return this.view.getUint16(this.offset + 8, LITTLE_ENDIAN);
+  }
+  set sideRaw(value) {
+    this.view.setUint16(this.offset + 8, value, LITTLE_ENDIAN);
+  }
+
+  /** Permissions at offset 10 */
+  get permissions() {
+    return flagNames(Permissions, this.permissionsRaw);
+  }
+  set permissions(value) {
+    this.permissionsRaw = flagValue(Permissions, value, "Permissions", 0);
+  }
+  get permissionsRaw() {
+    return this.view.getUint16(this.offset + 10, LITTLE_ENDIAN);
+  }
+  set permissionsRaw(value) {
+    this.view.setUint16(this.offset + 10, value, LITTLE_ENDIAN);
+  }
+
+  /** Price at offset 12 */
+  get bid() {
+    return new Price(this.view, this.offset + 12);
+  }
+
+  /** u32 at offset 23 */
+  get sequence() {
+    return this.view.getUint32(this.offset + 23, LITTLE_ENDIAN);
+  }
+  set sequence(value) {
+    this.view.setUint32(this.offset + 23, value, LITTLE_ENDIAN);
+  }
+
+  /** i16[2] at offset 27 */
+  getLevels(index) {
+    checkIndex(index, Quote.LEVELS_LENGTH, "levels");
+    return this.view.getInt16(this.offset + 27 + index * 2, LITTLE_ENDIAN);
+  }
+  setLevels(index, value) {
+    checkIndex(index, Quote.LEVELS_LENGTH, "levels");
+    this.view.setInt16(this.offset + 27 + index * 2, value, LITTLE_ENDIAN);
+  }
+
+  /** f32 at offset 31 */
+  get ratio() {
+    return this.view.getFloat32(this.offset + 31, LITTLE_ENDIAN);
+  }
+  set ratio(value) {
+    this.view.setFloat32(this.offset + 31, value, LITTLE_ENDIAN);
+  }
+
+  /** f64 at offset 35 */
+  get weight() {
+    return this.view.getFloat64(this.offset + 35, LITTLE_ENDIAN);
+  }
+  set weight(value) {
+    this.view.setFloat64(this.offset + 35, value, LITTLE_ENDIAN);
+  }
+
+  /** duration<us> at offset 43 */
+  get ttl() {
+    return this.view.getBigInt64(this.offset + 43, LITTLE_ENDIAN);
+  }
+  set ttl(value) {
+    this.view.setBigInt64(this.offset + 43, value, LITTLE_ENDIAN);
+  }
+
+  /** ttl in milliseconds */
+  get ttlMs() {
+    return this.ttl / 1000n;
+  }
+  set ttlMs(value) {
+    this.ttl = value * 1000n;
+  }
+}
+
+/** Views the message with the given id, throws a RangeError for an unknown id */
+export function decode(id, view, offset = 0) {
+  switch (id) {
+    case Quote.ID:
+      return new Quote(view, offset);
+    default:
+      throw new RangeError(`${id} is not the id of a message of test.big_endian`);
+  }
+}
+
+const CALLBACKS = new Map([
+  [Quote, "onQuote"],
+]);
+
+/**
+ * Reads the header at offset and calls the handler's callback for the message after it, returns
+ * the size of the frame. Throws a RangeError when the view doesn't hold the whole frame, its
+ * length is too small for its message or no message has its id.
+ */
+export function dispatch(view, handler, offset = 0) {
+  const header = new FrameHeader(view, offset);
+  const message = decode(header.messageId, view, offset + FrameHeader.SIZE);
+  const type = message.constructor;
+  const frame = header.length;
+  if (frame < type.FRAME_SIZE) {
+    throw new RangeError(`a frame length of ${frame} is too small for ${type.name}`);
+  }
+  checkSize(view, offset, frame, "the frame");
+  handler[CALLBACKS.get(type)]?.(message);
+  return frame;
+}
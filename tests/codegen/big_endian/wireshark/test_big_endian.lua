@@ -0,0 +1,149 @@
+-- Generated by sssc 0.1.0 from protocol test.big_endian, do not edit.
+-- Load with `wireshark -X lua_script:test_big_endian.lua`, every message is preceded by a header of 4 bytes.
+
+local proto = Proto("test_big_endian", "test.big_endian")
+
+local function decimal_text(mantissa, exponent)
+    local text = tostring(mantissa)
+    if exponent >= 0 then
+        return text .. string.rep("0", exponent)
+    end
+    local sign = ""
+    if text:sub(1, 1) == "-" then
+        sign, text = "-", text:sub(2)
+    end
+    local digits = -exponent
+    if #text <= digits then
+        text = string.rep("0", digits - #text + 1) .. text
+    end
+    return sign .. text:sub(1, #text - digits) .. "." .. text:sub(#text - digits + 1)
+end
+
+local function ascii(range)
+    return (range:string():match("^[^\0]*"))
+end
+
+-- a boolean field per flag of each bitflags
+local flags = {}
+
+-- enum Side, stored as a u16
+local side_names = {
+    [1] = "BUY",
+    [2] = "SELL",
+}
+
+-- bitflags Permissions, stored as a u16
+flags["permissions.read"] = ProtoField.bool("test_big_endian.permissions.read", "READ", 16, nil, 0x1)
+flags["permissions.write"] = ProtoField.bool("test_big_endian.permissions.write", "WRITE", 16, nil, 0x2)
+flags["permissions.admin"] = ProtoField.bool("test_big_endian.permissions.admin", "ADMIN", 16, nil, 0x80)
+
+local message_names = {
+    [1] = "Quote",
+}
+
+local fields = {}
+fields.frame_header = ProtoField.none("test_big_endian.frame_header", "FrameHeader")
+
+-- FrameHeader
+fields["frame_header.length"] = ProtoField.uint16("test_big_endian.frame_header.length", "length", base.DEC, nil, nil, "u16")
+fields["frame_header.message_id"] = ProtoField.uint16("test_big_endian.frame_header.message_id", "message_id", base.DEC, message_names, nil, "u16")
+
+-- Price
+fields["price.value"] = ProtoField.int64("test_big_endian.price.value", "value", base.DEC, nil, nil, "decimal<i64, -4>")
+fields["price.currency"] = ProtoField.string("test_big_endian.price.currency", "currency", base.ASCII, "ascii[3]")
+
+-- Quote
+fields["quote.sent_at"] = ProtoField.uint64("test_big_endian.quote.sent_at", "sent_at", base.DEC, nil, nil, "timestamp<ns>")
+fields["quote.side"] = ProtoField.uint16("test_big_endian.quote.side", "side", base.DEC, side_names, nil, "Side")
+fields["quote.permissions"] = ProtoField.uint16("test_big_endian.quote.permissions", "permissions", base.HEX, nil, nil, "Permissions")
+fields["quote.bid"] = ProtoField.none("test_big_endian.quote.bid", "bid", "Price")
+fields["quote.sequence"] = ProtoField.uint32("test_big_endian.quote.sequence", "sequence", base.DEC, nil, nil, "u32")
+fields["quote.levels.element"] = ProtoField.int16("test_big_endian.quote.levels.element", "levels", base.DEC, nil, nil, "i16")
+fields["quote.levels"] = ProtoField.none("test_big_endian.quote.levels", "levels", "i16[2]")
+fields["quote.ratio"] = ProtoField.float("test_big_endian.quote.ratio", "ratio", nil, "f32")
+fields["quote.weight"] = ProtoField.double("test_big_endian.quote.weight", "weight", nil, "f64")
+fields["quote.ttl"] = ProtoField.int64("test_big_endian.quote.ttl", "ttl", base.DEC, nil, nil, "duration<us>")
+
+local unknown_id = ProtoExpert.new(
+    "test_big_endian.unknown_id", "Unknown message id", expert.group.MALFORMED, expert.severity.ERROR)
+local truncated = ProtoExpert.new(
+    "test_big_endian.truncated", "Truncated message", expert.group.MALFORMED, expert.severity.ERROR)
+local bad_length = ProtoExpert.new(
+    "test_big_endian.bad_length", "Frame length too small for its message", expert.group.MALFORMED, expert.severity.ERROR)
+
+local field_list = {}
+for _, field in pairs(fields) do
+    field_list[#field_list + 1] = field
+end
+for _, field in pairs(flags) do
+    field_list[#field_list + 1] = field
+end
+proto.fields = field_list
+proto.experts = { unknown_id, truncated, bad_length }
+
+local function dissect_price(buffer, offset, tree)
+    tree:add(fields["price.value"], buffer(offset, 8)):append_text(" (" .. decimal_text(buffer(offset, 8):int64(), -4) .. ")")
+    tree:add(fields["price.currency"], buffer(offset + 8, 3), ascii(buffer(offset + 8, 3)))
+end
+
+local function dissect_frame_header(buffer, offset, tree)
+    tree:add(fields["frame_header.length"], buffer(offset, 2))
+    tree:add(fields["frame_header.message_id"], buffer(offset + 2, 2))
+end
+
+local function dissect_quote(buffer, offset, tree)
+    tree:add(fields["quote.sent_at"], buffer(offset, 8))
+    tree:add(fields["quote.side"], buffer(offset + 8, 2))
+    local permissions_item = tree:add(fields["quote.permissions"], buffer(offset + 10, 2))
+    permissions_item:add(flags["permissions.read"], buffer(offset + 10, 2))
+    permissions_item:add(flags["permissions.write"], buffer(offset + 10, 2))
+    permissions_item:add(flags["permissions.admin"], buffer(offset + 10, 2))
+    dissect_price(buffer, offset + 12, tree:add(fields["quote.bid"], buffer(offset + 12, 11)))
+    tree:add(fields["quote.sequence"], buffer(offset + 23, 4))
+    local levels_item = tree:add(fields["quote.levels"], buffer(offset + 27, 4))
+    for i = 0, 1 do
+        levels_item:add(fields["quote.levels.element"], buffer(offset + 27 + i * 2, 2)):prepend_text("[" .. i .. "] ")
+    end
+    tree:add(fields["quote.ratio"], buffer(offset + 31, 4))
+    tree:add(fields["quote.weight"], buffer(offset + 35, 8))
+    tree:add(fields["quote.ttl"], buffer(offset + 43, 8))
+end
+
+local messages = {
+    [1] = { name = "Quote", size = 51, dissect = dissect_quote },
+}
+
+function proto.dissector(buffer, pinfo, tree)
+    pinfo.cols.protocol = proto.name
+    local names = {}
+    local offset = 0
+    while offset + 4 <= buffer:len() do
+        local id = buffer(offset + 2, 2):uint()
+        local message = messages[id]
+        if message == nil then
+            local item = tree:add(fields.frame_header, buffer(offset, 4))
+            dissect_frame_header(buffer, offset, item)
+            item:add_proto_expert_info(unknown_id)
+            break
+        end
+        local frame = buffer(offset, 2):uint()
+        local length = math.min(frame, buffer:len() - offset)
+        local subtree = tree:add(proto, buffer(offset, length), message.name)
+        dissect_frame_header(buffer, offset, subtree:add(fields.frame_header, buffer(offset, 4)))
+        names[#names + 1] = message.name
+        if frame < 4 + message.size then
+            subtree:add_proto_expert_info(bad_length)
+            break
+        end
+        if length < frame then
+            subtree:add_proto_expert_info(truncated)
+            break
+        end
+        message.dissect(buffer, offset + 4, subtree)
+        offset = offset + frame
+    end
+    pinfo.cols.info = table.concat(names, ", ")
+    return offset
+end
+
+DissectorTable.get("udp.port"):add_for_decode_as(proto)
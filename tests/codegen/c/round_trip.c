@@ -0,0 +1,127 @@
+/* Exercises the generated header, built and run by tests/codegen.rs when a C compiler is found. */
+#include "test_codegen.h"
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#define CHECK(condition)                                                   \
+    do {                                                                   \
+        if (!(condition)) {                                                \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #condition); \
+            exit(1);                                                       \
+        }                                                                  \
+    } while (0)
+
+static void test_quote(void) {
+    /* one spare byte in front so every field is misaligned */
+    uint8_t storage[TEST_CODEGEN_QUOTE_SIZE + 1] = {0};
+    uint8_t *buf = storage + 1;
+    uint8_t *bid;
+
+    test_codegen_quote_set_sent_at(buf, 1700000000123456789u);
+    CHECK(test_codegen_quote_set_symbol(buf, "AAPL") == 0);
+    CHECK(test_codegen_quote_set_symbol(buf, "TOOLONGXX") == -1);
+    test_codegen_quote_set_side(buf, TEST_CODEGEN_SIDE_SELL);
+    bid = test_codegen_quote_bid_mut(buf);
+    test_codegen_price_set_value(bid, 1234500);
+    CHECK(test_codegen_price_set_currency(bid, "USD") == 0);
+    test_codegen_quote_set_levels(buf, 0, -1);
+    test_codegen_quote_set_levels(buf, 2, 1);
+    test_codegen_quote_set_weight(buf, 0.5);
+    test_codegen_quote_set_ttl(buf, -250);
+
+    CHECK(test_codegen_quote_get_sent_at(buf) == 1700000000123456789u);
+    CHECK(test_codegen_quote_symbol_length(buf) == 4);
+    CHECK(memcmp(test_codegen_quote_symbol(buf), "AAPL", 4) == 0);
+    CHECK(test_codegen_quote_get_side(buf) == TEST_CODEGEN_SIDE_SELL);
+    CHECK(strcmp(test_codegen_side_name(test_codegen_quote_get_side(buf)), "SELL") == 0);
+    CHECK(test_codegen_side_name(7) == NULL);
+    CHECK(test_codegen_price_get_value(test_codegen_quote_bid(buf)) == 1234500);
+    CHECK(TEST_CODEGEN_PRICE_VALUE_EXPONENT == -4);
+    CHECK(test_codegen_price_currency_length(test_codegen_quote_bid(buf)) == 3);
+    CHECK(test_codegen_quote_get_levels(buf, 0) == -1);
+    CHECK(test_codegen_quote_get_levels(buf, 1) == 0);
+    CHECK(test_codegen_quote_get_levels(buf, 2) == 1);
+    CHECK(test_codegen_quote_get_weight(buf) == 0.5);
+    CHECK(test_codegen_quote_get_ttl(buf) == -250);
+
+    /* the protocol is little endian whatever the host is */
+    CHECK(buf[TEST_CODEGEN_QUOTE_SENT_AT_OFFSET] == 0x15);
+    CHECK(buf[TEST_CODEGEN_QUOTE_SENT_AT_OFFSET + 7] == 0x17);
+}
+
+static void test_request(void) {
+    uint8_t buf[TEST_CODEGEN_REQUEST_SIZE] = {0};
+    const uint8_t checksum[4] = {1, 2, 3, 4};
+    uint8_t out[4];
+    test_codegen_permissions permissions = TEST_CODEGEN_PERMISSIONS_READ | TEST_CODEGEN_PERMISSIONS_ADMIN;
+
+    test_codegen_request_set_sequence(buf, 42);
+    test_codegen_request_set_permissions(buf, permissions);
+    test_codegen_request_set_kind(buf, TEST_CODEGEN_BODY_KIND_CANCEL);
+    test_codegen_body_set_cancel_id(test_codegen_request_body_mut(buf), 99);
+    test_codegen_request_set_checksum(buf, checksum);
+    test_codegen_request_set_type(buf, -2);
+
+    CHECK(test_codegen_request_get_sequence(buf) == 42);
+    CHECK(test_codegen_request_get_permissions(buf) & TEST_CODEGEN_PERMISSIONS_ADMIN);
+    CHECK(!(test_codegen_request_get_permissions(buf) & TEST_CODEGEN_PERMISSIONS_WRITE));
+    CHECK(test_codegen_request_get_kind(buf) == TEST_CODEGEN_BODY_KIND_CANCEL);
+    CHECK(test_codegen_body_get_cancel_id(test_codegen_request_body(buf)) == 99);
+    CHECK(test_codegen_body_get_cancel_id(test_codegen_request_body_cancel_id(buf)) == 99);
+    CHECK(test_codegen_request_body_order(buf) == NULL);
+    test_codegen_request_get_checksum(buf, out);
+    CHECK(memcmp(out, checksum, 4) == 0);
+    CHECK(test_codegen_request_get_type(buf) == -2);
+    CHECK(buf[TEST_CODEGEN_REQUEST_PERMISSIONS_OFFSET] == 0x81);
+
+    CHECK(test_codegen_request_set_venue(buf, "XLON") == 0);
+    CHECK(test_codegen_request_venue_length(buf) == 4);
+    CHECK(memcmp(test_codegen_request_venue(buf), "XLON  ", 6) == 0);
+}
+
+static void test_heartbeat_mirror(void) {
+    uint8_t buf[TEST_CODEGEN_HEARTBEAT_SIZE] = {0};
+    test_codegen_heartbeat_t mirror;
+
+    test_codegen_heartbeat_set_sent_at(buf, 5);
+    test_codegen_heartbeat_set_sequence(buf, 6);
+    test_codegen_heartbeat_set_side(buf, TEST_CODEGEN_SIDE_BUY);
+    memcpy(&mirror, buf, sizeof mirror);
+    if (SSS_HOST_BIG_ENDIAN == TEST_CODEGEN_BIG_ENDIAN) {
+        CHECK(mirror.sent_at == 5);
+        CHECK(mirror.sequence == 6);
+    }
+    CHECK(mirror.side == TEST_CODEGEN_SIDE_BUY);
+}
+
+static void on_heartbeat(void *context, const uint8_t *message) {
+    *(uint32_t *)context = test_codegen_heartbeat_get_sequence(message);
+}
+
+static void test_dispatch(void) {
+    uint8_t buf[TEST_CODEGEN_HEARTBEAT_FRAME_SIZE + TEST_CODEGEN_QUOTE_FRAME_SIZE] = {0};
+    test_codegen_handler handler = {0};
+    uint32_t sequence = 0;
+    size_t frame;
+
+    test_codegen_heartbeat_set_sequence(test_codegen_heartbeat_write_frame(buf), 42);
+    test_codegen_quote_write_frame(buf + TEST_CODEGEN_HEARTBEAT_FRAME_SIZE);
+    CHECK(buf[0] == 24 && buf[2] == 3);
+
+    handler.on_heartbeat = on_heartbeat;
+    frame = test_codegen_dispatch(buf, sizeof buf, &handler, &sequence);
+    CHECK(frame == TEST_CODEGEN_HEARTBEAT_FRAME_SIZE && sequence == 42);
+    CHECK(test_codegen_dispatch(buf + frame, sizeof buf - frame, &handler, &sequence) == TEST_CODEGEN_QUOTE_FRAME_SIZE);
+    CHECK(test_codegen_dispatch(buf, TEST_CODEGEN_HEARTBEAT_FRAME_SIZE - 1, &handler, &sequence) == 0);
+    buf[2] = 9;
+    CHECK(test_codegen_dispatch(buf, sizeof buf, &handler, &sequence) == 0);
+}
+
+int main(void) {
+    test_quote();
+    test_request();
+    test_heartbeat_mirror();
+    test_dispatch();
+    return 0;
+}
@@ -0,0 +1,446 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+#ifndef TEST_CODEGEN_H
+#define TEST_CODEGEN_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#ifndef SSS_C_RUNTIME_V1
+#define SSS_C_RUNTIME_V1
+
+#if defined(__BYTE_ORDER__) && defined(__ORDER_BIG_ENDIAN__) && __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+#define SSS_HOST_BIG_ENDIAN 1
+#else
+#define SSS_HOST_BIG_ENDIAN 0
+#endif
+
+static inline uint16_t sss_bswap16(uint16_t v) {
+    return (uint16_t)((v >> 8) | (v << 8));
+}
+
+static inline uint32_t sss_bswap32(uint32_t v) {
+    return ((v >> 24) & 0xffu) | ((v >> 8) & 0xff00u) | ((v << 8) & 0xff0000u) | (v << 24);
+}
+
+static inline uint64_t sss_bswap64(uint64_t v) {
+    return ((uint64_t)sss_bswap32((uint32_t)v) << 32) | sss_bswap32((uint32_t)(v >> 32));
+}
+
+static inline uint8_t sss_load8(const uint8_t *p, int big_endian) {
+    (void)big_endian;
+    return *p;
+}
+
+static inline uint16_t sss_load16(const uint8_t *p, int big_endian) {
+    uint16_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+}
+
+static inline uint32_t sss_load32(const uint8_t *p, int big_endian) {
+    uint32_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+}
+
+static inline uint64_t sss_load64(const uint8_t *p, int big_endian) {
+    uint64_t v;
+    memcpy(&v, p, sizeof v);
+    return big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+}
+
+static inline void sss_store8(uint8_t *p, uint8_t v, int big_endian) {
+    (void)big_endian;
+    *p = v;
+}
+
+static inline void sss_store16(uint8_t *p, uint16_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap16(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store32(uint8_t *p, uint32_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap32(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline void sss_store64(uint8_t *p, uint64_t v, int big_endian) {
+    v = big_endian != SSS_HOST_BIG_ENDIAN ? sss_bswap64(v) : v;
+    memcpy(p, &v, sizeof v);
+}
+
+static inline float sss_load_f32(const uint8_t *p, int big_endian) {
+    uint32_t bits = sss_load32(p, big_endian);
+    float v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline double sss_load_f64(const uint8_t *p, int big_endian) {
+    uint64_t bits = sss_load64(p, big_endian);
+    double v;
+    memcpy(&v, &bits, sizeof v);
+    return v;
+}
+
+static inline void sss_store_f32(uint8_t *p, float v, int big_endian) {
+    uint32_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store32(p, bits, big_endian);
+}
+
+static inline void sss_store_f64(uint8_t *p, double v, int big_endian) {
+    uint64_t bits;
+    memcpy(&bits, &v, sizeof bits);
+    sss_store64(p, bits, big_endian);
+}
+
+/* The length of the text in an ascii field, which ends at the first nul byte and leaves out the
+   pad bytes ending it */
+static inline size_t sss_ascii_length(const uint8_t *p, size_t capacity, uint8_t pad) {
+    size_t length = 0;
+    while (length < capacity && p[length] != 0) {
+        length++;
+    }
+    while (length > 0 && p[length - 1] == pad) {
+        length--;
+    }
+    return length;
+}
+
+/* Copies a nul terminated string into an ascii field and fills the rest with pad bytes,
+   returns -1 without writing anything if the string is too long or isn't ascii */
+static inline int sss_store_ascii(uint8_t *p, size_t capacity, uint8_t pad, const char *value) {
+    size_t length = strlen(value);
+    size_t i;
+    if (length > capacity) {
+        return -1;
+    }
+    for (i = 0; i < length; i++) {
+        if ((unsigned char)value[i] > 0x7f) {
+            return -1;
+        }
+    }
+    memcpy(p, value, length);
+    memset(p + length, pad, capacity - length);
+    return 0;
+}
+
+#endif
+
+#define TEST_CODEGEN_BIG_ENDIAN 0
+
+/* enum Side, stored as a u8 */
+typedef enum test_codegen_side {
+    TEST_CODEGEN_SIDE_BUY = 1,
+    TEST_CODEGEN_SIDE_SELL = 2,
+} test_codegen_side;
+
+/* The name of the value in the schema, NULL for an undeclared value */
+static inline const char *test_codegen_side_name(uint8_t value) {
+    switch (value) {
+        case 1: return "BUY";
+        case 2: return "SELL";
+        default: return NULL;
+    }
+}
+
+/* bitflags Permissions, stored as a u16 */
+typedef uint16_t test_codegen_permissions;
+#define TEST_CODEGEN_PERMISSIONS_READ ((test_codegen_permissions)1u)
+#define TEST_CODEGEN_PERMISSIONS_WRITE ((test_codegen_permissions)2u)
+#define TEST_CODEGEN_PERMISSIONS_ADMIN ((test_codegen_permissions)128u)
+
+/* enum BodyKind, stored as a u8 */
+typedef enum test_codegen_body_kind {
+    TEST_CODEGEN_BODY_KIND_ORDER = 1,
+    TEST_CODEGEN_BODY_KIND_CANCEL = 2,
+    TEST_CODEGEN_BODY_KIND_PING = 3,
+} test_codegen_body_kind;
+
+/* The name of the value in the schema, NULL for an undeclared value */
+static inline const char *test_codegen_body_kind_name(uint8_t value) {
+    switch (value) {
+        case 1: return "ORDER";
+        case 2: return "CANCEL";
+        case 3: return "PING";
+        default: return NULL;
+    }
+}
+
+/* struct Price */
+#define TEST_CODEGEN_PRICE_SIZE 11
+#define TEST_CODEGEN_PRICE_VALUE_OFFSET 0
+#define TEST_CODEGEN_PRICE_VALUE_EXPONENT (-4)
+#define TEST_CODEGEN_PRICE_CURRENCY_OFFSET 8
+#define TEST_CODEGEN_PRICE_CURRENCY_LENGTH 3
+
+/* value: the amount in units of the currency */
+static inline int64_t test_codegen_price_get_value(const uint8_t *buf) { return (int64_t)sss_load64(buf, 0); }
+static inline void test_codegen_price_set_value(uint8_t *buf, int64_t value) { sss_store64(buf, (uint64_t)value, 0); }
+
+/* currency: ascii[3] at offset 8 */
+/* not nul terminated when the text fills the field, see _length */
+static inline const char *test_codegen_price_currency(const uint8_t *buf) { return (const char *)(buf + 8); }
+static inline size_t test_codegen_price_currency_length(const uint8_t *buf) { return sss_ascii_length(buf + 8, 3, 0); }
+static inline int test_codegen_price_set_currency(uint8_t *buf, const char *value) { return sss_store_ascii(buf + 8, 3, 0, value); }
+
+/* struct Order */
+#define TEST_CODEGEN_ORDER_SIZE 16
+#define TEST_CODEGEN_ORDER_SIDE_OFFSET 0
+#define TEST_CODEGEN_ORDER_QUANTITY_OFFSET 1
+#define TEST_CODEGEN_ORDER_PRICE_OFFSET 5
+
+/* side: Side at offset 0 */
+static inline test_codegen_side test_codegen_order_get_side(const uint8_t *buf) { return (test_codegen_side)sss_load8(buf, 0); }
+static inline void test_codegen_order_set_side(uint8_t *buf, test_codegen_side value) { sss_store8(buf, (uint8_t)value, 0); }
+
+/* quantity: u32 at offset 1 */
+static inline uint32_t test_codegen_order_get_quantity(const uint8_t *buf) { return sss_load32(buf + 1, 0); }
+static inline void test_codegen_order_set_quantity(uint8_t *buf, uint32_t value) { sss_store32(buf + 1, value, 0); }
+
+/* price: Price at offset 5 */
+static inline const uint8_t *test_codegen_order_price(const uint8_t *buf) { return buf + 5; }
+static inline uint8_t *test_codegen_order_price_mut(uint8_t *buf) { return buf + 5; }
+
+/* union Body, the active variant is selected by a BodyKind */
+#define TEST_CODEGEN_BODY_SIZE 16
+#define TEST_CODEGEN_BODY_ORDER_OFFSET 0
+#define TEST_CODEGEN_BODY_CANCEL_ID_OFFSET 0
+#define TEST_CODEGEN_BODY_PING_OFFSET 0
+#define TEST_CODEGEN_BODY_PING_LENGTH 4
+
+/* order: active when the discriminator is ORDER */
+static inline const uint8_t *test_codegen_body_order(const uint8_t *buf) { return buf; }
+static inline uint8_t *test_codegen_body_order_mut(uint8_t *buf) { return buf; }
+
+/* cancel_id: active when the discriminator is CANCEL */
+static inline uint64_t test_codegen_body_get_cancel_id(const uint8_t *buf) { return sss_load64(buf, 0); }
+static inline void test_codegen_body_set_cancel_id(uint8_t *buf, uint64_t value) { sss_store64(buf, value, 0); }
+
+/* ping: active when the discriminator is PING */
+/* not nul terminated when the text fills the field, see _length */
+static inline const char *test_codegen_body_ping(const uint8_t *buf) { return (const char *)(buf); }
+static inline size_t test_codegen_body_ping_length(const uint8_t *buf) { return sss_ascii_length(buf, 4, 0); }
+static inline int test_codegen_body_set_ping(uint8_t *buf, const char *value) { return sss_store_ascii(buf, 4, 0, value); }
+
+/* the header in front of every message */
+#define TEST_CODEGEN_FRAME_HEADER_SIZE 8
+#define TEST_CODEGEN_FRAME_HEADER_LENGTH_OFFSET 0
+#define TEST_CODEGEN_FRAME_HEADER_MESSAGE_ID_OFFSET 2
+#define TEST_CODEGEN_FRAME_HEADER_SEQUENCE_OFFSET 4
+
+/* length: u16 at offset 0 */
+static inline uint16_t test_codegen_frame_header_get_length(const uint8_t *buf) { return sss_load16(buf, 0); }
+static inline void test_codegen_frame_header_set_length(uint8_t *buf, uint16_t value) { sss_store16(buf, value, 0); }
+
+/* message_id: u16 at offset 2 */
+static inline uint16_t test_codegen_frame_header_get_message_id(const uint8_t *buf) { return sss_load16(buf + 2, 0); }
+static inline void test_codegen_frame_header_set_message_id(uint8_t *buf, uint16_t value) { sss_store16(buf + 2, value, 0); }
+
+/* sequence: u32 at offset 4 */
+static inline uint32_t test_codegen_frame_header_get_sequence(const uint8_t *buf) { return sss_load32(buf + 4, 0); }
+static inline void test_codegen_frame_header_set_sequence(uint8_t *buf, uint32_t value) { sss_store32(buf + 4, value, 0); }
+
+/* Laid out like the wire format, only valid to read or write directly when the
+ * host byte order matches the protocol's */
+typedef struct {
+    uint16_t length;
+    uint16_t message_id;
+    uint32_t sequence;
+} test_codegen_frame_header_t;
+typedef char test_codegen_frame_header_size_check[(sizeof(test_codegen_frame_header_t) == 8) ? 1 : -1];
+typedef char test_codegen_frame_header_length_check[(offsetof(test_codegen_frame_header_t, length) == 0) ? 1 : -1];
+typedef char test_codegen_frame_header_message_id_check[(offsetof(test_codegen_frame_header_t, message_id) == 2) ? 1 : -1];
+typedef char test_codegen_frame_header_sequence_check[(offsetof(test_codegen_frame_header_t, sequence) == 4) ? 1 : -1];
+
+/* message Quote [1] */
+#define TEST_CODEGEN_QUOTE_SIZE 67
+#define TEST_CODEGEN_QUOTE_ID 1u
+#define TEST_CODEGEN_QUOTE_FRAME_SIZE 75
+#define TEST_CODEGEN_QUOTE_SENT_AT_OFFSET 0
+#define TEST_CODEGEN_QUOTE_SYMBOL_OFFSET 8
+#define TEST_CODEGEN_QUOTE_SYMBOL_LENGTH 8
+#define TEST_CODEGEN_QUOTE_SIDE_OFFSET 16
+#define TEST_CODEGEN_QUOTE_BID_OFFSET 17
+#define TEST_CODEGEN_QUOTE_ASK_OFFSET 28
+#define TEST_CODEGEN_QUOTE_LEVELS_OFFSET 39
+#define TEST_CODEGEN_QUOTE_LEVELS_LENGTH 3
+#define TEST_CODEGEN_QUOTE_WEIGHT_OFFSET 51
+#define TEST_CODEGEN_QUOTE_TTL_OFFSET 59
+
+/* sent_at: when the quote was sent */
+static inline uint64_t test_codegen_quote_get_sent_at(const uint8_t *buf) { return (uint64_t)sss_load64(buf, 0); }
+static inline void test_codegen_quote_set_sent_at(uint8_t *buf, uint64_t value) { sss_store64(buf, (uint64_t)value, 0); }
+
+/* symbol: ascii[8] at offset 8 */
+/* not nul terminated when the text fills the field, see _length */
+static inline const char *test_codegen_quote_symbol(const uint8_t *buf) { return (const char *)(buf + 8); }
+static inline size_t test_codegen_quote_symbol_length(const uint8_t *buf) { return sss_ascii_length(buf + 8, 8, 0); }
+static inline int test_codegen_quote_set_symbol(uint8_t *buf, const char *value) { return sss_store_ascii(buf + 8, 8, 0, value); }
+
+/* side: Side at offset 16 */
+static inline test_codegen_side test_codegen_quote_get_side(const uint8_t *buf) { return (test_codegen_side)sss_load8(buf + 16, 0); }
+static inline void test_codegen_quote_set_side(uint8_t *buf, test_codegen_side value) { sss_store8(buf + 16, (uint8_t)value, 0); }
+
+/* bid: Price at offset 17 */
+static inline const uint8_t *test_codegen_quote_bid(const uint8_t *buf) { return buf + 17; }
+static inline uint8_t *test_codegen_quote_bid_mut(uint8_t *buf) { return buf + 17; }
+
+/* ask: Price at offset 28 */
+static inline const uint8_t *test_codegen_quote_ask(const uint8_t *buf) { return buf + 28; }
+static inline uint8_t *test_codegen_quote_ask_mut(uint8_t *buf) { return buf + 28; }
+
+/* levels: i32[3] at offset 39 */
+/* index is not bounds checked */
+static inline int32_t test_codegen_quote_get_levels(const uint8_t *buf, size_t index) { return (int32_t)sss_load32(buf + 39 + index * 4, 0); }
+static inline void test_codegen_quote_set_levels(uint8_t *buf, size_t index, int32_t value) { sss_store32(buf + 39 + index * 4, (uint32_t)value, 0); }
+
+/* weight: f64 at offset 51 */
+static inline double test_codegen_quote_get_weight(const uint8_t *buf) { return sss_load_f64(buf + 51, 0); }
+static inline void test_codegen_quote_set_weight(uint8_t *buf, double value) { sss_store_f64(buf + 51, value, 0); }
+
+/* ttl: how long the quote can be traded on, counted from sent_at */
+static inline int64_t test_codegen_quote_get_ttl(const uint8_t *buf) { return (int64_t)sss_load64(buf + 59, 0); }
+static inline void test_codegen_quote_set_ttl(uint8_t *buf, int64_t value) { sss_store64(buf + 59, (uint64_t)value, 0); }
+
+/* writes the header to the start of buf and returns the message after it, buf must hold
+ * TEST_CODEGEN_QUOTE_FRAME_SIZE bytes */
+static inline uint8_t *test_codegen_quote_write_frame(uint8_t *buf) { test_codegen_frame_header_set_message_id(buf, TEST_CODEGEN_QUOTE_ID); test_codegen_frame_header_set_length(buf, TEST_CODEGEN_QUOTE_FRAME_SIZE); return buf + TEST_CODEGEN_FRAME_HEADER_SIZE; }
+
+/* message Request [2] */
+#define TEST_CODEGEN_REQUEST_SIZE 48
+#define TEST_CODEGEN_REQUEST_ID 2u
+#define TEST_CODEGEN_REQUEST_FRAME_SIZE 56
+#define TEST_CODEGEN_REQUEST_SEQUENCE_OFFSET 0
+#define TEST_CODEGEN_REQUEST_PERMISSIONS_OFFSET 4
+#define TEST_CODEGEN_REQUEST_KIND_OFFSET 6
+#define TEST_CODEGEN_REQUEST_BODY_OFFSET 7
+#define TEST_CODEGEN_REQUEST_CHECKSUM_OFFSET 23
+#define TEST_CODEGEN_REQUEST_CHECKSUM_LENGTH 4
+#define TEST_CODEGEN_REQUEST_TYPE_OFFSET 29
+#define TEST_CODEGEN_REQUEST_VENUE_OFFSET 31
+#define TEST_CODEGEN_REQUEST_VENUE_LENGTH 6
+
+/* sequence: u32 at offset 0 */
+static inline uint32_t test_codegen_request_get_sequence(const uint8_t *buf) { return sss_load32(buf, 0); }
+static inline void test_codegen_request_set_sequence(uint8_t *buf, uint32_t value) { sss_store32(buf, value, 0); }
+
+/* permissions: Permissions at offset 4 */
+static inline test_codegen_permissions test_codegen_request_get_permissions(const uint8_t *buf) { return (test_codegen_permissions)sss_load16(buf + 4, 0); }
+static inline void test_codegen_request_set_permissions(uint8_t *buf, test_codegen_permissions value) { sss_store16(buf + 4, (uint16_t)value, 0); }
+
+/* kind: BodyKind at offset 6 */
+static inline test_codegen_body_kind test_codegen_request_get_kind(const uint8_t *buf) { return (test_codegen_body_kind)sss_load8(buf + 6, 0); }
+static inline void test_codegen_request_set_kind(uint8_t *buf, test_codegen_body_kind value) { sss_store8(buf + 6, (uint8_t)value, 0); }
+
+/* body: the order or cancel the request is for */
+static inline const uint8_t *test_codegen_request_body(const uint8_t *buf) { return buf + 7; }
+static inline uint8_t *test_codegen_request_body_mut(uint8_t *buf) { return buf + 7; }
+/* the union if kind selects the variant, NULL otherwise */
+static inline const uint8_t *test_codegen_request_body_order(const uint8_t *buf) { return test_codegen_request_get_kind(buf) == TEST_CODEGEN_BODY_KIND_ORDER ? buf + 7 : NULL; }
+static inline const uint8_t *test_codegen_request_body_cancel_id(const uint8_t *buf) { return test_codegen_request_get_kind(buf) == TEST_CODEGEN_BODY_KIND_CANCEL ? buf + 7 : NULL; }
+static inline const uint8_t *test_codegen_request_body_ping(const uint8_t *buf) { return test_codegen_request_get_kind(buf) == TEST_CODEGEN_BODY_KIND_PING ? buf + 7 : NULL; }
+
+/* checksum: byte[4] at offset 23 */
+static inline void test_codegen_request_get_checksum(const uint8_t *buf, uint8_t out[4]) { memcpy(out, buf + 23, 4); }
+static inline void test_codegen_request_set_checksum(uint8_t *buf, const uint8_t value[4]) { memcpy(buf + 23, value, 4); }
+
+/* type: i16 at offset 29 */
+static inline int16_t test_codegen_request_get_type(const uint8_t *buf) { return (int16_t)sss_load16(buf + 29, 0); }
+static inline void test_codegen_request_set_type(uint8_t *buf, int16_t value) { sss_store16(buf + 29, (uint16_t)value, 0); }
+
+/* venue: ascii[6] at offset 31 */
+/* not nul terminated when the text fills the field, see _length */
+static inline const char *test_codegen_request_venue(const uint8_t *buf) { return (const char *)(buf + 31); }
+static inline size_t test_codegen_request_venue_length(const uint8_t *buf) { return sss_ascii_length(buf + 31, 6, ' '); }
+static inline int test_codegen_request_set_venue(uint8_t *buf, const char *value) { return sss_store_ascii(buf + 31, 6, ' ', value); }
+
+/* writes the header to the start of buf and returns the message after it, buf must hold
+ * TEST_CODEGEN_REQUEST_FRAME_SIZE bytes */
+static inline uint8_t *test_codegen_request_write_frame(uint8_t *buf) { test_codegen_frame_header_set_message_id(buf, TEST_CODEGEN_REQUEST_ID); test_codegen_frame_header_set_length(buf, TEST_CODEGEN_REQUEST_FRAME_SIZE); return buf + TEST_CODEGEN_FRAME_HEADER_SIZE; }
+
+/* message Heartbeat [3] */
+#define TEST_CODEGEN_HEARTBEAT_SIZE 16
+#define TEST_CODEGEN_HEARTBEAT_ID 3u
+#define TEST_CODEGEN_HEARTBEAT_FRAME_SIZE 24
+#define TEST_CODEGEN_HEARTBEAT_SENT_AT_OFFSET 0
+#define TEST_CODEGEN_HEARTBEAT_SEQUENCE_OFFSET 8
+#define TEST_CODEGEN_HEARTBEAT_SIDE_OFFSET 12
+
+/* sent_at: u64 at offset 0 */
+static inline uint64_t test_codegen_heartbeat_get_sent_at(const uint8_t *buf) { return sss_load64(buf, 0); }
+static inline void test_codegen_heartbeat_set_sent_at(uint8_t *buf, uint64_t value) { sss_store64(buf, value, 0); }
+
+/* sequence: u32 at offset 8 */
+static inline uint32_t test_codegen_heartbeat_get_sequence(const uint8_t *buf) { return sss_load32(buf + 8, 0); }
+static inline void test_codegen_heartbeat_set_sequence(uint8_t *buf, uint32_t value) { sss_store32(buf + 8, value, 0); }
+
+/* side: Side at offset 12 */
+static inline test_codegen_side test_codegen_heartbeat_get_side(const uint8_t *buf) { return (test_codegen_side)sss_load8(buf + 12, 0); }
+static inline void test_codegen_heartbeat_set_side(uint8_t *buf, test_codegen_side value) { sss_store8(buf + 12, (uint8_t)value, 0); }
+
+/* writes the header to the start of buf and returns the message after it, buf must hold
+ * TEST_CODEGEN_HEARTBEAT_FRAME_SIZE bytes */
+static inline uint8_t *test_codegen_heartbeat_write_frame(uint8_t *buf) { test_codegen_frame_header_set_message_id(buf, TEST_CODEGEN_HEARTBEAT_ID); test_codegen_frame_header_set_length(buf, TEST_CODEGEN_HEARTBEAT_FRAME_SIZE); return buf + TEST_CODEGEN_FRAME_HEADER_SIZE; }
+
+/* Laid out like the wire format, only valid to read or write directly when the
+ * host byte order matches the protocol's */
+typedef struct {
+    uint64_t sent_at;
+    uint32_t sequence;
+    uint8_t side;
+    uint8_t reserved0[3];
+} test_codegen_heartbeat_t;
+typedef char test_codegen_heartbeat_size_check[(sizeof(test_codegen_heartbeat_t) == 16) ? 1 : -1];
+typedef char test_codegen_heartbeat_sent_at_check[(offsetof(test_codegen_heartbeat_t, sent_at) == 0) ? 1 : -1];
+typedef char test_codegen_heartbeat_sequence_check[(offsetof(test_codegen_heartbeat_t, sequence) == 8) ? 1 : -1];
+typedef char test_codegen_heartbeat_side_check[(offsetof(test_codegen_heartbeat_t, side) == 12) ? 1 : -1];
+
+/* callbacks for test_codegen_dispatch, a NULL callback skips its message */
+typedef struct test_codegen_handler {
+    void (*on_quote)(void *context, const uint8_t *message);
+    void (*on_request)(void *context, const uint8_t *message);
+    void (*on_heartbeat)(void *context, const uint8_t *message);
+} test_codegen_handler;
+
+/* reads the header at the start of buf and calls the callback for its id with the message
+ * after it, returns the size of the frame or 0 when the size bytes of buf don't hold the
+ * whole frame, its length is too small for its message or no message has its id */
+static inline size_t test_codegen_dispatch(const uint8_t *buf, size_t size, const test_codegen_handler *handler, void *context) {
+    if (size < TEST_CODEGEN_FRAME_HEADER_SIZE) {
+        return 0;
+    }
+    size_t frame = (size_t)test_codegen_frame_header_get_length(buf);
+    switch (test_codegen_frame_header_get_message_id(buf)) {
+    case TEST_CODEGEN_QUOTE_ID:
+        if (frame < TEST_CODEGEN_QUOTE_FRAME_SIZE || size < frame) {
+            return 0;
+        }
+        if (handler->on_quote) {
+            handler->on_quote(context, buf + TEST_CODEGEN_FRAME_HEADER_SIZE);
+        }
+        return frame;
+    case TEST_CODEGEN_REQUEST_ID:
+        if (frame < TEST_CODEGEN_REQUEST_FRAME_SIZE || size < frame) {
+            return 0;
+        }
+        if (handler->on_request) {
+            handler->on_request(context, buf + TEST_CODEGEN_FRAME_HEADER_SIZE);
+        }
+        return frame;
+    case TEST_CODEGEN_HEARTBEAT_ID:
+        if (frame < TEST_CODEGEN_HEARTBEAT_FRAME_SIZE || size < frame) {
+            return 0;
+        }
+        if (handler->on_heartbeat) {
+            handler->on_heartbeat(context, buf + TEST_CODEGEN_FRAME_HEADER_SIZE);
+        }
+        return frame;
+    default:
+        return 0;
+    }
+}
+
+#endif /* TEST_CODEGEN_H */
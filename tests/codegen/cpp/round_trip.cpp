@@ -0,0 +1,76 @@
+// Exercises the generated header, built and run by tests/codegen.rs when a C++ compiler is found.
+#include "test_codegen.hpp"
+
+#include <cstdio>
+#include <cstdlib>
+
+#define CHECK(condition)                                                        \
+    do {                                                                        \
+        if (!(condition)) {                                                     \
+            std::fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #condition); \
+            std::exit(1);                                                       \
+        }                                                                       \
+    } while (0)
+
+using namespace test::codegen;
+
+int main() {
+    std::byte buf[Quote::size] = {};
+    Quote quote(buf);
+    quote.set_sent_at(sss::sys_time<std::chrono::nanoseconds>(std::chrono::nanoseconds(1700000000123456789)));
+    CHECK(quote.set_symbol("AAPL"));
+    CHECK(!quote.set_symbol("TOOLONGXX"));
+    quote.set_side(Side::SELL);
+    quote.bid().set_value(1234500);
+    CHECK(quote.bid().set_currency("USD"));
+    quote.set_levels({-1, 0, 1});
+    quote.set_weight(0.5);
+    quote.set_ttl(std::chrono::microseconds(-250));
+
+    CHECK(quote.sent_at().time_since_epoch().count() == 1700000000123456789);
+    CHECK(quote.symbol().view() == "AAPL");
+    CHECK(quote.side() == Side::SELL);
+    CHECK(to_string(quote.side()) == "SELL");
+    CHECK(!is_valid(static_cast<Side>(7)));
+    CHECK(quote.bid().value() == 1234500);
+    CHECK(Price::value_exponent == -4);
+    CHECK(std::string_view(quote.bid().currency()) == "USD");
+    CHECK(quote.levels()[0] == -1 && quote.levels()[2] == 1);
+    CHECK(quote.weight() == 0.5);
+    CHECK(quote.ttl().count() == -250);
+    CHECK(std::to_integer<int>(buf[Quote::offsets::side]) == 2);
+    static_assert(Quote::offsets::ttl == 59);
+
+    std::byte request_buf[Request::size] = {};
+    Request request(request_buf);
+    request.set_sequence(0x01020304);
+    CHECK(std::to_integer<int>(request_buf[0]) == 4 && std::to_integer<int>(request_buf[3]) == 1);
+    request.set_permissions(Permissions::READ | Permissions::ADMIN);
+    CHECK(has(request.permissions(), Permissions::ADMIN));
+    CHECK(!has(request.permissions(), Permissions::WRITE));
+    request.set_kind(BodyKind::ORDER);
+    request.body().order().set_quantity(10);
+    CHECK(request.body().order().quantity() == 10);
+    CHECK(request.body_order().has_value() && request.body_order()->quantity() == 10);
+    CHECK(!request.body_cancel_id().has_value());
+    request.set_type(-3);
+    CHECK(request.type() == -3);
+    CHECK(request.set_venue("XLON"));
+    CHECK(request.venue().view() == "XLON");
+    CHECK(std::to_integer<char>(request_buf[36]) == ' ');
+
+    std::byte frames[Heartbeat::frame_size + Quote::frame_size] = {};
+    Heartbeat::write_frame(frames).set_sequence(42);
+    Quote::write_frame(frames + Heartbeat::frame_size);
+    CHECK(std::to_integer<int>(frames[0]) == 24 && std::to_integer<int>(frames[2]) == 3);
+
+    std::uint32_t sequence = 0;
+    auto on_heartbeat = [&](Heartbeat heartbeat) { sequence = heartbeat.sequence(); };
+    std::size_t frame = dispatch(frames, sizeof(frames), on_heartbeat);
+    CHECK(frame == Heartbeat::frame_size && sequence == 42);
+    CHECK(dispatch(frames + frame, sizeof(frames) - frame, on_heartbeat) == Quote::frame_size);
+    CHECK(dispatch(frames, Heartbeat::frame_size - 1, on_heartbeat) == 0);
+    frames[2] = std::byte{9};
+    CHECK(dispatch(frames, sizeof(frames), on_heartbeat) == 0);
+    return 0;
+}
@@ -0,0 +1,694 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+#pragma once
+
+#include <array>
+#include <chrono>
+#include <cstddef>
+#include <cstdint>
+#include <cstring>
+#include <string_view>
+#include <optional>
+#include <type_traits>
+
+#ifndef SSS_CPP_RUNTIME_V1
+#define SSS_CPP_RUNTIME_V1
+namespace sss {
+
+/// A fixed length ascii field, the text ends at the first nul byte or after `N` characters and
+/// leaves out the `Pad` characters ending it
+template <std::size_t N, char Pad = '\0'>
+class ascii_view {
+public:
+    explicit ascii_view(const std::byte* data) noexcept : data_(data) {}
+
+    static constexpr std::size_t capacity() noexcept { return N; }
+
+    std::string_view view() const noexcept {
+        const char* chars = reinterpret_cast<const char*>(data_);
+        std::size_t length = 0;
+        while (length < N && chars[length] != '\0') {
+            ++length;
+        }
+        while (length > 0 && chars[length - 1] == Pad) {
+            --length;
+        }
+        return std::string_view(chars, length);
+    }
+
+    operator std::string_view() const noexcept { return view(); }
+
+    const std::byte* data() const noexcept { return data_; }
+
+private:
+    const std::byte* data_;
+};
+
+template <typename Duration>
+using sys_time = std::chrono::time_point<std::chrono::system_clock, Duration>;
+
+namespace detail {
+
+template <std::size_t N> struct uint_of;
+template <> struct uint_of<1> { using type = std::uint8_t; };
+template <> struct uint_of<2> { using type = std::uint16_t; };
+template <> struct uint_of<4> { using type = std::uint32_t; };
+template <> struct uint_of<8> { using type = std::uint64_t; };
+
+/// Reads a `T` byte by byte, which is safe at any alignment and independent of the host byte order
+template <typename T, bool BigEndian>
+inline T load(const std::byte* p) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits = 0;
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        bits |= static_cast<U>(static_cast<U>(std::to_integer<std::uint8_t>(p[i])) << shift);
+    }
+    T value;
+    std::memcpy(&value, &bits, sizeof(T));
+    return value;
+}
+
+template <typename T, bool BigEndian>
+inline void store(std::byte* p, T value) noexcept {
+    using U = typename uint_of<sizeof(T)>::type;
+    U bits;
+    std::memcpy(&bits, &value, sizeof(T));
+    for (std::size_t i = 0; i < sizeof(T); ++i) {
+        std::size_t shift = BigEndian ? (sizeof(T) - 1 - i) * 8 : i * 8;
+        p[i] = static_cast<std::byte>(bits >> shift);
+    }
+}
+
+/// Copies `value` into an ascii field and fills the rest with `pad`, fails if it doesn't fit
+inline bool store_ascii(std::byte* p, std::size_t capacity, char pad, std::string_view value) noexcept {
+    if (value.size() > capacity) {
+        return false;
+    }
+    for (char c : value) {
+        if (static_cast<unsigned char>(c) > 0x7f) {
+            return false;
+        }
+    }
+    std::memcpy(p, value.data(), value.size());
+    std::memset(p + value.size(), pad, capacity - value.size());
+    return true;
+}
+
+} // namespace detail
+} // namespace sss
+#endif
+
+namespace test::codegen {
+
+inline constexpr bool big_endian = false;
+
+enum class Side : std::uint8_t {
+    BUY = 1,
+    SELL = 2,
+};
+
+/// The name of the value in the schema, empty for an undeclared value
+constexpr std::string_view to_string(Side value) noexcept {
+    switch (value) {
+        case Side::BUY: return "BUY";
+        case Side::SELL: return "SELL";
+    }
+    return {};
+}
+
+constexpr bool is_valid(Side value) noexcept {
+    return !to_string(value).empty();
+}
+
+enum class Permissions : std::uint16_t {
+    READ = 1,
+    WRITE = 2,
+    ADMIN = 128,
+};
+
+constexpr Permissions operator|(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) | static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator|=(Permissions& a, Permissions b) noexcept {
+    return a = a | b;
+}
+
+constexpr Permissions operator&(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) & static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator&=(Permissions& a, Permissions b) noexcept {
+    return a = a & b;
+}
+
+constexpr Permissions operator^(Permissions a, Permissions b) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(a) ^ static_cast<std::uint16_t>(b));
+}
+
+constexpr Permissions& operator^=(Permissions& a, Permissions b) noexcept {
+    return a = a ^ b;
+}
+
+constexpr Permissions operator~(Permissions a) noexcept {
+    return static_cast<Permissions>(static_cast<std::uint16_t>(~static_cast<std::uint16_t>(a)));
+}
+
+/// True if every flag of `flags` is set in `value`
+constexpr bool has(Permissions value, Permissions flags) noexcept {
+    return (value & flags) == flags;
+}
+
+constexpr bool any(Permissions value) noexcept {
+    return static_cast<std::uint16_t>(value) != 0;
+}
+
+enum class BodyKind : std::uint8_t {
+    ORDER = 1,
+    CANCEL = 2,
+    PING = 3,
+};
+
+/// The name of the value in the schema, empty for an undeclared value
+constexpr std::string_view to_string(BodyKind value) noexcept {
+    switch (value) {
+        case BodyKind::ORDER: return "ORDER";
+        case BodyKind::CANCEL: return "CANCEL";
+        case BodyKind::PING: return "PING";
+    }
+    return {};
+}
+
+constexpr bool is_valid(BodyKind value) noexcept {
+    return !to_string(value).empty();
+}
+
+/// struct Price
+class Price {
+public:
+    static constexpr std::size_t size = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    static constexpr int value_exponent = -4;
+
+    struct offsets {
+        static constexpr std::size_t value = 0;
+        static constexpr std::size_t currency = 8;
+    };
+
+    explicit Price(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// the amount in units of the currency
+    std::int64_t value() const noexcept {
+        return sss::detail::load<std::int64_t, big_endian>(data_ + 0);
+    }
+    void set_value(std::int64_t value) noexcept {
+        sss::detail::store<std::int64_t, big_endian>(data_ + 0, value);
+    }
+
+    /// ascii[3], offset 8, 3 bytes
+    sss::ascii_view<3> currency() const noexcept {
+        return sss::ascii_view<3>(data_ + 8);
+    }
+    /// Fails if the value is longer than the field or isn't ascii
+    bool set_currency(std::string_view value) noexcept {
+        return sss::detail::store_ascii(data_ + 8, 3, '\0', value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Price::offsets::value + sizeof(std::int64_t) == 8, "Price.value must end at byte 8");
+static_assert(Price::offsets::currency + sizeof(char[3]) == 11, "Price.currency must end at byte 11");
+static_assert(sizeof(std::int64_t) + sizeof(char[3]) == Price::size, "Price must be 11 bytes");
+
+/// struct Order
+class Order {
+public:
+    static constexpr std::size_t size = 16;
+
+    struct offsets {
+        static constexpr std::size_t side = 0;
+        static constexpr std::size_t quantity = 1;
+        static constexpr std::size_t price = 5;
+    };
+
+    explicit Order(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Side, offset 0, 1 bytes
+    Side side() const noexcept {
+        return static_cast<Side>(sss::detail::load<std::uint8_t, big_endian>(data_ + 0));
+    }
+    void set_side(Side value) noexcept {
+        sss::detail::store<std::uint8_t, big_endian>(data_ + 0, static_cast<std::uint8_t>(value));
+    }
+
+    /// u32, offset 1, 4 bytes
+    std::uint32_t quantity() const noexcept {
+        return sss::detail::load<std::uint32_t, big_endian>(data_ + 1);
+    }
+    void set_quantity(std::uint32_t value) noexcept {
+        sss::detail::store<std::uint32_t, big_endian>(data_ + 1, value);
+    }
+
+    /// Price, offset 5, 11 bytes
+    Price price() const noexcept {
+        return Price(data_ + 5);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Order::offsets::side + sizeof(Side) == 1, "Order.side must end at byte 1");
+static_assert(Order::offsets::quantity + sizeof(std::uint32_t) == 5, "Order.quantity must end at byte 5");
+static_assert(Order::offsets::price + Price::size == 16, "Order.price must end at byte 16");
+static_assert(sizeof(Side) + sizeof(std::uint32_t) + Price::size == Order::size, "Order must be 16 bytes");
+
+/// union Body, the active variant is selected by a BodyKind
+class Body {
+public:
+    static constexpr std::size_t size = 16;
+
+    struct offsets {
+        static constexpr std::size_t order = 0;
+        static constexpr std::size_t cancel_id = 0;
+        static constexpr std::size_t ping = 0;
+    };
+
+    explicit Body(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Active when the discriminator is ORDER
+    Order order() const noexcept {
+        return Order(data_ + 0);
+    }
+
+    /// Active when the discriminator is CANCEL
+    std::uint64_t cancel_id() const noexcept {
+        return sss::detail::load<std::uint64_t, big_endian>(data_ + 0);
+    }
+    void set_cancel_id(std::uint64_t value) noexcept {
+        sss::detail::store<std::uint64_t, big_endian>(data_ + 0, value);
+    }
+
+    /// Active when the discriminator is PING
+    sss::ascii_view<4> ping() const noexcept {
+        return sss::ascii_view<4>(data_ + 0);
+    }
+    /// Fails if the value is longer than the field or isn't ascii
+    bool set_ping(std::string_view value) noexcept {
+        return sss::detail::store_ascii(data_ + 0, 4, '\0', value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Body::offsets::order + Order::size == 16, "Body.order must end at byte 16");
+static_assert(Body::offsets::cancel_id + sizeof(std::uint64_t) == 8, "Body.cancel_id must end at byte 8");
+static_assert(Body::offsets::ping + sizeof(char[4]) == 4, "Body.ping must end at byte 4");
+
+/// the header in front of every message
+class FrameHeader {
+public:
+    static constexpr std::size_t size = 8;
+
+    struct offsets {
+        static constexpr std::size_t length = 0;
+        static constexpr std::size_t message_id = 2;
+        static constexpr std::size_t sequence = 4;
+    };
+
+    explicit FrameHeader(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// u16, offset 0, 2 bytes
+    std::uint16_t length() const noexcept {
+        return sss::detail::load<std::uint16_t, big_endian>(data_ + 0);
+    }
+    void set_length(std::uint16_t value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 0, value);
+    }
+
+    /// u16, offset 2, 2 bytes
+    std::uint16_t message_id() const noexcept {
+        return sss::detail::load<std::uint16_t, big_endian>(data_ + 2);
+    }
+    void set_message_id(std::uint16_t value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 2, value);
+    }
+
+    /// u32, offset 4, 4 bytes
+    std::uint32_t sequence() const noexcept {
+        return sss::detail::load<std::uint32_t, big_endian>(data_ + 4);
+    }
+    void set_sequence(std::uint32_t value) noexcept {
+        sss::detail::store<std::uint32_t, big_endian>(data_ + 4, value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(FrameHeader::offsets::length + sizeof(std::uint16_t) == 2, "FrameHeader.length must end at byte 2");
+static_assert(FrameHeader::offsets::message_id + sizeof(std::uint16_t) == 4, "FrameHeader.message_id must end at byte 4");
+static_assert(FrameHeader::offsets::sequence + sizeof(std::uint32_t) == 8, "FrameHeader.sequence must end at byte 8");
+static_assert(sizeof(std::uint16_t) + sizeof(std::uint16_t) + sizeof(std::uint32_t) == FrameHeader::size, "FrameHeader must be 8 bytes");
+
+/// message Quote [1]
+class Quote {
+public:
+    static constexpr std::size_t size = 67;
+    static constexpr std::uint32_t id = 1;
+    /// The size of the header followed by the message
+    static constexpr std::size_t frame_size = 75;
+
+    struct offsets {
+        static constexpr std::size_t sent_at = 0;
+        static constexpr std::size_t symbol = 8;
+        static constexpr std::size_t side = 16;
+        static constexpr std::size_t bid = 17;
+        static constexpr std::size_t ask = 28;
+        static constexpr std::size_t levels = 39;
+        static constexpr std::size_t weight = 51;
+        static constexpr std::size_t ttl = 59;
+    };
+
+    explicit Quote(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Writes the header to the start of `data` and returns the message after it, `data` must
+    /// hold `frame_size` bytes
+    static Quote write_frame(std::byte* data) noexcept {
+        FrameHeader header(data);
+        header.set_message_id(static_cast<std::uint16_t>(id));
+        header.set_length(static_cast<std::uint16_t>(frame_size));
+        return Quote(data + FrameHeader::size);
+    }
+
+    /// when the quote was sent
+    sss::sys_time<std::chrono::nanoseconds> sent_at() const noexcept {
+        return sss::sys_time<std::chrono::nanoseconds>(std::chrono::nanoseconds(static_cast<std::chrono::nanoseconds::rep>(sss::detail::load<std::uint64_t, big_endian>(data_ + 0))));
+    }
+    void set_sent_at(sss::sys_time<std::chrono::nanoseconds> value) noexcept {
+        sss::detail::store<std::uint64_t, big_endian>(data_ + 0, static_cast<std::uint64_t>(value.time_since_epoch().count()));
+    }
+
+    /// ascii[8], offset 8, 8 bytes
+    sss::ascii_view<8> symbol() const noexcept {
+        return sss::ascii_view<8>(data_ + 8);
+    }
+    /// Fails if the value is longer than the field or isn't ascii
+    bool set_symbol(std::string_view value) noexcept {
+        return sss::detail::store_ascii(data_ + 8, 8, '\0', value);
+    }
+
+    /// Side, offset 16, 1 bytes
+    Side side() const noexcept {
+        return static_cast<Side>(sss::detail::load<std::uint8_t, big_endian>(data_ + 16));
+    }
+    void set_side(Side value) noexcept {
+        sss::detail::store<std::uint8_t, big_endian>(data_ + 16, static_cast<std::uint8_t>(value));
+    }
+
+    /// Price, offset 17, 11 bytes
+    Price bid() const noexcept {
+        return Price(data_ + 17);
+    }
+
+    /// Price, offset 28, 11 bytes
+    Price ask() const noexcept {
+        return Price(data_ + 28);
+    }
+
+    /// i32[3], offset 39, 12 bytes
+    std::array<std::int32_t, 3> levels() const noexcept {
+        std::array<std::int32_t, 3> value;
+        for (std::size_t i = 0; i < 3; ++i) {
+            value[i] = sss::detail::load<std::int32_t, big_endian>(data_ + 39 + i * 4);
+        }
+        return value;
+    }
+    void set_levels(const std::array<std::int32_t, 3>& value) noexcept {
+        for (std::size_t i = 0; i < 3; ++i) {
+            sss::detail::store<std::int32_t, big_endian>(data_ + 39 + i * 4, value[i]);
+        }
+    }
+
+    /// f64, offset 51, 8 bytes
+    double weight() const noexcept {
+        return sss::detail::load<double, big_endian>(data_ + 51);
+    }
+    void set_weight(double value) noexcept {
+        sss::detail::store<double, big_endian>(data_ + 51, value);
+    }
+
+    /// how long the quote can be traded on, counted from sent_at
+    std::chrono::microseconds ttl() const noexcept {
+        return std::chrono::microseconds(static_cast<std::chrono::microseconds::rep>(sss::detail::load<std::int64_t, big_endian>(data_ + 59)));
+    }
+    void set_ttl(std::chrono::microseconds value) noexcept {
+        sss::detail::store<std::int64_t, big_endian>(data_ + 59, static_cast<std::int64_t>(value.count()));
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Quote::offsets::sent_at + sizeof(std::uint64_t) == 8, "Quote.sent_at must end at byte 8");
+static_assert(Quote::offsets::symbol + sizeof(char[8]) == 16, "Quote.symbol must end at byte 16");
+static_assert(Quote::offsets::side + sizeof(Side) == 17, "Quote.side must end at byte 17");
+static_assert(Quote::offsets::bid + Price::size == 28, "Quote.bid must end at byte 28");
+static_assert(Quote::offsets::ask + Price::size == 39, "Quote.ask must end at byte 39");
+static_assert(Quote::offsets::levels + sizeof(std::int32_t[3]) == 51, "Quote.levels must end at byte 51");
+static_assert(Quote::offsets::weight + sizeof(double) == 59, "Quote.weight must end at byte 59");
+static_assert(Quote::offsets::ttl + sizeof(std::int64_t) == 67, "Quote.ttl must end at byte 67");
+static_assert(sizeof(std::uint64_t) + sizeof(char[8]) + sizeof(Side) + Price::size + Price::size + sizeof(std::int32_t[3]) + sizeof(double) + sizeof(std::int64_t) == Quote::size, "Quote must be 67 bytes");
+
+/// message Request [2]
+class Request {
+public:
+    static constexpr std::size_t size = 48;
+    static constexpr std::uint32_t id = 2;
+    /// The size of the header followed by the message
+    static constexpr std::size_t frame_size = 56;
+
+    struct offsets {
+        static constexpr std::size_t sequence = 0;
+        static constexpr std::size_t permissions = 4;
+        static constexpr std::size_t kind = 6;
+        static constexpr std::size_t body = 7;
+        static constexpr std::size_t checksum = 23;
+        static constexpr std::size_t type = 29;
+        static constexpr std::size_t venue = 31;
+    };
+
+    explicit Request(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Writes the header to the start of `data` and returns the message after it, `data` must
+    /// hold `frame_size` bytes
+    static Request write_frame(std::byte* data) noexcept {
+        FrameHeader header(data);
+        header.set_message_id(static_cast<std::uint16_t>(id));
+        header.set_length(static_cast<std::uint16_t>(frame_size));
+        return Request(data + FrameHeader::size);
+    }
+
+    /// u32, offset 0, 4 bytes
+    std::uint32_t sequence() const noexcept {
+        return sss::detail::load<std::uint32_t, big_endian>(data_ + 0);
+    }
+    void set_sequence(std::uint32_t value) noexcept {
+        sss::detail::store<std::uint32_t, big_endian>(data_ + 0, value);
+    }
+
+    /// Permissions, offset 4, 2 bytes
+    Permissions permissions() const noexcept {
+        return static_cast<Permissions>(sss::detail::load<std::uint16_t, big_endian>(data_ + 4));
+    }
+    void set_permissions(Permissions value) noexcept {
+        sss::detail::store<std::uint16_t, big_endian>(data_ + 4, static_cast<std::uint16_t>(value));
+    }
+
+    /// BodyKind, offset 6, 1 bytes
+    BodyKind kind() const noexcept {
+        return static_cast<BodyKind>(sss::detail::load<std::uint8_t, big_endian>(data_ + 6));
+    }
+    void set_kind(BodyKind value) noexcept {
+        sss::detail::store<std::uint8_t, big_endian>(data_ + 6, static_cast<std::uint8_t>(value));
+    }
+
+    /// the order or cancel the request is for
+    Body body() const noexcept {
+        return Body(data_ + 7);
+    }
+
+    /// `body().order()`, or nothing unless `kind()` is `BodyKind::ORDER`
+    std::optional<Order> body_order() const noexcept {
+        if (kind() != BodyKind::ORDER) {
+            return std::nullopt;
+        }
+        return body().order();
+    }
+
+    /// `body().cancel_id()`, or nothing unless `kind()` is `BodyKind::CANCEL`
+    std::optional<std::uint64_t> body_cancel_id() const noexcept {
+        if (kind() != BodyKind::CANCEL) {
+            return std::nullopt;
+        }
+        return body().cancel_id();
+    }
+
+    /// `body().ping()`, or nothing unless `kind()` is `BodyKind::PING`
+    std::optional<sss::ascii_view<4>> body_ping() const noexcept {
+        if (kind() != BodyKind::PING) {
+            return std::nullopt;
+        }
+        return body().ping();
+    }
+
+    /// byte[4], offset 23, 4 bytes
+    std::array<std::uint8_t, 4> checksum() const noexcept {
+        std::array<std::uint8_t, 4> value;
+        std::memcpy(value.data(), data_ + 23, 4);
+        return value;
+    }
+    void set_checksum(const std::array<std::uint8_t, 4>& value) noexcept {
+        std::memcpy(data_ + 23, value.data(), 4);
+    }
+
+    /// i16, offset 29, 2 bytes
+    std::int16_t type() const noexcept {
+        return sss::detail::load<std::int16_t, big_endian>(data_ + 29);
+    }
+    void set_type(std::int16_t value) noexcept {
+        sss::detail::store<std::int16_t, big_endian>(data_ + 29, value);
+    }
+
+    /// ascii[6], offset 31, 6 bytes
+    sss::ascii_view<6, ' '> venue() const noexcept {
+        return sss::ascii_view<6, ' '>(data_ + 31);
+    }
+    /// Fails if the value is longer than the field or isn't ascii
+    bool set_venue(std::string_view value) noexcept {
+        return sss::detail::store_ascii(data_ + 31, 6, ' ', value);
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Request::offsets::sequence + sizeof(std::uint32_t) == 4, "Request.sequence must end at byte 4");
+static_assert(Request::offsets::permissions + sizeof(Permissions) == 6, "Request.permissions must end at byte 6");
+static_assert(Request::offsets::kind + sizeof(BodyKind) == 7, "Request.kind must end at byte 7");
+static_assert(Request::offsets::body + Body::size == 23, "Request.body must end at byte 23");
+static_assert(Request::offsets::checksum + sizeof(std::uint8_t[4]) == 27, "Request.checksum must end at byte 27");
+static_assert(Request::offsets::type + sizeof(std::int16_t) == 31, "Request.type must end at byte 31");
+static_assert(Request::offsets::venue + sizeof(char[6]) == 37, "Request.venue must end at byte 37");
+static_assert(sizeof(std::uint32_t) + sizeof(Permissions) + sizeof(BodyKind) + Body::size + sizeof(std::uint8_t[4]) + 2 + sizeof(std::int16_t) + sizeof(char[6]) + 11 == Request::size, "Request must be 48 bytes");
+
+/// message Heartbeat [3]
+class Heartbeat {
+public:
+    static constexpr std::size_t size = 16;
+    static constexpr std::uint32_t id = 3;
+    /// The size of the header followed by the message
+    static constexpr std::size_t frame_size = 24;
+
+    struct offsets {
+        static constexpr std::size_t sent_at = 0;
+        static constexpr std::size_t sequence = 8;
+        static constexpr std::size_t side = 12;
+    };
+
+    explicit Heartbeat(std::byte* data) noexcept : data_(data) {}
+
+    std::byte* data() const noexcept { return data_; }
+
+    /// Writes the header to the start of `data` and returns the message after it, `data` must
+    /// hold `frame_size` bytes
+    static Heartbeat write_frame(std::byte* data) noexcept {
+        FrameHeader header(data);
+        header.set_message_id(static_cast<std::uint16_t>(id));
+        header.set_length(static_cast<std::uint16_t>(frame_size));
+        return Heartbeat(data + FrameHeader::size);
+    }
+
+    /// u64, offset 0, 8 bytes
+    std::uint64_t sent_at() const noexcept {
+        return sss::detail::load<std::uint64_t, big_endian>(data_ + 0);
+    }
+    void set_sent_at(std::uint64_t value) noexcept {
+        sss::detail::store<std::uint64_t, big_endian>(data_ + 0, value);
+    }
+
+    /// u32, offset 8, 4 bytes
+    std::uint32_t sequence() const noexcept {
+        return sss::detail::load<std::uint32_t, big_endian>(data_ + 8);
+    }
+    void set_sequence(std::uint32_t value) noexcept {
+        sss::detail::store<std::uint32_t, big_endian>(data_ + 8, value);
+    }
+
+    /// Side, offset 12, 1 bytes
+    Side side() const noexcept {
+        return static_cast<Side>(sss::detail::load<std::uint8_t, big_endian>(data_ + 12));
+    }
+    void set_side(Side value) noexcept {
+        sss::detail::store<std::uint8_t, big_endian>(data_ + 12, static_cast<std::uint8_t>(value));
+    }
+
+private:
+    std::byte* data_;
+};
+static_assert(Heartbeat::offsets::sent_at + sizeof(std::uint64_t) == 8, "Heartbeat.sent_at must end at byte 8");
+static_assert(Heartbeat::offsets::sequence + sizeof(std::uint32_t) == 12, "Heartbeat.sequence must end at byte 12");
+static_assert(Heartbeat::offsets::side + sizeof(Side) == 13, "Heartbeat.side must end at byte 13");
+static_assert(sizeof(std::uint64_t) + sizeof(std::uint32_t) + sizeof(Side) + 3 == Heartbeat::size, "Heartbeat must be 16 bytes");
+
+/// Reads the header at the start of `data` and calls `handler` with the message after it, as
+/// one of `Quote`, `Request`, `Heartbeat`. A message the handler can't be called with is skipped.
+/// Returns the size of the frame, or 0 when the `size` bytes of `data` don't hold the whole
+/// frame, its length is too small for its message or no message has its id.
+template <typename Handler>
+std::size_t dispatch(std::byte* data, std::size_t size, Handler&& handler) {
+    if (size < FrameHeader::size) {
+        return 0;
+    }
+    FrameHeader header(data);
+    std::size_t frame = header.length();
+    switch (header.message_id()) {
+    case Quote::id:
+        if (frame < Quote::frame_size || size < frame) {
+            return 0;
+        }
+        if constexpr (std::is_invocable_v<Handler&, Quote>) {
+            handler(Quote(data + FrameHeader::size));
+        }
+        return frame;
+    case Request::id:
+        if (frame < Request::frame_size || size < frame) {
+            return 0;
+        }
+        if constexpr (std::is_invocable_v<Handler&, Request>) {
+            handler(Request(data + FrameHeader::size));
+        }
+        return frame;
+    case Heartbeat::id:
+        if (frame < Heartbeat::frame_size || size < frame) {
+            return 0;
+        }
+        if constexpr (std::is_invocable_v<Handler&, Heartbeat>) {
+            handler(Heartbeat(data + FrameHeader::size));
+        }
+        return frame;
+    default:
+        return 0;
+    }
+}
+
+} // namespace test::codegen
@@ -0,0 +1,144 @@
+// Exercises the generated views and writers, built and run by tests/codegen.rs when dotnet is found.
+using System;
+
+namespace Test.Codegen
+{
+    public static class RoundTrip
+    {
+        private static void Check(bool condition, string what)
+        {
+            if (!condition)
+            {
+                throw new Exception(what);
+            }
+        }
+
+        private static bool Throws<T>(Action action) where T : Exception
+        {
+            try
+            {
+                action();
+            }
+            catch (T)
+            {
+                return true;
+            }
+            return false;
+        }
+
+        private static void Quote()
+        {
+            // a misaligned offset must not matter
+            var buffer = new byte[QuoteView.Size + 1];
+            var quote = new QuoteWriter(buffer.AsSpan(1));
+            quote.SentAt = 1700000000123456789UL;
+            quote.Symbol = "AAPL";
+            quote.Side = Side.Sell;
+            quote.SetLevels(0, -1);
+            quote.SetLevels(2, 1);
+            quote.Weight = 0.5;
+            quote.Ttl = -250;
+            var bid = quote.Bid;
+            bid.Value = 1234500;
+            bid.Currency = "USD";
+
+            var view = new QuoteView(buffer.AsSpan(1));
+            Check(view.SentAt == 1700000000123456789UL, "sent_at");
+            Check(view.Symbol == "AAPL", "symbol");
+            Check(view.Side == Side.Sell, "side");
+            Check(view.Bid.Value == 1234500 && PriceView.ValueExponent == -4, "bid");
+            Check(view.Bid.Currency == "USD", "currency");
+            Check(view.GetLevels(0) == -1 && view.GetLevels(1) == 0 && view.GetLevels(2) == 1, "levels");
+            Check(view.Weight == 0.5, "weight");
+            Check(view.Ttl == -250, "ttl");
+            Check(buffer[1] == 0x15, "little endian on the wire");
+            Check(view.SentAtTime == DateTimeOffset.FromUnixTimeSeconds(1700000000).AddTicks(1234567), "sent_at time");
+            Check(view.TtlTimeSpan == TimeSpan.FromMicroseconds(-250), "ttl time span");
+            quote.TtlTimeSpan = TimeSpan.FromMilliseconds(3);
+            Check(view.Ttl == 3000, "ttl from a time span");
+            quote.Ttl = -250;
+            Check(view.Bid.ValueDecimal == 123.45m, "bid as a decimal");
+            bid.ValueDecimal = 0.25m;
+            Check(view.Bid.Value == 2500, "bid from a decimal");
+            Check(Throws<ArgumentException>(() =>
+            {
+                var writer = new QuoteWriter(buffer.AsSpan(1)).Bid;
+                writer.ValueDecimal = 0.00001m;
+            }), "bid too precise");
+            bid.Value = 1234500;
+
+            Check(Throws<ArgumentException>(() =>
+            {
+                var writer = new QuoteWriter(buffer.AsSpan(1));
+                writer.Symbol = "TOOLONGXX";
+            }), "symbol too long");
+            Check(Throws<ArgumentOutOfRangeException>(() => new QuoteView(buffer.AsSpan(1)).GetLevels(3)), "levels index");
+            Check(Throws<ArgumentException>(() => new QuoteView(buffer.AsSpan(2))), "short buffer");
+        }
+
+        private static void Request()
+        {
+            var buffer = new byte[RequestView.Size];
+            var request = new RequestWriter(buffer);
+            request.Sequence = 0xFFFFFFFF;
+            request.Permissions = Permissions.Read | Permissions.Admin;
+            request.Kind = BodyKind.Cancel;
+            var body = request.Body;
+            body.CancelId = 99;
+            request.Checksum[3] = 4;
+            request.Type = -2;
+            request.Venue = "XLON";
+
+            var view = request.AsView();
+            Check(view.Sequence == 0xFFFFFFFF, "unsigned sequence");
+            Check(view.Permissions.HasFlag(Permissions.Admin), "permissions");
+            Check(view.Kind == BodyKind.Cancel, "kind");
+            Check(view.Body.CancelId == 99, "body");
+            Check(view.TryGetBodyCancelId(out var cancelId) && cancelId == 99, "checked body");
+            Check(!view.TryGetBodyOrder(out _), "inactive variant");
+            Check(view.Checksum[3] == 4, "checksum");
+            Check(view.Type == -2, "type");
+            Check(view.Venue == "XLON", "venue");
+            Check(buffer[RequestView.VenueOffset + 5] == (byte)' ', "space padding");
+        }
+
+        private sealed class Seen : IHandler
+        {
+            public int Heartbeats;
+
+            public void OnHeartbeat(HeartbeatView message)
+            {
+                Heartbeats++;
+            }
+        }
+
+        private static void Dispatch()
+        {
+            var buffer = new byte[HeartbeatView.FrameSize + QuoteView.FrameSize + 1];
+            var heartbeat = HeartbeatWriter.WriteFrame(buffer);
+            heartbeat.Sequence = 7;
+            QuoteWriter.WriteFrame(buffer.AsSpan(HeartbeatView.FrameSize));
+            Check(HeartbeatView.FrameSize == FrameHeaderView.Size + HeartbeatView.Size, "frame size");
+            Check(buffer[0] == 24 && buffer[1] == 0 && buffer[2] == 3 && buffer[3] == 0, "header");
+
+            var seen = new Seen();
+            Check(Dispatcher.Dispatch(buffer, seen) == HeartbeatView.FrameSize, "heartbeat frame");
+            Check(Dispatcher.Dispatch(buffer.AsSpan(HeartbeatView.FrameSize), seen) == QuoteView.FrameSize, "quote frame");
+            Check(seen.Heartbeats == 1, "only the heartbeat is handled");
+
+            Check(Throws<ArgumentException>(() => Dispatcher.Dispatch(buffer.AsSpan(0, 10), seen)), "short frame");
+            buffer[2] = 9;
+            Check(Throws<ArgumentException>(() => Dispatcher.Dispatch(buffer, seen)), "unknown id");
+            buffer[2] = 3;
+            buffer[0] = 4;
+            Check(Throws<ArgumentException>(() => Dispatcher.Dispatch(buffer, seen)), "length too small");
+        }
+
+        public static void Main()
+        {
+            Quote();
+            Request();
+            Dispatch();
+        }
+    }
+}
@@ -0,0 +1,938 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+using System;
+using System.Buffers.Binary;
+using System.Diagnostics.CodeAnalysis;
+using System.Text;
+
+namespace Test.Codegen
+{
+    /// <summary>Reads and writes fixed length ascii fields, the text ends at the first nul byte, without the pad bytes ending it</summary>
+    internal static class AsciiField
+    {
+        public static string Read(ReadOnlySpan<byte> field, byte pad)
+        {
+            int length = field.IndexOf((byte)0);
+            return Encoding.ASCII.GetString((length < 0 ? field : field.Slice(0, length)).TrimEnd(pad));
+        }
+
+        public static void Write(Span<byte> field, byte pad, string value, string name)
+        {
+            if (value.Length > field.Length)
+            {
+                throw new ArgumentException($"{name} holds at most {field.Length} characters but the value has {value.Length}", nameof(value));
+            }
+            foreach (char c in value)
+            {
+                if (c > 0x7f)
+                {
+                    throw new ArgumentException($"{name} only holds ascii characters", nameof(value));
+                }
+            }
+            field.Fill(pad);
+            for (int i = 0; i < value.Length; i++)
+            {
+                field[i] = (byte)value[i];
+            }
+        }
+    }
+
+    /// <summary>enum Side, stored as a u8</summary>
+    public enum Side : byte
+    {
+        Buy = 1,
+        Sell = 2,
+    }
+
+    /// <summary>bitflags Permissions, stored as a u16</summary>
+    [Flags]
+    public enum Permissions : ushort
+    {
+        Read = 1,
+        Write = 2,
+        Admin = 128,
+    }
+
+    /// <summary>enum BodyKind, stored as a u8</summary>
+    public enum BodyKind : byte
+    {
+        Order = 1,
+        Cancel = 2,
+        Ping = 3,
+    }
+
+    /// <summary>Reads struct Price in place</summary>
+    public readonly ref struct PriceView
+    {
+        public const int Size = 11;
+        public const int ValueOffset = 0;
+        public const int ValueExponent = -4;
+        public const int CurrencyOffset = 8;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public PriceView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Price needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out PriceView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new PriceView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>decimal&lt;i64, -4&gt;: the amount in units of the currency</summary>
+        public long Value => BinaryPrimitives.ReadInt64LittleEndian(_buffer.Slice(0));
+
+        /// <summary>value as a decimal</summary>
+        public decimal ValueDecimal => Value * 0.0001m;
+
+        /// <summary>ascii[3] at offset 8</summary>
+        public string Currency => AsciiField.Read(_buffer.Slice(8, 3), 0);
+        public ReadOnlySpan<byte> CurrencyBytes => _buffer.Slice(8, 3);
+    }
+
+    /// <summary>Writes struct Price in place</summary>
+    public readonly ref struct PriceWriter
+    {
+        public const int Size = PriceView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public PriceWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Price needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public PriceView AsView() => new PriceView(_buffer);
+
+        /// <summary>decimal&lt;i64, -4&gt;: the amount in units of the currency</summary>
+        public long Value
+        {
+            get => AsView().Value;
+            set => BinaryPrimitives.WriteInt64LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>value as a decimal</summary>
+        /// <exception cref="ArgumentException">The value isn't a multiple of the field's scale</exception>
+        /// <exception cref="OverflowException">The mantissa doesn't fit the field</exception>
+        public decimal ValueDecimal
+        {
+            get => AsView().ValueDecimal;
+            set
+            {
+                var mantissa = value / 0.0001m;
+                if (mantissa != decimal.Truncate(mantissa))
+                {
+                    throw new ArgumentException($"value only holds multiples of 0.0001, not {value}", nameof(value));
+                }
+                Value = checked((long)mantissa);
+            }
+        }
+
+        /// <summary>ascii[3] at offset 8</summary>
+        /// <exception cref="ArgumentException">The value is too long or isn't ascii</exception>
+        public string Currency
+        {
+            get => AsView().Currency;
+            set => AsciiField.Write(_buffer.Slice(8, 3), 0, value, "currency");
+        }
+    }
+
+    /// <summary>Reads struct Order in place</summary>
+    public readonly ref struct OrderView
+    {
+        public const int Size = 16;
+        public const int SideOffset = 0;
+        public const int QuantityOffset = 1;
+        public const int PriceOffset = 5;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public OrderView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Order needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out OrderView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new OrderView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>Side at offset 0</summary>
+        public Side Side => (Side)_buffer[0];
+
+        /// <summary>u32 at offset 1</summary>
+        public uint Quantity => BinaryPrimitives.ReadUInt32LittleEndian(_buffer.Slice(1));
+
+        /// <summary>Price at offset 5</summary>
+        public PriceView Price => new PriceView(_buffer.Slice(5, 11));
+    }
+
+    /// <summary>Writes struct Order in place</summary>
+    public readonly ref struct OrderWriter
+    {
+        public const int Size = OrderView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public OrderWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Order needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public OrderView AsView() => new OrderView(_buffer);
+
+        /// <summary>Side at offset 0</summary>
+        public Side Side
+        {
+            get => AsView().Side;
+            set => _buffer[0] = (byte)value;
+        }
+
+        /// <summary>u32 at offset 1</summary>
+        public uint Quantity
+        {
+            get => AsView().Quantity;
+            set => BinaryPrimitives.WriteUInt32LittleEndian(_buffer.Slice(1), value);
+        }
+
+        /// <summary>Price at offset 5</summary>
+        public PriceWriter Price => new PriceWriter(_buffer.Slice(5, 11));
+    }
+
+    /// <summary>Reads union Body in place, the active variant is selected by a BodyKind</summary>
+    public readonly ref struct BodyView
+    {
+        public const int Size = 16;
+        public const int OrderOffset = 0;
+        public const int CancelIdOffset = 0;
+        public const int PingOffset = 0;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public BodyView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Body needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out BodyView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new BodyView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>Order: Active when the discriminator is ORDER</summary>
+        public OrderView Order => new OrderView(_buffer.Slice(0, 16));
+
+        /// <summary>u64: Active when the discriminator is CANCEL</summary>
+        public ulong CancelId => BinaryPrimitives.ReadUInt64LittleEndian(_buffer.Slice(0));
+
+        /// <summary>ascii[4]: Active when the discriminator is PING</summary>
+        public string Ping => AsciiField.Read(_buffer.Slice(0, 4), 0);
+        public ReadOnlySpan<byte> PingBytes => _buffer.Slice(0, 4);
+    }
+
+    /// <summary>Writes union Body in place, the active variant is selected by a BodyKind</summary>
+    public readonly ref struct BodyWriter
+    {
+        public const int Size = BodyView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public BodyWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Body needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public BodyView AsView() => new BodyView(_buffer);
+
+        /// <summary>Order: Active when the discriminator is ORDER</summary>
+        public OrderWriter Order => new OrderWriter(_buffer.Slice(0, 16));
+
+        /// <summary>u64: Active when the discriminator is CANCEL</summary>
+        public ulong CancelId
+        {
+            get => AsView().CancelId;
+            set => BinaryPrimitives.WriteUInt64LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>ascii[4]: Active when the discriminator is PING</summary>
+        /// <exception cref="ArgumentException">The value is too long or isn't ascii</exception>
+        public string Ping
+        {
+            get => AsView().Ping;
+            set => AsciiField.Write(_buffer.Slice(0, 4), 0, value, "ping");
+        }
+    }
+
+    /// <summary>Reads struct FrameHeader in place</summary>
+    public readonly ref struct FrameHeaderView
+    {
+        public const int Size = 8;
+        public const int LengthOffset = 0;
+        public const int MessageIdOffset = 2;
+        public const int SequenceOffset = 4;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public FrameHeaderView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"FrameHeader needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out FrameHeaderView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new FrameHeaderView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>u16 at offset 0</summary>
+        public ushort Length => BinaryPrimitives.ReadUInt16LittleEndian(_buffer.Slice(0));
+
+        /// <summary>u16 at offset 2</summary>
+        public ushort MessageId => BinaryPrimitives.ReadUInt16LittleEndian(_buffer.Slice(2));
+
+        /// <summary>u32 at offset 4</summary>
+        public uint Sequence => BinaryPrimitives.ReadUInt32LittleEndian(_buffer.Slice(4));
+    }
+
+    /// <summary>Writes struct FrameHeader in place</summary>
+    public readonly ref struct FrameHeaderWriter
+    {
+        public const int Size = FrameHeaderView.Size;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public FrameHeaderWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"FrameHeader needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public FrameHeaderView AsView() => new FrameHeaderView(_buffer);
+
+        /// <summary>u16 at offset 0</summary>
+        public ushort Length
+        {
+            get => AsView().Length;
+            set => BinaryPrimitives.WriteUInt16LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>u16 at offset 2</summary>
+        public ushort MessageId
+        {
+            get => AsView().MessageId;
+            set => BinaryPrimitives.WriteUInt16LittleEndian(_buffer.Slice(2), value);
+        }
+
+        /// <summary>u32 at offset 4</summary>
+        public uint Sequence
+        {
+            get => AsView().Sequence;
+            set => BinaryPrimitives.WriteUInt32LittleEndian(_buffer.Slice(4), value);
+        }
+    }
+
+    /// <summary>Reads message Quote [1] in place</summary>
+    public readonly ref struct QuoteView
+    {
+        public const int Size = 67;
+        public const uint Id = 1;
+        public const int FrameSize = 75;
+        public const int SentAtOffset = 0;
+        public const int SymbolOffset = 8;
+        public const int SideOffset = 16;
+        public const int BidOffset = 17;
+        public const int AskOffset = 28;
+        public const int LevelsOffset = 39;
+        public const int LevelsLength = 3;
+        public const int WeightOffset = 51;
+        public const int TtlOffset = 59;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public QuoteView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Quote needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out QuoteView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new QuoteView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>timestamp&lt;ns&gt;: when the quote was sent</summary>
+        public ulong SentAt => BinaryPrimitives.ReadUInt64LittleEndian(_buffer.Slice(0));
+
+        /// <summary>sent_at as a DateTimeOffset</summary>
+        public DateTimeOffset SentAtTime => DateTimeOffset.UnixEpoch.AddTicks((long)SentAt / TimeSpan.NanosecondsPerTick);
+
+        /// <summary>ascii[8] at offset 8</summary>
+        public string Symbol => AsciiField.Read(_buffer.Slice(8, 8), 0);
+        public ReadOnlySpan<byte> SymbolBytes => _buffer.Slice(8, 8);
+
+        /// <summary>Side at offset 16</summary>
+        public Side Side => (Side)_buffer[16];
+
+        /// <summary>Price at offset 17</summary>
+        public PriceView Bid => new PriceView(_buffer.Slice(17, 11));
+
+        /// <summary>Price at offset 28</summary>
+        public PriceView Ask => new PriceView(_buffer.Slice(28, 11));
+
+        /// <summary>i32[3] at offset 39</summary>
+        public int GetLevels(int index)
+        {
+            if ((uint)index >= LevelsLength)
+            {
+                throw new ArgumentOutOfRangeException(nameof(index));
+            }
+            return BinaryPrimitives.ReadInt32LittleEndian(_buffer.Slice(39 + index * 4));
+        }
+
+        /// <summary>f64 at offset 51</summary>
+        public double Weight => BinaryPrimitives.ReadDoubleLittleEndian(_buffer.Slice(51));
+
+        /// <summary>duration&lt;us&gt;: how long the quote can be traded on, counted from sent_at</summary>
+        public long Ttl => BinaryPrimitives.ReadInt64LittleEndian(_buffer.Slice(59));
+
+        /// <summary>ttl as a TimeSpan</summary>
+        public TimeSpan TtlTimeSpan => TimeSpan.FromTicks(Ttl * TimeSpan.TicksPerMicrosecond);
+    }
+
+    /// <summary>Writes message Quote [1] in place</summary>
+    public readonly ref struct QuoteWriter
+    {
+        public const int Size = QuoteView.Size;
+        public const uint Id = QuoteView.Id;
+        public const int FrameSize = QuoteView.FrameSize;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public QuoteWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Quote needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public QuoteView AsView() => new QuoteView(_buffer);
+
+        /// <summary>Writes the header's id and length to the start of buffer and returns the message after it</summary>
+        /// <exception cref="ArgumentException">The buffer is shorter than FrameSize</exception>
+        public static QuoteWriter WriteFrame(Span<byte> buffer)
+        {
+            if (buffer.Length < FrameSize)
+            {
+                throw new ArgumentException($"the frame of Quote needs {FrameSize} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            var header = new FrameHeaderWriter(buffer);
+            header.MessageId = (ushort)Id;
+            header.Length = (ushort)FrameSize;
+            return new QuoteWriter(buffer.Slice(FrameHeaderView.Size));
+        }
+
+        /// <summary>timestamp&lt;ns&gt;: when the quote was sent</summary>
+        public ulong SentAt
+        {
+            get => AsView().SentAt;
+            set => BinaryPrimitives.WriteUInt64LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>sent_at as a DateTimeOffset</summary>
+        public DateTimeOffset SentAtTime
+        {
+            get => AsView().SentAtTime;
+            set => SentAt = (ulong)((value - DateTimeOffset.UnixEpoch).Ticks * TimeSpan.NanosecondsPerTick);
+        }
+
+        /// <summary>ascii[8] at offset 8</summary>
+        /// <exception cref="ArgumentException">The value is too long or isn't ascii</exception>
+        public string Symbol
+        {
+            get => AsView().Symbol;
+            set => AsciiField.Write(_buffer.Slice(8, 8), 0, value, "symbol");
+        }
+
+        /// <summary>Side at offset 16</summary>
+        public Side Side
+        {
+            get => AsView().Side;
+            set => _buffer[16] = (byte)value;
+        }
+
+        /// <summary>Price at offset 17</summary>
+        public PriceWriter Bid => new PriceWriter(_buffer.Slice(17, 11));
+
+        /// <summary>Price at offset 28</summary>
+        public PriceWriter Ask => new PriceWriter(_buffer.Slice(28, 11));
+
+        /// <summary>i32[3] at offset 39</summary>
+        public int GetLevels(int index) => AsView().GetLevels(index);
+
+        public void SetLevels(int index, int value)
+        {
+            if ((uint)index >= QuoteView.LevelsLength)
+            {
+                throw new ArgumentOutOfRangeException(nameof(index));
+            }
+            BinaryPrimitives.WriteInt32LittleEndian(_buffer.Slice(39 + index * 4), value);
+        }
+
+        /// <summary>f64 at offset 51</summary>
+        public double Weight
+        {
+            get => AsView().Weight;
+            set => BinaryPrimitives.WriteDoubleLittleEndian(_buffer.Slice(51), value);
+        }
+
+        /// <summary>duration&lt;us&gt;: how long the quote can be traded on, counted from sent_at</summary>
+        public long Ttl
+        {
+            get => AsView().Ttl;
+            set => BinaryPrimitives.WriteInt64LittleEndian(_buffer.Slice(59), value);
+        }
+
+        /// <summary>ttl as a TimeSpan</summary>
+        public TimeSpan TtlTimeSpan
+        {
+            get => AsView().TtlTimeSpan;
+            set => Ttl = value.Ticks / TimeSpan.TicksPerMicrosecond;
+        }
+    }
+
+    /// <summary>Reads message Request [2] in place</summary>
+    public readonly ref struct RequestView
+    {
+        public const int Size = 48;
+        public const uint Id = 2;
+        public const int FrameSize = 56;
+        public const int SequenceOffset = 0;
+        public const int PermissionsOffset = 4;
+        public const int KindOffset = 6;
+        public const int BodyOffset = 7;
+        public const int ChecksumOffset = 23;
+        public const int TypeOffset = 29;
+        public const int VenueOffset = 31;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public RequestView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Request needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out RequestView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new RequestView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>u32 at offset 0</summary>
+        public uint Sequence => BinaryPrimitives.ReadUInt32LittleEndian(_buffer.Slice(0));
+
+        /// <summary>Permissions at offset 4</summary>
+        public Permissions Permissions => (Permissions)BinaryPrimitives.ReadUInt16LittleEndian(_buffer.Slice(4));
+
+        /// <summary>BodyKind at offset 6</summary>
+        public BodyKind Kind => (BodyKind)_buffer[6];
+
+        /// <summary>Body: the order or cancel the request is for</summary>
+        public BodyView Body => new BodyView(_buffer.Slice(7, 16));
+
+        /// <summary>Body.Order, false unless Kind is ORDER</summary>
+        public bool TryGetBodyOrder(out OrderView value)
+        {
+            if (Kind != BodyKind.Order)
+            {
+                value = default;
+                return false;
+            }
+            value = Body.Order;
+            return true;
+        }
+
+        /// <summary>Body.CancelId, false unless Kind is CANCEL</summary>
+        public bool TryGetBodyCancelId(out ulong value)
+        {
+            if (Kind != BodyKind.Cancel)
+            {
+                value = default;
+                return false;
+            }
+            value = Body.CancelId;
+            return true;
+        }
+
+        /// <summary>Body.Ping, false unless Kind is PING</summary>
+        public bool TryGetBodyPing([NotNullWhen(true)] out string? value)
+        {
+            if (Kind != BodyKind.Ping)
+            {
+                value = default;
+                return false;
+            }
+            value = Body.Ping;
+            return true;
+        }
+
+        /// <summary>byte[4] at offset 23</summary>
+        public ReadOnlySpan<byte> Checksum => _buffer.Slice(23, 4);
+
+        /// <summary>i16 at offset 29</summary>
+        public short Type => BinaryPrimitives.ReadInt16LittleEndian(_buffer.Slice(29));
+
+        /// <summary>ascii[6] at offset 31</summary>
+        public string Venue => AsciiField.Read(_buffer.Slice(31, 6), (byte)' ');
+        public ReadOnlySpan<byte> VenueBytes => _buffer.Slice(31, 6);
+    }
+
+    /// <summary>Writes message Request [2] in place</summary>
+    public readonly ref struct RequestWriter
+    {
+        public const int Size = RequestView.Size;
+        public const uint Id = RequestView.Id;
+        public const int FrameSize = RequestView.FrameSize;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public RequestWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Request needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public RequestView AsView() => new RequestView(_buffer);
+
+        /// <summary>Writes the header's id and length to the start of buffer and returns the message after it</summary>
+        /// <exception cref="ArgumentException">The buffer is shorter than FrameSize</exception>
+        public static RequestWriter WriteFrame(Span<byte> buffer)
+        {
+            if (buffer.Length < FrameSize)
+            {
+                throw new ArgumentException($"the frame of Request needs {FrameSize} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            var header = new FrameHeaderWriter(buffer);
+            header.MessageId = (ushort)Id;
+            header.Length = (ushort)FrameSize;
+            return new RequestWriter(buffer.Slice(FrameHeaderView.Size));
+        }
+
+        /// <summary>u32 at offset 0</summary>
+        public uint Sequence
+        {
+            get => AsView().Sequence;
+            set => BinaryPrimitives.WriteUInt32LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>Permissions at offset 4</summary>
+        public Permissions Permissions
+        {
+            get => AsView().Permissions;
+            set => BinaryPrimitives.WriteUInt16LittleEndian(_buffer.Slice(4), (ushort)value);
+        }
+
+        /// <summary>BodyKind at offset 6</summary>
+        public BodyKind Kind
+        {
+            get => AsView().Kind;
+            set => _buffer[6] = (byte)value;
+        }
+
+        /// <summary>Body: the order or cancel the request is for</summary>
+        public BodyWriter Body => new BodyWriter(_buffer.Slice(7, 16));
+
+        /// <summary>byte[4] at offset 23</summary>
+        public Span<byte> Checksum => _buffer.Slice(23, 4);
+
+        /// <summary>i16 at offset 29</summary>
+        public short Type
+        {
+            get => AsView().Type;
+            set => BinaryPrimitives.WriteInt16LittleEndian(_buffer.Slice(29), value);
+        }
+
+        /// <summary>ascii[6] at offset 31</summary>
+        /// <exception cref="ArgumentException">The value is too long or isn't ascii</exception>
+        public string Venue
+        {
+            get => AsView().Venue;
+            set => AsciiField.Write(_buffer.Slice(31, 6), (byte)' ', value, "venue");
+        }
+    }
+
+    /// <summary>Reads message Heartbeat [3] in place</summary>
+    public readonly ref struct HeartbeatView
+    {
+        public const int Size = 16;
+        public const uint Id = 3;
+        public const int FrameSize = 24;
+        public const int SentAtOffset = 0;
+        public const int SequenceOffset = 8;
+        public const int SideOffset = 12;
+
+        private readonly ReadOnlySpan<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public HeartbeatView(ReadOnlySpan<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Heartbeat needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        /// <summary>Views the start of buffer, false if it is shorter than Size</summary>
+        public static bool TryRead(ReadOnlySpan<byte> buffer, out HeartbeatView view)
+        {
+            if (buffer.Length < Size)
+            {
+                view = default;
+                return false;
+            }
+            view = new HeartbeatView(buffer);
+            return true;
+        }
+
+        public ReadOnlySpan<byte> Buffer => _buffer;
+
+        /// <summary>u64 at offset 0</summary>
+        public ulong SentAt => BinaryPrimitives.ReadUInt64LittleEndian(_buffer.Slice(0));
+
+        /// <summary>u32 at offset 8</summary>
+        public uint Sequence => BinaryPrimitives.ReadUInt32LittleEndian(_buffer.Slice(8));
+
+        /// <summary>Side at offset 12</summary>
+        public Side Side => (Side)_buffer[12];
+    }
+
+    /// <summary>Writes message Heartbeat [3] in place</summary>
+    public readonly ref struct HeartbeatWriter
+    {
+        public const int Size = HeartbeatView.Size;
+        public const uint Id = HeartbeatView.Id;
+        public const int FrameSize = HeartbeatView.FrameSize;
+
+        private readonly Span<byte> _buffer;
+
+        /// <exception cref="ArgumentException">The buffer is shorter than Size</exception>
+        public HeartbeatWriter(Span<byte> buffer)
+        {
+            if (buffer.Length < Size)
+            {
+                throw new ArgumentException($"Heartbeat needs {Size} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            _buffer = buffer.Slice(0, Size);
+        }
+
+        public Span<byte> Buffer => _buffer;
+
+        public HeartbeatView AsView() => new HeartbeatView(_buffer);
+
+        /// <summary>Writes the header's id and length to the start of buffer and returns the message after it</summary>
+        /// <exception cref="ArgumentException">The buffer is shorter than FrameSize</exception>
+        public static HeartbeatWriter WriteFrame(Span<byte> buffer)
+        {
+            if (buffer.Length < FrameSize)
+            {
+                throw new ArgumentException($"the frame of Heartbeat needs {FrameSize} bytes but the buffer holds {buffer.Length}", nameof(buffer));
+            }
+            var header = new FrameHeaderWriter(buffer);
+            header.MessageId = (ushort)Id;
+            header.Length = (ushort)FrameSize;
+            return new HeartbeatWriter(buffer.Slice(FrameHeaderView.Size));
+        }
+
+        /// <summary>u64 at offset 0</summary>
+        public ulong SentAt
+        {
+            get => AsView().SentAt;
+            set => BinaryPrimitives.WriteUInt64LittleEndian(_buffer.Slice(0), value);
+        }
+
+        /// <summary>u32 at offset 8</summary>
+        public uint Sequence
+        {
+            get => AsView().Sequence;
+            set => BinaryPrimitives.WriteUInt32LittleEndian(_buffer.Slice(8), value);
+        }
+
+        /// <summary>Side at offset 12</summary>
+        public Side Side
+        {
+            get => AsView().Side;
+            set => _buffer[12] = (byte)value;
+        }
+    }
+
+    /// <summary>Callbacks for Dispatcher.Dispatch, a message without an override is ignored</summary>
+    public interface IHandler
+    {
+        void OnQuote(QuoteView message) { }
+
+        void OnRequest(RequestView message) { }
+
+        void OnHeartbeat(HeartbeatView message) { }
+    }
+
+    /// <summary>Calls a handler with the message of each frame</summary>
+    public static class Dispatcher
+    {
+        /// <summary>Reads the header at the start of buffer and calls handler with the message after it, returns the size of the frame</summary>
+        /// <exception cref="ArgumentException">The buffer doesn't hold the whole frame, its length is too small for its message or no message has its id</exception>
+        public static int Dispatch(ReadOnlySpan<byte> buffer, IHandler handler)
+        {
+            var header = new FrameHeaderView(buffer);
+            long frame = header.Length;
+            switch ((ulong)header.MessageId)
+            {
+                case QuoteView.Id:
+                    CheckFrame(buffer, frame, QuoteView.FrameSize);
+                    handler.OnQuote(new QuoteView(buffer.Slice(FrameHeaderView.Size)));
+                    return (int)frame;
+                case RequestView.Id:
+                    CheckFrame(buffer, frame, RequestView.FrameSize);
+                    handler.OnRequest(new RequestView(buffer.Slice(FrameHeaderView.Size)));
+                    return (int)frame;
+                case HeartbeatView.Id:
+                    CheckFrame(buffer, frame, HeartbeatView.FrameSize);
+                    handler.OnHeartbeat(new HeartbeatView(buffer.Slice(FrameHeaderView.Size)));
+                    return (int)frame;
+                default:
+                    throw new ArgumentException($"no message has the id {header.MessageId}", nameof(buffer));
+            }
+        }
+
+        private static void CheckFrame(ReadOnlySpan<byte> buffer, long frame, int size)
+        {
+            if (frame < size)
+            {
+                throw new ArgumentException($"a frame length of {frame} is too small for a message of {size} bytes", nameof(buffer));
+            }
+            if (frame > buffer.Length)
+            {
+                throw new ArgumentException($"a frame of {frame} bytes overruns a buffer of {buffer.Length}", nameof(buffer));
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// Exercises the generated package, vetted and tested by tests/codegen.rs when go is found.
+package codegen
+
+import (
+	"errors"
+	"io"
+	"testing"
+	"time"
+)
+
+func TestQuote(t *testing.T) {
+	quote := Quote{
+		SentAt: 1700000000123456789,
+		Symbol: "AAPL",
+		Side:   SideSell,
+		Bid:    Price{Value: 1234500, Currency: "USD"},
+		Levels: [3]int32{-1, 0, 1},
+		Weight: 0.5,
+		TTL:    -250,
+	}
+	b, err := quote.MarshalBinary()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(b) != QuoteSize || b[0] != 0x15 {
+		t.Fatalf("not little endian on the wire: % x", b)
+	}
+
+	var decoded Quote
+	if err := decoded.UnmarshalBinary(b); err != nil {
+		t.Fatal(err)
+	}
+	if decoded != quote {
+		t.Fatalf("decoded %+v, encoded %+v", decoded, quote)
+	}
+
+	v := QuoteView(b)
+	if v.TTL() != -250 || v.Bid().Value() != 1234500 || PriceValueExponent != -4 {
+		t.Fatal("view disagrees with the decoded quote")
+	}
+	if v.SentAtTime() != time.Unix(0, 1700000000123456789).UTC() || v.TTLDuration() != -250*time.Microsecond {
+		t.Fatal("time accessors")
+	}
+	v.SetTTLDuration(3 * time.Millisecond)
+	if v.TTL() != 3000 {
+		t.Fatal("ttl duration")
+	}
+	v.SetTTL(-250)
+	v.SetLevels(1, 7)
+	if v.Levels(1) != 7 {
+		t.Fatal("levels")
+	}
+
+	if err := v.SetSymbol("TOOLONGXX"); err == nil {
+		t.Fatal("symbol too long")
+	}
+	if _, err := NewQuoteView(b[1:]); !errors.Is(err, io.ErrUnexpectedEOF) {
+		t.Fatalf("short buffer: %v", err)
+	}
+	b[QuoteSideOffset] = 7
+	if v.Side().String() != "Side(7)" {
+		t.Fatalf("undeclared side: %v", v.Side())
+	}
+}
+
+func TestRequest(t *testing.T) {
+	request := Request{
+		Sequence:    0xFFFFFFFF,
+		Permissions: PermissionsRead | PermissionsAdmin,
+		Kind:        BodyKindCancel,
+		Checksum:    [4]byte{1, 2, 3, 4},
+		Type:        -2,
+		Venue:       "XLON",
+	}
+	request.Body.View().SetCancelID(99)
+	b, err := request.MarshalBinary()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	v := RequestView(b)
+	if v.Sequence() != 0xFFFFFFFF || v.Type() != -2 || v.Checksum()[3] != 4 {
+		t.Fatal("scalars")
+	}
+	if !v.Permissions().Has(PermissionsAdmin) || v.Permissions().String() != "READ|ADMIN" {
+		t.Fatalf("permissions: %v", v.Permissions())
+	}
+	if v.Kind() != BodyKindCancel || v.Body().CancelID() != 99 {
+		t.Fatal("body")
+	}
+	if id, ok := v.BodyCancelID(); !ok || id != 99 {
+		t.Fatal("checked body")
+	}
+	if _, ok := v.BodyOrder(); ok {
+		t.Fatal("inactive variant")
+	}
+	if v.Venue() != "XLON" || string(b[RequestVenueOffset:RequestVenueOffset+6]) != "XLON  " {
+		t.Fatalf("venue: %q", b[RequestVenueOffset:RequestVenueOffset+6])
+	}
+}
+
+type seen struct {
+	NopHandler
+	heartbeats int
+}
+
+func (s *seen) OnHeartbeat(HeartbeatView) { s.heartbeats++ }
+
+func TestDispatch(t *testing.T) {
+	heartbeat, err := Heartbeat{Sequence: 7}.MarshalFrame()
+	if err != nil {
+		t.Fatal(err)
+	}
+	quote, err := Quote{}.MarshalFrame()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if HeartbeatFrameSize != FrameHeaderSize+HeartbeatSize {
+		t.Fatal("frame size")
+	}
+	if heartbeat[0] != 24 || heartbeat[1] != 0 || heartbeat[2] != 3 || heartbeat[3] != 0 {
+		t.Fatalf("header: % x", heartbeat[:4])
+	}
+
+	b := append(heartbeat, quote...)
+	h := &seen{}
+	if n, err := Dispatch(b, h); err != nil || n != HeartbeatFrameSize {
+		t.Fatalf("heartbeat frame: %d %v", n, err)
+	}
+	if n, err := Dispatch(b[HeartbeatFrameSize:], h); err != nil || n != QuoteFrameSize {
+		t.Fatalf("quote frame: %d %v", n, err)
+	}
+	if h.heartbeats != 1 {
+		t.Fatal("only the heartbeat is handled")
+	}
+
+	if _, err := Dispatch(b[:10], h); !errors.Is(err, io.ErrUnexpectedEOF) {
+		t.Fatalf("short frame: %v", err)
+	}
+	b[2] = 9
+	if _, err := Dispatch(b, h); err == nil {
+		t.Fatal("unknown id")
+	}
+	b[2], b[0] = 3, 4
+	if _, err := Dispatch(b, h); err == nil {
+		t.Fatal("length too small")
+	}
+}
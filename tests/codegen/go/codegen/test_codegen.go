@@ -0,0 +1,863 @@
+// Code generated by sssc 0.1.0 from protocol test.codegen. DO NOT EDIT.
+
+// Package codegen reads and writes the types of protocol test.codegen.
+package codegen
+
+import (
+	"encoding/binary"
+	"fmt"
+	"io"
+	"math"
+	"strings"
+	"time"
+)
+
+var byteOrder = binary.LittleEndian
+
+// shortBuffer is returned when a buffer is too short for the type read from it.
+func shortBuffer(name string, size, length int) error {
+	return fmt.Errorf("codegen: %s needs %d bytes but the buffer holds %d: %w", name, size, length, io.ErrUnexpectedEOF)
+}
+
+// getASCII returns the text of an ascii field, which ends at the first nul byte, without
+// the pad bytes ending it.
+func getASCII(b []byte, pad byte) string {
+	n := 0
+	for n < len(b) && b[n] != 0 {
+		n++
+	}
+	for n > 0 && b[n-1] == pad {
+		n--
+	}
+	return string(b[:n])
+}
+
+// setASCII writes s to an ascii field, followed by pad bytes.
+func setASCII(b []byte, pad byte, s, name string) error {
+	if len(s) > len(b) {
+		return fmt.Errorf("codegen: %s holds at most %d characters but the value has %d", name, len(b), len(s))
+	}
+	for i := 0; i < len(s); i++ {
+		if s[i] > 0x7f {
+			return fmt.Errorf("codegen: %s only holds ascii characters", name)
+		}
+	}
+	for i := copy(b, s); i < len(b); i++ {
+		b[i] = pad
+	}
+	return nil
+}
+
+// Side is enum Side, stored as a u8.
+type Side uint8
+
+const (
+	SideBuy  Side = 1
+	SideSell Side = 2
+)
+
+// String returns the name of v as declared in the schema.
+func (v Side) String() string {
+	switch v {
+	case SideBuy:
+		return "BUY"
+	case SideSell:
+		return "SELL"
+	}
+	return fmt.Sprintf("Side(%d)", uint8(v))
+}
+
+// Permissions is bitflags Permissions, stored as a u16.
+type Permissions uint16
+
+const (
+	PermissionsRead  Permissions = 1
+	PermissionsWrite Permissions = 2
+	PermissionsAdmin Permissions = 128
+)
+
+// Has reports whether every flag of flags is set in v.
+func (v Permissions) Has(flags Permissions) bool { return v&flags == flags }
+
+// String joins the names of the flags set in v with |.
+func (v Permissions) String() string {
+	var names []string
+	if v&PermissionsRead != 0 {
+		names = append(names, "READ")
+	}
+	if v&PermissionsWrite != 0 {
+		names = append(names, "WRITE")
+	}
+	if v&PermissionsAdmin != 0 {
+		names = append(names, "ADMIN")
+	}
+	if rest := v &^ 0x83; rest != 0 {
+		names = append(names, fmt.Sprintf("%#x", uint16(rest)))
+	}
+	return strings.Join(names, "|")
+}
+
+// BodyKind is enum BodyKind, stored as a u8.
+type BodyKind uint8
+
+const (
+	BodyKindOrder  BodyKind = 1
+	BodyKindCancel BodyKind = 2
+	BodyKindPing   BodyKind = 3
+)
+
+// String returns the name of v as declared in the schema.
+func (v BodyKind) String() string {
+	switch v {
+	case BodyKindOrder:
+		return "ORDER"
+	case BodyKindCancel:
+		return "CANCEL"
+	case BodyKindPing:
+		return "PING"
+	}
+	return fmt.Sprintf("BodyKind(%d)", uint8(v))
+}
+
+// Price holds struct Price.
+type Price struct {
+	Value    int64
+	Currency string
+}
+
+// Layout of Price.
+const (
+	PriceSize           = 11
+	PriceValueOffset    = 0
+	PriceValueExponent  = -4
+	PriceCurrencyOffset = 8
+)
+
+// PriceView reads and writes struct Price in place.
+type PriceView []byte
+
+// NewPriceView views the start of b, which must hold at least PriceSize bytes.
+func NewPriceView(b []byte) (PriceView, error) {
+	if len(b) < PriceSize {
+		return nil, shortBuffer("Price", PriceSize, len(b))
+	}
+	return PriceView(b[:PriceSize]), nil
+}
+
+// Value reads the decimal<i64, -4> at offset 0, the amount in units of the currency.
+func (v PriceView) Value() int64 { return int64(byteOrder.Uint64(v[0:])) }
+
+// SetValue writes the decimal<i64, -4> at offset 0, the amount in units of the currency.
+func (v PriceView) SetValue(x int64) { byteOrder.PutUint64(v[0:], uint64(x)) }
+
+// Currency reads the ascii[3] at offset 8.
+func (v PriceView) Currency() string { return getASCII(v[8:11], 0) }
+
+// SetCurrency writes the ascii[3] at offset 8, it fails when s is too long or isn't ascii.
+func (v PriceView) SetCurrency(s string) error { return setASCII(v[8:11], 0, s, "currency") }
+
+// MarshalBinary encodes m into a new buffer of PriceSize bytes.
+func (m Price) MarshalBinary() ([]byte, error) {
+	b := make([]byte, PriceSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least PriceSize bytes.
+func (m *Price) UnmarshalBinary(data []byte) error {
+	v, err := NewPriceView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+func (m *Price) decode(v PriceView) {
+	m.Value = v.Value()
+	m.Currency = v.Currency()
+}
+
+func (m *Price) encode(v PriceView) error {
+	v.SetValue(m.Value)
+	if err := v.SetCurrency(m.Currency); err != nil {
+		return err
+	}
+	return nil
+}
+
+// Order holds struct Order.
+type Order struct {
+	Side     Side
+	Quantity uint32
+	Price    Price
+}
+
+// Layout of Order.
+const (
+	OrderSize           = 16
+	OrderSideOffset     = 0
+	OrderQuantityOffset = 1
+	OrderPriceOffset    = 5
+)
+
+// OrderView reads and writes struct Order in place.
+type OrderView []byte
+
+// NewOrderView views the start of b, which must hold at least OrderSize bytes.
+func NewOrderView(b []byte) (OrderView, error) {
+	if len(b) < OrderSize {
+		return nil, shortBuffer("Order", OrderSize, len(b))
+	}
+	return OrderView(b[:OrderSize]), nil
+}
+
+// Side reads the Side at offset 0.
+func (v OrderView) Side() Side { return Side(v[0]) }
+
+// SetSide writes the Side at offset 0.
+func (v OrderView) SetSide(x Side) { v[0] = uint8(x) }
+
+// Quantity reads the u32 at offset 1.
+func (v OrderView) Quantity() uint32 { return byteOrder.Uint32(v[1:]) }
+
+// SetQuantity writes the u32 at offset 1.
+func (v OrderView) SetQuantity(x uint32) { byteOrder.PutUint32(v[1:], x) }
+
+// Price views the Price at offset 5.
+func (v OrderView) Price() PriceView { return PriceView(v[5:16]) }
+
+// MarshalBinary encodes m into a new buffer of OrderSize bytes.
+func (m Order) MarshalBinary() ([]byte, error) {
+	b := make([]byte, OrderSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least OrderSize bytes.
+func (m *Order) UnmarshalBinary(data []byte) error {
+	v, err := NewOrderView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+func (m *Order) decode(v OrderView) {
+	m.Side = v.Side()
+	m.Quantity = v.Quantity()
+	m.Price.decode(v.Price())
+}
+
+func (m *Order) encode(v OrderView) error {
+	v.SetSide(m.Side)
+	v.SetQuantity(m.Quantity)
+	if err := m.Price.encode(v.Price()); err != nil {
+		return err
+	}
+	return nil
+}
+
+// Body holds union Body, the active variant is selected by a BodyKind.
+type Body [BodySize]byte
+
+// Layout of Body.
+const (
+	BodySize           = 16
+	BodyOrderOffset    = 0
+	BodyCancelIDOffset = 0
+	BodyPingOffset     = 0
+)
+
+// BodyView reads and writes union Body in place.
+type BodyView []byte
+
+// NewBodyView views the start of b, which must hold at least BodySize bytes.
+func NewBodyView(b []byte) (BodyView, error) {
+	if len(b) < BodySize {
+		return nil, shortBuffer("Body", BodySize, len(b))
+	}
+	return BodyView(b[:BodySize]), nil
+}
+
+// Order views the Order at offset 0, active when the discriminator is ORDER.
+func (v BodyView) Order() OrderView { return OrderView(v[0:16]) }
+
+// CancelID reads the u64 at offset 0, active when the discriminator is CANCEL.
+func (v BodyView) CancelID() uint64 { return byteOrder.Uint64(v[0:]) }
+
+// SetCancelID writes the u64 at offset 0, active when the discriminator is CANCEL.
+func (v BodyView) SetCancelID(x uint64) { byteOrder.PutUint64(v[0:], x) }
+
+// Ping reads the ascii[4] at offset 0, active when the discriminator is PING.
+func (v BodyView) Ping() string { return getASCII(v[0:4], 0) }
+
+// SetPing writes the ascii[4] at offset 0, active when the discriminator is PING, it fails when s is too long or isn't ascii.
+func (v BodyView) SetPing(s string) error { return setASCII(v[0:4], 0, s, "ping") }
+
+// View reads and writes the variants held by u.
+func (u *Body) View() BodyView { return u[:] }
+
+// MarshalBinary returns a copy of the BodySize bytes of u.
+func (u Body) MarshalBinary() ([]byte, error) { return u[:], nil }
+
+// UnmarshalBinary copies the start of data, which must hold at least BodySize bytes.
+func (u *Body) UnmarshalBinary(data []byte) error {
+	if len(data) < BodySize {
+		return shortBuffer("Body", BodySize, len(data))
+	}
+	copy(u[:], data)
+	return nil
+}
+
+// FrameHeader holds struct FrameHeader.
+type FrameHeader struct {
+	Length    uint16
+	MessageID uint16
+	Sequence  uint32
+}
+
+// Layout of FrameHeader.
+const (
+	FrameHeaderSize            = 8
+	FrameHeaderLengthOffset    = 0
+	FrameHeaderMessageIDOffset = 2
+	FrameHeaderSequenceOffset  = 4
+)
+
+// FrameHeaderView reads and writes struct FrameHeader in place.
+type FrameHeaderView []byte
+
+// NewFrameHeaderView views the start of b, which must hold at least FrameHeaderSize bytes.
+func NewFrameHeaderView(b []byte) (FrameHeaderView, error) {
+	if len(b) < FrameHeaderSize {
+		return nil, shortBuffer("FrameHeader", FrameHeaderSize, len(b))
+	}
+	return FrameHeaderView(b[:FrameHeaderSize]), nil
+}
+
+// Length reads the u16 at offset 0.
+func (v FrameHeaderView) Length() uint16 { return byteOrder.Uint16(v[0:]) }
+
+// SetLength writes the u16 at offset 0.
+func (v FrameHeaderView) SetLength(x uint16) { byteOrder.PutUint16(v[0:], x) }
+
+// MessageID reads the u16 at offset 2.
+func (v FrameHeaderView) MessageID() uint16 { return byteOrder.Uint16(v[2:]) }
+
+// SetMessageID writes the u16 at offset 2.
+func (v FrameHeaderView) SetMessageID(x uint16) { byteOrder.PutUint16(v[2:], x) }
+
+// Sequence reads the u32 at offset 4.
+func (v FrameHeaderView) Sequence() uint32 { return byteOrder.Uint32(v[4:]) }
+
+// SetSequence writes the u32 at offset 4.
+func (v FrameHeaderView) SetSequence(x uint32) { byteOrder.PutUint32(v[4:], x) }
+
+// MarshalBinary encodes m into a new buffer of FrameHeaderSize bytes.
+func (m FrameHeader) MarshalBinary() ([]byte, error) {
+	b := make([]byte, FrameHeaderSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least FrameHeaderSize bytes.
+func (m *FrameHeader) UnmarshalBinary(data []byte) error {
+	v, err := NewFrameHeaderView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+func (m *FrameHeader) decode(v FrameHeaderView) {
+	m.Length = v.Length()
+	m.MessageID = v.MessageID()
+	m.Sequence = v.Sequence()
+}
+
+func (m *FrameHeader) encode(v FrameHeaderView) error {
+	v.SetLength(m.Length)
+	v.SetMessageID(m.MessageID)
+	v.SetSequence(m.Sequence)
+	return nil
+}
+
+// Quote holds message Quote [1].
+type Quote struct {
+	SentAt uint64
+	Symbol string
+	Side   Side
+	Bid    Price
+	Ask    Price
+	Levels [3]int32
+	Weight float64
+	TTL    int64
+}
+
+// Layout of Quote.
+const (
+	QuoteID           = 1
+	QuoteSize         = 67
+	QuoteFrameSize    = 75
+	QuoteSentAtOffset = 0
+	QuoteSentAtUnit   = time.Nanosecond
+	QuoteSymbolOffset = 8
+	QuoteSideOffset   = 16
+	QuoteBidOffset    = 17
+	QuoteAskOffset    = 28
+	QuoteLevelsOffset = 39
+	QuoteLevelsLength = 3
+	QuoteWeightOffset = 51
+	QuoteTTLOffset    = 59
+	QuoteTTLUnit      = time.Microsecond
+)
+
+// QuoteView reads and writes message Quote [1] in place.
+type QuoteView []byte
+
+// NewQuoteView views the start of b, which must hold at least QuoteSize bytes.
+func NewQuoteView(b []byte) (QuoteView, error) {
+	if len(b) < QuoteSize {
+		return nil, shortBuffer("Quote", QuoteSize, len(b))
+	}
+	return QuoteView(b[:QuoteSize]), nil
+}
+
+// SentAt reads the timestamp<ns> at offset 0, when the quote was sent.
+func (v QuoteView) SentAt() uint64 { return byteOrder.Uint64(v[0:]) }
+
+// SetSentAt writes the timestamp<ns> at offset 0, when the quote was sent.
+func (v QuoteView) SetSentAt(x uint64) { byteOrder.PutUint64(v[0:], x) }
+
+// SentAtTime reads sent_at as a UTC time.
+func (v QuoteView) SentAtTime() time.Time { return time.Unix(0, int64(v.SentAt())).UTC() }
+
+// SetSentAtTime writes t to sent_at, truncated to QuoteSentAtUnit.
+func (v QuoteView) SetSentAtTime(t time.Time) { v.SetSentAt(uint64(t.UnixNano())) }
+
+// Symbol reads the ascii[8] at offset 8.
+func (v QuoteView) Symbol() string { return getASCII(v[8:16], 0) }
+
+// SetSymbol writes the ascii[8] at offset 8, it fails when s is too long or isn't ascii.
+func (v QuoteView) SetSymbol(s string) error { return setASCII(v[8:16], 0, s, "symbol") }
+
+// Side reads the Side at offset 16.
+func (v QuoteView) Side() Side { return Side(v[16]) }
+
+// SetSide writes the Side at offset 16.
+func (v QuoteView) SetSide(x Side) { v[16] = uint8(x) }
+
+// Bid views the Price at offset 17.
+func (v QuoteView) Bid() PriceView { return PriceView(v[17:28]) }
+
+// Ask views the Price at offset 28.
+func (v QuoteView) Ask() PriceView { return PriceView(v[28:39]) }
+
+// Levels reads element i of the i32[3] at offset 39.
+func (v QuoteView) Levels(i int) int32 {
+	if uint(i) >= QuoteLevelsLength {
+		panic(fmt.Sprintf("codegen: index %d out of range for levels", i))
+	}
+	return int32(byteOrder.Uint32(v[39+4*i:]))
+}
+
+// SetLevels writes element i of the i32[3] at offset 39.
+func (v QuoteView) SetLevels(i int, x int32) {
+	if uint(i) >= QuoteLevelsLength {
+		panic(fmt.Sprintf("codegen: index %d out of range for levels", i))
+	}
+	byteOrder.PutUint32(v[39+4*i:], uint32(x))
+}
+
+// Weight reads the f64 at offset 51.
+func (v QuoteView) Weight() float64 { return math.Float64frombits(byteOrder.Uint64(v[51:])) }
+
+// SetWeight writes the f64 at offset 51.
+func (v QuoteView) SetWeight(x float64) { byteOrder.PutUint64(v[51:], math.Float64bits(x)) }
+
+// TTL reads the duration<us> at offset 59, how long the quote can be traded on, counted from sent_at.
+func (v QuoteView) TTL() int64 { return int64(byteOrder.Uint64(v[59:])) }
+
+// SetTTL writes the duration<us> at offset 59, how long the quote can be traded on, counted from sent_at.
+func (v QuoteView) SetTTL(x int64) { byteOrder.PutUint64(v[59:], uint64(x)) }
+
+// TTLDuration reads ttl as a time.Duration.
+func (v QuoteView) TTLDuration() time.Duration { return time.Duration(v.TTL()) * QuoteTTLUnit }
+
+// SetTTLDuration writes d to ttl, truncated to QuoteTTLUnit.
+func (v QuoteView) SetTTLDuration(d time.Duration) { v.SetTTL(int64(d / QuoteTTLUnit)) }
+
+// MarshalBinary encodes m into a new buffer of QuoteSize bytes.
+func (m Quote) MarshalBinary() ([]byte, error) {
+	b := make([]byte, QuoteSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least QuoteSize bytes.
+func (m *Quote) UnmarshalBinary(data []byte) error {
+	v, err := NewQuoteView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+// MarshalFrame encodes m after a FrameHeader holding its id and length.
+func (m Quote) MarshalFrame() ([]byte, error) {
+	b := make([]byte, QuoteFrameSize)
+	h := FrameHeaderView(b)
+	h.SetMessageID(QuoteID)
+	h.SetLength(QuoteFrameSize)
+	if err := m.encode(QuoteView(b[FrameHeaderSize:])); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+func (m *Quote) decode(v QuoteView) {
+	m.SentAt = v.SentAt()
+	m.Symbol = v.Symbol()
+	m.Side = v.Side()
+	m.Bid.decode(v.Bid())
+	m.Ask.decode(v.Ask())
+	for i := range m.Levels {
+		m.Levels[i] = v.Levels(i)
+	}
+	m.Weight = v.Weight()
+	m.TTL = v.TTL()
+}
+
+func (m *Quote) encode(v QuoteView) error {
+	v.SetSentAt(m.SentAt)
+	if err := v.SetSymbol(m.Symbol); err != nil {
+		return err
+	}
+	v.SetSide(m.Side)
+	if err := m.Bid.encode(v.Bid()); err != nil {
+		return err
+	}
+	if err := m.Ask.encode(v.Ask()); err != nil {
+		return err
+	}
+	for i, x := range m.Levels {
+		v.SetLevels(i, x)
+	}
+	v.SetWeight(m.Weight)
+	v.SetTTL(m.TTL)
+	return nil
+}
+
+// Request holds message Request [2].
+type Request struct {
+	Sequence    uint32
+	Permissions Permissions
+	Kind        BodyKind
+	Body        Body
+	Checksum    [4]byte
+	Type        int16
+	Venue       string
+}
+
+// Layout of Request.
+const (
+	RequestID                = 2
+	RequestSize              = 48
+	RequestFrameSize         = 56
+	RequestSequenceOffset    = 0
+	RequestPermissionsOffset = 4
+	RequestKindOffset        = 6
+	RequestBodyOffset        = 7
+	RequestChecksumOffset    = 23
+	RequestTypeOffset        = 29
+	RequestVenueOffset       = 31
+)
+
+// RequestView reads and writes message Request [2] in place.
+type RequestView []byte
+
+// NewRequestView views the start of b, which must hold at least RequestSize bytes.
+func NewRequestView(b []byte) (RequestView, error) {
+	if len(b) < RequestSize {
+		return nil, shortBuffer("Request", RequestSize, len(b))
+	}
+	return RequestView(b[:RequestSize]), nil
+}
+
+// Sequence reads the u32 at offset 0.
+func (v RequestView) Sequence() uint32 { return byteOrder.Uint32(v[0:]) }
+
+// SetSequence writes the u32 at offset 0.
+func (v RequestView) SetSequence(x uint32) { byteOrder.PutUint32(v[0:], x) }
+
+// Permissions reads the Permissions at offset 4.
+func (v RequestView) Permissions() Permissions { return Permissions(byteOrder.Uint16(v[4:])) }
+
+// SetPermissions writes the Permissions at offset 4.
+func (v RequestView) SetPermissions(x Permissions) { byteOrder.PutUint16(v[4:], uint16(x)) }
+
+// Kind reads the BodyKind at offset 6.
+func (v RequestView) Kind() BodyKind { return BodyKind(v[6]) }
+
+// SetKind writes the BodyKind at offset 6.
+func (v RequestView) SetKind(x BodyKind) { v[6] = uint8(x) }
+
+// Body views the Body at offset 7, the order or cancel the request is for.
+func (v RequestView) Body() BodyView { return BodyView(v[7:23]) }
+
+// BodyOrder reads Body().Order(), ok is false unless Kind() is BodyKindOrder.
+func (v RequestView) BodyOrder() (x OrderView, ok bool) {
+	if v.Kind() != BodyKindOrder {
+		return x, false
+	}
+	return v.Body().Order(), true
+}
+
+// BodyCancelID reads Body().CancelID(), ok is false unless Kind() is BodyKindCancel.
+func (v RequestView) BodyCancelID() (x uint64, ok bool) {
+	if v.Kind() != BodyKindCancel {
+		return x, false
+	}
+	return v.Body().CancelID(), true
+}
+
+// BodyPing reads Body().Ping(), ok is false unless Kind() is BodyKindPing.
+func (v RequestView) BodyPing() (x string, ok bool) {
+	if v.Kind() != BodyKindPing {
+		return x, false
+	}
+	return v.Body().Ping(), true
+}
+
+// Checksum views the byte[4] at offset 23.
+func (v RequestView) Checksum() []byte { return v[23:27] }
+
+// Type reads the i16 at offset 29.
+func (v RequestView) Type() int16 { return int16(byteOrder.Uint16(v[29:])) }
+
+// SetType writes the i16 at offset 29.
+func (v RequestView) SetType(x int16) { byteOrder.PutUint16(v[29:], uint16(x)) }
+
+// Venue reads the ascii[6] at offset 31.
+func (v RequestView) Venue() string { return getASCII(v[31:37], ' ') }
+
+// SetVenue writes the ascii[6] at offset 31, it fails when s is too long or isn't ascii.
+func (v RequestView) SetVenue(s string) error { return setASCII(v[31:37], ' ', s, "venue") }
+
+// MarshalBinary encodes m into a new buffer of RequestSize bytes.
+func (m Request) MarshalBinary() ([]byte, error) {
+	b := make([]byte, RequestSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least RequestSize bytes.
+func (m *Request) UnmarshalBinary(data []byte) error {
+	v, err := NewRequestView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+// MarshalFrame encodes m after a FrameHeader holding its id and length.
+func (m Request) MarshalFrame() ([]byte, error) {
+	b := make([]byte, RequestFrameSize)
+	h := FrameHeaderView(b)
+	h.SetMessageID(RequestID)
+	h.SetLength(RequestFrameSize)
+	if err := m.encode(RequestView(b[FrameHeaderSize:])); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+func (m *Request) decode(v RequestView) {
+	m.Sequence = v.Sequence()
+	m.Permissions = v.Permissions()
+	m.Kind = v.Kind()
+	copy(m.Body[:], v.Body())
+	copy(m.Checksum[:], v.Checksum())
+	m.Type = v.Type()
+	m.Venue = v.Venue()
+}
+
+func (m *Request) encode(v RequestView) error {
+	v.SetSequence(m.Sequence)
+	v.SetPermissions(m.Permissions)
+	v.SetKind(m.Kind)
+	copy(v.Body(), m.Body[:])
+	copy(v.Checksum(), m.Checksum[:])
+	v.SetType(m.Type)
+	if err := v.SetVenue(m.Venue); err != nil {
+		return err
+	}
+	return nil
+}
+
+// Heartbeat holds message Heartbeat [3].
+type Heartbeat struct {
+	SentAt   uint64
+	Sequence uint32
+	Side     Side
+}
+
+// Layout of Heartbeat.
+const (
+	HeartbeatID             = 3
+	HeartbeatSize           = 16
+	HeartbeatFrameSize      = 24
+	HeartbeatSentAtOffset   = 0
+	HeartbeatSequenceOffset = 8
+	HeartbeatSideOffset     = 12
+)
+
+// HeartbeatView reads and writes message Heartbeat [3] in place.
+type HeartbeatView []byte
+
+// NewHeartbeatView views the start of b, which must hold at least HeartbeatSize bytes.
+func NewHeartbeatView(b []byte) (HeartbeatView, error) {
+	if len(b) < HeartbeatSize {
+		return nil, shortBuffer("Heartbeat", HeartbeatSize, len(b))
+	}
+	return HeartbeatView(b[:HeartbeatSize]), nil
+}
+
+// SentAt reads the u64 at offset 0.
+func (v HeartbeatView) SentAt() uint64 { return byteOrder.Uint64(v[0:]) }
+
+// SetSentAt writes the u64 at offset 0.
+func (v HeartbeatView) SetSentAt(x uint64) { byteOrder.PutUint64(v[0:], x) }
+
+// Sequence reads the u32 at offset 8.
+func (v HeartbeatView) Sequence() uint32 { return byteOrder.Uint32(v[8:]) }
+
+// SetSequence writes the u32 at offset 8.
+func (v HeartbeatView) SetSequence(x uint32) { byteOrder.PutUint32(v[8:], x) }
+
+// Side reads the Side at offset 12.
+func (v HeartbeatView) Side() Side { return Side(v[12]) }
+
+// SetSide writes the Side at offset 12.
+func (v HeartbeatView) SetSide(x Side) { v[12] = uint8(x) }
+
+// MarshalBinary encodes m into a new buffer of HeartbeatSize bytes.
+func (m Heartbeat) MarshalBinary() ([]byte, error) {
+	b := make([]byte, HeartbeatSize)
+	if err := m.encode(b); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+// UnmarshalBinary decodes m from the start of data, which must hold at least HeartbeatSize bytes.
+func (m *Heartbeat) UnmarshalBinary(data []byte) error {
+	v, err := NewHeartbeatView(data)
+	if err != nil {
+		return err
+	}
+	m.decode(v)
+	return nil
+}
+
+// MarshalFrame encodes m after a FrameHeader holding its id and length.
+func (m Heartbeat) MarshalFrame() ([]byte, error) {
+	b := make([]byte, HeartbeatFrameSize)
+	h := FrameHeaderView(b)
+	h.SetMessageID(HeartbeatID)
+	h.SetLength(HeartbeatFrameSize)
+	if err := m.encode(HeartbeatView(b[FrameHeaderSize:])); err != nil {
+		return nil, err
+	}
+	return b, nil
+}
+
+func (m *Heartbeat) decode(v HeartbeatView) {
+	m.SentAt = v.SentAt()
+	m.Sequence = v.Sequence()
+	m.Side = v.Side()
+}
+
+func (m *Heartbeat) encode(v HeartbeatView) error {
+	v.SetSentAt(m.SentAt)
+	v.SetSequence(m.Sequence)
+	v.SetSide(m.Side)
+	return nil
+}
+
+// Handler receives the messages of the frames passed to Dispatch.
+type Handler interface {
+	OnQuote(QuoteView)
+	OnRequest(RequestView)
+	OnHeartbeat(HeartbeatView)
+}
+
+// NopHandler ignores every message, embed it in a Handler to handle only some of them.
+type NopHandler struct{}
+
+// OnQuote ignores the message.
+func (NopHandler) OnQuote(QuoteView) {}
+
+// OnRequest ignores the message.
+func (NopHandler) OnRequest(RequestView) {}
+
+// OnHeartbeat ignores the message.
+func (NopHandler) OnHeartbeat(HeartbeatView) {}
+
+// checkFrame returns an error when the length of a frame is too small for its message or
+// the buffer doesn't hold the whole frame.
+func checkFrame(name string, frame, size, length int) error {
+	if frame < size {
+		return fmt.Errorf("codegen: a frame length of %d is too small for %s", frame, name)
+	}
+	if length < frame {
+		return shortBuffer("the frame of "+name, frame, length)
+	}
+	return nil
+}
+
+// Dispatch reads the FrameHeader at the start of b and passes the message after it to h, returning
+// the size of the frame.
+func Dispatch(b []byte, h Handler) (int, error) {
+	header, err := NewFrameHeaderView(b)
+	if err != nil {
+		return 0, err
+	}
+	frame := int(header.Length())
+	switch header.MessageID() {
+	case QuoteID:
+		if err := checkFrame("Quote", frame, QuoteFrameSize, len(b)); err != nil {
+			return 0, err
+		}
+		h.OnQuote(QuoteView(b[FrameHeaderSize : FrameHeaderSize+QuoteSize]))
+		return frame, nil
+	case RequestID:
+		if err := checkFrame("Request", frame, RequestFrameSize, len(b)); err != nil {
+			return 0, err
+		}
+		h.OnRequest(RequestView(b[FrameHeaderSize : FrameHeaderSize+RequestSize]))
+		return frame, nil
+	case HeartbeatID:
+		if err := checkFrame("Heartbeat", frame, HeartbeatFrameSize, len(b)); err != nil {
+			return 0, err
+		}
+		h.OnHeartbeat(HeartbeatView(b[FrameHeaderSize : FrameHeaderSize+HeartbeatSize]))
+		return frame, nil
+	}
+	return 0, fmt.Errorf("codegen: no message has the id %d", header.MessageID())
+}
@@ -0,0 +1,95 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** union Body, the active variant is selected by a BodyKind */
+public final class Body {
+    public static final int SIZE = 16;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int ORDER_OFFSET = 0;
+    public static final int CANCEL_ID_OFFSET = 0;
+    public static final int PING_OFFSET = 0;
+    public static final int PING_LENGTH = 4;
+
+    private static final VarHandle I64 = MethodHandles.byteBufferViewVarHandle(long[].class, BYTE_ORDER);
+
+    private final Order order = new Order();
+    private final AsciiSequence ping = new AsciiSequence();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Body wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** Order: active when the discriminator is ORDER */
+    public Order order() {
+        return order.wrap(buffer, offset);
+    }
+
+    /** u64: active when the discriminator is CANCEL */
+    public long cancelId() {
+        return (long) I64.get(buffer, offset);
+    }
+
+    public Body cancelId(final long value) {
+        I64.set(buffer, offset, value);
+        return this;
+    }
+
+    /** ascii[4]: active when the discriminator is PING */
+    public CharSequence ping() {
+        return ping.wrap(buffer, offset, PING_LENGTH, '\0');
+    }
+
+    /** Fails if the value is longer than the field or isn't ascii */
+    public Body ping(final CharSequence value) {
+        AsciiSequence.put(buffer, offset, PING_LENGTH, '\0', value, "Body.ping");
+        return this;
+    }
+
+    /** The variant selected by a discriminator value, the raw bytes if none is */
+    public String toString(final long discriminator) {
+        if (discriminator == 1L) {
+            return "order=" + order();
+        }
+        if (discriminator == 2L) {
+            return "cancelId=" + cancelId();
+        }
+        if (discriminator == 3L) {
+            return "ping=" + ping();
+        }
+        return toString();
+    }
+
+    @Override
+    public String toString() {
+        return hex(0, SIZE);
+    }
+
+    private String hex(final int from, final int length) {
+        final StringBuilder text = new StringBuilder("0x");
+        for (int i = 0; i < length; i++) {
+            text.append(String.format("%02x", buffer.get(offset + from + i)));
+        }
+        return text.toString();
+    }
+}
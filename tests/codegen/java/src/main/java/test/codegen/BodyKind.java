@@ -0,0 +1,36 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+/** enum BodyKind, stored as a u8 */
+public enum BodyKind {
+    ORDER((short) 1),
+    CANCEL((short) 2),
+    PING((short) 3),
+    /** Not declared in the schema, returned for any undeclared value */
+    NULL_VAL((short) 255);
+
+    private final short value;
+
+    BodyKind(final short value) {
+        this.value = value;
+    }
+
+    /** The value stored in a message */
+    public short value() {
+        return value;
+    }
+
+    /** The constant for a stored value, NULL_VAL if it isn't declared */
+    public static BodyKind get(final short value) {
+        if (value == 1) {
+            return ORDER;
+        }
+        if (value == 2) {
+            return CANCEL;
+        }
+        if (value == 3) {
+            return PING;
+        }
+        return NULL_VAL;
+    }
+}
@@ -0,0 +1,55 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.nio.ByteBuffer;
+import java.util.Objects;
+
+/** Reads frames and calls a Handler with the message of each, reusing one flyweight per message */
+public final class Dispatcher {
+    private final Handler handler;
+    private final FrameHeader header = new FrameHeader();
+    private final Quote quote = new Quote();
+    private final Request request = new Request();
+    private final Heartbeat heartbeat = new Heartbeat();
+
+    public Dispatcher(final Handler handler) {
+        this.handler = Objects.requireNonNull(handler);
+    }
+
+    /**
+     * Reads the header at offset and calls the handler with the message after it, returns the
+     * size of the frame. Throws IndexOutOfBoundsException when the buffer doesn't hold the whole
+     * frame and IllegalArgumentException when its length is too small or no message has its id.
+     */
+    public int dispatch(final ByteBuffer buffer, final int offset) {
+        header.wrap(buffer, offset);
+        final long id = header.messageId();
+        final long length = header.length();
+        if (id == Quote.ID) {
+            final int frame = frame(buffer, offset, length, Quote.FRAME_SIZE);
+            handler.onQuote(quote.wrap(buffer, offset + FrameHeader.SIZE));
+            return frame;
+        }
+        if (id == Request.ID) {
+            final int frame = frame(buffer, offset, length, Request.FRAME_SIZE);
+            handler.onRequest(request.wrap(buffer, offset + FrameHeader.SIZE));
+            return frame;
+        }
+        if (id == Heartbeat.ID) {
+            final int frame = frame(buffer, offset, length, Heartbeat.FRAME_SIZE);
+            handler.onHeartbeat(heartbeat.wrap(buffer, offset + FrameHeader.SIZE));
+            return frame;
+        }
+        throw new IllegalArgumentException("no message has the id " + id);
+    }
+
+    private static int frame(final ByteBuffer buffer, final int offset, final long length, final int size) {
+        if (length < size) {
+            throw new IllegalArgumentException("a frame length of " + length + " is too small for a message of " + size + " bytes");
+        }
+        if (length > buffer.capacity() - offset) {
+            throw new IndexOutOfBoundsException("a frame of " + length + " bytes at " + offset + " overruns a buffer of " + buffer.capacity());
+        }
+        return (int) length;
+    }
+}
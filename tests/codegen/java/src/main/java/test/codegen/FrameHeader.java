@@ -0,0 +1,79 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** struct FrameHeader */
+public final class FrameHeader {
+    public static final int SIZE = 8;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int LENGTH_OFFSET = 0;
+    public static final int MESSAGE_ID_OFFSET = 2;
+    public static final int SEQUENCE_OFFSET = 4;
+
+    private static final VarHandle I16 = MethodHandles.byteBufferViewVarHandle(short[].class, BYTE_ORDER);
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public FrameHeader wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** u16 at offset 0 */
+    public int length() {
+        return (short) I16.get(buffer, offset) & 0xFFFF;
+    }
+
+    public FrameHeader length(final int value) {
+        I16.set(buffer, offset, (short) value);
+        return this;
+    }
+
+    /** u16 at offset 2 */
+    public int messageId() {
+        return (short) I16.get(buffer, offset + 2) & 0xFFFF;
+    }
+
+    public FrameHeader messageId(final int value) {
+        I16.set(buffer, offset + 2, (short) value);
+        return this;
+    }
+
+    /** u32 at offset 4 */
+    public long sequence() {
+        return Integer.toUnsignedLong((int) I32.get(buffer, offset + 4));
+    }
+
+    public FrameHeader sequence(final long value) {
+        I32.set(buffer, offset + 4, (int) value);
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("FrameHeader{");
+        text.append("length=").append(length());
+        text.append(", messageId=").append(messageId());
+        text.append(", sequence=").append(sequence());
+        return text.append('}').toString();
+    }
+}
@@ -0,0 +1,17 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+/** Callbacks for Dispatcher, a message without an override is ignored */
+public interface Handler {
+    /** Called with a Quote */
+    default void onQuote(final Quote message) {
+    }
+
+    /** Called with a Request */
+    default void onRequest(final Request message) {
+    }
+
+    /** Called with a Heartbeat */
+    default void onHeartbeat(final Heartbeat message) {
+    }
+}
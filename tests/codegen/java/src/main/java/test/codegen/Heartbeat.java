@@ -0,0 +1,98 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** message Heartbeat [3] */
+public final class Heartbeat {
+    public static final int ID = 3;
+    public static final int SIZE = 16;
+    public static final int FRAME_SIZE = 24;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int SENT_AT_OFFSET = 0;
+    public static final int SEQUENCE_OFFSET = 8;
+    public static final int SIDE_OFFSET = 12;
+
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+    private static final VarHandle I64 = MethodHandles.byteBufferViewVarHandle(long[].class, BYTE_ORDER);
+
+    private final FrameHeader frameHeader = new FrameHeader();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Heartbeat wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    /** Writes the header's id and length at offset and points this flyweight at the message after it */
+    public Heartbeat wrapFrame(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, FRAME_SIZE, buffer.capacity());
+        frameHeader.wrap(buffer, offset).messageId(ID).length(FRAME_SIZE);
+        return wrap(buffer, offset + FrameHeader.SIZE);
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** u64 at offset 0 */
+    public long sentAt() {
+        return (long) I64.get(buffer, offset);
+    }
+
+    public Heartbeat sentAt(final long value) {
+        I64.set(buffer, offset, value);
+        return this;
+    }
+
+    /** u32 at offset 8 */
+    public long sequence() {
+        return Integer.toUnsignedLong((int) I32.get(buffer, offset + 8));
+    }
+
+    public Heartbeat sequence(final long value) {
+        I32.set(buffer, offset + 8, (int) value);
+        return this;
+    }
+
+    /** Side at offset 12 */
+    public Side side() {
+        return Side.get(sideRaw());
+    }
+
+    public Heartbeat side(final Side value) {
+        buffer.put(offset + 12, (byte) value.value());
+        return this;
+    }
+
+    public short sideRaw() {
+        return (short) (buffer.get(offset + 12) & 0xFF);
+    }
+
+    public Heartbeat sideRaw(final short value) {
+        buffer.put(offset + 12, (byte) value);
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Heartbeat{");
+        text.append("sentAt=").append(sentAt());
+        text.append(", sequence=").append(sequence());
+        text.append(", side=").append(side());
+        return text.append('}').toString();
+    }
+}
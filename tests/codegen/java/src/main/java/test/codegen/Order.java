@@ -0,0 +1,83 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** struct Order */
+public final class Order {
+    public static final int SIZE = 16;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int SIDE_OFFSET = 0;
+    public static final int QUANTITY_OFFSET = 1;
+    public static final int PRICE_OFFSET = 5;
+
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+
+    private final Price price = new Price();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Order wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** Side at offset 0 */
+    public Side side() {
+        return Side.get(sideRaw());
+    }
+
+    public Order side(final Side value) {
+        buffer.put(offset, (byte) value.value());
+        return this;
+    }
+
+    public short sideRaw() {
+        return (short) (buffer.get(offset) & 0xFF);
+    }
+
+    public Order sideRaw(final short value) {
+        buffer.put(offset, (byte) value);
+        return this;
+    }
+
+    /** u32 at offset 1 */
+    public long quantity() {
+        return Integer.toUnsignedLong((int) I32.get(buffer, offset + 1));
+    }
+
+    public Order quantity(final long value) {
+        I32.set(buffer, offset + 1, (int) value);
+        return this;
+    }
+
+    /** Price at offset 5 */
+    public Price price() {
+        return price.wrap(buffer, offset + 5);
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Order{");
+        text.append("side=").append(side());
+        text.append(", quantity=").append(quantity());
+        text.append(", price=").append(price());
+        return text.append('}').toString();
+    }
+}
@@ -0,0 +1,82 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.math.BigDecimal;
+import java.math.RoundingMode;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** struct Price */
+public final class Price {
+    public static final int SIZE = 11;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int VALUE_OFFSET = 0;
+    public static final int VALUE_EXPONENT = -4;
+    public static final int CURRENCY_OFFSET = 8;
+    public static final int CURRENCY_LENGTH = 3;
+
+    private static final VarHandle I64 = MethodHandles.byteBufferViewVarHandle(long[].class, BYTE_ORDER);
+
+    private final AsciiSequence currency = new AsciiSequence();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Price wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** decimal<i64, -4>: the amount in units of the currency */
+    public long value() {
+        return (long) I64.get(buffer, offset);
+    }
+
+    public Price value(final long value) {
+        I64.set(buffer, offset, value);
+        return this;
+    }
+
+    /** value as a BigDecimal, setting it throws ArithmeticException unless it fits */
+    public BigDecimal valueBigDecimal() {
+        return BigDecimal.valueOf(value(), -VALUE_EXPONENT);
+    }
+
+    public Price valueBigDecimal(final BigDecimal value) {
+        value(value.setScale(-VALUE_EXPONENT, RoundingMode.UNNECESSARY).unscaledValue().longValueExact());
+        return this;
+    }
+
+    /** ascii[3] at offset 8 */
+    public CharSequence currency() {
+        return currency.wrap(buffer, offset + 8, CURRENCY_LENGTH, '\0');
+    }
+
+    /** Fails if the value is longer than the field or isn't ascii */
+    public Price currency(final CharSequence value) {
+        AsciiSequence.put(buffer, offset + 8, CURRENCY_LENGTH, '\0', value, "Price.currency");
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Price{");
+        text.append("value=").append(value());
+        text.append(", currency=").append(currency());
+        return text.append('}').toString();
+    }
+}
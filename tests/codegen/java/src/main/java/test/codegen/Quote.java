@@ -0,0 +1,183 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.time.Duration;
+import java.time.Instant;
+import java.time.temporal.ChronoUnit;
+import java.util.Objects;
+import java.util.concurrent.TimeUnit;
+
+/** message Quote [1] */
+public final class Quote {
+    public static final int ID = 1;
+    public static final int SIZE = 67;
+    public static final int FRAME_SIZE = 75;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int SENT_AT_OFFSET = 0;
+    public static final TimeUnit SENT_AT_UNIT = TimeUnit.NANOSECONDS;
+    public static final int SYMBOL_OFFSET = 8;
+    public static final int SYMBOL_LENGTH = 8;
+    public static final int SIDE_OFFSET = 16;
+    public static final int BID_OFFSET = 17;
+    public static final int ASK_OFFSET = 28;
+    public static final int LEVELS_OFFSET = 39;
+    public static final int LEVELS_LENGTH = 3;
+    public static final int WEIGHT_OFFSET = 51;
+    public static final int TTL_OFFSET = 59;
+    public static final TimeUnit TTL_UNIT = TimeUnit.MICROSECONDS;
+
+    private static final VarHandle F64 = MethodHandles.byteBufferViewVarHandle(double[].class, BYTE_ORDER);
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+    private static final VarHandle I64 = MethodHandles.byteBufferViewVarHandle(long[].class, BYTE_ORDER);
+
+    private final AsciiSequence symbol = new AsciiSequence();
+    private final Price bid = new Price();
+    private final Price ask = new Price();
+    private final FrameHeader frameHeader = new FrameHeader();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Quote wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    /** Writes the header's id and length at offset and points this flyweight at the message after it */
+    public Quote wrapFrame(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, FRAME_SIZE, buffer.capacity());
+        frameHeader.wrap(buffer, offset).messageId(ID).length(FRAME_SIZE);
+        return wrap(buffer, offset + FrameHeader.SIZE);
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** timestamp<ns>: when the quote was sent */
+    public long sentAt() {
+        return (long) I64.get(buffer, offset);
+    }
+
+    public Quote sentAt(final long value) {
+        I64.set(buffer, offset, value);
+        return this;
+    }
+
+    /** sent_at as an Instant */
+    public Instant sentAtInstant() {
+        return Instant.EPOCH.plus(sentAt(), ChronoUnit.NANOS);
+    }
+
+    public Quote sentAtInstant(final Instant value) {
+        sentAt(ChronoUnit.NANOS.between(Instant.EPOCH, value));
+        return this;
+    }
+
+    /** ascii[8] at offset 8 */
+    public CharSequence symbol() {
+        return symbol.wrap(buffer, offset + 8, SYMBOL_LENGTH, '\0');
+    }
+
+    /** Fails if the value is longer than the field or isn't ascii */
+    public Quote symbol(final CharSequence value) {
+        AsciiSequence.put(buffer, offset + 8, SYMBOL_LENGTH, '\0', value, "Quote.symbol");
+        return this;
+    }
+
+    /** Side at offset 16 */
+    public Side side() {
+        return Side.get(sideRaw());
+    }
+
+    public Quote side(final Side value) {
+        buffer.put(offset + 16, (byte) value.value());
+        return this;
+    }
+
+    public short sideRaw() {
+        return (short) (buffer.get(offset + 16) & 0xFF);
+    }
+
+    public Quote sideRaw(final short value) {
+        buffer.put(offset + 16, (byte) value);
+        return this;
+    }
+
+    /** Price at offset 17 */
+    public Price bid() {
+        return bid.wrap(buffer, offset + 17);
+    }
+
+    /** Price at offset 28 */
+    public Price ask() {
+        return ask.wrap(buffer, offset + 28);
+    }
+
+    /** i32[3] at offset 39 */
+    public int levels(final int index) {
+        Objects.checkIndex(index, LEVELS_LENGTH);
+        return (int) I32.get(buffer, offset + 39 + index * 4);
+    }
+
+    public Quote levels(final int index, final int value) {
+        Objects.checkIndex(index, LEVELS_LENGTH);
+        I32.set(buffer, offset + 39 + index * 4, value);
+        return this;
+    }
+
+    /** f64 at offset 51 */
+    public double weight() {
+        return (double) F64.get(buffer, offset + 51);
+    }
+
+    public Quote weight(final double value) {
+        F64.set(buffer, offset + 51, value);
+        return this;
+    }
+
+    /** duration<us>: how long the quote can be traded on, counted from sent_at */
+    public long ttl() {
+        return (long) I64.get(buffer, offset + 59);
+    }
+
+    public Quote ttl(final long value) {
+        I64.set(buffer, offset + 59, value);
+        return this;
+    }
+
+    /** ttl as a Duration */
+    public Duration ttlDuration() {
+        return Duration.of(ttl(), ChronoUnit.MICROS);
+    }
+
+    public Quote ttlDuration(final Duration value) {
+        ttl(value.dividedBy(ChronoUnit.MICROS.getDuration()));
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Quote{");
+        text.append("sentAt=").append(sentAt());
+        text.append(", symbol=").append(symbol());
+        text.append(", side=").append(side());
+        text.append(", bid=").append(bid());
+        text.append(", ask=").append(ask());
+        text.append(", levels=").append("[" + levels(0) + ", " + levels(1) + ", " + levels(2) + "]");
+        text.append(", weight=").append(weight());
+        text.append(", ttl=").append(ttl());
+        return text.append('}').toString();
+    }
+}
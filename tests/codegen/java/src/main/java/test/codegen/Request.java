@@ -0,0 +1,185 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.VarHandle;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.util.Objects;
+
+/** message Request [2] */
+public final class Request {
+    public static final int ID = 2;
+    public static final int SIZE = 48;
+    public static final int FRAME_SIZE = 56;
+    public static final ByteOrder BYTE_ORDER = ByteOrder.LITTLE_ENDIAN;
+
+    public static final int SEQUENCE_OFFSET = 0;
+    public static final int PERMISSIONS_OFFSET = 4;
+    public static final int KIND_OFFSET = 6;
+    public static final int BODY_OFFSET = 7;
+    public static final int CHECKSUM_OFFSET = 23;
+    public static final int CHECKSUM_LENGTH = 4;
+    public static final int TYPE_OFFSET = 29;
+    public static final int VENUE_OFFSET = 31;
+    public static final int VENUE_LENGTH = 6;
+
+    private static final VarHandle I16 = MethodHandles.byteBufferViewVarHandle(short[].class, BYTE_ORDER);
+    private static final VarHandle I32 = MethodHandles.byteBufferViewVarHandle(int[].class, BYTE_ORDER);
+
+    private final Body body = new Body();
+    private final AsciiSequence venue = new AsciiSequence();
+    private final FrameHeader frameHeader = new FrameHeader();
+    private ByteBuffer buffer;
+    private int offset;
+
+    /** Points this flyweight at SIZE bytes of buffer from offset, the buffer's own byte order is ignored */
+    public Request wrap(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, SIZE, buffer.capacity());
+        this.buffer = buffer;
+        this.offset = offset;
+        return this;
+    }
+
+    /** Writes the header's id and length at offset and points this flyweight at the message after it */
+    public Request wrapFrame(final ByteBuffer buffer, final int offset) {
+        Objects.checkFromIndexSize(offset, FRAME_SIZE, buffer.capacity());
+        frameHeader.wrap(buffer, offset).messageId(ID).length(FRAME_SIZE);
+        return wrap(buffer, offset + FrameHeader.SIZE);
+    }
+
+    public ByteBuffer buffer() {
+        return buffer;
+    }
+
+    public int offset() {
+        return offset;
+    }
+
+    /** u32 at offset 0 */
+    public long sequence() {
+        return Integer.toUnsignedLong((int) I32.get(buffer, offset));
+    }
+
+    public Request sequence(final long value) {
+        I32.set(buffer, offset, (int) value);
+        return this;
+    }
+
+    /** Permissions at offset 4, the flags are the constants of Permissions */
+    public int permissions() {
+        return (short) I16.get(buffer, offset + 4) & 0xFFFF;
+    }
+
+    public Request permissions(final int value) {
+        I16.set(buffer, offset + 4, (short) value);
+        return this;
+    }
+
+    /** BodyKind at offset 6 */
+    public BodyKind kind() {
+        return BodyKind.get(kindRaw());
+    }
+
+    public Request kind(final BodyKind value) {
+        buffer.put(offset + 6, (byte) value.value());
+        return this;
+    }
+
+    public short kindRaw() {
+        return (short) (buffer.get(offset + 6) & 0xFF);
+    }
+
+    public Request kindRaw(final short value) {
+        buffer.put(offset + 6, (byte) value);
+        return this;
+    }
+
+    /** Body at offset 7, the active variant is selected by kind */
+    public Body body() {
+        return body.wrap(buffer, offset + 7);
+    }
+
+    /** body().order(), throws IllegalStateException unless kind is ORDER */
+    public Order bodyOrder() {
+        if (kindRaw() != BodyKind.ORDER.value()) {
+            throw new IllegalStateException("kind is not ORDER, it is " + kind());
+        }
+        return body().order();
+    }
+
+    /** body().cancelId(), throws IllegalStateException unless kind is CANCEL */
+    public long bodyCancelId() {
+        if (kindRaw() != BodyKind.CANCEL.value()) {
+            throw new IllegalStateException("kind is not CANCEL, it is " + kind());
+        }
+        return body().cancelId();
+    }
+
+    /** body().ping(), throws IllegalStateException unless kind is PING */
+    public CharSequence bodyPing() {
+        if (kindRaw() != BodyKind.PING.value()) {
+            throw new IllegalStateException("kind is not PING, it is " + kind());
+        }
+        return body().ping();
+    }
+
+    /** byte[4] at offset 23, copied into dst from dstOffset */
+    public void getChecksum(final byte[] dst, final int dstOffset) {
+        Objects.checkFromIndexSize(dstOffset, CHECKSUM_LENGTH, dst.length);
+        for (int i = 0; i < CHECKSUM_LENGTH; i++) {
+            dst[dstOffset + i] = buffer.get(offset + 23 + i);
+        }
+    }
+
+    /** Copies the field from src starting at srcOffset */
+    public Request putChecksum(final byte[] src, final int srcOffset) {
+        Objects.checkFromIndexSize(srcOffset, CHECKSUM_LENGTH, src.length);
+        for (int i = 0; i < CHECKSUM_LENGTH; i++) {
+            buffer.put(offset + 23 + i, src[srcOffset + i]);
+        }
+        return this;
+    }
+
+    /** i16 at offset 29 */
+    public short type() {
+        return (short) I16.get(buffer, offset + 29);
+    }
+
+    public Request type(final short value) {
+        I16.set(buffer, offset + 29, value);
+        return this;
+    }
+
+    /** ascii[6] at offset 31 */
+    public CharSequence venue() {
+        return venue.wrap(buffer, offset + 31, VENUE_LENGTH, ' ');
+    }
+
+    /** Fails if the value is longer than the field or isn't ascii */
+    public Request venue(final CharSequence value) {
+        AsciiSequence.put(buffer, offset + 31, VENUE_LENGTH, ' ', value, "Request.venue");
+        return this;
+    }
+
+    @Override
+    public String toString() {
+        final StringBuilder text = new StringBuilder("Request{");
+        text.append("sequence=").append(sequence());
+        text.append(", permissions=").append(Permissions.toString(permissions()));
+        text.append(", kind=").append(kind());
+        text.append(", body=").append(body().toString(kindRaw()));
+        text.append(", checksum=").append(hex(23, CHECKSUM_LENGTH));
+        text.append(", type=").append(type());
+        text.append(", venue=").append(venue());
+        return text.append('}').toString();
+    }
+
+    private String hex(final int from, final int length) {
+        final StringBuilder text = new StringBuilder("0x");
+        for (int i = 0; i < length; i++) {
+            text.append(String.format("%02x", buffer.get(offset + from + i)));
+        }
+        return text.toString();
+    }
+}
@@ -0,0 +1,32 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+package test.codegen;
+
+/** enum Side, stored as a u8 */
+public enum Side {
+    BUY((short) 1),
+    SELL((short) 2),
+    /** Not declared in the schema, returned for any undeclared value */
+    NULL_VAL((short) 255);
+
+    private final short value;
+
+    Side(final short value) {
+        this.value = value;
+    }
+
+    /** The value stored in a message */
+    public short value() {
+        return value;
+    }
+
+    /** The constant for a stored value, NULL_VAL if it isn't declared */
+    public static Side get(final short value) {
+        if (value == 1) {
+            return BUY;
+        }
+        if (value == 2) {
+            return SELL;
+        }
+        return NULL_VAL;
+    }
+}
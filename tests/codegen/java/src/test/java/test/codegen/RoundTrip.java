@@ -0,0 +1,161 @@
+// Exercises the generated flyweights, built and run by tests/codegen.rs when a JDK is found.
+package test.codegen;
+
+import java.math.BigDecimal;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.time.Duration;
+import java.time.Instant;
+
+public final class RoundTrip {
+    private static void check(final boolean condition, final String what) {
+        if (!condition) {
+            throw new AssertionError(what);
+        }
+    }
+
+    private static void quote() {
+        // big endian buffer and a misaligned offset, neither may matter
+        final ByteBuffer buffer = ByteBuffer.allocate(Quote.SIZE + 1).order(ByteOrder.BIG_ENDIAN);
+        final Quote quote = new Quote().wrap(buffer, 1);
+        quote.sentAt(1700000000123456789L)
+            .symbol("AAPL")
+            .side(Side.SELL)
+            .levels(0, -1)
+            .levels(2, 1)
+            .weight(0.5)
+            .ttl(-250);
+        quote.bid().value(1234500).currency("USD");
+
+        check(quote.sentAt() == 1700000000123456789L, "sentAt");
+        check(Quote.SENT_AT_UNIT == java.util.concurrent.TimeUnit.NANOSECONDS, "unit");
+        check("AAPL".contentEquals(quote.symbol()), "symbol");
+        check(quote.side() == Side.SELL, "side");
+        check(quote.bid().value() == 1234500 && Price.VALUE_EXPONENT == -4, "bid");
+        check("USD".contentEquals(quote.bid().currency()), "currency");
+        check(quote.levels(0) == -1 && quote.levels(1) == 0 && quote.levels(2) == 1, "levels");
+        check(quote.weight() == 0.5, "weight");
+        check(quote.ttl() == -250, "ttl");
+        check(buffer.get(1) == 0x15, "little endian on the wire");
+        check(quote.sentAtInstant().equals(Instant.ofEpochSecond(1700000000L, 123456789L)), "instant");
+        check(quote.ttlDuration().equals(Duration.ofNanos(-250000)), "duration");
+        quote.ttlDuration(Duration.ofMillis(3));
+        check(quote.ttl() == 3000, "ttl from a duration");
+        quote.ttl(-250);
+        check(quote.bid().valueBigDecimal().equals(new BigDecimal("123.4500")), "bid as a BigDecimal");
+        quote.bid().valueBigDecimal(new BigDecimal("0.25"));
+        check(quote.bid().value() == 2500, "bid from a BigDecimal");
+        boolean rounded = true;
+        try {
+            quote.bid().valueBigDecimal(new BigDecimal("0.00001"));
+        } catch (final ArithmeticException e) {
+            rounded = false;
+        }
+        check(!rounded, "bid too precise");
+        quote.bid().value(1234500);
+
+        boolean threw = false;
+        try {
+            quote.symbol("TOOLONGXX");
+        } catch (final IllegalArgumentException e) {
+            threw = true;
+        }
+        check(threw, "symbol too long");
+        threw = false;
+        try {
+            quote.levels(3);
+        } catch (final IndexOutOfBoundsException e) {
+            threw = true;
+        }
+        check(threw, "levels index");
+
+        quote.buffer().put(quote.offset() + Quote.SIDE_OFFSET, (byte) 7);
+        check(quote.side() == Side.NULL_VAL && quote.sideRaw() == 7, "undeclared side");
+    }
+
+    private static void request() {
+        final ByteBuffer buffer = ByteBuffer.allocateDirect(Request.SIZE);
+        final Request request = new Request().wrap(buffer, 0);
+        request.sequence(0xFFFFFFFFL)
+            .permissions(Permissions.READ | Permissions.ADMIN)
+            .kind(BodyKind.CANCEL)
+            .putChecksum(new byte[] {1, 2, 3, 4}, 0)
+            .type((short) -2);
+        request.body().cancelId(99);
+
+        check(request.sequence() == 0xFFFFFFFFL, "unsigned sequence");
+        check(Permissions.has(request.permissions(), Permissions.ADMIN), "permissions");
+        check(request.kind() == BodyKind.CANCEL, "kind");
+        check(request.body().cancelId() == 99, "body");
+        check(request.bodyCancelId() == 99, "checked body");
+        boolean threw = false;
+        try {
+            request.bodyOrder();
+        } catch (final IllegalStateException e) {
+            threw = true;
+        }
+        check(threw, "inactive variant");
+        final byte[] checksum = new byte[4];
+        request.getChecksum(checksum, 0);
+        check(checksum[3] == 4, "checksum");
+        check(request.type() == -2, "type");
+        request.venue("XLON");
+        check("XLON".contentEquals(request.venue()), "venue");
+        check(buffer.get(Request.VENUE_OFFSET + 5) == ' ', "space padding");
+        check(request.toString().contains("body=cancelId=99"), request.toString());
+        check(request.toString().contains("permissions=READ | ADMIN"), request.toString());
+    }
+
+    private static final class Seen implements Handler {
+        int heartbeats;
+
+        @Override
+        public void onHeartbeat(final Heartbeat message) {
+            heartbeats++;
+        }
+    }
+
+    private static void dispatch() {
+        final ByteBuffer buffer = ByteBuffer.allocate(Heartbeat.FRAME_SIZE + Quote.FRAME_SIZE + 1);
+        new Heartbeat().wrapFrame(buffer, 0).sequence(7);
+        new Quote().wrapFrame(buffer, Heartbeat.FRAME_SIZE);
+        check(Heartbeat.FRAME_SIZE == FrameHeader.SIZE + Heartbeat.SIZE, "frame size");
+        check(buffer.get(0) == 24 && buffer.get(1) == 0 && buffer.get(2) == 3 && buffer.get(3) == 0, "header");
+
+        final Seen seen = new Seen();
+        final Dispatcher dispatcher = new Dispatcher(seen);
+        check(dispatcher.dispatch(buffer, 0) == Heartbeat.FRAME_SIZE, "heartbeat frame");
+        check(dispatcher.dispatch(buffer, Heartbeat.FRAME_SIZE) == Quote.FRAME_SIZE, "quote frame");
+        check(seen.heartbeats == 1, "only the heartbeat is handled");
+
+        boolean threw = false;
+        try {
+            dispatcher.dispatch(ByteBuffer.allocate(10).put(0, buffer.array(), 0, 10), 0);
+        } catch (final IndexOutOfBoundsException e) {
+            threw = true;
+        }
+        check(threw, "short frame");
+        buffer.put(2, (byte) 9);
+        threw = false;
+        try {
+            dispatcher.dispatch(buffer, 0);
+        } catch (final IllegalArgumentException e) {
+            threw = true;
+        }
+        check(threw, "unknown id");
+        buffer.put(2, (byte) 3).put(0, (byte) 4);
+        threw = false;
+        try {
+            dispatcher.dispatch(buffer, 0);
+        } catch (final IllegalArgumentException e) {
+            threw = true;
+        }
+        check(threw, "length too small");
+    }
+
+    public static void main(final String[] args) {
+        quote();
+        request();
+        dispatch();
+    }
+}
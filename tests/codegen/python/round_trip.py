@@ -0,0 +1,153 @@
+# Exercises the generated module, run by tests/codegen.rs when a Python interpreter is found.
+import ast
+import datetime
+import decimal
+import os
+import struct
+import sys
+
+sys.path.insert(0, os.path.dirname(os.path.abspath(__file__)))
+
+from test_codegen import (  # noqa: E402
+    Body,
+    BodyKind,
+    FrameHeader,
+    Handler,
+    Heartbeat,
+    Permissions,
+    Price,
+    Quote,
+    Request,
+    Side,
+    dispatch,
+)
+
+
+def check(condition, message=""):
+    if not condition:
+        raise AssertionError(message)
+
+
+def raises(error, f):
+    try:
+        f()
+    except error:
+        return True
+    return False
+
+
+def test_layouts_match_formats():
+    for cls in (Price, Body, Quote, Request, Heartbeat):
+        check(struct.calcsize(cls.FORMAT) == cls.SIZE, cls.__name__)
+
+
+def test_quote():
+    quote = Quote()
+    quote.sent_at = 1700000000123456789
+    quote.symbol = "AAPL"
+    check(raises(ValueError, lambda: setattr(quote, "symbol", "TOOLONGXX")))
+    quote.side = Side.SELL
+    quote.bid.value = 1234500
+    quote.bid.currency = "USD"
+    quote.levels = (-1, 0, 1)
+    quote.weight = 0.5
+    quote.ttl = -250
+
+    decoded = Quote.decode(quote.encode())
+    check(decoded.sent_at == 1700000000123456789)
+    check(decoded.symbol == "AAPL")
+    check(decoded.side is Side.SELL)
+    check(decoded.bid.value == 1234500 and Price.VALUE_EXPONENT == -4)
+    check(decoded.bid.currency == "USD")
+    check(decoded.levels == (-1, 0, 1))
+    check(decoded.weight == 0.5)
+    check(decoded.ttl == -250)
+    check(decoded.unpack()[0] == 1700000000123456789)
+    check(decoded.to_dict()["bid"] == {"value": 1234500, "currency": "USD"})
+    # little endian on the wire
+    check(quote.encode()[0] == 0x15)
+
+    utc = datetime.timezone.utc
+    check(decoded.sent_at_datetime == datetime.datetime(2023, 11, 14, 22, 13, 20, 123456, utc))
+    check(decoded.ttl_timedelta == datetime.timedelta(microseconds=-250))
+    quote.sent_at_datetime = datetime.datetime(1970, 1, 1, 0, 0, 1, tzinfo=utc)
+    quote.ttl_timedelta = datetime.timedelta(milliseconds=3)
+    check(quote.sent_at == 1000000000 and quote.ttl == 3000)
+
+    check(decoded.bid.value_decimal == decimal.Decimal("123.45"))
+    quote.bid.value_decimal = "0.25"
+    check(quote.bid.value == 2500)
+    check(raises(ValueError, lambda: setattr(quote.bid, "value_decimal", "0.00001")))
+
+    readonly = Quote.decode(bytes(Quote.SIZE))
+    check(raises(TypeError, lambda: setattr(readonly, "ttl", 1)))
+    check(raises(ValueError, lambda: Quote.decode(bytes(3))))
+
+
+def test_request():
+    buf = bytearray(Request.SIZE)
+    request = Request(buf)
+    request.sequence = 42
+    request.permissions = Permissions.READ | Permissions.ADMIN
+    request.kind = BodyKind.CANCEL
+    request.body.cancel_id = 99
+    request.checksum = b"\x01\x02\x03\x04"
+    request.type = -2
+
+    check(request.permissions == Permissions.READ | Permissions.ADMIN)
+    check(buf[Request.OFFSETS["permissions"]] == 0x81)
+    check(request.body.select(request.kind) == 99)
+    check(request.body_cancel_id == 99)
+    check(request.body_order is None)
+    check(request.to_dict()["body"] == {"cancel_id": 99})
+    check(request.checksum == b"\x01\x02\x03\x04")
+    check(raises(ValueError, lambda: setattr(request, "checksum", b"\x01")))
+    check(request.type == -2)
+    request.venue = "XLON"
+    check(request.venue == "XLON")
+    check(buf[31:37] == b"XLON  ")
+
+    request.kind_raw = 9
+    check(raises(ValueError, lambda: request.kind))
+    check(request.to_dict()["kind"] == 9)
+    check(request.to_dict()["body"] == {})
+
+
+class Seen(Handler):
+    def __init__(self):
+        self.heartbeats = []
+
+    def on_heartbeat(self, message):
+        self.heartbeats.append(message.to_dict())
+
+
+def test_dispatch():
+    frame = bytearray(Heartbeat().encode_frame())
+    check(len(frame) == Heartbeat.FRAME_SIZE == FrameHeader.SIZE + Heartbeat.SIZE)
+    check(frame[:4] == b"\x18\x00\x03\x00")
+
+    seen = Seen()
+    check(dispatch(frame + b"\xff", seen) == Heartbeat.FRAME_SIZE)
+    check(len(seen.heartbeats) == 1)
+    check(dispatch(Quote().encode_frame(), seen) == Quote.FRAME_SIZE)
+    check(len(seen.heartbeats) == 1)
+
+    check(raises(ValueError, lambda: dispatch(frame[:10], seen)))
+    frame[2] = 9
+    check(raises(ValueError, lambda: dispatch(frame, seen)))
+    frame[2] = 3
+    frame[0] = 4
+    check(raises(ValueError, lambda: dispatch(frame, seen)))
+
+
+def test_stub_parses():
+    with open(os.path.join(os.path.dirname(os.path.abspath(__file__)), "test_codegen.pyi")) as f:
+        ast.parse(f.read())
+
+
+if __name__ == "__main__":
+    test_layouts_match_formats()
+    test_quote()
+    test_request()
+    test_dispatch()
+    test_stub_parses()
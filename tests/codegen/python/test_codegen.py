@@ -0,0 +1,817 @@
+# Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+"""Protocol test.codegen"""
+
+import datetime
+import decimal
+import enum
+import struct
+
+BYTE_ORDER = "little"
+_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def _view(buf, size, name):
+    view = memoryview(buf).cast("B")
+    if len(view) < size:
+        raise ValueError(f"{name} needs {size} bytes but the buffer holds {len(view)}")
+    return view[:size]
+
+
+def _get_ascii(view, offset, length, pad):
+    raw = bytes(view[offset:offset + length])
+    end = raw.find(b"\0")
+    return (raw if end < 0 else raw[:end]).rstrip(pad).decode("ascii")
+
+
+def _set_ascii(view, offset, length, pad, value, field):
+    raw = value.encode("ascii")
+    if len(raw) > length:
+        raise ValueError(f"{field} holds at most {length} characters but {value!r} has {len(raw)}")
+    view[offset:offset + length] = raw.ljust(length, pad)
+
+
+def _set_bytes(view, offset, length, value, field):
+    if len(value) != length:
+        raise ValueError(f"{field} holds {length} bytes but {len(value)} were given")
+    view[offset:offset + length] = bytes(value)
+
+
+def _enum(cls, raw):
+    """The member of cls for raw, or raw itself when the value isn't declared"""
+    try:
+        return cls(raw)
+    except ValueError:
+        return raw
+
+
+def _plain(value):
+    return value.to_dict() if hasattr(value, "to_dict") else value
+
+
+class Side(enum.IntEnum):
+    """enum Side, stored as a u8"""
+
+    BUY = 1
+    SELL = 2
+
+
+class Permissions(enum.IntFlag):
+    """bitflags Permissions, stored as a u16"""
+
+    READ = 1
+    WRITE = 2
+    ADMIN = 128
+
+
+class BodyKind(enum.IntEnum):
+    """enum BodyKind, stored as a u8"""
+
+    ORDER = 1
+    CANCEL = 2
+    PING = 3
+
+
+class Price:
+    """struct Price"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 11
+    FORMAT = "<q3s"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "value": 0,
+        "currency": 8,
+    }
+    VALUE_EXPONENT = -4
+    _VALUE = struct.Struct("<q")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed struct when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Price")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "value": self.value,
+            "currency": self.currency,
+        }
+
+    def __repr__(self):
+        return f"Price({self.to_dict()!r})"
+
+    @property
+    def value(self):
+        """decimal<i64, -4>: the amount in units of the currency"""
+        return self._VALUE.unpack_from(self._buf, 0)[0]
+
+    @value.setter
+    def value(self, value):
+        self._VALUE.pack_into(self._buf, 0, value)
+
+    @property
+    def value_decimal(self):
+        """value as a decimal.Decimal"""
+        return decimal.Decimal(self.value).scaleb(self.VALUE_EXPONENT)
+
+    @value_decimal.setter
+    def value_decimal(self, value):
+        mantissa = decimal.Decimal(value).scaleb(-self.VALUE_EXPONENT)
+        if mantissa != mantissa.to_integral_value():
+            raise ValueError(f"Price.value only holds multiples of 1e{self.VALUE_EXPONENT}, not {value}")
+        self.value = int(mantissa)
+
+    @property
+    def currency(self):
+        """ascii[3] at offset 8"""
+        return _get_ascii(self._buf, 8, 3, b"\0")
+
+    @currency.setter
+    def currency(self, value):
+        _set_ascii(self._buf, 8, 3, b"\0", value, "Price.currency")
+
+
+class Order:
+    """struct Order"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 16
+    FORMAT = "<BIq3s"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "side": 0,
+        "quantity": 1,
+        "price": 5,
+    }
+    _SIDE = struct.Struct("<B")
+    _QUANTITY = struct.Struct("<I")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed struct when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Order")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "side": _enum(Side, self.side_raw),
+            "quantity": self.quantity,
+            "price": self.price.to_dict(),
+        }
+
+    def __repr__(self):
+        return f"Order({self.to_dict()!r})"
+
+    @property
+    def side(self):
+        """Side at offset 0, raises ValueError for an undeclared value"""
+        return Side(self._SIDE.unpack_from(self._buf, 0)[0])
+
+    @side.setter
+    def side(self, value):
+        self._SIDE.pack_into(self._buf, 0, value)
+
+    @property
+    def side_raw(self):
+        return self._SIDE.unpack_from(self._buf, 0)[0]
+
+    @side_raw.setter
+    def side_raw(self, value):
+        self._SIDE.pack_into(self._buf, 0, value)
+
+    @property
+    def quantity(self):
+        """u32 at offset 1"""
+        return self._QUANTITY.unpack_from(self._buf, 1)[0]
+
+    @quantity.setter
+    def quantity(self, value):
+        self._QUANTITY.pack_into(self._buf, 1, value)
+
+    @property
+    def price(self):
+        """Price at offset 5, a view sharing the same buffer"""
+        return Price(self._buf[5:16])
+
+    @price.setter
+    def price(self, value):
+        self._buf[5:16] = value.encode()
+
+
+class Body:
+    """union Body, the active variant is selected by a BodyKind"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 16
+    FORMAT = "<16s"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "order": 0,
+        "cancel_id": 0,
+        "ping": 0,
+    }
+    _CANCEL_ID = struct.Struct("<Q")
+    _VARIANTS = {
+        1: "order",
+        2: "cancel_id",
+        3: "ping",
+    }
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed union when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Body")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def select(self, discriminator):
+        """The variant selected by a discriminator value"""
+        name = self._VARIANTS.get(int(discriminator))
+        if name is None:
+            raise ValueError(f"Body has no variant for {discriminator!r}")
+        return getattr(self, name)
+
+    def to_dict(self, discriminator):
+        """The active variant decoded, empty when the discriminator selects none"""
+        name = self._VARIANTS.get(int(discriminator))
+        return {} if name is None else {name: _plain(getattr(self, name))}
+
+    @property
+    def order(self):
+        """Order: active when the discriminator is ORDER, a view sharing the same buffer"""
+        return Order(self._buf[0:16])
+
+    @order.setter
+    def order(self, value):
+        self._buf[0:16] = value.encode()
+
+    @property
+    def cancel_id(self):
+        """u64: active when the discriminator is CANCEL"""
+        return self._CANCEL_ID.unpack_from(self._buf, 0)[0]
+
+    @cancel_id.setter
+    def cancel_id(self, value):
+        self._CANCEL_ID.pack_into(self._buf, 0, value)
+
+    @property
+    def ping(self):
+        """ascii[4]: active when the discriminator is PING"""
+        return _get_ascii(self._buf, 0, 4, b"\0")
+
+    @ping.setter
+    def ping(self, value):
+        _set_ascii(self._buf, 0, 4, b"\0", value, "Body.ping")
+
+
+class FrameHeader:
+    """struct FrameHeader"""
+
+    __slots__ = ("_buf",)
+
+    SIZE = 8
+    FORMAT = "<HHI"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "length": 0,
+        "message_id": 2,
+        "sequence": 4,
+    }
+    _LENGTH = struct.Struct("<H")
+    _MESSAGE_ID = struct.Struct("<H")
+    _SEQUENCE = struct.Struct("<I")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed struct when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "FrameHeader")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "length": self.length,
+            "message_id": self.message_id,
+            "sequence": self.sequence,
+        }
+
+    def __repr__(self):
+        return f"FrameHeader({self.to_dict()!r})"
+
+    @property
+    def length(self):
+        """u16 at offset 0"""
+        return self._LENGTH.unpack_from(self._buf, 0)[0]
+
+    @length.setter
+    def length(self, value):
+        self._LENGTH.pack_into(self._buf, 0, value)
+
+    @property
+    def message_id(self):
+        """u16 at offset 2"""
+        return self._MESSAGE_ID.unpack_from(self._buf, 2)[0]
+
+    @message_id.setter
+    def message_id(self, value):
+        self._MESSAGE_ID.pack_into(self._buf, 2, value)
+
+    @property
+    def sequence(self):
+        """u32 at offset 4"""
+        return self._SEQUENCE.unpack_from(self._buf, 4)[0]
+
+    @sequence.setter
+    def sequence(self, value):
+        self._SEQUENCE.pack_into(self._buf, 4, value)
+
+
+class Quote:
+    """message Quote [1]"""
+
+    __slots__ = ("_buf",)
+
+    ID = 1
+    SIZE = 67
+    FRAME_SIZE = 75
+    FORMAT = "<Q8sBq3sq3s3idq"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "sent_at": 0,
+        "symbol": 8,
+        "side": 16,
+        "bid": 17,
+        "ask": 28,
+        "levels": 39,
+        "weight": 51,
+        "ttl": 59,
+    }
+    _SENT_AT = struct.Struct("<Q")
+    _SIDE = struct.Struct("<B")
+    _LEVELS = struct.Struct("<3i")
+    _WEIGHT = struct.Struct("<d")
+    _TTL = struct.Struct("<q")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed message when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Quote")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def encode_frame(self):
+        """The header followed by the message"""
+        header = FrameHeader()
+        header.message_id = self.ID
+        header.length = self.FRAME_SIZE
+        return header.encode() + self.encode()
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "sent_at": self.sent_at,
+            "symbol": self.symbol,
+            "side": _enum(Side, self.side_raw),
+            "bid": self.bid.to_dict(),
+            "ask": self.ask.to_dict(),
+            "levels": self.levels,
+            "weight": self.weight,
+            "ttl": self.ttl,
+        }
+
+    def __repr__(self):
+        return f"Quote({self.to_dict()!r})"
+
+    @property
+    def sent_at(self):
+        """timestamp<ns>: when the quote was sent"""
+        return self._SENT_AT.unpack_from(self._buf, 0)[0]
+
+    @sent_at.setter
+    def sent_at(self, value):
+        self._SENT_AT.pack_into(self._buf, 0, value)
+
+    @property
+    def sent_at_datetime(self):
+        """sent_at as a datetime.datetime"""
+        return _EPOCH + datetime.timedelta(microseconds=self.sent_at // 1000)
+
+    @sent_at_datetime.setter
+    def sent_at_datetime(self, value):
+        self.sent_at = (value - _EPOCH) // datetime.timedelta(microseconds=1) * 1000
+
+    @property
+    def symbol(self):
+        """ascii[8] at offset 8"""
+        return _get_ascii(self._buf, 8, 8, b"\0")
+
+    @symbol.setter
+    def symbol(self, value):
+        _set_ascii(self._buf, 8, 8, b"\0", value, "Quote.symbol")
+
+    @property
+    def side(self):
+        """Side at offset 16, raises ValueError for an undeclared value"""
+        return Side(self._SIDE.unpack_from(self._buf, 16)[0])
+
+    @side.setter
+    def side(self, value):
+        self._SIDE.pack_into(self._buf, 16, value)
+
+    @property
+    def side_raw(self):
+        return self._SIDE.unpack_from(self._buf, 16)[0]
+
+    @side_raw.setter
+    def side_raw(self, value):
+        self._SIDE.pack_into(self._buf, 16, value)
+
+    @property
+    def bid(self):
+        """Price at offset 17, a view sharing the same buffer"""
+        return Price(self._buf[17:28])
+
+    @bid.setter
+    def bid(self, value):
+        self._buf[17:28] = value.encode()
+
+    @property
+    def ask(self):
+        """Price at offset 28, a view sharing the same buffer"""
+        return Price(self._buf[28:39])
+
+    @ask.setter
+    def ask(self, value):
+        self._buf[28:39] = value.encode()
+
+    @property
+    def levels(self):
+        """i32[3] at offset 39"""
+        return self._LEVELS.unpack_from(self._buf, 39)
+
+    @levels.setter
+    def levels(self, value):
+        self._LEVELS.pack_into(self._buf, 39, *value)
+
+    @property
+    def weight(self):
+        """f64 at offset 51"""
+        return self._WEIGHT.unpack_from(self._buf, 51)[0]
+
+    @weight.setter
+    def weight(self, value):
+        self._WEIGHT.pack_into(self._buf, 51, value)
+
+    @property
+    def ttl(self):
+        """duration<us>: how long the quote can be traded on, counted from sent_at"""
+        return self._TTL.unpack_from(self._buf, 59)[0]
+
+    @ttl.setter
+    def ttl(self, value):
+        self._TTL.pack_into(self._buf, 59, value)
+
+    @property
+    def ttl_timedelta(self):
+        """ttl as a datetime.timedelta"""
+        return datetime.timedelta(microseconds=self.ttl)
+
+    @ttl_timedelta.setter
+    def ttl_timedelta(self, value):
+        self.ttl = value // datetime.timedelta(microseconds=1)
+
+
+class Request:
+    """message Request [2]"""
+
+    __slots__ = ("_buf",)
+
+    ID = 2
+    SIZE = 48
+    FRAME_SIZE = 56
+    FORMAT = "<IHB16s4s2xh6s11x"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "sequence": 0,
+        "permissions": 4,
+        "kind": 6,
+        "body": 7,
+        "checksum": 23,
+        "type": 29,
+        "venue": 31,
+    }
+    _SEQUENCE = struct.Struct("<I")
+    _PERMISSIONS = struct.Struct("<H")
+    _KIND = struct.Struct("<B")
+    _TYPE = struct.Struct("<h")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed message when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Request")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def encode_frame(self):
+        """The header followed by the message"""
+        header = FrameHeader()
+        header.message_id = self.ID
+        header.length = self.FRAME_SIZE
+        return header.encode() + self.encode()
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "sequence": self.sequence,
+            "permissions": _enum(Permissions, self.permissions_raw),
+            "kind": _enum(BodyKind, self.kind_raw),
+            "body": self.body.to_dict(self.kind_raw),
+            "checksum": self.checksum,
+            "type": self.type,
+            "venue": self.venue,
+        }
+
+    def __repr__(self):
+        return f"Request({self.to_dict()!r})"
+
+    @property
+    def sequence(self):
+        """u32 at offset 0"""
+        return self._SEQUENCE.unpack_from(self._buf, 0)[0]
+
+    @sequence.setter
+    def sequence(self, value):
+        self._SEQUENCE.pack_into(self._buf, 0, value)
+
+    @property
+    def permissions(self):
+        """Permissions at offset 4"""
+        return Permissions(self._PERMISSIONS.unpack_from(self._buf, 4)[0])
+
+    @permissions.setter
+    def permissions(self, value):
+        self._PERMISSIONS.pack_into(self._buf, 4, value)
+
+    @property
+    def permissions_raw(self):
+        return self._PERMISSIONS.unpack_from(self._buf, 4)[0]
+
+    @permissions_raw.setter
+    def permissions_raw(self, value):
+        self._PERMISSIONS.pack_into(self._buf, 4, value)
+
+    @property
+    def kind(self):
+        """BodyKind at offset 6, raises ValueError for an undeclared value"""
+        return BodyKind(self._KIND.unpack_from(self._buf, 6)[0])
+
+    @kind.setter
+    def kind(self, value):
+        self._KIND.pack_into(self._buf, 6, value)
+
+    @property
+    def kind_raw(self):
+        return self._KIND.unpack_from(self._buf, 6)[0]
+
+    @kind_raw.setter
+    def kind_raw(self, value):
+        self._KIND.pack_into(self._buf, 6, value)
+
+    @property
+    def body(self):
+        """Body: the order or cancel the request is for, the active variant is selected by kind"""
+        return Body(self._buf[7:23])
+
+    @body.setter
+    def body(self, value):
+        self._buf[7:23] = value.encode()
+
+    @property
+    def body_order(self):
+        """body.order, None unless kind is ORDER"""
+        return self.body.order if self.kind_raw == 1 else None
+
+    @property
+    def body_cancel_id(self):
+        """body.cancel_id, None unless kind is CANCEL"""
+        return self.body.cancel_id if self.kind_raw == 2 else None
+
+    @property
+    def body_ping(self):
+        """body.ping, None unless kind is PING"""
+        return self.body.ping if self.kind_raw == 3 else None
+
+    @property
+    def checksum(self):
+        """byte[4] at offset 23"""
+        return bytes(self._buf[23:27])
+
+    @checksum.setter
+    def checksum(self, value):
+        _set_bytes(self._buf, 23, 4, value, "Request.checksum")
+
+    @property
+    def type(self):
+        """i16 at offset 29"""
+        return self._TYPE.unpack_from(self._buf, 29)[0]
+
+    @type.setter
+    def type(self, value):
+        self._TYPE.pack_into(self._buf, 29, value)
+
+    @property
+    def venue(self):
+        """ascii[6] at offset 31"""
+        return _get_ascii(self._buf, 31, 6, b" ")
+
+    @venue.setter
+    def venue(self, value):
+        _set_ascii(self._buf, 31, 6, b" ", value, "Request.venue")
+
+
+class Heartbeat:
+    """message Heartbeat [3]"""
+
+    __slots__ = ("_buf",)
+
+    ID = 3
+    SIZE = 16
+    FRAME_SIZE = 24
+    FORMAT = "<QIB3x"
+    _STRUCT = struct.Struct(FORMAT)
+    OFFSETS = {
+        "sent_at": 0,
+        "sequence": 8,
+        "side": 12,
+    }
+    _SENT_AT = struct.Struct("<Q")
+    _SEQUENCE = struct.Struct("<I")
+    _SIDE = struct.Struct("<B")
+
+    def __init__(self, buf=None):
+        """Wraps the first SIZE bytes of buf without copying them, a zeroed message when buf is None"""
+        self._buf = _view(bytearray(self.SIZE) if buf is None else buf, self.SIZE, "Heartbeat")
+
+    @classmethod
+    def decode(cls, buf):
+        """Reads from buf without copying it, each field is only decoded when it's accessed"""
+        return cls(buf)
+
+    def encode(self):
+        return bytes(self._buf)
+
+    def encode_frame(self):
+        """The header followed by the message"""
+        header = FrameHeader()
+        header.message_id = self.ID
+        header.length = self.FRAME_SIZE
+        return header.encode() + self.encode()
+
+    def unpack(self):
+        """Every raw value at once, in the order of FORMAT"""
+        return self._STRUCT.unpack_from(self._buf)
+
+    def to_dict(self):
+        """Every field decoded, nested structs become dicts too"""
+        return {
+            "sent_at": self.sent_at,
+            "sequence": self.sequence,
+            "side": _enum(Side, self.side_raw),
+        }
+
+    def __repr__(self):
+        return f"Heartbeat({self.to_dict()!r})"
+
+    @property
+    def sent_at(self):
+        """u64 at offset 0"""
+        return self._SENT_AT.unpack_from(self._buf, 0)[0]
+
+    @sent_at.setter
+    def sent_at(self, value):
+        self._SENT_AT.pack_into(self._buf, 0, value)
+
+    @property
+    def sequence(self):
+        """u32 at offset 8"""
+        return self._SEQUENCE.unpack_from(self._buf, 8)[0]
+
+    @sequence.setter
+    def sequence(self, value):
+        self._SEQUENCE.pack_into(self._buf, 8, value)
+
+    @property
+    def side(self):
+        """Side at offset 12, raises ValueError for an undeclared value"""
+        return Side(self._SIDE.unpack_from(self._buf, 12)[0])
+
+    @side.setter
+    def side(self, value):
+        self._SIDE.pack_into(self._buf, 12, value)
+
+    @property
+    def side_raw(self):
+        return self._SIDE.unpack_from(self._buf, 12)[0]
+
+    @side_raw.setter
+    def side_raw(self, value):
+        self._SIDE.pack_into(self._buf, 12, value)
+
+
+class Handler:
+    """Callbacks for dispatch, a message without an override is ignored"""
+
+    def on_quote(self, message):
+        """Called with a Quote"""
+
+    def on_request(self, message):
+        """Called with a Request"""
+
+    def on_heartbeat(self, message):
+        """Called with a Heartbeat"""
+
+
+_HANDLERS = {
+    1: (Quote, "on_quote"),
+    2: (Request, "on_request"),
+    3: (Heartbeat, "on_heartbeat"),
+}
+
+
+def dispatch(buf, handler):
+    """Reads the header at the start of buf and calls the handler's callback for its id with the
+    message after it, returns the size of the frame. Raises ValueError when buf doesn't hold the
+    whole frame, its length is too small for its message or no message has its id."""
+    header = FrameHeader(buf)
+    entry = _HANDLERS.get(header.message_id)
+    if entry is None:
+        raise ValueError(f"no message has the id {header.message_id}")
+    cls, callback = entry
+    frame = header.length
+    if frame < cls.FRAME_SIZE:
+        raise ValueError(f"a frame length of {frame} is too small for {cls.__name__}")
+    view = _view(buf, frame, "the frame")
+    getattr(handler, callback)(cls(view[FrameHeader.SIZE:]))
+    return frame
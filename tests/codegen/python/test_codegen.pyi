@@ -0,0 +1,272 @@
+# Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+import datetime
+import decimal
+import enum
+import typing
+
+Buffer = typing.Union[bytes, bytearray, memoryview]
+
+BYTE_ORDER: str
+
+class Side(enum.IntEnum):
+    BUY = 1
+    SELL = 2
+
+class Permissions(enum.IntFlag):
+    READ = 1
+    WRITE = 2
+    ADMIN = 128
+
+class BodyKind(enum.IntEnum):
+    ORDER = 1
+    CANCEL = 2
+    PING = 3
+
+class Price:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    VALUE_EXPONENT: typing.ClassVar[int]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Price: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def value(self) -> int: ...
+    @value.setter
+    def value(self, value: int) -> None: ...
+    @property
+    def value_decimal(self) -> decimal.Decimal: ...
+    @value_decimal.setter
+    def value_decimal(self, value: typing.Union[decimal.Decimal, int, str]) -> None: ...
+    @property
+    def currency(self) -> str: ...
+    @currency.setter
+    def currency(self, value: str) -> None: ...
+
+class Order:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Order: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def side(self) -> Side: ...
+    @side.setter
+    def side(self, value: Side) -> None: ...
+    @property
+    def side_raw(self) -> int: ...
+    @side_raw.setter
+    def side_raw(self, value: int) -> None: ...
+    @property
+    def quantity(self) -> int: ...
+    @quantity.setter
+    def quantity(self, value: int) -> None: ...
+    @property
+    def price(self) -> Price: ...
+    @price.setter
+    def price(self, value: Price) -> None: ...
+
+class Body:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Body: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def select(self, discriminator: int) -> typing.Union[Order, int, str]: ...
+    def to_dict(self, discriminator: int) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def order(self) -> Order: ...
+    @order.setter
+    def order(self, value: Order) -> None: ...
+    @property
+    def cancel_id(self) -> int: ...
+    @cancel_id.setter
+    def cancel_id(self, value: int) -> None: ...
+    @property
+    def ping(self) -> str: ...
+    @ping.setter
+    def ping(self, value: str) -> None: ...
+
+class FrameHeader:
+    SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> FrameHeader: ...
+    def encode(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def length(self) -> int: ...
+    @length.setter
+    def length(self, value: int) -> None: ...
+    @property
+    def message_id(self) -> int: ...
+    @message_id.setter
+    def message_id(self, value: int) -> None: ...
+    @property
+    def sequence(self) -> int: ...
+    @sequence.setter
+    def sequence(self, value: int) -> None: ...
+
+class Quote:
+    ID: typing.ClassVar[int]
+    SIZE: typing.ClassVar[int]
+    FRAME_SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Quote: ...
+    def encode(self) -> bytes: ...
+    def encode_frame(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def sent_at(self) -> int: ...
+    @sent_at.setter
+    def sent_at(self, value: int) -> None: ...
+    @property
+    def sent_at_datetime(self) -> datetime.datetime: ...
+    @sent_at_datetime.setter
+    def sent_at_datetime(self, value: datetime.datetime) -> None: ...
+    @property
+    def symbol(self) -> str: ...
+    @symbol.setter
+    def symbol(self, value: str) -> None: ...
+    @property
+    def side(self) -> Side: ...
+    @side.setter
+    def side(self, value: Side) -> None: ...
+    @property
+    def side_raw(self) -> int: ...
+    @side_raw.setter
+    def side_raw(self, value: int) -> None: ...
+    @property
+    def bid(self) -> Price: ...
+    @bid.setter
+    def bid(self, value: Price) -> None: ...
+    @property
+    def ask(self) -> Price: ...
+    @ask.setter
+    def ask(self, value: Price) -> None: ...
+    @property
+    def levels(self) -> typing.Tuple[int, ...]: ...
+    @levels.setter
+    def levels(self, value: typing.Sequence[int]) -> None: ...
+    @property
+    def weight(self) -> float: ...
+    @weight.setter
+    def weight(self, value: float) -> None: ...
+    @property
+    def ttl(self) -> int: ...
+    @ttl.setter
+    def ttl(self, value: int) -> None: ...
+    @property
+    def ttl_timedelta(self) -> datetime.timedelta: ...
+    @ttl_timedelta.setter
+    def ttl_timedelta(self, value: datetime.timedelta) -> None: ...
+
+class Request:
+    ID: typing.ClassVar[int]
+    SIZE: typing.ClassVar[int]
+    FRAME_SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Request: ...
+    def encode(self) -> bytes: ...
+    def encode_frame(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def sequence(self) -> int: ...
+    @sequence.setter
+    def sequence(self, value: int) -> None: ...
+    @property
+    def permissions(self) -> Permissions: ...
+    @permissions.setter
+    def permissions(self, value: Permissions) -> None: ...
+    @property
+    def permissions_raw(self) -> int: ...
+    @permissions_raw.setter
+    def permissions_raw(self, value: int) -> None: ...
+    @property
+    def kind(self) -> BodyKind: ...
+    @kind.setter
+    def kind(self, value: BodyKind) -> None: ...
+    @property
+    def kind_raw(self) -> int: ...
+    @kind_raw.setter
+    def kind_raw(self, value: int) -> None: ...
+    @property
+    def body(self) -> Body: ...
+    @body.setter
+    def body(self, value: Body) -> None: ...
+    @property
+    def body_order(self) -> typing.Optional[Order]: ...
+    @property
+    def body_cancel_id(self) -> typing.Optional[int]: ...
+    @property
+    def body_ping(self) -> typing.Optional[str]: ...
+    @property
+    def checksum(self) -> bytes: ...
+    @checksum.setter
+    def checksum(self, value: Buffer) -> None: ...
+    @property
+    def type(self) -> int: ...
+    @type.setter
+    def type(self, value: int) -> None: ...
+    @property
+    def venue(self) -> str: ...
+    @venue.setter
+    def venue(self, value: str) -> None: ...
+
+class Heartbeat:
+    ID: typing.ClassVar[int]
+    SIZE: typing.ClassVar[int]
+    FRAME_SIZE: typing.ClassVar[int]
+    FORMAT: typing.ClassVar[str]
+    OFFSETS: typing.ClassVar[typing.Dict[str, int]]
+    def __init__(self, buf: typing.Optional[Buffer] = ...) -> None: ...
+    @classmethod
+    def decode(cls, buf: Buffer) -> Heartbeat: ...
+    def encode(self) -> bytes: ...
+    def encode_frame(self) -> bytes: ...
+    def unpack(self) -> typing.Tuple[typing.Any, ...]: ...
+    def to_dict(self) -> typing.Dict[str, typing.Any]: ...
+    @property
+    def sent_at(self) -> int: ...
+    @sent_at.setter
+    def sent_at(self, value: int) -> None: ...
+    @property
+    def sequence(self) -> int: ...
+    @sequence.setter
+    def sequence(self, value: int) -> None: ...
+    @property
+    def side(self) -> Side: ...
+    @side.setter
+    def side(self, value: Side) -> None: ...
+    @property
+    def side_raw(self) -> int: ...
+    @side_raw.setter
+    def side_raw(self, value: int) -> None: ...
+
+class Handler:
+    def on_quote(self, message: Quote) -> None: ...
+    def on_request(self, message: Request) -> None: ...
+    def on_heartbeat(self, message: Heartbeat) -> None: ...
+
+def dispatch(buf: Buffer, handler: Handler) -> int: ...
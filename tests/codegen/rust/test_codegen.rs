@@ -0,0 +1,1445 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+//
+// Only `core` is used so this module can be part of a `#![no_std]` crate.
+
+use core::fmt;
+
+/// Errors from reading or writing a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is shorter than the message or struct
+    BufferTooShort { required: usize, actual: usize },
+    /// A field holds a value which isn't declared by its enum or union
+    InvalidEnumValue { name: &'static str, value: i128 },
+    /// An ascii field holds a byte outside of the ascii range
+    InvalidAscii { field: &'static str },
+    /// A value is longer than the field it is written to
+    ValueTooLong { field: &'static str, max: usize, actual: usize },
+    /// The header of a frame holds an id no message has
+    UnknownMessageId { id: u64 },
+    /// The header of a frame gives a length too small for the frame
+    InvalidLength { length: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BufferTooShort { required, actual } => {
+                write!(f, "buffer of {} bytes is shorter than {} bytes", actual, required)
+            }
+            Error::InvalidEnumValue { name, value } => {
+                write!(f, "{} is not a value of '{}'", value, name)
+            }
+            Error::InvalidAscii { field } => write!(f, "'{}' is not ascii", field),
+            Error::ValueTooLong { field, max, actual } => write!(
+                f,
+                "{} bytes is too long for '{}' which holds {} bytes",
+                actual, field, max
+            ),
+            Error::UnknownMessageId { id } => write!(f, "no message has the id {}", id),
+            Error::InvalidLength { length } => {
+                write!(f, "a frame length of {} is too small", length)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[inline]
+fn check_size(buf: &[u8], required: usize) -> Result<(), Error> {
+    if buf.len() < required {
+        return Err(Error::BufferTooShort {
+            required,
+            actual: buf.len(),
+        });
+    }
+    Ok(())
+}
+
+#[inline]
+#[allow(dead_code)]
+fn get<const N: usize>(buf: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&buf[offset..offset + N]);
+    bytes
+}
+
+#[inline]
+#[allow(dead_code)]
+fn put<const N: usize>(buf: &mut [u8], offset: usize, bytes: [u8; N]) {
+    buf[offset..offset + N].copy_from_slice(&bytes);
+}
+
+#[inline]
+#[allow(dead_code)]
+fn bytes<const N: usize>(buf: &[u8], offset: usize) -> &[u8; N] {
+    buf[offset..offset + N].try_into().unwrap()
+}
+
+/// The text of an ascii field up to the first nul byte, without the `pad` bytes ending it
+#[allow(dead_code)]
+fn ascii_str<'a>(bytes: &'a [u8], pad: u8, field: &'static str) -> Result<&'a str, Error> {
+    let mut end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    while end > 0 && bytes[end - 1] == pad {
+        end -= 1;
+    }
+    match core::str::from_utf8(&bytes[..end]) {
+        Ok(text) if text.is_ascii() => Ok(text),
+        _ => Err(Error::InvalidAscii { field }),
+    }
+}
+
+/// Writes `value` to an ascii field, the rest of the field is filled with `pad`
+#[allow(dead_code)]
+fn put_ascii(buf: &mut [u8], value: &str, pad: u8, field: &'static str) -> Result<(), Error> {
+    if !value.is_ascii() {
+        return Err(Error::InvalidAscii { field });
+    }
+    if value.len() > buf.len() {
+        return Err(Error::ValueTooLong {
+            field,
+            max: buf.len(),
+            actual: value.len(),
+        });
+    }
+    buf[..value.len()].copy_from_slice(value.as_bytes());
+    buf[value.len()..].fill(pad);
+    Ok(())
+}
+
+/// Shows a value which couldn't be decoded as its raw form
+#[allow(dead_code)]
+struct Decoded<T, R>(Result<T, R>);
+
+impl<T: fmt::Debug, R: fmt::Debug> fmt::Debug for Decoded<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(value) => value.fmt(f),
+            Err(raw) => write!(f, "{:?} (invalid)", raw),
+        }
+    }
+}
+
+/// Shows a decimal's mantissa scaled by its exponent
+#[allow(dead_code)]
+struct Decimal(i128, i8);
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Decimal(mantissa, exponent) = *self;
+        if exponent >= 0 || mantissa == 0 {
+            write!(f, "{}", mantissa)?;
+            for _ in 0..exponent.max(0) {
+                f.write_str("0")?;
+            }
+            return Ok(());
+        }
+        let scale = 10u128.pow(exponent.unsigned_abs() as u32);
+        let sign = if mantissa < 0 { "-" } else { "" };
+        let abs = mantissa.unsigned_abs();
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale,
+            abs % scale,
+            width = exponent.unsigned_abs() as usize
+        )
+    }
+}
+
+/// The qualified name of the protocol
+pub const PROTOCOL: &str = "test.codegen";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Side {
+    Buy = 1,
+    Sell = 2,
+}
+
+impl Side {
+    /// Every declared value
+    pub const ALL: [Side; 2] = [Side::Buy, Side::Sell];
+
+    /// The name of the value in the schema
+    pub const fn name(self) -> &'static str {
+        match self {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        }
+    }
+}
+
+impl TryFrom<u8> for Side {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Side::Buy),
+            2 => Ok(Side::Sell),
+            _ => Err(Error::InvalidEnumValue { name: "Side", value: value as i128 }),
+        }
+    }
+}
+
+impl From<Side> for u8 {
+    fn from(value: Side) -> u8 {
+        value as u8
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Permissions(u16);
+
+impl Permissions {
+    pub const READ: Permissions = Permissions(1);
+    pub const WRITE: Permissions = Permissions(2);
+    pub const ADMIN: Permissions = Permissions(128);
+    /// Every declared flag
+    pub const ALL: Permissions = Permissions(131);
+
+    const NAMES: [(Permissions, &'static str); 3] = [
+        (Self::READ, "READ"),
+        (Self::WRITE, "WRITE"),
+        (Self::ADMIN, "ADMIN"),
+    ];
+
+    pub const fn empty() -> Self {
+        Permissions(0)
+    }
+
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Keeps any bits which aren't declared flags
+    pub const fn from_bits_retain(bits: u16) -> Self {
+        Permissions(bits)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl TryFrom<u16> for Permissions {
+    type Error = Error;
+
+    /// Fails if any bit isn't a declared flag
+    fn try_from(bits: u16) -> Result<Self, Error> {
+        if bits & !Self::ALL.0 != 0 {
+            return Err(Error::InvalidEnumValue { name: "Permissions", value: bits as i128 });
+        }
+        Ok(Permissions(bits))
+    }
+}
+
+impl From<Permissions> for u16 {
+    fn from(value: Permissions) -> u16 {
+        value.0
+    }
+}
+
+impl core::ops::BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Permissions(self.0 | other.0)
+    }
+}
+
+impl core::ops::BitOrAssign for Permissions {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl core::ops::BitAnd for Permissions {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Permissions(self.0 & other.0)
+    }
+}
+
+impl core::ops::BitAndAssign for Permissions {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0 &= other.0;
+    }
+}
+
+impl core::ops::BitXor for Permissions {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        Permissions(self.0 ^ other.0)
+    }
+}
+
+impl core::ops::BitXorAssign for Permissions {
+    fn bitxor_assign(&mut self, other: Self) {
+        self.0 ^= other.0;
+    }
+}
+
+impl core::ops::Not for Permissions {
+    type Output = Self;
+
+    /// Only the declared flags are inverted
+    fn not(self) -> Self {
+        Permissions(!self.0 & Self::ALL.0)
+    }
+}
+
+impl fmt::Display for Permissions {
+    /// The names of the set flags separated by ` | `, undeclared bits are shown in hex
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.0;
+        let mut first = true;
+        for (flag, name) in Self::NAMES {
+            if flag.0 != 0 && remaining & flag.0 == flag.0 {
+                f.write_str(if first { "" } else { " | " })?;
+                f.write_str(name)?;
+                remaining &= !flag.0;
+                first = false;
+            }
+        }
+        if remaining != 0 || first {
+            f.write_str(if first { "" } else { " | " })?;
+            write!(f, "{:#x}", remaining)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Permissions({})", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum BodyKind {
+    Order = 1,
+    Cancel = 2,
+    Ping = 3,
+}
+
+impl BodyKind {
+    /// Every declared value
+    pub const ALL: [BodyKind; 3] = [BodyKind::Order, BodyKind::Cancel, BodyKind::Ping];
+
+    /// The name of the value in the schema
+    pub const fn name(self) -> &'static str {
+        match self {
+            BodyKind::Order => "ORDER",
+            BodyKind::Cancel => "CANCEL",
+            BodyKind::Ping => "PING",
+        }
+    }
+}
+
+impl TryFrom<u8> for BodyKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            1 => Ok(BodyKind::Order),
+            2 => Ok(BodyKind::Cancel),
+            3 => Ok(BodyKind::Ping),
+            _ => Err(Error::InvalidEnumValue { name: "BodyKind", value: value as i128 }),
+        }
+    }
+}
+
+impl From<BodyKind> for u8 {
+    fn from(value: BodyKind) -> u8 {
+        value as u8
+    }
+}
+
+impl fmt::Display for BodyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The active variant of the union `Body`, selected by a `BodyKind`
+#[derive(Debug, Clone, Copy)]
+pub enum BodyView<'a> {
+    Order(OrderView<'a>),
+    CancelId(u64),
+    Ping(&'a [u8; 4]),
+}
+
+/// The struct `Price`, read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct PriceView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> PriceView<'a> {
+    pub const SIZE: usize = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    pub const VALUE_EXPONENT: i8 = -4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(PriceView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// the amount in units of the currency (offset 0, 8 bytes)
+    pub fn value(&self) -> i64 {
+        i64::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn currency(&self) -> &'a [u8; 3] {
+        bytes(self.buf, 8)
+    }
+
+    /// The text of `currency` up to the first nul byte
+    pub fn currency_str(&self) -> Result<&'a str, Error> {
+        ascii_str(self.currency(), 0, "currency")
+    }
+}
+
+impl fmt::Debug for PriceView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Price")
+            .field("value", &Decimal(self.value() as i128, Self::VALUE_EXPONENT))
+            .field("currency", &Decoded(self.currency_str().map_err(|_| self.currency())))
+            .finish()
+    }
+}
+
+impl fmt::Display for PriceView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The struct `Price`, written in place to a byte slice
+pub struct PriceMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> PriceMut<'a> {
+    pub const SIZE: usize = 11;
+    /// `value` holds the mantissa of a decimal, its value is `value() * 10^-4`
+    pub const VALUE_EXPONENT: i8 = -4;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(PriceMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> PriceView<'_> {
+        PriceView { buf: self.buf }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// the amount in units of the currency (offset 0, 8 bytes)
+    pub fn value(&self) -> i64 {
+        self.view().value()
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn currency(&self) -> &'_ [u8; 3] {
+        self.view().currency()
+    }
+
+    /// The text of `currency` up to the first nul byte
+    pub fn currency_str(&self) -> Result<&'_ str, Error> {
+        self.view().currency_str()
+    }
+
+    /// the amount in units of the currency (offset 0, 8 bytes)
+    pub fn set_value(&mut self, value: i64) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `ascii[3]`, offset 8, 3 bytes
+    pub fn set_currency(&mut self, value: &str) -> Result<(), Error> {
+        put_ascii(&mut self.buf[8..11], value, 0, "currency")
+    }
+}
+
+impl fmt::Debug for PriceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for PriceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The struct `Order`, read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct OrderView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> OrderView<'a> {
+    pub const SIZE: usize = 16;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(OrderView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `Side`, offset 0, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        Side::try_from(self.side_raw())
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        u8::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `u32`, offset 1, 4 bytes
+    pub fn quantity(&self) -> u32 {
+        u32::from_le_bytes(get(self.buf, 1))
+    }
+
+    /// `Price`, offset 5, 11 bytes
+    pub fn price(&self) -> PriceView<'a> {
+        PriceView { buf: &self.buf[5..16] }
+    }
+}
+
+impl fmt::Debug for OrderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Order")
+            .field("side", &Decoded(self.side().map_err(|_| self.side_raw())))
+            .field("quantity", &self.quantity())
+            .field("price", &self.price())
+            .finish()
+    }
+}
+
+impl fmt::Display for OrderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The struct `Order`, written in place to a byte slice
+pub struct OrderMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> OrderMut<'a> {
+    pub const SIZE: usize = 16;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(OrderMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> OrderView<'_> {
+        OrderView { buf: self.buf }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `Side`, offset 0, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        self.view().side()
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        self.view().side_raw()
+    }
+
+    /// `u32`, offset 1, 4 bytes
+    pub fn quantity(&self) -> u32 {
+        self.view().quantity()
+    }
+
+    /// `Price`, offset 5, 11 bytes
+    pub fn price(&self) -> PriceView<'_> {
+        self.view().price()
+    }
+
+    /// Stores any value in `side`, even one which isn't declared
+    pub fn set_side_raw(&mut self, value: u8) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `Side`, offset 0, 1 bytes
+    pub fn set_side(&mut self, value: Side) {
+        put(self.buf, 0, u8::from(value).to_le_bytes());
+    }
+
+    /// `u32`, offset 1, 4 bytes
+    pub fn set_quantity(&mut self, value: u32) {
+        put(self.buf, 1, value.to_le_bytes());
+    }
+
+    /// `Price`, offset 5, 11 bytes
+    pub fn price_mut(&mut self) -> PriceMut<'_> {
+        PriceMut { buf: &mut self.buf[5..16] }
+    }
+}
+
+impl fmt::Debug for OrderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for OrderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The header in front of every message, read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct FrameHeaderView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> FrameHeaderView<'a> {
+    pub const SIZE: usize = 8;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(FrameHeaderView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn length(&self) -> u16 {
+        u16::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn message_id(&self) -> u16 {
+        u16::from_le_bytes(get(self.buf, 2))
+    }
+
+    /// `u32`, offset 4, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        u32::from_le_bytes(get(self.buf, 4))
+    }
+}
+
+impl fmt::Debug for FrameHeaderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameHeader")
+            .field("length", &self.length())
+            .field("message_id", &self.message_id())
+            .field("sequence", &self.sequence())
+            .finish()
+    }
+}
+
+impl fmt::Display for FrameHeaderView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The header in front of every message, written in place to a byte slice
+pub struct FrameHeaderMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> FrameHeaderMut<'a> {
+    pub const SIZE: usize = 8;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the struct are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(FrameHeaderMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> FrameHeaderView<'_> {
+        FrameHeaderView { buf: self.buf }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn length(&self) -> u16 {
+        self.view().length()
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn message_id(&self) -> u16 {
+        self.view().message_id()
+    }
+
+    /// `u32`, offset 4, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        self.view().sequence()
+    }
+
+    /// `u16`, offset 0, 2 bytes
+    pub fn set_length(&mut self, value: u16) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `u16`, offset 2, 2 bytes
+    pub fn set_message_id(&mut self, value: u16) {
+        put(self.buf, 2, value.to_le_bytes());
+    }
+
+    /// `u32`, offset 4, 4 bytes
+    pub fn set_sequence(&mut self, value: u32) {
+        put(self.buf, 4, value.to_le_bytes());
+    }
+}
+
+impl fmt::Debug for FrameHeaderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for FrameHeaderMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Quote` [1], read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct QuoteView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> QuoteView<'a> {
+    pub const SIZE: usize = 67;
+    pub const ID: u32 = 1;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 75;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(QuoteView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// when the quote was sent (offset 0, 8 bytes)
+    pub fn sent_at(&self) -> u64 {
+        u64::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `sent_at` as the time since the unix epoch
+    pub fn sent_at_since_epoch(&self) -> core::time::Duration {
+        core::time::Duration::from_nanos(self.sent_at())
+    }
+
+    /// `ascii[8]`, offset 8, 8 bytes
+    pub fn symbol(&self) -> &'a [u8; 8] {
+        bytes(self.buf, 8)
+    }
+
+    /// The text of `symbol` up to the first nul byte
+    pub fn symbol_str(&self) -> Result<&'a str, Error> {
+        ascii_str(self.symbol(), 0, "symbol")
+    }
+
+    /// `Side`, offset 16, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        Side::try_from(self.side_raw())
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        u8::from_le_bytes(get(self.buf, 16))
+    }
+
+    /// `Price`, offset 17, 11 bytes
+    pub fn bid(&self) -> PriceView<'a> {
+        PriceView { buf: &self.buf[17..28] }
+    }
+
+    /// `Price`, offset 28, 11 bytes
+    pub fn ask(&self) -> PriceView<'a> {
+        PriceView { buf: &self.buf[28..39] }
+    }
+
+    /// `i32[3]`, offset 39, 12 bytes
+    pub fn levels(&self) -> [i32; 3] {
+        core::array::from_fn(|i| i32::from_le_bytes(get(self.buf, 39 + i * 4)))
+    }
+
+    /// `f64`, offset 51, 8 bytes
+    pub fn weight(&self) -> f64 {
+        f64::from_le_bytes(get(self.buf, 51))
+    }
+
+    /// how long the quote can be traded on, counted from sent_at (offset 59, 8 bytes)
+    pub fn ttl(&self) -> i64 {
+        i64::from_le_bytes(get(self.buf, 59))
+    }
+}
+
+impl fmt::Debug for QuoteView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quote")
+            .field("sent_at", &self.sent_at())
+            .field("symbol", &Decoded(self.symbol_str().map_err(|_| self.symbol())))
+            .field("side", &Decoded(self.side().map_err(|_| self.side_raw())))
+            .field("bid", &self.bid())
+            .field("ask", &self.ask())
+            .field("levels", &self.levels())
+            .field("weight", &self.weight())
+            .field("ttl", &self.ttl())
+            .finish()
+    }
+}
+
+impl fmt::Display for QuoteView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Quote` [1], written in place to a byte slice
+pub struct QuoteMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> QuoteMut<'a> {
+    pub const SIZE: usize = 67;
+    pub const ID: u32 = 1;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 75;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(QuoteMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> QuoteView<'_> {
+        QuoteView { buf: self.buf }
+    }
+
+    /// Writes the header to the start of `buf` and returns the message after it, fails if
+    /// `buf` is shorter than [`Self::FRAME_SIZE`]
+    pub fn new_frame(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::FRAME_SIZE)?;
+        let (header, body) = buf.split_at_mut(FrameHeaderMut::SIZE);
+        let mut header = FrameHeaderMut::new(header)?;
+        header.set_message_id(Self::ID as u16);
+        header.set_length(Self::FRAME_SIZE as u16);
+        Self::new(body)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// when the quote was sent (offset 0, 8 bytes)
+    pub fn sent_at(&self) -> u64 {
+        self.view().sent_at()
+    }
+
+    /// `sent_at` as the time since the unix epoch
+    pub fn sent_at_since_epoch(&self) -> core::time::Duration {
+        self.view().sent_at_since_epoch()
+    }
+
+    /// `ascii[8]`, offset 8, 8 bytes
+    pub fn symbol(&self) -> &'_ [u8; 8] {
+        self.view().symbol()
+    }
+
+    /// The text of `symbol` up to the first nul byte
+    pub fn symbol_str(&self) -> Result<&'_ str, Error> {
+        self.view().symbol_str()
+    }
+
+    /// `Side`, offset 16, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        self.view().side()
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        self.view().side_raw()
+    }
+
+    /// `Price`, offset 17, 11 bytes
+    pub fn bid(&self) -> PriceView<'_> {
+        self.view().bid()
+    }
+
+    /// `Price`, offset 28, 11 bytes
+    pub fn ask(&self) -> PriceView<'_> {
+        self.view().ask()
+    }
+
+    /// `i32[3]`, offset 39, 12 bytes
+    pub fn levels(&self) -> [i32; 3] {
+        self.view().levels()
+    }
+
+    /// `f64`, offset 51, 8 bytes
+    pub fn weight(&self) -> f64 {
+        self.view().weight()
+    }
+
+    /// how long the quote can be traded on, counted from sent_at (offset 59, 8 bytes)
+    pub fn ttl(&self) -> i64 {
+        self.view().ttl()
+    }
+
+    /// when the quote was sent (offset 0, 8 bytes)
+    pub fn set_sent_at(&mut self, value: u64) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `ascii[8]`, offset 8, 8 bytes
+    pub fn set_symbol(&mut self, value: &str) -> Result<(), Error> {
+        put_ascii(&mut self.buf[8..16], value, 0, "symbol")
+    }
+
+    /// Stores any value in `side`, even one which isn't declared
+    pub fn set_side_raw(&mut self, value: u8) {
+        put(self.buf, 16, value.to_le_bytes());
+    }
+
+    /// `Side`, offset 16, 1 bytes
+    pub fn set_side(&mut self, value: Side) {
+        put(self.buf, 16, u8::from(value).to_le_bytes());
+    }
+
+    /// `Price`, offset 17, 11 bytes
+    pub fn bid_mut(&mut self) -> PriceMut<'_> {
+        PriceMut { buf: &mut self.buf[17..28] }
+    }
+
+    /// `Price`, offset 28, 11 bytes
+    pub fn ask_mut(&mut self) -> PriceMut<'_> {
+        PriceMut { buf: &mut self.buf[28..39] }
+    }
+
+    /// `i32[3]`, offset 39, 12 bytes
+    pub fn set_levels(&mut self, value: &[i32; 3]) {
+        for (i, v) in value.iter().enumerate() {
+            put(self.buf, 39 + i * 4, v.to_le_bytes());
+        }
+    }
+
+    /// `f64`, offset 51, 8 bytes
+    pub fn set_weight(&mut self, value: f64) {
+        put(self.buf, 51, value.to_le_bytes());
+    }
+
+    /// how long the quote can be traded on, counted from sent_at (offset 59, 8 bytes)
+    pub fn set_ttl(&mut self, value: i64) {
+        put(self.buf, 59, value.to_le_bytes());
+    }
+}
+
+impl fmt::Debug for QuoteMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for QuoteMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Request` [2], read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct RequestView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> RequestView<'a> {
+    pub const SIZE: usize = 48;
+    pub const ID: u32 = 2;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 56;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(RequestView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `u32`, offset 0, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        u32::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `Permissions`, offset 4, 2 bytes
+    pub fn permissions(&self) -> Permissions {
+        Permissions::from_bits_retain(u16::from_le_bytes(get(self.buf, 4)))
+    }
+
+    /// `BodyKind`, offset 6, 1 bytes
+    pub fn kind(&self) -> Result<BodyKind, Error> {
+        BodyKind::try_from(self.kind_raw())
+    }
+
+    /// The stored value of `kind`, even if it isn't declared
+    pub fn kind_raw(&self) -> u8 {
+        u8::from_le_bytes(get(self.buf, 6))
+    }
+
+    /// the order or cancel the request is for (offset 7, 16 bytes), the variant is selected by `kind`
+    pub fn body(&self) -> Result<BodyView<'a>, Error> {
+        Ok(match self.kind_raw() {
+            1 => BodyView::Order(OrderView { buf: &self.buf[7..23] }),
+            2 => BodyView::CancelId(u64::from_le_bytes(get(self.buf, 7))),
+            3 => BodyView::Ping(bytes(self.buf, 7)),
+            value => return Err(Error::InvalidEnumValue { name: "Body", value: value as i128 }),
+        })
+    }
+
+    /// The bytes of `body` whichever variant is active
+    pub fn body_bytes(&self) -> &'a [u8; 16] {
+        bytes(self.buf, 7)
+    }
+
+    /// `byte[4]`, offset 23, 4 bytes
+    pub fn checksum(&self) -> &'a [u8; 4] {
+        bytes(self.buf, 23)
+    }
+
+    /// `i16`, offset 29, 2 bytes
+    pub fn type_(&self) -> i16 {
+        i16::from_le_bytes(get(self.buf, 29))
+    }
+
+    /// `ascii[6]`, offset 31, 6 bytes
+    pub fn venue(&self) -> &'a [u8; 6] {
+        bytes(self.buf, 31)
+    }
+
+    /// The text of `venue` up to the first nul byte, without trailing spaces
+    pub fn venue_str(&self) -> Result<&'a str, Error> {
+        ascii_str(self.venue(), b' ', "venue")
+    }
+}
+
+impl fmt::Debug for RequestView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("sequence", &self.sequence())
+            .field("permissions", &self.permissions())
+            .field("kind", &Decoded(self.kind().map_err(|_| self.kind_raw())))
+            .field("body", &Decoded(self.body().map_err(|_| self.body_bytes())))
+            .field("checksum", &self.checksum())
+            .field("type", &self.type_())
+            .field("venue", &Decoded(self.venue_str().map_err(|_| self.venue())))
+            .finish()
+    }
+}
+
+impl fmt::Display for RequestView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Request` [2], written in place to a byte slice
+pub struct RequestMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> RequestMut<'a> {
+    pub const SIZE: usize = 48;
+    pub const ID: u32 = 2;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 56;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(RequestMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> RequestView<'_> {
+        RequestView { buf: self.buf }
+    }
+
+    /// Writes the header to the start of `buf` and returns the message after it, fails if
+    /// `buf` is shorter than [`Self::FRAME_SIZE`]
+    pub fn new_frame(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::FRAME_SIZE)?;
+        let (header, body) = buf.split_at_mut(FrameHeaderMut::SIZE);
+        let mut header = FrameHeaderMut::new(header)?;
+        header.set_message_id(Self::ID as u16);
+        header.set_length(Self::FRAME_SIZE as u16);
+        Self::new(body)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `u32`, offset 0, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        self.view().sequence()
+    }
+
+    /// `Permissions`, offset 4, 2 bytes
+    pub fn permissions(&self) -> Permissions {
+        self.view().permissions()
+    }
+
+    /// `BodyKind`, offset 6, 1 bytes
+    pub fn kind(&self) -> Result<BodyKind, Error> {
+        self.view().kind()
+    }
+
+    /// The stored value of `kind`, even if it isn't declared
+    pub fn kind_raw(&self) -> u8 {
+        self.view().kind_raw()
+    }
+
+    /// the order or cancel the request is for (offset 7, 16 bytes), the variant is selected by `kind`
+    pub fn body(&self) -> Result<BodyView<'_>, Error> {
+        self.view().body()
+    }
+
+    /// The bytes of `body` whichever variant is active
+    pub fn body_bytes(&self) -> &'_ [u8; 16] {
+        self.view().body_bytes()
+    }
+
+    /// `byte[4]`, offset 23, 4 bytes
+    pub fn checksum(&self) -> &'_ [u8; 4] {
+        self.view().checksum()
+    }
+
+    /// `i16`, offset 29, 2 bytes
+    pub fn type_(&self) -> i16 {
+        self.view().type_()
+    }
+
+    /// `ascii[6]`, offset 31, 6 bytes
+    pub fn venue(&self) -> &'_ [u8; 6] {
+        self.view().venue()
+    }
+
+    /// The text of `venue` up to the first nul byte, without trailing spaces
+    pub fn venue_str(&self) -> Result<&'_ str, Error> {
+        self.view().venue_str()
+    }
+
+    /// `u32`, offset 0, 4 bytes
+    pub fn set_sequence(&mut self, value: u32) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `Permissions`, offset 4, 2 bytes
+    pub fn set_permissions(&mut self, value: Permissions) {
+        put(self.buf, 4, value.bits().to_le_bytes());
+    }
+
+    /// Stores any value in `kind`, even one which isn't declared
+    pub fn set_kind_raw(&mut self, value: u8) {
+        put(self.buf, 6, value.to_le_bytes());
+    }
+
+    /// `BodyKind`, offset 6, 1 bytes
+    pub fn set_kind(&mut self, value: BodyKind) {
+        put(self.buf, 6, u8::from(value).to_le_bytes());
+    }
+
+    /// Makes `order` the active variant by setting `kind` to `ORDER`
+    pub fn body_order_mut(&mut self) -> OrderMut<'_> {
+        self.buf[7..23].fill(0);
+        self.set_kind_raw(1);
+        OrderMut { buf: &mut self.buf[7..23] }
+    }
+
+    /// Makes `cancel_id` the active variant by setting `kind` to `CANCEL`
+    pub fn set_body_cancel_id(&mut self, value: u64) {
+        self.buf[7..23].fill(0);
+        self.set_kind_raw(2);
+        put(self.buf, 7, value.to_le_bytes());
+    }
+
+    /// Makes `ping` the active variant by setting `kind` to `PING`
+    pub fn set_body_ping(&mut self, value: &str) -> Result<(), Error> {
+        self.buf[7..23].fill(0);
+        self.set_kind_raw(3);
+        put_ascii(&mut self.buf[7..11], value, 0, "ping")
+    }
+
+    /// `byte[4]`, offset 23, 4 bytes
+    pub fn set_checksum(&mut self, value: &[u8; 4]) {
+        self.buf[23..27].copy_from_slice(value);
+    }
+
+    /// `i16`, offset 29, 2 bytes
+    pub fn set_type_(&mut self, value: i16) {
+        put(self.buf, 29, value.to_le_bytes());
+    }
+
+    /// `ascii[6]`, offset 31, 6 bytes
+    pub fn set_venue(&mut self, value: &str) -> Result<(), Error> {
+        put_ascii(&mut self.buf[31..37], value, b' ', "venue")
+    }
+}
+
+impl fmt::Debug for RequestMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for RequestMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Heartbeat` [3], read in place from a byte slice
+#[derive(Clone, Copy)]
+pub struct HeartbeatView<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> HeartbeatView<'a> {
+    pub const SIZE: usize = 16;
+    pub const ID: u32 = 3;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 24;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are ignored
+    pub fn new(buf: &'a [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(HeartbeatView { buf: &buf[..Self::SIZE] })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// `u64`, offset 0, 8 bytes
+    pub fn sent_at(&self) -> u64 {
+        u64::from_le_bytes(get(self.buf, 0))
+    }
+
+    /// `u32`, offset 8, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        u32::from_le_bytes(get(self.buf, 8))
+    }
+
+    /// `Side`, offset 12, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        Side::try_from(self.side_raw())
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        u8::from_le_bytes(get(self.buf, 12))
+    }
+}
+
+impl fmt::Debug for HeartbeatView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Heartbeat")
+            .field("sent_at", &self.sent_at())
+            .field("sequence", &self.sequence())
+            .field("side", &Decoded(self.side().map_err(|_| self.side_raw())))
+            .finish()
+    }
+}
+
+impl fmt::Display for HeartbeatView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message `Heartbeat` [3], written in place to a byte slice
+pub struct HeartbeatMut<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> HeartbeatMut<'a> {
+    pub const SIZE: usize = 16;
+    pub const ID: u32 = 3;
+    /// The size of the header followed by the message
+    pub const FRAME_SIZE: usize = 24;
+
+    /// Fails if `buf` is shorter than [`Self::SIZE`], any bytes after the message are left untouched
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::SIZE)?;
+        Ok(HeartbeatMut { buf: &mut buf[..Self::SIZE] })
+    }
+
+    pub fn view(&self) -> HeartbeatView<'_> {
+        HeartbeatView { buf: self.buf }
+    }
+
+    /// Writes the header to the start of `buf` and returns the message after it, fails if
+    /// `buf` is shorter than [`Self::FRAME_SIZE`]
+    pub fn new_frame(buf: &'a mut [u8]) -> Result<Self, Error> {
+        check_size(buf, Self::FRAME_SIZE)?;
+        let (header, body) = buf.split_at_mut(FrameHeaderMut::SIZE);
+        let mut header = FrameHeaderMut::new(header)?;
+        header.set_message_id(Self::ID as u16);
+        header.set_length(Self::FRAME_SIZE as u16);
+        Self::new(body)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// `u64`, offset 0, 8 bytes
+    pub fn sent_at(&self) -> u64 {
+        self.view().sent_at()
+    }
+
+    /// `u32`, offset 8, 4 bytes
+    pub fn sequence(&self) -> u32 {
+        self.view().sequence()
+    }
+
+    /// `Side`, offset 12, 1 bytes
+    pub fn side(&self) -> Result<Side, Error> {
+        self.view().side()
+    }
+
+    /// The stored value of `side`, even if it isn't declared
+    pub fn side_raw(&self) -> u8 {
+        self.view().side_raw()
+    }
+
+    /// `u64`, offset 0, 8 bytes
+    pub fn set_sent_at(&mut self, value: u64) {
+        put(self.buf, 0, value.to_le_bytes());
+    }
+
+    /// `u32`, offset 8, 4 bytes
+    pub fn set_sequence(&mut self, value: u32) {
+        put(self.buf, 8, value.to_le_bytes());
+    }
+
+    /// Stores any value in `side`, even one which isn't declared
+    pub fn set_side_raw(&mut self, value: u8) {
+        put(self.buf, 12, value.to_le_bytes());
+    }
+
+    /// `Side`, offset 12, 1 bytes
+    pub fn set_side(&mut self, value: Side) {
+        put(self.buf, 12, u8::from(value).to_le_bytes());
+    }
+}
+
+impl fmt::Debug for HeartbeatMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.view(), f)
+    }
+}
+
+impl fmt::Display for HeartbeatMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Called by [`dispatch`] with the message of a frame, a message without an
+/// implementation is ignored
+pub trait Handler {
+    /// Called with a `Quote` [1]
+    fn on_quote(&mut self, message: QuoteView<'_>) {
+        let _ = message;
+    }
+
+    /// Called with a `Request` [2]
+    fn on_request(&mut self, message: RequestView<'_>) {
+        let _ = message;
+    }
+
+    /// Called with a `Heartbeat` [3]
+    fn on_heartbeat(&mut self, message: HeartbeatView<'_>) {
+        let _ = message;
+    }
+}
+
+/// Reads the header at the start of `buf` and passes the message after it to the
+/// callback for its id. Returns the size of the frame, anything after it is left
+/// alone.
+pub fn dispatch<H: Handler + ?Sized>(buf: &[u8], handler: &mut H) -> Result<usize, Error> {
+    let header = FrameHeaderView::new(buf)?;
+    let frame = header.length() as usize;
+    if frame < FrameHeaderView::SIZE {
+        return Err(Error::InvalidLength { length: frame });
+    }
+    check_size(buf, frame)?;
+    let body = &buf[FrameHeaderView::SIZE..frame];
+    match u64::from(header.message_id()) {
+        1 => handler.on_quote(QuoteView::new(body)?),
+        2 => handler.on_request(RequestView::new(body)?),
+        3 => handler.on_heartbeat(HeartbeatView::new(body)?),
+        id => return Err(Error::UnknownMessageId { id }),
+    }
+    Ok(frame)
+}
@@ -0,0 +1,123 @@
+// Exercises the generated module, run by tests/codegen.rs when node is found.
+import {
+  Body,
+  BodyKind,
+  FrameHeader,
+  Heartbeat,
+  Permissions,
+  Price,
+  Quote,
+  Request,
+  Side,
+  decode,
+  dispatch,
+} from "./test_codegen.js";
+
+function check(condition, message = "") {
+  if (!condition) {
+    throw new Error(`check failed ${message}`);
+  }
+}
+
+function throws(error, f) {
+  try {
+    f();
+  } catch (e) {
+    return e instanceof error;
+  }
+  return false;
+}
+
+function testQuote() {
+  // misaligned offset into a larger buffer
+  const view = new DataView(new ArrayBuffer(Quote.SIZE + 3));
+  const quote = new Quote(view, 3);
+  quote.sentAt = 1700000000123456789n;
+  quote.symbol = "AAPL";
+  check(throws(RangeError, () => (quote.symbol = "TOOLONGXX")), "symbol too long");
+  check(throws(RangeError, () => (quote.symbol = "é")), "symbol not ascii");
+  quote.side = "SELL";
+  quote.bid.value = 1234500n;
+  quote.bid.currency = "USD";
+  quote.setLevels(0, -1);
+  quote.setLevels(2, 1);
+  check(throws(RangeError, () => quote.getLevels(3)), "levels index");
+  quote.weight = 0.5;
+  quote.ttl = -250n;
+
+  const decoded = decode(Quote.ID, view, 3);
+  check(decoded instanceof Quote, "decoded class");
+  check(decoded.sentAt === 1700000000123456789n, "sentAt");
+  check(decoded.symbol === "AAPL", "symbol");
+  check(decoded.side === "SELL" && decoded.sideRaw === Side.SELL, "side");
+  check(decoded.bid.value === 1234500n && Price.VALUE_EXPONENT === -4, "bid");
+  check(decoded.bid.currency === "USD", "currency");
+  check(decoded.getLevels(0) === -1 && decoded.getLevels(1) === 0 && decoded.getLevels(2) === 1, "levels");
+  check(decoded.weight === 0.5, "weight");
+  check(decoded.ttl === -250n, "ttl");
+  // little endian on the wire
+  check(view.getUint8(3) === 0x15, "byte order");
+  check(decoded.sentAtDate.getTime() === 1700000000123, "sentAtDate");
+  decoded.ttlMs = 3n;
+  check(decoded.ttl === 3000n && decoded.ttlMs === 3n, "ttlMs");
+  decoded.sentAtDate = new Date(1000);
+  check(decoded.sentAt === 1000000000n, "sentAt from a Date");
+
+  decoded.sideRaw = 7;
+  check(throws(RangeError, () => decoded.side), "undeclared side");
+  check(throws(RangeError, () => (decoded.side = "HOLD")), "unknown side name");
+  check(throws(RangeError, () => new Quote(view, 4)), "short view");
+  check(throws(RangeError, () => decode(9, view)), "unknown id");
+}
+
+function testRequest() {
+  const request = Request.allocate();
+  request.sequence = 0xffffffff;
+  request.permissions = ["READ", "ADMIN"];
+  request.kind = "CANCEL";
+  request.body.cancelId = 99n;
+  request.checksum = [1, 2, 3, 4];
+  check(throws(RangeError, () => (request.checksum = [1])), "checksum length");
+  request.type = -2;
+  request.venue = "XLON";
+
+  check(request.sequence === 0xffffffff, "unsigned sequence");
+  check(request.permissionsRaw === (Permissions.READ | Permissions.ADMIN), "permissions raw");
+  check(request.permissions.join() === "READ,ADMIN", "permissions");
+  check(request.kind === "CANCEL" && BodyKind[request.kind] === 2, "kind");
+  check(new Body(request.view, Request.BODY_OFFSET).cancelId === 99n, "body");
+  check(request.bodyCancelId === 99n, "checked body");
+  check(request.bodyOrder === undefined, "inactive variant");
+  check(request.checksum[3] === 4 && request.checksum.length === 4, "checksum");
+  check(request.type === -2, "type");
+  check(request.venue === "XLON", "venue");
+  check(request.view.getUint8(Request.VENUE_OFFSET + 5) === 0x20, "space padding");
+
+  const heartbeat = decode(Heartbeat.ID, new DataView(new ArrayBuffer(Heartbeat.SIZE)));
+  check(heartbeat instanceof Heartbeat && heartbeat.sentAt === 0n, "heartbeat");
+}
+
+function testDispatch() {
+  const view = new DataView(new ArrayBuffer(Heartbeat.FRAME_SIZE + Quote.FRAME_SIZE + 1));
+  Heartbeat.wrapFrame(view).sequence = 7;
+  Quote.wrapFrame(view, Heartbeat.FRAME_SIZE);
+  check(Heartbeat.FRAME_SIZE === FrameHeader.SIZE + Heartbeat.SIZE, "frame size");
+  check(new Uint8Array(view.buffer, 0, 4).join() === "24,0,3,0", "header");
+
+  const heartbeats = [];
+  const handler = { onHeartbeat: (message) => heartbeats.push(message.sequence) };
+  check(dispatch(view, handler) === Heartbeat.FRAME_SIZE, "heartbeat frame");
+  check(dispatch(view, handler, Heartbeat.FRAME_SIZE) === Quote.FRAME_SIZE, "quote frame");
+  check(heartbeats.join() === "7", "only the heartbeat is handled");
+
+  check(throws(RangeError, () => dispatch(new DataView(view.buffer, 0, 10), handler)), "short frame");
+  view.setUint8(2, 9);
+  check(throws(RangeError, () => dispatch(view, handler)), "unknown id");
+  view.setUint8(2, 3);
+  view.setUint8(0, 4);
+  check(throws(RangeError, () => dispatch(view, handler)), "length too small");
+}
+
+testQuote();
+testRequest();
+testDispatch();
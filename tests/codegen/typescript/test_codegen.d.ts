@@ -0,0 +1,246 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+
+/** enum Side, stored as a u8 */
+export type Side = "BUY" | "SELL";
+export declare const Side: {
+  readonly BUY: 1;
+  readonly SELL: 2;
+};
+
+/** bitflags Permissions, stored as a u16 */
+export type Permissions = "READ" | "WRITE" | "ADMIN";
+export declare const Permissions: {
+  readonly READ: 1;
+  readonly WRITE: 2;
+  readonly ADMIN: 128;
+};
+
+/** enum BodyKind, stored as a u8 */
+export type BodyKind = "ORDER" | "CANCEL" | "PING";
+export declare const BodyKind: {
+  readonly ORDER: 1;
+  readonly CANCEL: 2;
+  readonly PING: 3;
+};
+
+/** struct Price */
+export declare class Price {
+  static readonly SIZE: 11;
+  static readonly VALUE_OFFSET: 0;
+  static readonly VALUE_EXPONENT: -4;
+  static readonly CURRENCY_OFFSET: 8;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Price;
+  /** decimal<i64, -4> at offset 0, the amount in units of the currency */
+  value: bigint;
+  /** ascii[3] at offset 8 */
+  currency: string;
+}
+
+/** struct Order */
+export declare class Order {
+  static readonly SIZE: 16;
+  static readonly SIDE_OFFSET: 0;
+  static readonly QUANTITY_OFFSET: 1;
+  static readonly PRICE_OFFSET: 5;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Order;
+  /** Side at offset 0 */
+  side: Side;
+  sideRaw: number;
+  /** u32 at offset 1 */
+  quantity: number;
+  /** Price at offset 5 */
+  readonly price: Price;
+}
+
+/** union Body, the active variant is selected by a BodyKind */
+export declare class Body {
+  static readonly SIZE: 16;
+  static readonly ORDER_OFFSET: 0;
+  static readonly CANCEL_ID_OFFSET: 0;
+  static readonly PING_OFFSET: 0;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Body;
+  /** Order at offset 0, active when the discriminator is ORDER */
+  readonly order: Order;
+  /** u64 at offset 0, active when the discriminator is CANCEL */
+  cancelId: bigint;
+  /** ascii[4] at offset 0, active when the discriminator is PING */
+  ping: string;
+}
+
+/** the header in front of every message */
+export declare class FrameHeader {
+  static readonly SIZE: 8;
+  static readonly LENGTH_OFFSET: 0;
+  static readonly MESSAGE_ID_OFFSET: 2;
+  static readonly SEQUENCE_OFFSET: 4;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): FrameHeader;
+  /** u16 at offset 0 */
+  length: number;
+  /** u16 at offset 2 */
+  messageId: number;
+  /** u32 at offset 4 */
+  sequence: number;
+}
+
+/** message Quote [1] */
+export declare class Quote {
+  static readonly ID: 1;
+  static readonly SIZE: 67;
+  static readonly FRAME_SIZE: 75;
+  static readonly SENT_AT_OFFSET: 0;
+  static readonly SYMBOL_OFFSET: 8;
+  static readonly SIDE_OFFSET: 16;
+  static readonly BID_OFFSET: 17;
+  static readonly ASK_OFFSET: 28;
+  static readonly LEVELS_OFFSET: 39;
+  static readonly LEVELS_LENGTH: 3;
+  static readonly WEIGHT_OFFSET: 51;
+  static readonly TTL_OFFSET: 59;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Quote;
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view: DataView, offset?: number): Quote;
+  /** timestamp<ns> at offset 0, when the quote was sent */
+  sentAt: bigint;
+  /** sent_at as a Date */
+  sentAtDate: Date;
+  /** ascii[8] at offset 8 */
+  symbol: string;
+  /** Side at offset 16 */
+  side: Side;
+  sideRaw: number;
+  /** Price at offset 17 */
+  readonly bid: Price;
+  /** Price at offset 28 */
+  readonly ask: Price;
+  /** i32[3] at offset 39 */
+  getLevels(index: number): number;
+  setLevels(index: number, value: number): void;
+  /** f64 at offset 51 */
+  weight: number;
+  /** duration<us> at offset 59, how long the quote can be traded on, counted from sent_at */
+  ttl: bigint;
+  /** ttl in milliseconds */
+  ttlMs: bigint;
+}
+
+/** message Request [2] */
+export declare class Request {
+  static readonly ID: 2;
+  static readonly SIZE: 48;
+  static readonly FRAME_SIZE: 56;
+  static readonly SEQUENCE_OFFSET: 0;
+  static readonly PERMISSIONS_OFFSET: 4;
+  static readonly KIND_OFFSET: 6;
+  static readonly BODY_OFFSET: 7;
+  static readonly CHECKSUM_OFFSET: 23;
+  static readonly TYPE_OFFSET: 29;
+  static readonly VENUE_OFFSET: 31;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Request;
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view: DataView, offset?: number): Request;
+  /** u32 at offset 0 */
+  sequence: number;
+  /** Permissions at offset 4 */
+  permissions: Permissions[];
+  permissionsRaw: number;
+  /** BodyKind at offset 6 */
+  kind: BodyKind;
+  kindRaw: number;
+  /** Body at offset 7, the order or cancel the request is for */
+  readonly body: Body;
+  /** body.order, undefined unless kind is ORDER */
+  readonly bodyOrder: Order | undefined;
+  /** body.cancelId, undefined unless kind is CANCEL */
+  readonly bodyCancelId: bigint | undefined;
+  /** body.ping, undefined unless kind is PING */
+  readonly bodyPing: string | undefined;
+  /** byte[4] at offset 23 */
+  get checksum(): Uint8Array;
+  set checksum(value: ArrayLike<number>);
+  /** i16 at offset 29 */
+  type: number;
+  /** ascii[6] at offset 31 */
+  venue: string;
+}
+
+/** message Heartbeat [3] */
+export declare class Heartbeat {
+  static readonly ID: 3;
+  static readonly SIZE: 16;
+  static readonly FRAME_SIZE: 24;
+  static readonly SENT_AT_OFFSET: 0;
+  static readonly SEQUENCE_OFFSET: 8;
+  static readonly SIDE_OFFSET: 12;
+
+  readonly view: DataView;
+  readonly offset: number;
+  /** Throws a RangeError when the view is too short */
+  constructor(view: DataView, offset?: number);
+  /** Wraps a new zeroed buffer */
+  static allocate(): Heartbeat;
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view: DataView, offset?: number): Heartbeat;
+  /** u64 at offset 0 */
+  sentAt: bigint;
+  /** u32 at offset 8 */
+  sequence: number;
+  /** Side at offset 12 */
+  side: Side;
+  sideRaw: number;
+}
+
+/** Every message of the protocol */
+export type AnyMessage = Quote | Request | Heartbeat;
+
+/** Views the message with the given id, throws a RangeError for an unknown id */
+export declare function decode(id: number, view: DataView, offset?: number): AnyMessage;
+
+/** Callbacks for dispatch, a message without a callback is ignored */
+export interface Handler {
+  onQuote?(message: Quote): void;
+  onRequest?(message: Request): void;
+  onHeartbeat?(message: Heartbeat): void;
+}
+
+/**
+ * Reads the header at offset and calls the handler's callback for the message after it, returns
+ * the size of the frame. Throws a RangeError when the view doesn't hold the whole frame, its
+ * length is too small for its message or no message has its id.
+ */
+export declare function dispatch(view: DataView, handler: Handler, offset?: number): number;
@@ -0,0 +1,602 @@
+// Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+
+const LITTLE_ENDIAN = true;
+
+const ASCII = new TextDecoder("ascii");
+
+function checkSize(view, offset, size, name) {
+  if (!Number.isInteger(offset) || offset < 0 || offset + size > view.byteLength) {
+    throw new RangeError(`${name} needs ${size} bytes at offset ${offset} but the view holds ${view.byteLength}`);
+  }
+}
+
+function checkIndex(index, length, name) {
+  if (!Number.isInteger(index) || index < 0 || index >= length) {
+    throw new RangeError(`${index} is out of range for ${name}, which holds ${length} elements`);
+  }
+}
+
+function bytes(view, offset, length) {
+  return new Uint8Array(view.buffer, view.byteOffset + offset, length);
+}
+
+function getAscii(view, offset, length, pad) {
+  const field = bytes(view, offset, length);
+  let end = field.indexOf(0);
+  if (end < 0) {
+    end = length;
+  }
+  while (end > 0 && field[end - 1] === pad) {
+    end--;
+  }
+  return ASCII.decode(field.subarray(0, end));
+}
+
+function setAscii(view, offset, length, pad, value, name) {
+  if (value.length > length) {
+    throw new RangeError(`${name} holds at most ${length} characters but the value has ${value.length}`);
+  }
+  for (let i = 0; i < value.length; i++) {
+    if (value.charCodeAt(i) > 0x7f) {
+      throw new RangeError(`${name} only holds ascii characters`);
+    }
+  }
+  const field = bytes(view, offset, length);
+  field.fill(pad);
+  for (let i = 0; i < value.length; i++) {
+    field[i] = value.charCodeAt(i);
+  }
+}
+
+function setBytes(view, offset, length, value, name) {
+  if (value.length !== length) {
+    throw new RangeError(`${name} holds exactly ${length} bytes but the value has ${value.length}`);
+  }
+  bytes(view, offset, length).set(value);
+}
+
+function enumName(names, value, type) {
+  const name = names.get(value);
+  if (name === undefined) {
+    throw new RangeError(`${value} is not a declared ${type}`);
+  }
+  return name;
+}
+
+function enumValue(values, name, type) {
+  if (!Object.hasOwn(values, name)) {
+    throw new RangeError(`${name} is not a declared ${type}`);
+  }
+  return values[name];
+}
+
+function flagNames(values, raw) {
+  return Object.keys(values).filter((name) => (raw & values[name]) === values[name]);
+}
+
+function flagValue(values, names, type, zero) {
+  return names.reduce((raw, name) => raw | enumValue(values, name, type), zero);
+}
+
+/** enum Side, stored as a u8 */
+export const Side = Object.freeze({
+  BUY: 1,
+  SELL: 2,
+});
+const SIDE_NAMES = new Map(Object.entries(Side).map(([name, value]) => [value, name]));
+
+/** bitflags Permissions, stored as a u16 */
+export const Permissions = Object.freeze({
+  READ: 1,
+  WRITE: 2,
+  ADMIN: 128,
+});
+
+/** enum BodyKind, stored as a u8 */
+export const BodyKind = Object.freeze({
+  ORDER: 1,
+  CANCEL: 2,
+  PING: 3,
+});
+const BODY_KIND_NAMES = new Map(Object.entries(BodyKind).map(([name, value]) => [value, name]));
+
+/** struct Price */
+export class Price {
+  static SIZE = 11;
+  static VALUE_OFFSET = 0;
+  static VALUE_EXPONENT = -4;
+  static CURRENCY_OFFSET = 8;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Price.SIZE, "Price");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Price(new DataView(new ArrayBuffer(Price.SIZE)));
+  }
+
+  /** decimal<i64, -4> at offset 0, the amount in units of the currency */
+  get value() {
+    return this.view.getBigInt64(this.offset, LITTLE_ENDIAN);
+  }
+  set value(value) {
+    this.view.setBigInt64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** ascii[3] at offset 8 */
+  get currency() {
+    return getAscii(this.view, this.offset + 8, 3, 0);
+  }
+  set currency(value) {
+    setAscii(this.view, this.offset + 8, 3, 0, value, "currency");
+  }
+}
+
+/** struct Order */
+export class Order {
+  static SIZE = 16;
+  static SIDE_OFFSET = 0;
+  static QUANTITY_OFFSET = 1;
+  static PRICE_OFFSET = 5;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Order.SIZE, "Order");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Order(new DataView(new ArrayBuffer(Order.SIZE)));
+  }
+
+  /** Side at offset 0 */
+  get side() {
+    return enumName(SIDE_NAMES, this.sideRaw, "Side");
+  }
+  set side(value) {
+    this.sideRaw = enumValue(Side, value, "Side");
+  }
+  get sideRaw() {
+    return this.view.getUint8(this.offset);
+  }
+  set sideRaw(value) {
+    this.view.setUint8(this.offset, value);
+  }
+
+  /** u32 at offset 1 */
+  get quantity() {
+    return this.view.getUint32(this.offset + 1, LITTLE_ENDIAN);
+  }
+  set quantity(value) {
+    this.view.setUint32(this.offset + 1, value, LITTLE_ENDIAN);
+  }
+
+  /** Price at offset 5 */
+  get price() {
+    return new Price(this.view, this.offset + 5);
+  }
+}
+
+/** union Body, the active variant is selected by a BodyKind */
+export class Body {
+  static SIZE = 16;
+  static ORDER_OFFSET = 0;
+  static CANCEL_ID_OFFSET = 0;
+  static PING_OFFSET = 0;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Body.SIZE, "Body");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Body(new DataView(new ArrayBuffer(Body.SIZE)));
+  }
+
+  /** Order at offset 0, active when the discriminator is ORDER */
+  get order() {
+    return new Order(this.view, this.offset);
+  }
+
+  /** u64 at offset 0, active when the discriminator is CANCEL */
+  get cancelId() {
+    return this.view.getBigUint64(this.offset, LITTLE_ENDIAN);
+  }
+  set cancelId(value) {
+    this.view.setBigUint64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** ascii[4] at offset 0, active when the discriminator is PING */
+  get ping() {
+    return getAscii(this.view, this.offset, 4, 0);
+  }
+  set ping(value) {
+    setAscii(this.view, this.offset, 4, 0, value, "ping");
+  }
+}
+
+/** the header in front of every message */
+export class FrameHeader {
+  static SIZE = 8;
+  static LENGTH_OFFSET = 0;
+  static MESSAGE_ID_OFFSET = 2;
+  static SEQUENCE_OFFSET = 4;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, FrameHeader.SIZE, "FrameHeader");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new FrameHeader(new DataView(new ArrayBuffer(FrameHeader.SIZE)));
+  }
+
+  /** u16 at offset 0 */
+  get length() {
+    return this.view.getUint16(this.offset, LITTLE_ENDIAN);
+  }
+  set length(value) {
+    this.view.setUint16(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** u16 at offset 2 */
+  get messageId() {
+    return this.view.getUint16(this.offset + 2, LITTLE_ENDIAN);
+  }
+  set messageId(value) {
+    this.view.setUint16(this.offset + 2, value, LITTLE_ENDIAN);
+  }
+
+  /** u32 at offset 4 */
+  get sequence() {
+    return this.view.getUint32(this.offset + 4, LITTLE_ENDIAN);
+  }
+  set sequence(value) {
+    this.view.setUint32(this.offset + 4, value, LITTLE_ENDIAN);
+  }
+}
+
+/** message Quote [1] */
+export class Quote {
+  static ID = 1;
+  static SIZE = 67;
+  static FRAME_SIZE = 75;
+  static SENT_AT_OFFSET = 0;
+  static SYMBOL_OFFSET = 8;
+  static SIDE_OFFSET = 16;
+  static BID_OFFSET = 17;
+  static ASK_OFFSET = 28;
+  static LEVELS_OFFSET = 39;
+  static LEVELS_LENGTH = 3;
+  static WEIGHT_OFFSET = 51;
+  static TTL_OFFSET = 59;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Quote.SIZE, "Quote");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Quote(new DataView(new ArrayBuffer(Quote.SIZE)));
+  }
+
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view, offset = 0) {
+    checkSize(view, offset, Quote.FRAME_SIZE, "the frame of Quote");
+    const header = new FrameHeader(view, offset);
+    header.messageId = 1;
+    header.length = 75;
+    return new Quote(view, offset + FrameHeader.SIZE);
+  }
+
+  /** timestamp<ns> at offset 0, when the quote was sent */
+  get sentAt() {
+    return this.view.getBigUint64(this.offset, LITTLE_ENDIAN);
+  }
+  set sentAt(value) {
+    this.view.setBigUint64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** sent_at as a Date */
+  get sentAtDate() {
+    return new Date(Number(this.sentAt / 1000000n));
+  }
+  set sentAtDate(value) {
+    this.sentAt = BigInt(value.getTime()) * 1000000n;
+  }
+
+  /** ascii[8] at offset 8 */
+  get symbol() {
+    return getAscii(this.view, this.offset + 8, 8, 0);
+  }
+  set symbol(value) {
+    setAscii(this.view, this.offset + 8, 8, 0, value, "symbol");
+  }
+
+  /** Side at offset 16 */
+  get side() {
+    return enumName(SIDE_NAMES, this.sideRaw, "Side");
+  }
+  set side(value) {
+    this.sideRaw = enumValue(Side, value, "Side");
+  }
+  get sideRaw() {
+    return this.view.getUint8(this.offset + 16);
+  }
+  set sideRaw(value) {
+    this.view.setUint8(this.offset + 16, value);
+  }
+
+  /** Price at offset 17 */
+  get bid() {
+    return new Price(this.view, this.offset + 17);
+  }
+
+  /** Price at offset 28 */
+  get ask() {
+    return new Price(this.view, this.offset + 28);
+  }
+
+  /** i32[3] at offset 39 */
+  getLevels(index) {
+    checkIndex(index, Quote.LEVELS_LENGTH, "levels");
+    return this.view.getInt32(this.offset + 39 + index * 4, LITTLE_ENDIAN);
+  }
+  setLevels(index, value) {
+    checkIndex(index, Quote.LEVELS_LENGTH, "levels");
+    this.view.setInt32(this.offset + 39 + index * 4, value, LITTLE_ENDIAN);
+  }
+
+  /** f64 at offset 51 */
+  get weight() {
+    return this.view.getFloat64(this.offset + 51, LITTLE_ENDIAN);
+  }
+  set weight(value) {
+    this.view.setFloat64(this.offset + 51, value, LITTLE_ENDIAN);
+  }
+
+  /** duration<us> at offset 59, how long the quote can be traded on, counted from sent_at */
+  get ttl() {
+    return this.view.getBigInt64(this.offset + 59, LITTLE_ENDIAN);
+  }
+  set ttl(value) {
+    this.view.setBigInt64(this.offset + 59, value, LITTLE_ENDIAN);
+  }
+
+  /** ttl in milliseconds */
+  get ttlMs() {
+    return this.ttl / 1000n;
+  }
+  set ttlMs(value) {
+    this.ttl = value * 1000n;
+  }
+}
+
+/** message Request [2] */
+export class Request {
+  static ID = 2;
+  static SIZE = 48;
+  static FRAME_SIZE = 56;
+  static SEQUENCE_OFFSET = 0;
+  static PERMISSIONS_OFFSET = 4;
+  static KIND_OFFSET = 6;
+  static BODY_OFFSET = 7;
+  static CHECKSUM_OFFSET = 23;
+  static TYPE_OFFSET = 29;
+  static VENUE_OFFSET = 31;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Request.SIZE, "Request");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Request(new DataView(new ArrayBuffer(Request.SIZE)));
+  }
+
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view, offset = 0) {
+    checkSize(view, offset, Request.FRAME_SIZE, "the frame of Request");
+    const header = new FrameHeader(view, offset);
+    header.messageId = 2;
+    header.length = 56;
+    return new Request(view, offset + FrameHeader.SIZE);
+  }
+
+  /** u32 at offset 0 */
+  get sequence() {
+    return this.view.getUint32(this.offset, LITTLE_ENDIAN);
+  }
+  set sequence(value) {
+    this.view.setUint32(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** Permissions at offset 4 */
+  get permissions() {
+    return flagNames(Permissions, this.permissionsRaw);
+  }
+  set permissions(value) {
+    this.permissionsRaw = flagValue(Permissions, value, "Permissions", 0);
+  }
+  get permissionsRaw() {
+    return this.view.getUint16(this.offset + 4, LITTLE_ENDIAN);
+  }
+  set permissionsRaw(value) {
+    this.view.setUint16(this.offset + 4, value, LITTLE_ENDIAN);
+  }
+
+  /** BodyKind at offset 6 */
+  get kind() {
+    return enumName(BODY_KIND_NAMES, this.kindRaw, "BodyKind");
+  }
+  set kind(value) {
+    this.kindRaw = enumValue(BodyKind, value, "BodyKind");
+  }
+  get kindRaw() {
+    return this.view.getUint8(this.offset + 6);
+  }
+  set kindRaw(value) {
+    this.view.setUint8(this.offset + 6, value);
+  }
+
+  /** Body at offset 7, the order or cancel the request is for */
+  get body() {
+    return new Body(this.view, this.offset + 7);
+  }
+
+  /** body.order, undefined unless kind is ORDER */
+  get bodyOrder() {
+    return this.kindRaw === 1 ? this.body.order : undefined;
+  }
+
+  /** body.cancelId, undefined unless kind is CANCEL */
+  get bodyCancelId() {
+    return this.kindRaw === 2 ? this.body.cancelId : undefined;
+  }
+
+  /** body.ping, undefined unless kind is PING */
+  get bodyPing() {
+    return this.kindRaw === 3 ? this.body.ping : undefined;
+  }
+
+  /** byte[4] at offset 23 */
+  get checksum() {
+    return bytes(this.view, this.offset + 23, 4);
+  }
+  set checksum(value) {
+    setBytes(this.view, this.offset + 23, 4, value, "checksum");
+  }
+
+  /** i16 at offset 29 */
+  get type() {
+    return this.view.getInt16(this.offset + 29, LITTLE_ENDIAN);
+  }
+  set type(value) {
+    this.view.setInt16(this.offset + 29, value, LITTLE_ENDIAN);
+  }
+
+  /** ascii[6] at offset 31 */
+  get venue() {
+    return getAscii(this.view, this.offset + 31, 6, 0x20);
+  }
+  set venue(value) {
+    setAscii(this.view, this.offset + 31, 6, 0x20, value, "venue");
+  }
+}
+
+/** message Heartbeat [3] */
+export class Heartbeat {
+  static ID = 3;
+  static SIZE = 16;
+  static FRAME_SIZE = 24;
+  static SENT_AT_OFFSET = 0;
+  static SEQUENCE_OFFSET = 8;
+  static SIDE_OFFSET = 12;
+
+  /** Throws a RangeError when the view is too short */
+  constructor(view, offset = 0) {
+    checkSize(view, offset, Heartbeat.SIZE, "Heartbeat");
+    this.view = view;
+    this.offset = offset;
+  }
+
+  /** Wraps a new zeroed buffer */
+  static allocate() {
+    return new Heartbeat(new DataView(new ArrayBuffer(Heartbeat.SIZE)));
+  }
+
+  /** Writes the header's id and length at offset and views the message after it */
+  static wrapFrame(view, offset = 0) {
+    checkSize(view, offset, Heartbeat.FRAME_SIZE, "the frame of Heartbeat");
+    const header = new FrameHeader(view, offset);
+    header.messageId = 3;
+    header.length = 24;
+    return new Heartbeat(view, offset + FrameHeader.SIZE);
+  }
+
+  /** u64 at offset 0 */
+  get sentAt() {
+    return this.view.getBigUint64(this.offset, LITTLE_ENDIAN);
+  }
+  set sentAt(value) {
+    this.view.setBigUint64(this.offset, value, LITTLE_ENDIAN);
+  }
+
+  /** u32 at offset 8 */
+  get sequence() {
+    return this.view.getUint32(this.offset + 8, LITTLE_ENDIAN);
+  }
+  set sequence(value) {
+    this.view.setUint32(this.offset + 8, value, LITTLE_ENDIAN);
+  }
+
+  /** Side at offset 12 */
+  get side() {
+    return enumName(SIDE_NAMES, this.sideRaw, "Side");
+  }
+  set side(value) {
+    this.sideRaw = enumValue(Side, value, "Side");
+  }
+  get sideRaw() {
+    return this.view.getUint8(this.offset + 12);
+  }
+  set sideRaw(value) {
+    this.view.setUint8(this.offset + 12, value);
+  }
+}
+
+/** Views the message with the given id, throws a RangeError for an unknown id */
+export function decode(id, view, offset = 0) {
+  switch (id) {
+    case Quote.ID:
+      return new Quote(view, offset);
+    case Request.ID:
+      return new Request(view, offset);
+    case Heartbeat.ID:
+      return new Heartbeat(view, offset);
+    default:
+      throw new RangeError(`${id} is not the id of a message of test.codegen`);
+  }
+}
+
+const CALLBACKS = new Map([
+  [Quote, "onQuote"],
+  [Request, "onRequest"],
+  [Heartbeat, "onHeartbeat"],
+]);
+
+/**
+ * Reads the header at offset and calls the handler's callback for the message after it, returns
+ * the size of the frame. Throws a RangeError when the view doesn't hold the whole frame, its
+ * length is too small for its message or no message has its id.
+ */
+export function dispatch(view, handler, offset = 0) {
+  const header = new FrameHeader(view, offset);
+  const message = decode(header.messageId, view, offset + FrameHeader.SIZE);
+  const type = message.constructor;
+  const frame = header.length;
+  if (frame < type.FRAME_SIZE) {
+    throw new RangeError(`a frame length of ${frame} is too small for ${type.name}`);
+  }
+  checkSize(view, offset, frame, "the frame");
+  handler[CALLBACKS.get(type)]?.(message);
+  return frame;
+}
@@ -0,0 +1,218 @@
+-- Generated by sssc 0.1.0 from protocol test.codegen, do not edit.
+-- Load with `wireshark -X lua_script:test_codegen.lua`, every message is preceded by a header of 8 bytes.
+
+local proto = Proto("test_codegen", "test.codegen")
+
+local function decimal_text(mantissa, exponent)
+    local text = tostring(mantissa)
+    if exponent >= 0 then
+        return text .. string.rep("0", exponent)
+    end
+    local sign = ""
+    if text:sub(1, 1) == "-" then
+        sign, text = "-", text:sub(2)
+    end
+    local digits = -exponent
+    if #text <= digits then
+        text = string.rep("0", digits - #text + 1) .. text
+    end
+    return sign .. text:sub(1, #text - digits) .. "." .. text:sub(#text - digits + 1)
+end
+
+local function ascii(range)
+    return (range:string():match("^[^\0]*"))
+end
+
+-- a boolean field per flag of each bitflags
+local flags = {}
+
+-- enum Side, stored as a u8
+local side_names = {
+    [1] = "BUY",
+    [2] = "SELL",
+}
+
+-- bitflags Permissions, stored as a u16
+flags["permissions.read"] = ProtoField.bool("test_codegen.permissions.read", "READ", 16, nil, 0x1)
+flags["permissions.write"] = ProtoField.bool("test_codegen.permissions.write", "WRITE", 16, nil, 0x2)
+flags["permissions.admin"] = ProtoField.bool("test_codegen.permissions.admin", "ADMIN", 16, nil, 0x80)
+
+-- enum BodyKind, stored as a u8
+local body_kind_names = {
+    [1] = "ORDER",
+    [2] = "CANCEL",
+    [3] = "PING",
+}
+
+local message_names = {
+    [1] = "Quote",
+    [2] = "Request",
+    [3] = "Heartbeat",
+}
+
+local fields = {}
+fields.frame_header = ProtoField.none("test_codegen.frame_header", "FrameHeader")
+
+-- FrameHeader
+fields["frame_header.length"] = ProtoField.uint16("test_codegen.frame_header.length", "length", base.DEC, nil, nil, "u16")
+fields["frame_header.message_id"] = ProtoField.uint16("test_codegen.frame_header.message_id", "message_id", base.DEC, message_names, nil, "u16")
+fields["frame_header.sequence"] = ProtoField.uint32("test_codegen.frame_header.sequence", "sequence", base.DEC, nil, nil, "u32")
+
+-- Price
+fields["price.value"] = ProtoField.int64("test_codegen.price.value", "value", base.DEC, nil, nil, "decimal<i64, -4>")
+fields["price.currency"] = ProtoField.string("test_codegen.price.currency", "currency", base.ASCII, "ascii[3]")
+
+-- Order
+fields["order.side"] = ProtoField.uint8("test_codegen.order.side", "side", base.DEC, side_names, nil, "Side")
+fields["order.quantity"] = ProtoField.uint32("test_codegen.order.quantity", "quantity", base.DEC, nil, nil, "u32")
+fields["order.price"] = ProtoField.none("test_codegen.order.price", "price", "Price")
+
+-- Body
+fields["body.order"] = ProtoField.none("test_codegen.body.order", "order", "Order")
+fields["body.cancel_id"] = ProtoField.uint64("test_codegen.body.cancel_id", "cancel_id", base.DEC, nil, nil, "u64")
+fields["body.ping"] = ProtoField.string("test_codegen.body.ping", "ping", base.ASCII, "ascii[4]")
+
+-- Quote
+fields["quote.sent_at"] = ProtoField.uint64("test_codegen.quote.sent_at", "sent_at", base.DEC, nil, nil, "timestamp<ns>")
+fields["quote.symbol"] = ProtoField.string("test_codegen.quote.symbol", "symbol", base.ASCII, "ascii[8]")
+fields["quote.side"] = ProtoField.uint8("test_codegen.quote.side", "side", base.DEC, side_names, nil, "Side")
+fields["quote.bid"] = ProtoField.none("test_codegen.quote.bid", "bid", "Price")
+fields["quote.ask"] = ProtoField.none("test_codegen.quote.ask", "ask", "Price")
+fields["quote.levels.element"] = ProtoField.int32("test_codegen.quote.levels.element", "levels", base.DEC, nil, nil, "i32")
+fields["quote.levels"] = ProtoField.none("test_codegen.quote.levels", "levels", "i32[3]")
+fields["quote.weight"] = ProtoField.double("test_codegen.quote.weight", "weight", nil, "f64")
+fields["quote.ttl"] = ProtoField.int64("test_codegen.quote.ttl", "ttl", base.DEC, nil, nil, "duration<us>")
+
+-- Request
+fields["request.sequence"] = ProtoField.uint32("test_codegen.request.sequence", "sequence", base.DEC, nil, nil, "u32")
+fields["request.permissions"] = ProtoField.uint16("test_codegen.request.permissions", "permissions", base.HEX, nil, nil, "Permissions")
+fields["request.kind"] = ProtoField.uint8("test_codegen.request.kind", "kind", base.DEC, body_kind_names, nil, "BodyKind")
+fields["request.body"] = ProtoField.none("test_codegen.request.body", "body", "Body")
+fields["request.checksum"] = ProtoField.bytes("test_codegen.request.checksum", "checksum", base.NONE, "byte[4]")
+fields["request.type"] = ProtoField.int16("test_codegen.request.type", "type", base.DEC, nil, nil, "i16")
+fields["request.venue"] = ProtoField.string("test_codegen.request.venue", "venue", base.ASCII, "ascii[6]")
+
+-- Heartbeat
+fields["heartbeat.sent_at"] = ProtoField.uint64("test_codegen.heartbeat.sent_at", "sent_at", base.DEC, nil, nil, "u64")
+fields["heartbeat.sequence"] = ProtoField.uint32("test_codegen.heartbeat.sequence", "sequence", base.DEC, nil, nil, "u32")
+fields["heartbeat.side"] = ProtoField.uint8("test_codegen.heartbeat.side", "side", base.DEC, side_names, nil, "Side")
+
+local unknown_id = ProtoExpert.new(
+    "test_codegen.unknown_id", "Unknown message id", expert.group.MALFORMED, expert.severity.ERROR)
+local truncated = ProtoExpert.new(
+    "test_codegen.truncated", "Truncated message", expert.group.MALFORMED, expert.severity.ERROR)
+local bad_length = ProtoExpert.new(
+    "test_codegen.bad_length", "Frame length too small for its message", expert.group.MALFORMED, expert.severity.ERROR)
+
+local field_list = {}
+for _, field in pairs(fields) do
+    field_list[#field_list + 1] = field
+end
+for _, field in pairs(flags) do
+    field_list[#field_list + 1] = field
+end
+proto.fields = field_list
+proto.experts = { unknown_id, truncated, bad_length }
+
+local function dissect_price(buffer, offset, tree)
+    tree:add_le(fields["price.value"], buffer(offset, 8)):append_text(" (" .. decimal_text(buffer(offset, 8):le_int64(), -4) .. ")")
+    tree:add(fields["price.currency"], buffer(offset + 8, 3), ascii(buffer(offset + 8, 3)))
+end
+
+local function dissect_order(buffer, offset, tree)
+    tree:add_le(fields["order.side"], buffer(offset, 1))
+    tree:add_le(fields["order.quantity"], buffer(offset + 1, 4))
+    dissect_price(buffer, offset + 5, tree:add(fields["order.price"], buffer(offset + 5, 11)))
+end
+
+local function dissect_body(buffer, offset, tree, selector)
+    if selector == 1 then
+        dissect_order(buffer, offset, tree:add(fields["body.order"], buffer(offset, 16)))
+    end
+    if selector == 2 then
+        tree:add_le(fields["body.cancel_id"], buffer(offset, 8))
+    end
+    if selector == 3 then
+        tree:add(fields["body.ping"], buffer(offset, 4), ascii(buffer(offset, 4)))
+    end
+end
+
+local function dissect_frame_header(buffer, offset, tree)
+    tree:add_le(fields["frame_header.length"], buffer(offset, 2))
+    tree:add_le(fields["frame_header.message_id"], buffer(offset + 2, 2))
+    tree:add_le(fields["frame_header.sequence"], buffer(offset + 4, 4))
+end
+
+local function dissect_quote(buffer, offset, tree)
+    tree:add_le(fields["quote.sent_at"], buffer(offset, 8))
+    tree:add(fields["quote.symbol"], buffer(offset + 8, 8), ascii(buffer(offset + 8, 8)))
+    tree:add_le(fields["quote.side"], buffer(offset + 16, 1))
+    dissect_price(buffer, offset + 17, tree:add(fields["quote.bid"], buffer(offset + 17, 11)))
+    dissect_price(buffer, offset + 28, tree:add(fields["quote.ask"], buffer(offset + 28, 11)))
+    local levels_item = tree:add(fields["quote.levels"], buffer(offset + 39, 12))
+    for i = 0, 2 do
+        levels_item:add_le(fields["quote.levels.element"], buffer(offset + 39 + i * 4, 4)):prepend_text("[" .. i .. "] ")
+    end
+    tree:add_le(fields["quote.weight"], buffer(offset + 51, 8))
+    tree:add_le(fields["quote.ttl"], buffer(offset + 59, 8))
+end
+
+local function dissect_request(buffer, offset, tree)
+    tree:add_le(fields["request.sequence"], buffer(offset, 4))
+    local permissions_item = tree:add_le(fields["request.permissions"], buffer(offset + 4, 2))
+    permissions_item:add_le(flags["permissions.read"], buffer(offset + 4, 2))
+    permissions_item:add_le(flags["permissions.write"], buffer(offset + 4, 2))
+    permissions_item:add_le(flags["permissions.admin"], buffer(offset + 4, 2))
+    tree:add_le(fields["request.kind"], buffer(offset + 6, 1))
+    dissect_body(buffer, offset + 7, tree:add(fields["request.body"], buffer(offset + 7, 16)), buffer(offset + 6, 1):le_uint())
+    tree:add(fields["request.checksum"], buffer(offset + 23, 4))
+    tree:add_le(fields["request.type"], buffer(offset + 29, 2))
+    tree:add(fields["request.venue"], buffer(offset + 31, 6), ascii(buffer(offset + 31, 6)))
+end
+
+local function dissect_heartbeat(buffer, offset, tree)
+    tree:add_le(fields["heartbeat.sent_at"], buffer(offset, 8))
+    tree:add_le(fields["heartbeat.sequence"], buffer(offset + 8, 4))
+    tree:add_le(fields["heartbeat.side"], buffer(offset + 12, 1))
+end
+
+local messages = {
+    [1] = { name = "Quote", size = 67, dissect = dissect_quote },
+    [2] = { name = "Request", size = 48, dissect = dissect_request },
+    [3] = { name = "Heartbeat", size = 16, dissect = dissect_heartbeat },
+}
+
+function proto.dissector(buffer, pinfo, tree)
+    pinfo.cols.protocol = proto.name
+    local names = {}
+    local offset = 0
+    while offset + 8 <= buffer:len() do
+        local id = buffer(offset + 2, 2):le_uint()
+        local message = messages[id]
+        if message == nil then
+            local item = tree:add(fields.frame_header, buffer(offset, 8))
+            dissect_frame_header(buffer, offset, item)
+            item:add_proto_expert_info(unknown_id)
+            break
+        end
+        local frame = buffer(offset, 2):le_uint()
+        local length = math.min(frame, buffer:len() - offset)
+        local subtree = tree:add(proto, buffer(offset, length), message.name)
+        dissect_frame_header(buffer, offset, subtree:add(fields.frame_header, buffer(offset, 8)))
+        names[#names + 1] = message.name
+        if frame < 8 + message.size then
+            subtree:add_proto_expert_info(bad_length)
+            break
+        end
+        if length < frame then
+            subtree:add_proto_expert_info(truncated)
+            break
+        end
+        message.dissect(buffer, offset + 8, subtree)
+        offset = offset + frame
+    end
+    pinfo.cols.info = table.concat(names, ", ")
+    return offset
+end
+
+DissectorTable.get("udp.port"):add_for_decode_as(proto)
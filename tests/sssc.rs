@@ -0,0 +1,430 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const SCHEMA: &str = "\
+protocol my.protocol;
+
+enum Outcome : u8 { SUCCESS = 1, FAIL = 2 }
+
+message EventOutcome [2] {
+    u64: timestamp;
+    Outcome: outcome;
+    4;
+    u64: quantity_granted;
+}
+";
+
+fn write_schema(name: &str, text: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sssc-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+fn sssc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sssc"))
+        .args(args)
+        .output()
+        .expect("failed to run sssc")
+}
+
+#[test]
+fn test_check_valid_schema() {
+    let schema = write_schema("valid.sss", SCHEMA);
+    let output = sssc(&["check", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+}
+
+#[test]
+fn test_check_reports_every_invalid_schema() {
+    let unknown = write_schema("unknown.sss", "protocol x; message a [1] { Foo: f; }");
+    let mismatch = write_schema(
+        "mismatch.sss",
+        "protocol x; message a [1] { u8: a; @offset(4) u8: b; }",
+    );
+    let output = sssc(&[
+        "check",
+        unknown.to_str().unwrap(),
+        mismatch.to_str().unwrap(),
+    ]);
+
+    assert_eq!(Some(1), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unknown type 'Foo'"), "{}", stderr);
+    assert!(
+        stderr.contains("expected at offset 4 but is at offset 1"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_missing_file_and_bad_usage() {
+    assert_eq!(
+        Some(3),
+        sssc(&["check", "/does/not/exist.sss"]).status.code()
+    );
+    assert_eq!(Some(2), sssc(&["check"]).status.code());
+    assert_eq!(Some(2), sssc(&["frobnicate"]).status.code());
+}
+
+#[test]
+fn test_layout_prints_offsets() {
+    let schema = write_schema("layout.sss", SCHEMA);
+    let output = sssc(&["layout", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("message EventOutcome [2] size 21 align 1"));
+    assert!(stdout.contains("<padding, reserved>"));
+    assert!(stdout.contains("13      8  u64: quantity_granted"));
+}
+
+#[test]
+fn test_layout_prints_ruler() {
+    let schema = write_schema("ruler.sss", SCHEMA);
+    let output = sssc(&["layout", "--format", "ruler", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("       0  AAAAAAAAB----CCCCCCCC\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("       -        9      4  <padding, reserved>"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_dump_json() {
+    let schema = write_schema("dump.sss", SCHEMA);
+    let output = sssc(&["dump", "--format", "json", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!("EventOutcome", json["messages"][0]["name"]);
+    assert_eq!(21, json["messages"][0]["layout"]["size"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_dump_round_trips() {
+    let schema = write_schema("round_trip.sss", SCHEMA);
+
+    let output = sssc(&["dump", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+    let dump: sss::sssc::Dump<sss::protocol::Protocol> =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sss::sssc::DUMP_FORMAT_VERSION, dump.format_version);
+    assert_eq!(sss::sssc::compile(SCHEMA).unwrap(), dump.model);
+
+    let output = sssc(&["dump", "--model", "parsed", schema.to_str().unwrap()]);
+    assert_eq!(Some(0), output.status.code());
+    let dump: sss::sssc::Dump<sss::syntax::SyntaxUnit> =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sss::sssc::DUMP_FORMAT_VERSION, dump.format_version);
+    assert_eq!(sss::sssc::parse_schema(SCHEMA).unwrap(), dump.model);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_dump_yaml_matches_json() {
+    let schema = write_schema("yaml.sss", SCHEMA);
+    for model in ["parsed", "resolved"] {
+        let json = sssc(&["dump", "--model", model, schema.to_str().unwrap()]);
+        let yaml = sssc(&[
+            "dump",
+            "--format",
+            "yaml",
+            "--model",
+            model,
+            schema.to_str().unwrap(),
+        ]);
+        assert_eq!(Some(0), yaml.status.code());
+
+        let from_json: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+        let from_yaml: serde_json::Value = serde_yaml::from_slice(&yaml.stdout).unwrap();
+        assert_eq!(from_json, from_yaml);
+        assert!(!String::from_utf8(yaml.stdout).unwrap().contains('!'));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_encode_and_decode() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let schema = write_schema("transcode.sss", SCHEMA);
+    let messages =
+        "{\"EventOutcome\":{\"timestamp\":12,\"outcome\":\"SUCCESS\",\"quantity_granted\":100}}\n";
+    let input = schema.with_file_name("messages.ndjson");
+    std::fs::write(&input, messages).unwrap();
+
+    let output = sssc(&[
+        "encode",
+        "--schema",
+        schema.to_str().unwrap(),
+        input.to_str().unwrap(),
+    ]);
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!(
+        "02000c0000000000000001000000006400000000000000\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+
+    let frames = sssc(&[
+        "encode",
+        "--schema",
+        schema.to_str().unwrap(),
+        "--binary",
+        "base64",
+        input.to_str().unwrap(),
+    ])
+    .stdout;
+    let mut decode = Command::new(env!("CARGO_BIN_EXE_sssc"))
+        .args([
+            "decode",
+            "--schema",
+            schema.to_str().unwrap(),
+            "--binary",
+            "base64",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    decode.stdin.take().unwrap().write_all(&frames).unwrap();
+    let output = decode.wait_with_output().unwrap();
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!(messages, String::from_utf8(output.stdout).unwrap());
+
+    std::fs::write(&input, messages.replace("SUCCESS", "MAYBE")).unwrap();
+    let output = sssc(&[
+        "encode",
+        "--schema",
+        schema.to_str().unwrap(),
+        input.to_str().unwrap(),
+    ]);
+    assert_eq!(Some(5), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("line 1: 'MAYBE' is not declared by the type of 'EventOutcome.outcome'"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_hexdump() {
+    let schema = write_schema("hexdump.sss", SCHEMA);
+    let input = schema.with_file_name("capture.bin");
+    let mut bytes = vec![12, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0x2a, 0];
+    bytes.extend_from_slice(&100u64.to_le_bytes());
+    std::fs::write(&input, bytes).unwrap();
+
+    for message in ["EventOutcome", "2"] {
+        let output = sssc(&[
+            "hexdump",
+            "--schema",
+            schema.to_str().unwrap(),
+            "--message",
+            message,
+            input.to_str().unwrap(),
+        ]);
+        assert_eq!(Some(0), output.status.code());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(
+            stdout.contains(
+                "       8  01                                               outcome = SUCCESS\n"
+            ),
+            "{}",
+            stdout
+        );
+        assert!(stdout.contains("       9  00 00 2a!00                                      <padding, reserved>  <- 1 non-zero byte\n"), "{}", stdout);
+        assert!(!stdout.contains('\x1b'));
+    }
+
+    let output = sssc(&[
+        "hexdump",
+        "--schema",
+        schema.to_str().unwrap(),
+        "--message",
+        "Quote",
+        input.to_str().unwrap(),
+    ]);
+    assert_eq!(Some(2), output.status.code());
+}
+
+struct ListMessages;
+
+impl sss::codegen::Generator for ListMessages {
+    fn name(&self) -> &str {
+        "list"
+    }
+
+    fn description(&self) -> &str {
+        "lists the messages of a protocol"
+    }
+
+    fn generate(
+        &self,
+        protocol: &sss::protocol::Protocol,
+        options: &sss::codegen::GeneratorOptions,
+    ) -> Result<Vec<sss::codegen::OutputFile>, sss::codegen::GeneratorError> {
+        options.check_known(&["suffix"])?;
+        let suffix = options.get("suffix").unwrap_or("txt");
+        let names: Vec<&str> = protocol.messages.iter().map(|m| m.name.as_str()).collect();
+        Ok(vec![sss::codegen::OutputFile::new(
+            format!("nested/{}.{}", protocol.qualified_name(), suffix),
+            names.join("\n"),
+        )])
+    }
+}
+
+fn run_with_list(args: &[&str]) -> (sss::sssc::Status, String) {
+    let mut registry = sss::codegen::Registry::new();
+    registry.register(Box::new(ListMessages));
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    let status = sss::sssc::run_with(&registry, &args, &mut stdout, &mut stderr);
+    (status, String::from_utf8(stderr).unwrap())
+}
+
+#[test]
+fn test_build_with_registered_generator() {
+    let schema = write_schema("build.sss", SCHEMA);
+    let out = schema.parent().unwrap().join("build-out");
+
+    let (status, stderr) = run_with_list(&[
+        "build",
+        "--lang",
+        "list",
+        "--out",
+        out.to_str().unwrap(),
+        "--opt",
+        "suffix=list",
+        schema.to_str().unwrap(),
+    ]);
+    assert_eq!(sss::sssc::Status::Success, status, "{}", stderr);
+    assert_eq!(
+        "EventOutcome",
+        std::fs::read_to_string(out.join("nested/my.protocol.list")).unwrap()
+    );
+
+    let (status, stderr) = run_with_list(&[
+        "build",
+        "--lang",
+        "list",
+        "--out",
+        out.to_str().unwrap(),
+        "--opt",
+        "sufix=list",
+        schema.to_str().unwrap(),
+    ]);
+    assert_eq!(sss::sssc::Status::GeneratorFailed, status);
+    assert!(stderr.contains("unknown option 'sufix'"), "{}", stderr);
+
+    let (status, stderr) = run_with_list(&[
+        "build",
+        "--lang",
+        "cobol",
+        "--out",
+        out.to_str().unwrap(),
+        schema.to_str().unwrap(),
+    ]);
+    assert_eq!(sss::sssc::Status::Usage, status);
+    assert!(
+        stderr.contains("available generators are: list"),
+        "{}",
+        stderr
+    );
+}
+
+#[cfg(feature = "test-plugin")]
+fn sssc_with_plugins(args: &[&str]) -> Output {
+    let plugin = PathBuf::from(env!("CARGO_BIN_EXE_sss-gen-echo"));
+    let mut path = vec![plugin.parent().unwrap().to_path_buf()];
+    path.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    Command::new(env!("CARGO_BIN_EXE_sssc"))
+        .args(args)
+        .env("PATH", std::env::join_paths(path).unwrap())
+        .output()
+        .expect("failed to run sssc")
+}
+
+#[cfg(feature = "test-plugin")]
+#[test]
+fn test_build_with_plugin() {
+    let schema = write_schema("plugin.sss", SCHEMA);
+    let out = schema.parent().unwrap().join("plugin-out");
+
+    let output = sssc_with_plugins(&[
+        "build",
+        "--lang",
+        "echo",
+        "--out",
+        out.to_str().unwrap(),
+        "--opt",
+        "warn=careful",
+        schema.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(Some(0), output.status.code(), "{}", stderr);
+    assert!(stderr.contains("warning: echo: careful"), "{}", stderr);
+    assert_eq!(
+        "protocol my.protocol\n2 EventOutcome 21\n",
+        std::fs::read_to_string(out.join("my.protocol.txt")).unwrap()
+    );
+
+    let output = sssc_with_plugins(&[
+        "build",
+        "--lang",
+        "echo",
+        "--out",
+        out.to_str().unwrap(),
+        "--opt",
+        "fail=no can do",
+        schema.to_str().unwrap(),
+    ]);
+    assert_eq!(Some(4), output.status.code());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("echo: no can do"), "{}", stderr);
+}
+
+#[cfg(feature = "test-plugin")]
+#[test]
+fn test_plugin_warnings_go_to_the_given_stderr() {
+    let schema = write_schema("plugin-warn.sss", SCHEMA);
+    let out = schema.parent().unwrap().join("plugin-warn-out");
+
+    let mut registry = sss::codegen::Registry::new();
+    registry.register(Box::new(sss::codegen::plugin::PluginGenerator::new(
+        "echo",
+        env!("CARGO_BIN_EXE_sss-gen-echo"),
+    )));
+    let args: Vec<String> = [
+        "build",
+        "--lang",
+        "echo",
+        "--out",
+        out.to_str().unwrap(),
+        "--opt",
+        "warn=careful",
+        schema.to_str().unwrap(),
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect();
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    let status = sss::sssc::run_with(&registry, &args, &mut stdout, &mut stderr);
+    let stderr = String::from_utf8(stderr).unwrap();
+    assert_eq!(sss::sssc::Status::Success, status, "{}", stderr);
+    assert_eq!("warning: echo: careful\n", stderr);
+}
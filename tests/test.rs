@@ -91,7 +91,7 @@ macro_rules! token {
         TokenType::Identifier(String::from(stringify!($id)))
     };
     (num: $num:literal ) => {
-        TokenType::IntegerLiteral(String::from(stringify!($num)))
+        TokenType::IntegerLiteral($num)
     };
 }
 
@@ -164,6 +164,20 @@ fn test_lex_type() {
     );
 }
 
+#[test]
+fn test_lex_comments() {
+    assert_token_types!(
+        "// skipped
+//// skipped too
+///  documents b
+u8 b; // skipped",
+        TokenType::DocComment(String::from("documents b")),
+        token!(id: u8),
+        token!(id: b),
+        token!(;)
+    );
+}
+
 #[test]
 fn test_parse_protocol_declaration() {
     let mut tokens = tokenize("protocol foo.bar.baz;");